@@ -0,0 +1,754 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    ethabi::{encode, Token},
+    parity_scale_codec::{Decode, DecodeAll, DecodeLimit, Encode},
+    serde::{Serialize, Serializer},
+    serde_json::{json, Value},
+    snowbridge_inbound_queue_primitives::v1::{
+        Command as InboundCommand, Destination, MessageV1, VersionedXcmMessage,
+    },
+    sp_core::{H160, H256},
+    sp_runtime::AccountId32,
+    tp_bridge::{Command as OutboundCommand, SlashData, MAGIC_BYTES},
+    xcm::{latest::Xcm, IdentifyVersion, VersionedXcm, MAX_XCM_DECODE_DEPTH},
+};
+
+#[derive(Debug, clap::ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    /// Try every known format
+    Auto,
+    /// Snowbridge V1 inbound payload (`VersionedXcmMessage`)
+    InboundV1,
+    /// Snowbridge V2 inbound raw payload (`RawPayload`)
+    InboundV2,
+    /// Symbiotic middleware payload, starting with the magic bytes
+    Symbiotic,
+    /// ABI encoded params of an outbound command
+    OutboundCommand,
+    /// SCALE encoded `VersionedXcm`
+    Xcm,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DecodeCmd {
+    /// Payload to decode, in hex. Inbound payloads can also be given as the data of the
+    /// `OutboundMessageAccepted` log, as printed by `payload-generator`.
+    #[arg(long)]
+    pub payload: String,
+
+    /// How to interpret the payload
+    #[arg(long, value_enum, default_value_t = PayloadKind::Auto)]
+    pub kind: PayloadKind,
+
+    /// Print the explanations as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// A successful interpretation of a payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Explanation {
+    /// Format the payload was decoded as.
+    pub format: &'static str,
+    /// Processor of the Tanssi runtimes that would accept the payload, if any.
+    pub processor: Option<&'static str>,
+    /// Conditions checked on chain that cannot be verified from the payload alone.
+    pub notes: Vec<String>,
+    /// Decoded payload.
+    pub decoded: Value,
+}
+
+impl DecodeCmd {
+    pub fn run(&self) -> Vec<Explanation> {
+        let hex_trimmed = self.payload.strip_prefix("0x").unwrap_or(&self.payload);
+        let bytes = hex::decode(hex_trimmed).expect("invalid hex in payload");
+
+        let explanations = explain(&bytes, self.kind);
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&explanations).expect("explanations are valid JSON")
+            );
+        } else if explanations.is_empty() {
+            println!("\nPayload could not be decoded as {:?}", self.kind);
+        } else {
+            for explanation in &explanations {
+                print_explanation(explanation);
+            }
+        }
+
+        explanations
+    }
+}
+
+fn print_explanation(explanation: &Explanation) {
+    println!("\n=== {} ===\n", explanation.format);
+    println!(
+        "Processor       : {}",
+        explanation.processor.unwrap_or("none")
+    );
+    if !explanation.notes.is_empty() {
+        println!("Notes:");
+        for note in &explanation.notes {
+            println!("  - {}", note);
+        }
+    }
+    println!(
+        "Decoded:\n{}",
+        serde_json::to_string_pretty(&explanation.decoded).expect("decoded payload is valid JSON")
+    );
+}
+
+/// Returns every interpretation of `payload` as `kind`. SCALE is not self-describing, so the
+/// same bytes may be valid in more than one format when `kind` is `Auto`.
+pub fn explain(payload: &[u8], kind: PayloadKind) -> Vec<Explanation> {
+    let mut explanations = explain_payload(payload, kind);
+
+    // Inbound payloads are often copied from the log emitted by the gateway
+    if let Some((nonce, inner)) = unwrap_log_data(payload) {
+        if kind != PayloadKind::OutboundCommand && kind != PayloadKind::Xcm {
+            for mut explanation in explain_payload(&inner, kind) {
+                explanation.notes.insert(
+                    0,
+                    format!(
+                        "Payload extracted from OutboundMessageAccepted log data with nonce {}",
+                        nonce
+                    ),
+                );
+                explanations.push(explanation);
+            }
+        }
+    }
+
+    explanations
+}
+
+fn explain_payload(payload: &[u8], kind: PayloadKind) -> Vec<Explanation> {
+    let wants = |candidate: PayloadKind| kind == PayloadKind::Auto || kind == candidate;
+    let mut explanations = vec![];
+
+    if wants(PayloadKind::Symbiotic) {
+        explanations.extend(explain_symbiotic(payload, "SymbioticMessageProcessor"));
+    }
+    if wants(PayloadKind::InboundV1) {
+        explanations.extend(explain_inbound_v1(payload));
+    }
+    if wants(PayloadKind::InboundV2) {
+        explanations.extend(explain_inbound_v2(payload));
+    }
+    if wants(PayloadKind::OutboundCommand) {
+        explanations.extend(explain_outbound_command(payload));
+    }
+    if wants(PayloadKind::Xcm) {
+        explanations.extend(explain_xcm(payload, "SCALE encoded VersionedXcm", None));
+    }
+
+    explanations
+}
+
+/// Mirrors `tp_bridge::Payload` for runtimes whose validator and account ids are `AccountId32`,
+/// like Dancelight and Starlight, so it can be decoded without a runtime.
+#[derive(Encode, Decode, Debug, Serialize)]
+pub struct SymbioticPayload {
+    pub magic_bytes: [u8; 4],
+    pub message: SymbioticMessage,
+}
+
+#[derive(Encode, Decode, Debug, Serialize)]
+pub enum SymbioticMessage {
+    V1(SymbioticCommand),
+    V2(SymbioticCommandV2),
+    V3(SymbioticCommandV3),
+}
+
+#[derive(Encode, Decode, Debug, Serialize)]
+pub enum SymbioticCommand {
+    ReceiveValidators {
+        validators: Vec<AccountId32>,
+        external_index: u64,
+    },
+}
+
+#[derive(Encode, Decode, Debug, Serialize)]
+pub enum SymbioticCommandV2 {
+    ReceiveValidatorsWithStake {
+        validators: Vec<(AccountId32, Amount)>,
+        external_index: u64,
+    },
+}
+
+#[derive(Encode, Decode, Debug, Serialize)]
+pub enum SymbioticCommandV3 {
+    AddWhitelisted { validator: AccountId32 },
+    RemoveWhitelisted { validator: AccountId32 },
+    ForceEra { mode: Forcing },
+    SetSlashingMode { mode: SlashingModeOption },
+    SetRewardsReportingPaused { paused: bool },
+    CancelDeferredSlash { era: u32, slash_indices: Vec<u32> },
+}
+
+/// `u128` serialized as a string, as JSON numbers cannot hold all of them.
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Amount(pub u128);
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+/// Mirrors `pallet_external_validators::Forcing`.
+#[derive(Encode, Decode, Debug, Serialize)]
+pub enum Forcing {
+    NotForcing,
+    ForceNew,
+    ForceNone,
+    ForceAlways,
+}
+
+/// Mirrors `tp_traits::SlashingModeOption`.
+#[derive(Encode, Decode, Debug, Serialize)]
+pub enum SlashingModeOption {
+    Enabled,
+    LogOnly,
+    Disabled,
+}
+
+/// Mirrors `tanssi_runtime_common::processors::v2::RawPayload`.
+#[derive(Encode, Decode, Debug)]
+pub enum RawPayload {
+    Xcm(Vec<u8>),
+    Symbiotic(Vec<u8>),
+}
+
+fn explain_symbiotic(mut payload: &[u8], processor: &'static str) -> Option<Explanation> {
+    let decoded = SymbioticPayload::decode_all(&mut payload).ok()?;
+    if decoded.magic_bytes != MAGIC_BYTES {
+        return None;
+    }
+
+    let notes = match &decoded.message {
+        SymbioticMessage::V1(_) | SymbioticMessage::V2(_) => {
+            vec!["The channel must be mapped to a source of external validators".to_string()]
+        }
+        SymbioticMessage::V3(_) => vec![
+            "Governance commands are only accepted through the primary governance channel"
+                .to_string(),
+            "The kind of governance command must be enabled on chain".to_string(),
+        ],
+    };
+
+    Some(Explanation {
+        format: "Symbiotic middleware payload",
+        processor: Some(processor),
+        notes,
+        decoded: json!(decoded.message),
+    })
+}
+
+fn explain_inbound_v1(mut payload: &[u8]) -> Option<Explanation> {
+    let VersionedXcmMessage::V1(MessageV1 { chain_id, command }) =
+        VersionedXcmMessage::decode_all(&mut payload).ok()?;
+
+    let mut notes = vec![
+        "The message must come from the gateway through the token transfer channel".to_string(),
+    ];
+    let (processor, decoded) = match &command {
+        InboundCommand::RegisterToken { token, fee } => {
+            notes.push("Token registration messages are accepted and ignored".to_string());
+            (
+                Some("GenericTokenInboundMessageProcessor"),
+                json!({ "RegisterToken": { "token": token, "fee": Amount(*fee) } }),
+            )
+        }
+        InboundCommand::SendToken {
+            token,
+            destination,
+            amount,
+            fee,
+        } => {
+            if *token == H160::zero() {
+                notes.push("The token is ETH".to_string());
+            }
+            if let Destination::AccountId32 { .. } = destination {
+                notes.push("The token must be registered as a foreign asset".to_string());
+            } else {
+                notes.push("The tokens are forwarded to the container chain".to_string());
+            }
+            (
+                Some("EthTokensLocalProcessor"),
+                json!({ "SendToken": {
+                    "token": token,
+                    "destination": destination_json(destination),
+                    "amount": Amount(*amount),
+                    "fee": Amount(*fee),
+                } }),
+            )
+        }
+        InboundCommand::SendNativeToken {
+            token_id,
+            destination,
+            amount,
+            fee,
+        } => {
+            let processor = match destination {
+                Destination::AccountId32 { .. } => {
+                    notes
+                        .push("The token id must be the one of the relay native token".to_string());
+                    "NativeTokenTransferMessageProcessor"
+                }
+                Destination::ForeignAccountId32 { .. } | Destination::ForeignAccountId20 { .. } => {
+                    notes.push(
+                        "The token id must belong to a token of the destination container chain"
+                            .to_string(),
+                    );
+                    "NativeContainerTokensProcessor"
+                }
+            };
+            (
+                Some(processor),
+                json!({ "SendNativeToken": {
+                    "token_id": token_id,
+                    "destination": destination_json(destination),
+                    "amount": Amount(*amount),
+                    "fee": Amount(*fee),
+                } }),
+            )
+        }
+    };
+
+    Some(Explanation {
+        format: "Snowbridge V1 inbound message",
+        processor,
+        notes,
+        decoded: json!({ "chain_id": chain_id, "command": decoded }),
+    })
+}
+
+fn destination_json(destination: &Destination) -> Value {
+    match destination {
+        Destination::AccountId32 { id } => json!({ "AccountId32": { "id": hex_string(id) } }),
+        Destination::ForeignAccountId32 { para_id, id, fee } => json!({
+            "ForeignAccountId32": { "para_id": para_id, "id": hex_string(id), "fee": Amount(*fee) }
+        }),
+        Destination::ForeignAccountId20 { para_id, id, fee } => json!({
+            "ForeignAccountId20": { "para_id": para_id, "id": hex_string(id), "fee": Amount(*fee) }
+        }),
+    }
+}
+
+fn explain_inbound_v2(mut payload: &[u8]) -> Vec<Explanation> {
+    let Ok(raw_payload) = RawPayload::decode_all(&mut payload) else {
+        return vec![];
+    };
+
+    match raw_payload {
+        RawPayload::Xcm(xcm) => explain_xcm(
+            &xcm,
+            "Snowbridge V2 inbound raw XCM payload",
+            Some("RawMessageProcessor"),
+        )
+        .into_iter()
+        .map(|mut explanation| {
+            explanation.notes.push(
+                "The user XCM is executed after the assets and fees of the message are deposited"
+                    .to_string(),
+            );
+            explanation
+        })
+        .collect(),
+        RawPayload::Symbiotic(symbiotic) => {
+            explain_symbiotic(&symbiotic, "SymbioticMessageProcessor (inbound queue V2)")
+                .map(|mut explanation| {
+                    explanation.format = "Snowbridge V2 inbound Symbiotic payload";
+                    explanation.notes.insert(
+                        0,
+                        "The message origin must be the gateway proxy address".to_string(),
+                    );
+                    explanation
+                })
+                .into_iter()
+                .collect()
+        }
+    }
+}
+
+fn explain_xcm(
+    mut payload: &[u8],
+    format: &'static str,
+    processor: Option<&'static str>,
+) -> Option<Explanation> {
+    let versioned =
+        VersionedXcm::<()>::decode_with_depth_limit(MAX_XCM_DECODE_DEPTH, &mut payload).ok()?;
+    if !payload.is_empty() {
+        return None;
+    }
+
+    let version = versioned.identify_version();
+    let mut notes = vec![];
+    let instructions = match Xcm::<()>::try_from(versioned) {
+        Ok(xcm) => xcm
+            .0
+            .iter()
+            .map(|instruction| format!("{:?}", instruction))
+            .collect(),
+        Err(_) => {
+            notes.push(format!(
+                "XCM version {} cannot be converted to the latest version",
+                version
+            ));
+            vec![]
+        }
+    };
+
+    Some(Explanation {
+        format,
+        processor,
+        notes,
+        decoded: json!({ "version": version, "instructions": instructions }),
+    })
+}
+
+fn explain_outbound_command(payload: &[u8]) -> Vec<Explanation> {
+    decode_outbound_commands(payload)
+        .into_iter()
+        .map(|command| Explanation {
+            format: "ABI encoded outbound command",
+            processor: None,
+            notes: vec![format!("Command index {}", command.index())],
+            decoded: outbound_command_json(&command),
+        })
+        .collect()
+}
+
+/// ABI is not self-describing either, so each command is decoded from the expected layout and only
+/// kept if encoding it back gives the same bytes.
+pub(crate) fn decode_outbound_commands(payload: &[u8]) -> Vec<OutboundCommand> {
+    let candidates = [
+        decode_report_rewards(payload),
+        decode_report_slashes(payload),
+        decode_report_slashes_merkle_root(payload),
+        decode_test_command(payload),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .filter(|command| command.abi_encode() == payload)
+        .collect()
+}
+
+fn decode_report_rewards(payload: &[u8]) -> Option<OutboundCommand> {
+    Some(OutboundCommand::ReportRewards {
+        external_idx: abi_uint(payload, 0)?.try_into().ok()?,
+        era_index: abi_uint(payload, 32)?.try_into().ok()?,
+        total_points: abi_uint(payload, 64)?,
+        tokens_inflated: abi_uint(payload, 96)?,
+        rewards_merkle_root: H256::from_slice(abi_word(payload, 128)?),
+        token_id: H256::from_slice(abi_word(payload, 160)?),
+    })
+}
+
+fn decode_report_slashes(payload: &[u8]) -> Option<OutboundCommand> {
+    let tuple = abi_offset(payload, 0)?;
+    let era_index = abi_uint(payload, tuple)?.try_into().ok()?;
+    let array = tuple.checked_add(abi_offset(payload, tuple.checked_add(32)?)?)?;
+    let len = abi_offset(payload, array)?;
+
+    let mut slashes = vec![];
+    for index in 0..len {
+        let slash = array.checked_add(32)?.checked_add(index.checked_mul(96)?)?;
+        slashes.push(SlashData {
+            encoded_validator_id: abi_word(payload, slash)?.to_vec(),
+            slash_fraction: abi_uint(payload, slash.checked_add(32)?)?.try_into().ok()?,
+            external_idx: abi_uint(payload, slash.checked_add(64)?)?.try_into().ok()?,
+        });
+    }
+
+    Some(OutboundCommand::ReportSlashes { era_index, slashes })
+}
+
+fn decode_report_slashes_merkle_root(payload: &[u8]) -> Option<OutboundCommand> {
+    Some(OutboundCommand::ReportSlashesMerkleRoot {
+        era_index: abi_uint(payload, 0)?.try_into().ok()?,
+        chunk_index: abi_uint(payload, 32)?.try_into().ok()?,
+        slashes_count: abi_uint(payload, 64)?.try_into().ok()?,
+        slashes_merkle_root: H256::from_slice(abi_word(payload, 96)?),
+    })
+}
+
+fn decode_test_command(payload: &[u8]) -> Option<OutboundCommand> {
+    let tuple = abi_offset(payload, 0)?;
+    let bytes = tuple.checked_add(abi_offset(payload, tuple)?)?;
+    Some(OutboundCommand::Test(abi_bytes(payload, bytes)?.to_vec()))
+}
+
+fn outbound_command_json(command: &OutboundCommand) -> Value {
+    match command {
+        OutboundCommand::Test(payload) => json!({ "Test": hex_string(payload) }),
+        OutboundCommand::ReportRewards {
+            external_idx,
+            era_index,
+            total_points,
+            tokens_inflated,
+            rewards_merkle_root,
+            token_id,
+        } => json!({ "ReportRewards": {
+            "external_idx": external_idx,
+            "era_index": era_index,
+            "total_points": Amount(*total_points),
+            "tokens_inflated": Amount(*tokens_inflated),
+            "rewards_merkle_root": rewards_merkle_root,
+            "token_id": token_id,
+        } }),
+        OutboundCommand::ReportSlashes { era_index, slashes } => json!({ "ReportSlashes": {
+            "era_index": era_index,
+            "slashes": slashes
+                .iter()
+                .map(|slash| json!({
+                    "validator": hex_string(&slash.encoded_validator_id),
+                    "slash_fraction": slash.slash_fraction,
+                    "external_idx": slash.external_idx,
+                }))
+                .collect::<Vec<_>>(),
+        } }),
+        OutboundCommand::ReportSlashesMerkleRoot {
+            era_index,
+            chunk_index,
+            slashes_count,
+            slashes_merkle_root,
+        } => json!({ "ReportSlashesMerkleRoot": {
+            "era_index": era_index,
+            "chunk_index": chunk_index,
+            "slashes_count": slashes_count,
+            "slashes_merkle_root": slashes_merkle_root,
+        } }),
+    }
+}
+
+/// Extracts the payload from the data of an `OutboundMessageAccepted` log, which is the ABI
+/// encoding of `(uint64 nonce, bytes payload)`.
+fn unwrap_log_data(data: &[u8]) -> Option<(u64, Vec<u8>)> {
+    let nonce: u64 = abi_uint(data, 0)?.try_into().ok()?;
+    let payload = abi_bytes(data, abi_offset(data, 32)?)?.to_vec();
+
+    let encoded = encode(&[Token::Uint(nonce.into()), Token::Bytes(payload.clone())]);
+    (encoded == data).then_some((nonce, payload))
+}
+
+fn abi_word(data: &[u8], offset: usize) -> Option<&[u8]> {
+    data.get(offset..offset.checked_add(32)?)
+}
+
+fn abi_uint(data: &[u8], offset: usize) -> Option<u128> {
+    let word = abi_word(data, offset)?;
+    if word[..16].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+    Some(u128::from_be_bytes(word[16..].try_into().ok()?))
+}
+
+fn abi_offset(data: &[u8], offset: usize) -> Option<usize> {
+    abi_uint(data, offset)?.try_into().ok()
+}
+
+fn abi_bytes(data: &[u8], offset: usize) -> Option<&[u8]> {
+    let len = abi_offset(data, offset)?;
+    let start = offset.checked_add(32)?;
+    data.get(start..start.checked_add(len)?)
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::xcm_payload::{DestinationType, PayloadGeneratorCmd, TokenType},
+        xcm::latest::prelude::*,
+    };
+
+    fn formats(explanations: &[Explanation]) -> Vec<&'static str> {
+        explanations.iter().map(|e| e.format).collect()
+    }
+
+    #[test]
+    fn decodes_generated_erc20_payload() {
+        let generated = PayloadGeneratorCmd {
+            token_location: None,
+            para_id: 2002,
+            beneficiary: "0x0505050505050505050505050505050505050505050505050505050505050505"
+                .into(),
+            container_fee: 500000000000000,
+            amount: 100000000,
+            fee: 1500000000000000,
+            destination: DestinationType::Relay,
+            token: TokenType::Erc20,
+            genesis_hash: None,
+            nonce: 7,
+            token_address: Some("0xdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef".into()),
+        }
+        .run();
+
+        let explanations = explain(&generated.payload_bytes, PayloadKind::InboundV1);
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(explanations[0].processor, Some("EthTokensLocalProcessor"));
+        assert_eq!(
+            explanations[0].decoded["command"]["SendToken"]["amount"],
+            json!("100000000")
+        );
+
+        // The log data is unwrapped
+        let log_data = hex::decode(&generated.encoded_hex).unwrap();
+        let explanations = explain(&log_data, PayloadKind::Auto);
+        let explanation = explanations
+            .iter()
+            .find(|e| e.format == "Snowbridge V1 inbound message")
+            .expect("payload is decoded from the log data");
+        assert_eq!(
+            explanation.notes[0],
+            "Payload extracted from OutboundMessageAccepted log data with nonce 7"
+        );
+    }
+
+    #[test]
+    fn decodes_native_container_transfer() {
+        let payload = VersionedXcmMessage::V1(MessageV1 {
+            chain_id: 1,
+            command: InboundCommand::SendNativeToken {
+                token_id: H256::repeat_byte(1),
+                destination: Destination::ForeignAccountId20 {
+                    para_id: 2001,
+                    id: [5; 20],
+                    fee: 10,
+                },
+                amount: 100,
+                fee: 1,
+            },
+        })
+        .encode();
+
+        let explanations = explain(&payload, PayloadKind::InboundV1);
+        assert_eq!(
+            explanations[0].processor,
+            Some("NativeContainerTokensProcessor")
+        );
+        assert_eq!(
+            explanations[0].decoded["command"]["SendNativeToken"]["destination"],
+            json!({ "ForeignAccountId20": {
+                "para_id": 2001,
+                "id": hex_string(&[5; 20]),
+                "fee": "10",
+            } })
+        );
+    }
+
+    #[test]
+    fn decodes_symbiotic_payload() {
+        let payload = SymbioticPayload {
+            magic_bytes: MAGIC_BYTES,
+            message: SymbioticMessage::V3(SymbioticCommandV3::ForceEra {
+                mode: Forcing::ForceNew,
+            }),
+        }
+        .encode();
+
+        let explanations = explain(&payload, PayloadKind::Symbiotic);
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(explanations[0].processor, Some("SymbioticMessageProcessor"));
+        assert_eq!(
+            explanations[0].decoded,
+            json!({ "V3": { "ForceEra": { "mode": "ForceNew" } } })
+        );
+
+        // Wrong magic bytes are rejected
+        let mut wrong_magic = payload.clone();
+        wrong_magic[0] += 1;
+        assert!(explain(&wrong_magic, PayloadKind::Symbiotic).is_empty());
+
+        // And wrapped in a V2 raw payload
+        let raw = RawPayload::Symbiotic(payload).encode();
+        assert_eq!(
+            formats(&explain(&raw, PayloadKind::InboundV2)),
+            vec!["Snowbridge V2 inbound Symbiotic payload"]
+        );
+    }
+
+    #[test]
+    fn decodes_v2_raw_xcm_payload() {
+        let xcm = VersionedXcm::<()>::from(Xcm::<()>(vec![ClearOrigin, ClearTopic]));
+        let raw = RawPayload::Xcm(xcm.encode()).encode();
+
+        let explanations = explain(&raw, PayloadKind::InboundV2);
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(explanations[0].processor, Some("RawMessageProcessor"));
+        assert_eq!(
+            explanations[0].decoded,
+            json!({ "version": 5, "instructions": ["ClearOrigin", "ClearTopic"] })
+        );
+
+        assert_eq!(
+            formats(&explain(&xcm.encode(), PayloadKind::Xcm)),
+            vec!["SCALE encoded VersionedXcm"]
+        );
+    }
+
+    #[test]
+    fn decodes_outbound_commands() {
+        let commands = vec![
+            OutboundCommand::ReportRewards {
+                external_idx: 3,
+                era_index: 10,
+                total_points: 1000,
+                tokens_inflated: 5000,
+                rewards_merkle_root: H256::repeat_byte(1),
+                token_id: H256::repeat_byte(2),
+            },
+            OutboundCommand::ReportSlashes {
+                era_index: 10,
+                slashes: vec![
+                    SlashData {
+                        encoded_validator_id: [4; 32].to_vec(),
+                        slash_fraction: 500,
+                        external_idx: 3,
+                    },
+                    SlashData {
+                        encoded_validator_id: [5; 32].to_vec(),
+                        slash_fraction: 1000,
+                        external_idx: 3,
+                    },
+                ],
+            },
+            OutboundCommand::ReportSlashesMerkleRoot {
+                era_index: 10,
+                chunk_index: 1,
+                slashes_count: 20,
+                slashes_merkle_root: H256::repeat_byte(3),
+            },
+        ];
+
+        for command in commands {
+            let decoded = decode_outbound_commands(&command.abi_encode());
+            assert_eq!(decoded, vec![command]);
+        }
+
+        assert!(decode_outbound_commands(&[1, 2, 3]).is_empty());
+    }
+}
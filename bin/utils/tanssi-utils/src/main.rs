@@ -16,16 +16,22 @@
 
 use {
     clap::{Parser, Subcommand},
+    decode::DecodeCmd,
+    parity_scale_codec::Encode,
+    reward_claims::RewardClaimCalldataCmd,
     serde::{Deserialize, Deserializer},
-    snowbridge_merkle_tree::merkle_proof,
+    snowbridge_merkle_tree::{merkle_proof, merkle_root},
     sp_runtime::{traits::Keccak256, AccountId32},
     std::{
         collections::BTreeMap,
         path::{Path, PathBuf},
     },
+    tp_bridge::SlashData,
     xcm_payload::PayloadGeneratorCmd,
 };
 
+pub mod decode;
+pub mod reward_claims;
 pub mod xcm_payload;
 
 #[derive(Deserialize, Debug, Clone)]
@@ -41,6 +47,21 @@ pub struct RewardClaimInput {
     pub(crate) era: u32,
 }
 
+#[derive(Deserialize, Debug, Clone)]
+pub struct SlashInput {
+    #[serde(deserialize_with = "hex_to_account_id32")]
+    account: AccountId32,
+    /// Slash fraction in parts per billion.
+    slash_fraction: u32,
+    external_idx: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SlashProofInput {
+    pub(crate) slashes: Vec<SlashInput>,
+    pub(crate) era: u32,
+}
+
 #[derive(Debug, Parser)]
 #[command(rename_all = "kebab-case", version, about)]
 pub struct TanssiUtils {
@@ -53,6 +74,9 @@ pub struct TanssiUtils {
 pub enum TanssiUtilsCmd {
     RewardClaimGenerator(RewardClaimGeneratorCmd),
     PayloadGenerator(PayloadGeneratorCmd),
+    SlashProofGenerator(SlashProofGeneratorCmd),
+    Decode(DecodeCmd),
+    RewardClaimCalldata(RewardClaimCalldataCmd),
 }
 
 #[derive(Parser, Debug)]
@@ -62,6 +86,13 @@ pub struct RewardClaimGeneratorCmd {
     pub input_path: PathBuf,
 }
 
+#[derive(Parser, Debug)]
+pub struct SlashProofGeneratorCmd {
+    /// The path where the json containing the slashes of a reported chunk is located.
+    #[arg(long, short)]
+    pub input_path: PathBuf,
+}
+
 impl TanssiUtils {
     /// Executes the internal command.
     pub fn run(&self) {
@@ -75,6 +106,18 @@ impl TanssiUtils {
             TanssiUtilsCmd::PayloadGenerator(cmd) => {
                 cmd.run();
             }
+            TanssiUtilsCmd::SlashProofGenerator(cmd) => {
+                println!("\nInput path is: {:?}\n", cmd.input_path);
+                let slashes =
+                    extract_slashes_data_from_file(&cmd.input_path).expect("command fail");
+                generate_slash_proofs(slashes)
+            }
+            TanssiUtilsCmd::Decode(cmd) => {
+                cmd.run();
+            }
+            TanssiUtilsCmd::RewardClaimCalldata(cmd) => {
+                cmd.run();
+            }
         }
     }
 }
@@ -160,6 +203,56 @@ fn generate_reward_utils(reward_input: RewardClaimInput) {
     });
 }
 
+/// Extract the slashes of a reported chunk from a JSON file.
+fn extract_slashes_data_from_file(slashes_path: &Path) -> Result<SlashProofInput, String> {
+    let reader = std::fs::File::open(slashes_path).expect("Can open file");
+    let slashes_input =
+        serde_json::from_reader(&reader).expect("Cant parse slashes input from JSON");
+    Ok(slashes_input)
+}
+
+fn generate_slash_proofs(slashes_input: SlashProofInput) {
+    let era_index = slashes_input.era;
+    // The order must match the one of the reported chunk, as it determines the leaf indices
+    let slashes: Vec<SlashData> = slashes_input
+        .slashes
+        .iter()
+        .map(|slash| SlashData {
+            encoded_validator_id: slash.account.encode(),
+            slash_fraction: slash.slash_fraction,
+            external_idx: slash.external_idx,
+        })
+        .collect();
+    let leaves =
+        pallet_external_validator_slashes::generate_slashes_merkle_leaves::<Keccak256>(&slashes);
+
+    println!("=== Slashes Utils: Overall info ===\n");
+    println!("Era index       : {:?}", era_index);
+    println!(
+        "Merkle Root     : {:?}",
+        merkle_root::<Keccak256, _>(leaves.clone().into_iter())
+    );
+    println!("Slashes Count   : {}", slashes.len());
+    println!("Leaves:");
+    for (i, leaf) in leaves.iter().enumerate() {
+        println!("  [{}] {:?}", i, leaf);
+    }
+
+    println!("\n=== Merkle Proofs ===");
+    for (index, slash) in slashes_input.slashes.iter().enumerate() {
+        let proof = merkle_proof::<Keccak256, _>(leaves.clone().into_iter(), index as u64);
+        println!(
+            "\nMerkle proof for account {:?} in era {:?}: \n",
+            slash.account, era_index
+        );
+        println!("   - Root: {:?}", proof.root);
+        println!("   - Proof: {:?}", proof.proof);
+        println!("   - Number of leaves: {:?}", proof.number_of_leaves);
+        println!("   - Leaf index: {:?}", proof.leaf_index);
+        println!("   - Leaf: {:?}", proof.leaf);
+    }
+}
+
 fn main() {
     // Parses the options
     let cmd = TanssiUtils::parse();
@@ -0,0 +1,555 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    crate::decode::{decode_outbound_commands, Amount},
+    ethabi::{encode, Token},
+    pallet_external_validators_rewards::EraRewardPoints,
+    parity_scale_codec::{DecodeAll, Encode},
+    serde::Serialize,
+    snowbridge_merkle_tree::merkle_proof,
+    sp_core::{
+        hashing::{keccak_256, twox_128, twox_64},
+        H256,
+    },
+    sp_runtime::{traits::Keccak256, AccountId32},
+    std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+    },
+    tp_bridge::Command,
+    tp_traits::{ExternalSourceId, DEFAULT_EXTERNAL_SOURCE},
+};
+
+/// Name of the rewards pallet in the Dancelight and Starlight runtimes.
+pub const REWARDS_PALLET_NAME: &[u8] = b"ExternalValidatorsRewards";
+
+/// Name of the external validators pallet in the Dancelight and Starlight runtimes.
+pub const EXTERNAL_VALIDATORS_PALLET_NAME: &[u8] = b"ExternalValidators";
+
+/// Name of the runtime parameter that enables stake weighted reward points.
+pub const STAKE_WEIGHTED_POINTS_PARAMETER: &[u8] = b"StakeWeightedRewardPoints";
+
+/// Signature of the claim function of the operator rewards contract of the middleware.
+pub const CLAIM_REWARDS_SIGNATURE: &[u8] = b"claimRewards((bytes32,uint48,uint32,bytes32[],bytes))";
+
+#[derive(Debug, clap::Parser)]
+#[command(group(
+    clap::ArgGroup::new("era_points_source")
+        .required(true)
+        .args(["state_path", "era_points"])
+))]
+pub struct RewardClaimCalldataCmd {
+    /// Era to build the claims of.
+    #[arg(long)]
+    pub era: u32,
+
+    /// Exported state to read `RewardPointsForEra`, the source and stake of each validator and
+    /// whether points are stake weighted from. Either a chain spec with raw storage, like the
+    /// one written by `export-state`, or a JSON map of storage keys to values.
+    #[arg(long)]
+    pub state_path: Option<PathBuf>,
+
+    /// SCALE encoded `EraRewardPoints` of the era in hex, as returned by `state_getStorage`.
+    /// Every validator is accounted to the default source and points are not stake weighted.
+    #[arg(long)]
+    pub era_points: Option<String>,
+
+    /// External source to build the claims of. Each source receives a merkle root of its own
+    /// validators only.
+    #[arg(long, default_value_t = DEFAULT_EXTERNAL_SOURCE)]
+    pub source: ExternalSourceId,
+
+    /// ABI encoded params of the `ReportRewards` command sent for the era in hex. If given, the
+    /// merkle root and total points are checked against it.
+    #[arg(long)]
+    pub report_rewards: Option<String>,
+
+    /// Max admin fee accepted when claiming, used to build the claim data.
+    #[arg(long, default_value_t = 10_000)]
+    pub max_admin_fee: u128,
+
+    /// Claim data in hex, replaces the one built from `--max-admin-fee`.
+    #[arg(long)]
+    pub claim_data: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RewardClaims {
+    pub era_index: u32,
+    pub rewards_merkle_root: H256,
+    pub total_points: Amount,
+    /// Whether the merkle root and total points match the `ReportRewards` command, if given.
+    pub matches_report: Option<bool>,
+    pub claims: Vec<OperatorClaim>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OperatorClaim {
+    pub operator: H256,
+    pub points: u32,
+    pub leaf_index: u64,
+    pub proof: Vec<H256>,
+    /// Calldata of `claimRewards` for this operator.
+    pub calldata: String,
+}
+
+impl RewardClaimCalldataCmd {
+    pub fn run(&self) -> RewardClaims {
+        let era_state = match (&self.state_path, &self.era_points) {
+            (Some(path), _) => read_era_state(path, self.era),
+            (None, Some(era_points)) => EraState {
+                era_points: decode_era_points(&decode_hex(era_points)),
+                ..Default::default()
+            },
+            (None, None) => unreachable!("clap requires --state-path or --era-points"),
+        };
+        let era_points = era_state.source_points(self.source);
+        let report = self.report_rewards.as_ref().map(|report| {
+            decode_outbound_commands(&decode_hex(report))
+                .into_iter()
+                .find(|command| matches!(command, Command::ReportRewards { .. }))
+                .expect("--report-rewards is not a ReportRewards command")
+        });
+        let claim_data = self
+            .claim_data
+            .as_ref()
+            .map(|data| decode_hex(data))
+            .unwrap_or_else(|| default_claim_data(self.max_admin_fee));
+
+        let claims = build_reward_claims(self.era, &era_points, report.as_ref(), &claim_data);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&claims).expect("claims are valid JSON")
+        );
+        if claims.matches_report == Some(false) {
+            eprintln!(
+                "\nThe rewards merkle root does not match the ReportRewards command. Check that \
+                --source is the source the command was sent to, and use --state-path if the era \
+                has several external sources or stake weighted points."
+            );
+            std::process::exit(1);
+        }
+
+        claims
+    }
+}
+
+/// Reward points of an era together with the source and stake of each validator, as needed to
+/// rebuild the points reported to each external source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EraState {
+    pub era_points: EraRewardPoints<AccountId32>,
+    pub sources: BTreeMap<AccountId32, ExternalSourceId>,
+    pub stakes: BTreeMap<AccountId32, u128>,
+    pub stake_weighted: bool,
+}
+
+impl EraState {
+    /// Points reported to `source`, split and weighted the same way as the rewards pallet does.
+    pub fn source_points(&self, source: ExternalSourceId) -> EraRewardPoints<AccountId32> {
+        let mut points = self
+            .era_points
+            .clone()
+            .split_by_source(|account| self.sources.get(account).copied())
+            .remove(&source)
+            .unwrap_or_default();
+        if self.stake_weighted {
+            points.weight_by_stake(|account| self.stakes.get(account).copied());
+        }
+        points
+    }
+}
+
+/// Builds the proof and calldata of every operator with points in `era_points`.
+pub fn build_reward_claims(
+    era_index: u32,
+    era_points: &EraRewardPoints<AccountId32>,
+    report: Option<&Command>,
+    claim_data: &[u8],
+) -> RewardClaims {
+    let utils = era_points
+        .generate_era_rewards_utils::<Keccak256>(era_index, None)
+        .expect("utils are always generated without an account to check");
+
+    let matches_report = report.map(|command| match command {
+        Command::ReportRewards {
+            total_points,
+            rewards_merkle_root,
+            ..
+        } => {
+            *rewards_merkle_root == utils.rewards_merkle_root && *total_points == utils.total_points
+        }
+        _ => false,
+    });
+
+    let claims = era_points
+        .individual
+        .iter()
+        .enumerate()
+        .map(|(index, (account, points))| {
+            let leaf_index = index as u64;
+            let proof = merkle_proof::<Keccak256, _>(utils.leaves.clone().into_iter(), leaf_index);
+            let operator = H256::from(<[u8; 32]>::from(account.clone()));
+
+            OperatorClaim {
+                operator,
+                points: *points,
+                leaf_index,
+                calldata: format!(
+                    "0x{}",
+                    hex::encode(claim_rewards_calldata(
+                        operator,
+                        era_index,
+                        *points,
+                        &proof.proof,
+                        claim_data,
+                    ))
+                ),
+                proof: proof.proof,
+            }
+        })
+        .collect();
+
+    RewardClaims {
+        era_index,
+        rewards_merkle_root: utils.rewards_merkle_root,
+        total_points: Amount(utils.total_points),
+        matches_report,
+        claims,
+    }
+}
+
+/// Calldata of `claimRewards(ClaimRewardsInput)`, where `ClaimRewardsInput` is
+/// `(bytes32 operatorKey, uint48 eraIndex, uint32 totalPointsClaimable, bytes32[] proof, bytes data)`.
+pub fn claim_rewards_calldata(
+    operator: H256,
+    era_index: u32,
+    points: u32,
+    proof: &[H256],
+    data: &[u8],
+) -> Vec<u8> {
+    let mut calldata = keccak_256(CLAIM_REWARDS_SIGNATURE)[..4].to_vec();
+    calldata.extend(encode(&[Token::Tuple(vec![
+        Token::FixedBytes(operator.0.to_vec()),
+        Token::Uint(era_index.into()),
+        Token::Uint(points.into()),
+        Token::Array(
+            proof
+                .iter()
+                .map(|node| Token::FixedBytes(node.0.to_vec()))
+                .collect(),
+        ),
+        Token::Bytes(data.to_vec()),
+    ])]));
+    calldata
+}
+
+/// Claim data expected by the middleware: `(uint256 maxAdminFee, bytes activeSharesHint,
+/// bytes activeStakeHint)`, without hints.
+pub fn default_claim_data(max_admin_fee: u128) -> Vec<u8> {
+    encode(&[
+        Token::Uint(max_admin_fee.into()),
+        Token::Bytes(vec![]),
+        Token::Bytes(vec![]),
+    ])
+}
+
+/// Storage key of `RewardPointsForEra` for `era`.
+pub fn era_points_storage_key(era: u32) -> Vec<u8> {
+    let encoded_era = era.encode();
+    let mut key = twox_128(REWARDS_PALLET_NAME).to_vec();
+    key.extend(twox_128(b"RewardPointsForEra"));
+    key.extend(twox_64(&encoded_era));
+    key.extend(encoded_era);
+    key
+}
+
+/// Storage key of an `ExternalValidators` map keyed by era and validator, like
+/// `ErasValidatorSource` and `ErasValidatorStake`.
+pub fn era_validator_storage_key(storage_name: &[u8], era: u32, account: &AccountId32) -> Vec<u8> {
+    let encoded_era = era.encode();
+    let encoded_account = account.encode();
+    let mut key = twox_128(EXTERNAL_VALIDATORS_PALLET_NAME).to_vec();
+    key.extend(twox_128(storage_name));
+    key.extend(twox_64(&encoded_era));
+    key.extend(encoded_era);
+    key.extend(twox_64(&encoded_account));
+    key.extend(encoded_account);
+    key
+}
+
+/// Storage key of a `parameter_types!` storage parameter.
+pub fn storage_parameter_key(name: &[u8]) -> Vec<u8> {
+    twox_128(&[b":", name, b":"].concat()).to_vec()
+}
+
+fn read_era_state(path: &Path, era: u32) -> EraState {
+    let reader = std::fs::File::open(path).expect("Can open file");
+    let state: serde_json::Value =
+        serde_json::from_reader(reader).expect("Cant parse state from JSON");
+    let storage = state
+        .pointer("/genesis/raw/top")
+        .unwrap_or(&state)
+        .as_object()
+        .expect("state must be a map of storage keys to values");
+    let get = |key: &[u8]| {
+        storage
+            .get(&format!("0x{}", hex::encode(key)))
+            .map(|value| decode_hex(value.as_str().expect("storage values must be hex strings")))
+    };
+
+    let era_points = match get(&era_points_storage_key(era)) {
+        Some(value) => decode_era_points(&value),
+        None => panic!("no reward points found for era {} in the state", era),
+    };
+
+    let mut sources = BTreeMap::new();
+    let mut stakes = BTreeMap::new();
+    for account in era_points.individual.keys() {
+        if let Some(value) = get(&era_validator_storage_key(
+            b"ErasValidatorSource",
+            era,
+            account,
+        )) {
+            let source = ExternalSourceId::decode_all(&mut &value[..])
+                .expect("invalid ErasValidatorSource encoding");
+            sources.insert(account.clone(), source);
+        }
+        if let Some(value) = get(&era_validator_storage_key(
+            b"ErasValidatorStake",
+            era,
+            account,
+        )) {
+            let stake =
+                u128::decode_all(&mut &value[..]).expect("invalid ErasValidatorStake encoding");
+            stakes.insert(account.clone(), stake);
+        }
+    }
+
+    // Storage parameters are not written until they are changed from their default
+    let stake_weighted = get(&storage_parameter_key(STAKE_WEIGHTED_POINTS_PARAMETER))
+        .map(|value| bool::decode_all(&mut &value[..]).expect("invalid bool encoding"))
+        .unwrap_or(false);
+
+    EraState {
+        era_points,
+        sources,
+        stakes,
+        stake_weighted,
+    }
+}
+
+fn decode_era_points(mut encoded: &[u8]) -> EraRewardPoints<AccountId32> {
+    EraRewardPoints::decode_all(&mut encoded).expect("invalid EraRewardPoints encoding")
+}
+
+fn decode_hex(value: &str) -> Vec<u8> {
+    let hex_trimmed = value.trim().strip_prefix("0x").unwrap_or(value.trim());
+    hex::decode(hex_trimmed).expect("invalid hex")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn era_points() -> EraRewardPoints<AccountId32> {
+        EraRewardPoints {
+            total: 40,
+            individual: BTreeMap::from([
+                (AccountId32::from([4; 32]), 20),
+                (AccountId32::from([5; 32]), 20),
+            ]),
+        }
+    }
+
+    #[test]
+    fn claims_match_reward_claim_generator() {
+        let claims = build_reward_claims(1, &era_points(), None, &default_claim_data(10_000));
+
+        // Same values as in the README of the reward claim generator
+        assert_eq!(
+            claims.rewards_merkle_root,
+            H256(hex_literal::hex!(
+                "4b0ddd8b9b8ec6aec84bcd2003c973254c41d976f6f29a163054eec4e7947810"
+            ))
+        );
+        assert_eq!(claims.matches_report, None);
+        assert_eq!(claims.claims.len(), 2);
+        assert_eq!(
+            claims.claims[0].proof,
+            vec![H256(hex_literal::hex!(
+                "27e610a11a547f210646001377ae223bc6bce387931f8153624d21f6478512d2"
+            ))]
+        );
+        assert_eq!(claims.claims[1].leaf_index, 1);
+    }
+
+    #[test]
+    fn claims_are_verified_against_report() {
+        let claims = build_reward_claims(1, &era_points(), None, &[]);
+        let mut report = Command::ReportRewards {
+            external_idx: 0,
+            era_index: 1,
+            total_points: 40,
+            tokens_inflated: 1_000,
+            rewards_merkle_root: claims.rewards_merkle_root,
+            token_id: H256::repeat_byte(1),
+        };
+
+        let checked = build_reward_claims(1, &era_points(), Some(&report), &[]);
+        assert_eq!(checked.matches_report, Some(true));
+
+        if let Command::ReportRewards {
+            rewards_merkle_root,
+            ..
+        } = &mut report
+        {
+            *rewards_merkle_root = H256::zero();
+        }
+        let checked = build_reward_claims(1, &era_points(), Some(&report), &[]);
+        assert_eq!(checked.matches_report, Some(false));
+    }
+
+    #[test]
+    fn calldata_is_abi_encoded() {
+        let calldata =
+            claim_rewards_calldata(H256::repeat_byte(4), 1, 20, &[H256::repeat_byte(9)], &[]);
+
+        assert_eq!(calldata[..4], keccak_256(CLAIM_REWARDS_SIGNATURE)[..4]);
+        // Offset of the tuple, operator, era, points
+        assert_eq!(calldata[4 + 31], 0x20);
+        assert_eq!(calldata[4 + 32..4 + 64], [4; 32]);
+        assert_eq!(calldata[4 + 95], 1);
+        assert_eq!(calldata[4 + 127], 20);
+    }
+
+    #[test]
+    fn one_era_points_source_is_required() {
+        use clap::Parser;
+
+        let parse = |args: &[&str]| {
+            RewardClaimCalldataCmd::try_parse_from(
+                ["reward-claim-calldata", "--era", "1"].iter().chain(args),
+            )
+        };
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--state-path", "state.json", "--era-points", "0x00"]).is_err());
+        assert!(parse(&["--state-path", "state.json"]).is_ok());
+        assert!(parse(&["--era-points", "0x00"]).is_ok());
+    }
+
+    #[test]
+    fn reads_era_state_from_exported_state() {
+        let state = serde_json::json!({
+            "genesis": { "raw": { "top": {
+                format!("0x{}", hex::encode(era_points_storage_key(3))):
+                    format!("0x{}", hex::encode(era_points().encode())),
+                format!("0x{}", hex::encode(era_validator_storage_key(
+                    b"ErasValidatorSource", 3, &AccountId32::from([5; 32])
+                ))): format!("0x{}", hex::encode(1u32.encode())),
+                format!("0x{}", hex::encode(era_validator_storage_key(
+                    b"ErasValidatorStake", 3, &AccountId32::from([4; 32])
+                ))): format!("0x{}", hex::encode(1_000u128.encode())),
+                format!("0x{}", hex::encode(storage_parameter_key(
+                    STAKE_WEIGHTED_POINTS_PARAMETER
+                ))): format!("0x{}", hex::encode(true.encode())),
+            } } }
+        });
+        let path = std::env::temp_dir().join("tanssi-utils-reward-claims-state.json");
+        std::fs::write(&path, state.to_string()).unwrap();
+
+        assert_eq!(
+            read_era_state(&path, 3),
+            EraState {
+                era_points: era_points(),
+                sources: BTreeMap::from([(AccountId32::from([5; 32]), 1)]),
+                stakes: BTreeMap::from([(AccountId32::from([4; 32]), 1_000)]),
+                stake_weighted: true,
+            }
+        );
+    }
+
+    #[test]
+    fn source_points_are_split_and_weighted_like_the_pallet() {
+        let mut era_state = EraState {
+            era_points: EraRewardPoints {
+                total: 60,
+                individual: BTreeMap::from([
+                    (AccountId32::from([4; 32]), 20),
+                    (AccountId32::from([5; 32]), 20),
+                    (AccountId32::from([6; 32]), 20),
+                ]),
+            },
+            sources: BTreeMap::from([(AccountId32::from([6; 32]), 1)]),
+            stakes: BTreeMap::from([
+                (AccountId32::from([4; 32]), 3),
+                (AccountId32::from([5; 32]), 1),
+                (AccountId32::from([6; 32]), 1),
+            ]),
+            stake_weighted: false,
+        };
+
+        // Validators without a source belong to the default one
+        assert_eq!(
+            era_state.source_points(DEFAULT_EXTERNAL_SOURCE),
+            era_points()
+        );
+        assert_eq!(
+            era_state.source_points(1),
+            EraRewardPoints {
+                total: 20,
+                individual: BTreeMap::from([(AccountId32::from([6; 32]), 20)]),
+            }
+        );
+        assert_eq!(era_state.source_points(2), EraRewardPoints::default());
+
+        era_state.stake_weighted = true;
+        assert_eq!(
+            era_state.source_points(DEFAULT_EXTERNAL_SOURCE),
+            EraRewardPoints {
+                total: 40,
+                individual: BTreeMap::from([
+                    (AccountId32::from([4; 32]), 30),
+                    (AccountId32::from([5; 32]), 10),
+                ]),
+            }
+        );
+
+        // Nothing is weighted if a stake is unknown
+        era_state.stakes.remove(&AccountId32::from([5; 32]));
+        assert_eq!(
+            era_state.source_points(DEFAULT_EXTERNAL_SOURCE),
+            era_points()
+        );
+    }
+
+    #[test]
+    fn source_defaults_to_the_default_external_source() {
+        use clap::Parser;
+
+        let cmd = RewardClaimCalldataCmd::try_parse_from([
+            "reward-claim-calldata",
+            "--era",
+            "1",
+            "--era-points",
+            "0x00",
+        ])
+        .unwrap();
+        assert_eq!(cmd.source, DEFAULT_EXTERNAL_SOURCE);
+    }
+}
@@ -460,6 +460,8 @@ fn data_preserver_mode(cli: &Cli, cmd: &crate::cli::DataPreserverCmd) -> Result<
             config,
             provider_profile_id: cmd.base.profile_id,
             orchestrator_endpoints: cmd.base.orchestrator_endpoints.clone(),
+            orchestrator_endpoints_quorum: cmd.base.orchestrator_endpoints_quorum,
+            orchestrator_light_client_checkpoint: cmd.base.orchestrator_light_client_checkpoint,
             collator_options: cmd.base.container_run.collator_options(),
             polkadot_cli,
             orchestrator_cli,
@@ -427,6 +427,8 @@ fn data_preserver_mode(cli: &Cli, cmd: &DataPreserverCmd) -> Result<()> {
             config,
             provider_profile_id: cmd.profile_id,
             orchestrator_endpoints: cmd.orchestrator_endpoints.clone(),
+            orchestrator_endpoints_quorum: cmd.orchestrator_endpoints_quorum,
+            orchestrator_light_client_checkpoint: cmd.orchestrator_light_client_checkpoint,
             collator_options: cmd.container_run.collator_options(),
             polkadot_cli,
             orchestrator_cli,
@@ -1553,6 +1553,13 @@ impl_runtime_apis! {
         }
     }
 
+    impl tp_container_chain_runtime_api::ContainerEthereumTransfersApi<Block> for Runtime {
+        fn quote_ethereum_export(message: VersionedXcm<()>) -> Result<tp_container_chain_runtime_api::ExportFeeQuote, XcmPaymentApiError> {
+            let message = message.try_into().map_err(|_| XcmPaymentApiError::VersionedConversionFailed)?;
+            xcm_config::EthereumExporter::quote_export(message).map_err(|_| XcmPaymentApiError::Unroutable)
+        }
+    }
+
     impl xcm_runtime_apis::dry_run::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
         fn dry_run_call(origin: OriginCaller, call: RuntimeCall, result_xcms_version: XcmVersion) -> Result<CallDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
             PolkadotXcm::dry_run_call::<Runtime, xcm_config::XcmRouter, OriginCaller, RuntimeCall>(origin, call, result_xcms_version)
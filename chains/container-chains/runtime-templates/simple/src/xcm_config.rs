@@ -191,15 +191,18 @@ pub type XcmRouter = WithUniqueTopic<(
     UmpRouter,
     // ..and XCMP to communicate with the sibling chains.
     XcmpQueue,
-    SovereignPaidRemoteExporter<
-        UmpRouter,
-        UniversalLocation,
-        EthereumNetwork,
-        ContainerToEthTransferFee,
-        ParachainInfo,
-    >,
+    EthereumExporter,
 )>;
 
+/// Exports messages to Ethereum through the relay chain, paying with the sovereign account.
+pub type EthereumExporter = SovereignPaidRemoteExporter<
+    UmpRouter,
+    UniversalLocation,
+    EthereumNetwork,
+    ContainerToEthTransferFee,
+    ParachainInfo,
+>;
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
     type RuntimeCall = RuntimeCall;
@@ -311,6 +311,8 @@ pub fn start_dev_node(
                 xcm_senders: xcm_senders.clone(),
                 randomness_sender: randomness_sender.clone(),
                 container_chain_exclusion_sender: container_chains_exclusion_sender.clone(),
+                container_chain_spawner: None,
+                session_keys: None,
             };
 
             tc_service_orchestrator_chain::parachain::rpc::create_full(deps).map_err(Into::into)
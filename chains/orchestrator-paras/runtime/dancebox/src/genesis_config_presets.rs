@@ -178,6 +178,7 @@ fn testnet_genesis(
         dancebox_runtime::StakingAccount::get(),
         dancebox_runtime::ParachainBondAccount::get(),
         dancebox_runtime::PendingRewardsAccount::get(),
+        dancebox_runtime::InvulnerablesPendingRewardsAccount::get(),
         dancebox_runtime::TreasuryId::get().into_account_truncating(),
     ];
     let g = dancebox_runtime::RuntimeGenesisConfig {
@@ -92,7 +92,7 @@ use {
     pallet_session::{SessionManager, ShouldEndSession},
     pallet_stream_payment_runtime_api::{StreamPaymentApiError, StreamPaymentApiStatus},
     pallet_transaction_payment::FungibleAdapter,
-    pallet_xcm_core_buyer::BuyingError,
+    pallet_xcm_core_buyer::{BuyingError, CorePurchaseRecord, CorePurchaseStats},
     parity_scale_codec::DecodeWithMemTracking,
     polkadot_runtime_common::BlockHashCount,
     scale_info::prelude::format,
@@ -114,9 +114,10 @@ use {
     tanssi_runtime_common::SessionTimer,
     tp_stream_payment_common::StreamId,
     tp_traits::{
-        apply, derive_storage_traits, GetContainerChainAuthor, GetHostConfiguration,
-        GetSessionContainerChains, MaybeSelfChainBlockAuthor, NodeActivityTrackingHelper,
-        ParaIdAssignmentHooks, RelayStorageRootProvider, RemoveInvulnerables, SlotFrequency,
+        apply, derive_storage_traits, ContainerChainEquivocationProof, GetContainerChainAuthor,
+        GetHostConfiguration, GetSessionContainerChains, MaybeSelfChainBlockAuthor,
+        NodeActivityTrackingHelper, ParaIdAssignmentHooks, RelayStorageRootProvider,
+        RemoveInvulnerables, SlotFrequency,
     },
     tp_xcm_core_buyer::BuyCoreCollatorProof,
     xcm::Version as XcmVersion,
@@ -664,6 +665,9 @@ impl pallet_initializer::ApplyNewSession<Runtime> for OwnApplySession {
 
         // Next: InactivityTracking
         InactivityTracking::process_ended_session();
+
+        // Next: AuthorNoting
+        AuthorNoting::initializer_on_new_session(&session_index);
     }
 
     fn on_before_session_ending() {
@@ -703,6 +707,8 @@ impl SessionManager<CollatorId> for CollatorsFromInvulnerablesAndThenFromStaking
             );
         }
 
+        // Remove expired invulnerables before selecting the collators of the next session
+        Invulnerables::initializer_on_new_session(&index);
         let invulnerables = Invulnerables::invulnerables().to_vec();
         let candidates_staking =
             pallet_pooled_staking::SortedEligibleCandidates::<Runtime>::get().to_vec();
@@ -1086,6 +1092,11 @@ impl pallet_author_noting::Config for Runtime {
         cumulus_pallet_parachain_system::RelaychainDataProvider<Self>,
     >;
     type MaxContainerChains = MaxLengthParaIds;
+    type CollatorAccount =
+        pallet_authority_mapping::CollatorAccountLookup<Runtime, CurrentSessionIndexGetter>;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    // Equivocations are only recorded for now, no pallet punishes them yet
+    type EquivocationReporter = ();
     type WeightInfo = weights::pallet_author_noting::SubstrateWeight<Runtime>;
 }
 
@@ -1095,6 +1106,8 @@ parameter_types! {
     pub const MinCandidates: u32 = 5;
     pub const SessionLength: BlockNumber = 5;
     pub const MaxInvulnerables: u32 = 100;
+    pub InvulnerablesPendingRewardsAccount: AccountId =
+        PalletId(*b"InvPndRw").into_account_truncating();
     pub const ExecutiveBody: BodyId = BodyId::Executive;
 }
 
@@ -1104,9 +1117,11 @@ impl pallet_invulnerables::Config for Runtime {
     type CollatorId = <Self as frame_system::Config>::AccountId;
     type CollatorIdOf = ConvertInto;
     type CollatorRegistration = Session;
-    type WeightInfo = weights::pallet_invulnerables::SubstrateWeight<Runtime>;
-    #[cfg(feature = "runtime-benchmarks")]
     type Currency = Balances;
+    type RewardsTreasury = TreasuryAccount;
+    type PendingRewardsAccount = InvulnerablesPendingRewardsAccount;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type WeightInfo = weights::pallet_invulnerables::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -2461,6 +2476,18 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_author_noting_runtime_api::ContainerChainEquivocationApi<Block> for Runtime {
+        fn create_equivocation_report_unsigned_extrinsic(equivocation_proof: ContainerChainEquivocationProof) -> Box<<Block as BlockT>::Extrinsic> {
+            let call = RuntimeCall::AuthorNoting(pallet_author_noting::Call::report_container_chain_equivocation {
+                equivocation_proof: Box::new(equivocation_proof),
+            });
+
+            let unsigned_extrinsic = UncheckedExtrinsic::new_bare(call);
+
+            Box::new(unsigned_extrinsic)
+        }
+    }
+
     impl dp_consensus::TanssiAuthorityAssignmentApi<Block, NimbusId> for Runtime {
         /// Return the current authorities assigned to a given paraId
         fn para_id_authorities(para_id: ParaId) -> Option<Vec<NimbusId>> {
@@ -2495,6 +2522,31 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_authority_assignment_runtime_api::AuthorityAssignmentApi<Block, NimbusId, ParaId> for Runtime {
+        /// Return every paraId assigned to a given authority
+        fn para_ids_of_authority(authority: NimbusId) -> Vec<ParaId> {
+            let parent_number = System::block_number();
+            let should_end_session = <Runtime as pallet_session::Config>::ShouldEndSession::should_end_session(parent_number + 1);
+
+            let session_index = if should_end_session {
+                Session::current_index() +1
+            }
+            else {
+                Session::current_index()
+            };
+
+            AuthorityAssignment::para_ids_of(session_index, &authority, ParachainInfo::get())
+        }
+
+        /// Return every paraId assigned to a given authority on the next session.
+        /// On session boundary this returns the same as `para_ids_of_authority`.
+        fn para_ids_of_authority_next_session(authority: NimbusId) -> Vec<ParaId> {
+            let session_index = Session::current_index() + 1;
+
+            AuthorityAssignment::para_ids_of(session_index, &authority, ParachainInfo::get())
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
     for Runtime {
         fn query_info(
@@ -2616,6 +2668,14 @@ impl_runtime_apis! {
         fn get_buy_core_slot_drift() -> Slot {
             <Runtime as pallet_xcm_core_buyer::Config>::BuyCoreSlotDrift::get()
         }
+
+        fn get_core_purchase_history(para_id: ParaId) -> Vec<CorePurchaseRecord<BlockNumber, NimbusId>> {
+            pallet_xcm_core_buyer::CorePurchaseHistory::<Runtime>::get(para_id).into_inner()
+        }
+
+        fn get_core_purchase_stats(para_id: ParaId) -> CorePurchaseStats {
+            pallet_xcm_core_buyer::CorePurchaseStatsOf::<Runtime>::get(para_id)
+        }
     }
 
     impl xcm_runtime_apis::fees::XcmPaymentApi<Block> for Runtime {
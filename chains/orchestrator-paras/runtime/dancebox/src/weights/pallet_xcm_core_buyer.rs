@@ -63,6 +63,8 @@ impl<T: frame_system::Config> pallet_xcm_core_buyer::WeightInfo for SubstrateWei
 	/// Proof: `Registrar::ParathreadParams` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
 	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
 	/// Storage: `XcmCoreBuyer::RelayXcmWeightConfig` (r:1 w:0)
 	/// Proof: `XcmCoreBuyer::RelayXcmWeightConfig` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
 	/// Storage: `ServicesPayment::MaxCorePrice` (r:1 w:0)
@@ -87,14 +89,18 @@ impl<T: frame_system::Config> pallet_xcm_core_buyer::WeightInfo for SubstrateWei
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: Some(2733), added: 5208, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
 	fn buy_core() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1604`
-		//  Estimated: `5069`
+		//  Estimated: `6198`
 		// Minimum execution time: 125_146_000 picoseconds.
-		Weight::from_parts(128_274_000, 5069)
-			.saturating_add(T::DbWeight::get().reads(15_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+		Weight::from_parts(131_906_000, 6198)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
 	}
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1 w:1)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
@@ -104,6 +110,8 @@ impl<T: frame_system::Config> pallet_xcm_core_buyer::WeightInfo for SubstrateWei
 	/// Proof: `Registrar::ParathreadParams` (`max_values`: None, `max_size`: Some(28), added: 2503, mode: `MaxEncodedLen`)
 	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
 	/// Storage: `XcmCoreBuyer::RelayXcmWeightConfig` (r:1 w:0)
 	/// Proof: `XcmCoreBuyer::RelayXcmWeightConfig` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
 	/// Storage: `ServicesPayment::MaxCorePrice` (r:1 w:0)
@@ -128,48 +136,62 @@ impl<T: frame_system::Config> pallet_xcm_core_buyer::WeightInfo for SubstrateWei
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: Some(2733), added: 5208, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
 	fn force_buy_core() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1604`
-		//  Estimated: `5069`
+		//  Estimated: `6198`
 		// Minimum execution time: 119_206_000 picoseconds.
-		Weight::from_parts(125_363_000, 5069)
-			.saturating_add(T::DbWeight::get().reads(14_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+		Weight::from_parts(128_810_000, 6198)
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: `XcmCoreBuyer::QueryIdToParaId` (r:1 w:1)
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
 	/// Storage: `XcmCoreBuyer::PendingBlocks` (r:0 w:1)
 	/// Proof: `XcmCoreBuyer::PendingBlocks` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:0 w:1)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: Some(2733), added: 5208, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
 	fn query_response() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1221`
-		//  Estimated: `3485`
-		// Minimum execution time: 40_054_000 picoseconds.
-		Weight::from_parts(41_508_000, 3485)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		//  Estimated: `6198`
+		// Minimum execution time: 41_731_000 picoseconds.
+		Weight::from_parts(47_539_000, 6198)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1000 w:1000)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
 	/// Storage: `XcmCoreBuyer::QueryIdToParaId` (r:0 w:1000)
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1000 w:1000)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: Some(2733), added: 5208, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1000 w:1000)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: Some(60), added: 2535, mode: `MaxEncodedLen`)
 	/// The range of component `x` is `[1, 1000]`.
 	fn clean_up_expired_in_flight_orders(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `205 + x * (36 ±0)`
-		//  Estimated: `3497 + x * (2507 ±0)`
+		//  Estimated: `3497 + x * (5208 ±0)`
 		// Minimum execution time: 24_798_000 picoseconds.
 		Weight::from_parts(25_071_000, 3497)
 			// Standard Error: 5_155
 			.saturating_add(Weight::from_parts(6_992_979, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(x.into())))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
-			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 2507).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 5208).saturating_mul(x.into()))
 	}
 	/// Storage: `XcmCoreBuyer::PendingBlocks` (r:1000 w:1000)
 	/// Proof: `XcmCoreBuyer::PendingBlocks` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
@@ -208,4 +230,27 @@ impl<T: frame_system::Config> pallet_xcm_core_buyer::WeightInfo for SubstrateWei
 		Weight::from_parts(4_188_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-}
\ No newline at end of file
+	/// Storage: `XcmCoreBuyer::CoretimeXcmWeightConfig` (r:0 w:1)
+	/// Proof: `XcmCoreBuyer::CoretimeXcmWeightConfig` (`max_values`: Some(1), `max_size`: Some(32), added: 527, mode: `MaxEncodedLen`)
+	fn set_coretime_xcm_weight_config() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 4_051_000 picoseconds.
+		Weight::from_parts(4_263_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1 w:0)
+	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:0 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: Some(45), added: 2520, mode: `MaxEncodedLen`)
+	fn set_bulk_region() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `42`
+		//  Estimated: `3497`
+		// Minimum execution time: 11_302_000 picoseconds.
+		Weight::from_parts(11_748_000, 3497)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
@@ -35,15 +35,18 @@ use {
     cumulus_primitives_core::{AggregateMessageOrigin, ParaId},
     frame_support::{
         parameter_types,
-        traits::{Disabled, Equals, Everything, Nothing, PalletInfoAccess, TransformOrigin},
+        traits::{
+            Disabled, EitherOfDiverse, Equals, Everything, Nothing, PalletInfoAccess,
+            TransformOrigin,
+        },
         weights::Weight,
     },
     frame_system::{pallet_prelude::BlockNumberFor, EnsureRoot},
     nimbus_primitives::NimbusId,
     pallet_xcm::XcmPassthrough,
     pallet_xcm_core_buyer::{
-        CheckCollatorValidity, GetParathreadMaxCorePrice, GetParathreadParams, GetPurchaseCoreCall,
-        ParaIdIntoAccountTruncating, XCMNotifier,
+        BulkRegionId, CheckCollatorValidity, GetAssignRegionCall, GetParathreadMaxCorePrice,
+        GetParathreadParams, GetPurchaseCoreCall, ParaIdIntoAccountTruncating, XCMNotifier,
     },
     parachains_common::message_queue::{NarrowOriginToSibling, ParaIdToSibling},
     parity_scale_codec::{Decode, DecodeWithMemTracking, Encode},
@@ -453,6 +456,7 @@ impl pallet_xcm_core_buyer::Config for Runtime {
 
     type XcmSender = XcmRouter;
     type GetPurchaseCoreCall = EncodedCallToBuyCore;
+    type GetAssignRegionCall = EncodedCallToAssignRegion;
     type GetParathreadAccountId = ParaIdIntoAccountTruncating;
     type GetParathreadMaxCorePrice = GetMaxCorePriceFromServicesPayment;
     type SelfParaId = parachain_info::Pallet<Runtime>;
@@ -471,6 +475,9 @@ impl pallet_xcm_core_buyer::Config for Runtime {
     type LatestAuthorInfoFetcher = AuthorNoting;
     type SlotBeacon = dp_consensus::AuraDigestSlotBeacon<Runtime>;
     type CollatorPublicKey = NimbusId;
+    type MaxPurchaseHistoryLength = ConstU32<32>;
+    type ManagerOrigin =
+        EitherOfDiverse<pallet_registrar::EnsureSignedByManager<Runtime>, EnsureRoot<AccountId>>;
     type WeightInfo = weights::pallet_xcm_core_buyer::SubstrateWeight<Runtime>;
 }
 
@@ -567,6 +574,39 @@ impl GetPurchaseCoreCall<RelayChain> for EncodedCallToBuyCore {
     }
 }
 
+pub struct EncodedCallToAssignRegion;
+
+impl GetAssignRegionCall<RelayChain> for EncodedCallToAssignRegion {
+    fn coretime_chain_para_id(relay_chain: RelayChain) -> ParaId {
+        match relay_chain {
+            RelayChain::Westend => tanssi_relay_encoder::westend::CORETIME_PARA_ID.into(),
+            RelayChain::Rococo => tanssi_relay_encoder::rococo::CORETIME_PARA_ID.into(),
+        }
+    }
+
+    fn get_encoded(relay_chain: RelayChain, region_id: BulkRegionId, para_id: ParaId) -> Vec<u8> {
+        // Final assignments are required to be able to renew the core
+        let assign = tanssi_relay_encoder::broker::BrokerCall::Assign {
+            region_id: tanssi_relay_encoder::broker::RegionId {
+                begin: region_id.begin,
+                core: region_id.core,
+                mask: region_id.mask,
+            },
+            task: para_id.into(),
+            finality: tanssi_relay_encoder::broker::Finality::Final,
+        };
+
+        match relay_chain {
+            RelayChain::Westend => {
+                tanssi_relay_encoder::westend::CoretimeCall::Broker(assign).encode()
+            }
+            RelayChain::Rococo => {
+                tanssi_relay_encoder::rococo::CoretimeCall::Broker(assign).encode()
+            }
+        }
+    }
+}
+
 pub struct GetMaxCorePriceFromServicesPayment;
 
 impl GetParathreadMaxCorePrice for GetMaxCorePriceFromServicesPayment {
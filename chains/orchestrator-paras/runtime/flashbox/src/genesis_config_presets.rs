@@ -179,6 +179,7 @@ fn testnet_genesis(
         flashbox_runtime::StakingAccount::get(),
         flashbox_runtime::ParachainBondAccount::get(),
         flashbox_runtime::PendingRewardsAccount::get(),
+        flashbox_runtime::InvulnerablesPendingRewardsAccount::get(),
         flashbox_runtime::TreasuryId::get().into_account_truncating(),
     ];
     let g = flashbox_runtime::RuntimeGenesisConfig {
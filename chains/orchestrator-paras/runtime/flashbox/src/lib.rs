@@ -50,6 +50,7 @@ mod tests;
 use {
     alloc::string::ToString,
     alloc::{
+        boxed::Box,
         collections::{btree_map::BTreeMap, btree_set::BTreeSet},
         vec,
         vec::Vec,
@@ -109,9 +110,10 @@ use {
     sp_version::RuntimeVersion,
     tp_stream_payment_common::StreamId,
     tp_traits::{
-        apply, derive_storage_traits, GetContainerChainAuthor, GetHostConfiguration,
-        GetSessionContainerChains, MaybeSelfChainBlockAuthor, ParaIdAssignmentHooks,
-        RelayStorageRootProvider, RemoveInvulnerables, ShouldRotateAllCollators,
+        apply, derive_storage_traits, ContainerChainEquivocationProof, GetContainerChainAuthor,
+        GetHostConfiguration, GetSessionContainerChains, MaybeSelfChainBlockAuthor,
+        ParaIdAssignmentHooks, RelayStorageRootProvider, RemoveInvulnerables,
+        ShouldRotateAllCollators,
     },
 };
 pub use {
@@ -574,6 +576,9 @@ impl pallet_initializer::ApplyNewSession<Runtime> for OwnApplySession {
             &queued_id_to_nimbus_map,
             &assignments.next_assignment,
         );
+
+        // Next: AuthorNoting
+        AuthorNoting::initializer_on_new_session(&session_index);
     }
     fn on_before_session_ending() {}
 }
@@ -600,6 +605,8 @@ impl SessionManager<CollatorId> for CollatorsFromInvulnerables {
             <frame_system::Pallet<Runtime>>::block_number(),
         );
 
+        // Remove expired invulnerables before selecting the collators of the next session
+        Invulnerables::initializer_on_new_session(&index);
         let invulnerables = Invulnerables::invulnerables().to_vec();
         let target_session_index = index.saturating_add(1);
         let max_collators =
@@ -924,6 +931,11 @@ impl pallet_author_noting::Config for Runtime {
         cumulus_pallet_parachain_system::RelaychainDataProvider<Self>,
     >;
     type MaxContainerChains = MaxLengthParaIds;
+    type CollatorAccount =
+        pallet_authority_mapping::CollatorAccountLookup<Runtime, CurrentSessionIndexGetter>;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    // Equivocations are only recorded for now, no pallet punishes them yet
+    type EquivocationReporter = ();
     type WeightInfo = weights::pallet_author_noting::SubstrateWeight<Runtime>;
 }
 
@@ -933,6 +945,8 @@ parameter_types! {
     pub const MinCandidates: u32 = 5;
     pub const SessionLength: BlockNumber = 5;
     pub const MaxInvulnerables: u32 = 200;
+    pub InvulnerablesPendingRewardsAccount: AccountId =
+        PalletId(*b"InvPndRw").into_account_truncating();
     pub const ExecutiveBody: BodyId = BodyId::Executive;
 }
 
@@ -942,9 +956,11 @@ impl pallet_invulnerables::Config for Runtime {
     type CollatorId = CollatorId;
     type CollatorIdOf = ConvertInto;
     type CollatorRegistration = Session;
-    type WeightInfo = weights::pallet_invulnerables::SubstrateWeight<Runtime>;
-    #[cfg(feature = "runtime-benchmarks")]
     type Currency = Balances;
+    type RewardsTreasury = TreasuryAccount;
+    type PendingRewardsAccount = InvulnerablesPendingRewardsAccount;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type WeightInfo = weights::pallet_invulnerables::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -1893,6 +1909,18 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_author_noting_runtime_api::ContainerChainEquivocationApi<Block> for Runtime {
+        fn create_equivocation_report_unsigned_extrinsic(equivocation_proof: ContainerChainEquivocationProof) -> Box<<Block as BlockT>::Extrinsic> {
+            let call = RuntimeCall::AuthorNoting(pallet_author_noting::Call::report_container_chain_equivocation {
+                equivocation_proof: Box::new(equivocation_proof),
+            });
+
+            let unsigned_extrinsic = UncheckedExtrinsic::new_bare(call);
+
+            Box::new(unsigned_extrinsic)
+        }
+    }
+
     impl dp_consensus::TanssiAuthorityAssignmentApi<Block, NimbusId> for Runtime {
         /// Return the current authorities assigned to a given paraId
         fn para_id_authorities(para_id: ParaId) -> Option<Vec<NimbusId>> {
@@ -1946,6 +1974,31 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_authority_assignment_runtime_api::AuthorityAssignmentApi<Block, NimbusId, ParaId> for Runtime {
+        /// Return every paraId assigned to a given authority
+        fn para_ids_of_authority(authority: NimbusId) -> Vec<ParaId> {
+            let parent_number = System::block_number();
+            let should_end_session = <Runtime as pallet_session::Config>::ShouldEndSession::should_end_session(parent_number + 1);
+
+            let session_index = if should_end_session {
+                Session::current_index() +1
+            }
+            else {
+                Session::current_index()
+            };
+
+            AuthorityAssignment::para_ids_of(session_index, &authority, ParachainInfo::get())
+        }
+
+        /// Return every paraId assigned to a given authority on the next session.
+        /// On session boundary this returns the same as `para_ids_of_authority`.
+        fn para_ids_of_authority_next_session(authority: NimbusId) -> Vec<ParaId> {
+            let session_index = Session::current_index() + 1;
+
+            AuthorityAssignment::para_ids_of(session_index, &authority, ParachainInfo::get())
+        }
+    }
+
     impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
     for Runtime {
         fn query_info(
@@ -22,6 +22,7 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `benchmark-1`, CPU: `Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("flashbox_dev"), DB CACHE: 1024
+//! PLACEHOLDERS, written by hand and not benchmarked yet: `kill_author_data`, `report_container_chain_equivocation`, `set_latest_author_data`
 
 // Executed Command:
 // ./target/release/tanssi-node
@@ -53,32 +54,15 @@ use core::marker::PhantomData;
 /// Weights for pallet_author_noting using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_author_noting::WeightInfo for SubstrateWeight<T> {
-	/// Storage: `AuthorNoting::DidSetContainerAuthorData` (r:1 w:1)
-	/// Proof: `AuthorNoting::DidSetContainerAuthorData` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
-	/// Storage: `CollatorAssignment::CollatorContainerChain` (r:1 w:0)
-	/// Proof: `CollatorAssignment::CollatorContainerChain` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `ParachainSystem::ValidationData` (r:1 w:0)
-	/// Proof: `ParachainSystem::ValidationData` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `System::Digest` (r:1 w:0)
-	/// Proof: `System::Digest` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `AuthorNoting::LatestAuthor` (r:100 w:100)
-	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
-	/// Storage: `InflationRewards::ChainsToReward` (r:1 w:1)
-	/// Proof: `InflationRewards::ChainsToReward` (`max_values`: Some(1), `max_size`: Some(818), added: 1313, mode: `MaxEncodedLen`)
-	/// The range of component `x` is `[1, 100]`.
+	/// Placeholder weight, not benchmarked yet.
 	fn set_latest_author_data(x: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `314 + x * (37 ±0)`
-		//  Estimated: `2303 + x * (2539 ±0)`
-		// Minimum execution time: 37_286_000 picoseconds.
 		Weight::from_parts(37_673_000, 2303)
-			// Standard Error: 151_097
 			.saturating_add(Weight::from_parts(22_866_112, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 2539).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 5611).saturating_mul(x.into()))
 	}
 	/// Storage: `AuthorNoting::LatestAuthor` (r:0 w:1)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
@@ -90,15 +74,10 @@ impl<T: frame_system::Config> pallet_author_noting::WeightInfo for SubstrateWeig
 		Weight::from_parts(9_423_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: `AuthorNoting::LatestAuthor` (r:0 w:1)
-	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Placeholder weight, not benchmarked yet.
 	fn kill_author_data() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 8_671_000 picoseconds.
 		Weight::from_parts(8_885_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `InflationRewards::ChainsToReward` (r:1 w:1)
 	/// Proof: `InflationRewards::ChainsToReward` (`max_values`: Some(1), `max_size`: Some(818), added: 1313, mode: `MaxEncodedLen`)
@@ -123,4 +102,10 @@ impl<T: frame_system::Config> pallet_author_noting::WeightInfo for SubstrateWeig
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(x.into()))
 	}
+	/// Placeholder weight, not benchmarked yet.
+	fn report_container_chain_equivocation() -> Weight {
+		Weight::from_parts(154_337_000, 7561)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
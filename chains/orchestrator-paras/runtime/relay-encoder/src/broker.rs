@@ -0,0 +1,46 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Types of `pallet_broker`, shared by the coretime chains of all supported relay chains.
+
+use parity_scale_codec::Encode;
+
+/// Same encoding as `pallet_broker::RegionId`.
+#[derive(Encode)]
+pub struct RegionId {
+    pub begin: u32,
+    pub core: u16,
+    pub mask: [u8; 10],
+}
+
+/// Same encoding as `pallet_broker::Finality`.
+#[derive(Encode)]
+pub enum Finality {
+    #[codec(index = 0u8)]
+    Provisional,
+    #[codec(index = 1u8)]
+    Final,
+}
+
+#[derive(Encode)]
+pub enum BrokerCall {
+    #[codec(index = 10u8)]
+    Assign {
+        region_id: RegionId,
+        task: u32,
+        finality: Finality,
+    },
+}
@@ -16,5 +16,6 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod broker;
 pub mod rococo;
 pub mod westend;
@@ -14,10 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
-use {cumulus_primitives_core::ParaId, parity_scale_codec::Encode};
+use {crate::broker::BrokerCall, cumulus_primitives_core::ParaId, parity_scale_codec::Encode};
 
 pub type Balance = u128;
 
+/// Para id of the coretime chain of this relay chain.
+pub const CORETIME_PARA_ID: u32 = 1005;
+
 #[derive(Encode)]
 pub enum RelayCall {
     #[codec(index = 56u8)]
@@ -33,9 +36,19 @@ pub enum OnDemandAssignmentProviderCall {
     },
 }
 
+#[derive(Encode)]
+pub enum CoretimeCall {
+    #[codec(index = 50u8)]
+    Broker(BrokerCall),
+}
+
 #[cfg(test)]
 mod tests {
-    use {super::*, polkadot_runtime_parachains::on_demand as parachains_assigner_on_demand};
+    use {
+        super::*,
+        crate::broker::{Finality, RegionId},
+        polkadot_runtime_parachains::on_demand as parachains_assigner_on_demand,
+    };
 
     #[test]
     fn encode_place_order_allow_death() {
@@ -57,4 +70,27 @@ mod tests {
         // If this fails check most probably indices changed
         assert_eq!(call.encode(), call2.encode());
     }
+
+    #[test]
+    fn encode_assign_region() {
+        let call = CoretimeCall::Broker(BrokerCall::Assign {
+            region_id: RegionId {
+                begin: 1,
+                core: 2,
+                mask: [0xff; 10],
+            },
+            task: 3000,
+            finality: Finality::Final,
+        });
+
+        let mut expected = vec![50u8, 10u8];
+        expected.extend(1u32.to_le_bytes());
+        expected.extend(2u16.to_le_bytes());
+        expected.extend([0xff; 10]);
+        expected.extend(3000u32.to_le_bytes());
+        expected.push(1);
+
+        // If this fails check most probably the broker pallet or call indices changed
+        assert_eq!(call.encode(), expected);
+    }
 }
@@ -183,6 +183,9 @@ where
             )
             .map(|full| full.task_manager)?
         } else {
+            // The RPC builder of this service must call
+            // `tanssi_relay_service::rpc::merge_tanssi_rpcs`, as the dev service does, to serve
+            // the tanssi specific RPCs
             polkadot_service::build_full(
                 config,
                 polkadot_service::NewFullParams {
@@ -44,7 +44,8 @@ use {
         ManualContainerChainsExclusion, ManualContainerChainsExclusionApiServer,
     },
     node_common::service::node_builder::Sealing,
-    polkadot_core_primitives::{AccountId, Balance, Block, Hash, Nonce},
+    outbound_message_tracking_rpc::OutboundMessageTrackingRuntimeApi,
+    polkadot_core_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Nonce},
     polkadot_node_core_parachains_inherent::Error as InherentError,
     polkadot_overseer::Handle,
     polkadot_parachain_primitives::primitives::UpwardMessages,
@@ -58,7 +59,7 @@ use {
     polkadot_service::{
         BlockT, Error, IdentifyVariant, NewFullParams, OverseerGen, SelectRelayChain,
     },
-    sc_client_api::{AuxStore, Backend},
+    sc_client_api::{AuxStore, Backend, UsageProvider},
     sc_consensus_manual_seal::{
         consensus::babe::BabeConsensusDataProvider,
         rpc::{ManualSeal, ManualSealApiServer},
@@ -134,12 +135,14 @@ where
         + HeaderBackend<Block>
         + AuxStore
         + HeaderMetadata<Block, Error = sp_blockchain::Error>
+        + UsageProvider<Block>
         + Send
         + Sync
         + 'static,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BlockBuilder<Block>,
+    C::Api: OutboundMessageTrackingRuntimeApi<Block, BlockNumber>,
     P: TransactionPool + Sync + Send + 'static,
 {
     use {
@@ -150,6 +153,7 @@ where
     let mut io = RpcModule::new(());
     io.merge(System::new(client.clone(), pool.clone()).into_rpc())?;
     io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    crate::rpc::merge_tanssi_rpcs(&mut io, client.clone())?;
 
     if let Some(command_sink) = maybe_command_sink {
         io.merge(ManualSeal::new(command_sink).into_rpc())?;
@@ -19,3 +19,5 @@ pub mod chain_spec;
 pub mod dev_service;
 
 pub mod dev_rpcs;
+
+pub mod rpc;
@@ -0,0 +1,47 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Tanssi specific RPCs served by relay nodes on top of the polkadot ones.
+
+use {
+    jsonrpsee::RpcModule,
+    outbound_message_tracking_rpc::{
+        OutboundMessageTracking, OutboundMessageTrackingApiServer,
+        OutboundMessageTrackingRuntimeApi,
+    },
+    polkadot_core_primitives::{Block, BlockNumber},
+    sc_client_api::UsageProvider,
+    sp_api::ProvideRuntimeApi,
+    std::sync::Arc,
+};
+
+/// Merge the tanssi specific RPCs into `io`.
+///
+/// Production nodes are built by `polkadot_service::build_full`, whose RPC builder lives in our
+/// `polkadot-sdk` fork and must call this function to serve these RPCs. Until then the runtime
+/// APIs behind them can be queried through `state_call`.
+pub fn merge_tanssi_rpcs<C>(
+    io: &mut RpcModule<()>,
+    client: Arc<C>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    C: ProvideRuntimeApi<Block> + UsageProvider<Block> + Send + Sync + 'static,
+    C::Api: OutboundMessageTrackingRuntimeApi<Block, BlockNumber>,
+{
+    io.merge(OutboundMessageTracking::new(client).into_rpc())?;
+
+    Ok(())
+}
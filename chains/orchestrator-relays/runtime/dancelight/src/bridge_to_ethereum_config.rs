@@ -59,15 +59,18 @@ use {
     crate::{
         parameter_types, weights, xcm_config, Balance, Balances, EthereumInboundQueue,
         EthereumOutboundQueue, EthereumOutboundQueueV2, EthereumSovereignAccount, EthereumSystem,
-        FixedU128, GetAggregateMessageOrigin, Keccak256, MessageQueue,
-        OutboundMessageCommitmentRecorder, Runtime, RuntimeEvent, SnowbridgeFeesAccount,
-        TanssiAggregateMessageOrigin, TokenLocationReanchored, TransactionByteFee, TreasuryAccount,
-        WeightToFee, UNITS,
+        ExternalValidatorSlashes, ExternalValidatorsRewards, FixedU128, GetAggregateMessageOrigin,
+        Keccak256, MessageQueue, OutboundMessageCommitmentRecorder, Runtime, RuntimeEvent,
+        RuntimeOrigin, SnowbridgeFeesAccount, TanssiAggregateMessageOrigin,
+        TokenLocationReanchored, TransactionByteFee, TreasuryAccount, WeightToFee, UNITS,
     },
+    alloc::vec::Vec,
     frame_support::{
         traits::{EitherOf, MapSuccess, PalletInfoAccess},
         weights::ConstantMultiplier,
     },
+    pallet_external_validator_slashes::SlashingModeOption,
+    pallet_outbound_message_commitment_recorder::{CommittedMessage, MessageQueueVersion},
     pallet_xcm::EnsureXcm,
     snowbridge_beacon_primitives::ForkVersions,
     snowbridge_core::{gwei, meth, PricingParameters, Rewards},
@@ -75,7 +78,10 @@ use {
     snowbridge_pallet_outbound_queue_v2::OnNewCommitment as OnNewCommitmentV2,
     sp_core::{ConstU32, ConstU8, H160, H256},
     tanssi_runtime_common::relay::v1::{EthTokensLocalProcessor, RewardThroughFeesAccount},
-    tp_bridge::{DoNothingConvertMessage, DoNothingRouter, EthereumSystemHandler},
+    tp_bridge::{
+        symbiotic_message_processor::{GovernanceCommandKind, SymbioticGovernanceHandler},
+        DoNothingConvertMessage, DoNothingRouter, EthereumSystemHandler,
+    },
     xcm::latest::{Asset, XcmContext},
     xcm_executor::traits::TransactAsset,
 };
@@ -89,6 +95,47 @@ parameter_types! {
     pub SetExternalValidatorsWeight: Weight = weights::pallet_external_validators::SubstrateWeight::<Runtime>::set_external_validators();
 }
 
+// Governance commands that the Ethereum middleware can send through the primary governance
+// channel. Each kind of command must be enabled separately.
+parameter_types! {
+    pub storage EthereumGovernanceWhitelistEnabled: bool = false;
+    pub storage EthereumGovernanceForceEraEnabled: bool = false;
+    pub storage EthereumGovernanceSlashingModeEnabled: bool = false;
+    pub storage EthereumGovernanceRewardsReportingEnabled: bool = false;
+    pub storage EthereumGovernanceCancelDeferredSlashEnabled: bool = false;
+}
+
+pub struct EthereumGovernanceHandler;
+
+impl SymbioticGovernanceHandler for EthereumGovernanceHandler {
+    fn is_enabled(kind: GovernanceCommandKind) -> bool {
+        match kind {
+            GovernanceCommandKind::Whitelist => EthereumGovernanceWhitelistEnabled::get(),
+            GovernanceCommandKind::ForceEra => EthereumGovernanceForceEraEnabled::get(),
+            GovernanceCommandKind::SlashingMode => EthereumGovernanceSlashingModeEnabled::get(),
+            GovernanceCommandKind::RewardsReporting => {
+                EthereumGovernanceRewardsReportingEnabled::get()
+            }
+            GovernanceCommandKind::CancelDeferredSlash => {
+                EthereumGovernanceCancelDeferredSlashEnabled::get()
+            }
+        }
+    }
+
+    fn set_slashing_mode(mode: SlashingModeOption) -> DispatchResult {
+        ExternalValidatorSlashes::set_slashing_mode(RuntimeOrigin::root(), mode)
+    }
+
+    fn set_rewards_reporting_paused(paused: bool) -> DispatchResult {
+        ExternalValidatorsRewards::set_rewards_reporting_paused_inner(paused);
+        Ok(())
+    }
+
+    fn cancel_deferred_slash(era: u32, slash_indices: Vec<u32>) -> DispatchResult {
+        ExternalValidatorSlashes::cancel_deferred_slash(RuntimeOrigin::root(), era, slash_indices)
+    }
+}
+
 parameter_types! {
     pub Parameters: PricingParameters<u128> = PricingParameters {
         exchange_rate: FixedU128::from_rational(1, 400),
@@ -102,16 +149,59 @@ pub struct CommitmentRecorder;
 
 impl OnNewCommitment for CommitmentRecorder {
     fn on_new_commitment(commitment: H256) {
-        OutboundMessageCommitmentRecorder::record_commitment_root(commitment);
+        // Called from the commit of the queue on finalize, while it still holds the messages of
+        // the block. Its weight is reserved by the recorder on initialize
+        let messages = snowbridge_pallet_outbound_queue::Messages::<Runtime>::get();
+        let leaves = snowbridge_pallet_outbound_queue::MessageLeaves::<Runtime>::get();
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V1,
+            commitment,
+            messages
+                .into_iter()
+                .zip(leaves)
+                .map(|(message, leaf)| CommittedMessage {
+                    message_id: message.id,
+                    channel_id: Some(message.channel_id.into()),
+                    nonce: message.nonce,
+                    leaf,
+                }),
+        );
     }
 }
 
 impl OnNewCommitmentV2 for CommitmentRecorder {
     fn on_new_commitment(commitment: H256) {
-        OutboundMessageCommitmentRecorder::record_commitment_root(commitment);
+        // Called from the commit of the queue on finalize, while it still holds the messages of
+        // the block. Its weight is reserved by the recorder on initialize
+        let messages = snowbridge_pallet_outbound_queue_v2::Messages::<Runtime>::get();
+        let leaves = snowbridge_pallet_outbound_queue_v2::MessageLeaves::<Runtime>::get();
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V2,
+            commitment,
+            messages
+                .into_iter()
+                .zip(leaves)
+                .map(|(message, leaf)| CommittedMessage {
+                    message_id: message.topic,
+                    channel_id: None,
+                    nonce: message.nonce,
+                    leaf,
+                }),
+        );
     }
 }
-impl pallet_outbound_message_commitment_recorder::Config for Runtime {}
+
+parameter_types! {
+    pub const OutboundMessageTrackingPeriod: crate::BlockNumber = 7 * crate::DAYS;
+}
+
+impl pallet_outbound_message_commitment_recorder::Config for Runtime {
+    type Hashing = Keccak256;
+    type MaxMessagesPerBlock = ConstU32<32>;
+    type MessageTrackingPeriod = OutboundMessageTrackingPeriod;
+    type WeightInfo =
+        weights::pallet_outbound_message_commitment_recorder::SubstrateWeight<Runtime>;
+}
 
 // https://github.com/paritytech/polkadot-sdk/blob/2ae79be8e028a995b850621ee55f46c041eceefe/cumulus/parachains/runtimes/bridge-hubs/bridge-hub-westend/src/bridge_to_ethereum_config.rs#L105
 impl snowbridge_pallet_outbound_queue::Config for Runtime {
@@ -390,10 +480,76 @@ impl pallet_ethereum_token_transfers::Config for Runtime {
     type EthereumLocation = EthereumLocation;
     type LocationHashOf = tp_bridge::TanssiAgentIdOf;
     #[cfg(feature = "runtime-benchmarks")]
-    type BenchmarkHelper = tp_bridge::EthereumTokenTransfersBenchHelper<Runtime>;
+    type BenchmarkHelper = EthereumTokenTransfersBenchHelper;
     type WeightInfo = crate::weights::pallet_ethereum_token_transfers::SubstrateWeight<Runtime>;
     type TipHandler = EthereumTipForwarder<Runtime>;
     type PalletOrigin = Self::RuntimeOrigin;
+    type BridgeGuard = crate::BridgeGuard;
+    type ForeignAssets = crate::ForeignAssets;
+    type ForeignAssetIdFromLocation = crate::ForeignAssetsCreator;
+}
+
+/// Benchmark helper for EthereumTokenTransfers that also registers foreign tokens.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct EthereumTokenTransfersBenchHelper;
+
+#[cfg(feature = "runtime-benchmarks")]
+impl tp_bridge::TokenChannelSetterBenchmarkHelperTrait for EthereumTokenTransfersBenchHelper {
+    fn set_up_token(location: xcm::latest::Location, token_id: snowbridge_core::TokenId) {
+        tp_bridge::EthereumTokenTransfersBenchHelper::<Runtime>::set_up_token(location, token_id)
+    }
+
+    fn set_up_channel(
+        channel_id: snowbridge_core::ChannelId,
+        para_id: snowbridge_core::ParaId,
+        agent_id: snowbridge_core::AgentId,
+    ) {
+        tp_bridge::EthereumTokenTransfersBenchHelper::<Runtime>::set_up_channel(
+            channel_id, para_id, agent_id,
+        )
+    }
+
+    fn set_up_foreign_token(location: xcm::latest::Location) {
+        if pallet_foreign_asset_creator::ForeignAssetToAssetId::<Runtime>::get(&location).is_none()
+        {
+            let _ = crate::ForeignAssetsCreator::create_foreign_asset(
+                RuntimeOrigin::root(),
+                location,
+                42,
+                crate::AccountId::from([0u8; 32]),
+                true,
+                1,
+            );
+        }
+    }
+}
+
+parameter_types! {
+    /// Large transfers to Ethereum are held for a day, giving governance time to cancel them.
+    pub const LargeTransferDelay: crate::BlockNumber = crate::DAYS;
+}
+
+impl pallet_bridge_guard::Config for Runtime {
+    type GuardOrigin = EnsureRoot<AccountId>;
+    type LargeTransferDelay = LargeTransferDelay;
+    type MaxReleasesPerBlock = ConstU32<10>;
+    type DelayedTransferExecutor = crate::EthereumTokenTransfers;
+    type WeightInfo = crate::weights::pallet_bridge_guard::SubstrateWeight<Runtime>;
+}
+
+impl pallet_bridge_asset_recovery::Config for Runtime {
+    type AssetTrap = crate::XcmPallet;
+    type AssetTransactor = <xcm_config::XcmConfig as xcm_executor::Config>::AssetTransactor;
+    type XcmExecutor = xcm_executor::XcmExecutor<xcm_config::XcmConfig>;
+    type Weigher = <xcm_config::XcmConfig as xcm_executor::Config>::Weigher;
+    // Same claimer as inbound V2 messages without one
+    type XcmRouter = xcm_config::XcmRouter;
+    type FallbackClaimer = TreasuryAccount;
+    type WeightInfo = crate::weights::pallet_bridge_asset_recovery::SubstrateWeight<Runtime>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type DeliveryHelper = benchmark_helper::ToContainerDeliveryHelper;
+    #[cfg(feature = "runtime-benchmarks")]
+    type ReachableDestination = benchmark_helper::ContainerLocation;
 }
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -432,6 +588,24 @@ mod benchmark_helper {
         },
     };
 
+    frame_support::parameter_types! {
+        pub ExistentialDepositAsset: Option<Asset> = Some((
+            crate::xcm_config::TokenLocation::get(),
+            crate::ExistentialDeposit::get()
+        ).into());
+        pub const ContainerParaId: crate::ParaId = crate::ParaId::new(2000);
+        pub ContainerLocation: Location = Location::new(0, [Parachain(ContainerParaId::get().into())]);
+    }
+
+    /// Makes `ContainerLocation` reachable, as the destination of container token transfers.
+    pub type ToContainerDeliveryHelper = runtime_common::xcm_sender::ToParachainDeliveryHelper<
+        crate::xcm_config::XcmConfig,
+        ExistentialDepositAsset,
+        crate::xcm_config::PriceForChildParachainDelivery,
+        ContainerParaId,
+        crate::Dmp,
+    >;
+
     pub struct EthSystemBenchHelper;
 
     impl snowbridge_pallet_system::BenchmarkHelper<RuntimeOrigin> for EthSystemBenchHelper {
@@ -740,7 +914,7 @@ impl snowbridge_pallet_inbound_queue::Config for Runtime {
     type AssetTransactor = AssetTransactor;
     #[cfg(not(feature = "runtime-benchmarks"))]
     type MessageProcessor = (
-        SymbioticMessageProcessor<Self>,
+        SymbioticMessageProcessor<Self, EthereumGovernanceHandler>,
         GenericTokenInboundMessageProcessor<
             Self,
             (NativeTokensProcessor, NativeContainerProcessor),
@@ -775,6 +949,7 @@ pub type SymbioticInboundMessageProcessorV2 = SymbioticMessageProcessorV2<
     <xcm_config::XcmConfig as xcm_executor::Config>::Weigher,
     MaxXcmWeight,
     SetExternalValidatorsWeight,
+    EthereumGovernanceHandler,
 >;
 
 impl snowbridge_pallet_inbound_queue_v2::Config for Runtime {
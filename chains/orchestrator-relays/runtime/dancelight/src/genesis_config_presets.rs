@@ -321,6 +321,7 @@ fn dancelight_testnet_genesis(
         crate::StakingAccount::get(),
         crate::DancelightBondAccount::get(),
         crate::PendingRewardsAccount::get(),
+        crate::InvulnerablesPendingRewardsAccount::get(),
         crate::EthereumSovereignAccount::get(),
         crate::SnowbridgeFeesAccount::get(),
         crate::TreasuryPalletId::get().into_account_truncating(),
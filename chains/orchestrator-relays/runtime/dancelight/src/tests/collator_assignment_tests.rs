@@ -234,6 +234,37 @@ fn test_author_collation_aura_change_of_authorities_on_session() {
         });
 }
 
+#[test]
+fn test_invulnerable_expiry_uses_planned_session_index() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+        ])
+        .with_collators(vec![
+            (AccountId::from(ALICE), 210 * UNIT),
+            (AccountId::from(BOB), 100 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            assert_ok!(TanssiInvulnerables::set_invulnerable_expiry(
+                root_origin(),
+                AccountId::from(BOB),
+                Some(2)
+            ));
+
+            // Session 1 plans session 2, so Bob is removed before its collators are selected,
+            // same as in the parachain runtimes
+            run_to_session(1u32);
+
+            assert_eq!(
+                TanssiInvulnerables::invulnerables().to_vec(),
+                vec![AccountId::from(ALICE)]
+            );
+        });
+}
+
 #[test]
 fn test_collators_per_container() {
     ExtBuilder::default()
@@ -21,9 +21,9 @@ use {
         bridge_to_ethereum_config::{EthereumGatewayAddress, InboundQueuePalletInstance},
         filter_events,
         tests::common::*,
-        xcm_config::UniversalLocation,
-        EthereumInboundQueue, EthereumLocation, EthereumSystem, EthereumTokenTransfers, Paras,
-        RuntimeEvent, SnowbridgeFeesAccount, XcmPallet,
+        xcm_config::{LocationConverter, UniversalLocation},
+        BridgeAssetRecovery, EthereumInboundQueue, EthereumLocation, EthereumSystem,
+        EthereumTokenTransfers, Paras, RuntimeEvent, SnowbridgeFeesAccount, XcmPallet,
     },
     alloc::vec,
     alloy_sol_types::SolEvent,
@@ -40,7 +40,11 @@ use {
     snowbridge_inbound_queue_primitives::{EventProof, Log},
     sp_core::{H160, H256},
     sp_runtime::traits::MaybeEquivalence,
-    xcm::latest::{prelude::*, Asset as XcmAsset, Junctions::*, Location},
+    xcm::{
+        latest::{prelude::*, Asset as XcmAsset, Junctions::*, Location},
+        VersionedAsset,
+    },
+    xcm_executor::traits::ConvertLocation,
 };
 
 #[test]
@@ -333,6 +337,148 @@ fn receive_container_native_tokens_from_eth_doesnt_error_if_error_sending_xcm()
         });
 }
 
+#[test]
+fn receive_container_native_tokens_keeps_transfer_if_error_sending_xcm() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 100_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+            (SnowbridgeFeesAccount::get(), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            let relayer =
+                <Runtime as frame_system::Config>::RuntimeOrigin::signed(AccountId::from(ALICE));
+
+            let channel_id: ChannelId = ChannelId::new(hex!(
+                "0000000000000000000000000000000000000000000000000000000000000004"
+            ));
+            let agent_id = AgentId::from(hex!(
+                "0000000000000000000000000000000000000000000000000000000000000005"
+            ));
+            let para_id: ParaId = 2000u32.into();
+
+            let amount_to_transfer = 100_000_000;
+            let fee = 1_500_000_000_000_000;
+            let container_fee = 500_000_000_000_000;
+
+            let container_para_id = 2001u32;
+
+            // We don't set the current head on purpose, so the XCM sending will fail
+            assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+                root_origin(),
+                channel_id,
+                agent_id,
+                para_id
+            ));
+
+            let token_location =
+                Location::new(0, [Parachain(container_para_id), PalletInstance(10)]);
+
+            assert_ok!(EthereumSystem::register_token(
+                root_origin(),
+                Box::new(token_location.clone().into()),
+                snowbridge_core::AssetMetadata {
+                    name: "para".as_bytes().to_vec().try_into().unwrap(),
+                    symbol: "para".as_bytes().to_vec().try_into().unwrap(),
+                    decimals: 12,
+                }
+            ));
+
+            let token_location_reanchored = token_location
+                .clone()
+                .reanchored(&EthereumLocation::get(), &UniversalLocation::get())
+                .expect("unable to reanchor token");
+
+            let token_id = EthereumSystem::convert_back(&token_location_reanchored).unwrap();
+            let beneficiary = AccountId::from([5u8; 32]);
+            let payload = VersionedXcmMessage::V1(MessageV1 {
+                chain_id: 1,
+                command: Command::SendNativeToken {
+                    token_id,
+                    destination: Destination::ForeignAccountId32 {
+                        para_id: container_para_id,
+                        id: beneficiary.clone().into(),
+                        fee: container_fee,
+                    },
+                    amount: amount_to_transfer,
+                    fee,
+                },
+            });
+
+            let event = OutboundMessageAccepted {
+                channel_id: <[u8; 32]>::from(channel_id).into(),
+                nonce: 1,
+                message_id: Default::default(),
+                payload: payload.encode(),
+            };
+
+            let message = EventProof {
+                event_log: Log {
+                    address:
+                        <Runtime as snowbridge_pallet_inbound_queue::Config>::GatewayAddress::get(),
+                    topics: event
+                        .encode_topics()
+                        .into_iter()
+                        .map(|word| H256::from(word.0 .0))
+                        .collect(),
+                    data: event.encode_data(),
+                },
+                proof: mock_snowbridge_message_proof(),
+            };
+
+            let container_location = Location::new(0, [Parachain(container_para_id)]);
+            let container_sovereign = LocationConverter::convert_location(&container_location)
+                .expect("container has a sovereign account");
+
+            // Goes through the generic token processor, which dispatches to the container one
+            assert_ok!(EthereumInboundQueue::submit(relayer, message.clone()));
+
+            assert_eq!(
+                filter_events!(RuntimeEvent::XcmPallet(pallet_xcm::Event::Sent { .. })).count(),
+                0,
+                "XCM Sent event should NOT be emitted!"
+            );
+
+            // The whole transfer is kept for the beneficiary and the fee is not paid
+            let transfers = BridgeAssetRecovery::failed_transfers(&beneficiary);
+            assert_eq!(transfers.len(), 1);
+            let (transfer_id, transfer) = transfers[0].clone();
+            assert_eq!(transfer.destination, container_location.clone().into());
+            assert_eq!(
+                transfer.fee,
+                VersionedAsset::from(XcmAsset::from((Location::here(), container_fee)))
+            );
+            assert_eq!(Balances::free_balance(&container_sovereign), 0);
+            assert!(BridgeAssetRecovery::trapped_assets(&beneficiary).is_empty());
+
+            // Once the container chain is reachable the transfer can be sent again
+            assert_ok!(XcmPallet::force_default_xcm_version(
+                root_origin(),
+                Some(5u32)
+            ));
+            assert_ok!(Paras::force_set_current_head(
+                root_origin(),
+                container_para_id.into(),
+                HeadData::from(vec![1u8, 2u8, 3u8])
+            ));
+            assert_ok!(BridgeAssetRecovery::retry_failed_transfer(
+                origin_of(beneficiary.clone()),
+                transfer_id
+            ));
+
+            assert_eq!(
+                filter_events!(RuntimeEvent::BridgeAssetRecovery(
+                    pallet_bridge_asset_recovery::Event::FailedTransferSent { .. }
+                ))
+                .count(),
+                1
+            );
+            assert_eq!(Balances::free_balance(&container_sovereign), container_fee);
+            assert!(BridgeAssetRecovery::failed_transfers(&beneficiary).is_empty());
+        });
+}
+
 #[test]
 fn receive_container_native_tokens_fails_if_account_id_32() {
     ExtBuilder::default()
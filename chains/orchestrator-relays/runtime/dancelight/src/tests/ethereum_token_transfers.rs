@@ -19,9 +19,10 @@
 use {
     crate::{
         bridge_to_ethereum_config::EthereumGatewayAddress, filter_events, tests::common::*,
-        Balances, EthereumInboundQueue, EthereumLocation, EthereumSovereignAccount, EthereumSystem,
-        EthereumTokenTransfers, ForeignAssets, ForeignAssetsCreator, RuntimeEvent,
-        SnowbridgeFeesAccount, TokenLocationReanchored, XcmPallet,
+        Balances, BridgeAssetRecovery, BridgeGuard, EthereumInboundQueue, EthereumLocation,
+        EthereumSovereignAccount, EthereumSystem, EthereumTokenTransfers, ForeignAssets,
+        ForeignAssetsCreator, Runtime, RuntimeEvent, SnowbridgeFeesAccount,
+        TokenLocationReanchored, XcmPallet,
     },
     alloc::vec,
     alloy_sol_types::SolEvent,
@@ -32,6 +33,9 @@ use {
         traits::{fungible::Inspect, fungibles::Mutate},
     },
     hex_literal::hex,
+    pallet_ethereum_token_transfers::TransferredToken,
+    pallet_ethereum_token_transfers_runtime_api::runtime_decl_for_ethereum_token_transfers_api::EthereumTokenTransfersApi,
+    pallet_outbound_message_commitment_recorder_runtime_api::runtime_decl_for_outbound_message_tracking_api::OutboundMessageTrackingApi,
     pallet_xcm::ExecutionError,
     parity_scale_codec::Encode,
     snowbridge_core::{reward::MessageId, TokenIdOf},
@@ -286,6 +290,280 @@ fn test_transfer_native_token() {
         });
 }
 
+fn set_up_native_token_transfers() -> H256 {
+    let token_location: VersionedLocation = Location::here().into();
+
+    assert_ok!(EthereumSystem::register_token(
+        root_origin(),
+        Box::new(token_location),
+        snowbridge_core::AssetMetadata {
+            name: "dance".as_bytes().to_vec().try_into().unwrap(),
+            symbol: "dance".as_bytes().to_vec().try_into().unwrap(),
+            decimals: 12,
+        }
+    ));
+
+    assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+        root_origin(),
+        ChannelId::new([5u8; 32]),
+        AgentId::from_low_u64_be(10),
+        2000u32.into()
+    ));
+
+    EthereumSystem::convert_back(&TokenLocationReanchored::get()).unwrap()
+}
+
+#[test]
+fn test_transfer_native_token_fails_while_bridge_paused() {
+    ExtBuilder::default()
+        .with_balances(vec![(AccountId::from(ALICE), 210_000 * UNIT)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            set_up_native_token_transfers();
+
+            assert_ok!(BridgeGuard::pause_bridge(root_origin()));
+
+            assert_noop!(
+                EthereumTokenTransfers::transfer_native_token(
+                    origin_of(AccountId::from(ALICE)),
+                    100 * UNIT,
+                    H160::random()
+                ),
+                pallet_bridge_guard::Error::<Runtime>::BridgePaused
+            );
+
+            assert_ok!(BridgeGuard::resume_bridge(root_origin()));
+
+            assert_ok!(EthereumTokenTransfers::transfer_native_token(
+                origin_of(AccountId::from(ALICE)),
+                100 * UNIT,
+                H160::random()
+            ));
+        });
+}
+
+#[test]
+fn test_large_native_token_transfer_is_delayed_and_can_be_cancelled() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (AccountId::from(ALICE), 210_000 * UNIT),
+            (EthereumSovereignAccount::get(), 1_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            let token_id = set_up_native_token_transfers();
+
+            assert_ok!(BridgeGuard::set_token_limits(
+                root_origin(),
+                token_id,
+                Some(pallet_bridge_guard::TransferLimits {
+                    inbound_limit: None,
+                    outbound_limit: Some(1_000 * UNIT),
+                    period: 100,
+                    large_transfer_threshold: Some(50 * UNIT),
+                    max_net_inbound: None,
+                })
+            ));
+
+            let alice_balance_before = Balances::free_balance(AccountId::from(ALICE));
+
+            assert_ok!(EthereumTokenTransfers::transfer_native_token(
+                origin_of(AccountId::from(ALICE)),
+                100 * UNIT,
+                H160::random()
+            ));
+
+            // The amount is taken but nothing is sent yet
+            assert_eq!(
+                filter_events!(RuntimeEvent::EthereumOutboundQueue(
+                    snowbridge_pallet_outbound_queue::Event::MessageQueued { .. },
+                ))
+                .count(),
+                0
+            );
+            assert_eq!(
+                filter_events!(RuntimeEvent::EthereumTokenTransfers(
+                    pallet_ethereum_token_transfers::Event::NativeTokenTransferDelayed { .. },
+                ))
+                .count(),
+                1
+            );
+            assert_eq!(
+                Balances::free_balance(EthereumSovereignAccount::get()),
+                1_100 * UNIT
+            );
+            assert_eq!(
+                BridgeGuard::current_usage(token_id, tp_bridge::TransferDirection::Outbound),
+                100 * UNIT
+            );
+
+            assert_ok!(BridgeGuard::cancel_delayed_transfer(root_origin(), 0));
+
+            // The amount is refunded, the fee is not
+            let fee = Balances::free_balance(SnowbridgeFeesAccount::get());
+            assert_eq!(
+                Balances::free_balance(AccountId::from(ALICE)),
+                alice_balance_before - fee
+            );
+            assert_eq!(
+                BridgeGuard::current_usage(token_id, tp_bridge::TransferDirection::Outbound),
+                0
+            );
+        });
+}
+
+#[test]
+fn test_native_token_transfer_can_be_tracked_by_message_id() {
+    ExtBuilder::default()
+        .with_balances(vec![(AccountId::from(ALICE), 210_000 * UNIT)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            set_up_native_token_transfers();
+
+            assert_ok!(EthereumTokenTransfers::transfer_native_token(
+                origin_of(AccountId::from(ALICE)),
+                100 * UNIT,
+                H160::random()
+            ));
+
+            let message_id = System::events()
+                .into_iter()
+                .find_map(|r| match r.event {
+                    RuntimeEvent::EthereumTokenTransfers(
+                        pallet_ethereum_token_transfers::Event::NativeTokenTransferred {
+                            message_id,
+                            ..
+                        },
+                    ) => Some(message_id),
+                    _ => None,
+                })
+                .expect("NativeTokenTransferred event should be emitted!");
+
+            // The message is only known once the outbound queue accepts it
+            assert!(Runtime::outbound_message_status(message_id).is_none());
+
+            run_to_block(4);
+            let status =
+                Runtime::outbound_message_status(message_id).expect("message should be tracked");
+            assert_eq!(status.block_number, 3);
+            assert_eq!(status.channel_id, Some(H256([5u8; 32])));
+            assert_eq!(status.nonce, 1);
+            assert_eq!(status.leaf_index, 0);
+            assert_eq!(status.number_of_leaves, 1);
+            assert_eq!(status.commitment_root, Some(status.leaf));
+        });
+}
+
+#[test]
+fn test_quote_transfer_matches_native_token_transfer_fee() {
+    ExtBuilder::default()
+        .with_balances(vec![(AccountId::from(ALICE), 210_000 * UNIT)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            set_up_native_token_transfers();
+            let amount = 100 * UNIT;
+
+            let quote =
+                Runtime::quote_transfer(TransferredToken::Native, amount, H160::random()).unwrap();
+            assert_eq!(quote.total_fee, quote.local_fee + quote.remote_fee);
+            assert!(!quote.delayed);
+
+            let alice_balance_before = Balances::free_balance(AccountId::from(ALICE));
+            assert_ok!(EthereumTokenTransfers::transfer_native_token(
+                origin_of(AccountId::from(ALICE)),
+                amount,
+                H160::random()
+            ));
+            assert_eq!(
+                Balances::free_balance(AccountId::from(ALICE)),
+                alice_balance_before - quote.total_cost
+            );
+        });
+}
+
+#[test]
+fn test_transfer_foreign_token_burns_erc20_and_sends_message() {
+    ExtBuilder::default()
+        .with_balances(vec![(AccountId::from(ALICE), 210_000 * UNIT)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            set_up_native_token_transfers();
+
+            let token_address = H160(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+            let erc20_asset_location = Location::new(
+                1,
+                [
+                    GlobalConsensus(EthereumNetwork::get()),
+                    AccountKey20 {
+                        network: Some(EthereumNetwork::get()),
+                        key: token_address.into(),
+                    },
+                ],
+            );
+            let asset_id = 42u16;
+            assert_ok!(ForeignAssetsCreator::create_foreign_asset(
+                root_origin(),
+                erc20_asset_location,
+                asset_id,
+                AccountId::from(ALICE),
+                true,
+                1
+            ));
+            assert_ok!(ForeignAssets::mint_into(
+                asset_id,
+                &AccountId::from(ALICE),
+                1_000
+            ));
+
+            let recipient = H160::random();
+            let quote =
+                Runtime::quote_transfer(TransferredToken::Foreign(token_address), 400, recipient)
+                    .unwrap();
+            // Only the fees are paid in the native token
+            assert_eq!(quote.total_cost, quote.total_fee);
+
+            let alice_balance_before = Balances::free_balance(AccountId::from(ALICE));
+            assert_ok!(EthereumTokenTransfers::transfer_foreign_token(
+                origin_of(AccountId::from(ALICE)),
+                token_address,
+                400,
+                recipient
+            ));
+            assert_eq!(
+                Balances::free_balance(AccountId::from(ALICE)),
+                alice_balance_before - quote.total_cost
+            );
+
+            assert_eq!(
+                ForeignAssets::balance(asset_id, AccountId::from(ALICE)),
+                600
+            );
+            assert_eq!(ForeignAssets::total_supply(asset_id), 600);
+
+            let events = filter_events!(RuntimeEvent::EthereumTokenTransfers(
+                pallet_ethereum_token_transfers::Event::ForeignTokenTransferred { .. },
+            ))
+            .count();
+            assert_eq!(events, 1);
+
+            // Tokens not registered in ForeignAssets can't be sent
+            assert_noop!(
+                EthereumTokenTransfers::transfer_foreign_token(
+                    origin_of(AccountId::from(ALICE)),
+                    H160::random(),
+                    400,
+                    recipient
+                ),
+                pallet_ethereum_token_transfers::Error::<Runtime>::UnknownForeignToken
+            );
+        });
+}
+
 #[test]
 fn test_transfer_native_token_fails_if_channel_info_not_set() {
     ExtBuilder::default()
@@ -853,14 +1131,24 @@ fn fail_receiving_native_tokens_with_destination_below_existential_deposit() {
 
             assert_ok!(EthereumInboundQueue::submit(relayer, message.clone()));
 
-            // Sovereign account balance should not change
+            // Tokens are withdrawn from the sovereign account and trapped
             assert_eq!(
                 Balances::free_balance(EthereumSovereignAccount::get()),
-                sovereign_balance_before
+                sovereign_balance_before - amount_to_transfer
             );
 
             // Random account balance should not change
-            assert_eq!(Balances::free_balance(random_account), 0u128);
+            assert_eq!(Balances::free_balance(random_account.clone()), 0u128);
+
+            // The destination account can claim the trapped tokens
+            let traps = BridgeAssetRecovery::trapped_assets(&random_account);
+            assert_eq!(traps.len(), 1);
+            let (_, record) = &traps[0];
+            assert_eq!(record.message_id, H256::zero());
+            assert_eq!(
+                record.assets,
+                VersionedAssets::from(XcmAssets::from((Location::here(), amount_to_transfer)))
+            );
         });
 }
 
@@ -1787,6 +2075,112 @@ fn send_eth_native_token_works() {
         })
 }
 
+#[test]
+fn send_eth_native_token_fails_while_bridge_paused() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (EthereumSovereignAccount::get(), 100_000 * UNIT),
+            (SnowbridgeFeesAccount::get(), 100_000 * UNIT),
+            (AccountId::from(ALICE), 100_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            let channel_id: ChannelId = ChannelId::new(hex!(
+                "00000000000000000000006e61746976655f746f6b656e5f7472616e73666572"
+            ));
+            let amount_to_transfer = 10_000u128;
+
+            assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+                root_origin(),
+                channel_id,
+                AgentId::from_low_u64_be(10),
+                2000u32.into()
+            ));
+
+            let token_address = H160(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+            let erc20_asset_location = Location {
+                parents: 1,
+                interior: X2([
+                    GlobalConsensus(EthereumNetwork::get()),
+                    AccountKey20 {
+                        network: Some(EthereumNetwork::get()),
+                        key: token_address.into(),
+                    },
+                ]
+                .into()),
+            };
+
+            let asset_id = 42u16;
+            assert_ok!(ForeignAssetsCreator::create_foreign_asset(
+                root_origin(),
+                erc20_asset_location.clone(),
+                asset_id,
+                AccountId::from(ALICE),
+                true,
+                1
+            ));
+            ForeignAssets::mint_into(asset_id, &AccountId::from(BOB), amount_to_transfer)
+                .expect("to mint amount");
+
+            let beneficiary_location = Location {
+                parents: 0,
+                interior: X1([AccountKey20 {
+                    network: Some(EthereumNetwork::get()),
+                    key: H160(hex!("0123456789abcdef0123456789abcdef01234567")).into(),
+                }]
+                .into()),
+            };
+            let eth_asset = AssetId(erc20_asset_location.clone())
+                .into_asset(Fungibility::Fungible(amount_to_transfer));
+
+            assert_ok!(BridgeGuard::pause_bridge(root_origin()));
+
+            // The exporter refuses the transfer, so nothing leaves the chain
+            assert!(XcmPallet::transfer_assets(
+                RuntimeOrigin::signed(AccountId::from(BOB)),
+                Box::new(EthereumLocation::get().into()),
+                Box::new(beneficiary_location.clone().into()),
+                Box::new(vec![eth_asset.clone()].into()),
+                0u32,
+                Unlimited,
+            )
+            .is_err());
+            assert_eq!(
+                ForeignAssets::balance(asset_id, AccountId::from(BOB)),
+                amount_to_transfer
+            );
+            assert_eq!(
+                filter_events!(RuntimeEvent::EthereumOutboundQueue(
+                    snowbridge_pallet_outbound_queue::Event::MessageQueued { .. },
+                ))
+                .count(),
+                0,
+                "MessageQueued event should NOT be emitted!"
+            );
+
+            assert_ok!(BridgeGuard::resume_bridge(root_origin()));
+
+            assert_ok!(XcmPallet::transfer_assets(
+                RuntimeOrigin::signed(AccountId::from(BOB)),
+                Box::new(EthereumLocation::get().into()),
+                Box::new(beneficiary_location.into()),
+                Box::new(vec![eth_asset].into()),
+                0u32,
+                Unlimited,
+            ));
+            assert_eq!(ForeignAssets::balance(asset_id, AccountId::from(BOB)), 0);
+            assert_eq!(
+                filter_events!(RuntimeEvent::EthereumOutboundQueue(
+                    snowbridge_pallet_outbound_queue::Event::MessageQueued { .. },
+                ))
+                .count(),
+                1,
+                "MessageQueued event should be emitted!"
+            );
+        })
+}
+
 #[test]
 fn cant_send_eth_unknown_token() {
     ExtBuilder::default()
@@ -3180,7 +3574,14 @@ fn receive_erc20_tokens_does_not_fail_if_not_sufficient_and_random_address() {
             }]
             .into();
 
-            let ethereum_location = EthereumLocation::get();
+            // The recipient is set as the asset claimer
+            let recipient_location = Location::new(
+                0,
+                [AccountId32 {
+                    network: None,
+                    id: recipient.into(),
+                }],
+            );
 
             // Check that assets are trapped
             let assets_trapped_event = System::events()
@@ -3191,7 +3592,7 @@ fn receive_erc20_tokens_does_not_fail_if_not_sufficient_and_random_address() {
                         origin,
                         assets,
                     }) => {
-                        *origin == ethereum_location
+                        *origin == recipient_location
                             && *assets == VersionedAssets::V5(trapped_assets.clone())
                     }
                     _ => false,
@@ -3202,6 +3603,11 @@ fn receive_erc20_tokens_does_not_fail_if_not_sufficient_and_random_address() {
                 assets_trapped_event, 1,
                 "AssetsTrapped event should be emitted!"
             );
+
+            // And indexed so the recipient can claim them
+            let traps = BridgeAssetRecovery::trapped_assets(&AccountId::from(recipient.0));
+            assert_eq!(traps.len(), 1);
+            assert_eq!(traps[0].1.assets, VersionedAssets::V5(trapped_assets));
         });
 }
 
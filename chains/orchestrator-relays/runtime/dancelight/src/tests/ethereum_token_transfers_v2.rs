@@ -21,8 +21,8 @@ use {
         filter_events,
         tests::common::*,
         xcm_config::{LocationConverter, MinV2Reward},
-        Balances, EthereumLocation, EthereumSovereignAccount, EthereumSystem, ForeignAssets,
-        ForeignAssetsCreator, RuntimeEvent, SnowbridgeFeesAccount, XcmPallet,
+        Balances, BridgeGuard, EthereumLocation, EthereumSovereignAccount, EthereumSystem,
+        ForeignAssets, ForeignAssetsCreator, RuntimeEvent, SnowbridgeFeesAccount, XcmPallet,
     },
     alloc::vec,
     dancelight_runtime_constants::snowbridge::EthereumNetwork,
@@ -166,6 +166,117 @@ fn send_eth_native_token_works_v2() {
         })
 }
 
+#[test]
+fn send_eth_native_token_fails_while_bridge_paused_v2() {
+    ExtBuilder::default()
+        .with_balances(vec![
+            (EthereumSovereignAccount::get(), 100_000 * UNIT),
+            (SnowbridgeFeesAccount::get(), 100_000 * UNIT),
+            (AccountId::from(ALICE), 100_000 * UNIT),
+            (AccountId::from(BOB), 100_000 * UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            let amount_to_transfer = 10_000u128;
+
+            let token_address = H160(hex!("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"));
+            let erc20_asset_location = Location {
+                parents: 1,
+                interior: X2([
+                    GlobalConsensus(EthereumNetwork::get()),
+                    AccountKey20 {
+                        network: Some(EthereumNetwork::get()),
+                        key: token_address.into(),
+                    },
+                ]
+                .into()),
+            };
+
+            let asset_id = 42u16;
+            assert_ok!(ForeignAssetsCreator::create_foreign_asset(
+                root_origin(),
+                erc20_asset_location.clone(),
+                asset_id,
+                AccountId::from(ALICE),
+                true,
+                1
+            ));
+            ForeignAssets::mint_into(asset_id, &AccountId::from(BOB), amount_to_transfer)
+                .expect("to mint amount");
+
+            let beneficiary_location = Location {
+                parents: 0,
+                interior: X1([AccountKey20 {
+                    network: Some(EthereumNetwork::get()),
+                    key: H160(hex!("0123456789abcdef0123456789abcdef01234567")).into(),
+                }]
+                .into()),
+            };
+
+            let eth_asset = AssetId(erc20_asset_location.clone())
+                .into_asset(Fungibility::Fungible(amount_to_transfer));
+            let fee_asset_withdrawn = AssetId(crate::xcm_config::TokenLocation::get().clone())
+                .into_asset(Fungibility::Fungible(1_000_000_000_000u128));
+            let fee_asset = AssetId(crate::xcm_config::TokenLocation::get().clone())
+                .into_asset(Fungibility::Fungible(600_000_000_000u128));
+
+            let xcm = VersionedXcm::from(Xcm(vec![
+                WithdrawAsset(vec![fee_asset_withdrawn, eth_asset.clone()].into()),
+                InitiateTransfer {
+                    destination: EthereumLocation::get(),
+                    remote_fees: Some(AssetTransferFilter::ReserveDeposit(fee_asset.into())),
+                    preserve_origin: true,
+                    assets: BoundedVec::truncate_from(vec![AssetTransferFilter::ReserveWithdraw(
+                        Definite(eth_asset.into()),
+                    )]),
+                    remote_xcm: Xcm(vec![DepositAsset {
+                        assets: Wild(AllCounted(1)),
+                        beneficiary: beneficiary_location,
+                    }]),
+                },
+            ]));
+
+            assert_ok!(BridgeGuard::pause_bridge(root_origin()));
+
+            // The exporter refuses the transfer, so nothing leaves the chain
+            assert!(XcmPallet::execute(
+                RuntimeOrigin::signed(AccountId::from(BOB)),
+                Box::new(xcm.clone()),
+                Weight::from(16_000_000_000),
+            )
+            .is_err());
+            assert_eq!(
+                ForeignAssets::balance(asset_id, AccountId::from(BOB)),
+                amount_to_transfer
+            );
+            assert_eq!(
+                filter_events!(RuntimeEvent::EthereumOutboundQueueV2(
+                    snowbridge_pallet_outbound_queue_v2::Event::MessageQueued { .. },
+                ))
+                .count(),
+                0,
+                "MessageQueued event should NOT be emitted!"
+            );
+
+            assert_ok!(BridgeGuard::resume_bridge(root_origin()));
+
+            assert_ok!(XcmPallet::execute(
+                RuntimeOrigin::signed(AccountId::from(BOB)),
+                Box::new(xcm),
+                Weight::from(16_000_000_000),
+            ));
+            assert_eq!(ForeignAssets::balance(asset_id, AccountId::from(BOB)), 0);
+            assert_eq!(
+                filter_events!(RuntimeEvent::EthereumOutboundQueueV2(
+                    snowbridge_pallet_outbound_queue_v2::Event::MessageQueued { .. },
+                ))
+                .count(),
+                1,
+                "MessageQueued event should be emitted!"
+            );
+        })
+}
+
 #[test]
 fn send_eth_native_does_not_work_if_min_reward_not_covered() {
     ExtBuilder::default()
@@ -15,16 +15,24 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{tests::common::ExtBuilder, ExternalValidators, Runtime},
+    crate::{
+        bridge_to_ethereum_config::{
+            EthereumGovernanceHandler, EthereumGovernanceSlashingModeEnabled,
+        },
+        tests::common::ExtBuilder,
+        ExternalValidators, Runtime,
+    },
     frame_support::pallet_prelude::*,
     hex_literal::hex,
     keyring::Sr25519Keyring,
-    snowbridge_core::{Channel, PRIMARY_GOVERNANCE_CHANNEL},
+    pallet_external_validator_slashes::{SlashingMode, SlashingModeOption},
+    snowbridge_core::{Channel, ChannelId, PRIMARY_GOVERNANCE_CHANNEL},
     snowbridge_inbound_queue_primitives::v1::{Envelope, MessageProcessor},
     sp_core::{H160, H256},
     sp_runtime::DispatchError,
     tp_bridge::symbiotic_message_processor::{
-        InboundCommand, Message, Payload, SymbioticMessageProcessor, MAGIC_BYTES,
+        InboundCommand, InboundCommandV2, InboundCommandV3, Message, Payload,
+        SymbioticMessageProcessor, MAGIC_BYTES,
     },
 };
 
@@ -122,6 +130,58 @@ fn test_symbiotic_message_processor() {
     });
 }
 
+#[test]
+fn test_symbiotic_message_processor_with_stake() {
+    ExtBuilder::default().build().execute_with(|| {
+        let default_channel = Channel {
+            agent_id: H256::default(),
+            para_id: 0.into(),
+        };
+
+        let payload = Payload {
+            magic_bytes: MAGIC_BYTES,
+            message: Message::V2(InboundCommandV2::<Runtime>::ReceiveValidatorsWithStake {
+                validators: vec![
+                    (Sr25519Keyring::Alice.to_account_id(), 10),
+                    (Sr25519Keyring::Charlie.to_account_id(), 30),
+                    (Sr25519Keyring::Bob.to_account_id(), 20),
+                ],
+                external_index: 10u64,
+            }),
+        };
+        let envelope = Envelope {
+            channel_id: PRIMARY_GOVERNANCE_CHANNEL,
+            gateway: H160::default(),
+            message_id: Default::default(),
+            nonce: 0,
+            payload: payload.encode(),
+        };
+        assert!(SymbioticMessageProcessor::<Runtime>::can_process_message(
+            &default_channel,
+            &envelope
+        ));
+        assert_eq!(
+            SymbioticMessageProcessor::<Runtime>::process_message(
+                default_channel.clone(),
+                envelope
+            ),
+            Ok(())
+        );
+
+        // Validators are sorted by stake
+        let expected_validators = [
+            ExternalValidators::whitelisted_validators(),
+            vec![
+                Sr25519Keyring::Charlie.to_account_id(),
+                Sr25519Keyring::Bob.to_account_id(),
+                Sr25519Keyring::Alice.to_account_id(),
+            ],
+        ]
+        .concat();
+        assert_eq!(ExternalValidators::validators(), expected_validators);
+    });
+}
+
 #[test]
 fn test_symbiotic_message_processor_rejects_invalid_channel_id() {
     ExtBuilder::default().build().execute_with(|| {
@@ -212,3 +272,79 @@ fn test_symbiotic_message_processor_as_payload() {
         assert_eq!(ExternalValidators::validators().len(), 50+ExternalValidators::whitelisted_validators().len());
     });
 }
+
+#[test]
+fn test_symbiotic_message_processor_governance_commands() {
+    ExtBuilder::default().build().execute_with(|| {
+        type Processor = SymbioticMessageProcessor<Runtime, EthereumGovernanceHandler>;
+
+        let default_channel = Channel {
+            agent_id: H256::default(),
+            para_id: 0.into(),
+        };
+        let payload = Payload {
+            magic_bytes: MAGIC_BYTES,
+            message: Message::V3(InboundCommandV3::<Runtime>::SetSlashingMode {
+                mode: SlashingModeOption::LogOnly,
+            }),
+        };
+        let envelope_for_channel = |channel_id| Envelope {
+            channel_id,
+            gateway: H160::default(),
+            message_id: Default::default(),
+            nonce: 0,
+            payload: payload.encode(),
+        };
+
+        // Commands are disabled by default
+        assert_eq!(
+            Processor::process_message(
+                default_channel.clone(),
+                envelope_for_channel(PRIMARY_GOVERNANCE_CHANNEL)
+            ),
+            Err(DispatchError::Other("Governance command is not enabled"))
+        );
+
+        EthereumGovernanceSlashingModeEnabled::set(&true);
+
+        // Only the primary governance channel is allowed
+        assert_eq!(
+            Processor::process_message(
+                default_channel.clone(),
+                envelope_for_channel(ChannelId::new([1u8; 32]))
+            ),
+            Err(DispatchError::Other(
+                "Governance commands are only accepted from the primary governance channel"
+            ))
+        );
+        assert_eq!(SlashingMode::<Runtime>::get(), SlashingModeOption::Enabled);
+
+        assert_eq!(
+            Processor::process_message(
+                default_channel.clone(),
+                envelope_for_channel(PRIMARY_GOVERNANCE_CHANNEL)
+            ),
+            Ok(())
+        );
+        assert_eq!(SlashingMode::<Runtime>::get(), SlashingModeOption::LogOnly);
+
+        // Other commands are still disabled
+        let payload = Payload {
+            magic_bytes: MAGIC_BYTES,
+            message: Message::V3(InboundCommandV3::<Runtime>::SetRewardsReportingPaused {
+                paused: true,
+            }),
+        };
+        let envelope = Envelope {
+            channel_id: PRIMARY_GOVERNANCE_CHANNEL,
+            gateway: H160::default(),
+            message_id: Default::default(),
+            nonce: 0,
+            payload: payload.encode(),
+        };
+        assert_eq!(
+            Processor::process_message(default_channel, envelope),
+            Err(DispatchError::Other("Governance command is not enabled"))
+        );
+    });
+}
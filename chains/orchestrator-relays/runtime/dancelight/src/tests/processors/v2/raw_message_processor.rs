@@ -18,12 +18,14 @@ use crate::bridge_to_ethereum_config::MaxXcmWeight;
 use {
     super::ALICE,
     crate as dancelight_runtime,
-    crate::tests::common::ExtBuilder,
-    dancelight_runtime::{xcm_config, AccountId, Runtime},
-    frame_support::{parameter_types, BoundedVec},
+    crate::tests::common::{root_origin, ExtBuilder},
+    dancelight_runtime::{xcm_config, AccountId, BridgeGuard, Runtime},
+    frame_support::{assert_ok, parameter_types, BoundedVec},
     parity_scale_codec::Encode,
     snowbridge_core::{AgentId, ChannelId},
-    snowbridge_inbound_queue_primitives::v2::{message::Message, EthereumAsset, Payload},
+    snowbridge_inbound_queue_primitives::v2::{
+        message::Message, EthereumAsset, MessageProcessorError, Payload,
+    },
     sp_core::{H160, H256},
     tanssi_runtime_common::processors::v2::{
         MessageExtractionError, MessageProcessorWithFallback, RawMessageProcessor, RawPayload,
@@ -187,6 +189,78 @@ fn message_processor_succeeds_even_if_xcm_is_invalid() {
     });
 }
 
+#[test]
+fn message_processor_fails_while_bridge_paused() {
+    ExtBuilder::default().build().execute_with(|| {
+        let sender: AccountId = AccountId::from(ALICE);
+        let origin = GatewayAddress::get();
+
+        let assets = vec![EthereumAsset::NativeTokenERC20 {
+            token_id: H160::random(),
+            value: 1_000_000_000_000u128,
+        }];
+        let claimer = AccountId32 {
+            network: None,
+            id: H256::random().into(),
+        };
+        let message_with_payload = |raw_payload: RawPayload| Message {
+            gateway: origin,
+            nonce: 1,
+            origin,
+            assets: assets.clone(),
+            payload: Payload::Raw(raw_payload.encode()),
+            claimer: Some(claimer.encode()),
+            value: 1_000_000_000_000u128,
+            execution_fee: 0,
+            relayer_fee: 0,
+        };
+        // Processed by the raw message processor itself
+        let valid_xcm_message =
+            message_with_payload(RawPayload::Xcm(VersionedXcm::V5(Xcm::<()>::new()).encode()));
+        // Processed by the asset trap fallback
+        let invalid_xcm_message =
+            message_with_payload(RawPayload::Xcm(vec![0xAA, 0xBB, 0xCC].encode()));
+
+        type Processor = RawMessageProcessor<
+            Runtime,
+            GatewayAddress,
+            DefaultClaimer,
+            EthereumNetwork,
+            EthereumUniversalLocation,
+            TanssiUniversalLocation,
+            xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
+            <xcm_config::XcmConfig as xcm_executor::Config>::Weigher,
+            MaxXcmWeight,
+        >;
+
+        assert_ok!(BridgeGuard::pause_bridge(root_origin()));
+
+        for message in [valid_xcm_message.clone(), invalid_xcm_message.clone()] {
+            let result = <Processor as snowbridge_inbound_queue_primitives::v2::MessageProcessor<
+                AccountId,
+            >>::process_message(sender.clone(), message);
+            assert!(
+                matches!(
+                    result,
+                    Err(MessageProcessorError::ProcessMessage(error))
+                        if error == pallet_bridge_guard::Error::<Runtime>::BridgePaused.into()
+                ),
+                "Messages with tokens should be rejected while the bridge is paused, got: {:?}",
+                result
+            );
+        }
+
+        assert_ok!(BridgeGuard::resume_bridge(root_origin()));
+
+        for message in [valid_xcm_message, invalid_xcm_message] {
+            let result = <Processor as snowbridge_inbound_queue_primitives::v2::MessageProcessor<
+                AccountId,
+            >>::process_message(sender.clone(), message);
+            assert!(result.is_ok());
+        }
+    });
+}
+
 #[test]
 fn message_processor_fails_with_invalid_symbiotic_payload() {
     ExtBuilder::default().build().execute_with(|| {
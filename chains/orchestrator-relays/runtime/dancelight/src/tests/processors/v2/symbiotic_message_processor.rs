@@ -14,7 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
-use crate::bridge_to_ethereum_config::{MaxXcmWeight, SetExternalValidatorsWeight};
+use crate::bridge_to_ethereum_config::{
+    EthereumGovernanceHandler, MaxXcmWeight, SetExternalValidatorsWeight,
+};
 use {
     super::ALICE,
     crate as dancelight_runtime,
@@ -71,6 +73,7 @@ fn symbiotic_try_extract_message_fails_with_invalid_symbiotic_payload() {
             <xcm_config::XcmConfig as xcm_executor::Config>::Weigher,
             MaxXcmWeight,
             SetExternalValidatorsWeight,
+            EthereumGovernanceHandler,
         >;
 
         let result = <Processor as MessageProcessorWithFallback<AccountId>>::try_extract_message(
@@ -116,6 +119,7 @@ fn symbiotic_try_extract_message_fails_with_invalid_origin_payload() {
             <xcm_config::XcmConfig as xcm_executor::Config>::Weigher,
             MaxXcmWeight,
             SetExternalValidatorsWeight,
+            EthereumGovernanceHandler,
         >;
 
         let result = <Processor as MessageProcessorWithFallback<AccountId>>::try_extract_message(
@@ -20,6 +20,8 @@ pub mod pallet_assets;
 pub mod pallet_author_noting;
 pub mod pallet_balances;
 pub mod pallet_beefy_mmr;
+pub mod pallet_bridge_asset_recovery;
+pub mod pallet_bridge_guard;
 pub mod pallet_bridge_relayers;
 pub mod pallet_collator_assignment;
 pub mod pallet_collective;
@@ -38,6 +40,7 @@ pub mod pallet_message_queue;
 pub mod pallet_mmr;
 pub mod pallet_multiblock_migrations;
 pub mod pallet_multisig;
+pub mod pallet_outbound_message_commitment_recorder;
 pub mod pallet_parameters;
 pub mod pallet_pooled_staking;
 pub mod pallet_preimage;
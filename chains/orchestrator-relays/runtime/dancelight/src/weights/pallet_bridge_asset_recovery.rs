@@ -0,0 +1,63 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+
+//! Placeholder weights for pallet_bridge_asset_recovery
+//!
+//! THESE WEIGHTS WERE WRITTEN BY HAND AND ARE NOT BENCHMARKED YET. They must be regenerated
+//! with the command below before being relied upon.
+
+// Command to generate them:
+// target/release/tanssi-relay
+// benchmark
+// pallet
+// --wasm-execution=compiled
+// --pallet
+// pallet_bridge_asset_recovery
+// --extrinsic
+// *
+// --chain=dancelight-dev
+// --steps
+// 50
+// --repeat
+// 20
+// --template=benchmarking/frame-weight-runtime-template.hbs
+// --json-file
+// raw.json
+// --output
+// tmp/dancelight_weights/pallet_bridge_asset_recovery.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weights for pallet_bridge_asset_recovery using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_bridge_asset_recovery::WeightInfo for SubstrateWeight<T> {
+	fn claim_trapped_assets() -> Weight {
+		Weight::from_parts(124_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	fn retry_failed_transfer() -> Weight {
+		Weight::from_parts(98_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+}
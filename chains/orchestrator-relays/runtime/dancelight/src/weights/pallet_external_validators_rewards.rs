@@ -22,6 +22,7 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `benchmark-1`, CPU: `Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dancelight-dev"), DB CACHE: 1024
+//! PLACEHOLDERS, written by hand and not benchmarked yet: `set_rewards_reporting_paused`
 
 // Executed Command:
 // target/release/tanssi-relay
@@ -73,13 +74,21 @@ impl<T: frame_system::Config> pallet_external_validators_rewards::WeightInfo for
 	/// Proof: `MessageQueue::Pages` (`max_values`: None, `max_size`: Some(32845), added: 35320, mode: `MaxEncodedLen`)
 	/// Storage: UNKNOWN KEY `0xf5207f03cfdce586301014700e2c2593fad157e461d71fd4c1f936839a5f1f3e` (r:0 w:1)
 	/// Proof: UNKNOWN KEY `0xf5207f03cfdce586301014700e2c2593fad157e461d71fd4c1f936839a5f1f3e` (r:0 w:1)
+	/// Storage: `ExternalValidatorsRewards::RewardsReportingPaused` (r:1 w:0)
+	/// Proof: `ExternalValidatorsRewards::RewardsReportingPaused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
 	fn on_era_end() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `37790`
 		//  Estimated: `41255`
 		// Minimum execution time: 1_573_964_000 picoseconds.
 		Weight::from_parts(1_589_850_000, 41255)
-			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_rewards_reporting_paused() -> Weight {
+		Weight::from_parts(4_238_000, 1743)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
\ No newline at end of file
@@ -22,6 +22,7 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `benchmark-1`, CPU: `Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dancelight-dev"), DB CACHE: 1024
+//! PLACEHOLDERS, written by hand and not benchmarked yet: `claim_pending_rewards`, `expire_invulnerables`, `reward_invulnerable`, `set_invulnerable_expiry`, `set_reward_policy`, `swap_invulnerable`
 
 // Executed Command:
 // target/release/tanssi-relay
@@ -97,20 +98,46 @@ impl<T: frame_system::Config> pallet_invulnerables::WeightInfo for SubstrateWeig
 			.saturating_add(Weight::from_parts(78_644, 0).saturating_mul(r.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 	}
-	/// Storage: `TanssiInvulnerables::Invulnerables` (r:1 w:0)
-	/// Proof: `TanssiInvulnerables::Invulnerables` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 100]`.
+	/// Placeholder weight, not benchmarked yet.
 	fn reward_invulnerable(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `285 + b * (33 ±0)`
-		//  Estimated: `4687`
-		// Minimum execution time: 28_772_000 picoseconds.
 		Weight::from_parts(32_738_171, 4687)
-			// Standard Error: 1_632
 			.saturating_add(Weight::from_parts(95_761, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_reward_policy() -> Weight {
+		Weight::from_parts(7_412_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn claim_pending_rewards() -> Weight {
+		Weight::from_parts(32_480_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_invulnerable_expiry(b: u32, ) -> Weight {
+		Weight::from_parts(15_203_118, 4687)
+			.saturating_add(Weight::from_parts(61_092, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn swap_invulnerable(b: u32, ) -> Weight {
+		Weight::from_parts(26_342_571, 4687)
+			.saturating_add(Weight::from_parts(88_430, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn expire_invulnerables(r: u32, ) -> Weight {
+		Weight::from_parts(7_913_441, 4687)
+			.saturating_add(Weight::from_parts(7_390_871, 0).saturating_mul(r.into()))
 			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(r.into()))
 	}
 }
\ No newline at end of file
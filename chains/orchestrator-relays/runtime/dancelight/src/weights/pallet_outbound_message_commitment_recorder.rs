@@ -0,0 +1,60 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+
+//! Placeholder weights for pallet_outbound_message_commitment_recorder
+//!
+//! THESE WEIGHTS WERE WRITTEN BY HAND AND ARE NOT BENCHMARKED YET. They must be regenerated
+//! with the command below before being relied upon.
+
+// Command to generate them:
+// target/release/tanssi-relay
+// benchmark
+// pallet
+// --wasm-execution=compiled
+// --pallet
+// pallet_outbound_message_commitment_recorder
+// --extrinsic
+// *
+// --chain=dancelight-dev
+// --steps
+// 50
+// --repeat
+// 20
+// --template=benchmarking/frame-weight-runtime-template.hbs
+// --json-file
+// raw.json
+// --output
+// tmp/dancelight_weights/pallet_outbound_message_commitment_recorder.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weights for pallet_outbound_message_commitment_recorder using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_outbound_message_commitment_recorder::WeightInfo for SubstrateWeight<T> {
+	fn record_commitment(n: u32, ) -> Weight {
+		Weight::from_parts(14_150_000, 3542)
+			.saturating_add(Weight::from_parts(3_320_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+	}
+}
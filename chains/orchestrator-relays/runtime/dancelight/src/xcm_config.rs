@@ -230,10 +230,10 @@ impl xcm_executor::Config for XcmConfig {
     type Trader =
         UsingComponents<WeightToFee, TokenLocation, AccountId, Balances, ToAuthor<Runtime>>;
     type ResponseHandler = XcmPallet;
-    type AssetTrap = XcmPallet;
+    type AssetTrap = pallet_bridge_asset_recovery::IndexedAssetTrap<Runtime, XcmPallet>;
     type AssetLocker = ();
     type AssetExchanger = ();
-    type AssetClaims = XcmPallet;
+    type AssetClaims = pallet_bridge_asset_recovery::IndexedAssetClaims<Runtime, XcmPallet>;
     type SubscriptionService = XcmPallet;
     type PalletInstancesInfo = AllPalletsWithSystem;
     type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
@@ -371,6 +371,7 @@ pub type SnowbridgeExporter = EthereumBlobExporter<
     snowbridge_pallet_outbound_queue::Pallet<Runtime>,
     EthereumSystem,
     SnowbridgeChannelInfo,
+    crate::BridgeGuard,
 >;
 
 /// Exports message to the Ethereum Gateway contract.
@@ -381,6 +382,7 @@ pub type SnowbridgeExporterv2 = EthereumBlobExporterV2<
     EthereumSystem,
     MinSnowbridgeV2Reward,
     SendXcmFeeToAccount<LocalAssetTransactor, SnowbridgeFeesAccount>,
+    crate::BridgeGuard,
 >;
 
 /// Exports message to the Ethereum Gateway contract.
@@ -391,6 +393,7 @@ pub type ContainerToSnowbridgeMessageExporter = ContainerEthereumBlobExporter<
     snowbridge_pallet_outbound_queue::Pallet<Runtime>,
     EthereumSystem,
     SnowbridgeChannelInfo,
+    crate::BridgeGuard,
 >;
 
 /// Exports message to the Ethereum Gateway contract using Snowbridge V2.
@@ -401,4 +404,5 @@ pub type ContainerToSnowbridgeMessageExporterV2 = ContainerEthereumBlobExporterV
     snowbridge_pallet_outbound_queue_v2::Pallet<Runtime>,
     EthereumSystem,
     MinSnowbridgeV2Reward,
+    crate::BridgeGuard,
 >;
@@ -314,6 +314,7 @@ fn starlight_testnet_genesis(
         crate::StakingAccount::get(),
         crate::StarlightBondAccount::get(),
         crate::PendingRewardsAccount::get(),
+        crate::InvulnerablesPendingRewardsAccount::get(),
         crate::EthereumSovereignAccount::get(),
         crate::SnowbridgeFeesAccount::get(),
         crate::TreasuryPalletId::get().into_account_truncating(),
@@ -26,6 +26,7 @@ use frame_support::storage::{with_storage_layer, with_transaction};
 // Fix compile error in impl_runtime_weights! macro
 use {
     alloc::{
+        boxed::Box,
         collections::{btree_map::BTreeMap, btree_set::BTreeSet, vec_deque::VecDeque},
         vec,
         vec::Vec,
@@ -105,8 +106,9 @@ use {
     tp_message_queue::{MessageQueueWrapper, OnQueueChangedWrapper},
     tp_stream_payment_common::StreamId,
     tp_traits::{
-        prod_or_fast_parameter_types, EraIndex, GetHostConfiguration, GetSessionContainerChains,
-        NodeActivityTrackingHelper, ParaIdAssignmentHooks, RegistrarHandler, Slot, SlotFrequency,
+        prod_or_fast_parameter_types, ContainerChainEquivocationProof, EraIndex,
+        GetHostConfiguration, GetSessionContainerChains, NodeActivityTrackingHelper,
+        ParaIdAssignmentHooks, RegistrarHandler, Slot, SlotFrequency,
     },
     xcm::Version as XcmVersion,
     xcm_runtime_apis::{
@@ -1476,6 +1478,7 @@ impl pallet_external_validators::Config for Runtime {
     type HistoryDepth = ConstU32<84>;
     type MaxWhitelistedValidators = MaxWhitelistedValidators;
     type MaxExternalValidators = MaxExternalValidators;
+    type MaxExternalSources = MaxExternalSources;
     type ValidatorId = AccountId;
     type ValidatorIdOf = ValidatorIdOf;
     type ValidatorRegistration = Session;
@@ -1509,6 +1512,9 @@ parameter_types! {
         &EthereumLocation::get(),
         &xcm_config::UniversalLocation::get()
     ).expect("unable to reanchor reward token");
+
+    // Whether era points sent to Ethereum are weighted by the stake of each validator.
+    pub storage StakeWeightedRewardPoints: bool = false;
 }
 
 pub struct GetWhitelistedValidators;
@@ -1541,6 +1547,9 @@ impl pallet_external_validators_rewards::Config for Runtime {
 
     type EraInflationProvider = ExternalRewardsEraInflationProvider;
     type ExternalIndexProvider = ExternalValidators;
+    type ExternalSourcesProvider = ExternalValidators;
+    type ExternalStakeProvider = ExternalValidators;
+    type StakeWeightedPoints = StakeWeightedRewardPoints;
     type GetWhitelistedValidators = GetWhitelistedValidators;
     type Hashing = Keccak256;
     type ValidateMessage = tp_bridge::TanssiEthMessageValidatorV1<Runtime>;
@@ -1568,7 +1577,9 @@ impl pallet_external_validator_slashes::Config for Runtime {
     type OutboundQueue =
         tp_bridge::TanssiEthMessageSenderV1<Runtime, GetAggregateMessageOriginTanssi>;
     type ExternalIndexProvider = ExternalValidators;
+    type ExternalSourcesProvider = ExternalValidators;
     type QueuedSlashesProcessedPerBlock = ConstU32<10>;
+    type Hashing = Keccak256;
     type WeightInfo = weights::pallet_external_validator_slashes::SubstrateWeight<Runtime>;
 }
 
@@ -1584,8 +1595,11 @@ impl pallet_root_testing::Config for Runtime {
 
 parameter_types! {
     pub const MaxInvulnerables: u32 = 100;
+    pub InvulnerablesPendingRewardsAccount: AccountId =
+        PalletId(*b"InvPndRw").into_account_truncating();
     pub const MaxWhitelistedValidators: u32 = 100;
     pub const MaxExternalValidators: u32 = 100;
+    pub const MaxExternalSources: u32 = 4;
 }
 
 impl pallet_invulnerables::Config for Runtime {
@@ -1594,9 +1608,11 @@ impl pallet_invulnerables::Config for Runtime {
     type CollatorId = <Self as frame_system::Config>::AccountId;
     type CollatorIdOf = ConvertInto;
     type CollatorRegistration = Session;
-    type WeightInfo = weights::pallet_invulnerables::SubstrateWeight<Runtime>;
-    #[cfg(feature = "runtime-benchmarks")]
     type Currency = Balances;
+    type RewardsTreasury = TreasuryAccount;
+    type PendingRewardsAccount = InvulnerablesPendingRewardsAccount;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type WeightInfo = weights::pallet_invulnerables::SubstrateWeight<Runtime>;
 }
 
 pub struct CurrentSessionIndexGetter;
@@ -1952,6 +1968,8 @@ construct_runtime! {
         EthereumSystem: snowbridge_pallet_system = 25,
         OutboundMessageCommitmentRecorder: pallet_outbound_message_commitment_recorder = 26,
         EthereumTokenTransfers: pallet_ethereum_token_transfers = 27,
+        BridgeGuard: pallet_bridge_guard = 28,
+        BridgeAssetRecovery: pallet_bridge_asset_recovery = 29,
 
         // Session management
         Session: pallet_session = 30,
@@ -2297,6 +2315,11 @@ impl pallet_author_noting::Config for Runtime {
     type AuthorNotingHook = (InflationRewards, ServicesPayment, InactivityTracking);
     type RelayOrPara = pallet_author_noting::RelayMode;
     type MaxContainerChains = MaxLengthParaIds;
+    type CollatorAccount =
+        pallet_authority_mapping::CollatorAccountLookup<Runtime, CurrentSessionIndexGetter>;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    // Equivocations are only recorded for now, no pallet punishes them yet
+    type EquivocationReporter = ();
     type WeightInfo = weights::pallet_author_noting::SubstrateWeight<Runtime>;
 }
 
@@ -2327,6 +2350,7 @@ mod benches {
         [pallet_identity, Identity]
         [pallet_message_queue, MessageQueue]
         [pallet_multisig, Multisig]
+        [pallet_outbound_message_commitment_recorder, OutboundMessageCommitmentRecorder]
         [pallet_parameters, Parameters]
         [pallet_preimage, Preimage]
         [pallet_proxy, Proxy]
@@ -2371,6 +2395,8 @@ mod benches {
         [pallet_xcm_benchmarks::generic, pallet_xcm_benchmarks::generic::Pallet::<Runtime>]
         // Bridges
         [pallet_ethereum_token_transfers, EthereumTokenTransfers]
+        [pallet_bridge_guard, BridgeGuard]
+        [pallet_bridge_asset_recovery, BridgeAssetRecovery]
         [snowbridge_pallet_ethereum_client, EthereumBeaconClient]
         [snowbridge_pallet_outbound_queue, EthereumOutboundQueue]
         [snowbridge_pallet_system, EthereumSystem]
@@ -3035,6 +3061,18 @@ sp_api::impl_runtime_apis! {
         }
     }
 
+    impl pallet_author_noting_runtime_api::ContainerChainEquivocationApi<Block> for Runtime {
+        fn create_equivocation_report_unsigned_extrinsic(equivocation_proof: ContainerChainEquivocationProof) -> Box<<Block as BlockT>::Extrinsic> {
+            let call = RuntimeCall::AuthorNoting(pallet_author_noting::Call::report_container_chain_equivocation {
+                equivocation_proof: Box::new(equivocation_proof),
+            });
+
+            let unsigned_extrinsic = UncheckedExtrinsic::new_bare(call);
+
+            Box::new(unsigned_extrinsic)
+        }
+    }
+
     impl pallet_external_validators_rewards_runtime_api::ExternalValidatorsRewardsApi<Block, AccountId, EraIndex> for Runtime
         where
         EraIndex: parity_scale_codec::Codec,
@@ -3048,6 +3086,19 @@ sp_api::impl_runtime_apis! {
         }
     }
 
+    impl pallet_external_validator_slashes_runtime_api::ExternalValidatorSlashesApi<Block, AccountId, EraIndex> for Runtime
+        where
+        EraIndex: parity_scale_codec::Codec,
+    {
+        fn generate_slashes_merkle_proof(account_id: AccountId, era_index: EraIndex) -> Option<MerkleProof> {
+            ExternalValidatorSlashes::generate_slashes_merkle_proof(account_id, era_index)
+        }
+
+        fn verify_slashes_merkle_proof(merkle_proof: MerkleProof) -> bool {
+            ExternalValidatorSlashes::verify_slashes_merkle_proof(merkle_proof)
+        }
+    }
+
     impl dp_consensus::TanssiAuthorityAssignmentApi<Block, NimbusId> for Runtime {
         /// Return the current authorities assigned to a given paraId
         fn para_id_authorities(para_id: ParaId) -> Option<Vec<NimbusId>> {
@@ -3081,6 +3132,27 @@ sp_api::impl_runtime_apis! {
         }
     }
 
+    impl pallet_authority_assignment_runtime_api::AuthorityAssignmentApi<Block, NimbusId, ParaId> for Runtime {
+        /// Return every paraId assigned to a given authority
+        fn para_ids_of_authority(authority: NimbusId) -> Vec<ParaId> {
+            let session_index = Session::current_index();
+            // The orchestrator is always empty in this runtime, see `check_para_id_assignment`
+            let self_para_id = 0u32.into();
+
+            TanssiAuthorityAssignment::para_ids_of(session_index, &authority, self_para_id)
+        }
+
+        /// Return every paraId assigned to a given authority on the next session.
+        /// On session boundary this returns the same as `para_ids_of_authority`.
+        fn para_ids_of_authority_next_session(authority: NimbusId) -> Vec<ParaId> {
+            let session_index = Session::current_index() + 1;
+            // The orchestrator is always empty in this runtime, see `check_para_id_assignment`
+            let self_para_id = 0u32.into();
+
+            TanssiAuthorityAssignment::para_ids_of(session_index, &authority, self_para_id)
+        }
+    }
+
     impl pallet_services_payment_runtime_api::ServicesPaymentApi<Block, AccountId, Balance, ParaId> for Runtime {
         fn block_cost(para_id: ParaId) -> Balance {
             let (block_production_costs, _) = <Runtime as pallet_services_payment::Config>::ProvideBlockProductionCost::block_cost(&para_id);
@@ -3107,6 +3179,77 @@ sp_api::impl_runtime_apis! {
         }
     }
 
+    impl pallet_ethereum_token_transfers_runtime_api::EthereumTokenTransfersApi<Block, Balance> for Runtime {
+        fn quote_transfer(
+            token: pallet_ethereum_token_transfers_runtime_api::TransferredToken,
+            amount: u128,
+            recipient: sp_core::H160,
+        ) -> Result<pallet_ethereum_token_transfers_runtime_api::TransferFeeQuote<Balance>, sp_runtime::DispatchError> {
+            EthereumTokenTransfers::quote_transfer(token, amount, recipient)
+        }
+
+        fn quote_transfer_v2(
+            token: pallet_ethereum_token_transfers_runtime_api::TransferredToken,
+            amount: u128,
+            recipient: sp_core::H160,
+            reward: Option<u128>,
+            tip: u128,
+        ) -> Result<pallet_ethereum_token_transfers_runtime_api::TransferFeeQuote<Balance>, sp_runtime::DispatchError> {
+            EthereumTokenTransfers::quote_transfer_v2(token, amount, recipient, reward, tip)
+        }
+    }
+
+    impl pallet_bridge_asset_recovery_runtime_api::BridgeAssetRecoveryApi<
+        Block,
+        AccountId,
+        pallet_bridge_asset_recovery::TrapId,
+        pallet_bridge_asset_recovery::TrappedAssetsRecord<BlockNumber>,
+        pallet_bridge_asset_recovery::FailedTransferRecord<BlockNumber>,
+    > for Runtime {
+        fn trapped_assets(
+            claimer: AccountId,
+        ) -> Vec<(
+            pallet_bridge_asset_recovery::TrapId,
+            pallet_bridge_asset_recovery::TrappedAssetsRecord<BlockNumber>,
+        )> {
+            BridgeAssetRecovery::trapped_assets(&claimer)
+        }
+
+        fn failed_transfers(
+            claimer: AccountId,
+        ) -> Vec<(
+            pallet_bridge_asset_recovery::TrapId,
+            pallet_bridge_asset_recovery::FailedTransferRecord<BlockNumber>,
+        )> {
+            BridgeAssetRecovery::failed_transfers(&claimer)
+        }
+    }
+
+    impl pallet_outbound_message_commitment_recorder_runtime_api::OutboundMessageTrackingApi<Block, BlockNumber> for Runtime {
+        fn outbound_message_status(
+            message_id: H256,
+        ) -> Option<pallet_outbound_message_commitment_recorder_runtime_api::OutboundMessageStatus<BlockNumber>> {
+            let (record, proof) =
+                OutboundMessageCommitmentRecorder::outbound_message_proof(message_id)?;
+            let commitment_root = OutboundMessageCommitmentRecorder::is_recorded_commitment(
+                record.block_number,
+                proof.root,
+            )
+            .then_some(proof.root);
+
+            Some(pallet_outbound_message_commitment_recorder_runtime_api::OutboundMessageStatus {
+                nonce: record.nonce,
+                channel_id: record.channel_id,
+                block_number: record.block_number,
+                commitment_root,
+                leaf_index: proof.leaf_index,
+                leaf: proof.leaf,
+                number_of_leaves: proof.number_of_leaves,
+                proof: proof.proof,
+            })
+        }
+    }
+
     impl pallet_stream_payment_runtime_api::StreamPaymentApi<Block, StreamId, Balance, Balance>
     for Runtime {
         fn stream_payment_status(
@@ -3483,11 +3626,16 @@ impl tanssi_initializer::ApplyNewSession<Runtime> for OwnApplySession {
         // 2. Second, registrar
         ContainerRegistrar::initializer_on_new_session(&session_index);
 
+        // The collators selected here are used in the next session
+        let target_session_index = session_index.saturating_add(1);
+        // Remove expired invulnerables before selecting the collators of the next session. Like
+        // the parachain runtimes, where this happens in `SessionManager::new_session`, use the
+        // index of the planned session.
+        TanssiInvulnerables::initializer_on_new_session(&target_session_index);
         let invulnerables = TanssiInvulnerables::invulnerables().to_vec();
         let candidates_staking =
             pallet_pooled_staking::SortedEligibleCandidates::<Runtime>::get().to_vec();
         // Max number of collators is set in pallet_configuration
-        let target_session_index = session_index.saturating_add(1);
         let max_collators = <CollatorConfiguration as GetHostConfiguration<u32>>::max_collators(
             target_session_index,
         );
@@ -3555,6 +3703,8 @@ impl tanssi_initializer::ApplyNewSession<Runtime> for OwnApplySession {
         );
         // 6. InactivityTracking
         InactivityTracking::process_ended_session();
+        // 7. AuthorNoting
+        AuthorNoting::initializer_on_new_session(&session_index);
     }
 
     fn on_before_session_ending() {
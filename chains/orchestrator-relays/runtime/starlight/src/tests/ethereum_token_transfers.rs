@@ -19,9 +19,10 @@
 use {
     crate::{
         bridge_to_ethereum_config::EthereumGatewayAddress, filter_events, tests::common::*,
-        Balances, EthereumInboundQueue, EthereumLocation, EthereumSovereignAccount, EthereumSystem,
-        EthereumTokenTransfers, ForeignAssets, ForeignAssetsCreator, RuntimeEvent,
-        SnowbridgeFeesAccount, TokenLocationReanchored, XcmPallet,
+        Balances, BridgeAssetRecovery, EthereumInboundQueue, EthereumLocation,
+        EthereumSovereignAccount, EthereumSystem, EthereumTokenTransfers, ForeignAssets,
+        ForeignAssetsCreator, RuntimeEvent, SnowbridgeFeesAccount, TokenLocationReanchored,
+        XcmPallet,
     },
     alloc::vec,
     alloy_sol_types::SolEvent,
@@ -860,14 +861,24 @@ fn fail_receiving_native_tokens_with_destination_below_existential_deposit() {
 
             assert_ok!(EthereumInboundQueue::submit(relayer, message.clone()));
 
-            // Sovereign account balance should not change
+            // Tokens are withdrawn from the sovereign account and trapped
             assert_eq!(
                 Balances::free_balance(EthereumSovereignAccount::get()),
-                sovereign_balance_before
+                sovereign_balance_before - amount_to_transfer
             );
 
             // Random account balance should not change
-            assert_eq!(Balances::free_balance(random_account), 0u128);
+            assert_eq!(Balances::free_balance(random_account.clone()), 0u128);
+
+            // The destination account can claim the trapped tokens
+            let traps = BridgeAssetRecovery::trapped_assets(&random_account);
+            assert_eq!(traps.len(), 1);
+            let (_, record) = &traps[0];
+            assert_eq!(record.message_id, H256::zero());
+            assert_eq!(
+                record.assets,
+                VersionedAssets::from(XcmAssets::from((Location::here(), amount_to_transfer)))
+            );
         });
 }
 
@@ -2710,7 +2721,14 @@ fn receive_erc20_tokens_does_not_fail_if_not_sufficient_and_random_address() {
             }]
             .into();
 
-            let ethereum_location = EthereumLocation::get();
+            // The recipient is set as the asset claimer
+            let recipient_location = Location::new(
+                0,
+                [AccountId32 {
+                    network: None,
+                    id: recipient.into(),
+                }],
+            );
 
             // Check that assets are trapped
             let assets_trapped_event = System::events()
@@ -2721,7 +2739,7 @@ fn receive_erc20_tokens_does_not_fail_if_not_sufficient_and_random_address() {
                         origin,
                         assets,
                     }) => {
-                        *origin == ethereum_location
+                        *origin == recipient_location
                             && *assets == VersionedAssets::V5(trapped_assets.clone())
                     }
                     _ => false,
@@ -2732,5 +2750,10 @@ fn receive_erc20_tokens_does_not_fail_if_not_sufficient_and_random_address() {
                 assets_trapped_event, 1,
                 "AssetsTrapped event should be emitted!"
             );
+
+            // And indexed so the recipient can claim them
+            let traps = BridgeAssetRecovery::trapped_assets(&AccountId::from(recipient.0));
+            assert_eq!(traps.len(), 1);
+            assert_eq!(traps[0].1.assets, VersionedAssets::V5(trapped_assets));
         });
 }
@@ -22,6 +22,7 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `benchmark-1`, CPU: `Intel(R) Xeon(R) Platinum 8375C CPU @ 2.90GHz`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("starlight-dev"), DB CACHE: 1024
+//! PLACEHOLDERS, written by hand and not benchmarked yet: `kill_author_data`, `report_container_chain_equivocation`, `set_latest_author_data`
 
 // Executed Command:
 // target/release/tanssi-relay
@@ -53,36 +54,15 @@ use core::marker::PhantomData;
 /// Weights for pallet_author_noting using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_author_noting::WeightInfo for SubstrateWeight<T> {
-	/// Storage: `AuthorNoting::DidSetContainerAuthorData` (r:1 w:1)
-	/// Proof: `AuthorNoting::DidSetContainerAuthorData` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
-	/// Storage: `TanssiCollatorAssignment::CollatorContainerChain` (r:1 w:0)
-	/// Proof: `TanssiCollatorAssignment::CollatorContainerChain` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `Babe::CurrentSlot` (r:1 w:0)
-	/// Proof: `Babe::CurrentSlot` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
-	/// Storage: `Paras::Heads` (r:99 w:0)
-	/// Proof: `Paras::Heads` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `AuthorNoting::LatestAuthor` (r:99 w:99)
-	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
-	/// Storage: `InflationRewards::ChainsToReward` (r:1 w:1)
-	/// Proof: `InflationRewards::ChainsToReward` (`max_values`: Some(1), `max_size`: Some(418), added: 913, mode: `MaxEncodedLen`)
-	/// Storage: `InactivityTracking::CurrentActivityTrackingStatus` (r:1 w:0)
-	/// Proof: `InactivityTracking::CurrentActivityTrackingStatus` (`max_values`: Some(1), `max_size`: Some(9), added: 504, mode: `MaxEncodedLen`)
-	/// Storage: `Session::CurrentIndex` (r:1 w:0)
-	/// Proof: `Session::CurrentIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// The range of component `x` is `[1, 100]`.
+	/// Placeholder weight, not benchmarked yet.
 	fn set_latest_author_data(x: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `1144 + x * (170 ±0)`
-		//  Estimated: `7595 + x * (2527 ±0)`
-		// Minimum execution time: 12_413_000 picoseconds.
 		Weight::from_parts(12_566_000, 7595)
-			// Standard Error: 146_514
 			.saturating_add(Weight::from_parts(22_486_753, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(x.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
-			.saturating_add(Weight::from_parts(0, 2527).saturating_mul(x.into()))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
+			.saturating_add(Weight::from_parts(0, 5599).saturating_mul(x.into()))
 	}
 	/// Storage: `AuthorNoting::LatestAuthor` (r:0 w:1)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
@@ -94,15 +74,10 @@ impl<T: frame_system::Config> pallet_author_noting::WeightInfo for SubstrateWeig
 		Weight::from_parts(9_703_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: `AuthorNoting::LatestAuthor` (r:0 w:1)
-	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Placeholder weight, not benchmarked yet.
 	fn kill_author_data() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `0`
-		//  Estimated: `0`
-		// Minimum execution time: 9_257_000 picoseconds.
 		Weight::from_parts(9_420_000, 0)
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `InflationRewards::ChainsToReward` (r:1 w:1)
 	/// Proof: `InflationRewards::ChainsToReward` (`max_values`: Some(1), `max_size`: Some(418), added: 913, mode: `MaxEncodedLen`)
@@ -137,4 +112,10 @@ impl<T: frame_system::Config> pallet_author_noting::WeightInfo for SubstrateWeig
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(x.into())))
 			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(x.into()))
 	}
+	/// Placeholder weight, not benchmarked yet.
+	fn report_container_chain_equivocation() -> Weight {
+		Weight::from_parts(154_337_000, 7561)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
@@ -0,0 +1,79 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+
+//! Placeholder weights for pallet_bridge_guard
+//!
+//! THESE WEIGHTS WERE WRITTEN BY HAND AND ARE NOT BENCHMARKED YET. They must be regenerated
+//! with the command below before being relied upon.
+
+// Command to generate them:
+// target/release/tanssi-relay
+// benchmark
+// pallet
+// --wasm-execution=compiled
+// --pallet
+// pallet_bridge_guard
+// --extrinsic
+// *
+// --chain=starlight-dev
+// --steps
+// 50
+// --repeat
+// 20
+// --template=benchmarking/frame-weight-runtime-template.hbs
+// --json-file
+// raw.json
+// --output
+// tmp/starlight_weights/pallet_bridge_guard.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weights for pallet_bridge_guard using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_bridge_guard::WeightInfo for SubstrateWeight<T> {
+	fn set_token_limits() -> Weight {
+		Weight::from_parts(8_240_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn pause_bridge() -> Weight {
+		Weight::from_parts(5_390_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn resume_bridge() -> Weight {
+		Weight::from_parts(5_330_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn cancel_delayed_transfer() -> Weight {
+		Weight::from_parts(49_850_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	fn on_initialize(n: u32, ) -> Weight {
+		Weight::from_parts(7_120_000, 6196)
+			.saturating_add(Weight::from_parts(104_480_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2641).saturating_mul(n.into()))
+	}
+}
@@ -146,4 +146,44 @@ impl<T: frame_system::Config> pallet_external_validators::WeightInfo for Substra
 			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `ExternalValidators::ExternalSources` (r:2 w:1)
+	/// Proof: `ExternalValidators::ExternalSources` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `ExternalValidators::CounterForExternalSources` (r:1 w:1)
+	/// Proof: `ExternalValidators::CounterForExternalSources` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_external_source() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `350`
+		//  Estimated: `6036`
+		// Minimum execution time: 15_320_000 picoseconds.
+		Weight::from_parts(16_104_000, 6036)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `ExternalValidators::ExternalSources` (r:1 w:1)
+	/// Proof: `ExternalValidators::ExternalSources` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `ExternalValidators::CounterForExternalSources` (r:1 w:1)
+	/// Proof: `ExternalValidators::CounterForExternalSources` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `ExternalValidators::ExternalValidatorsBySource` (r:0 w:1)
+	/// Proof: `ExternalValidators::ExternalValidatorsBySource` (`max_values`: None, `max_size`: Some(3213), added: 5688, mode: `MaxEncodedLen`)
+	/// Storage: `ExternalValidators::ExternalIndexBySource` (r:0 w:1)
+	/// Proof: `ExternalValidators::ExternalIndexBySource` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+	fn remove_external_source() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `191`
+		//  Estimated: `3513`
+		// Minimum execution time: 14_077_000 picoseconds.
+		Weight::from_parts(14_652_000, 3513)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `ExternalValidators::ExternalMergePolicy` (r:0 w:1)
+	/// Proof: `ExternalValidators::ExternalMergePolicy` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	fn set_external_merge_policy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_402_000 picoseconds.
+		Weight::from_parts(1_521_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
\ No newline at end of file
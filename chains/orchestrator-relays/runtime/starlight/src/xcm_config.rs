@@ -224,10 +224,10 @@ impl xcm_executor::Config for XcmConfig {
     type Trader =
         UsingComponents<WeightToFee, TokenLocation, AccountId, Balances, ToAuthor<Runtime>>;
     type ResponseHandler = XcmPallet;
-    type AssetTrap = XcmPallet;
+    type AssetTrap = pallet_bridge_asset_recovery::IndexedAssetTrap<Runtime, XcmPallet>;
     type AssetLocker = ();
     type AssetExchanger = ();
-    type AssetClaims = XcmPallet;
+    type AssetClaims = pallet_bridge_asset_recovery::IndexedAssetClaims<Runtime, XcmPallet>;
     type SubscriptionService = XcmPallet;
     type PalletInstancesInfo = AllPalletsWithSystem;
     type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
@@ -358,6 +358,7 @@ pub type SnowbridgeExporter = EthereumBlobExporter<
     snowbridge_pallet_outbound_queue::Pallet<Runtime>,
     EthereumSystem,
     SnowbridgeChannelInfo,
+    crate::BridgeGuard,
 >;
 
 /// Exports message to the Ethereum Gateway contract.
@@ -368,4 +369,5 @@ pub type ContainerToSnowbridgeMessageExporter = ContainerEthereumBlobExporter<
     snowbridge_pallet_outbound_queue::Pallet<Runtime>,
     EthereumSystem,
     SnowbridgeChannelInfo,
+    crate::BridgeGuard,
 >;
@@ -1097,6 +1097,58 @@ where
     }
 }
 
+/// Funds the account holding the pending rewards of invulnerables with the existential deposit.
+/// It is only funded at genesis, and rewards below the existential deposit cannot be kept in it
+/// until it exists.
+pub struct InvulnerablesPendingRewardsAccountMigration<Runtime>(pub PhantomData<Runtime>);
+impl<Runtime> Migration for InvulnerablesPendingRewardsAccountMigration<Runtime>
+where
+    Runtime: pallet_invulnerables::Config,
+{
+    fn friendly_name(&self) -> &str {
+        "TM_InvulnerablesPendingRewardsAccountMigration"
+    }
+
+    fn migrate(&self, _available_weight: Weight) -> Weight {
+        use frame_support::traits::fungible::{Inspect, Mutate};
+        type Currency<Runtime> = <Runtime as pallet_invulnerables::Config>::Currency;
+
+        let holder = <Runtime as pallet_invulnerables::Config>::PendingRewardsAccount::get();
+        let balance = Currency::<Runtime>::balance(&holder);
+        let existential_deposit = Currency::<Runtime>::minimum_balance();
+        if balance < existential_deposit {
+            if let Err(e) = Currency::<Runtime>::mint_into(&holder, existential_deposit - balance) {
+                log::error!(
+                    "Failed to fund the invulnerables pending rewards account: {:?}",
+                    e
+                );
+            }
+        }
+
+        // Read the account, and write it together with the total issuance
+        Runtime::DbWeight::get().reads_writes(2, 2)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade(&self) -> Result<Vec<u8>, sp_runtime::DispatchError> {
+        Ok(vec![])
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(&self, _state: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+        use frame_support::traits::fungible::Inspect;
+        type Currency<Runtime> = <Runtime as pallet_invulnerables::Config>::Currency;
+
+        let holder = <Runtime as pallet_invulnerables::Config>::PendingRewardsAccount::get();
+        ensure!(
+            Currency::<Runtime>::balance(&holder) >= Currency::<Runtime>::minimum_balance(),
+            "Invulnerables pending rewards account must hold the existential deposit"
+        );
+
+        Ok(())
+    }
+}
+
 pub struct FlashboxMigrations<Runtime>(PhantomData<Runtime>);
 
 impl<Runtime> GetMigrations for FlashboxMigrations<Runtime>
@@ -1108,6 +1160,7 @@ where
     Runtime: pallet_services_payment::Config,
     Runtime: pallet_data_preservers::Config,
     Runtime: pallet_stream_payment::Config,
+    Runtime: pallet_invulnerables::Config,
     Runtime::AccountId: From<[u8; 32]>,
     <Runtime as pallet_balances::Config>::RuntimeHoldReason: From<pallet_registrar::HoldReason>,
     <Runtime as pallet_balances::Config>::Balance: From<<<Runtime as pallet_registrar::Config>::Currency as frame_support::traits::fungible::Inspect<Runtime::AccountId>>::Balance>,
@@ -1134,6 +1187,8 @@ where
         //let migrate_stream_payment_new_config_items = MigrateStreamPaymentNewConfigFields::<Runtime>(Default::default());
         //let migrate_pallet_session_v0_to_v1 = MigratePalletSessionV0toV1::<Runtime>(Default::default());
         //let migrate_data_preservers_profiles = DataPreserversProfileContentMigration::<Runtime>(Default::default());
+        let migrate_invulnerables_pending_rewards_account =
+            InvulnerablesPendingRewardsAccountMigration::<Runtime>(Default::default());
 
         vec![
             // Applied in runtime 400
@@ -1162,6 +1217,7 @@ where
             // Box::new(migrate_pallet_session_v0_to_v1),
             // Applied in runtime 1600
             //Box::new(migrate_data_preservers_profiles),
+            Box::new(migrate_invulnerables_pending_rewards_account),
         ]
     }
 }
@@ -1181,6 +1237,7 @@ where
     Runtime: pallet_xcm::Config,
     Runtime: pallet_stream_payment::Config,
     Runtime: pallet_inactivity_tracking::Config,
+    Runtime: pallet_invulnerables::Config,
     <Runtime as pallet_balances::Config>::RuntimeHoldReason:
         From<pallet_pooled_staking::HoldReason>,
     Runtime: pallet_foreign_asset_creator::Config,
@@ -1225,7 +1282,8 @@ where
         /*let migrate_offline_marking_storage =
             OfflineMarkingStorageMigration::<Runtime>(Default::default());
             let migrate_data_preservers_profiles = DataPreserversProfileContentMigration::<Runtime>(Default::default());*/
-
+        let migrate_invulnerables_pending_rewards_account =
+            InvulnerablesPendingRewardsAccountMigration::<Runtime>(Default::default());
 
         vec![
             // Applied in runtime 200
@@ -1276,6 +1334,7 @@ where
             //Box::new(migrate_offline_marking_storage),
             // Applied in runtime 1600
             //Box::new(migrate_data_preservers_profiles),
+            Box::new(migrate_invulnerables_pending_rewards_account),
         ]
     }
 }
@@ -1387,6 +1446,133 @@ mod relay {
         }
     }
 
+    /// `Slash` before the external source of the offender was added to it.
+    #[derive(parity_scale_codec::Encode, parity_scale_codec::Decode)]
+    struct OldSlash<AccountId, SlashId> {
+        external_idx: u64,
+        validator: AccountId,
+        reporters: Vec<AccountId>,
+        slash_id: SlashId,
+        percentage: Perbill,
+        confirmed: bool,
+    }
+
+    impl<AccountId, SlashId> OldSlash<AccountId, SlashId> {
+        fn with_default_source(
+            self,
+        ) -> pallet_external_validator_slashes::Slash<AccountId, SlashId> {
+            pallet_external_validator_slashes::Slash {
+                external_idx: self.external_idx,
+                source: pallet_external_validators::DEFAULT_EXTERNAL_SOURCE,
+                validator: self.validator,
+                reporters: self.reporters,
+                slash_id: self.slash_id,
+                percentage: self.percentage,
+                confirmed: self.confirmed,
+            }
+        }
+    }
+
+    /// Adds the external source to pending and unreported slashes. Every slash stored before
+    /// this migration belongs to the default source, the only one there was.
+    pub struct ExternalValidatorSlashesSourceMigration<Runtime>(pub PhantomData<Runtime>);
+
+    impl<Runtime> Migration for ExternalValidatorSlashesSourceMigration<Runtime>
+    where
+        Runtime: pallet_external_validator_slashes::Config,
+    {
+        fn friendly_name(&self) -> &str {
+            "TM_ExternalValidatorSlashesSourceMigration"
+        }
+
+        fn migrate(&self, _available_weight: Weight) -> Weight {
+            type OldSlashOf<Runtime> = OldSlash<
+                <Runtime as frame_system::Config>::AccountId,
+                <Runtime as pallet_external_validator_slashes::Config>::SlashId,
+            >;
+
+            let mut translated = 0u64;
+            pallet_external_validator_slashes::Slashes::<Runtime>::translate::<
+                Vec<OldSlashOf<Runtime>>,
+                _,
+            >(|_era, old_slashes| {
+                translated += 1;
+                Some(
+                    old_slashes
+                        .into_iter()
+                        .map(OldSlash::with_default_source)
+                        .collect(),
+                )
+            });
+
+            let _ = pallet_external_validator_slashes::UnreportedSlashesQueue::<Runtime>::translate::<
+                alloc::collections::vec_deque::VecDeque<OldSlashOf<Runtime>>,
+                _,
+            >(|old_queue| {
+                old_queue.map(|old_queue| {
+                    old_queue
+                        .into_iter()
+                        .map(OldSlash::with_default_source)
+                        .collect()
+                })
+            });
+
+            // One db read and one db write per era with slashes, plus the queue
+            Runtime::DbWeight::get()
+                .reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade(&self) -> Result<Vec<u8>, sp_runtime::DispatchError> {
+            use parity_scale_codec::{Decode, Encode};
+
+            let slashes_count =
+                pallet_external_validator_slashes::Slashes::<Runtime>::iter_keys().count() as u32;
+            let queue_len: u32 = frame_support::storage::unhashed::get_raw(
+                &pallet_external_validator_slashes::UnreportedSlashesQueue::<Runtime>::hashed_key(),
+            )
+            .map(|raw| {
+                parity_scale_codec::Compact::<u32>::decode(&mut &raw[..])
+                    .expect("queue starts with its length")
+                    .0
+            })
+            .unwrap_or_default();
+
+            Ok((slashes_count, queue_len).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(&self, state: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+            use parity_scale_codec::Decode;
+
+            let (slashes_count, queue_len) =
+                <(u32, u32)>::decode(&mut &state[..]).expect("state to be decoded properly");
+
+            let mut new_slashes_count = 0u32;
+            for (_era, slashes) in pallet_external_validator_slashes::Slashes::<Runtime>::iter() {
+                new_slashes_count += 1;
+                ensure!(
+                    slashes
+                        .iter()
+                        .all(|slash| slash.source
+                            == pallet_external_validators::DEFAULT_EXTERNAL_SOURCE),
+                    "Migrated slashes must belong to the default source"
+                );
+            }
+            ensure!(
+                new_slashes_count == slashes_count,
+                "All eras with slashes must be migrated"
+            );
+            ensure!(
+                pallet_external_validator_slashes::UnreportedSlashesQueue::<Runtime>::get().len()
+                    == queue_len as usize,
+                "All unreported slashes must be migrated"
+            );
+
+            Ok(())
+        }
+    }
+
     pub struct DancelightMigrations<Runtime>(PhantomData<Runtime>);
 
     impl<Runtime> GetMigrations for DancelightMigrations<Runtime>
@@ -1404,6 +1590,7 @@ mod relay {
         Runtime: pallet_xcm::Config,
         Runtime: pallet_inactivity_tracking::Config,
         Runtime: pallet_data_preservers::Config,
+        Runtime: pallet_invulnerables::Config,
     {
         fn get_migrations() -> Vec<Box<dyn Migration>> {
             /*let migrate_config_full_rotation_mode =
@@ -1427,6 +1614,10 @@ mod relay {
                 OfflineMarkingStorageMigration::<Runtime>(Default::default());
             let migrate_data_preservers_profiles =
                 DataPreserversProfileContentMigration::<Runtime>(Default::default());*/
+            let external_validator_slashes_source =
+                ExternalValidatorSlashesSourceMigration::<Runtime>(Default::default());
+            let migrate_invulnerables_pending_rewards_account =
+                InvulnerablesPendingRewardsAccountMigration::<Runtime>(Default::default());
 
             vec![
                 // Applied in runtime 1000
@@ -1455,6 +1646,8 @@ mod relay {
                 // Box::new(migrate_offline_marking_storage),
                 // Applied in runtime 1600
                 // Box::new(migrate_data_preservers_profiles),
+                Box::new(external_validator_slashes_source),
+                Box::new(migrate_invulnerables_pending_rewards_account),
             ]
         }
     }
@@ -1468,6 +1661,8 @@ mod relay {
         Runtime: snowbridge_pallet_system::Config,
         Runtime: pallet_inactivity_tracking::Config,
         Runtime: pallet_data_preservers::Config,
+        Runtime: pallet_external_validator_slashes::Config,
+        Runtime: pallet_invulnerables::Config,
     {
         fn get_migrations() -> Vec<Box<dyn Migration>> {
             /*let migrate_pallet_session_v0_to_v1 =
@@ -1486,6 +1681,10 @@ mod relay {
 
             let _migrate_data_preservers_profiles =
                 DataPreserversProfileContentMigration::<Runtime>(Default::default());*/
+            let external_validator_slashes_source =
+                ExternalValidatorSlashesSourceMigration::<Runtime>(Default::default());
+            let migrate_invulnerables_pending_rewards_account =
+                InvulnerablesPendingRewardsAccountMigration::<Runtime>(Default::default());
 
             vec![
                 // Applied in runtime 1400
@@ -1496,6 +1695,8 @@ mod relay {
                 // Box::new(migrate_offline_marking_storage),
                 // Applied in runtime 1600
                 // Box::new(migrate_data_preservers_profiles),
+                Box::new(external_validator_slashes_source),
+                Box::new(migrate_invulnerables_pending_rewards_account),
             ]
         }
     }
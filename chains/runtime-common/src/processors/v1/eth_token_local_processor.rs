@@ -21,20 +21,25 @@ use alloc::vec;
 use core::marker::PhantomData;
 use parity_scale_codec::{DecodeAll, Encode, EncodeLike};
 use snowbridge_core::Channel;
+use snowbridge_core::TokenIdOf;
 use snowbridge_inbound_queue_primitives::v1::{
     Command, Destination, Envelope, MessageProcessor, MessageV1, VersionedXcmMessage,
 };
-use sp_core::{Get, H160};
+use sp_core::{Get, H160, H256};
 use sp_runtime::{DispatchError, DispatchResult};
+use tp_bridge::{BridgeTransferGuard, TransferDirection};
 use xcm::latest::{
     prelude::*, Asset as XcmAsset, AssetId as XcmAssetId, Assets as XcmAssets, ExecuteXcm,
     Fungibility, Junctions::*,
 };
-use xcm_executor::traits::{TransactAsset, WeightBounds};
+use xcm_executor::traits::{ConvertLocation, TransactAsset, WeightBounds};
 
 /// `EthTokensLocalProcessor` is responsible for receiving and processing the ETH native
 /// token and ERC20s coming from Ethereum with Tanssi chain or container-chains as final destinations.
-/// TODO: add support for container transfers
+///
+/// Tokens that cannot be delivered are trapped for the beneficiary, or for the fallback claimer of
+/// `pallet_bridge_asset_recovery` if the beneficiary is an `AccountKey20`, and can be claimed to a
+/// new destination through that pallet.
 pub struct EthTokensLocalProcessor<
     T,
     XcmProcessor,
@@ -67,9 +72,10 @@ where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_ethereum_token_transfers::Config
         + pallet_foreign_asset_creator::Config
-        + pallet_xcm::Config,
+        + pallet_xcm::Config
+        + pallet_bridge_asset_recovery::Config,
     <T as frame_system::Config>::RuntimeEvent: From<pallet_xcm::Event<T>>,
-    <T as frame_system::Config>::AccountId: Into<Location>,
+    <T as frame_system::Config>::AccountId: Into<Location> + From<[u8; 32]>,
     XcmProcessor: ExecuteXcm<<T as pallet_xcm::Config>::RuntimeCall>,
     XcmWeigher: WeightBounds<<T as pallet_xcm::Config>::RuntimeCall>,
     AssetTransactor: TransactAsset,
@@ -107,14 +113,30 @@ where
         let eth_transfer_data = Self::decode_message_for_eth_transfer(envelope.payload.as_slice())
             .ok_or(DispatchError::Other("unexpected message"))?;
 
+        let token_id = TokenIdOf::convert_location(&eth_transfer_data.token_location)
+            .ok_or(DispatchError::Other("unable to compute token id"))?;
+        let amount = eth_transfer_data.amount;
+        <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::ensure_transfer_allowed(
+            token_id,
+            TransferDirection::Inbound,
+            amount,
+        )?;
+
         match eth_transfer_data.destination {
             Destination::AccountId32 { id: _ } => {
-                Self::process_xcm_local_native_eth_transfer(eth_transfer_data)
+                Self::process_xcm_local_native_eth_transfer(eth_transfer_data, envelope.message_id)
             }
             Destination::ForeignAccountId32 { .. } | Destination::ForeignAccountId20 { .. } => {
-                Self::process_xcm_container_eth_transfer(eth_transfer_data)
+                Self::process_xcm_container_eth_transfer(eth_transfer_data, envelope.message_id)
             }
-        }
+        }?;
+
+        <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::note_transfer(
+            token_id,
+            TransferDirection::Inbound,
+            amount,
+        );
+        Ok(())
     }
 }
 
@@ -134,9 +156,12 @@ impl<T, XcmProcessor, XcmWeigher, AssetTransactor, EthereumLocation, EthereumNet
         EthereumNetwork,
     >
 where
-    T: frame_system::Config + pallet_xcm::Config + pallet_ethereum_token_transfers::Config,
+    T: frame_system::Config
+        + pallet_xcm::Config
+        + pallet_ethereum_token_transfers::Config
+        + pallet_bridge_asset_recovery::Config,
     <T as frame_system::Config>::RuntimeEvent: From<pallet_xcm::Event<T>>,
-    <T as frame_system::Config>::AccountId: Into<Location>,
+    <T as frame_system::Config>::AccountId: Into<Location> + From<[u8; 32]>,
     XcmProcessor: ExecuteXcm<<T as pallet_xcm::Config>::RuntimeCall>,
     XcmWeigher: WeightBounds<<T as pallet_xcm::Config>::RuntimeCall>,
     AssetTransactor: TransactAsset,
@@ -184,7 +209,10 @@ where
         }
     }
 
-    fn process_xcm_local_native_eth_transfer(eth_transfer_data: EthTransferData) -> DispatchResult {
+    fn process_xcm_local_native_eth_transfer(
+        eth_transfer_data: EthTransferData,
+        message_id: H256,
+    ) -> DispatchResult {
         let assets_to_holding: XcmAssets = vec![XcmAsset {
             id: XcmAssetId::from(eth_transfer_data.token_location),
             fun: Fungibility::Fungible(eth_transfer_data.amount),
@@ -199,17 +227,29 @@ where
             }
         };
 
+        let beneficiary = Location::new(
+            0,
+            [AccountId32 {
+                network: None,
+                id: destination_account,
+            }],
+        );
+
+        // If the deposit fails the assets are trapped for the beneficiary, indexed under the
+        // bridge message id.
         let mut xcm = Xcm::<<T as pallet_xcm::Config>::RuntimeCall>(vec![
+            SetHints {
+                hints: vec![AssetClaimer {
+                    location: beneficiary.clone(),
+                }]
+                .try_into()
+                .expect("checked statically, qed"),
+            },
+            SetTopic(message_id.into()),
             ReserveAssetDeposited(assets_to_holding),
             DepositAsset {
                 assets: AllCounted(1).into(),
-                beneficiary: Location::new(
-                    0,
-                    [AccountId32 {
-                        network: None,
-                        id: destination_account,
-                    }],
-                ),
+                beneficiary,
             },
         ]);
 
@@ -240,13 +280,18 @@ where
         Ok(())
     }
 
-    fn process_xcm_container_eth_transfer(eth_transfer_data: EthTransferData) -> DispatchResult {
-        // Get the para_id, beneficiary and fee from the destination
-        let (para_id, beneficiary, fee) = match eth_transfer_data.destination {
+    fn process_xcm_container_eth_transfer(
+        eth_transfer_data: EthTransferData,
+        message_id: H256,
+    ) -> DispatchResult {
+        // Get the para_id, beneficiary and fee from the destination, and who can claim the
+        // tokens if they cannot be sent
+        let (para_id, beneficiary, fee, claimer) = match eth_transfer_data.destination {
             Destination::ForeignAccountId32 { para_id, id, fee } => (
                 para_id,
                 Location::new(0, [AccountId32 { network: None, id }]),
                 fee,
+                id.into(),
             ),
             Destination::ForeignAccountId20 { para_id, id, fee } => (
                 para_id,
@@ -258,6 +303,7 @@ where
                     }],
                 ),
                 fee,
+                <T as pallet_bridge_asset_recovery::Config>::FallbackClaimer::get(),
             ),
             _ => {
                 log::error!(
@@ -388,6 +434,20 @@ where
                     para_id,
                     e
                 );
+
+                // Take the tokens and the fee back from the container sovereign account
+                let mut assets_to_trap = vec![eth_token_location];
+                if fee > 0 {
+                    assets_to_trap.push(asset_fee_relay);
+                }
+                if let Err(e) = pallet_bridge_asset_recovery::Pallet::<T>::trap_assets(
+                    &container_location,
+                    assets_to_trap.into(),
+                    &claimer,
+                    message_id,
+                ) {
+                    log::error!("EthTokensLocalProcessor: failed to trap assets: {:?}", e);
+                }
             }
         };
 
@@ -21,13 +21,18 @@ use alloc::vec;
 use core::marker::PhantomData;
 use snowbridge_core::Channel;
 use snowbridge_inbound_queue_primitives::v1::{Destination, Envelope, MessageProcessor};
-use sp_core::Get;
+use sp_core::{Get, H256};
 use sp_runtime::{traits::MaybeEquivalence, DispatchError, DispatchResult};
+use tp_bridge::{BridgeTransferGuard, TransferDirection};
 use xcm::prelude::*;
 use xcm_executor::traits::TransactAsset;
 
 /// `NativeContainerTokensProcessor` is responsible for receiving and processing native container
 /// chain tokens coming from Ethereum and forwarding them to the container chain via Tanssi through XCM.
+///
+/// The transfer is sent through `pallet_bridge_asset_recovery`. If the fee can't be paid or the
+/// message can't be sent, nothing is paid and the whole transfer, tokens and fee, is kept for the
+/// beneficiary, who can send it again once the container chain is reachable.
 pub struct NativeContainerTokensProcessor<
     T,
     AssetTransactor,
@@ -66,9 +71,10 @@ where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_ethereum_token_transfers::Config
         + snowbridge_pallet_system::Config
-        + pallet_xcm::Config,
+        + pallet_xcm::Config
+        + pallet_bridge_asset_recovery::Config,
     <T as frame_system::Config>::RuntimeEvent: From<pallet_xcm::Event<T>>,
-    <T as frame_system::Config>::AccountId: Into<Location>,
+    <T as frame_system::Config>::AccountId: Into<Location> + From<[u8; 32]>,
     AssetTransactor: TransactAsset,
     EthereumLocation: Get<Location>,
     EthereumNetwork: Get<NetworkId>,
@@ -114,7 +120,24 @@ where
     fn process_message(_channel: Channel, envelope: Envelope) -> DispatchResult {
         match Self::get_token_data_and_location(&envelope.payload) {
             TokenDataResult::Success(token_data, token_location) => {
-                Self::process_native_container_token_transfer(token_data, token_location);
+                let (token_id, amount) = (token_data.token_id, token_data.amount);
+                <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::ensure_transfer_allowed(
+                    token_id,
+                    TransferDirection::Inbound,
+                    amount,
+                )?;
+
+                Self::process_native_container_token_transfer(
+                    token_data,
+                    token_location,
+                    envelope.message_id,
+                );
+                // Failures after this point are only logged, the volume is always recorded.
+                <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::note_transfer(
+                    token_id,
+                    TransferDirection::Inbound,
+                    amount,
+                );
                 Ok(())
             }
             TokenDataResult::DecodeFailure => Err(DispatchError::Other(
@@ -167,9 +190,10 @@ where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_ethereum_token_transfers::Config
         + snowbridge_pallet_system::Config
-        + pallet_xcm::Config,
+        + pallet_xcm::Config
+        + pallet_bridge_asset_recovery::Config,
     <T as frame_system::Config>::RuntimeEvent: From<pallet_xcm::Event<T>>,
-    <T as frame_system::Config>::AccountId: Into<Location>,
+    <T as frame_system::Config>::AccountId: Into<Location> + From<[u8; 32]>,
     AssetTransactor: TransactAsset,
     EthereumLocation: Get<Location>,
     EthereumNetwork: Get<NetworkId>,
@@ -239,6 +263,7 @@ where
     fn process_native_container_token_transfer(
         token_data: NativeTokenTransferData,
         token_location: Location,
+        message_id: H256,
     ) {
         let interior = match token_location.interior().clone().split_global().ok() {
             Some((_, interior)) => interior,
@@ -250,10 +275,11 @@ where
             }
         };
 
-        let (beneficiary, container_fee, container_para_id) = match token_data.destination {
+        let (beneficiary, container_fee, container_para_id, claimer) = match token_data.destination
+        {
             Destination::ForeignAccountId32 { para_id, id, fee } => {
                 let beneficiary = Location::new(0, [AccountId32 { network: None, id }]);
-                (beneficiary, fee, para_id)
+                (beneficiary, fee, para_id, id.into())
             }
             Destination::ForeignAccountId20 { para_id, id, fee } => {
                 let beneficiary = Location::new(
@@ -263,7 +289,12 @@ where
                         key: id,
                     }],
                 );
-                (beneficiary, fee, para_id)
+                (
+                    beneficiary,
+                    fee,
+                    para_id,
+                    <T as pallet_bridge_asset_recovery::Config>::FallbackClaimer::get(),
+                )
             }
             _ => {
                 log::error!("NativeContainerTokensProcessor::process_native_token_transfer: invalid destination");
@@ -306,26 +337,6 @@ where
             }
         };
 
-        let dummy_context = XcmContext {
-            origin: None,
-            message_id: Default::default(),
-            topic: None,
-        };
-
-        // Transfer fee from FeesAccount to container sovereign account
-        if let Err(e) = AssetTransactor::transfer_asset(
-            &asset_fee_relay,
-            &T::FeesAccount::get().into(),
-            &container_location,
-            &dummy_context,
-        ) {
-            log::error!(
-                "NativeContainerTokensProcessor: failed to transfer fee from FeesAccount to container sovereign account: {:?}",
-                e
-            );
-            return;
-        }
-
         // Reanchor Ethereum location to the container chain's point of view
         let bridge_location = match EthereumLocation::get().clone().reanchored(
             &container_location,
@@ -368,27 +379,25 @@ where
             }])),
         ]);
 
-        send_xcm::<<T as pallet_xcm::Config>::XcmRouter>(
+        // The fee is moved from FeesAccount to the container sovereign account when the message
+        // is sent.
+        if let Some(sent_message_id) = pallet_bridge_asset_recovery::Pallet::<T>::send_transfer(
             container_location.clone(),
             remote_xcm.clone(),
-        )
-        .map(|(message_id, _price)| {
+            asset_fee_relay,
+            T::FeesAccount::get().into(),
+            &claimer,
+            message_id,
+        ) {
             let xcm_event: pallet_xcm::Event<T> = pallet_xcm::Event::Sent {
                 origin: Here.into_location(),
                 destination: container_location,
                 message: remote_xcm,
-                message_id,
+                message_id: sent_message_id,
             };
             frame_system::Pallet::<T>::deposit_event(
                 <T as frame_system::Config>::RuntimeEvent::from(xcm_event),
             );
-        })
-        .map_err(|e| {
-            log::error!(
-                "NativeContainerTokensProcessor: XCM send failed with error: {:?}",
-                e
-            );
-        })
-        .ok();
+        }
     }
 }
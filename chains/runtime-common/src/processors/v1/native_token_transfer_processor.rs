@@ -26,16 +26,24 @@ use snowbridge_core::Channel;
 use snowbridge_inbound_queue_primitives::v1::{Destination, Envelope, MessageProcessor};
 use sp_core::Get;
 use sp_runtime::{traits::MaybeEquivalence, DispatchError, DispatchResult};
+use tp_bridge::{BridgeTransferGuard, TransferDirection};
+use xcm::latest::Location;
 
 /// `NativeTokenTransferMessageProcessor` is responsible for receiving and processing the Tanssi
 /// native token sent from Ethereum. If the message is valid, it performs the token transfer
 /// from the Ethereum sovereign account to the specified destination account.
+///
+/// Transfers rejected by the bridge guard make the message fail, so that it can be submitted
+/// again once the limits allow it. If the transfer itself fails, for example because the amount
+/// is below the existential deposit of a new account, the tokens are trapped for the destination
+/// account, which can claim them through `pallet_bridge_asset_recovery`.
 pub struct NativeTokenTransferMessageProcessor<T>(PhantomData<T>);
 impl<T> MessageProcessor for NativeTokenTransferMessageProcessor<T>
 where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_ethereum_token_transfers::Config
-        + snowbridge_pallet_system::Config,
+        + snowbridge_pallet_system::Config
+        + pallet_bridge_asset_recovery::Config,
     T::AccountId: From<[u8; 32]>,
 {
     fn can_process_message(channel: &Channel, envelope: &Envelope) -> bool {
@@ -87,19 +95,49 @@ where
                 Destination::AccountId32 {
                     id: destination_account,
                 } => {
+                    <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::ensure_transfer_allowed(
+                        token_data.token_id,
+                        TransferDirection::Inbound,
+                        token_data.amount,
+                    )?;
+
                     // Transfer the amounts of tokens from Ethereum sov account to the destination
                     let sovereign_account = T::EthereumSovereignAccount::get();
+                    let destination_account: T::AccountId = destination_account.into();
 
-                    if let Err(e) = T::Currency::transfer(
+                    let received = T::Currency::transfer(
                         &sovereign_account,
-                        &destination_account.into(),
+                        &destination_account,
                         token_data.amount.into(),
                         Preservation::Preserve,
-                    ) {
+                    )
+                    .map(|_| ())
+                    .or_else(|e| {
+                        log::warn!(
+                            "NativeTokenTransferMessageProcessor: Error transferring tokens, trapping them: {:?}",
+                            e
+                        );
+                        pallet_bridge_asset_recovery::Pallet::<T>::trap_assets(
+                            &pallet_bridge_asset_recovery::Pallet::<T>::account_location(
+                                &sovereign_account,
+                            ),
+                            (Location::here(), token_data.amount).into(),
+                            &destination_account,
+                            envelope.message_id,
+                        )
+                    });
+
+                    if let Err(e) = received {
                         log::warn!(
-                            "NativeTokenTransferMessageProcessor: Error transferring tokens: {:?}",
+                            "NativeTokenTransferMessageProcessor: Error trapping tokens: {:?}",
                             e
                         );
+                    } else {
+                        <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::note_transfer(
+                            token_data.token_id,
+                            TransferDirection::Inbound,
+                            token_data.amount,
+                        );
                     }
 
                     Ok(())
@@ -17,7 +17,8 @@
 extern crate alloc;
 
 use crate::processors::v2::{
-    execute_xcm, reanchor_location_to_tanssi, ExtractedXcmConstructionInfo,
+    ensure_inbound_transfers_allowed, execute_xcm, guarded_inbound_transfers,
+    note_inbound_transfers, reanchor_location_to_tanssi, ExtractedXcmConstructionInfo,
     FallbackMessageProcessor,
 };
 use core::marker::PhantomData;
@@ -90,7 +91,8 @@ impl<
 where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_xcm::Config
-        + snowbridge_pallet_system::Config,
+        + snowbridge_pallet_system::Config
+        + pallet_ethereum_token_transfers::Config,
     [u8; 32]: From<<T as frame_system::Config>::AccountId>,
     GatewayAddress: Get<H160>,
     DefaultClaimer: Get<<T as frame_system::Config>::AccountId>,
@@ -105,6 +107,10 @@ where
         _who: AccountId,
         message: Message,
     ) -> Result<Option<Weight>, MessageProcessorError> {
+        let guarded_transfers =
+            guarded_inbound_transfers(EthereumNetwork::get(), &message.assets, message.value);
+        ensure_inbound_transfers_allowed::<T>(&guarded_transfers)?;
+
         let extracted_message: ExtractedXcmConstructionInfo<
             <T as pallet_xcm::Config>::RuntimeCall,
         > = ExtractedXcmConstructionInfo {
@@ -152,6 +158,9 @@ where
             ))
         })?;
 
+        // From here on the message is not reverted, the tokens are either deposited or trapped
+        note_inbound_transfers::<T>(guarded_transfers);
+
         // Depending upon the content of raw xcm, it might be the case that it is not fully revertible
         // (i.e xcm that sends a message in another container chain and then return an error).
         // Another reason we are not returning error here as otherwise the tx will be reverted and assets will be in limbo in ethereum.
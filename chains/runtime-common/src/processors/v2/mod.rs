@@ -36,6 +36,7 @@ use sp_core::{H160, H256};
 use sp_io::hashing::blake2_256;
 use sp_runtime::traits::MaybeEquivalence;
 use sp_runtime::DispatchError;
+use tp_bridge::{ethereum_token_id, BridgeTransferGuard, GuardedTransfers, TransferDirection};
 use xcm::latest::prelude::*;
 use xcm_executor::traits::WeightBounds;
 
@@ -245,6 +246,68 @@ where
     Ok(asset_transfers)
 }
 
+/// Tokens brought from Ethereum by a message, as tracked by the bridge guard.
+pub fn guarded_inbound_transfers(
+    eth_network_id: NetworkId,
+    assets: &[EthereumAsset],
+    eth_value: u128,
+) -> GuardedTransfers {
+    let mut transfers: GuardedTransfers = assets
+        .iter()
+        .filter_map(|asset| match asset {
+            EthereumAsset::NativeTokenERC20 { token_id, value } => {
+                ethereum_token_id(eth_network_id, *token_id).map(|token_id| (token_id, *value))
+            }
+            EthereumAsset::ForeignTokenERC20 { token_id, value } => Some((*token_id, *value)),
+        })
+        .collect();
+
+    if eth_value > 0 {
+        transfers.extend(
+            ethereum_token_id(eth_network_id, H160::zero()).map(|token_id| (token_id, eth_value)),
+        );
+    }
+
+    transfers
+}
+
+/// Ensure that the bridge guard lets all `transfers` in. Returning an error reverts the message
+/// so that it can be relayed again once the guard allows it.
+pub fn ensure_inbound_transfers_allowed<T>(
+    transfers: &GuardedTransfers,
+) -> Result<(), MessageProcessorError>
+where
+    T: pallet_ethereum_token_transfers::Config,
+{
+    for (token_id, amount) in transfers {
+        <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::ensure_transfer_allowed(
+            *token_id,
+            TransferDirection::Inbound,
+            *amount,
+        )
+        .map_err(|err| {
+            log::warn!("Inbound transfer rejected by the bridge guard: {:?}", err);
+            MessageProcessorError::ProcessMessage(err)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Record `transfers` in the bridge guard once the message was processed.
+pub fn note_inbound_transfers<T>(transfers: GuardedTransfers)
+where
+    T: pallet_ethereum_token_transfers::Config,
+{
+    for (token_id, amount) in transfers {
+        <T as pallet_ethereum_token_transfers::Config>::BridgeGuard::note_transfer(
+            token_id,
+            TransferDirection::Inbound,
+            amount,
+        );
+    }
+}
+
 pub fn prepare_raw_message_xcm_instructions<T>(
     eth_network_id: NetworkId,
     eth_chain_universal_location: &InteriorLocation,
@@ -17,9 +17,11 @@
 extern crate alloc;
 
 use crate::processors::v2::{
-    execute_xcm, fallback_message_processor::AssetTrapFallbackProcessor,
-    prepare_raw_message_xcm_instructions, CodecError, ExtractedXcmConstructionInfo,
-    FallbackMessageProcessor, MessageExtractionError, MessageProcessorWithFallback, RawPayload,
+    ensure_inbound_transfers_allowed, execute_xcm,
+    fallback_message_processor::AssetTrapFallbackProcessor, guarded_inbound_transfers,
+    note_inbound_transfers, prepare_raw_message_xcm_instructions, CodecError,
+    ExtractedXcmConstructionInfo, FallbackMessageProcessor, MessageExtractionError,
+    MessageProcessorWithFallback, RawPayload,
 };
 use crate::processors::v2::{reanchor_location_to_tanssi, RAW_MESSAGE_PROCESSOR_TOPIC_PREFIX};
 use alloc::boxed::Box;
@@ -183,7 +185,8 @@ impl<
 where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_xcm::Config
-        + snowbridge_pallet_system::Config,
+        + snowbridge_pallet_system::Config
+        + pallet_ethereum_token_transfers::Config,
     [u8; 32]: From<<T as frame_system::Config>::AccountId>,
     GatewayAddress: Get<H160>,
     DefaultClaimer: Get<<T as frame_system::Config>::AccountId>,
@@ -218,6 +221,13 @@ where
         _sender: AccountId,
         extracted_message: Self::ExtractedMessage,
     ) -> Result<Option<Weight>, MessageProcessorError> {
+        let guarded_transfers = guarded_inbound_transfers(
+            EthereumNetwork::get(),
+            &extracted_message.assets,
+            extracted_message.eth_value,
+        );
+        ensure_inbound_transfers_allowed::<T>(&guarded_transfers)?;
+
         let prepared_xcm = prepare_raw_message_xcm_instructions::<T>(
             EthereumNetwork::get(),
             &EthereumUniversalLocation::get(),
@@ -253,6 +263,9 @@ where
             ))
         })?;
 
+        // From here on the message is not reverted, the tokens are either deposited or trapped
+        note_inbound_transfers::<T>(guarded_transfers);
+
         // Depending upon the content of raw xcm, it might be the case that it is not fully revertible
         // (i.e xcm that sends a message in another container chain and then return an error).
         // Another reason we are not returning error here as otherwise the tx will be reverted and assets will be in limbo in ethereum.
@@ -31,7 +31,8 @@ use snowbridge_inbound_queue_primitives::v2::{Message, MessageProcessorError, Pa
 use sp_core::{Get, H160};
 use sp_runtime::Weight;
 use tp_bridge::symbiotic_message_processor::{
-    InboundCommand, Message as SymbioticMessage, Payload as SymbioticPayload, MAGIC_BYTES,
+    process_governance_command, InboundCommand, InboundCommandV2, Message as SymbioticMessage,
+    Payload as SymbioticPayload, SymbioticGovernanceHandler, MAGIC_BYTES,
 };
 use v2_processor_proc_macro::MessageProcessor;
 use xcm::latest::{ExecuteXcm, InteriorLocation, NetworkId};
@@ -99,7 +100,10 @@ pub fn try_extract_message<T: pallet_external_validators::Config>(
     }
 }
 
-pub fn process_message<T: pallet_external_validators::Config>(
+pub fn process_message<
+    T: pallet_external_validators::Config,
+    Governance: SymbioticGovernanceHandler,
+>(
     symbiotic_message: SymbioticMessage<T>,
 ) -> Result<(), MessageProcessorError> {
     match symbiotic_message {
@@ -120,6 +124,28 @@ pub fn process_message<T: pallet_external_validators::Config>(
             .map_err(|error| MessageProcessorError::ProcessMessage(error))?;
             Ok(())
         }
+        tp_bridge::symbiotic_message_processor::Message::V2(
+            InboundCommandV2::ReceiveValidatorsWithStake {
+                validators,
+                external_index,
+            },
+        ) => {
+            // Errors are returned for the same reasons as V1. The gateway feeds the default source.
+            pallet_external_validators::Pallet::<T>::set_external_validators_with_stake(
+                pallet_external_validators::DEFAULT_EXTERNAL_SOURCE,
+                validators,
+                external_index,
+            )
+            .map_err(|error| MessageProcessorError::ProcessMessage(error))?;
+            Ok(())
+        }
+        tp_bridge::symbiotic_message_processor::Message::V3(command) => {
+            // The origin was already checked to be the gateway, which plays the role of the
+            // primary governance channel in V2.
+            process_governance_command::<T, Governance>(command)
+                .map_err(|error| MessageProcessorError::ProcessMessage(error))?;
+            Ok(())
+        }
     }
 }
 
@@ -138,11 +164,15 @@ pub fn process_message<T: pallet_external_validators::Config>(
 ///
 /// # Supported Commands
 ///
-/// Currently, processes `InboundCommand::ReceiveValidators` which:
+/// Currently, processes `InboundCommand::ReceiveValidators` and
+/// `InboundCommandV2::ReceiveValidatorsWithStake` which:
 /// - Updates the external validator set via `pallet_external_validators`
 /// - Tracks validator changes by external index
 /// - Returns errors on validation failures (no asset trapping needed)
 ///
+/// It also processes the governance commands of `InboundCommandV3`, as long as they are enabled
+/// by `Governance`.
+///
 /// # Fallback Behavior
 ///
 /// Uses `SymbioticFallbackProcessor` which conditionally applies asset trapping:
@@ -164,6 +194,7 @@ pub struct SymbioticMessageProcessor<
     XcmWeigher,
     MaxXcmWeight,
     SetExternalValidatorsWeight,
+    Governance,
 >(
     PhantomData<(
         T,
@@ -176,6 +207,7 @@ pub struct SymbioticMessageProcessor<
         XcmWeigher,
         MaxXcmWeight,
         SetExternalValidatorsWeight,
+        Governance,
     )>,
 );
 
@@ -191,6 +223,7 @@ impl<
         XcmWeigher,
         MaxXcmWeight,
         SetExternalValidatorsWeight,
+        Governance,
     > MessageProcessorWithFallback<AccountId>
     for SymbioticMessageProcessor<
         T,
@@ -203,12 +236,14 @@ impl<
         XcmWeigher,
         MaxXcmWeight,
         SetExternalValidatorsWeight,
+        Governance,
     >
 where
     T: snowbridge_pallet_inbound_queue::Config
         + pallet_xcm::Config
         + snowbridge_pallet_system::Config
-        + pallet_external_validators::Config,
+        + pallet_external_validators::Config
+        + pallet_ethereum_token_transfers::Config,
     [u8; 32]: From<<T as frame_system::Config>::AccountId>,
     GatewayAddress: Get<H160>,
     DefaultClaimer: Get<<T as frame_system::Config>::AccountId>,
@@ -219,6 +254,7 @@ where
     XcmWeigher: WeightBounds<<T as pallet_xcm::Config>::RuntimeCall>,
     MaxXcmWeight: Get<Weight>,
     SetExternalValidatorsWeight: Get<Weight>,
+    Governance: SymbioticGovernanceHandler,
 {
     type Fallback = SymbioticFallbackProcessor<
         T,
@@ -249,7 +285,8 @@ where
         _sender: AccountId,
         extracted_message: Self::ExtractedMessage,
     ) -> Result<Option<Weight>, MessageProcessorError> {
-        process_message(extracted_message).map(|_| Some(SetExternalValidatorsWeight::get()))
+        process_message::<T, Governance>(extracted_message)
+            .map(|_| Some(SetExternalValidatorsWeight::get()))
     }
 
     fn worst_case_message_processor_weight() -> Weight {
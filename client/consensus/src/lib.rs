@@ -33,12 +33,13 @@ pub use {
     crate::consensus_orchestrator::OrchestratorAuraWorkerAuxData,
     cumulus_primitives_core::ParaId,
     cumulus_relay_chain_interface::{call_runtime_api, RelayChainInterface},
-    dc_orchestrator_chain_interface::OrchestratorChainInterface,
+    dc_orchestrator_chain_interface::{OrchestratorChainInterface, OrchestratorChainResult},
     dp_consensus::TanssiAuthorityAssignmentApi,
     manual_seal::{
         get_aura_id_from_seed, ContainerManualSealAuraConsensusDataProvider,
         OrchestratorManualSealAuraConsensusDataProvider,
     },
+    pallet_authority_assignment_runtime_api::AuthorityAssignmentApi,
     pallet_registrar_runtime_api::OnDemandBlockProductionApi,
     parity_scale_codec::{Decode, Encode},
     sc_consensus_aura::{
@@ -58,7 +59,7 @@ pub use {
     std::hash::Hash,
 };
 
-use {sp_consensus_slots::Slot, sp_core::crypto::Pair};
+use {sp_consensus_slots::Slot, sp_core::crypto::Pair, std::collections::BTreeSet};
 
 const LOG_TARGET: &str = "aura::tanssi";
 
@@ -145,6 +146,73 @@ use {
     tp_traits::SlotFrequency,
 };
 
+/// Orchestrator chain queries that return every ParaId assigned to an authority.
+/// `OrchestratorChainInterface::check_para_id_assignment` only returns the first one, but a
+/// collator can be assigned to more than one parathread at the same time.
+#[async_trait::async_trait]
+pub trait OrchestratorAssignmentInterface: OrchestratorChainInterface {
+    /// Return every ParaId assigned to `authority`, either for the current session or for the
+    /// next session if `next_session` is true. Uses `AuthorityAssignmentApi`.
+    async fn para_ids_of_authority(
+        &self,
+        orchestrator_parent: H256,
+        authority: NimbusId,
+        next_session: bool,
+    ) -> OrchestratorChainResult<Vec<ParaId>>;
+}
+
+/// Return the ParaId assignments of all the nimbus keys in the keystore, either for the current
+/// session or for the next session if `next_session` is true.
+/// A collator can be assigned to more than one parathread at the same time, so unlike
+/// `first_eligible_key` this does not stop at the first eligible key, and every ParaId of each
+/// key is returned.
+pub async fn eligible_para_ids<C>(
+    client: &C,
+    parent_hash: &H256,
+    keystore: KeystorePtr,
+    next_session: bool,
+) -> BTreeSet<ParaId>
+where
+    C: OrchestratorAssignmentInterface + ?Sized,
+{
+    let mut para_ids = BTreeSet::new();
+
+    // Get all the available keys
+    let Ok(available_keys) = Keystore::keys(&*keystore, NIMBUS_KEY_ID) else {
+        return para_ids;
+    };
+
+    // Print a more helpful message than "not eligible" when there are no keys at all.
+    if available_keys.is_empty() {
+        log::warn!(
+            target: LOG_TARGET,
+            "🔏 No Nimbus keys available. We will not be able to author."
+        );
+        return para_ids;
+    }
+
+    for type_public_pair in available_keys {
+        if let Ok(nimbus_id) = NimbusId::from_slice(&type_public_pair) {
+            match client
+                .para_ids_of_authority(*parent_hash, nimbus_id.clone(), next_session)
+                .await
+            {
+                Ok(assigned) if !assigned.is_empty() => {
+                    log::debug!("Para ids found for assignment {:?}", assigned);
+                    para_ids.extend(assigned);
+                }
+                _ => {
+                    log::debug!("No Para id found for assignment {:?}", nimbus_id);
+                }
+            }
+        } else {
+            log::debug!("Invalid nimbus id: {:?}", type_public_pair);
+        }
+    }
+
+    para_ids
+}
+
 /// Grab the first eligible nimbus key from the keystore
 /// If multiple keys are eligible this function still only returns one
 /// and makes no guarantees which one as that depends on the keystore's iterator behavior.
@@ -35,7 +35,7 @@ use {
     nimbus_primitives::{
         CompatibleDigestItem, NimbusId, NimbusPair, NIMBUS_ENGINE_ID, NIMBUS_KEY_ID,
     },
-    pallet_xcm_core_buyer_runtime_api::BuyingError,
+    pallet_xcm_core_buyer_runtime_api::{BuyingError, CorePurchaseRecord, CorePurchaseStats},
     parity_scale_codec::Encode,
     polkadot_core_primitives::{Header as PHeader, InboundDownwardMessage, InboundHrmpMessage},
     polkadot_node_subsystem::{
@@ -194,6 +194,14 @@ sp_api::mock_impl_runtime_apis! {
         fn get_buy_core_slot_drift() -> Slot {
          0u64.into()
         }
+
+        fn get_core_purchase_history(_para_id: ParaId) -> Vec<CorePurchaseRecord<BlockNumber, NimbusId>> {
+            vec![]
+        }
+
+        fn get_core_purchase_stats(_para_id: ParaId) -> CorePurchaseStats {
+            Default::default()
+        }
     }
 
 }
@@ -31,7 +31,9 @@ use {
     sp_api::StorageProof,
     sp_state_machine::StorageValue,
     std::{pin::Pin, sync::Arc},
-    tc_consensus::{OrchestratorChainInterface, RelayChainInterface},
+    tc_consensus::{
+        OrchestratorAssignmentInterface, OrchestratorChainInterface, RelayChainInterface,
+    },
 };
 
 /// Builder for a concrete relay chain interface, created from a full node. Builds
@@ -46,7 +48,7 @@ pub struct RelayAsOrchestratorChainInterfaceBuilder {
 }
 
 impl RelayAsOrchestratorChainInterfaceBuilder {
-    pub fn build(self) -> Arc<dyn OrchestratorChainInterface> {
+    pub fn build(self) -> Arc<RelayAsOrchestratorChainInterface> {
         Arc::new(RelayAsOrchestratorChainInterface::new(
             self.overseer_handle,
             self.relay_chain_interface,
@@ -257,3 +259,29 @@ impl OrchestratorChainInterface for RelayAsOrchestratorChainInterface {
         Ok(res)
     }
 }
+
+#[async_trait::async_trait]
+impl OrchestratorAssignmentInterface for RelayAsOrchestratorChainInterface {
+    async fn para_ids_of_authority(
+        &self,
+        relay_parent: PHash,
+        authority: NimbusId,
+        next_session: bool,
+    ) -> OrchestratorChainResult<Vec<ParaId>> {
+        let method_name = if next_session {
+            "AuthorityAssignmentApi_para_ids_of_authority_next_session"
+        } else {
+            "AuthorityAssignmentApi_para_ids_of_authority"
+        };
+        let res: Vec<ParaId> = call_runtime_api(
+            &self.relay_chain_interface,
+            method_name,
+            relay_parent,
+            &authority,
+        )
+        .await
+        .map_err(|e| OrchestratorChainError::Application(Box::new(e)))?;
+
+        Ok(res)
+    }
+}
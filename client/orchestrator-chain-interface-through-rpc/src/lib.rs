@@ -14,6 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
 
+pub mod light_client;
+pub mod metrics;
 mod ws_client;
 
 use {
@@ -27,6 +29,7 @@ use {
     dp_core::ParaId,
     futures::{Stream, StreamExt},
     jsonrpsee::{core::params::ArrayParams, rpc_params},
+    metrics::OrchestratorRpcMetrics,
     sc_client_api::{StorageData, StorageProof},
     sc_rpc_api::state::ReadProof,
     sc_service::TaskManager,
@@ -34,6 +37,7 @@ use {
     sp_core::{Decode, Encode},
     sp_state_machine::StorageValue,
     sp_storage::StorageKey,
+    substrate_prometheus_endpoint::Registry,
     tokio::sync::{mpsc, oneshot},
     url::Url,
     ws_client::{JsonRpcRequest, WsClientRequest},
@@ -63,22 +67,50 @@ fn url_to_string_with_port(url: Url) -> Option<String> {
     ))
 }
 
+/// Connect to the orchestrator chain through the provided RPC endpoints, switching to another
+/// endpoint when the active one disconnects or fails a health check.
+///
+/// If `quorum` is set, the container chain assignment of data preservers and the genesis data of
+/// container chains are requested from all the endpoints, and only accepted if at least `quorum`
+/// endpoints return the same value.
 pub async fn create_client_and_start_worker(
     urls: Vec<Url>,
+    quorum: Option<usize>,
     task_manager: &mut TaskManager,
     overseer_handle: Option<polkadot_overseer::Handle>,
+    prometheus_registry: Option<&Registry>,
 ) -> OrchestratorChainResult<OrchestratorChainRpcClient> {
     let urls: Vec<_> = urls
         .into_iter()
         .filter_map(url_to_string_with_port)
         .collect();
-    let (worker, request_sender) = ws_client::ReconnectingWsClientWorker::new(urls)
-        .await
-        .map_err(|_| {
-            OrchestratorChainError::GenericError(
-                "Failed to connect to all provided Orchestrator chain RPC endpoints".to_string(),
-            )
+    if let Some(quorum) = quorum {
+        if quorum == 0 || quorum > urls.len() {
+            return Err(OrchestratorChainError::GenericError(format!(
+                "Orchestrator endpoints quorum must be between 1 and the number of endpoints ({}), got {}",
+                urls.len(),
+                quorum
+            )));
+        }
+    }
+    let metrics = prometheus_registry
+        .map(OrchestratorRpcMetrics::register)
+        .transpose()
+        .map_err(|e| {
+            OrchestratorChainError::GenericError(format!(
+                "Failed to register orchestrator RPC metrics: {}",
+                e
+            ))
         })?;
+    let (worker, request_sender) =
+        ws_client::ReconnectingWsClientWorker::new(urls, quorum, metrics)
+            .await
+            .map_err(|_| {
+                OrchestratorChainError::GenericError(
+                    "Failed to connect to all provided Orchestrator chain RPC endpoints"
+                        .to_string(),
+                )
+            })?;
 
     task_manager
         .spawn_essential_handle()
@@ -105,6 +137,29 @@ impl OrchestratorChainRpcClient {
         method_name: &str,
         hash: PHash,
         payload: Option<impl Encode>,
+    ) -> OrchestratorChainResult<R> {
+        self.state_call(WsClientRequest::JsonRpcRequest, method_name, hash, payload)
+            .await
+    }
+
+    /// Same as [Self::call_remote_runtime_function], but in quorum mode the result is
+    /// cross-checked between endpoints.
+    pub async fn call_remote_runtime_function_with_quorum<R: Decode>(
+        &self,
+        method_name: &str,
+        hash: PHash,
+        payload: Option<impl Encode>,
+    ) -> OrchestratorChainResult<R> {
+        self.state_call(WsClientRequest::QuorumRequest, method_name, hash, payload)
+            .await
+    }
+
+    async fn state_call<R: Decode>(
+        &self,
+        request_kind: fn(JsonRpcRequest) -> WsClientRequest,
+        method_name: &str,
+        hash: PHash,
+        payload: Option<impl Encode>,
     ) -> OrchestratorChainResult<R> {
         let payload_bytes =
             payload.map_or(sp_core::Bytes(Vec::new()), |v| sp_core::Bytes(v.encode()));
@@ -114,7 +169,7 @@ impl OrchestratorChainRpcClient {
             hash
         };
         let res = self
-            .request_tracing::<sp_core::Bytes, _>("state_call", params, |err| {
+            .request_tracing::<sp_core::Bytes, _>(request_kind, "state_call", params, |err| {
                 tracing::debug!(
                     target: LOG_TARGET,
                     %method_name,
@@ -132,6 +187,7 @@ impl OrchestratorChainRpcClient {
         R: DeserializeOwned + std::fmt::Debug,
     {
         self.request_tracing(
+            WsClientRequest::JsonRpcRequest,
             method,
             params,
             |e| tracing::trace!(target:LOG_TARGET, error = %e, %method, "Unable to complete RPC request"),
@@ -153,6 +209,7 @@ impl OrchestratorChainRpcClient {
     /// for retrying requests if connection dies.
     async fn request_tracing<R, OR>(
         &self,
+        request_kind: fn(JsonRpcRequest) -> WsClientRequest,
         method: &str,
         params: ArrayParams,
         trace_error: OR,
@@ -163,7 +220,7 @@ impl OrchestratorChainRpcClient {
     {
         let (response_sender, response_receiver) = oneshot::channel();
 
-        let request = WsClientRequest::JsonRpcRequest(JsonRpcRequest {
+        let request = request_kind(JsonRpcRequest {
             method: method.into(),
             params,
             response_sender,
@@ -286,7 +343,7 @@ impl OrchestratorChainInterface for OrchestratorChainRpcClient {
         orchestrator_parent: PHash,
         para_id: ParaId,
     ) -> OrchestratorChainResult<Option<ContainerChainGenesisData>> {
-        self.call_remote_runtime_function(
+        self.call_remote_runtime_function_with_quorum(
             "RegistrarApi_genesis_data",
             orchestrator_parent,
             Some(para_id),
@@ -333,7 +390,7 @@ impl OrchestratorChainInterface for OrchestratorChainRpcClient {
         orchestrator_parent: PHash,
         profile_id: DataPreserverProfileId,
     ) -> OrchestratorChainResult<DataPreserverAssignment<ParaId>> {
-        self.call_remote_runtime_function(
+        self.call_remote_runtime_function_with_quorum(
             "DataPreserversApi_get_active_assignment",
             orchestrator_parent,
             Some(profile_id),
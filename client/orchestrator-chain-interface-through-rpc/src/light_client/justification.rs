@@ -0,0 +1,252 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Verification of GRANDPA finality proofs, as returned by `grandpa_proveFinality`.
+
+use {
+    dc_orchestrator_chain_interface::{BlockNumber, PHash, PHeader},
+    grandpa_primitives::{
+        localized_payload, AuthorityList, ConsensusLog, GrandpaJustification, Message,
+        ScheduledChange, SetId, GRANDPA_ENGINE_ID,
+    },
+    parity_scale_codec::Decode,
+    sp_runtime::{generic::OpaqueDigestItemId, traits::Header as HeaderT, RuntimeAppPublic},
+    std::collections::{HashMap, HashSet},
+};
+
+/// Finality proof of a block, same encoding as `sc_consensus_grandpa::FinalityProof`.
+#[derive(Debug, Decode)]
+pub struct FinalityProof {
+    /// Hash of the block justified by `justification`.
+    pub block: PHash,
+    /// Justification of `block`.
+    pub justification: Vec<u8>,
+    /// Headers from the requested block up to `block`, ordered.
+    pub unknown_headers: Vec<PHeader>,
+}
+
+/// Check that `justification` finalizes the block `(target_hash, target_number)`, and that it is
+/// signed by more than 2/3 of the weight of the authority set `set_id`.
+///
+/// Same checks as `sc_consensus_grandpa::GrandpaJustification::verify`.
+pub fn verify_justification(
+    justification: &GrandpaJustification<PHeader>,
+    target_hash: PHash,
+    target_number: BlockNumber,
+    set_id: SetId,
+    authorities: &AuthorityList,
+) -> Result<(), String> {
+    let commit = &justification.commit;
+    if (commit.target_hash, commit.target_number) != (target_hash, target_number) {
+        return Err(format!(
+            "Justification is for block {:?} (#{}), expected {:?} (#{})",
+            commit.target_hash, commit.target_number, target_hash, target_number
+        ));
+    }
+
+    let ancestries: HashMap<PHash, &PHeader> = justification
+        .votes_ancestries
+        .iter()
+        .map(|header| (header.hash(), header))
+        .collect();
+    let weights: HashMap<_, _> = authorities.iter().cloned().collect();
+    let total_weight: u64 = authorities.iter().map(|(_, weight)| weight).sum();
+
+    let mut signers = HashSet::new();
+    let mut signed_weight = 0u64;
+    for signed in &commit.precommits {
+        let Some(weight) = weights.get(&signed.id) else {
+            return Err(format!(
+                "Precommit signed by unknown authority {:?}",
+                signed.id
+            ));
+        };
+        if !signers.insert(&signed.id) {
+            return Err(format!("Duplicate precommit from {:?}", signed.id));
+        }
+
+        let message = Message::<PHeader>::Precommit(signed.precommit.clone());
+        let payload = localized_payload(justification.round, set_id, &message);
+        if !signed.id.verify(&payload, &signed.signature) {
+            return Err(format!("Invalid precommit signature from {:?}", signed.id));
+        }
+
+        // Precommits can be for descendants of the target, which must be in `votes_ancestries`
+        let mut hash = signed.precommit.target_hash;
+        while hash != commit.target_hash {
+            let header = ancestries.get(&hash).ok_or_else(|| {
+                format!(
+                    "Precommit for {:?} is not a descendant of the justification target",
+                    signed.precommit.target_hash
+                )
+            })?;
+            if *header.number() <= commit.target_number {
+                return Err(format!(
+                    "Precommit for {:?} is not a descendant of the justification target",
+                    signed.precommit.target_hash
+                ));
+            }
+            hash = *header.parent_hash();
+        }
+
+        signed_weight = signed_weight.saturating_add(*weight);
+    }
+
+    // Same threshold as `finality_grandpa::VoterSet`
+    let threshold = total_weight - total_weight.saturating_sub(1) / 3;
+    if signed_weight < threshold {
+        return Err(format!(
+            "Justification signed by {} of {} weight, {} required",
+            signed_weight, total_weight, threshold
+        ));
+    }
+
+    Ok(())
+}
+
+/// Find the authority set change signaled in `header`, if any.
+pub fn find_scheduled_change(header: &PHeader) -> Option<ScheduledChange<BlockNumber>> {
+    let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+
+    header.digest().convert_first(|log| {
+        log.try_to::<ConsensusLog<BlockNumber>>(id)
+            .and_then(|log| match log {
+                ConsensusLog::ScheduledChange(change) => Some(change),
+                ConsensusLog::ForcedChange(_, change) => Some(change),
+                _ => None,
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        grandpa_primitives::{AuthorityId, Commit, Precommit, SignedPrecommit},
+        sp_core::{ed25519, Pair},
+        sp_runtime::Digest,
+    };
+
+    fn header(number: u32, parent_hash: PHash) -> PHeader {
+        PHeader::new(
+            number,
+            Default::default(),
+            Default::default(),
+            parent_hash,
+            Digest::default(),
+        )
+    }
+
+    fn authorities(seeds: std::ops::Range<u8>) -> (Vec<ed25519::Pair>, AuthorityList) {
+        let pairs: Vec<_> = seeds.map(|i| ed25519::Pair::from_seed(&[i; 32])).collect();
+        let authorities = pairs
+            .iter()
+            .map(|pair| (AuthorityId::from(pair.public()), 1))
+            .collect();
+
+        (pairs, authorities)
+    }
+
+    fn justification(
+        signers: &[ed25519::Pair],
+        target: &PHeader,
+        vote_target: &PHeader,
+        set_id: SetId,
+    ) -> GrandpaJustification<PHeader> {
+        let round = 1;
+        let precommit = Precommit::<PHeader> {
+            target_hash: vote_target.hash(),
+            target_number: *vote_target.number(),
+        };
+        let payload = localized_payload(
+            round,
+            set_id,
+            &Message::<PHeader>::Precommit(precommit.clone()),
+        );
+        let precommits = signers
+            .iter()
+            .map(|pair| SignedPrecommit::<PHeader> {
+                precommit: precommit.clone(),
+                signature: pair.sign(&payload).into(),
+                id: pair.public().into(),
+            })
+            .collect();
+
+        GrandpaJustification {
+            round,
+            commit: Commit::<PHeader> {
+                target_hash: target.hash(),
+                target_number: *target.number(),
+                precommits,
+            },
+            votes_ancestries: if vote_target.hash() == target.hash() {
+                vec![]
+            } else {
+                vec![vote_target.clone()]
+            },
+        }
+    }
+
+    #[test]
+    fn valid_justification() {
+        let (pairs, authorities) = authorities(0..4);
+        let target = header(10, Default::default());
+        let descendant = header(11, target.hash());
+
+        // 3 of 4 is above the threshold
+        let j = justification(&pairs[..3], &target, &target, 5);
+        assert_eq!(
+            verify_justification(&j, target.hash(), 10, 5, &authorities),
+            Ok(())
+        );
+
+        // Votes for a descendant of the target also count
+        let j = justification(&pairs[..3], &target, &descendant, 5);
+        assert_eq!(
+            verify_justification(&j, target.hash(), 10, 5, &authorities),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn invalid_justification() {
+        let (pairs, authorities) = authorities(0..4);
+        let target = header(10, Default::default());
+        let unrelated = header(11, Default::default());
+
+        // Not enough weight
+        let j = justification(&pairs[..2], &target, &target, 5);
+        assert!(verify_justification(&j, target.hash(), 10, 5, &authorities).is_err());
+
+        // Signed for another set id
+        let j = justification(&pairs[..3], &target, &target, 4);
+        assert!(verify_justification(&j, target.hash(), 10, 5, &authorities).is_err());
+
+        // Justification for another block
+        let j = justification(&pairs[..3], &target, &target, 5);
+        assert!(verify_justification(&j, unrelated.hash(), 11, 5, &authorities).is_err());
+
+        // Votes for a block that does not descend from the target
+        let mut j = justification(&pairs[..3], &target, &unrelated, 5);
+        j.votes_ancestries.clear();
+        assert!(verify_justification(&j, target.hash(), 10, 5, &authorities).is_err());
+
+        // Signed by someone outside of the authority set
+        let (other_pairs, _) = authorities(100..103);
+        let j = justification(&other_pairs, &target, &target, 5);
+        assert!(verify_justification(&j, target.hash(), 10, 5, &authorities).is_err());
+    }
+}
@@ -0,0 +1,530 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Orchestrator chain light client
+//!
+//! [OrchestratorChainInterface] implementation that uses the orchestrator RPC endpoints as an
+//! untrusted transport. Starting from a trusted checkpoint block, it follows the finalized chain
+//! by verifying GRANDPA finality proofs, and serves storage reads and runtime calls by checking
+//! them against the state root of the verified blocks.
+//!
+//! Only orchestrator chains finalized by GRANDPA (solochains) are supported. Only finalized
+//! blocks are verified, so the best block and import notifications are the finalized ones.
+
+mod justification;
+mod remote_call;
+
+use {
+    crate::{ws_client::distribute, OrchestratorChainRpcClient, LOG_TARGET},
+    async_trait::async_trait,
+    core::pin::Pin,
+    dc_orchestrator_chain_interface::{
+        BlockNumber, ContainerChainGenesisData, DataPreserverAssignment, DataPreserverProfileId,
+        NimbusId, OrchestratorChainError, OrchestratorChainInterface, OrchestratorChainResult,
+        PHash, PHeader,
+    },
+    dp_core::ParaId,
+    futures::{Stream, StreamExt},
+    grandpa_primitives::{AuthorityList, GrandpaJustification, ScheduledChange, SetId},
+    jsonrpsee::rpc_params,
+    justification::{find_scheduled_change, verify_justification, FinalityProof},
+    remote_call::RemoteCallExecutor,
+    sc_client_api::StorageProof,
+    sc_service::TaskManager,
+    schnellru::{ByLength, LruMap},
+    sp_core::{Bytes, Decode, Encode},
+    sp_runtime::traits::Header as HeaderT,
+    sp_state_machine::StorageValue,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    tokio::sync::mpsc,
+};
+
+/// Number of verified headers kept, runtime calls are only allowed at these blocks.
+const VERIFIED_HEADERS_CACHE_SIZE: u32 = 4096;
+
+/// Finality is synced when the RPC endpoints notify a new finalized block, or after this
+/// interval without notifications.
+const SYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Authority set and finalized chain verified by the light client.
+struct VerifiedChain {
+    finalized: PHeader,
+    set_id: SetId,
+    authorities: AuthorityList,
+    /// Authority set change signaled in a verified header, and the block number at which it is
+    /// enacted.
+    pending_change: Option<(BlockNumber, ScheduledChange<BlockNumber>)>,
+    headers: LruMap<PHash, PHeader>,
+    listeners: Vec<mpsc::Sender<PHeader>>,
+}
+
+/// Light client implementation of [OrchestratorChainInterface], see the module docs.
+#[derive(Clone)]
+pub struct OrchestratorChainLightClient {
+    rpc: OrchestratorChainRpcClient,
+    remote_call: RemoteCallExecutor,
+    chain: Arc<Mutex<VerifiedChain>>,
+}
+
+/// Start a light client that trusts the orchestrator block `checkpoint`, and uses `rpc` to follow
+/// the finalized chain from there.
+///
+/// The checkpoint should be a recent finalized block, obtained from a trusted source. Blocks
+/// before the checkpoint cannot be read.
+pub async fn start_light_client(
+    rpc: OrchestratorChainRpcClient,
+    checkpoint: PHash,
+    task_manager: &mut TaskManager,
+) -> OrchestratorChainResult<OrchestratorChainLightClient> {
+    let header: PHeader = rpc
+        .request::<Option<PHeader>>("chain_getHeader", rpc_params![checkpoint])
+        .await?
+        .ok_or_else(|| {
+            OrchestratorChainError::GenericError(format!(
+                "Orchestrator light client checkpoint {:?} not found",
+                checkpoint
+            ))
+        })?;
+    if header.hash() != checkpoint {
+        return Err(OrchestratorChainError::GenericError(format!(
+            "Orchestrator RPC returned an invalid header for checkpoint {:?}",
+            checkpoint
+        )));
+    }
+
+    let remote_call = RemoteCallExecutor::new(rpc.clone());
+    let call = |method: &'static str| {
+        let remote_call = remote_call.clone();
+        let state_root = *header.state_root();
+        async move {
+            remote_call
+                .call(checkpoint, state_root, method, vec![])
+                .await
+                .map_err(|e| {
+                    OrchestratorChainError::GenericError(format!(
+                        "Failed to call {} at the orchestrator light client checkpoint, only \
+                         GRANDPA orchestrator chains are supported: {}",
+                        method, e
+                    ))
+                })
+        }
+    };
+    let authorities =
+        AuthorityList::decode(&mut &call("GrandpaApi_grandpa_authorities").await?[..])?;
+    let set_id = SetId::decode(&mut &call("GrandpaApi_current_set_id").await?[..])?;
+
+    tracing::info!(
+        target: LOG_TARGET,
+        number = header.number(),
+        hash = ?checkpoint,
+        set_id,
+        "Orchestrator light client started."
+    );
+
+    let mut headers = LruMap::new(ByLength::new(VERIFIED_HEADERS_CACHE_SIZE));
+    headers.insert(checkpoint, header.clone());
+    let client = OrchestratorChainLightClient {
+        rpc,
+        remote_call,
+        chain: Arc::new(Mutex::new(VerifiedChain {
+            finalized: header,
+            set_id,
+            authorities,
+            pending_change: None,
+            headers,
+            listeners: vec![],
+        })),
+    };
+
+    task_manager.spawn_essential_handle().spawn(
+        "orchestrator-light-client-sync",
+        None,
+        client.clone().sync_task(),
+    );
+
+    Ok(client)
+}
+
+impl OrchestratorChainLightClient {
+    /// Follow the finalized chain, using the finality notifications of the RPC endpoints only as
+    /// a hint that new blocks can be verified.
+    async fn sync_task(self) {
+        let mut finality_hints = match self.rpc.finality_notification_stream().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!(
+                    target: LOG_TARGET,
+                    error = %e,
+                    "Failed to subscribe to orchestrator finality notifications, syncing periodically."
+                );
+                futures::stream::pending().boxed()
+            }
+        };
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+
+        loop {
+            tokio::select! {
+                hint = finality_hints.next() => {
+                    if hint.is_none() {
+                        finality_hints = futures::stream::pending().boxed();
+                    }
+                }
+                _ = interval.tick() => {}
+            }
+
+            // Each finality proof covers at most one authority set
+            loop {
+                match self.sync_step().await {
+                    Ok(true) => continue,
+                    Ok(false) => break,
+                    Err(e) => {
+                        tracing::warn!(target: LOG_TARGET, error = %e, "Failed to verify orchestrator finality.");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Verify a finality proof of a block after the current finalized one. Returns `false` if the
+    /// RPC endpoints do not know any newer finalized block.
+    async fn sync_step(&self) -> OrchestratorChainResult<bool> {
+        let (finalized_hash, finalized_number) = {
+            let chain = self.chain.lock().expect("poison error");
+            (chain.finalized.hash(), *chain.finalized.number())
+        };
+
+        let Some(proof) = self
+            .rpc
+            .request::<Option<Bytes>>(
+                "grandpa_proveFinality",
+                rpc_params![finalized_number.saturating_add(1)],
+            )
+            .await?
+        else {
+            return Ok(false);
+        };
+        let proof = FinalityProof::decode(&mut &proof[..])?;
+        let justification = GrandpaJustification::<PHeader>::decode(&mut &proof.justification[..])?;
+        let target_number = justification.commit.target_number;
+        if target_number <= finalized_number {
+            return Ok(false);
+        }
+
+        // Link the justified block to the current finalized block, using the headers included in
+        // the proof and fetching the missing ones
+        let mut unknown_headers: HashMap<PHash, PHeader> = proof
+            .unknown_headers
+            .into_iter()
+            .map(|header| (header.hash(), header))
+            .collect();
+        let mut new_headers = vec![];
+        let mut hash = proof.block;
+        while hash != finalized_hash {
+            let header = match unknown_headers.remove(&hash) {
+                Some(header) => header,
+                None => self.fetch_header(hash).await?,
+            };
+            if *header.number() <= finalized_number {
+                return Err(OrchestratorChainError::GenericError(format!(
+                    "Finalized block {:?} does not descend from the verified finalized block {:?}",
+                    proof.block, finalized_hash
+                )));
+            }
+            hash = *header.parent_hash();
+            new_headers.push(header);
+        }
+        new_headers.reverse();
+        let Some(finalized) = new_headers
+            .last()
+            .filter(|header| *header.number() == target_number)
+            .cloned()
+        else {
+            return Err(OrchestratorChainError::GenericError(format!(
+                "Finality proof of block #{} does not match the header of {:?}",
+                target_number, proof.block
+            )));
+        };
+
+        let mut chain = self.chain.lock().expect("poison error");
+        if chain.finalized.hash() != finalized_hash {
+            // Already synced by a concurrent call
+            return Ok(true);
+        }
+
+        let mut pending_change = chain.pending_change.clone();
+        for header in &new_headers {
+            if let Some(change) = find_scheduled_change(header) {
+                pending_change = Some((header.number().saturating_add(change.delay), change));
+            }
+        }
+        if let Some((enacted_at, _)) = &pending_change {
+            if *enacted_at < target_number {
+                return Err(OrchestratorChainError::GenericError(format!(
+                    "Finality proof of block #{} skips the authority set change at block #{}",
+                    target_number, enacted_at
+                )));
+            }
+        }
+
+        verify_justification(
+            &justification,
+            proof.block,
+            target_number,
+            chain.set_id,
+            &chain.authorities,
+        )
+        .map_err(OrchestratorChainError::GenericError)?;
+
+        for header in new_headers {
+            chain.headers.insert(header.hash(), header);
+        }
+        chain.finalized = finalized.clone();
+        chain.pending_change = match pending_change {
+            Some((enacted_at, change)) if enacted_at == target_number => {
+                chain.set_id = chain.set_id.saturating_add(1);
+                chain.authorities = change.next_authorities;
+                tracing::info!(
+                    target: LOG_TARGET,
+                    number = target_number,
+                    set_id = chain.set_id,
+                    "Orchestrator authority set changed."
+                );
+                None
+            }
+            pending_change => pending_change,
+        };
+        tracing::debug!(
+            target: LOG_TARGET,
+            number = target_number,
+            hash = ?proof.block,
+            "Orchestrator light client verified finalized block."
+        );
+        distribute(finalized, &mut chain.listeners);
+
+        Ok(true)
+    }
+
+    async fn fetch_header(&self, hash: PHash) -> OrchestratorChainResult<PHeader> {
+        let header = self
+            .rpc
+            .request::<Option<PHeader>>("chain_getHeader", rpc_params![hash])
+            .await?
+            .filter(|header| header.hash() == hash)
+            .ok_or_else(|| {
+                OrchestratorChainError::GenericError(format!(
+                    "Orchestrator RPC did not return the header of block {:?}",
+                    hash
+                ))
+            })?;
+
+        Ok(header)
+    }
+
+    /// State root of a verified block.
+    fn verified_state_root(&self, hash: PHash) -> OrchestratorChainResult<PHash> {
+        self.chain
+            .lock()
+            .expect("poison error")
+            .headers
+            .get(&hash)
+            .map(|header| *header.state_root())
+            .ok_or_else(|| {
+                OrchestratorChainError::GenericError(format!(
+                    "Orchestrator block {:?} has not been verified by the light client",
+                    hash
+                ))
+            })
+    }
+
+    /// Call a runtime API at a verified block, executing the orchestrator runtime locally.
+    pub async fn call_runtime_function<R: Decode>(
+        &self,
+        method_name: &str,
+        hash: PHash,
+        payload: impl Encode,
+    ) -> OrchestratorChainResult<R> {
+        let state_root = self.verified_state_root(hash)?;
+        let res = self
+            .remote_call
+            .call(hash, state_root, method_name, payload.encode())
+            .await?;
+
+        Decode::decode(&mut &res[..]).map_err(Into::into)
+    }
+
+    fn register_listener(
+        &self,
+    ) -> OrchestratorChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>> {
+        let (tx, rx) = mpsc::channel(crate::NOTIFICATION_CHANNEL_SIZE_LIMIT);
+        self.chain.lock().expect("poison error").listeners.push(tx);
+
+        Ok(tokio_stream::wrappers::ReceiverStream::new(rx).boxed())
+    }
+}
+
+#[async_trait]
+impl OrchestratorChainInterface for OrchestratorChainLightClient {
+    /// Fetch a storage item by key.
+    async fn get_storage_by_key(
+        &self,
+        orchestrator_parent: PHash,
+        key: &[u8],
+    ) -> OrchestratorChainResult<Option<StorageValue>> {
+        let state_root = self.verified_state_root(orchestrator_parent)?;
+        self.remote_call
+            .storage(orchestrator_parent, state_root, key.to_vec())
+            .await
+    }
+
+    /// The light client does not run an overseer.
+    fn overseer_handle(&self) -> OrchestratorChainResult<polkadot_overseer::Handle> {
+        Err(OrchestratorChainError::GenericError(
+            "OrchestratorChainLightClient doesn't contain an Overseer Handle".to_string(),
+        ))
+    }
+
+    /// Generate a storage read proof, checked against the verified state root.
+    async fn prove_read(
+        &self,
+        orchestrator_parent: PHash,
+        relevant_keys: &Vec<Vec<u8>>,
+    ) -> OrchestratorChainResult<StorageProof> {
+        let state_root = self.verified_state_root(orchestrator_parent)?;
+        let proof: StorageProof = self
+            .rpc
+            .prove_read(orchestrator_parent, relevant_keys)
+            .await?;
+
+        sp_state_machine::read_proof_check::<sp_runtime::traits::BlakeTwo256, _>(
+            state_root,
+            proof.clone(),
+            relevant_keys,
+        )
+        .map_err(|e| {
+            OrchestratorChainError::GenericError(format!(
+                "Invalid read proof at orchestrator block {:?}: {}",
+                orchestrator_parent, e
+            ))
+        })?;
+
+        Ok(proof)
+    }
+
+    /// Get a stream of verified finalized blocks.
+    async fn import_notification_stream(
+        &self,
+    ) -> OrchestratorChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>> {
+        self.register_listener()
+    }
+
+    /// Get a stream of verified finalized blocks.
+    async fn new_best_notification_stream(
+        &self,
+    ) -> OrchestratorChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>> {
+        self.register_listener()
+    }
+
+    /// Get a stream of verified finalized blocks.
+    async fn finality_notification_stream(
+        &self,
+    ) -> OrchestratorChainResult<Pin<Box<dyn Stream<Item = PHeader> + Send>>> {
+        self.register_listener()
+    }
+
+    async fn genesis_data(
+        &self,
+        orchestrator_parent: PHash,
+        para_id: ParaId,
+    ) -> OrchestratorChainResult<Option<ContainerChainGenesisData>> {
+        self.call_runtime_function("RegistrarApi_genesis_data", orchestrator_parent, para_id)
+            .await
+    }
+
+    async fn boot_nodes(
+        &self,
+        orchestrator_parent: PHash,
+        para_id: ParaId,
+    ) -> OrchestratorChainResult<Vec<Vec<u8>>> {
+        self.call_runtime_function("RegistrarApi_boot_nodes", orchestrator_parent, para_id)
+            .await
+    }
+
+    async fn latest_block_number(
+        &self,
+        orchestrator_parent: PHash,
+        para_id: ParaId,
+    ) -> OrchestratorChainResult<Option<BlockNumber>> {
+        self.call_runtime_function(
+            "AuthorNotingApi_latest_block_number",
+            orchestrator_parent,
+            para_id,
+        )
+        .await
+    }
+
+    /// Only finalized blocks are verified, so this is the finalized block.
+    async fn best_block_hash(&self) -> OrchestratorChainResult<PHash> {
+        self.finalized_block_hash().await
+    }
+
+    async fn finalized_block_hash(&self) -> OrchestratorChainResult<PHash> {
+        Ok(self.chain.lock().expect("poison error").finalized.hash())
+    }
+
+    async fn data_preserver_active_assignment(
+        &self,
+        orchestrator_parent: PHash,
+        profile_id: DataPreserverProfileId,
+    ) -> OrchestratorChainResult<DataPreserverAssignment<ParaId>> {
+        self.call_runtime_function(
+            "DataPreserversApi_get_active_assignment",
+            orchestrator_parent,
+            profile_id,
+        )
+        .await
+    }
+
+    async fn check_para_id_assignment(
+        &self,
+        orchestrator_parent: PHash,
+        authority: NimbusId,
+    ) -> OrchestratorChainResult<Option<ParaId>> {
+        self.call_runtime_function(
+            "TanssiAuthorityAssignmentApi_check_para_id_assignment",
+            orchestrator_parent,
+            authority,
+        )
+        .await
+    }
+
+    async fn check_para_id_assignment_next_session(
+        &self,
+        orchestrator_parent: PHash,
+        authority: NimbusId,
+    ) -> OrchestratorChainResult<Option<ParaId>> {
+        self.call_runtime_function(
+            "TanssiAuthorityAssignmentApi_check_para_id_assignment_next_session",
+            orchestrator_parent,
+            authority,
+        )
+        .await
+    }
+}
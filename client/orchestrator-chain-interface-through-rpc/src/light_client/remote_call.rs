@@ -0,0 +1,227 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Storage reads and runtime calls of verified orchestrator blocks.
+//!
+//! The trie nodes are downloaded on demand from the RPC endpoints using `state_getReadProof`, and
+//! only used if their hash matches the one expected by the trie lookup, so a malicious endpoint
+//! can make a call fail but cannot change its result. Runtime calls execute the orchestrator
+//! runtime code stored in the verified state.
+
+use {
+    crate::OrchestratorChainRpcClient,
+    dc_orchestrator_chain_interface::{OrchestratorChainError, OrchestratorChainResult, PHash},
+    sc_executor::WasmExecutor,
+    sp_core::traits::CallContext,
+    sp_externalities::Extensions,
+    sp_runtime::traits::{BlakeTwo256, Hash as HashT},
+    sp_state_machine::{
+        Backend, BackendRuntimeCode, OverlayedChanges, StateMachine, TrieBackend,
+        TrieBackendBuilder, TrieBackendStorage,
+    },
+    sp_storage::StorageKey,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+};
+
+/// Trie nodes are dropped once their total size exceeds this limit. The runtime code is usually
+/// the largest one, and the compiled runtime is cached by the executor anyway.
+const MAX_TRIE_NODES_SIZE: usize = 64 * 1024 * 1024;
+
+/// Trie nodes downloaded from the RPC endpoints, by hash. Shared by all the blocks, since most
+/// of the nodes do not change between blocks.
+#[derive(Default)]
+struct TrieNodes {
+    nodes: HashMap<PHash, Vec<u8>>,
+    size: usize,
+}
+
+impl TrieNodes {
+    fn insert(&mut self, node: Vec<u8>) {
+        if self.size.saturating_add(node.len()) > MAX_TRIE_NODES_SIZE {
+            self.nodes.clear();
+            self.size = 0;
+        }
+
+        self.size = self.size.saturating_add(node.len());
+        if let Some(old) = self.nodes.insert(BlakeTwo256::hash(&node), node) {
+            self.size = self.size.saturating_sub(old.len());
+        }
+    }
+}
+
+/// Trie storage of an orchestrator block, fetching missing nodes from the RPC endpoints.
+///
+/// Must only be used outside of the async runtime, because fetching nodes blocks the thread.
+struct RemoteTrieStorage {
+    rpc: OrchestratorChainRpcClient,
+    block_hash: PHash,
+    nodes: Arc<Mutex<TrieNodes>>,
+}
+
+impl TrieBackendStorage<BlakeTwo256> for RemoteTrieStorage {
+    fn get(&self, key: &PHash, prefix: (&[u8], Option<u8>)) -> Result<Option<Vec<u8>>, String> {
+        if let Some(node) = self.nodes.lock().expect("poison error").get(key) {
+            return Ok(Some(node.clone()));
+        }
+
+        // A read proof of the node path includes the node itself. If the prefix has an odd
+        // number of nibbles, the last one is in the high bits of `prefix.1`.
+        let mut path = prefix.0.to_vec();
+        path.extend(prefix.1);
+
+        let read_proof = futures::executor::block_on(
+            self.rpc
+                .state_get_read_proof(vec![StorageKey(path)], Some(self.block_hash)),
+        )
+        .map_err(|e| format!("Failed to fetch trie node {:?}: {}", key, e))?;
+
+        let mut nodes = self.nodes.lock().expect("poison error");
+        for node in read_proof.proof {
+            nodes.insert(node.0);
+        }
+
+        Ok(nodes.nodes.get(key).cloned())
+    }
+}
+
+/// Executes runtime calls and storage reads against the state of verified orchestrator blocks.
+#[derive(Clone)]
+pub struct RemoteCallExecutor {
+    rpc: OrchestratorChainRpcClient,
+    executor: Arc<WasmExecutor<sp_io::SubstrateHostFunctions>>,
+    nodes: Arc<Mutex<TrieNodes>>,
+}
+
+impl RemoteCallExecutor {
+    pub fn new(rpc: OrchestratorChainRpcClient) -> Self {
+        let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+            // The orchestrator runtime may import host functions only available to full nodes,
+            // such as the relay chain ones. They are never called by the runtime APIs we use.
+            .with_allow_missing_host_functions(true)
+            .build();
+
+        Self {
+            rpc,
+            executor: Arc::new(executor),
+            nodes: Default::default(),
+        }
+    }
+
+    /// Call the runtime API `method` at block `block_hash`, whose state root must have been
+    /// verified.
+    pub async fn call(
+        &self,
+        block_hash: PHash,
+        state_root: PHash,
+        method: &str,
+        call_data: Vec<u8>,
+    ) -> OrchestratorChainResult<Vec<u8>> {
+        let executor = self.executor.clone();
+        let method_name = method.to_string();
+
+        self.with_backend(block_hash, state_root, move |backend| {
+            let runtime_code_fetcher = BackendRuntimeCode::new(backend);
+            let runtime_code = runtime_code_fetcher
+                .runtime_code()
+                .map_err(|e| e.to_string())?;
+
+            StateMachine::new(
+                backend,
+                &mut OverlayedChanges::default(),
+                &*executor,
+                &method_name,
+                &call_data,
+                &mut Extensions::default(),
+                &runtime_code,
+                CallContext::Offchain,
+            )
+            .execute()
+            .map_err(|e| e.to_string())
+        })
+        .await
+        .map_err(|e| OrchestratorChainError::RpcCallError(method.to_string(), e))
+    }
+
+    /// Read the storage value at `key` of block `block_hash`, whose state root must have been
+    /// verified.
+    pub async fn storage(
+        &self,
+        block_hash: PHash,
+        state_root: PHash,
+        key: Vec<u8>,
+    ) -> OrchestratorChainResult<Option<Vec<u8>>> {
+        self.with_backend(block_hash, state_root, move |backend| backend.storage(&key))
+            .await
+            .map_err(OrchestratorChainError::GenericError)
+    }
+
+    async fn with_backend<R, F>(
+        &self,
+        block_hash: PHash,
+        state_root: PHash,
+        f: F,
+    ) -> Result<R, String>
+    where
+        R: Send + 'static,
+        F: FnOnce(&TrieBackend<RemoteTrieStorage, BlakeTwo256>) -> Result<R, String>
+            + Send
+            + 'static,
+    {
+        let storage = RemoteTrieStorage {
+            rpc: self.rpc.clone(),
+            block_hash,
+            nodes: self.nodes.clone(),
+        };
+
+        tokio::task::spawn_blocking(move || {
+            let backend = TrieBackendBuilder::new(storage, state_root).build();
+            f(&backend)
+        })
+        .await
+        .map_err(|e| format!("Remote call task failed: {}", e))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trie_nodes_size_limit() {
+        let mut nodes = TrieNodes::default();
+        let large_node = vec![1u8; MAX_TRIE_NODES_SIZE / 2];
+
+        nodes.insert(vec![0u8; 10]);
+        nodes.insert(vec![0u8; 10]);
+        assert_eq!((nodes.nodes.len(), nodes.size), (1, 10));
+
+        nodes.insert(large_node.clone());
+        assert_eq!(nodes.nodes.len(), 2);
+
+        // Exceeding the limit drops the previous nodes
+        let mut other_large_node = large_node;
+        other_large_node[0] = 2;
+        nodes.insert(other_large_node.clone());
+        assert_eq!(nodes.nodes.len(), 1);
+        assert_eq!(nodes.size, other_large_node.len());
+        assert!(nodes
+            .nodes
+            .contains_key(&BlakeTwo256::hash(&other_large_node)));
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the orchestrator RPC client
+//!
+//! Endpoints are labeled by their position in `--orchestrator-endpoints`, because the urls may
+//! contain API keys.
+
+use substrate_prometheus_endpoint::{
+    register, Counter, CounterVec, GaugeVec, Opts, PrometheusError, Registry, U64,
+};
+
+#[derive(Clone)]
+pub struct OrchestratorRpcMetrics {
+    endpoint_healthy: GaugeVec<U64>,
+    endpoint_active: GaugeVec<U64>,
+    failovers: Counter<U64>,
+    health_check_failures: CounterVec<U64>,
+    quorum_requests: CounterVec<U64>,
+    endpoint_disagreements: CounterVec<U64>,
+}
+
+impl OrchestratorRpcMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            endpoint_healthy: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "orchestrator_rpc_endpoint_healthy",
+                        "Whether the last connection attempt or health check of each orchestrator endpoint succeeded",
+                    ),
+                    &["endpoint"],
+                )?,
+                registry,
+            )?,
+            endpoint_active: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "orchestrator_rpc_endpoint_active",
+                        "Orchestrator endpoint currently used for requests and subscriptions",
+                    ),
+                    &["endpoint"],
+                )?,
+                registry,
+            )?,
+            failovers: register(
+                Counter::new(
+                    "orchestrator_rpc_failovers_total",
+                    "Number of times the client switched to another orchestrator endpoint",
+                )?,
+                registry,
+            )?,
+            health_check_failures: register(
+                CounterVec::new(
+                    Opts::new(
+                        "orchestrator_rpc_health_check_failures_total",
+                        "Number of failed health checks of the active orchestrator endpoint, by reason",
+                    ),
+                    &["endpoint", "reason"],
+                )?,
+                registry,
+            )?,
+            quorum_requests: register(
+                CounterVec::new(
+                    Opts::new(
+                        "orchestrator_rpc_quorum_requests_total",
+                        "Number of requests cross-checked between orchestrator endpoints, by result",
+                    ),
+                    &["result"],
+                )?,
+                registry,
+            )?,
+            endpoint_disagreements: register(
+                CounterVec::new(
+                    Opts::new(
+                        "orchestrator_rpc_endpoint_disagreements_total",
+                        "Number of quorum requests where an orchestrator endpoint returned a different response than the quorum",
+                    ),
+                    &["endpoint"],
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    pub(crate) fn on_endpoint_health(&self, index: usize, healthy: bool) {
+        self.endpoint_healthy
+            .with_label_values(&[&index.to_string()])
+            .set(healthy.into());
+    }
+
+    pub(crate) fn on_active_endpoint(&self, active_index: usize, endpoint_count: usize) {
+        for index in 0..endpoint_count {
+            self.endpoint_active
+                .with_label_values(&[&index.to_string()])
+                .set((index == active_index).into());
+        }
+    }
+
+    pub(crate) fn on_failover(&self) {
+        self.failovers.inc();
+    }
+
+    pub(crate) fn on_health_check_failure(&self, index: usize, reason: &str) {
+        self.health_check_failures
+            .with_label_values(&[&index.to_string(), reason])
+            .inc();
+    }
+
+    /// `result` is one of "agreed", "disagreement" or "no_quorum".
+    pub(crate) fn on_quorum_request(&self, result: &str) {
+        self.quorum_requests.with_label_values(&[result]).inc();
+    }
+
+    pub(crate) fn on_endpoint_disagreement(&self, index: usize) {
+        self.endpoint_disagreements
+            .with_label_values(&[&index.to_string()])
+            .inc();
+    }
+}
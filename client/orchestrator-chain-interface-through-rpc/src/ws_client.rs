@@ -15,8 +15,9 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
 
 use {
+    crate::metrics::OrchestratorRpcMetrics,
     futures::{
-        future::BoxFuture,
+        future::{join_all, BoxFuture},
         stream::{FuturesUnordered, StreamExt},
         FutureExt,
     },
@@ -26,18 +27,36 @@ use {
             params::ArrayParams,
             ClientError as JsonRpseeError, JsonValue,
         },
+        rpc_params,
         ws_client::WsClientBuilder,
     },
     sc_rpc_api::chain::ChainApiClient,
     schnellru::{ByLength, LruMap},
-    std::sync::Arc,
-    tokio::sync::{mpsc, oneshot},
+    serde::Deserialize,
+    std::{sync::Arc, time::Duration},
+    tokio::{
+        sync::{mpsc, oneshot, Mutex},
+        time::{interval_at, timeout, Instant, MissedTickBehavior},
+    },
 };
 
 const LOG_TARGET: &str = "reconnecting-websocket-client-orchestrator";
 
+/// How often the health of the active endpoint is checked.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Max time to wait for the response of a health check or quorum request.
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The active endpoint is considered stalled if it has not sent any finalized block for this long.
+/// Orchestrator blocks are finalized every few seconds.
+const MAX_FINALIZATION_STALL: Duration = Duration::from_secs(120);
+
 type RpcRequestFuture = BoxFuture<'static, Result<(), JsonRpcRequest>>;
 
+/// Resolves to the endpoints whose response disagreed with the quorum.
+type QuorumRequestFuture = BoxFuture<'static, Vec<usize>>;
+
 /// A Json Rpc/Rpsee request with a oneshot sender to send the request's response.
 pub struct JsonRpcRequest {
     pub method: String,
@@ -47,6 +66,9 @@ pub struct JsonRpcRequest {
 
 pub enum WsClientRequest {
     JsonRpcRequest(JsonRpcRequest),
+    /// Request whose response is cross-checked between endpoints when running in quorum mode.
+    /// Sent to the active endpoint only otherwise.
+    QuorumRequest(JsonRpcRequest),
     RegisterBestHeadListener(mpsc::Sender<dp_core::Header>),
     RegisterImportListener(mpsc::Sender<dp_core::Header>),
     RegisterFinalizationListener(mpsc::Sender<dp_core::Header>),
@@ -60,7 +82,8 @@ enum ConnectionStatus {
 }
 
 /// Worker that manage a WebSocket connection and handle disconnects by changing endpoint and
-/// retrying pending requests.
+/// retrying pending requests. The active endpoint is periodically health checked, and replaced
+/// by the healthiest of the other endpoints if it stops following the chain.
 ///
 /// Is first created with [`ReconnectingWsClientWorker::new`], which returns both a
 /// [`ReconnectingWsClientWorker`] and an [`mpsc::Sender`] to send the requests.
@@ -70,6 +93,12 @@ pub struct ReconnectingWsClientWorker {
     urls: Vec<String>,
     active_client: Arc<JsonRpcClient>,
     active_index: usize,
+    health: Vec<EndpointHealth>,
+
+    /// Number of endpoints that must return the same response to a quorum request
+    quorum: Option<usize>,
+    endpoint_pool: EndpointPool,
+    metrics: Option<OrchestratorRpcMetrics>,
 
     request_receiver: mpsc::Receiver<WsClientRequest>,
 
@@ -84,22 +113,141 @@ struct OrchestratorSubscription {
     best_subscription: Subscription<dp_core::Header>,
 }
 
-/// Connects to a ws server by cycle throught all provided urls from the starting position until
+/// Health of an endpoint, used to decide which endpoint to use after a failover.
+#[derive(Clone, Debug, Default)]
+struct EndpointHealth {
+    /// Failed connections, health checks and quorum disagreements since the last successful health
+    /// check
+    consecutive_failures: u32,
+}
+
+/// Response of `system_health`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemHealth {
+    peers: usize,
+    is_syncing: bool,
+    should_have_peers: bool,
+}
+
+/// Lazily created connections to all the endpoints, used to send quorum requests.
+#[derive(Clone)]
+struct EndpointPool {
+    urls: Arc<Vec<String>>,
+    clients: Arc<Vec<Mutex<Option<Arc<JsonRpcClient>>>>>,
+}
+
+impl EndpointPool {
+    fn new(urls: Vec<String>) -> Self {
+        let clients = urls.iter().map(|_| Mutex::new(None)).collect();
+
+        Self {
+            urls: Arc::new(urls),
+            clients: Arc::new(clients),
+        }
+    }
+
+    /// Get a connection to the endpoint at `index`, reconnecting if needed.
+    async fn client(&self, index: usize) -> Result<Arc<JsonRpcClient>, JsonRpseeError> {
+        let mut client = self.clients[index].lock().await;
+        if let Some(client) = client.as_ref().filter(|client| client.is_connected()) {
+            return Ok(client.clone());
+        }
+
+        let new_client = Arc::new(WsClientBuilder::default().build(&self.urls[index]).await?);
+        *client = Some(new_client.clone());
+
+        Ok(new_client)
+    }
+
+    /// Send a request to the endpoint at `index`. Returns `None` if the endpoint did not respond
+    /// in time or returned an error.
+    async fn request(&self, index: usize, method: &str, params: ArrayParams) -> Option<JsonValue> {
+        let response = timeout(HEALTH_CHECK_TIMEOUT, async {
+            let client = self.client(index).await?;
+            client.request::<JsonValue, _>(method, params).await
+        })
+        .await;
+
+        match response {
+            Ok(Ok(value)) => Some(value),
+            Ok(Err(err)) => {
+                tracing::debug!(target: LOG_TARGET, index, %method, ?err, "Quorum request failed.");
+                None
+            }
+            Err(_) => {
+                tracing::debug!(target: LOG_TARGET, index, %method, "Quorum request timed out.");
+                None
+            }
+        }
+    }
+}
+
+/// Result of a request sent to all the endpoints in quorum mode.
+#[derive(Debug, PartialEq)]
+struct QuorumOutcome {
+    /// Response returned by at least `quorum` endpoints
+    value: Option<JsonValue>,
+    /// Endpoints that returned a response different from `value`
+    disagreeing: Vec<usize>,
+}
+
+/// Find the most common response, which is only accepted if at least `quorum` endpoints returned
+/// it. Endpoints that did not respond are ignored.
+fn quorum_outcome(responses: Vec<(usize, Option<JsonValue>)>, quorum: usize) -> QuorumOutcome {
+    let responses: Vec<(usize, JsonValue)> = responses
+        .into_iter()
+        .filter_map(|(index, value)| Some((index, value?)))
+        .collect();
+
+    let most_common = responses
+        .iter()
+        .map(|(_, value)| {
+            let count = responses.iter().filter(|(_, other)| other == value).count();
+            (count, value)
+        })
+        .max_by_key(|(count, _)| *count);
+
+    match most_common {
+        Some((count, value)) if count >= quorum => QuorumOutcome {
+            value: Some(value.clone()),
+            disagreeing: responses
+                .iter()
+                .filter(|(_, other)| other != value)
+                .map(|(index, _)| *index)
+                .collect(),
+        },
+        _ => QuorumOutcome {
+            value: None,
+            disagreeing: vec![],
+        },
+    }
+}
+
+/// Order in which to try to connect to the endpoints: endpoints with fewer consecutive failures
+/// first, cycling from `starting_position` between endpoints as healthy as each other.
+fn endpoint_order(health: &[EndpointHealth], starting_position: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..health.len())
+        .map(|i| (starting_position + i) % health.len())
+        .collect();
+    // Stable sort, keeps the cycle order in case of a tie
+    order.sort_by_key(|index| health[*index].consecutive_failures);
+
+    order
+}
+
+/// Connects to a ws server by trying all provided urls, starting with the healthiest ones, until
 /// each one one was tried. Stops once a connection was successfully made.
 async fn connect_next_available_rpc_server(
     urls: &[String],
+    health: &mut [EndpointHealth],
     starting_position: usize,
+    metrics: Option<&OrchestratorRpcMetrics>,
 ) -> Result<(usize, Arc<JsonRpcClient>), ()> {
     tracing::debug!(target: LOG_TARGET, starting_position, "Connecting to RPC server.");
 
-    for (counter, url) in urls
-        .iter()
-        .cycle()
-        .skip(starting_position)
-        .take(urls.len())
-        .enumerate()
-    {
-        let index = (starting_position + counter) % urls.len();
+    for index in endpoint_order(health, starting_position) {
+        let url = &urls[index];
 
         tracing::info!(
             target: LOG_TARGET,
@@ -108,9 +256,20 @@ async fn connect_next_available_rpc_server(
             "Trying to connect to next external orchestrator node.",
         );
 
-        match WsClientBuilder::default().build(&url).await {
-            Ok(ws_client) => return Ok((index, Arc::new(ws_client))),
-            Err(err) => tracing::debug!(target: LOG_TARGET, url, ?err, "Unable to connect."),
+        match WsClientBuilder::default().build(url).await {
+            Ok(ws_client) => {
+                if let Some(metrics) = metrics {
+                    metrics.on_endpoint_health(index, true);
+                }
+                return Ok((index, Arc::new(ws_client)));
+            }
+            Err(err) => {
+                tracing::debug!(target: LOG_TARGET, url, ?err, "Unable to connect.");
+                health[index].consecutive_failures += 1;
+                if let Some(metrics) = metrics {
+                    metrics.on_endpoint_health(index, false);
+                }
+            }
         };
     }
     Err(())
@@ -118,19 +277,36 @@ async fn connect_next_available_rpc_server(
 
 impl ReconnectingWsClientWorker {
     /// Create a new worker that will connect to the provided URLs.
-    pub async fn new(urls: Vec<String>) -> Result<(Self, mpsc::Sender<WsClientRequest>), ()> {
+    ///
+    /// If `quorum` is set, quorum requests are sent to all the endpoints and only succeed if at
+    /// least `quorum` of them return the same response.
+    pub async fn new(
+        urls: Vec<String>,
+        quorum: Option<usize>,
+        metrics: Option<OrchestratorRpcMetrics>,
+    ) -> Result<(Self, mpsc::Sender<WsClientRequest>), ()> {
         if urls.is_empty() {
             return Err(());
         }
 
-        let (active_index, active_client) = connect_next_available_rpc_server(&urls, 0).await?;
+        let mut health = vec![EndpointHealth::default(); urls.len()];
+        let (active_index, active_client) =
+            connect_next_available_rpc_server(&urls, &mut health, 0, metrics.as_ref()).await?;
         let (request_sender, request_receiver) = mpsc::channel(100);
 
+        if let Some(metrics) = &metrics {
+            metrics.on_active_endpoint(active_index, urls.len());
+        }
+
         Ok((
             Self {
+                endpoint_pool: EndpointPool::new(urls.clone()),
                 urls,
                 active_client,
                 active_index,
+                health,
+                quorum,
+                metrics,
                 request_receiver,
                 best_header_listeners: vec![],
                 imported_header_listeners: vec![],
@@ -142,13 +318,143 @@ impl ReconnectingWsClientWorker {
 
     /// Change RPC server for future requests.
     async fn connect_to_new_rpc_server(&mut self) -> Result<(), ()> {
-        let (active_index, active_client) =
-            connect_next_available_rpc_server(&self.urls, self.active_index + 1).await?;
+        let (active_index, active_client) = connect_next_available_rpc_server(
+            &self.urls,
+            &mut self.health,
+            self.active_index + 1,
+            self.metrics.as_ref(),
+        )
+        .await?;
+        if active_index != self.active_index {
+            tracing::warn!(
+                target: LOG_TARGET,
+                from = self.active_index,
+                to = active_index,
+                "Switched to another orchestrator endpoint."
+            );
+            if let Some(metrics) = &self.metrics {
+                metrics.on_failover();
+            }
+        }
         self.active_index = active_index;
         self.active_client = active_client;
+        if let Some(metrics) = &self.metrics {
+            metrics.on_active_endpoint(active_index, self.urls.len());
+        }
+        Ok(())
+    }
+
+    /// Record a failure of the endpoint at `index`, so that other endpoints are preferred.
+    fn on_endpoint_failure(&mut self, index: usize) {
+        self.health[index].consecutive_failures += 1;
+        if let Some(metrics) = &self.metrics {
+            metrics.on_endpoint_health(index, false);
+        }
+    }
+
+    /// Check that the active endpoint is still following the chain. Returns the reason of the
+    /// failure otherwise.
+    async fn check_active_endpoint_health(
+        &self,
+        last_finalized_notification: Instant,
+    ) -> Result<(), &'static str> {
+        if last_finalized_notification.elapsed() > MAX_FINALIZATION_STALL {
+            return Err("finality_stalled");
+        }
+
+        let health: SystemHealth = timeout(
+            HEALTH_CHECK_TIMEOUT,
+            self.active_client.request("system_health", rpc_params![]),
+        )
+        .await
+        .map_err(|_| "timeout")?
+        .map_err(|err| {
+            tracing::debug!(target: LOG_TARGET, ?err, "Health check request failed.");
+            "rpc_error"
+        })?;
+
+        if health.is_syncing {
+            return Err("syncing");
+        }
+        if health.should_have_peers && health.peers == 0 {
+            return Err("no_peers");
+        }
+
         Ok(())
     }
 
+    /// Send the request to all the endpoints, and answer with the response returned by at least
+    /// `quorum` of them.
+    fn send_quorum_request(
+        &self,
+        quorum: usize,
+        JsonRpcRequest {
+            method,
+            params,
+            response_sender,
+        }: JsonRpcRequest,
+    ) -> QuorumRequestFuture {
+        let endpoint_pool = self.endpoint_pool.clone();
+        let metrics = self.metrics.clone();
+        async move {
+            let responses = join_all((0..endpoint_pool.urls.len()).map(|index| {
+                let endpoint_pool = &endpoint_pool;
+                let method = &method;
+                let params = params.clone();
+                async move { (index, endpoint_pool.request(index, method, params).await) }
+            }))
+            .await;
+            let responded = responses.iter().filter(|(_, x)| x.is_some()).count();
+            let outcome = quorum_outcome(responses, quorum);
+
+            let result = match outcome.value {
+                Some(value) => Ok(value),
+                None => {
+                    tracing::warn!(
+                        target: LOG_TARGET,
+                        %method,
+                        responded,
+                        quorum,
+                        "Orchestrator endpoints did not reach quorum."
+                    );
+                    Err(JsonRpseeError::Custom(format!(
+                        "Less than {} orchestrator endpoints returned the same response",
+                        quorum
+                    )))
+                }
+            };
+            if !outcome.disagreeing.is_empty() {
+                tracing::warn!(
+                    target: LOG_TARGET,
+                    %method,
+                    disagreeing = ?outcome.disagreeing,
+                    "Orchestrator endpoints returned a different response than the quorum."
+                );
+            }
+            if let Some(metrics) = &metrics {
+                metrics.on_quorum_request(match (&result, outcome.disagreeing.is_empty()) {
+                    (Err(_), _) => "no_quorum",
+                    (Ok(_), true) => "agreed",
+                    (Ok(_), false) => "disagreement",
+                });
+                for index in &outcome.disagreeing {
+                    metrics.on_endpoint_disagreement(*index);
+                }
+            }
+
+            if let Err(err) = response_sender.send(result) {
+                tracing::debug!(
+                    target: LOG_TARGET,
+                    ?err,
+                    "Recipient no longer interested in request result"
+                );
+            }
+
+            outcome.disagreeing
+        }
+        .boxed()
+    }
+
     /// Send the request to the current client. If this connection becomes dead, the returned future
     /// will return the request so it can be sent to another client.
     fn send_request(
@@ -244,11 +550,12 @@ impl ReconnectingWsClientWorker {
     }
 
     /// Handle a reconnection by fnding a new RPC server and sending all pending requests.
+    /// Returns the subscriptions of the new RPC server, which replace the ones of the previous server.
     async fn handle_reconnect(
         &mut self,
         pending_requests: &mut FuturesUnordered<RpcRequestFuture>,
         first_failed_request: Option<JsonRpcRequest>,
-    ) -> Result<(), String> {
+    ) -> Result<OrchestratorSubscription, String> {
         let mut requests_to_retry = Vec::new();
         if let Some(req) = first_failed_request {
             requests_to_retry.push(req)
@@ -275,13 +582,12 @@ impl ReconnectingWsClientWorker {
         // Get subscriptions from new endpoint.
         self.get_subscriptions().await.map_err(|e| {
 			format!("Not able to create streams from newly connected RPC server, shutting down. err: {:?}", e)
-		})?;
-
-        Ok(())
+		})
     }
 
     pub async fn run(mut self) {
         let mut pending_requests = FuturesUnordered::new();
+        let mut quorum_requests = FuturesUnordered::<QuorumRequestFuture>::new();
         let mut connection_status = ConnectionStatus::Connected;
 
         let Ok(mut subscriptions) = self.get_subscriptions().await else {
@@ -291,23 +597,34 @@ impl ReconnectingWsClientWorker {
 
         let mut imported_blocks_cache = LruMap::new(ByLength::new(40));
         let mut last_seen_finalized_num: dp_core::BlockNumber = 0;
+        let mut last_finalized_notification = Instant::now();
+        let mut health_check = interval_at(
+            Instant::now() + HEALTH_CHECK_INTERVAL,
+            HEALTH_CHECK_INTERVAL,
+        );
+        health_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
         loop {
             // Handle reconnection.
             if let ConnectionStatus::Disconnected { failed_request } = connection_status {
-                if let Err(message) = self
+                match self
                     .handle_reconnect(&mut pending_requests, failed_request)
                     .await
                 {
-                    tracing::error!(
-                        target: LOG_TARGET,
-                        message,
-                        "Unable to reconnect, stopping worker."
-                    );
-                    return;
+                    Ok(new_subscriptions) => subscriptions = new_subscriptions,
+                    Err(message) => {
+                        tracing::error!(
+                            target: LOG_TARGET,
+                            message,
+                            "Unable to reconnect, stopping worker."
+                        );
+                        return;
+                    }
                 }
 
                 connection_status = ConnectionStatus::Connected;
+                last_finalized_notification = Instant::now();
+                health_check.reset();
             }
 
             tokio::select! {
@@ -316,6 +633,10 @@ impl ReconnectingWsClientWorker {
                     Some(WsClientRequest::JsonRpcRequest(req)) => {
                         pending_requests.push(self.send_request(req));
                     },
+                    Some(WsClientRequest::QuorumRequest(req)) => match self.quorum {
+                        Some(quorum) => quorum_requests.push(self.send_quorum_request(quorum, req)),
+                        None => pending_requests.push(self.send_request(req)),
+                    },
                     Some(WsClientRequest::RegisterBestHeadListener(tx)) => {
                         self.best_header_listeners.push(tx);
                     },
@@ -334,9 +655,43 @@ impl ReconnectingWsClientWorker {
                 // ws client was disconnected and we need to reconnect to a new ws client.
                 pending = pending_requests.next(), if !pending_requests.is_empty() => {
                     if let Some(Err(req)) = pending {
+                        self.on_endpoint_failure(self.active_index);
                         connection_status = ConnectionStatus::Disconnected { failed_request: Some(req) };
                     }
                 },
+                // Endpoints that disagree with the quorum are less preferred, and if the active
+                // endpoint is one of them, we switch to another one.
+                Some(disagreeing) = quorum_requests.next(), if !quorum_requests.is_empty() => {
+                    for index in &disagreeing {
+                        self.on_endpoint_failure(*index);
+                    }
+                    if disagreeing.contains(&self.active_index) {
+                        connection_status = ConnectionStatus::Disconnected { failed_request: None };
+                    }
+                },
+                _ = health_check.tick() => {
+                    match self.check_active_endpoint_health(last_finalized_notification).await {
+                        Ok(()) => {
+                            self.health[self.active_index].consecutive_failures = 0;
+                            if let Some(metrics) = &self.metrics {
+                                metrics.on_endpoint_health(self.active_index, true);
+                            }
+                        }
+                        Err(reason) => {
+                            tracing::warn!(
+                                target: LOG_TARGET,
+                                index = self.active_index,
+                                reason,
+                                "Orchestrator endpoint health check failed."
+                            );
+                            if let Some(metrics) = &self.metrics {
+                                metrics.on_health_check_failure(self.active_index, reason);
+                            }
+                            self.on_endpoint_failure(self.active_index);
+                            connection_status = ConnectionStatus::Disconnected { failed_request: None };
+                        }
+                    }
+                },
                 import_event = subscriptions.import_subscription.next() => {
                     match import_event {
                         Some(Ok(header)) => {
@@ -355,10 +710,12 @@ impl ReconnectingWsClientWorker {
                         },
                         None => {
                             tracing::error!(target: LOG_TARGET, "Subscription closed.");
+                            self.on_endpoint_failure(self.active_index);
                             connection_status = ConnectionStatus::Disconnected { failed_request: None};
                         },
                         Some(Err(error)) => {
                             tracing::error!(target: LOG_TARGET, ?error, "Error in RPC subscription.");
+                            self.on_endpoint_failure(self.active_index);
                             connection_status = ConnectionStatus::Disconnected { failed_request: None};
                         },
                     }
@@ -368,10 +725,12 @@ impl ReconnectingWsClientWorker {
                         Some(Ok(header)) => distribute(header, &mut self.best_header_listeners),
                         None => {
                             tracing::error!(target: LOG_TARGET, "Subscription closed.");
+                            self.on_endpoint_failure(self.active_index);
                             connection_status = ConnectionStatus::Disconnected { failed_request: None};
                         },
                         Some(Err(error)) => {
                             tracing::error!(target: LOG_TARGET, ?error, "Error in RPC subscription.");
+                            self.on_endpoint_failure(self.active_index);
                             connection_status = ConnectionStatus::Disconnected { failed_request: None};
                         },
                     }
@@ -380,6 +739,7 @@ impl ReconnectingWsClientWorker {
                     match finalized_event {
                         Some(Ok(header)) if header.number > last_seen_finalized_num => {
                             last_seen_finalized_num = header.number;
+                            last_finalized_notification = Instant::now();
                             distribute(header, &mut self.finalized_header_listeners);
                         },
                         Some(Ok(header)) => {
@@ -392,10 +752,12 @@ impl ReconnectingWsClientWorker {
                         },
                         None => {
                             tracing::error!(target: LOG_TARGET, "Subscription closed.");
+                            self.on_endpoint_failure(self.active_index);
                             connection_status = ConnectionStatus::Disconnected { failed_request: None};
                         },
                         Some(Err(error)) => {
                             tracing::error!(target: LOG_TARGET, ?error, "Error in RPC subscription.");
+                            self.on_endpoint_failure(self.active_index);
                             connection_status = ConnectionStatus::Disconnected { failed_request: None};
                         },
                     }
@@ -423,3 +785,68 @@ pub fn distribute<T: Clone + Send>(value: T, senders: &mut Vec<mpsc::Sender<T>>)
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    fn health(consecutive_failures: &[u32]) -> Vec<EndpointHealth> {
+        consecutive_failures
+            .iter()
+            .map(|x| EndpointHealth {
+                consecutive_failures: *x,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn endpoint_order_prefers_healthy_endpoints() {
+        assert_eq!(endpoint_order(&health(&[0, 0, 0]), 1), vec![1, 2, 0]);
+        assert_eq!(endpoint_order(&health(&[0, 1, 0]), 1), vec![2, 0, 1]);
+        assert_eq!(endpoint_order(&health(&[3, 1, 2]), 0), vec![1, 2, 0]);
+        assert_eq!(endpoint_order(&health(&[0]), 1), vec![0]);
+    }
+
+    #[test]
+    fn quorum_outcome_accepts_most_common_response() {
+        let outcome = quorum_outcome(
+            vec![
+                (0, Some(json!("0x01"))),
+                (1, Some(json!("0x02"))),
+                (2, Some(json!("0x01"))),
+                (3, None),
+            ],
+            2,
+        );
+
+        assert_eq!(
+            outcome,
+            QuorumOutcome {
+                value: Some(json!("0x01")),
+                disagreeing: vec![1],
+            }
+        );
+    }
+
+    #[test]
+    fn quorum_outcome_without_quorum() {
+        // Endpoints that did not respond do not count towards the quorum
+        let outcome = quorum_outcome(vec![(0, Some(json!("0x01"))), (1, None), (2, None)], 2);
+        assert_eq!(
+            outcome,
+            QuorumOutcome {
+                value: None,
+                disagreeing: vec![],
+            }
+        );
+
+        let outcome = quorum_outcome(vec![(0, Some(json!("0x01"))), (1, Some(json!("0x02")))], 2);
+        assert_eq!(
+            outcome,
+            QuorumOutcome {
+                value: None,
+                disagreeing: vec![],
+            }
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! RPC client to track the messages sent to Ethereum through the outbound queues
+
+pub use pallet_outbound_message_commitment_recorder_runtime_api::OutboundMessageTrackingApi as OutboundMessageTrackingRuntimeApi;
+use {
+    core::marker::PhantomData,
+    jsonrpsee::{
+        core::{async_trait, RpcResult},
+        proc_macros::rpc,
+    },
+    pallet_outbound_message_commitment_recorder_runtime_api::OutboundMessageStatus,
+    sc_client_api::UsageProvider,
+    sp_api::ProvideRuntimeApi,
+    sp_core::H256,
+    sp_runtime::traits::Block as BlockT,
+    std::sync::Arc,
+};
+
+#[rpc(server)]
+pub trait OutboundMessageTrackingApi<BlockNumber> {
+    /// Status of a message sent to Ethereum, by message id or topic, at the best block.
+    #[method(name = "tanssi_outboundMessageStatus")]
+    async fn outbound_message_status(
+        &self,
+        message_id: H256,
+    ) -> RpcResult<Option<OutboundMessageStatus<BlockNumber>>>;
+}
+
+pub struct OutboundMessageTracking<Client, Block> {
+    client: Arc<Client>,
+    _phantom: PhantomData<Block>,
+}
+
+impl<Client, Block> OutboundMessageTracking<Client, Block> {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<Client, Hash, Block, BlockNumber> OutboundMessageTrackingApiServer<BlockNumber>
+    for OutboundMessageTracking<Client, Block>
+where
+    Hash: Send + 'static,
+    Block: BlockT<Hash = Hash>,
+    Client: ProvideRuntimeApi<Block> + Sync + Send + UsageProvider<Block> + 'static,
+    Client::Api: OutboundMessageTrackingRuntimeApi<Block, BlockNumber>,
+    BlockNumber: parity_scale_codec::Codec + Send + 'static,
+{
+    async fn outbound_message_status(
+        &self,
+        message_id: H256,
+    ) -> RpcResult<Option<OutboundMessageStatus<BlockNumber>>> {
+        let status = self
+            .client
+            .runtime_api()
+            .outbound_message_status(self.client.usage_info().chain.best_hash, message_id)
+            .map_err(internal_err)?;
+        Ok(status)
+    }
+}
+
+pub fn internal_err<T: ToString>(message: T) -> jsonrpsee::types::ErrorObjectOwned {
+    jsonrpsee::types::error::ErrorObject::borrowed(
+        jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+        &message.to_string(),
+        None,
+    )
+    .into_owned()
+}
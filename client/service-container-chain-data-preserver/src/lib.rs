@@ -25,7 +25,9 @@ use {
     sc_cli::SubstrateCli,
     sc_service::{Configuration, KeystoreContainer, TaskManager},
     sc_telemetry::TelemetryWorker,
+    sp_core::H256,
     std::{marker::PhantomData, sync::Arc},
+    tc_orchestrator_chain_interface_through_rpc::light_client,
     tc_service_container_chain_spawner::{
         cli::{ContainerChainCli, ContainerChainRunCmd},
         rpc::generate_rpc_builder::GenerateRpcBuilder,
@@ -54,6 +56,17 @@ pub struct DataPreserverCmd {
     #[arg(long)]
     pub orchestrator_endpoints: Vec<Url>,
 
+    /// Number of orchestrator endpoints that must return the same container chain assignment and
+    /// genesis data before using them. If not set, only the active endpoint is queried.
+    #[arg(long, requires = "orchestrator_endpoints")]
+    pub orchestrator_endpoints_quorum: Option<usize>,
+
+    /// Trusted orchestrator block hash from which to start an embedded light client, verifying
+    /// the finality proofs, storage and runtime calls returned by the orchestrator endpoints
+    /// instead of trusting them. Only supported for solochain orchestrators.
+    #[arg(long, requires = "orchestrator_endpoints")]
+    pub orchestrator_light_client_checkpoint: Option<H256>,
+
     /// If running an embeded node, will run it as a solochain orchestrator.
     /// If not present, will run it as a parachain orchestrator.
     #[arg(long)]
@@ -105,6 +118,14 @@ pub struct DataPreserverMode<PolkaCli, GRB, RuntimeApi, DVC> {
     /// embeded orchestrator node.
     pub orchestrator_endpoints: Vec<Url>,
 
+    /// Number of remote orchestrator nodes that must agree on the data preserver assignment and
+    /// container chain genesis data.
+    pub orchestrator_endpoints_quorum: Option<usize>,
+
+    /// If set, the remote orchestrator nodes are only used as an untrusted transport by a light
+    /// client starting at this block.
+    pub orchestrator_light_client_checkpoint: Option<H256>,
+
     pub collator_options: CollatorOptions,
 
     /// Run arguments for the relaychain. Will also be the orchestrator chain if
@@ -234,16 +255,33 @@ where
                 .map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
 
             // Orchestrator
-            orchestrator_chain_interface =
+            let orchestrator_rpc_client =
                 tc_orchestrator_chain_interface_through_rpc::create_client_and_start_worker(
                     self.orchestrator_endpoints.clone(),
+                    self.orchestrator_endpoints_quorum,
                     &mut task_manager,
                     None,
+                    self.config.prometheus_registry(),
                 )
                 .await
-                .map(Arc::new)
                 .map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
 
+            orchestrator_chain_interface = match self.orchestrator_light_client_checkpoint {
+                Some(checkpoint) => {
+                    log::info!("Verifying orchestrator data with a light client ...");
+
+                    light_client::start_light_client(
+                        orchestrator_rpc_client,
+                        checkpoint,
+                        &mut task_manager,
+                    )
+                    .await
+                    .map(Arc::new)
+                    .map_err(|e| sc_cli::Error::Application(Box::new(e)))?
+                }
+                None => Arc::new(orchestrator_rpc_client),
+            };
+
             // Relay
             let collator_options = self.collator_options;
 
@@ -310,6 +348,7 @@ where
                 override_sync_mode: None,
                 start_bootnode_params,
                 data_preserver: true,
+                metrics: None,
                 phantom: PhantomData,
             },
             state: Default::default(),
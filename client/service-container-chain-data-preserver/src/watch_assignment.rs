@@ -23,7 +23,7 @@ use {
     futures::stream::StreamExt,
     sc_tracing::tracing::Instrument,
     std::{future::Future, time::Duration},
-    tc_service_container_chain_spawner::spawner::{wait_for_paritydb_lock, Spawner},
+    tc_service_container_chain_spawner::spawner::{wait_for_db_lock, Spawner},
 };
 
 pub type ProfileId = <dancebox_runtime::Runtime as pallet_data_preservers::Config>::ProfileId;
@@ -76,7 +76,7 @@ pub async fn task_watch_assignment(spawner: impl Spawner, profile_id: ProfileId)
                 (Assignment::Active(para_id), Assignment::Inactive(x)) if para_id == x => {
                     let db_path = spawner.stop(para_id, true); // keep db
                     if let Some(db_path) = db_path {
-                        wait_for_paritydb_lock(&db_path, Duration::from_secs(10))
+                        wait_for_db_lock(&db_path, Duration::from_secs(10))
                             .await
                             .map_err(OrchestratorChainError::GenericError)?;
                     }
@@ -92,7 +92,7 @@ pub async fn task_watch_assignment(spawner: impl Spawner, profile_id: ProfileId)
                 (Assignment::Active(previous_para_id), Assignment::Active(para_id)) => {
                     let db_path = spawner.stop(previous_para_id, false); // don't keep db
                     if let Some(db_path) = db_path {
-                        wait_for_paritydb_lock(&db_path, Duration::from_secs(10))
+                        wait_for_db_lock(&db_path, Duration::from_secs(10))
                             .await
                             .map_err(OrchestratorChainError::GenericError)?;
                     }
@@ -340,7 +340,7 @@ mod tests {
         ///
         /// It is possible that we try to spawn-stop-spawn the same chain, and the second spawn fails
         /// because the chain has not stopped yet, because `stop` does not wait for the chain to stop,
-        /// so before calling `spawn` make sure to call `wait_for_paritydb_lock` before, like we do in
+        /// so before calling `spawn` make sure to call `wait_for_db_lock` before, like we do in
         /// `handle_update_assignment`.
         fn spawn(
             &self,
@@ -357,7 +357,7 @@ mod tests {
         }
 
         /// Stop a container chain. Prints a warning if the container chain was not running.
-        /// Returns the database path for the container chain, can be used with `wait_for_paritydb_lock`
+        /// Returns the database path for the container chain, can be used with `wait_for_db_lock`
         /// to ensure that the container chain has fully stopped. The database path can be `None` if the
         /// chain was not running.
         fn stop(&self, container_chain_para_id: ParaId, keep_db: bool) -> Option<PathBuf> {
@@ -0,0 +1,234 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Container chain spawner admin RPC
+//!
+//! Allows node operators to inspect the container chains spawned by the orchestrator node, and to
+//! manually start, stop or resync them. All the methods are unsafe, so they are only available
+//! when running with `--rpc-methods unsafe`, or on a local RPC interface.
+//!
+//! Manual changes only last until the next assignment change: the spawner will then start and
+//! stop container chains based on the new assignment, as usual.
+
+use {
+    crate::{
+        monitor::DbRemoval,
+        spawner::{CcSpawnMsg, ContainerChainSpawnerState},
+    },
+    cumulus_primitives_core::ParaId,
+    dancebox_runtime::{opaque::Block, AccountId, BlockNumber},
+    jsonrpsee::{
+        core::{async_trait, RpcResult},
+        proc_macros::rpc,
+        types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned},
+        Extensions,
+    },
+    pallet_author_noting_runtime_api::AuthorNotingApi,
+    sc_rpc_api::check_if_safe,
+    serde::{Deserialize, Serialize},
+    sp_api::ProvideRuntimeApi,
+    sp_blockchain::HeaderBackend,
+    sp_core::H256,
+    std::sync::{Arc, Mutex},
+    tokio::sync::{mpsc, oneshot},
+};
+
+/// What a spawned container chain is currently doing.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContainerChainRunState {
+    /// Running with collation enabled
+    Collating,
+    /// Running as a full node, usually to be ready to collate in the next session
+    Syncing,
+    /// Asked to stop, but some of its components are still running
+    Stopping,
+    /// Failed to start or stopped unexpectedly
+    Failed,
+}
+
+/// Best and finalized block of a container chain database.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerChainSyncStatus {
+    pub best_number: u32,
+    pub best_hash: H256,
+    pub finalized_number: u32,
+    pub finalized_hash: H256,
+}
+
+/// Status of a container chain known by the spawner.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerChainStatus {
+    pub para_id: u32,
+    pub state: ContainerChainRunState,
+    /// Database path, only known for running chains
+    pub db_path: Option<String>,
+    /// Only known for running chains
+    pub sync_status: Option<ContainerChainSyncStatus>,
+    /// Latest container chain block noted on the finalized orchestrator block
+    pub latest_block_number_onchain: Option<u32>,
+}
+
+/// Response of `tanssi_containerChainSpawnerStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpawnerStatus {
+    /// Chains this node is assigned to in the current session, including the orchestrator chain
+    pub assigned_para_ids: Vec<u32>,
+    /// Chains this node is assigned to in the next session
+    pub next_assigned_para_ids: Vec<u32>,
+    pub container_chains: Vec<ContainerChainStatus>,
+    /// Most recent database removals, oldest first
+    pub db_removals: Vec<DbRemoval>,
+}
+
+/// Admin RPC of the container chain spawner.
+#[rpc(client, server)]
+pub trait ContainerChainSpawnerAdminApi {
+    /// List the container chains spawned by this node, and the database removal history.
+    #[method(name = "tanssi_containerChainSpawnerStatus", with_extensions)]
+    async fn status(&self) -> RpcResult<SpawnerStatus>;
+
+    /// Start a container chain, optionally collating on it.
+    #[method(name = "tanssi_containerChainSpawnerSpawn", with_extensions)]
+    async fn spawn(&self, para_id: u32, collate: bool) -> RpcResult<()>;
+
+    /// Stop a running container chain. When running as a collator the database is removed,
+    /// unless `keep_db` is true or the node was started with `--keep-db`.
+    #[method(name = "tanssi_containerChainSpawnerStop", with_extensions)]
+    async fn stop(&self, para_id: u32, keep_db: bool) -> RpcResult<()>;
+
+    /// Stop a running container chain, remove its database and start it again.
+    #[method(name = "tanssi_containerChainSpawnerResync", with_extensions)]
+    async fn resync(&self, para_id: u32) -> RpcResult<()>;
+}
+
+/// Implementation of [ContainerChainSpawnerAdminApiServer], using the orchestrator chain client
+/// to read the latest block number of each container chain.
+pub struct ContainerChainSpawnerAdmin<C> {
+    client: Arc<C>,
+    state: Arc<Mutex<ContainerChainSpawnerState>>,
+    cc_spawn_tx: mpsc::UnboundedSender<CcSpawnMsg>,
+}
+
+impl<C> ContainerChainSpawnerAdmin<C> {
+    pub fn new(
+        client: Arc<C>,
+        state: Arc<Mutex<ContainerChainSpawnerState>>,
+        cc_spawn_tx: mpsc::UnboundedSender<CcSpawnMsg>,
+    ) -> Self {
+        Self {
+            client,
+            state,
+            cc_spawn_tx,
+        }
+    }
+
+    /// Send a message to the spawner and wait for the result.
+    async fn send(
+        &self,
+        msg: impl FnOnce(oneshot::Sender<Result<(), String>>) -> CcSpawnMsg,
+    ) -> RpcResult<()> {
+        let (result, rx) = oneshot::channel();
+        self.cc_spawn_tx
+            .send(msg(result))
+            .map_err(|_| internal_err("Container chain spawner is not running"))?;
+
+        rx.await
+            .map_err(|_| internal_err("Container chain spawner dropped the request"))?
+            .map_err(internal_err)
+    }
+}
+
+#[async_trait]
+impl<C> ContainerChainSpawnerAdminApiServer for ContainerChainSpawnerAdmin<C>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    C::Api: AuthorNotingApi<Block, AccountId, BlockNumber, ParaId>,
+{
+    async fn status(&self, ext: &Extensions) -> RpcResult<SpawnerStatus> {
+        check_if_safe(ext)?;
+
+        let (assigned_para_ids, next_assigned_para_ids, mut container_chains, db_removals) = {
+            let state = self.state.lock().expect("poison error");
+            let (current, next) = state.assignment();
+
+            (
+                current.into_iter().map(Into::into).collect(),
+                next.into_iter().map(Into::into).collect(),
+                state.container_chains_status(),
+                state
+                    .spawned_containers_monitor
+                    .db_removals()
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        let finalized_hash = self.client.info().finalized_hash;
+        let api = self.client.runtime_api();
+        for chain in &mut container_chains {
+            chain.latest_block_number_onchain = api
+                .latest_block_number(finalized_hash, chain.para_id.into())
+                .map_err(internal_err)?;
+        }
+
+        Ok(SpawnerStatus {
+            assigned_para_ids,
+            next_assigned_para_ids,
+            container_chains,
+            db_removals,
+        })
+    }
+
+    async fn spawn(&self, ext: &Extensions, para_id: u32, collate: bool) -> RpcResult<()> {
+        check_if_safe(ext)?;
+
+        self.send(|result| CcSpawnMsg::Spawn {
+            para_id: para_id.into(),
+            start_collation: collate,
+            result,
+        })
+        .await
+    }
+
+    async fn stop(&self, ext: &Extensions, para_id: u32, keep_db: bool) -> RpcResult<()> {
+        check_if_safe(ext)?;
+
+        self.send(|result| CcSpawnMsg::Stop {
+            para_id: para_id.into(),
+            keep_db,
+            result,
+        })
+        .await
+    }
+
+    async fn resync(&self, ext: &Extensions, para_id: u32) -> RpcResult<()> {
+        check_if_safe(ext)?;
+
+        self.send(|result| CcSpawnMsg::Resync {
+            para_id: para_id.into(),
+            result,
+        })
+        .await
+    }
+}
+
+fn internal_err<T: ToString>(message: T) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, message.to_string(), None::<()>)
+}
@@ -20,7 +20,7 @@ use {
     dp_container_chain_genesis_data::json::properties_to_map,
     node_common::chain_spec::Extensions,
     sc_chain_spec::ChainSpec,
-    sc_cli::{CliConfiguration, SubstrateCli},
+    sc_cli::{CliConfiguration, DatabasePruningMode, SubstrateCli},
     sc_network::config::MultiaddrWithPeerId,
     sc_service::BasePath,
     sp_runtime::Storage,
@@ -50,11 +50,71 @@ pub struct ContainerChainRunCmd {
     #[arg(long)]
     pub keep_db: bool,
 
+    /// Maximum number of container chains this node will collate on at the same time.
+    ///
+    /// A collator can be assigned to more than one parathread. Each assigned parathread gets its
+    /// own collation task, up to this limit. Assignments above the limit are ignored, in
+    /// ascending para id order.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_collating_container_chains: u32,
+
+    /// Distance between the ports of the container chains collated at the same time.
+    ///
+    /// The first collating container chain uses the configured p2p, rpc and prometheus ports.
+    /// Each additional one uses the same ports moved by this stride, so that they do not collide
+    /// with the configured ports or with each other.
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u16).range(1..))]
+    pub collating_container_chains_port_stride: u16,
+
+    /// Bootstrap the container chain database from a state snapshot served by the data
+    /// preservers assigned to the chain, when the local database is far behind.
+    ///
+    /// The snapshot is verified against the container chain head included in the relay chain.
+    /// If no snapshot can be downloaded, the node falls back to syncing from its peers.
+    #[arg(long)]
+    pub bootstrap_from_snapshot: bool,
+
+    /// Maximum disk usage in MiB of all the container chain databases.
+    ///
+    /// When exceeded, the databases of the least recently assigned container chains are removed.
+    /// Databases of running or assigned container chains are never removed.
+    #[arg(long)]
+    pub container_dbs_max_size_mib: Option<u64>,
+
+    /// Maximum number of container chain databases to keep on disk.
+    ///
+    /// When exceeded, the databases of the least recently assigned container chains are removed.
+    /// Databases of running or assigned container chains are never removed.
+    #[arg(long)]
+    pub container_dbs_max_count: Option<u32>,
+
+    /// Override the state pruning mode of one container chain, as `<PARA_ID>=<MODE>`.
+    ///
+    /// The mode has the same format as `--state-pruning`. Can be passed more than once. Changing
+    /// the pruning mode of an existing database is not supported, so remove it or use
+    /// `tanssi_containerChainSpawnerResync` after changing this.
+    #[arg(long, value_parser = parse_container_chain_pruning)]
+    pub container_chain_pruning: Vec<(u32, DatabasePruningMode)>,
+
     /// Download the full block history for container chains after the warp sync is done.
     /// Default value: false for container collators, true for data preservers.
     #[arg(long)]
     pub download_block_history: Option<bool>,
 
+    /// Secret URI of the account that controls the session keys of this collator.
+    ///
+    /// When the local Nimbus keys are not registered on the orchestrator chain, the node generates
+    /// a key if needed and submits `session.set_keys` signed by this account. Only supported when
+    /// the orchestrator chain is a parachain. Solochain collators only use it to check that the
+    /// registered keys belong to this account.
+    #[arg(long, requires = "collator")]
+    pub session_keys_controller: Option<String>,
+
+    /// Do not collate while the local Nimbus keys do not match the session keys registered on the
+    /// orchestrator chain.
+    #[arg(long, requires = "collator")]
+    pub refuse_collating_on_session_keys_mismatch: bool,
+
     /// Creates a less resource-hungry node that retrieves relay chain data from an RPC endpoint.
     ///
     /// The provided URLs should point to RPC endpoints of the relay chain.
@@ -476,6 +536,18 @@ fn parse_container_chain_id_str(id: &str) -> std::result::Result<u32, String> {
         .ok_or_else(|| format!("load_spec called with invalid id: {:?}", id))
 }
 
+fn parse_container_chain_pruning(arg: &str) -> Result<(u32, DatabasePruningMode), String> {
+    let (para_id, mode) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("'{}' must have the format <PARA_ID>=<MODE>", arg))?;
+    let para_id = para_id
+        .parse()
+        .map_err(|e| format!("Invalid para id '{}': {}", para_id, e))?;
+    let mode = mode.parse()?;
+
+    Ok((para_id, mode))
+}
+
 // Copied from polkadot-sdk/cumulus/client/cli/src/lib.rs
 fn validate_relay_chain_url(arg: &str) -> Result<Url, String> {
     let url = Url::parse(arg).map_err(|e| e.to_string())?;
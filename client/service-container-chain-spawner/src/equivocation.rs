@@ -0,0 +1,302 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Detection of container chain collators that seal two different blocks for the same slot.
+//!
+//! [EquivocationWatcher] inspects the container chain blocks imported from the network, and sends
+//! an equivocation proof to [run_equivocation_reporter] when it finds two different blocks sealed
+//! by the same author for the same slot. The reporter submits the proof to the orchestrator as an
+//! unsigned extrinsic. This needs the embedded orchestrator node of parachain collators, so
+//! solochain collators and data preservers only log the equivocation.
+
+use {
+    crate::service::ParachainClient,
+    cumulus_client_consensus_common::ParachainBlockImportMarker,
+    cumulus_primitives_core::ParaId,
+    dancebox_runtime::opaque::Block,
+    nimbus_primitives::NimbusId,
+    pallet_author_noting::equivocation::sealed_header_info,
+    pallet_author_noting_runtime_api::ContainerChainEquivocationApi,
+    sc_consensus::{BlockCheckParams, BlockImport, BlockImportParams, ImportResult},
+    sc_transaction_pool::TransactionPoolHandle,
+    sc_transaction_pool_api::TransactionPool,
+    sp_api::ProvideRuntimeApi,
+    sp_blockchain::HeaderBackend,
+    sp_consensus::BlockOrigin,
+    sp_consensus_aura::Slot,
+    sp_core::H256,
+    sp_runtime::{traits::Header as HeaderT, transaction_validity::TransactionSource},
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    },
+    tokio::sync::mpsc,
+    tp_traits::{ContainerChainEquivocationProof, ContainerChainHeader},
+};
+
+const LOG_TARGET: &str = "container-chain-equivocation";
+
+/// Blocks older than this number of slots, compared to the latest imported block, are ignored.
+/// Equivocations can only be reported during the session in which they happened anyway.
+const SLOTS_TO_KEEP: u64 = 600;
+
+struct SeenHeader {
+    author: NimbusId,
+    pre_hash: H256,
+    header: ContainerChainHeader,
+    reported: bool,
+}
+
+/// Sealed headers imported recently, by slot.
+#[derive(Default)]
+struct SeenHeaders {
+    by_slot: BTreeMap<Slot, Vec<SeenHeader>>,
+    latest_slot: Slot,
+}
+
+impl SeenHeaders {
+    /// Remember `header`, and return the header it conflicts with if its author already sealed
+    /// a different block for the same slot. Each author is only returned once per slot.
+    fn note_header(
+        &mut self,
+        header: ContainerChainHeader,
+    ) -> Option<(Slot, ContainerChainHeader)> {
+        let (pre_hash, slot, author) = sealed_header_info(&header)?;
+
+        self.latest_slot = self.latest_slot.max(slot);
+        let oldest_slot = Slot::from(u64::from(self.latest_slot).saturating_sub(SLOTS_TO_KEEP));
+        if slot < oldest_slot {
+            return None;
+        }
+        self.by_slot = self.by_slot.split_off(&oldest_slot);
+
+        let seen = self.by_slot.entry(slot).or_default();
+        match seen.iter_mut().find(|seen| seen.author == author) {
+            Some(first) if first.pre_hash != pre_hash && !first.reported => {
+                first.reported = true;
+                Some((slot, first.header.clone()))
+            }
+            Some(_) => None,
+            None => {
+                seen.push(SeenHeader {
+                    author,
+                    pre_hash,
+                    header,
+                    reported: false,
+                });
+                None
+            }
+        }
+    }
+}
+
+/// Container chain block import that looks for equivocations in the imported blocks before
+/// passing them to the inner block import.
+#[derive(Clone)]
+pub struct EquivocationWatcher<BI> {
+    inner: BI,
+    para_id: ParaId,
+    seen: Arc<Mutex<SeenHeaders>>,
+    proof_sender: mpsc::UnboundedSender<ContainerChainEquivocationProof>,
+}
+
+impl<BI> EquivocationWatcher<BI> {
+    pub fn new(
+        inner: BI,
+        para_id: ParaId,
+        proof_sender: mpsc::UnboundedSender<ContainerChainEquivocationProof>,
+    ) -> Self {
+        Self {
+            inner,
+            para_id,
+            seen: Default::default(),
+            proof_sender,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<BI> BlockImport<Block> for EquivocationWatcher<BI>
+where
+    BI: BlockImport<Block> + Send + Sync,
+{
+    type Error = BI::Error;
+
+    async fn check_block(
+        &self,
+        block: BlockCheckParams<Block>,
+    ) -> Result<ImportResult, Self::Error> {
+        self.inner.check_block(block).await
+    }
+
+    async fn import_block(
+        &self,
+        params: BlockImportParams<Block>,
+    ) -> Result<ImportResult, Self::Error> {
+        // Blocks downloaded during the initial sync are too old to be reported
+        if params.origin != BlockOrigin::NetworkInitialSync {
+            // The import queue moves the seal to the post digests
+            let header = params.post_header();
+            let conflict = self
+                .seen
+                .lock()
+                .expect("poison error")
+                .note_header(header.clone());
+
+            if let Some((slot, first_header)) = conflict {
+                let _ = self.proof_sender.send(ContainerChainEquivocationProof {
+                    para_id: self.para_id,
+                    slot,
+                    first_header,
+                    second_header: header,
+                });
+            }
+        }
+
+        self.inner.import_block(params).await
+    }
+}
+
+impl<BI> ParachainBlockImportMarker for EquivocationWatcher<BI> {}
+
+/// Orchestrator client and transaction pool used to submit equivocation reports.
+pub type OrchestratorReporter = (
+    Arc<ParachainClient>,
+    Arc<TransactionPoolHandle<Block, ParachainClient>>,
+);
+
+/// Log the equivocations found by [EquivocationWatcher], and report them to the orchestrator if
+/// `orchestrator` is set.
+pub async fn run_equivocation_reporter(
+    mut proof_receiver: mpsc::UnboundedReceiver<ContainerChainEquivocationProof>,
+    orchestrator: Option<OrchestratorReporter>,
+) {
+    while let Some(proof) = proof_receiver.recv().await {
+        log::warn!(
+            target: LOG_TARGET,
+            "Collator equivocation in container chain {}: blocks {:?} and {:?} sealed by the same author for slot {}",
+            proof.para_id,
+            proof.first_header.hash(),
+            proof.second_header.hash(),
+            proof.slot,
+        );
+
+        let Some((orchestrator_client, orchestrator_tx_pool)) = &orchestrator else {
+            continue;
+        };
+
+        let best_hash = orchestrator_client.info().best_hash;
+        let extrinsic = match orchestrator_client
+            .runtime_api()
+            .create_equivocation_report_unsigned_extrinsic(best_hash, proof)
+        {
+            Ok(extrinsic) => extrinsic,
+            Err(e) => {
+                log::warn!(target: LOG_TARGET, "Failed to create equivocation report: {}", e);
+                continue;
+            }
+        };
+
+        if let Err(e) = orchestrator_tx_pool
+            .submit_one(best_hash, TransactionSource::Local, *extrinsic)
+            .await
+        {
+            log::warn!(target: LOG_TARGET, "Failed to submit equivocation report: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        nimbus_primitives::CompatibleDigestItem,
+        parity_scale_codec::Encode,
+        sp_consensus_aura::AURA_ENGINE_ID,
+        sp_core::{sr25519, Pair},
+        sp_runtime::{Digest, DigestItem},
+    };
+
+    /// Same digests as the ones added by `tc_consensus::collators::seal_tanssi`.
+    fn sealed_header(number: u32, slot: u64, author: &sr25519::Pair) -> ContainerChainHeader {
+        let mut header = ContainerChainHeader::new(
+            number,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Digest::default(),
+        );
+        header.digest_mut().push(DigestItem::PreRuntime(
+            AURA_ENGINE_ID,
+            Slot::from(slot).encode(),
+        ));
+        header
+            .digest_mut()
+            .push(<DigestItem as CompatibleDigestItem>::nimbus_pre_digest(
+                author.public().into(),
+            ));
+        let signature = author.sign(header.hash().as_ref());
+        header
+            .digest_mut()
+            .push(<DigestItem as CompatibleDigestItem>::nimbus_seal(
+                signature.into(),
+            ));
+
+        header
+    }
+
+    #[test]
+    fn detects_two_blocks_for_the_same_slot() {
+        let alice = sr25519::Pair::from_seed(&[1; 32]);
+        let bob = sr25519::Pair::from_seed(&[2; 32]);
+        let mut seen = SeenHeaders::default();
+
+        let first = sealed_header(1, 10, &alice);
+        assert_eq!(seen.note_header(first.clone()), None);
+        // Importing the same block again is not an equivocation
+        assert_eq!(seen.note_header(first.clone()), None);
+        // Another author for the same slot is not an equivocation either
+        assert_eq!(seen.note_header(sealed_header(2, 10, &bob)), None);
+
+        assert_eq!(
+            seen.note_header(sealed_header(2, 10, &alice)),
+            Some((10.into(), first))
+        );
+        // Only reported once
+        assert_eq!(seen.note_header(sealed_header(3, 10, &alice)), None);
+    }
+
+    #[test]
+    fn ignores_old_and_unsealed_blocks() {
+        let alice = sr25519::Pair::from_seed(&[1; 32]);
+        let mut seen = SeenHeaders::default();
+
+        assert_eq!(seen.note_header(sealed_header(1, 10, &alice)), None);
+        assert_eq!(
+            seen.note_header(sealed_header(2, 10 + SLOTS_TO_KEEP + 1, &alice)),
+            None
+        );
+
+        // Slot 10 was pruned and is now too old
+        assert_eq!(seen.note_header(sealed_header(3, 10, &alice)), None);
+        assert!(!seen.by_slot.contains_key(&10.into()));
+
+        // Headers without a valid seal are ignored
+        let mut unsealed = sealed_header(4, 10 + SLOTS_TO_KEEP + 1, &alice);
+        unsealed.digest_mut().pop();
+        assert_eq!(seen.note_header(unsealed), None);
+    }
+}
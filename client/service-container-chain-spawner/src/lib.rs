@@ -14,9 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
+pub mod admin_rpc;
 pub mod chain_spec;
 pub mod cli;
+pub mod equivocation;
+pub mod metrics;
 pub mod monitor;
 pub mod rpc;
 pub mod service;
+pub mod snapshot;
 pub mod spawner;
@@ -0,0 +1,270 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prometheus metrics of the container chain spawner
+
+use {
+    crate::spawner::{ContainerChainSpawnerState, DbRemovalReason},
+    cumulus_primitives_core::ParaId,
+    dc_orchestrator_chain_interface::OrchestratorChainInterface,
+    std::{
+        collections::HashSet,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    substrate_prometheus_endpoint::{
+        register, Counter, CounterVec, GaugeVec, Histogram, HistogramOpts, HistogramVec, Opts,
+        PrometheusError, Registry, U64,
+    },
+    tokio::time::sleep,
+};
+
+/// How often the block lag of the running container chains is updated.
+const LAG_UPDATE_PERIOD: Duration = Duration::from_secs(12);
+
+/// A container chain is considered synced when its best block is at most this number of blocks
+/// behind the block noted on the orchestrator chain.
+const SYNCED_MAX_LAG: u32 = 2;
+
+/// Buckets in seconds for the spawn, stop and sync duration histograms. Starting a container chain
+/// takes a few seconds, while syncing it can take several minutes.
+const DURATION_BUCKETS: [f64; 12] = [
+    0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0, 3600.0,
+];
+
+/// Metrics of the container chain spawner, registered in the orchestrator node registry.
+#[derive(Clone)]
+pub struct SpawnerMetrics {
+    spawn_duration: HistogramVec,
+    stop_duration: Histogram,
+    sync_duration: Histogram,
+    running_chains: GaugeVec<U64>,
+    db_removals: CounterVec<U64>,
+    assignment_changes: Counter<U64>,
+    best_block_lag: GaugeVec<U64>,
+    finalized_block_lag: GaugeVec<U64>,
+}
+
+impl SpawnerMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            spawn_duration: register(
+                HistogramVec::new(
+                    HistogramOpts::new(
+                        "container_chain_spawner_spawn_duration_seconds",
+                        "Time to start a container chain node, including the database checks",
+                    )
+                    .buckets(DURATION_BUCKETS.to_vec()),
+                    &["mode"],
+                )?,
+                registry,
+            )?,
+            stop_duration: register(
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "container_chain_spawner_stop_duration_seconds",
+                        "Time to stop a container chain node, including the database removal",
+                    )
+                    .buckets(DURATION_BUCKETS.to_vec()),
+                )?,
+                registry,
+            )?,
+            sync_duration: register(
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "container_chain_spawner_sync_duration_seconds",
+                        "Time since a container chain node started until it reached the block noted on the orchestrator chain",
+                    )
+                    .buckets(DURATION_BUCKETS.to_vec()),
+                )?,
+                registry,
+            )?,
+            running_chains: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "container_chain_spawner_running_chains",
+                        "Container chains currently running, by para id and mode",
+                    ),
+                    &["para_id", "mode"],
+                )?,
+                registry,
+            )?,
+            db_removals: register(
+                CounterVec::new(
+                    Opts::new(
+                        "container_chain_spawner_db_removals_total",
+                        "Number of container chain databases removed, by para id and reason",
+                    ),
+                    &["para_id", "reason"],
+                )?,
+                registry,
+            )?,
+            assignment_changes: register(
+                Counter::new(
+                    "container_chain_spawner_assignment_changes_total",
+                    "Number of times the current or next assignment of this node changed",
+                )?,
+                registry,
+            )?,
+            best_block_lag: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "container_chain_spawner_best_block_lag",
+                        "Blocks between the container chain block noted on the orchestrator chain and the local best block",
+                    ),
+                    &["para_id"],
+                )?,
+                registry,
+            )?,
+            finalized_block_lag: register(
+                GaugeVec::new(
+                    Opts::new(
+                        "container_chain_spawner_finalized_block_lag",
+                        "Blocks between the container chain block noted on the orchestrator chain and the local finalized block",
+                    ),
+                    &["para_id"],
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    /// A container chain node has started.
+    pub(crate) fn on_spawn(&self, para_id: ParaId, collating: bool, duration: Duration) {
+        self.spawn_duration
+            .with_label_values(&[mode(collating)])
+            .observe(duration.as_secs_f64());
+        self.running_chains
+            .with_label_values(&[&para_id.to_string(), mode(collating)])
+            .set(1);
+    }
+
+    /// A container chain node has been asked to stop.
+    pub(crate) fn on_stop(&self, para_id: ParaId, collating: bool) {
+        let _ = self
+            .running_chains
+            .remove_label_values(&[&para_id.to_string(), mode(collating)]);
+    }
+
+    /// A container chain node has finished stopping after being asked to.
+    pub(crate) fn on_stopped(&self, duration: Duration) {
+        self.stop_duration.observe(duration.as_secs_f64());
+    }
+
+    pub(crate) fn on_db_removal(&self, para_id: ParaId, reason: &DbRemovalReason) {
+        let reason = match reason {
+            DbRemovalReason::HighBlockDiff { .. } => "high_block_diff",
+            DbRemovalReason::GenesisHashMismatch { .. } => "genesis_hash_mismatch",
+            DbRemovalReason::ManualResync => "manual_resync",
+            DbRemovalReason::RetentionMaxCount { .. } => "retention_max_count",
+            DbRemovalReason::RetentionMaxSize { .. } => "retention_max_size",
+        };
+
+        self.db_removals
+            .with_label_values(&[&para_id.to_string(), reason])
+            .inc();
+    }
+
+    pub(crate) fn on_assignment_change(&self) {
+        self.assignment_changes.inc();
+    }
+}
+
+fn mode(collating: bool) -> &'static str {
+    if collating {
+        "collating"
+    } else {
+        "syncing"
+    }
+}
+
+/// Background task that periodically compares the best and finalized block of the running
+/// container chains against the latest block noted on the orchestrator chain.
+pub async fn lag_metrics_task(
+    state: Arc<Mutex<ContainerChainSpawnerState>>,
+    orchestrator_chain_interface: Arc<dyn OrchestratorChainInterface>,
+    metrics: SpawnerMetrics,
+) {
+    // Para ids with a lag gauge, to remove the gauge once the chain stops
+    let mut reported_para_ids: HashSet<ParaId> = HashSet::new();
+    // Spawned containers (by monitor id) that already reached the noted block
+    let mut synced_ids: HashSet<usize> = HashSet::new();
+
+    loop {
+        sleep(LAG_UPDATE_PERIOD).await;
+
+        let running_chains = state
+            .lock()
+            .expect("poison error")
+            .running_chains_sync_status();
+        let orchestrator_block_hash =
+            match orchestrator_chain_interface.finalized_block_hash().await {
+                Ok(x) => x,
+                Err(e) => {
+                    log::debug!("Failed to get orchestrator finalized block hash: {}", e);
+                    continue;
+                }
+            };
+
+        let mut running_para_ids = HashSet::new();
+        let mut running_ids = HashSet::new();
+        for (para_id, id, start_time, sync_status) in running_chains {
+            running_para_ids.insert(para_id);
+            running_ids.insert(id);
+
+            let Some(sync_status) = sync_status else {
+                continue;
+            };
+            let Ok(Some(noted_block_number)) = orchestrator_chain_interface
+                .latest_block_number(orchestrator_block_hash, para_id)
+                .await
+            else {
+                continue;
+            };
+
+            let best_block_lag = noted_block_number.saturating_sub(sync_status.best_number);
+            let finalized_block_lag =
+                noted_block_number.saturating_sub(sync_status.finalized_number);
+            metrics
+                .best_block_lag
+                .with_label_values(&[&para_id.to_string()])
+                .set(best_block_lag.into());
+            metrics
+                .finalized_block_lag
+                .with_label_values(&[&para_id.to_string()])
+                .set(finalized_block_lag.into());
+
+            if noted_block_number > 0 && best_block_lag <= SYNCED_MAX_LAG && synced_ids.insert(id) {
+                if let Some(start_time) = start_time {
+                    metrics
+                        .sync_duration
+                        .observe(start_time.elapsed().as_secs_f64());
+                }
+            }
+        }
+
+        for para_id in reported_para_ids.difference(&running_para_ids) {
+            let _ = metrics
+                .best_block_lag
+                .remove_label_values(&[&para_id.to_string()]);
+            let _ = metrics
+                .finalized_block_lag
+                .remove_label_values(&[&para_id.to_string()]);
+        }
+        reported_para_ids = running_para_ids;
+        synced_ids.retain(|id| running_ids.contains(id));
+    }
+}
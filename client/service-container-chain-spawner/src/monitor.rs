@@ -17,16 +17,17 @@
 use {
     crate::{
         service::ContainerChainBackend,
-        spawner::{CcSpawnMsg, ContainerChainSpawnerState},
+        spawner::{CcSpawnMsg, ContainerChainSpawnerState, DbRemovalReason},
     },
     cumulus_primitives_core::ParaId,
     frame_support::DefaultNoBound,
+    serde::{Deserialize, Serialize},
     std::{
         any::Any,
         cell::Cell,
-        collections::VecDeque,
+        collections::{BTreeSet, VecDeque},
         sync::{Arc, Mutex},
-        time::Instant,
+        time::{Instant, SystemTime, UNIX_EPOCH},
     },
     tokio::{
         sync::mpsc::UnboundedSender,
@@ -34,6 +35,9 @@ use {
     },
 };
 
+/// Max number of database removals remembered by the [SpawnedContainersMonitor].
+const MAX_DB_REMOVALS: usize = 100;
+
 #[derive(DefaultNoBound)]
 pub struct SpawnedContainersMonitor {
     /// List of the N most recently started container chains, with some statistics related to
@@ -41,6 +45,20 @@ pub struct SpawnedContainersMonitor {
     list: VecDeque<SpawnedContainer>,
     /// Count the number of times a container chain has been started
     count: usize,
+    /// The N most recent container chain database removals, oldest first
+    db_removals: VecDeque<DbRemoval>,
+}
+
+/// A container chain database that was removed by the spawner.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbRemoval {
+    /// Container chain para id
+    pub para_id: u32,
+    /// When was the database removed, in milliseconds since the unix epoch
+    pub timestamp: u64,
+    /// Why was the database removed
+    pub reason: DbRemovalReason,
 }
 
 pub struct SpawnedContainer {
@@ -157,6 +175,30 @@ impl SpawnedContainersMonitor {
         }
     }
 
+    pub fn start_time(&self, id: usize) -> Option<Instant> {
+        self.list.iter().find(|x| x.id == id).map(|x| x.start_time)
+    }
+
+    pub fn push_db_removal(&mut self, para_id: ParaId, reason: DbRemovalReason) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_millis() as u64)
+            .unwrap_or_default();
+
+        self.db_removals.push_back(DbRemoval {
+            para_id: para_id.into(),
+            timestamp,
+            reason,
+        });
+        while self.db_removals.len() > MAX_DB_REMOVALS {
+            self.db_removals.pop_front();
+        }
+    }
+
+    pub fn db_removals(&self) -> impl Iterator<Item = &DbRemoval> {
+        self.db_removals.iter()
+    }
+
     pub fn running_chains(&self) -> Vec<&SpawnedContainer> {
         self.list
             .iter()
@@ -255,29 +297,29 @@ pub async fn debug_start_and_stop_same_cc(cc_spawn_tx: UnboundedSender<CcSpawnMs
         sleep(sleep_delay).await;
         cc_spawn_tx
             .send(CcSpawnMsg::UpdateAssignment {
-                current: Some(2000u32.into()),
-                next: None,
+                current: BTreeSet::from([2000u32.into()]),
+                next: BTreeSet::new(),
             })
             .unwrap();
         sleep(sleep_delay).await;
         cc_spawn_tx
             .send(CcSpawnMsg::UpdateAssignment {
-                current: None,
-                next: None,
+                current: BTreeSet::new(),
+                next: BTreeSet::new(),
             })
             .unwrap();
         sleep(sleep_delay).await;
         cc_spawn_tx
             .send(CcSpawnMsg::UpdateAssignment {
-                current: None,
-                next: Some(2001u32.into()),
+                current: BTreeSet::new(),
+                next: BTreeSet::from([2001u32.into()]),
             })
             .unwrap();
         sleep(sleep_delay).await;
         cc_spawn_tx
             .send(CcSpawnMsg::UpdateAssignment {
-                current: None,
-                next: None,
+                current: BTreeSet::new(),
+                next: BTreeSet::new(),
             })
             .unwrap();
     }
@@ -326,4 +368,19 @@ mod tests {
         // Truncate should remove the oldest stopped chains, so the first id is now 10
         assert_eq!(monitor.list.front().map(|x| x.id), Some(10));
     }
+
+    #[test]
+    fn db_removals_keep_most_recent() {
+        let mut monitor = SpawnedContainersMonitor::default();
+
+        for para_id in 0..(MAX_DB_REMOVALS as u32 + 5) {
+            monitor.push_db_removal(para_id.into(), DbRemovalReason::ManualResync);
+        }
+
+        let para_ids: Vec<u32> = monitor.db_removals().map(|x| x.para_id).collect();
+        assert_eq!(para_ids.len(), MAX_DB_REMOVALS);
+        // Oldest removals are dropped first
+        assert_eq!(para_ids.first(), Some(&5));
+        assert_eq!(para_ids.last(), Some(&(MAX_DB_REMOVALS as u32 + 4)));
+    }
 }
@@ -16,6 +16,7 @@
 
 use {
     crate::cli::ContainerChainCli,
+    crate::equivocation::{run_equivocation_reporter, EquivocationWatcher},
     crate::rpc::generate_rpc_builder::{GenerateRpcBuilder, GenerateRpcBuilderParams},
     cumulus_client_bootnodes::{start_bootnode_tasks, StartBootnodeTasksParams},
     cumulus_client_consensus_common::{
@@ -61,7 +62,9 @@ use {
         },
         OrchestratorAuraWorkerAuxData,
     },
+    tokio::sync::mpsc,
     tokio_util::sync::CancellationToken,
+    tp_traits::ContainerChainEquivocationProof,
 };
 
 type FullBackend = TFullBackend<Block>;
@@ -189,11 +192,32 @@ pub fn start_node_impl_container<
         // Create a `NodeBuilder` which helps setup parachain nodes common systems.
         let node_builder = ContainerChainNodeConfig::new_builder(&parachain_config, None)?;
 
+        // Import a recent state before starting the network, so that the sync starts from there
+        if container_chain_cli.base.bootstrap_from_snapshot && !data_preserver {
+            if let Err(e) = crate::snapshot::bootstrap_from_snapshot(
+                &node_builder.client,
+                &relay_chain_interface,
+                &orchestrator_chain_interface,
+                para_id,
+            )
+            .await
+            {
+                log::warn!(
+                    "Failed to bootstrap container chain {} from snapshot, syncing from peers instead: {}",
+                    para_id,
+                    e
+                );
+            }
+        }
+
+        let (equivocation_proof_sender, equivocation_proof_receiver) = mpsc::unbounded_channel();
         let (block_import, import_queue) = container_chain_import_queue(
             &parachain_config,
             &node_builder,
             container_chain_cli,
             data_preserver,
+            para_id,
+            equivocation_proof_sender,
         );
         let import_queue_service = import_queue.service();
 
@@ -212,7 +236,7 @@ pub fn start_node_impl_container<
 
         // Disable RPC if the flag is set
         let rpc_builder = if !container_chain_cli.base.disable_rpc {
-            generate_rpc_builder.generate(GenerateRpcBuilderParams {
+            let rpc_builder = generate_rpc_builder.generate(GenerateRpcBuilderParams {
                 task_manager: &node_builder.task_manager,
                 container_chain_config: &parachain_config,
                 client: node_builder.client.clone(),
@@ -223,7 +247,14 @@ pub fn start_node_impl_container<
                 command_sink: None,
                 xcm_senders: None,
                 network: node_builder.network.network.clone(),
-            })?
+            })?;
+
+            // Data preservers serve state snapshots to collators rotated onto this chain
+            if data_preserver {
+                crate::snapshot::with_state_snapshot_rpc(rpc_builder, node_builder.client.clone())
+            } else {
+                rpc_builder
+            }
         } else {
             log::info!("RPC service disabled for bootnode-only node");
             crate::rpc::dummy_rpc_builder()
@@ -298,6 +329,20 @@ pub fn start_node_impl_container<
             });
         }
 
+        // Equivocation reports are orchestrator extrinsics, which can only be submitted by
+        // collators running an embedded orchestrator node
+        let equivocation_reporter = collation_params.as_ref().and_then(|collation_params| {
+            Some((
+                collation_params.orchestrator_client.clone()?,
+                collation_params.orchestrator_tx_pool.clone()?,
+            ))
+        });
+        node_builder.task_manager.spawn_handle().spawn(
+            "container-chain-equivocation-reporter",
+            None,
+            run_equivocation_reporter(equivocation_proof_receiver, equivocation_reporter),
+        );
+
         if let Some(collation_params) = collation_params {
             let node_spawn_handle = node_builder.task_manager.spawn_handle().clone();
             let node_client = node_builder.client.clone();
@@ -342,6 +387,8 @@ pub fn container_chain_import_queue<RuntimeApi: MinimalContainerRuntimeApi>(
     node_builder: &NodeBuilder<ContainerChainNodeConfig<RuntimeApi>>,
     container_chain_cli: &ContainerChainCli,
     data_preserver: bool,
+    para_id: ParaId,
+    equivocation_proof_sender: mpsc::UnboundedSender<ContainerChainEquivocationProof>,
 ) -> (ContainerChainBlockImport<RuntimeApi>, BasicQueue<Block>) {
     // The nimbus import queue ONLY checks the signature correctness
     // Any other checks corresponding to the author-correctness should be done
@@ -359,9 +406,11 @@ pub fn container_chain_import_queue<RuntimeApi: MinimalContainerRuntimeApi>(
         data_preserver,
     );
 
+    // Blocks authored by this node are imported directly, so only the blocks from the network
+    // are checked for equivocations
     let import_queue = nimbus_consensus::import_queue(
         node_builder.client.clone(),
-        block_import.clone(),
+        EquivocationWatcher::new(block_import.clone(), para_id, equivocation_proof_sender),
         move |_, _| async move {
             let time = sp_timestamp::InherentDataProvider::from_system_time();
 
@@ -0,0 +1,500 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Container chain state snapshots
+//!
+//! Data preservers serve the state of their container chain in chunks, using the
+//! `tanssi_stateSnapshotChunk` RPC method. Each chunk is a compact range proof, the same format
+//! used by the state sync network protocol.
+//!
+//! When a collator is rotated onto a container chain and its local database is far behind, it can
+//! bootstrap the database from one of these snapshots instead of syncing all the missing blocks.
+//! Snapshot sources are discovered from the `direct_rpc_urls` of the data preserver profiles
+//! assigned to the container chain. The target block is the container chain head included in the
+//! finalized relay chain block, which must not be behind the block noted on the orchestrator
+//! chain. Every chunk is verified against the state root of that header, so a malicious data
+//! preserver can only make the bootstrap fail, and the node then falls back to the usual sync.
+
+use {
+    crate::{
+        rpc::generate_rpc_builder::{CompleteRpcBuilder, ServiceError},
+        service::{ContainerChainClient, MinimalContainerRuntimeApi},
+    },
+    cumulus_primitives_core::ParaId,
+    cumulus_relay_chain_interface::RelayChainInterface,
+    dancebox_runtime::{
+        opaque::{Block, Header},
+        Runtime,
+    },
+    dc_orchestrator_chain_interface::OrchestratorChainInterface,
+    jsonrpsee::{
+        core::RpcResult,
+        proc_macros::rpc,
+        types::{error::INTERNAL_ERROR_CODE, ErrorObjectOwned},
+        ws_client::{WsClient, WsClientBuilder},
+    },
+    parity_scale_codec::{Decode, Encode},
+    polkadot_primitives::OccupiedCoreAssumption,
+    sc_client_api::ProofProvider,
+    sc_consensus::{
+        BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult, ImportedState,
+        StateAction, StorageChanges,
+    },
+    serde::{Deserialize, Serialize},
+    smallvec::SmallVec,
+    sp_blockchain::HeaderBackend,
+    sp_consensus::BlockOrigin,
+    sp_core::{storage::well_known_keys, Bytes, H256},
+    sp_runtime::traits::Header as HeaderT,
+    sp_state_machine::{KeyValueStates, KeyValueStorageLevel},
+    sp_trie::CompactProof,
+    std::{
+        collections::{BTreeSet, HashMap},
+        sync::Arc,
+    },
+};
+
+/// Maximum size of the proof returned by a single `tanssi_stateSnapshotChunk` call. The RPC
+/// response is hex encoded, so it will be twice as big.
+const MAX_SNAPSHOT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Maximum RPC response size accepted when downloading a snapshot.
+const MAX_SNAPSHOT_RESPONSE_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Only bootstrap from a snapshot if the local database is at least this number of blocks behind
+/// the target block. Below this, syncing the missing blocks is fast enough.
+/// 1_000 blocks = 100 minutes at 6s/block.
+const MIN_BLOCK_DIFF_FOR_SNAPSHOT: u32 = 1_000;
+
+type ProfileId = <Runtime as pallet_data_preservers::Config>::ProfileId;
+
+/// One chunk of a container chain state snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshotChunk {
+    /// SCALE encoded `CompactProof` of a range of the state, starting after the requested key.
+    pub proof: Bytes,
+    /// Number of key-value pairs included in the proof.
+    pub count: u32,
+}
+
+/// RPC used by data preservers to serve state snapshots of their container chain.
+#[rpc(client, server)]
+pub trait StateSnapshotApi {
+    /// Returns a proof of the state of `block`, starting after `start_key`. `start_key` is empty
+    /// for the first chunk, and contains the last top trie key and optionally the last child trie
+    /// key of the previous chunk for the next ones.
+    #[method(name = "tanssi_stateSnapshotChunk", blocking)]
+    fn state_snapshot_chunk(
+        &self,
+        block: H256,
+        start_key: Vec<Bytes>,
+    ) -> RpcResult<StateSnapshotChunk>;
+}
+
+/// Implementation of [StateSnapshotApiServer] for a container chain client.
+pub struct StateSnapshot<C> {
+    client: Arc<C>,
+}
+
+impl<C> StateSnapshot<C> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+impl<C> StateSnapshotApiServer for StateSnapshot<C>
+where
+    C: ProofProvider<Block> + Send + Sync + 'static,
+{
+    fn state_snapshot_chunk(
+        &self,
+        block: H256,
+        start_key: Vec<Bytes>,
+    ) -> RpcResult<StateSnapshotChunk> {
+        // A key in the top trie and a key in a child trie, child tries cannot be nested
+        if start_key.len() > 2 {
+            return Err(internal_err("start_key can have at most 2 elements"));
+        }
+        let start_key: Vec<Vec<u8>> = start_key.into_iter().map(|key| key.0).collect();
+
+        let (proof, count) = self
+            .client
+            .read_proof_collection(block, &start_key, MAX_SNAPSHOT_CHUNK_SIZE)
+            .map_err(internal_err)?;
+
+        Ok(StateSnapshotChunk {
+            proof: proof.encode().into(),
+            count,
+        })
+    }
+}
+
+fn internal_err<T: ToString>(message: T) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, message.to_string(), None::<()>)
+}
+
+/// Extend a container chain rpc builder with the [StateSnapshotApi] methods.
+pub fn with_state_snapshot_rpc<RuntimeApi: MinimalContainerRuntimeApi>(
+    rpc_builder: CompleteRpcBuilder,
+    client: Arc<ContainerChainClient<RuntimeApi>>,
+) -> CompleteRpcBuilder {
+    Box::new(move |subscription_executor| {
+        let mut module = rpc_builder(subscription_executor)?;
+        module
+            .merge(StateSnapshot::new(client.clone()).into_rpc())
+            .map_err(|e| ServiceError::Application(Box::new(e)))?;
+
+        Ok(module)
+    })
+}
+
+/// Try to bootstrap the container chain database from a state snapshot served by a data
+/// preserver. Does nothing if the local database is close enough to the latest block.
+///
+/// Returns the number and hash of the imported block, or `None` if no snapshot was imported.
+/// Errors are not fatal, the node can always fall back to syncing the chain from its peers.
+pub async fn bootstrap_from_snapshot<RuntimeApi: MinimalContainerRuntimeApi>(
+    client: &Arc<ContainerChainClient<RuntimeApi>>,
+    relay_chain_interface: &Arc<dyn RelayChainInterface>,
+    orchestrator_chain_interface: &Arc<dyn OrchestratorChainInterface>,
+    para_id: ParaId,
+) -> Result<Option<(u32, H256)>, String> {
+    let target_header =
+        snapshot_target_header(relay_chain_interface, orchestrator_chain_interface, para_id)
+            .await?;
+    let target_hash = target_header.hash();
+    let target_number = *target_header.number();
+    let best_number = client.chain_info().best_number;
+
+    if !needs_snapshot(best_number, target_number) {
+        return Ok(None);
+    }
+    if client.header(target_hash).ok().flatten().is_some() {
+        // Already imported, nothing to do
+        return Ok(None);
+    }
+
+    let orchestrator_block_hash = orchestrator_chain_interface
+        .finalized_block_hash()
+        .await
+        .map_err(|e| format!("Failed to get latest block hash: {e}"))?;
+    let sources = snapshot_sources(
+        orchestrator_chain_interface,
+        orchestrator_block_hash,
+        para_id,
+    )
+    .await;
+    if sources.is_empty() {
+        log::info!(
+            "No snapshot sources for container chain {}, syncing from peers",
+            para_id
+        );
+        return Ok(None);
+    }
+
+    log::info!(
+        "Container chain {} database is at block {}, bootstrapping from a snapshot at block {} ({:?})",
+        para_id,
+        best_number,
+        target_number,
+        target_hash,
+    );
+
+    for url in sources {
+        let state = match download_state(client, &url, &target_header).await {
+            Ok(state) => state,
+            Err(e) => {
+                log::warn!(
+                    "Failed to download snapshot of container chain {} from {}: {}",
+                    para_id,
+                    url,
+                    e
+                );
+                continue;
+            }
+        };
+
+        import_state(client, target_header, state).await?;
+        log::info!(
+            "Imported snapshot of container chain {} at block {} from {}",
+            para_id,
+            target_number,
+            url
+        );
+
+        return Ok(Some((target_number, target_hash)));
+    }
+
+    Err("all snapshot sources failed".to_string())
+}
+
+fn needs_snapshot(best_number: u32, target_number: u32) -> bool {
+    target_number.saturating_sub(best_number) >= MIN_BLOCK_DIFF_FOR_SNAPSHOT
+}
+
+/// The container chain header included in the latest finalized relay chain block. This is the
+/// same header the orchestrator chain reads when noting container chain authors, so check that it
+/// is not behind the latest block noted on the orchestrator chain.
+async fn snapshot_target_header(
+    relay_chain_interface: &Arc<dyn RelayChainInterface>,
+    orchestrator_chain_interface: &Arc<dyn OrchestratorChainInterface>,
+    para_id: ParaId,
+) -> Result<Header, String> {
+    let relay_block_hash = relay_chain_interface
+        .finalized_block_hash()
+        .await
+        .map_err(|e| format!("Failed to get finalized relay block hash: {e}"))?;
+    let validation_data = relay_chain_interface
+        .persisted_validation_data(relay_block_hash, para_id, OccupiedCoreAssumption::TimedOut)
+        .await
+        .map_err(|e| format!("Failed to get persisted validation data: {e}"))?
+        .ok_or_else(|| {
+            format!(
+                "Container chain {} is not registered in relay chain",
+                para_id
+            )
+        })?;
+    let header = Header::decode(&mut &validation_data.parent_head.0[..])
+        .map_err(|e| format!("Failed to decode container chain head: {e}"))?;
+
+    let orchestrator_block_hash = orchestrator_chain_interface
+        .finalized_block_hash()
+        .await
+        .map_err(|e| format!("Failed to get latest block hash: {e}"))?;
+    let noted_block_number = orchestrator_chain_interface
+        .latest_block_number(orchestrator_block_hash, para_id)
+        .await
+        .map_err(|e| format!("Failed to call latest_block_number runtime api: {e}"))?
+        .unwrap_or(0);
+
+    if *header.number() < noted_block_number {
+        return Err(format!(
+            "Container chain head in relay chain (block {}) is behind the block noted on the orchestrator chain (block {})",
+            header.number(),
+            noted_block_number
+        ));
+    }
+
+    Ok(header)
+}
+
+/// RPC urls of the data preserver profiles assigned to this container chain. Only websocket urls
+/// are supported.
+async fn snapshot_sources(
+    orchestrator_chain_interface: &Arc<dyn OrchestratorChainInterface>,
+    orchestrator_block_hash: H256,
+    para_id: ParaId,
+) -> Vec<String> {
+    let assignments_key = pallet_data_preservers::Assignments::<Runtime>::hashed_key_for(para_id);
+    let profile_ids: BTreeSet<ProfileId> = orchestrator_chain_interface
+        .get_storage_by_key(orchestrator_block_hash, &assignments_key)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| Decode::decode(&mut &raw[..]).ok())
+        .unwrap_or_default();
+
+    let mut profiles = vec![];
+    for profile_id in profile_ids {
+        let profile_key = pallet_data_preservers::Profiles::<Runtime>::hashed_key_for(profile_id);
+        let maybe_profile = orchestrator_chain_interface
+            .get_storage_by_key(orchestrator_block_hash, &profile_key)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|raw| {
+                pallet_data_preservers::RegisteredProfile::<Runtime>::decode(&mut &raw[..]).ok()
+            });
+
+        if let Some(registered_profile) = maybe_profile {
+            profiles.push(registered_profile.profile);
+        }
+    }
+
+    snapshot_urls(profiles)
+}
+
+fn snapshot_urls(profiles: Vec<pallet_data_preservers::Profile<Runtime>>) -> Vec<String> {
+    profiles
+        .into_iter()
+        .flat_map(|profile| profile.direct_rpc_urls.into_iter())
+        .filter_map(|url| String::from_utf8(url.into_inner()).ok())
+        .filter(|url| url.starts_with("ws://") || url.starts_with("wss://"))
+        .collect()
+}
+
+/// Download the full state of `target_header` from `url`, verifying every chunk against the
+/// header state root.
+async fn download_state<RuntimeApi: MinimalContainerRuntimeApi>(
+    client: &Arc<ContainerChainClient<RuntimeApi>>,
+    url: &str,
+    target_header: &Header,
+) -> Result<ImportedState<Block>, String> {
+    let rpc_client: WsClient = WsClientBuilder::default()
+        .max_response_size(MAX_SNAPSHOT_RESPONSE_SIZE)
+        .build(url)
+        .await
+        .map_err(|e| format!("Failed to connect: {e}"))?;
+
+    let target_hash = target_header.hash();
+    let state_root = *target_header.state_root();
+
+    // Same algorithm as the state sync network protocol: keep the last imported key of the top
+    // trie and of the current child trie, and group key values by trie root.
+    let mut last_key: SmallVec<[Vec<u8>; 2]> = SmallVec::new();
+    let mut state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)> = HashMap::new();
+
+    loop {
+        let chunk = rpc_client
+            .state_snapshot_chunk(target_hash, last_key.iter().cloned().map(Bytes).collect())
+            .await
+            .map_err(|e| format!("Failed to get snapshot chunk: {e}"))?;
+        let proof = CompactProof::decode(&mut &chunk.proof[..])
+            .map_err(|e| format!("Failed to decode snapshot proof: {e}"))?;
+
+        let (values, completed) = client
+            .verify_range_proof(state_root, proof, last_key.as_slice())
+            .map_err(|e| format!("Invalid snapshot proof: {e}"))?;
+
+        if completed != 0 {
+            let previous_last_key = last_key.clone();
+            if !values.update_last_key(completed, &mut last_key) || last_key == previous_last_key {
+                return Err(format!(
+                    "Snapshot source is not making progress, depth: {}",
+                    completed
+                ));
+            }
+        }
+
+        for values in values.0 {
+            let key_values = if values.state_root.is_empty() {
+                // Read child trie roots
+                values
+                    .key_values
+                    .into_iter()
+                    .filter(|(key, value)| {
+                        if well_known_keys::is_child_storage_key(key) {
+                            state.entry(value.clone()).or_default().1.push(key.clone());
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .collect()
+            } else {
+                values.key_values
+            };
+
+            let entry = state.entry(values.state_root).or_default();
+            if !entry.0.is_empty() && entry.1.len() > 1 {
+                // Already imported child trie with the same root
+            } else {
+                entry.0.extend(key_values);
+            }
+        }
+
+        if completed == 0 {
+            break;
+        }
+    }
+
+    Ok(ImportedState {
+        block: target_hash,
+        state: KeyValueStates(
+            state
+                .into_iter()
+                .map(
+                    |(state_root, (key_values, parent_storage_keys))| KeyValueStorageLevel {
+                        state_root,
+                        parent_storage_keys,
+                        key_values,
+                    },
+                )
+                .collect(),
+        ),
+    })
+}
+
+/// Import `target_header` as the new finalized block, with the downloaded state. The blocks
+/// between the local database and the target block are left as a gap.
+async fn import_state<RuntimeApi: MinimalContainerRuntimeApi>(
+    client: &Arc<ContainerChainClient<RuntimeApi>>,
+    target_header: Header,
+    state: ImportedState<Block>,
+) -> Result<(), String> {
+    let mut import_params = BlockImportParams::new(BlockOrigin::NetworkInitialSync, target_header);
+    import_params.state_action = StateAction::ApplyChanges(StorageChanges::Import(state));
+    import_params.finalized = true;
+    import_params.fork_choice = Some(ForkChoiceStrategy::Custom(true));
+    import_params.import_existing = true;
+
+    match client.import_block(import_params).await {
+        Ok(ImportResult::Imported(_)) | Ok(ImportResult::AlreadyInChain) => Ok(()),
+        Ok(result) => Err(format!("Failed to import snapshot: {:?}", result)),
+        Err(e) => Err(format!("Failed to import snapshot: {e}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, pallet_data_preservers::NodeType};
+
+    fn profile(direct_rpc_urls: &[&str]) -> pallet_data_preservers::Profile<Runtime> {
+        pallet_data_preservers::Profile {
+            para_ids: pallet_data_preservers::ParaIdsFilter::AnyParaId,
+            assignment_request: pallet_data_preservers::ProviderRequestOf::<Runtime>::Free,
+            direct_rpc_urls: direct_rpc_urls
+                .iter()
+                .map(|url| url.as_bytes().to_vec().try_into().unwrap())
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+            proxy_rpc_urls: Default::default(),
+            bootnode_url: None,
+            node_type: NodeType::Substrate,
+            additional_info: Default::default(),
+        }
+    }
+
+    #[test]
+    fn snapshot_urls_only_include_websocket_urls() {
+        let urls = snapshot_urls(vec![
+            profile(&["wss://a.example.com", "https://b.example.com"]),
+            profile(&["ws://127.0.0.1:9944"]),
+        ]);
+
+        assert_eq!(
+            urls,
+            vec![
+                "wss://a.example.com".to_string(),
+                "ws://127.0.0.1:9944".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_is_only_used_if_far_behind() {
+        assert!(!needs_snapshot(0, MIN_BLOCK_DIFF_FOR_SNAPSHOT - 1));
+        assert!(needs_snapshot(0, MIN_BLOCK_DIFF_FOR_SNAPSHOT));
+        assert!(!needs_snapshot(
+            5_000,
+            5_000 + MIN_BLOCK_DIFF_FOR_SNAPSHOT - 1
+        ));
+        // Local database ahead of the target block
+        assert!(!needs_snapshot(5_000, 10));
+    }
+}
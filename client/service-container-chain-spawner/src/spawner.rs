@@ -24,7 +24,9 @@
 use node_common::service::node_builder::StartBootnodeParams;
 use {
     crate::{
+        admin_rpc::{ContainerChainRunState, ContainerChainStatus, ContainerChainSyncStatus},
         cli::ContainerChainCli,
+        metrics::SpawnerMetrics,
         monitor::{SpawnedContainer, SpawnedContainersMonitor},
         rpc::generate_rpc_builder::GenerateRpcBuilder,
         service::{
@@ -41,20 +43,21 @@ use {
     futures::FutureExt,
     node_common::command::generate_genesis_block,
     polkadot_primitives::CollatorPair,
-    sc_cli::{Database, SyncMode},
+    sc_cli::{Database, DefaultConfigurationValues, SyncMode},
     sc_network::config::MultiaddrWithPeerId,
     sc_service::SpawnTaskHandle,
     sc_transaction_pool::TransactionPoolHandle,
+    serde::{Deserialize, Serialize},
     sp_core::H256,
     sp_keystore::KeystorePtr,
     sp_runtime::traits::Block as BlockT,
     std::{
         any::Any,
-        collections::{HashMap, HashSet},
+        collections::{BTreeMap, BTreeSet, HashMap, HashSet},
         marker::PhantomData,
         path::{Path, PathBuf},
         sync::{Arc, Mutex},
-        time::Instant,
+        time::{Instant, SystemTime, UNIX_EPOCH},
     },
     tokio::{
         sync::{mpsc, oneshot},
@@ -63,7 +66,11 @@ use {
     tokio_util::sync::CancellationToken,
 };
 
-/// Timeout to wait for the database to close before starting it again, used in `wait_for_paritydb_lock`.
+/// Folders inside a container chain folder where each database backend stores its databases:
+/// `paritydb` for ParityDB and `db` for RocksDB.
+const DB_FOLDERS: [&str; 2] = ["paritydb", "db"];
+
+/// Timeout to wait for the database to close before starting it again, used in `wait_for_db_lock`.
 /// This is the max timeout, if the db is closed in 1 second then that function will only wait 1 second.
 const MAX_DB_RESTART_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -75,6 +82,10 @@ const MAX_DB_RESTART_TIMEOUT: Duration = Duration::from_secs(60);
 /// Assuming a syncing speed of 100 blocks per second, this will take 5 minutes to sync.
 const MAX_BLOCK_DIFF_FOR_FULL_SYNC: u32 = 30_000;
 
+/// File inside the `containers` folder that stores when was each container chain last assigned to
+/// this node, used to decide which databases to remove first when enforcing the retention policy.
+const LAST_ASSIGNED_FILE: &str = "last_assigned_container_chains.json";
+
 /// Task that handles spawning a stopping container chains based on assignment.
 /// The main loop is [rx_loop](ContainerChainSpawner::rx_loop).
 pub struct ContainerChainSpawner<
@@ -126,6 +137,7 @@ pub struct ContainerChainSpawnParams<
     pub generate_rpc_builder: TGenerateRpcBuilder,
     pub override_sync_mode: Option<SyncMode>,
     pub start_bootnode_params: StartBootnodeParams,
+    pub metrics: Option<SpawnerMetrics>,
 
     pub phantom: PhantomData<RuntimeApi>,
 }
@@ -146,8 +158,8 @@ pub struct CollationParams {
 #[derive(DefaultNoBound)]
 pub struct ContainerChainSpawnerState {
     spawned_container_chains: HashMap<ParaId, ContainerChainState>,
-    assigned_para_id: Option<ParaId>,
-    next_assigned_para_id: Option<ParaId>,
+    assigned_para_ids: BTreeSet<ParaId>,
+    next_assigned_para_ids: BTreeSet<ParaId>,
     failed_para_ids: HashSet<ParaId>,
     // For debugging and detecting errors
     pub spawned_containers_monitor: SpawnedContainersMonitor,
@@ -158,6 +170,16 @@ pub struct ContainerChainState {
     stop_handle: StopContainerChain,
     /// Database path
     db_path: PathBuf,
+    /// Whether this container chain uses the random ports reserved for syncing chains
+    syncing_ports: bool,
+    /// Offset applied to the ports of this container chain, to allow running more than one
+    /// container chain at the same time. Unique among chains with the same `syncing_ports`.
+    port_offset: u16,
+    /// Whether this container chain was started with collation enabled
+    collating: bool,
+    /// Reads the best and finalized block of the container chain client. Returns `None` if the
+    /// client has already been dropped.
+    sync_status: Arc<dyn Fn() -> Option<ContainerChainSyncStatus> + Send + Sync>,
 }
 
 /// Stops a container chain when signal is sent. The bool means `keep_db`, whether to keep the
@@ -172,11 +194,117 @@ pub struct StopContainerChain {
 /// `Arc<ContainerChainSpawner>` to other threads.
 #[derive(Debug)]
 pub enum CcSpawnMsg {
-    /// Update container chain assignment
+    /// Update container chain assignment. A collator can be assigned to more than one chain at
+    /// the same time when collating on parathreads.
     UpdateAssignment {
-        current: Option<ParaId>,
-        next: Option<ParaId>,
+        current: BTreeSet<ParaId>,
+        next: BTreeSet<ParaId>,
+    },
+    /// Manually start a container chain, regardless of the assignment.
+    Spawn {
+        para_id: ParaId,
+        start_collation: bool,
+        result: oneshot::Sender<Result<(), String>>,
     },
+    /// Manually stop a running container chain, regardless of the assignment.
+    Stop {
+        para_id: ParaId,
+        keep_db: bool,
+        result: oneshot::Sender<Result<(), String>>,
+    },
+    /// Stop a running container chain, remove its database and start it again.
+    Resync {
+        para_id: ParaId,
+        result: oneshot::Sender<Result<(), String>>,
+    },
+}
+
+impl ContainerChainSpawnerState {
+    /// Para ids of the container chains in the current and next assignment.
+    pub fn assignment(&self) -> (Vec<ParaId>, Vec<ParaId>) {
+        (
+            self.assigned_para_ids.iter().copied().collect(),
+            self.next_assigned_para_ids.iter().copied().collect(),
+        )
+    }
+
+    /// Status of all the running, stopping and failed container chains, sorted by para id.
+    /// The on-chain block number is not known by the spawner, so it is always `None`.
+    pub fn container_chains_status(&self) -> Vec<ContainerChainStatus> {
+        let mut status: Vec<ContainerChainStatus> = self
+            .spawned_container_chains
+            .iter()
+            .map(|(para_id, chain)| ContainerChainStatus {
+                para_id: (*para_id).into(),
+                state: if chain.collating {
+                    ContainerChainRunState::Collating
+                } else {
+                    ContainerChainRunState::Syncing
+                },
+                db_path: Some(chain.db_path.display().to_string()),
+                sync_status: (chain.sync_status)(),
+                latest_block_number_onchain: None,
+            })
+            .collect();
+
+        // Chains that have been asked to stop but still hold a reference to their database
+        let stopping: BTreeSet<ParaId> = self
+            .spawned_containers_monitor
+            .running_chains()
+            .into_iter()
+            .filter(|container| container.stop_signal_time.is_some())
+            .map(|container| container.para_id)
+            .filter(|para_id| !self.spawned_container_chains.contains_key(para_id))
+            .collect();
+        let failed = self
+            .failed_para_ids
+            .iter()
+            .filter(|para_id| !self.spawned_container_chains.contains_key(*para_id))
+            .filter(|para_id| !stopping.contains(*para_id));
+
+        status.extend(stopping.iter().map(|para_id| ContainerChainStatus {
+            para_id: (*para_id).into(),
+            state: ContainerChainRunState::Stopping,
+            db_path: None,
+            sync_status: None,
+            latest_block_number_onchain: None,
+        }));
+        status.extend(failed.map(|para_id| ContainerChainStatus {
+            para_id: (*para_id).into(),
+            state: ContainerChainRunState::Failed,
+            db_path: None,
+            sync_status: None,
+            latest_block_number_onchain: None,
+        }));
+        status.sort_by_key(|chain| chain.para_id);
+
+        status
+    }
+
+    /// Para id, monitor id, start time and sync status of all the running container chains.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn running_chains_sync_status(
+        &self,
+    ) -> Vec<(
+        ParaId,
+        usize,
+        Option<Instant>,
+        Option<ContainerChainSyncStatus>,
+    )> {
+        self.spawned_container_chains
+            .iter()
+            .map(|(para_id, chain)| {
+                let id = chain.stop_handle.id;
+
+                (
+                    *para_id,
+                    id,
+                    self.spawned_containers_monitor.start_time(id),
+                    (chain.sync_status)(),
+                )
+            })
+            .collect()
+    }
 }
 
 // Separate function to allow using `?` to return a result, and also to avoid using `self` in an
@@ -205,8 +333,10 @@ async fn try_spawn<
         generate_rpc_builder,
         override_sync_mode,
         start_bootnode_params,
+        metrics,
         phantom: _,
     } = try_spawn_params;
+    let spawn_start_time = Instant::now();
     // Preload genesis data from orchestrator chain storage.
 
     // TODO: the orchestrator chain node may not be fully synced yet,
@@ -271,13 +401,16 @@ async fn try_spawn<
         container_chain_para_id
     );
 
-    if !data_preserver && !start_collation {
+    let syncing_ports = !data_preserver && !start_collation;
+    let port_offset = free_port_offset(&state.lock().expect("poison error"), syncing_ports);
+
+    if syncing_ports {
         log::info!("This is a syncing container chain, using random ports");
 
         collation_params = None;
 
-        // Use random ports to avoid conflicts with the other running container chain
-        let random_ports = [23456, 23457, 23458];
+        // Use random ports to avoid conflicts with the other running container chains
+        let random_ports = [23456, 23457, 23458].map(|port| port + 3 * port_offset);
 
         container_chain_cli
             .base
@@ -326,18 +459,73 @@ async fn try_spawn<
             .network_params
             .node_key_params
             .unsafe_force_node_key_generation = true;
+    } else if port_offset > 0 {
+        // Move each collating container chain to its own ports, starting from the configured ones
+        let base = &container_chain_cli.base.base;
+        let base_ports = [
+            base.prometheus_params.prometheus_port.unwrap_or_else(
+                <ContainerChainCli as DefaultConfigurationValues>::prometheus_listen_port,
+            ),
+            base.network_params
+                .port
+                .unwrap_or_else(<ContainerChainCli as DefaultConfigurationValues>::p2p_listen_port),
+            base.rpc_params
+                .rpc_port
+                .unwrap_or_else(<ContainerChainCli as DefaultConfigurationValues>::rpc_listen_port),
+        ];
+        let port_stride = container_chain_cli
+            .base
+            .collating_container_chains_port_stride;
+        let ports = collating_chain_ports(base_ports, port_offset, port_stride)?;
+        log::info!(
+            "Collating on more than one container chain, using port offset {}: prometheus port {}, p2p port {}, rpc port {}",
+            port_offset,
+            ports[0],
+            ports[1],
+            ports[2]
+        );
+
+        let base = &mut container_chain_cli.base.base;
+        base.prometheus_params.prometheus_port = Some(ports[0]);
+        base.network_params.port = Some(ports[1]);
+        base.rpc_params.rpc_port = Some(ports[2]);
     }
 
     let validator = collation_params.is_some();
 
     // Update CLI params
     container_chain_cli.base.para_id = Some(container_chain_para_id.into());
-    container_chain_cli
+    // Container chains support ParityDB (default) and RocksDB
+    let database = &mut container_chain_cli
         .base
         .base
         .import_params
         .database_params
-        .database = Some(Database::ParityDb);
+        .database;
+    *database = match *database {
+        None | Some(Database::ParityDb) => Some(Database::ParityDb),
+        Some(Database::RocksDb) => Some(Database::RocksDb),
+        Some(other) => {
+            log::warn!(
+                "Database {:?} is not supported by container chains, using ParityDb",
+                other
+            );
+            Some(Database::ParityDb)
+        }
+    };
+    if let Some((_, state_pruning)) = container_chain_cli
+        .base
+        .container_chain_pruning
+        .iter()
+        .find(|(para_id, _)| ParaId::from(*para_id) == container_chain_para_id)
+    {
+        container_chain_cli
+            .base
+            .base
+            .import_params
+            .pruning_params
+            .state_pruning = Some(*state_pruning);
+    }
 
     let keep_db = container_chain_cli.base.keep_db;
 
@@ -369,6 +557,8 @@ async fn try_spawn<
     // be removed. If the db needs to be removed, this function will handle the node restart, and
     // return the components of a running container chain node.
     // This should be a separate function, but it has so many arguments that I prefer to have it as a closure for now
+    let db_removal_state = state.clone();
+    let db_removal_metrics = metrics.clone();
     let start_node_impl_container_with_restart = || async move {
         // Loop will run at most 2 times: 1 time if the db is good and 2 times if the db needs to be removed
         for _ in 0..2 {
@@ -399,6 +589,20 @@ async fn try_spawn<
             db_path.set_file_name(format!("full-container-{}", container_chain_para_id));
             container_chain_cli_config.database.set_path(&db_path);
 
+            // A previous instance of this container chain may still be closing the database, for
+            // example if the assignment changed twice in a row. Opening a locked database fails
+            // with both backends, so wait for the previous instance to release it.
+            if db_existed_before {
+                wait_for_db_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
+                    .await
+                    .map_err(|e| {
+                        format!(
+                            "Error waiting for chain {} to release db lock: {}",
+                            container_chain_para_id, e
+                        )
+                    })?;
+            }
+
             let (container_chain_task_manager, container_chain_client, container_chain_db) =
                 match container_chain_cli_config.network.network_backend {
                     sc_network::config::NetworkBackendType::Libp2p => {
@@ -456,17 +660,25 @@ async fn try_spawn<
                 .await?
                 {
                     let db_path = node_parts.3.clone();
-                    // Important, drop `node_parts` before trying to `wait_for_paritydb_lock`
+                    // Important, drop `node_parts` before trying to `wait_for_db_lock`
                     drop(node_parts);
                     // Wait here to for the database created in the previous loop iteration to close.
                     // Dropping is not enough because there is some background process that keeps the database open,
-                    // so we check the paritydb lock file directly.
+                    // so we check the database lock file directly.
                     log::info!(
                         "Restarting container chain {} after db deletion. Reason: {:?}",
                         container_chain_para_id,
                         db_removal_reason,
                     );
-                    wait_for_paritydb_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
+                    if let Some(metrics) = &db_removal_metrics {
+                        metrics.on_db_removal(container_chain_para_id, &db_removal_reason);
+                    }
+                    db_removal_state
+                        .lock()
+                        .expect("poison error")
+                        .spawned_containers_monitor
+                        .push_db_removal(container_chain_para_id, db_removal_reason);
+                    wait_for_db_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
                         .await
                         .map_err(|e| {
                             log::warn!(
@@ -516,6 +728,21 @@ async fn try_spawn<
     let (mut container_chain_task_manager, container_chain_client, container_chain_db, db_path) =
         start_node_impl_container_with_restart().await?;
 
+    let sync_status = {
+        let container_chain_client = Arc::downgrade(&container_chain_client);
+
+        Arc::new(move || {
+            let info = container_chain_client.upgrade()?.chain_info();
+
+            Some(ContainerChainSyncStatus {
+                best_number: info.best_number,
+                best_hash: info.best_hash,
+                finalized_number: info.finalized_number,
+                finalized_hash: info.finalized_hash,
+            })
+        })
+    };
+
     // Signal that allows to gracefully stop a container chain
     let (signal, on_exit) = oneshot::channel::<bool>();
 
@@ -549,10 +776,22 @@ async fn try_spawn<
                     id: monitor_id,
                 },
                 db_path: db_path.clone(),
+                syncing_ports,
+                port_offset,
+                collating: start_collation,
+                sync_status,
             },
         );
     }
 
+    if let Some(metrics) = &metrics {
+        metrics.on_spawn(
+            container_chain_para_id,
+            start_collation,
+            spawn_start_time.elapsed(),
+        );
+    }
+
     // Add the container chain task manager as a child task to the parent task manager.
     // We want to stop the node if this task manager stops, but we also want to allow a
     // graceful shutdown using the `on_exit` future.
@@ -562,7 +801,8 @@ async fn try_spawn<
             container_chain_task_manager.future().fuse();
         let mut on_exit_future = on_exit.fuse();
 
-        futures::select! {
+        // Set when the chain has been asked to stop, used to measure the stop duration
+        let stop_signal_time = futures::select! {
             res1 = container_chain_task_manager_future => {
                 // An essential task failed or the task manager was stopped unexpectedly
                 // using `.terminate()`. This should stop the container chain but not the node.
@@ -575,8 +815,11 @@ async fn try_spawn<
                 let mut state = state.lock().expect("poison error");
                 state.failed_para_ids.insert(container_chain_para_id);
                 // Never delete db in this case because it is not a graceful shutdown
+
+                None
             }
             stop_unassigned = on_exit_future => {
+                let stop_signal_time = stop_unassigned.is_ok().then(Instant::now);
                 // Graceful shutdown.
                 // `stop_unassigned` will be `Ok(keep_db)` if `.stop()` has been called, which means that the
                 // container chain has been unassigned, and will be `Err` if the handle has been dropped,
@@ -584,10 +827,10 @@ async fn try_spawn<
                 // Delete existing database if running as collator
                 if validator && stop_unassigned == Ok(false) && !keep_db {
                     // If this breaks after a code change, make sure that all the variables that
-                    // may keep the chain alive are dropped before the call to `wait_for_paritydb_lock`.
+                    // may keep the chain alive are dropped before the call to `wait_for_db_lock`.
                     drop(container_chain_task_manager_future);
                     drop(container_chain_task_manager);
-                    let db_closed = wait_for_paritydb_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
+                    let db_closed = wait_for_db_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
                         .await
                         .map_err(|e| {
                             log::warn!(
@@ -601,13 +844,19 @@ async fn try_spawn<
                         delete_container_chain_db(&db_path);
                     }
                 }
+
+                stop_signal_time
             }
-        }
+        };
 
         let mut state = state.lock().expect("poison error");
         state
             .spawned_containers_monitor
             .set_stop_task_manager_time(monitor_id, Instant::now());
+
+        if let (Some(metrics), Some(stop_signal_time)) = (&metrics, stop_signal_time) {
+            metrics.on_stopped(stop_signal_time.elapsed());
+        }
     });
 
     Ok(())
@@ -623,7 +872,7 @@ pub trait Spawner {
     ///
     /// It is possible that we try to spawn-stop-spawn the same chain, and the second spawn fails
     /// because the chain has not stopped yet, because `stop` does not wait for the chain to stop,
-    /// so before calling `spawn` make sure to call `wait_for_paritydb_lock` before, like we do in
+    /// so before calling `spawn` make sure to call `wait_for_db_lock` before, like we do in
     /// `handle_update_assignment`.
     fn spawn(
         &self,
@@ -632,7 +881,7 @@ pub trait Spawner {
     ) -> impl std::future::Future<Output = ()> + Send;
 
     /// Stop a container chain. Prints a warning if the container chain was not running.
-    /// Returns the database path for the container chain, can be used with `wait_for_paritydb_lock`
+    /// Returns the database path for the container chain, can be used with `wait_for_db_lock`
     /// to ensure that the container chain has fully stopped. The database path can be `None` if the
     /// chain was not running.
     fn stop(&self, container_chain_para_id: ParaId, keep_db: bool) -> Option<PathBuf>;
@@ -653,7 +902,7 @@ impl<
     ///
     /// It is possible that we try to spawn-stop-spawn the same chain, and the second spawn fails
     /// because the chain has not stopped yet, because `stop` does not wait for the chain to stop,
-    /// so before calling `spawn` make sure to call `wait_for_paritydb_lock` before, like we do in
+    /// so before calling `spawn` make sure to call `wait_for_db_lock` before, like we do in
     /// `handle_update_assignment`.
     async fn spawn(&self, container_chain_para_id: ParaId, start_collation: bool) {
         let try_spawn_params = self.params.clone();
@@ -683,7 +932,7 @@ impl<
     }
 
     /// Stop a container chain. Prints a warning if the container chain was not running.
-    /// Returns the database path for the container chain, can be used with `wait_for_paritydb_lock`
+    /// Returns the database path for the container chain, can be used with `wait_for_db_lock`
     /// to ensure that the container chain has fully stopped. The database path can be `None` if the
     /// chain was not running.
     fn stop(&self, container_chain_para_id: ParaId, keep_db: bool) -> Option<PathBuf> {
@@ -696,6 +945,10 @@ impl<
             Some(stop_handle) => {
                 log::info!("Stopping container chain {}", container_chain_para_id);
 
+                if let Some(metrics) = &self.params.metrics {
+                    metrics.on_stop(container_chain_para_id, stop_handle.collating);
+                }
+
                 let id = stop_handle.stop_handle.id;
                 state
                     .spawned_containers_monitor
@@ -748,8 +1001,12 @@ impl<
         // then the real assignment is used.
         // Except in solochain mode, then the initial assignment is None.
         if validator && !solochain {
-            self.handle_update_assignment(Some(orchestrator_para_id), None, true)
-                .await;
+            self.handle_update_assignment(
+                BTreeSet::from([orchestrator_para_id]),
+                BTreeSet::new(),
+                true,
+            )
+            .await;
         }
 
         while let Some(msg) = rx.recv().await {
@@ -757,6 +1014,23 @@ impl<
                 CcSpawnMsg::UpdateAssignment { current, next } => {
                     self.handle_update_assignment(current, next, false).await;
                 }
+                CcSpawnMsg::Spawn {
+                    para_id,
+                    start_collation,
+                    result,
+                } => {
+                    let _ = result.send(self.handle_manual_spawn(para_id, start_collation).await);
+                }
+                CcSpawnMsg::Stop {
+                    para_id,
+                    keep_db,
+                    result,
+                } => {
+                    let _ = result.send(self.handle_manual_stop(para_id, keep_db));
+                }
+                CcSpawnMsg::Resync { para_id, result } => {
+                    let _ = result.send(self.handle_manual_resync(para_id).await);
+                }
             }
         }
 
@@ -771,8 +1045,8 @@ impl<
     /// Handle `CcSpawnMsg::UpdateAssignment`
     async fn handle_update_assignment(
         &mut self,
-        current: Option<ParaId>,
-        next: Option<ParaId>,
+        current: BTreeSet<ParaId>,
+        next: BTreeSet<ParaId>,
         disable_db_folder_cleanup: bool,
     ) {
         if !disable_db_folder_cleanup && !self.db_folder_cleanup_done {
@@ -782,8 +1056,8 @@ impl<
             let keep_db = self.params.container_chain_cli.base.keep_db;
             if !keep_db {
                 let mut chains_to_keep = HashSet::new();
-                chains_to_keep.extend(current);
-                chains_to_keep.extend(next);
+                chains_to_keep.extend(current.iter().copied());
+                chains_to_keep.extend(next.iter().copied());
                 self.db_folder_cleanup(&chains_to_keep);
             }
         }
@@ -795,18 +1069,48 @@ impl<
             .expect("assignment update should only occur in a collating node")
             .orchestrator_para_id;
 
-        let HandleUpdateAssignmentResult {
-            chains_to_stop,
-            chains_to_start,
-            need_to_restart: _,
-        } = handle_update_assignment_state_change(
-            &mut self.state.lock().expect("poison error"),
-            orchestrator_para_id,
+        let max_collating_chains = self
+            .params
+            .container_chain_cli
+            .base
+            .max_collating_container_chains as usize;
+
+        let (
+            HandleUpdateAssignmentResult {
+                chains_to_stop,
+                chains_to_start,
+                need_to_restart: _,
+            },
             current,
-            next,
-        );
+            recently_assigned,
+        ) = {
+            let mut state = self.state.lock().expect("poison error");
+            let previous_assignment = state.assignment();
+            let result = handle_update_assignment_state_change(
+                &mut state,
+                orchestrator_para_id,
+                max_collating_chains,
+                current,
+                next,
+            );
+            let assignment = state.assignment();
+            // Chains assigned before or after the change, empty if the assignment did not change
+            let mut recently_assigned = BTreeSet::new();
+            if assignment != previous_assignment {
+                if let Some(metrics) = &self.params.metrics {
+                    metrics.on_assignment_change();
+                }
+                recently_assigned.extend(
+                    [previous_assignment, assignment]
+                        .into_iter()
+                        .flat_map(|(current, next)| current.into_iter().chain(next)),
+                );
+            }
+            // Chains above the budget have been discarded, use the assignment that was actually applied
+            (result, state.assigned_para_ids.clone(), recently_assigned)
+        };
 
-        if current != Some(orchestrator_para_id) {
+        if !current.contains(&orchestrator_para_id) {
             // If not assigned to orchestrator chain anymore, we need to stop the collator process
             let maybe_exit_notification_receiver = self
                 .collation_cancellation_constructs
@@ -828,7 +1132,7 @@ impl<
         let mut db_paths_restart = vec![];
         for para_id in chains_to_stop {
             // Keep db if we are currently assigned to this chain
-            let keep_db = Some(para_id) == current;
+            let keep_db = current.contains(&para_id);
             let maybe_db_path = self.stop(para_id, keep_db);
             // If we are restarting this chain, save its db_path to check when it actually stopped
             if let Some(db_path) = maybe_db_path {
@@ -845,7 +1149,7 @@ impl<
             let futs = db_paths_restart
                 .into_iter()
                 .map(|(para_id, db_path)| async move {
-                    wait_for_paritydb_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
+                    wait_for_db_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
                         .await
                         .map_err(|e| {
                             log::warn!(
@@ -862,13 +1166,222 @@ impl<
         for para_id in chains_to_start {
             // Edge case: when starting the node it may be assigned to a container chain, so we need to
             // start a container chain already collating.
-            // TODO: another edge case: if current == None, and running_chains == 0,
+            // TODO: another edge case: if current is empty, and running_chains == 0,
             // and chains_to_start == 1, we can start this chain as collating, and we won't need
             // to restart it on the next session. We need to add some extra state somewhere to
             // implement this properly.
-            let start_collation = Some(para_id) == current;
+            let start_collation = current.contains(&para_id);
             self.spawn(para_id, start_collation).await;
         }
+
+        if !recently_assigned.is_empty() {
+            self.enforce_db_retention(&recently_assigned);
+        }
+    }
+
+    /// Remove the databases of the least recently assigned container chains, if there are more
+    /// than `--container-dbs-max-count` databases or they use more than
+    /// `--container-dbs-max-size-mib`. Databases of running or assigned chains are never removed.
+    ///
+    /// `recently_assigned` are the chains assigned to this node before and after the last
+    /// assignment change, their last assigned time is updated to now.
+    fn enforce_db_retention(&self, recently_assigned: &BTreeSet<ParaId>) {
+        let container_chain_cli = &self.params.container_chain_cli.base;
+        let max_count = container_chain_cli.container_dbs_max_count;
+        let max_size = container_chain_cli
+            .container_dbs_max_size_mib
+            .map(|mib| mib.saturating_mul(1024 * 1024));
+        if max_count.is_none() && max_size.is_none() {
+            return;
+        }
+
+        // "containers" folder
+        let base_path = container_chain_cli
+            .base
+            .shared_params
+            .base_path
+            .as_ref()
+            .expect("base_path is always set")
+            .to_owned();
+        let last_assigned_path = base_path.join(LAST_ASSIGNED_FILE);
+        let mut last_assigned = read_last_assigned(&last_assigned_path);
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or_default();
+        for para_id in recently_assigned {
+            last_assigned.insert((*para_id).into(), now);
+        }
+
+        let in_use: HashSet<ParaId> = {
+            let state = self.state.lock().expect("poison error");
+            state
+                .assigned_para_ids
+                .iter()
+                .chain(state.next_assigned_para_ids.iter())
+                .chain(state.spawned_container_chains.keys())
+                .copied()
+                .collect()
+        };
+
+        // "containers/chains"
+        let mut stored_dbs = vec![];
+        for (para_id, folders) in sort_container_folders_by_para_id(&base_path.join("chains")) {
+            // Folders without a database are small, ignore them
+            let Some(para_id) = para_id else {
+                continue;
+            };
+
+            // A folder can have one database per backend
+            let db_paths = folders.iter().flat_map(|folder| {
+                DB_FOLDERS.map(|db_folder| {
+                    folder
+                        .join(db_folder)
+                        .join(format!("full-container-{}", para_id))
+                })
+            });
+            for db_path in db_paths.filter(|db_path| db_path.is_dir()) {
+                // Also skip databases that are still open, for example chains that are stopping
+                let in_use =
+                    in_use.contains(&para_id) || check_db_lock_held(&db_path).unwrap_or(true);
+
+                stored_dbs.push(StoredContainerDb {
+                    para_id,
+                    size: dir_size(&db_path),
+                    last_assigned: last_assigned
+                        .get(&para_id.into())
+                        .copied()
+                        .unwrap_or_default(),
+                    in_use,
+                    db_path,
+                });
+            }
+        }
+
+        for (i, reason) in select_dbs_to_evict(&stored_dbs, max_count, max_size) {
+            let db = &stored_dbs[i];
+            log::info!(
+                "Removing database of container chain {} to enforce the retention policy. Reason: {:?}. Path: {}",
+                db.para_id,
+                reason,
+                db.db_path.display(),
+            );
+            delete_container_chain_db(&db.db_path);
+            if let Some(metrics) = &self.params.metrics {
+                metrics.on_db_removal(db.para_id, &reason);
+            }
+            self.state
+                .lock()
+                .expect("poison error")
+                .spawned_containers_monitor
+                .push_db_removal(db.para_id, reason);
+        }
+
+        // Forget chains that no longer have a database
+        last_assigned.retain(|para_id, _| {
+            stored_dbs
+                .iter()
+                .any(|db| u32::from(db.para_id) == *para_id && db.db_path.exists())
+        });
+        write_last_assigned(&last_assigned_path, &last_assigned);
+    }
+
+    /// Handle `CcSpawnMsg::Spawn`. Unlike [Spawner::spawn], errors are returned to the caller.
+    /// The chain will be stopped on the next assignment change that does not include it.
+    async fn handle_manual_spawn(
+        &self,
+        para_id: ParaId,
+        start_collation: bool,
+    ) -> Result<(), String> {
+        let orchestrator_para_id = self
+            .params
+            .collation_params
+            .as_ref()
+            .map(|collation_params| collation_params.orchestrator_para_id);
+        if orchestrator_para_id == Some(para_id) {
+            return Err(format!(
+                "Para id {} is the orchestrator chain, not a container chain",
+                para_id
+            ));
+        }
+        if start_collation && self.params.collation_params.is_none() {
+            return Err(
+                "Cannot collate on a container chain when not running as a collator".into(),
+            );
+        }
+        if self
+            .state
+            .lock()
+            .expect("poison error")
+            .spawned_container_chains
+            .contains_key(&para_id)
+        {
+            return Err(format!("Container chain {} is already running", para_id));
+        }
+
+        log::info!("Manually starting container chain {}", para_id);
+        try_spawn(
+            self.params.clone(),
+            self.state.clone(),
+            para_id,
+            start_collation,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Failed to start container chain {}: {}", para_id, e);
+            // Mark this container chain as "failed to start"
+            let mut state = self.state.lock().expect("poison error");
+            state.failed_para_ids.insert(para_id);
+
+            e.to_string()
+        })
+    }
+
+    /// Handle `CcSpawnMsg::Stop`. The database is removed when running as a collator, unless
+    /// `keep_db` is true or the node was started with `--keep-db`.
+    fn handle_manual_stop(&self, para_id: ParaId, keep_db: bool) -> Result<(), String> {
+        log::info!("Manually stopping container chain {}", para_id);
+        self.stop(para_id, keep_db)
+            .map(|_db_path| ())
+            .ok_or_else(|| format!("Container chain {} is not running", para_id))
+    }
+
+    /// Handle `CcSpawnMsg::Resync`. The container chain is started again in the same mode, and
+    /// since the database no longer exists it will use warp sync if enabled.
+    async fn handle_manual_resync(&self, para_id: ParaId) -> Result<(), String> {
+        let start_collation = self
+            .state
+            .lock()
+            .expect("poison error")
+            .spawned_container_chains
+            .get(&para_id)
+            .map(|chain| chain.collating)
+            .ok_or_else(|| format!("Container chain {} is not running", para_id))?;
+
+        log::info!("Manually resyncing container chain {}", para_id);
+        // Keep db to avoid racing with the container chain task, the db is removed below
+        let db_path = self
+            .stop(para_id, true)
+            .ok_or_else(|| format!("Container chain {} is not running", para_id))?;
+        wait_for_db_lock(&db_path, MAX_DB_RESTART_TIMEOUT)
+            .await
+            .map_err(|e| {
+                format!(
+                    "Error waiting for chain {} to release db lock: {}",
+                    para_id, e
+                )
+            })?;
+        delete_container_chain_db(&db_path);
+        if let Some(metrics) = &self.params.metrics {
+            metrics.on_db_removal(para_id, &DbRemovalReason::ManualResync);
+        }
+        self.state
+            .lock()
+            .expect("poison error")
+            .spawned_containers_monitor
+            .push_db_removal(para_id, DbRemovalReason::ManualResync);
+
+        self.handle_manual_spawn(para_id, start_collation).await
     }
 
     fn db_folder_cleanup(&self, chains_to_keep: &HashSet<ParaId>) {
@@ -891,8 +1404,9 @@ impl<
         // containers/chains/simple_container_2000/
         // containers/chains/frontier_container_2001/
         // But this is not the para id, it's the chain id which we have set to include the para id, but that's not mandatory.
-        // To get the para id we need to look for the paritydb folder:
+        // To get the para id we need to look for the database folder:
         // containers/chains/frontier_container_2001/paritydb/full-container-2001/
+        // containers/chains/frontier_container_2001/db/full-container-2001/
         let mut chain_folders = sort_container_folders_by_para_id(&base_path);
 
         // Keep chains that we are assigned to
@@ -937,14 +1451,89 @@ struct HandleUpdateAssignmentResult {
     need_to_restart: bool,
 }
 
+/// Ports of a collating container chain, in the same order as `base_ports`. The configured ports
+/// are moved by `port_offset * port_stride`, and must not collide with any of the configured ones.
+fn collating_chain_ports(
+    base_ports: [u16; 3],
+    port_offset: u16,
+    port_stride: u16,
+) -> Result<[u16; 3], String> {
+    let shift = port_offset.checked_mul(port_stride);
+    let mut ports = base_ports;
+    for port in ports.iter_mut() {
+        *port = shift
+            .and_then(|shift| port.checked_add(shift))
+            .ok_or_else(|| {
+                format!(
+                    "Ports of collating container chain with offset {} out of range, try a smaller --collating-container-chains-port-stride",
+                    port_offset
+                )
+            })?;
+    }
+
+    if let Some(port) = ports.iter().find(|port| base_ports.contains(port)) {
+        return Err(format!(
+            "Port {} of collating container chain with offset {} is already used, try a different --collating-container-chains-port-stride",
+            port, port_offset
+        ));
+    }
+
+    Ok(ports)
+}
+
+/// Lowest port offset not used by any running container chain with the same `syncing_ports`.
+fn free_port_offset(state: &ContainerChainSpawnerState, syncing_ports: bool) -> u16 {
+    let used_offsets: HashSet<u16> = state
+        .spawned_container_chains
+        .values()
+        .filter(|chain| chain.syncing_ports == syncing_ports)
+        .map(|chain| chain.port_offset)
+        .collect();
+
+    (0..)
+        .find(|offset| !used_offsets.contains(offset))
+        .expect("cannot run more than u16::MAX container chains at the same time")
+}
+
+/// Split an assignment into the para ids that fit in the resource budget and the ones that do not.
+/// The orchestrator chain is always kept, and does not count towards `max_collating_chains`.
+/// Container chains are kept in ascending para id order.
+fn limit_assignment(
+    assignment: BTreeSet<ParaId>,
+    orchestrator_para_id: ParaId,
+    max_collating_chains: usize,
+) -> (BTreeSet<ParaId>, Vec<ParaId>) {
+    let mut kept = BTreeSet::new();
+    let mut ignored = vec![];
+    let mut container_chains = 0;
+
+    for para_id in assignment {
+        if para_id == orchestrator_para_id {
+            kept.insert(para_id);
+        } else if container_chains < max_collating_chains {
+            kept.insert(para_id);
+            container_chains += 1;
+        } else {
+            ignored.push(para_id);
+        }
+    }
+
+    (kept, ignored)
+}
+
 // This is a separate function to allow testing
 fn handle_update_assignment_state_change(
     state: &mut ContainerChainSpawnerState,
     orchestrator_para_id: ParaId,
-    current: Option<ParaId>,
-    next: Option<ParaId>,
+    max_collating_chains: usize,
+    current: BTreeSet<ParaId>,
+    next: BTreeSet<ParaId>,
 ) -> HandleUpdateAssignmentResult {
-    if (state.assigned_para_id, state.next_assigned_para_id) == (current, next) {
+    let (current, ignored_current) =
+        limit_assignment(current, orchestrator_para_id, max_collating_chains);
+    let (next, ignored_next) = limit_assignment(next, orchestrator_para_id, max_collating_chains);
+
+    if state.assigned_para_ids == current && state.next_assigned_para_ids == next {
         // If nothing changed there is nothing to update
         return HandleUpdateAssignmentResult {
             chains_to_stop: Default::default(),
@@ -953,80 +1542,70 @@ fn handle_update_assignment_state_change(
         };
     }
 
-    // Create a set with the container chains that were running before, and the container
-    // chains that should be running after the updated assignment. This is used to calculate
-    // the difference, and stop and start the required container chains.
-    let mut running_chains_before = HashSet::new();
-    let mut running_chains_after = HashSet::new();
+    if !ignored_current.is_empty() || !ignored_next.is_empty() {
+        log::warn!(
+            "Assigned to more container chains than allowed by --max-collating-container-chains ({}). Ignoring current: {:?}, next: {:?}",
+            max_collating_chains,
+            ignored_current,
+            ignored_next,
+        );
+    }
 
-    running_chains_before.extend(state.assigned_para_id);
-    running_chains_before.extend(state.next_assigned_para_id);
     // Ignore orchestrator_para_id because it is handled in a special way, as it does not need to
     // start one session before in order to sync.
+    let collating_before: HashSet<ParaId> = state
+        .assigned_para_ids
+        .iter()
+        .copied()
+        .filter(|para_id| *para_id != orchestrator_para_id)
+        .collect();
+    let collating_after: HashSet<ParaId> = current
+        .iter()
+        .copied()
+        .filter(|para_id| *para_id != orchestrator_para_id)
+        .collect();
+
+    // Create a set with the container chains that were running before, and the container
+    // chains that should be running after the updated assignment. This is used to calculate
+    // the difference, and stop and start the required container chains.
+    let mut running_chains_before = collating_before.clone();
+    running_chains_before.extend(state.next_assigned_para_ids.iter().copied());
     running_chains_before.remove(&orchestrator_para_id);
 
-    running_chains_after.extend(current);
-    running_chains_after.extend(next);
+    let mut running_chains_after = collating_after.clone();
+    running_chains_after.extend(next.iter().copied());
     running_chains_after.remove(&orchestrator_para_id);
-    let mut need_to_restart_current = false;
-    let mut need_to_restart_next = false;
-
-    if state.assigned_para_id != current {
-        if let Some(para_id) = current {
-            // If the assigned container chain has changed, we may need to
-            // restart it in collation mode, unless it is the orchestrator chain.
-            if para_id != orchestrator_para_id {
-                need_to_restart_current = true;
-            }
-        }
 
-        if let Some(para_id) = state.assigned_para_id {
-            if para_id != orchestrator_para_id && Some(para_id) == next {
-                need_to_restart_next = true;
-            }
-        }
-    }
+    // Force restart of container chains that keep running but change between "syncing mode" and
+    // "collation mode", because syncing chains don't use the correct ports. This also handles the
+    // edge case of going from (2000, 2001) to (2001, 2000), where both chains must be restarted.
+    let chains_to_restart: Vec<ParaId> = running_chains_before
+        .intersection(&running_chains_after)
+        .copied()
+        .filter(|para_id| collating_before.contains(para_id) != collating_after.contains(para_id))
+        .collect();
 
-    state.assigned_para_id = current;
-    state.next_assigned_para_id = next;
+    state.assigned_para_ids = current;
+    state.next_assigned_para_ids = next;
 
     let mut chains_to_stop: Vec<_> = running_chains_before
         .difference(&running_chains_after)
         .copied()
+        .chain(chains_to_restart.iter().copied())
         .collect();
     let mut chains_to_start: Vec<_> = running_chains_after
         .difference(&running_chains_before)
         .copied()
+        .chain(chains_to_restart.iter().copied())
         .collect();
-
-    if need_to_restart_current {
-        // Force restart of new assigned container chain: if it was running before it was in "syncing mode",
-        // which doesn't use the correct ports, so start it in "collation mode".
-        let id = current.unwrap();
-        if running_chains_before.contains(&id) && !chains_to_stop.contains(&id) {
-            chains_to_stop.push(id);
-        }
-        if !chains_to_start.contains(&id) {
-            chains_to_start.push(id);
-        }
-    }
-
-    if need_to_restart_next {
-        // Handle edge case of going from (2000, 2001) to (2001, 2000). In that case we must restart both chains,
-        // because previously 2000 was collating and now 2000 will only be syncing.
-        let id = next.unwrap();
-        if running_chains_before.contains(&id) && !chains_to_stop.contains(&id) {
-            chains_to_stop.push(id);
-        }
-        if !chains_to_start.contains(&id) {
-            chains_to_start.push(id);
-        }
-    }
+    // Sort to make port offsets deterministic
+    chains_to_stop.sort();
+    chains_to_start.sort();
 
     HandleUpdateAssignmentResult {
         chains_to_stop,
         chains_to_start,
-        need_to_restart: need_to_restart_current || need_to_restart_next,
+        need_to_restart: !chains_to_restart.is_empty(),
     }
 }
 
@@ -1042,9 +1621,14 @@ async fn get_latest_container_block_number_from_orchestrator(
         .unwrap_or_default()
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
-enum DbRemovalReason {
+/// Why the spawner removed the database of a container chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase",
+    tag = "type"
+)]
+pub enum DbRemovalReason {
     HighBlockDiff {
         best_block_number_db: u32,
         best_block_number_onchain: u32,
@@ -1054,6 +1638,12 @@ enum DbRemovalReason {
         chain_spec_genesis_hash_v0: H256,
         chain_spec_genesis_hash_v1: H256,
     },
+    /// Requested using the spawner admin RPC
+    ManualResync,
+    /// Removed by the retention policy, because there were more than `max_count` databases
+    RetentionMaxCount { db_count: u32, max_count: u32 },
+    /// Removed by the retention policy, because the databases used more than `max_size` bytes
+    RetentionMaxSize { total_size: u64, max_size: u64 },
 }
 
 /// Given a container chain client, check if the database is valid. If not, returns `Some` with the
@@ -1075,21 +1665,24 @@ async fn db_needs_removal<RuntimeApi: MinimalContainerRuntimeApi>(
         let last_container_block_temp = container_chain_client.chain_info().best_number;
         if last_container_block_temp == 0 {
             // Don't remove an empty database, as it may be in the process of a warp sync
-        } else if get_latest_container_block_number_from_orchestrator(
-            orchestrator_chain_interface,
-            orchestrator_block_hash,
-            container_chain_para_id,
-        )
-        .await
-        .unwrap_or(0)
-        .abs_diff(last_container_block_temp)
-            > MAX_BLOCK_DIFF_FOR_FULL_SYNC
-        {
-            // if the diff is big, delete db and restart using warp sync
-            return Ok(Some(DbRemovalReason::HighBlockDiff {
-                best_block_number_db: last_container_block_temp,
-                best_block_number_onchain: last_container_block_temp,
-            }));
+        } else {
+            let best_block_number_onchain = get_latest_container_block_number_from_orchestrator(
+                orchestrator_chain_interface,
+                orchestrator_block_hash,
+                container_chain_para_id,
+            )
+            .await
+            .unwrap_or(0);
+
+            if best_block_number_onchain.abs_diff(last_container_block_temp)
+                > MAX_BLOCK_DIFF_FOR_FULL_SYNC
+            {
+                // if the diff is big, delete db and restart using warp sync
+                return Ok(Some(DbRemovalReason::HighBlockDiff {
+                    best_block_number_db: last_container_block_temp,
+                    best_block_number_onchain,
+                }));
+            }
         }
     }
 
@@ -1128,6 +1721,91 @@ async fn db_needs_removal<RuntimeApi: MinimalContainerRuntimeApi>(
     Ok(None)
 }
 
+/// A container chain database found in the `containers/chains` folder.
+struct StoredContainerDb {
+    para_id: ParaId,
+    db_path: PathBuf,
+    /// Size in bytes of the database folder
+    size: u64,
+    /// Unix timestamp in seconds of the last time this chain was assigned to this node, 0 if unknown
+    last_assigned: u64,
+    /// Running, assigned, or still open. These databases are never removed.
+    in_use: bool,
+}
+
+/// Select the databases to remove to stay under `max_count` databases and `max_size` bytes,
+/// least recently assigned first. Returns the indexes in `dbs` and the reason for removal.
+/// The limits may still be exceeded after removing all the selected databases, if the remaining
+/// ones are in use.
+fn select_dbs_to_evict(
+    dbs: &[StoredContainerDb],
+    max_count: Option<u32>,
+    max_size: Option<u64>,
+) -> Vec<(usize, DbRemovalReason)> {
+    let mut candidates: Vec<usize> = (0..dbs.len()).filter(|i| !dbs[*i].in_use).collect();
+    candidates.sort_by_key(|i| (dbs[*i].last_assigned, dbs[*i].para_id));
+
+    let mut db_count = dbs.len() as u32;
+    let mut total_size: u64 = dbs.iter().map(|db| db.size).sum();
+    let mut evicted = vec![];
+
+    for i in candidates {
+        let reason = match (max_count, max_size) {
+            (Some(max_count), _) if db_count > max_count => DbRemovalReason::RetentionMaxCount {
+                db_count,
+                max_count,
+            },
+            (_, Some(max_size)) if total_size > max_size => DbRemovalReason::RetentionMaxSize {
+                total_size,
+                max_size,
+            },
+            _ => break,
+        };
+
+        db_count -= 1;
+        total_size = total_size.saturating_sub(dbs[i].size);
+        evicted.push((i, reason));
+    }
+
+    evicted
+}
+
+/// Size in bytes of all the files inside `path`, recursively. Ignores any IO errors.
+fn dir_size(path: &Path) -> u64 {
+    let entry_iter = match std::fs::read_dir(path) {
+        Ok(x) => x,
+        Err(_e) => return 0,
+    };
+
+    entry_iter
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_e) => 0,
+        })
+        .sum()
+}
+
+/// Read the last assigned time of each container chain. Returns an empty map if the file does
+/// not exist or is invalid.
+fn read_last_assigned(path: &Path) -> BTreeMap<u32, u64> {
+    std::fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+fn write_last_assigned(path: &Path, last_assigned: &BTreeMap<u32, u64>) {
+    let res = serde_json::to_vec(last_assigned)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| std::fs::write(path, bytes).map_err(|e| e.to_string()));
+
+    if let Err(e) = res {
+        log::warn!("Failed to write {}: {}", path.display(), e);
+    }
+}
+
 /// Remove the container chain database folder. This is called with db_path:
 ///     `Collator2002-01/data/containers/chains/simple_container_2002/paritydb/full-container-2002`
 /// but we want to delete everything under
@@ -1199,11 +1877,13 @@ fn parse_boot_nodes_ignore_invalid(
         .collect()
 }
 
-pub async fn wait_for_paritydb_lock(db_path: &Path, max_timeout: Duration) -> Result<(), String> {
+/// Wait until the database at `db_path` is no longer in use, or return an error if it is still
+/// in use after `max_timeout`. Works with both ParityDB and RocksDB databases.
+pub async fn wait_for_db_lock(db_path: &Path, max_timeout: Duration) -> Result<(), String> {
     let now = Instant::now();
 
     while now.elapsed() < max_timeout {
-        let lock_held = check_paritydb_lock_held(db_path)
+        let lock_held = check_db_lock_held(db_path)
             .map_err(|e| format!("Failed to check if lock file is held: {}", e))?;
         if !lock_held {
             return Ok(());
@@ -1211,20 +1891,48 @@ pub async fn wait_for_paritydb_lock(db_path: &Path, max_timeout: Duration) -> Re
         sleep(Duration::from_secs(1)).await;
     }
 
-    Err("Timeout when waiting for paritydb lock".to_string())
+    Err("Timeout when waiting for db lock".to_string())
 }
 
-/// Given a path to a paritydb database, check if its lock file is held. This indicates that a
-/// background process is still using the database, so we should wait before trying to open it.
-///
-/// This should be kept up to date with the way paritydb handles the lock file:
-/// <https://github.com/paritytech/parity-db/blob/2b6820e310a08678d4540c044f41a93d87343ac8/src/db.rs#L215>
-fn check_paritydb_lock_held(db_path: &Path) -> Result<bool, std::io::Error> {
+/// Database backends supported by container chains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DbBackend {
+    ParityDb,
+    RocksDb,
+}
+
+impl DbBackend {
+    /// Detect the backend of an existing database from the files it creates. Defaults to ParityDB,
+    /// which is also the default backend of container chains.
+    fn detect(db_path: &Path) -> Self {
+        // RocksDB always has a `CURRENT` file pointing to the latest manifest
+        if db_path.join("CURRENT").is_file() {
+            Self::RocksDb
+        } else {
+            Self::ParityDb
+        }
+    }
+}
+
+/// Given a path to a container chain database, check if its lock file is held. This indicates that
+/// a background process is still using the database, so we should wait before trying to open it.
+fn check_db_lock_held(db_path: &Path) -> Result<bool, std::io::Error> {
     if !db_path.is_dir() {
         // Lock file does not exist, so it is not held
         return Ok(false);
     }
 
+    match DbBackend::detect(db_path) {
+        DbBackend::ParityDb => check_paritydb_lock_held(db_path),
+        DbBackend::RocksDb => check_rocksdb_lock_held(db_path),
+    }
+}
+
+/// Given a path to a paritydb database, check if its lock file is held.
+///
+/// This should be kept up to date with the way paritydb handles the lock file:
+/// <https://github.com/paritytech/parity-db/blob/2b6820e310a08678d4540c044f41a93d87343ac8/src/db.rs#L215>
+fn check_paritydb_lock_held(db_path: &Path) -> Result<bool, std::io::Error> {
     let mut lock_path: std::path::PathBuf = db_path.to_owned();
     lock_path.push("lock");
     let lock_file = std::fs::OpenOptions::new()
@@ -1240,6 +1948,217 @@ fn check_paritydb_lock_held(db_path: &Path) -> Result<bool, std::io::Error> {
     Ok(lock_held)
 }
 
+/// Given a path to a rocksdb database, check if its lock file is held.
+///
+/// RocksDB locks the `LOCK` file using `fcntl` record locks. Those locks belong to the process, so
+/// the paritydb approach of trying to take the lock does not work when the database was opened by
+/// this same process, and closing our file descriptor would release the lock held by the database.
+/// Instead, look for the lock file in the list of active locks of the system.
+///
+/// The lock file is matched by its full `major:minor:inode` id. A false positive would prevent the
+/// chain from starting, because `try_spawn` waits for the lock to be released every time it starts
+/// a chain with an existing database, and would also keep the database from being deleted.
+#[cfg(target_os = "linux")]
+fn check_rocksdb_lock_held(db_path: &Path) -> Result<bool, std::io::Error> {
+    let file_ids = match lock_file_ids(&db_path.join("LOCK")) {
+        Ok(x) => x,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    let locks = std::fs::read_to_string("/proc/locks")?;
+
+    Ok(locks
+        .lines()
+        .filter_map(parse_proc_locks_file_id)
+        .any(|id| file_ids.contains(&id)))
+}
+
+/// Device and inode of a file, the way `/proc/locks` identifies it.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct LockFileId {
+    major: u32,
+    minor: u32,
+    inode: u64,
+}
+
+#[cfg(target_os = "linux")]
+impl LockFileId {
+    /// Split a device number the same way glibc's `major` and `minor` do.
+    fn from_dev(dev: u64, inode: u64) -> Self {
+        Self {
+            major: (((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff)) as u32,
+            minor: ((dev & 0xff) | ((dev >> 12) & !0xff)) as u32,
+            inode,
+        }
+    }
+}
+
+/// Parse the file id of a line of `/proc/locks`, such as
+/// `1: POSIX  ADVISORY  WRITE 1234 08:02:131073 0 EOF`. Major and minor are in hex.
+#[cfg(target_os = "linux")]
+fn parse_proc_locks_file_id(line: &str) -> Option<LockFileId> {
+    line.split_whitespace().find_map(|field| {
+        let mut parts = field.split(':');
+        let id = LockFileId {
+            major: u32::from_str_radix(parts.next()?, 16).ok()?,
+            minor: u32::from_str_radix(parts.next()?, 16).ok()?,
+            inode: parts.next()?.parse().ok()?,
+        };
+        parts.next().is_none().then_some(id)
+    })
+}
+
+/// Ids that `/proc/locks` may use for the file at `path`.
+///
+/// Locks are listed with the device of the superblock of the file, which is not always the one
+/// reported by `stat`: btrfs reports a different device for each subvolume, and overlayfs reports
+/// the device of the filesystem holding the file. The superblock device is read from
+/// `/proc/self/mountinfo`, and the upper copy of overlayfs files is also considered.
+#[cfg(target_os = "linux")]
+fn lock_file_ids(path: &Path) -> Result<Vec<LockFileId>, std::io::Error> {
+    use std::os::unix::fs::MetadataExt;
+
+    let path = path.canonicalize()?;
+    let metadata = std::fs::metadata(&path)?;
+    let mut ids = vec![LockFileId::from_dev(metadata.dev(), metadata.ino())];
+
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo")?;
+    let Some(mount) = find_mount(&mountinfo, &path) else {
+        return Ok(ids);
+    };
+    ids.push(LockFileId {
+        major: mount.major,
+        minor: mount.minor,
+        inode: metadata.ino(),
+    });
+
+    if let Some(upper_path) = mount.upper_path(&path) {
+        if let Ok(upper_metadata) = std::fs::metadata(&upper_path) {
+            ids.push(LockFileId::from_dev(
+                upper_metadata.dev(),
+                upper_metadata.ino(),
+            ));
+            if let Some(upper_mount) = find_mount(&mountinfo, &upper_path) {
+                ids.push(LockFileId {
+                    major: upper_mount.major,
+                    minor: upper_mount.minor,
+                    inode: upper_metadata.ino(),
+                });
+            }
+        }
+    }
+
+    Ok(ids)
+}
+
+/// A line of `/proc/self/mountinfo`.
+#[cfg(target_os = "linux")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MountInfo {
+    major: u32,
+    minor: u32,
+    mount_point: PathBuf,
+    fs_type: String,
+    super_options: String,
+}
+
+#[cfg(target_os = "linux")]
+impl MountInfo {
+    /// Parse a line such as
+    /// `36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue`.
+    fn parse(line: &str) -> Option<Self> {
+        let (mount_fields, fs_fields) = line.split_once(" - ")?;
+        let mut mount_fields = mount_fields.split(' ').skip(2);
+        let (major, minor) = mount_fields.next()?.split_once(':')?;
+        let _root = mount_fields.next()?;
+        let mount_point = unescape_mountinfo_field(mount_fields.next()?);
+        let mut fs_fields = fs_fields.split(' ');
+        let fs_type = fs_fields.next()?.to_string();
+        let _source = fs_fields.next()?;
+        let super_options = fs_fields.next().unwrap_or_default().to_string();
+
+        Some(Self {
+            major: major.parse().ok()?,
+            minor: minor.parse().ok()?,
+            mount_point,
+            fs_type,
+            super_options,
+        })
+    }
+
+    /// Path of the upper copy of `path` if this is an overlayfs mount.
+    fn upper_path(&self, path: &Path) -> Option<PathBuf> {
+        if self.fs_type != "overlay" {
+            return None;
+        }
+        let upper_dir = self
+            .super_options
+            .split(',')
+            .find_map(|option| option.strip_prefix("upperdir="))?;
+        let relative_path = path.strip_prefix(&self.mount_point).ok()?;
+
+        Some(unescape_mountinfo_field(upper_dir).join(relative_path))
+    }
+}
+
+/// Mount containing `path`: the one with the longest mount point, the last one if several mounts
+/// share it since later mounts hide earlier ones.
+#[cfg(target_os = "linux")]
+fn find_mount(mountinfo: &str, path: &Path) -> Option<MountInfo> {
+    mountinfo
+        .lines()
+        .filter_map(MountInfo::parse)
+        .filter(|mount| path.starts_with(&mount.mount_point))
+        .fold(None, |best: Option<MountInfo>, mount| match best {
+            Some(best)
+                if best.mount_point.components().count()
+                    > mount.mount_point.components().count() =>
+            {
+                Some(best)
+            }
+            _ => Some(mount),
+        })
+}
+
+/// Undo the octal escapes (`\040` for spaces) of the paths in `/proc/self/mountinfo`.
+#[cfg(target_os = "linux")]
+fn unescape_mountinfo_field(field: &str) -> PathBuf {
+    use std::os::unix::ffi::OsStringExt;
+
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 4)
+            .filter(|_| bytes[i] == b'\\')
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match escaped {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 4;
+            }
+            None => {
+                unescaped.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+
+    PathBuf::from(std::ffi::OsString::from_vec(unescaped))
+}
+
+/// Given a path to a rocksdb database, check if its lock file is held.
+///
+/// Not supported outside of Linux, assume that the database has been closed.
+#[cfg(not(target_os = "linux"))]
+fn check_rocksdb_lock_held(_db_path: &Path) -> Result<bool, std::io::Error> {
+    Ok(false)
+}
+
 fn sort_container_folders_by_para_id(
     chains_folder_path: &Path,
 ) -> HashMap<Option<ParaId>, Vec<PathBuf>> {
@@ -1269,52 +2188,54 @@ fn sort_container_folders_by_para_id(
 }
 
 fn process_container_folder_get_para_id(path: PathBuf) -> std::io::Result<Option<ParaId>> {
-    // Build the path to the paritydb directory
-    let paritydb_path = path.join("paritydb");
-
-    // Check if the paritydb directory exists and is a directory
-    if !paritydb_path.is_dir() {
-        // If not, associate the path with `None` in the hashmap
-        return Ok(None);
-    }
-
-    // Read the entries in the paritydb directory
-    let entry_iter = std::fs::read_dir(&paritydb_path)?;
-
     let mut para_id: Option<ParaId> = None;
 
-    // Iterate over each entry in the paritydb directory
-    for entry in entry_iter {
-        let entry = entry?;
-        let sub_path = entry.path();
+    for db_folder in DB_FOLDERS {
+        // Build the path to the database directory of this backend
+        let db_folder_path = path.join(db_folder);
 
-        // Only consider directories
-        if !sub_path.is_dir() {
+        // Check if the database directory exists and is a directory
+        if !db_folder_path.is_dir() {
             continue;
         }
 
-        let sub_path_file_name = match sub_path.file_name().and_then(|s| s.to_str()) {
-            Some(x) => x,
-            None => {
+        // Read the entries in the database directory
+        let entry_iter = std::fs::read_dir(&db_folder_path)?;
+
+        // Iterate over each entry in the database directory
+        for entry in entry_iter {
+            let entry = entry?;
+            let sub_path = entry.path();
+
+            // Only consider directories
+            if !sub_path.is_dir() {
                 continue;
             }
-        };
 
-        // That follow this pattern
-        if !sub_path_file_name.starts_with("full-container-") {
-            continue;
-        }
+            let sub_path_file_name = match sub_path.file_name().and_then(|s| s.to_str()) {
+                Some(x) => x,
+                None => {
+                    continue;
+                }
+            };
 
-        if let Some(id) = parse_para_id_from_folder_name(sub_path_file_name) {
-            if para_id.is_some() {
-                // If there is more than one folder with a para id, assume this folder is
-                // corrupted and ignore it, keep it for manual deletion
-                return Err(std::io::Error::new(std::io::ErrorKind::Other, ""));
+            // That follow this pattern
+            if !sub_path_file_name.starts_with("full-container-") {
+                continue;
+            }
+
+            if let Some(id) = parse_para_id_from_folder_name(sub_path_file_name) {
+                if para_id.is_some() && para_id != Some(id) {
+                    // If there is more than one folder with a different para id, assume this folder
+                    // is corrupted and ignore it, keep it for manual deletion
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, ""));
+                }
+                para_id = Some(id);
             }
-            para_id = Some(id);
         }
     }
 
+    // If no database was found, the folder will be associated with `None` in the hashmap
     Ok(para_id)
 }
 
@@ -1345,25 +2266,29 @@ mod tests {
             dyn Fn() -> (CancellationToken, futures::channel::oneshot::Receiver<()>) + Send + Sync,
         >,
         collation_cancellation_constructs: Option<()>,
-        // Keep track of the last CollateOn message, for tests
-        currently_collating_on: Arc<Mutex<Option<ParaId>>>,
+        // Keep track of the chains we are collating on, for tests
+        currently_collating_on: Arc<Mutex<BTreeSet<ParaId>>>,
+        max_collating_chains: usize,
     }
 
     impl MockContainerChainSpawner {
         fn new() -> Self {
+            Self::with_max_collating_chains(1)
+        }
+
+        fn with_max_collating_chains(max_collating_chains: usize) -> Self {
             let orchestrator_para_id = 1000.into();
             // The node always starts as an orchestrator chain collator
-            let currently_collating_on = Arc::new(Mutex::new(Some(orchestrator_para_id)));
+            let currently_collating_on =
+                Arc::new(Mutex::new(BTreeSet::from([orchestrator_para_id])));
             let currently_collating_on2 = currently_collating_on.clone();
             let collate_closure = move || {
                 let mut cco = currently_collating_on2.lock().unwrap();
-                assert_ne!(
-                    *cco,
-                    Some(orchestrator_para_id),
+                assert!(
+                    cco.insert(orchestrator_para_id),
                     "Received CollateOn message when we were already collating on this chain: {}",
                     orchestrator_para_id
                 );
-                *cco = Some(orchestrator_para_id);
                 let (_, receiver) = futures::channel::oneshot::channel();
                 (CancellationToken::new(), receiver)
             };
@@ -1376,8 +2301,8 @@ mod tests {
             Self {
                 state: Arc::new(Mutex::new(ContainerChainSpawnerState {
                     spawned_container_chains: Default::default(),
-                    assigned_para_id: Some(orchestrator_para_id),
-                    next_assigned_para_id: None,
+                    assigned_para_ids: BTreeSet::from([orchestrator_para_id]),
+                    next_assigned_para_ids: Default::default(),
                     failed_para_ids: Default::default(),
                     spawned_containers_monitor: Default::default(),
                 })),
@@ -1386,6 +2311,7 @@ mod tests {
                 // Some if collator starts on orchestrator chain
                 collation_cancellation_constructs: Some(()),
                 currently_collating_on,
+                max_collating_chains,
             }
         }
 
@@ -1394,13 +2320,11 @@ mod tests {
             let currently_collating_on2 = self.currently_collating_on.clone();
             let collate_closure = move || {
                 let mut cco = currently_collating_on2.lock().unwrap();
-                assert_ne!(
-                    *cco,
-                    Some(container_chain_para_id),
+                assert!(
+                    cco.insert(container_chain_para_id),
                     "Received CollateOn message when we were already collating on this chain: {}",
                     container_chain_para_id
                 );
-                *cco = Some(container_chain_para_id);
                 let (_, receiver) = futures::channel::oneshot::channel();
                 (CancellationToken::new(), receiver)
             };
@@ -1412,18 +2336,21 @@ mod tests {
             // Dummy db_path for tests, is not actually used
             let db_path = PathBuf::from(format!("/tmp/container-{}/db", container_chain_para_id));
 
-            let old = self
-                .state
-                .lock()
-                .expect("poison error")
-                .spawned_container_chains
-                .insert(
-                    container_chain_para_id,
-                    ContainerChainState {
-                        stop_handle: StopContainerChain { signal, id: 0 },
-                        db_path,
-                    },
-                );
+            let mut state = self.state.lock().expect("poison error");
+            let syncing_ports = !start_collation;
+            let port_offset = free_port_offset(&state, syncing_ports);
+            let old = state.spawned_container_chains.insert(
+                container_chain_para_id,
+                ContainerChainState {
+                    stop_handle: StopContainerChain { signal, id: 0 },
+                    db_path,
+                    syncing_ports,
+                    port_offset,
+                    collating: start_collation,
+                    sync_status: Arc::new(|| None),
+                },
+            );
+            drop(state);
 
             assert!(
                 old.is_none(),
@@ -1457,13 +2384,13 @@ mod tests {
             }
 
             // Update currently_collating_on, if we stopped the chain we are no longer collating there
-            let mut lco = self.currently_collating_on.lock().unwrap();
-            if *lco == Some(container_chain_para_id) {
-                *lco = None;
-            }
+            self.currently_collating_on
+                .lock()
+                .unwrap()
+                .remove(&container_chain_para_id);
         }
 
-        fn handle_update_assignment(&mut self, current: Option<ParaId>, next: Option<ParaId>) {
+        fn handle_update_assignment(&mut self, current: &[ParaId], next: &[ParaId]) {
             let HandleUpdateAssignmentResult {
                 chains_to_stop,
                 chains_to_start,
@@ -1471,16 +2398,19 @@ mod tests {
             } = handle_update_assignment_state_change(
                 &mut self.state.lock().unwrap(),
                 self.orchestrator_para_id,
-                current,
-                next,
+                self.max_collating_chains,
+                current.iter().copied().collect(),
+                next.iter().copied().collect(),
             );
+            let current = self.state.lock().unwrap().assigned_para_ids.clone();
+            let next = self.state.lock().unwrap().next_assigned_para_ids.clone();
 
-            if current != Some(self.orchestrator_para_id) {
+            if !current.contains(&self.orchestrator_para_id) {
                 // If not assigned to orchestrator chain anymore, we need to stop the collator process
-                let mut cco = self.currently_collating_on.lock().unwrap();
-                if *cco == Some(self.orchestrator_para_id) {
-                    *cco = None;
-                }
+                self.currently_collating_on
+                    .lock()
+                    .unwrap()
+                    .remove(&self.orchestrator_para_id);
                 self.collation_cancellation_constructs = None;
             } else if self.collation_cancellation_constructs.is_none() {
                 let (_cancellation_token, _exit_notification_receiver) = (self.collate_on_tanssi)();
@@ -1496,8 +2426,8 @@ mod tests {
                         para_id
                     );
                 } else {
-                    // Will try to start and stop container chain with id "current" or "next", so ignore that
-                    if Some(*para_id) != current && Some(*para_id) != next {
+                    // Will try to start and stop container chains in "current" or "next", so ignore that
+                    if !current.contains(para_id) && !next.contains(para_id) {
                         assert!(
                             !chains_to_stop.contains(para_id),
                             "Tried to start and stop same container chain: {}",
@@ -1519,22 +2449,22 @@ mod tests {
             for para_id in chains_to_start {
                 // Edge case: when starting the node it may be assigned to a container chain, so we need to
                 // start a container chain already collating.
-                let start_collation = Some(para_id) == current;
+                let start_collation = current.contains(&para_id);
                 self.spawn(para_id, start_collation);
             }
 
-            // Assert that if we are currently assigned to a container chain, we are collating there
-            if let Some(para_id) = current {
-                self.assert_collating_on(Some(para_id));
-            } else {
-                self.assert_collating_on(None);
-            }
+            // Assert that we are collating on all the chains we are currently assigned to
+            let current: Vec<ParaId> = current.into_iter().collect();
+            self.assert_collating_on(&current);
         }
 
         #[track_caller]
-        fn assert_collating_on(&self, para_id: Option<ParaId>) {
-            let currently_collating_on = *self.currently_collating_on.lock().unwrap();
-            assert_eq!(currently_collating_on, para_id);
+        fn assert_collating_on(&self, para_ids: &[ParaId]) {
+            let currently_collating_on = self.currently_collating_on.lock().unwrap().clone();
+            assert_eq!(
+                currently_collating_on,
+                para_ids.iter().copied().collect::<BTreeSet<_>>()
+            );
         }
 
         #[track_caller]
@@ -1557,11 +2487,11 @@ mod tests {
     #[test]
     fn starts_collating_on_tanssi() {
         let mut m = MockContainerChainSpawner::new();
-        m.assert_collating_on(Some(1000.into()));
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
     }
 
@@ -1569,24 +2499,24 @@ mod tests {
     fn assigned_to_orchestrator_chain() {
         let mut m = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(1000.into()), Some(1000.into()));
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[1000.into()]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(Some(1000.into()), None);
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(None, Some(1000.into()));
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[1000.into()]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(Some(1000.into()), Some(1000.into()));
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[1000.into()]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
     }
 
@@ -1594,24 +2524,24 @@ mod tests {
     fn assigned_to_container_chain() {
         let mut m = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(2000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2000.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(Some(2000.into()), None);
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(None, Some(2000.into()));
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[2000.into()]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(Some(2000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2000.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
     }
 
@@ -1619,28 +2549,28 @@ mod tests {
     fn spawn_container_chains() {
         let mut m = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(1000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[2000.into()]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(Some(2000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2000.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(Some(2000.into()), Some(2001.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2001.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into(), 2001.into()]);
 
-        m.handle_update_assignment(Some(2001.into()), Some(2001.into()));
-        m.assert_collating_on(Some(2001.into()));
+        m.handle_update_assignment(&[2001.into()], &[2001.into()]);
+        m.assert_collating_on(&[2001.into()]);
         m.assert_running_chains(&[2001.into()]);
 
-        m.handle_update_assignment(Some(2001.into()), Some(1000.into()));
-        m.assert_collating_on(Some(2001.into()));
+        m.handle_update_assignment(&[2001.into()], &[1000.into()]);
+        m.assert_collating_on(&[2001.into()]);
         m.assert_running_chains(&[2001.into()]);
 
-        m.handle_update_assignment(Some(1000.into()), Some(1000.into()));
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[1000.into()]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
     }
 
@@ -1649,12 +2579,12 @@ mod tests {
         // Going from (2000, 2001) to (2001, 2000) shouldn't start or stop any container chains
         let mut m: MockContainerChainSpawner = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(2000.into()), Some(2001.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2001.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into(), 2001.into()]);
 
-        m.handle_update_assignment(Some(2001.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2001.into()));
+        m.handle_update_assignment(&[2001.into()], &[2000.into()]);
+        m.assert_collating_on(&[2001.into()]);
         m.assert_running_chains(&[2000.into(), 2001.into()]);
     }
 
@@ -1662,20 +2592,20 @@ mod tests {
     fn stop_collating_orchestrator() {
         let mut m: MockContainerChainSpawner = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(1000.into()), Some(1000.into()));
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[1000.into()]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(Some(1000.into()), None);
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(Some(1000.into()), None);
-        m.assert_collating_on(Some(1000.into()));
+        m.handle_update_assignment(&[1000.into()], &[]);
+        m.assert_collating_on(&[1000.into()]);
         m.assert_running_chains(&[]);
     }
 
@@ -1683,22 +2613,22 @@ mod tests {
     fn stop_collating_container() {
         let mut m: MockContainerChainSpawner = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(2000.into()), None);
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
 
-        m.handle_update_assignment(None, Some(2000.into()));
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[2000.into()]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[2000.into()]);
 
         // This will send a CollateOn message to the same chain as the last CollateOn,
         // but this is needed because that chain has been stopped
-        m.handle_update_assignment(Some(2000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2000.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
     }
 
@@ -1706,17 +2636,17 @@ mod tests {
     fn stop_collating_container_start_immediately() {
         let mut m: MockContainerChainSpawner = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(2000.into()), None);
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
 
         // This will start the chain already collating
-        m.handle_update_assignment(Some(2000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2000.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
     }
 
@@ -1724,12 +2654,12 @@ mod tests {
     fn stop_all_chains() {
         let mut m: MockContainerChainSpawner = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(2000.into()), Some(2001.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2001.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into(), 2001.into()]);
 
-        m.handle_update_assignment(None, None);
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[]);
     }
 
@@ -1737,19 +2667,222 @@ mod tests {
     fn keep_collating_on_container() {
         let mut m: MockContainerChainSpawner = MockContainerChainSpawner::new();
 
-        m.handle_update_assignment(Some(2000.into()), None);
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(None, Some(2000.into()));
-        m.assert_collating_on(None);
+        m.handle_update_assignment(&[], &[2000.into()]);
+        m.assert_collating_on(&[]);
         m.assert_running_chains(&[2000.into()]);
 
-        m.handle_update_assignment(Some(2000.into()), Some(2000.into()));
-        m.assert_collating_on(Some(2000.into()));
+        m.handle_update_assignment(&[2000.into()], &[2000.into()]);
+        m.assert_collating_on(&[2000.into()]);
         m.assert_running_chains(&[2000.into()]);
     }
 
+    #[test]
+    fn collate_on_multiple_parathreads() {
+        let mut m = MockContainerChainSpawner::with_max_collating_chains(3);
+
+        m.handle_update_assignment(&[1000.into()], &[2000.into(), 2001.into()]);
+        m.assert_collating_on(&[1000.into()]);
+        m.assert_running_chains(&[2000.into(), 2001.into()]);
+
+        m.handle_update_assignment(&[2000.into(), 2001.into()], &[2001.into(), 2002.into()]);
+        m.assert_collating_on(&[2000.into(), 2001.into()]);
+        m.assert_running_chains(&[2000.into(), 2001.into(), 2002.into()]);
+
+        m.handle_update_assignment(&[2001.into(), 2002.into()], &[]);
+        m.assert_collating_on(&[2001.into(), 2002.into()]);
+        m.assert_running_chains(&[2001.into(), 2002.into()]);
+
+        m.handle_update_assignment(&[], &[2002.into()]);
+        m.assert_collating_on(&[]);
+        m.assert_running_chains(&[2002.into()]);
+    }
+
+    #[test]
+    fn collate_on_orchestrator_and_parathread() {
+        let mut m = MockContainerChainSpawner::with_max_collating_chains(1);
+
+        m.handle_update_assignment(&[1000.into(), 2000.into()], &[1000.into(), 2000.into()]);
+        m.assert_collating_on(&[1000.into(), 2000.into()]);
+        m.assert_running_chains(&[2000.into()]);
+
+        m.handle_update_assignment(&[2000.into()], &[]);
+        m.assert_collating_on(&[2000.into()]);
+        m.assert_running_chains(&[2000.into()]);
+    }
+
+    #[test]
+    fn parathreads_above_budget_are_ignored() {
+        let mut m = MockContainerChainSpawner::with_max_collating_chains(2);
+
+        m.handle_update_assignment(
+            &[2002.into(), 2000.into(), 2001.into()],
+            &[2003.into(), 2004.into(), 2005.into()],
+        );
+        m.assert_collating_on(&[2000.into(), 2001.into()]);
+        m.assert_running_chains(&[2000.into(), 2001.into(), 2003.into(), 2004.into()]);
+    }
+
+    #[test]
+    fn swap_current_next_parathreads() {
+        let mut m = MockContainerChainSpawner::with_max_collating_chains(2);
+
+        m.handle_update_assignment(&[2000.into(), 2001.into()], &[2001.into(), 2002.into()]);
+        m.assert_collating_on(&[2000.into(), 2001.into()]);
+        m.assert_running_chains(&[2000.into(), 2001.into(), 2002.into()]);
+
+        // 2000 changes from collating to syncing and 2002 from syncing to collating, so both
+        // need to be restarted
+        let result = handle_update_assignment_state_change(
+            &mut m.state.lock().unwrap(),
+            m.orchestrator_para_id,
+            m.max_collating_chains,
+            BTreeSet::from([2001.into(), 2002.into()]),
+            BTreeSet::from([2000.into()]),
+        );
+        assert!(result.need_to_restart);
+        assert_eq!(result.chains_to_stop, vec![2000.into(), 2002.into()]);
+        assert_eq!(result.chains_to_start, vec![2000.into(), 2002.into()]);
+    }
+
+    #[test]
+    fn collating_chains_get_different_port_offsets() {
+        let mut m = MockContainerChainSpawner::with_max_collating_chains(2);
+
+        m.handle_update_assignment(&[2000.into(), 2001.into()], &[2002.into(), 2003.into()]);
+
+        let state = m.state.lock().unwrap();
+        let port_offset = |para_id: u32| {
+            let chain = &state.spawned_container_chains[&ParaId::from(para_id)];
+            (chain.syncing_ports, chain.port_offset)
+        };
+        assert_eq!(port_offset(2000), (false, 0));
+        assert_eq!(port_offset(2001), (false, 1));
+        assert_eq!(port_offset(2002), (true, 0));
+        assert_eq!(port_offset(2003), (true, 1));
+    }
+
+    #[test]
+    fn collating_chains_do_not_share_ports() {
+        let base_ports = [
+            <ContainerChainCli as DefaultConfigurationValues>::prometheus_listen_port(),
+            <ContainerChainCli as DefaultConfigurationValues>::p2p_listen_port(),
+            <ContainerChainCli as DefaultConfigurationValues>::rpc_listen_port(),
+        ];
+
+        let second = collating_chain_ports(base_ports, 1, 100).unwrap();
+        let third = collating_chain_ports(base_ports, 2, 100).unwrap();
+        let mut all_ports: Vec<u16> = [base_ports, second, third].concat();
+        all_ports.sort();
+        all_ports.dedup();
+        assert_eq!(all_ports.len(), 9);
+
+        // Ports that would land on a configured one are rejected
+        assert!(collating_chain_ports([9615, 9616, 9944], 1, 1).is_err());
+        assert!(collating_chain_ports(base_ports, 2, u16::MAX).is_err());
+    }
+
+    #[test]
+    fn container_chains_status_lists_running_and_failed_chains() {
+        let mut m = MockContainerChainSpawner::new();
+
+        m.handle_update_assignment(&[2000.into()], &[2001.into()]);
+        m.state.lock().unwrap().failed_para_ids.insert(2002.into());
+
+        let status = m.state.lock().unwrap().container_chains_status();
+        let states: Vec<(u32, ContainerChainRunState)> = status
+            .iter()
+            .map(|chain| (chain.para_id, chain.state.clone()))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (2000, ContainerChainRunState::Collating),
+                (2001, ContainerChainRunState::Syncing),
+                (2002, ContainerChainRunState::Failed),
+            ]
+        );
+        assert_eq!(status[0].db_path.as_deref(), Some("/tmp/container-2000/db"));
+        assert_eq!(status[2].db_path, None);
+    }
+
+    fn stored_db(para_id: u32, size: u64, last_assigned: u64, in_use: bool) -> StoredContainerDb {
+        StoredContainerDb {
+            para_id: para_id.into(),
+            db_path: PathBuf::from(format!("/tmp/container-{}/db", para_id)),
+            size,
+            last_assigned,
+            in_use,
+        }
+    }
+
+    #[test]
+    fn retention_evicts_least_recently_assigned_above_max_count() {
+        let dbs = vec![
+            stored_db(2000, 10, 300, false),
+            stored_db(2001, 10, 100, false),
+            stored_db(2002, 10, 200, false),
+            stored_db(2003, 10, 0, true),
+        ];
+
+        let evicted = select_dbs_to_evict(&dbs, Some(2), None);
+
+        assert_eq!(
+            evicted,
+            vec![
+                (
+                    1,
+                    DbRemovalReason::RetentionMaxCount {
+                        db_count: 4,
+                        max_count: 2
+                    }
+                ),
+                (
+                    2,
+                    DbRemovalReason::RetentionMaxCount {
+                        db_count: 3,
+                        max_count: 2
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn retention_evicts_until_under_max_size() {
+        let dbs = vec![
+            stored_db(2000, 50, 100, false),
+            stored_db(2001, 30, 200, false),
+            stored_db(2002, 20, 300, false),
+        ];
+
+        // Removing 2000 is enough
+        let evicted = select_dbs_to_evict(&dbs, None, Some(60));
+        assert_eq!(
+            evicted,
+            vec![(
+                0,
+                DbRemovalReason::RetentionMaxSize {
+                    total_size: 100,
+                    max_size: 60
+                }
+            )]
+        );
+
+        // Under both limits, nothing to remove
+        assert_eq!(select_dbs_to_evict(&dbs, Some(3), Some(100)), vec![]);
+    }
+
+    #[test]
+    fn retention_never_evicts_dbs_in_use() {
+        let dbs = vec![stored_db(2000, 50, 0, true), stored_db(2001, 50, 0, true)];
+
+        assert_eq!(select_dbs_to_evict(&dbs, Some(0), Some(0)), vec![]);
+    }
+
     #[test]
     fn invalid_boot_nodes_are_ignored() {
         let para_id = 100.into();
@@ -1796,4 +2929,123 @@ mod tests {
             Some(ParaId::from(2000)),
         );
     }
+
+    #[test]
+    fn container_folder_para_id_with_both_backends() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain_folder = dir.path().join("simple_container_2000");
+
+        std::fs::create_dir_all(chain_folder.join("db").join("full-container-2000")).unwrap();
+        assert_eq!(
+            process_container_folder_get_para_id(chain_folder.clone()).unwrap(),
+            Some(ParaId::from(2000))
+        );
+
+        // Same para id with both backends is fine
+        std::fs::create_dir_all(chain_folder.join("paritydb").join("full-container-2000")).unwrap();
+        assert_eq!(
+            process_container_folder_get_para_id(chain_folder.clone()).unwrap(),
+            Some(ParaId::from(2000))
+        );
+
+        // Different para ids mean that the folder is corrupted
+        std::fs::create_dir_all(chain_folder.join("db").join("full-container-2001")).unwrap();
+        assert!(process_container_folder_get_para_id(chain_folder).is_err());
+    }
+
+    #[test]
+    fn paritydb_lock_held_while_db_is_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("paritydb").join("full-container-2000");
+        assert!(!check_db_lock_held(&db_path).unwrap());
+
+        let db =
+            parity_db::Db::open_or_create(&parity_db::Options::with_columns(&db_path, 1)).unwrap();
+        assert_eq!(DbBackend::detect(&db_path), DbBackend::ParityDb);
+        assert!(check_db_lock_held(&db_path).unwrap());
+
+        drop(db);
+        assert!(!check_db_lock_held(&db_path).unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn rocksdb_lock_held_while_db_is_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("db").join("full-container-2000");
+        std::fs::create_dir_all(&db_path).unwrap();
+        assert!(!check_db_lock_held(&db_path).unwrap());
+
+        let db =
+            kvdb_rocksdb::Database::open(&kvdb_rocksdb::DatabaseConfig::with_columns(1), &db_path)
+                .unwrap();
+        assert_eq!(DbBackend::detect(&db_path), DbBackend::RocksDb);
+        assert!(check_db_lock_held(&db_path).unwrap());
+        // Checking the lock must not release it
+        assert!(check_db_lock_held(&db_path).unwrap());
+
+        drop(db);
+        assert_eq!(DbBackend::detect(&db_path), DbBackend::RocksDb);
+        assert!(!check_db_lock_held(&db_path).unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn proc_locks_match_full_file_id() {
+        let lock_id = LockFileId::from_dev(0x0802, 131073);
+        assert_eq!(
+            lock_id,
+            LockFileId {
+                major: 8,
+                minor: 2,
+                inode: 131073
+            }
+        );
+
+        let line = "1: POSIX  ADVISORY  WRITE 1234 08:02:131073 0 EOF";
+        assert_eq!(parse_proc_locks_file_id(line), Some(lock_id));
+        let blocked = "1: -> POSIX  ADVISORY  WRITE 1235 08:02:131073 0 EOF";
+        assert_eq!(parse_proc_locks_file_id(blocked), Some(lock_id));
+
+        // Same inode on another device is a different file
+        let other_device = "2: POSIX  ADVISORY  WRITE 1234 00:2a:131073 0 EOF";
+        assert_ne!(parse_proc_locks_file_id(other_device), Some(lock_id));
+        assert_eq!(
+            parse_proc_locks_file_id(other_device),
+            Some(LockFileId {
+                major: 0,
+                minor: 0x2a,
+                inode: 131073
+            })
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn lock_file_device_is_read_from_mountinfo() {
+        let mountinfo = "\
+22 1 0:21 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+40 22 0:45 / /data rw,relatime shared:2 - btrfs /dev/sdb1 rw,subvol=/
+41 40 0:46 / /data/my\\040chains rw,relatime shared:3 - btrfs /dev/sdb1 rw,subvol=/chains
+50 22 0:52 / /overlay rw,relatime - overlay overlay rw,lowerdir=/lower,upperdir=/upper,workdir=/work
+";
+        let mount = find_mount(mountinfo, Path::new("/data/my chains/db/LOCK")).unwrap();
+        assert_eq!((mount.major, mount.minor), (0, 46));
+        assert_eq!(mount.mount_point, PathBuf::from("/data/my chains"));
+        assert_eq!(
+            find_mount(mountinfo, Path::new("/data/other/LOCK")).map(|m| m.minor),
+            Some(45)
+        );
+        assert_eq!(
+            find_mount(mountinfo, Path::new("/home/LOCK")).map(|m| m.minor),
+            Some(21)
+        );
+        assert_eq!(mount.upper_path(Path::new("/data/my chains/db/LOCK")), None);
+
+        let overlay = find_mount(mountinfo, Path::new("/overlay/db/LOCK")).unwrap();
+        assert_eq!(
+            overlay.upper_path(Path::new("/overlay/db/LOCK")),
+            Some(PathBuf::from("/upper/db/LOCK"))
+        );
+    }
 }
@@ -19,24 +19,28 @@
 //! to be used by `service-container-chain-rpc-provider` which can embed an orchestrator node too.
 
 pub mod parachain;
+pub mod session_keys;
 pub mod solochain;
 
 use {
-    dc_orchestrator_chain_interface::OrchestratorChainInterface,
+    crate::session_keys::SessionKeysManager,
     futures::StreamExt,
-    nimbus_primitives::NimbusPair,
     sp_core::{traits::SpawnEssentialNamed, H256},
     sp_keystore::KeystorePtr,
-    std::sync::Arc,
+    std::{collections::BTreeSet, sync::Arc},
+    tc_consensus::OrchestratorAssignmentInterface,
     tc_service_container_chain_spawner::spawner::CcSpawnMsg,
     tokio::sync::mpsc,
 };
 
 /// Background task used to detect changes to container chain assignment,
 /// and start/stop container chains on demand. The check runs on every new block.
+///
+/// The node is considered unassigned while `session_keys` does not allow collating.
 pub fn build_check_assigned_para_id(
-    client: Arc<dyn OrchestratorChainInterface>,
+    client: Arc<dyn OrchestratorAssignmentInterface>,
     sync_keystore: KeystorePtr,
+    session_keys: SessionKeysManager,
     cc_spawn_tx: mpsc::UnboundedSender<CcSpawnMsg>,
     spawner: impl SpawnEssentialNamed,
 ) {
@@ -52,6 +56,16 @@ pub fn build_check_assigned_para_id(
             let sync_keystore = sync_keystore.clone();
             let cc_spawn_tx = cc_spawn_tx.clone();
 
+            if !session_keys.collation_allowed() {
+                cc_spawn_tx
+                    .send(CcSpawnMsg::UpdateAssignment {
+                        current: BTreeSet::new(),
+                        next: BTreeSet::new(),
+                    })
+                    .unwrap();
+                continue;
+            }
+
             check_assigned_para_id(
                 cc_spawn_tx,
                 sync_keystore,
@@ -78,34 +92,30 @@ pub fn build_check_assigned_para_id(
 async fn check_assigned_para_id(
     cc_spawn_tx: mpsc::UnboundedSender<CcSpawnMsg>,
     sync_keystore: KeystorePtr,
-    client_set_aside_for_cidp: Arc<dyn OrchestratorChainInterface>,
+    client_set_aside_for_cidp: Arc<dyn OrchestratorAssignmentInterface>,
     block_hash: H256,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    // Check current assignment
-    let current_container_chain_para_id =
-        tc_consensus::first_eligible_key::<dyn OrchestratorChainInterface, NimbusPair>(
-            client_set_aside_for_cidp.as_ref(),
-            &block_hash,
-            sync_keystore.clone(),
-        )
-        .await
-        .map(|(_nimbus_key, para_id)| para_id);
+    // Check current assignment. A collator can be assigned to more than one parathread.
+    let current_container_chain_para_ids = tc_consensus::eligible_para_ids(
+        client_set_aside_for_cidp.as_ref(),
+        &block_hash,
+        sync_keystore.clone(),
+        false,
+    )
+    .await;
 
     // Check assignment in the next session
-    let next_container_chain_para_id = tc_consensus::first_eligible_key_next_session::<
-        dyn OrchestratorChainInterface,
-        NimbusPair,
-    >(
+    let next_container_chain_para_ids = tc_consensus::eligible_para_ids(
         client_set_aside_for_cidp.as_ref(),
         &block_hash,
         sync_keystore,
+        true,
     )
-    .await
-    .map(|(_nimbus_key, para_id)| para_id);
+    .await;
 
     cc_spawn_tx.send(CcSpawnMsg::UpdateAssignment {
-        current: current_container_chain_para_id,
-        next: next_container_chain_para_id,
+        current: current_container_chain_para_ids,
+        next: next_container_chain_para_ids,
     })?;
 
     Ok(())
@@ -20,6 +20,7 @@ use cumulus_client_bootnodes::{start_bootnode_tasks, StartBootnodeTasksParams};
 use node_common::service::node_builder::StartBootnodeParams;
 use sc_client_api::TrieCacheContext;
 use {
+    crate::session_keys::{SessionKeysManager, SessionKeysSubmitter},
     cumulus_client_cli::CollatorOptions,
     cumulus_client_collator::service::CollatorService,
     cumulus_client_consensus_proposer::Proposer,
@@ -60,20 +61,31 @@ use {
     sp_core::H256,
     sp_keystore::KeystorePtr,
     sp_state_machine::{Backend as StateBackend, StorageValue},
-    std::{marker::PhantomData, pin::Pin, sync::Arc, time::Duration},
+    std::{
+        collections::BTreeSet,
+        marker::PhantomData,
+        pin::Pin,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
     tc_consensus::{
         collators::lookahead::{
             self as lookahead_tanssi_aura, BuyCoreParams, Params as LookaheadTanssiAuraParams,
         },
-        OnDemandBlockProductionApi, OrchestratorAuraWorkerAuxData, TanssiAuthorityAssignmentApi,
+        AuthorityAssignmentApi, OnDemandBlockProductionApi, OrchestratorAssignmentInterface,
+        OrchestratorAuraWorkerAuxData, TanssiAuthorityAssignmentApi,
     },
     tc_service_container_chain_spawner::{
         cli::ContainerChainCli,
+        metrics::{lag_metrics_task, SpawnerMetrics},
         monitor,
         service::{
             ParachainBlockImport, ParachainClient, ParachainExecutor, ParachainProposerFactory,
         },
-        spawner::{self, CcSpawnMsg, ContainerChainSpawnParams, ContainerChainSpawner},
+        spawner::{
+            self, CcSpawnMsg, ContainerChainSpawnParams, ContainerChainSpawner,
+            ContainerChainSpawnerState,
+        },
     },
     tokio::sync::mpsc,
     tokio_util::sync::CancellationToken,
@@ -323,6 +335,8 @@ where
 
     // Channel to send messages to start/stop container chains
     let (cc_spawn_tx, cc_spawn_rx) = mpsc::unbounded_channel();
+    // Shared with the spawner admin RPC
+    let container_chain_spawner_state: Arc<Mutex<ContainerChainSpawnerState>> = Default::default();
 
     // Create a `NodeBuilder` which helps setup parachain nodes common systems.
     let mut node_builder = NodeConfig::new_builder(&parachain_config, hwbench.clone())?;
@@ -336,6 +350,18 @@ where
     let validator = parachain_config.role.is_authority();
     let force_authoring = parachain_config.force_authoring;
 
+    // Collators that can be assigned to container chains check their session keys
+    let session_keys = match &container_chain_config {
+        Some((container_chain_cli, _)) if validator => Some(SessionKeysManager::new(
+            node_builder.keystore_container.keystore(),
+            container_chain_cli.base.session_keys_controller.as_deref(),
+            container_chain_cli
+                .base
+                .refuse_collating_on_session_keys_mismatch,
+        )?),
+        _ => None,
+    };
+
     let node_builder = node_builder
         .build_cumulus_network::<_, Net>(
             &parachain_config,
@@ -348,6 +374,11 @@ where
     let rpc_builder = {
         let client = node_builder.client.clone();
         let transaction_pool = node_builder.transaction_pool.clone();
+        // Only available if the container chain spawner is running
+        let container_chain_spawner = container_chain_config
+            .is_some()
+            .then(|| (container_chain_spawner_state.clone(), cc_spawn_tx.clone()));
+        let session_keys = session_keys.clone();
 
         Box::new(move |_| {
             let deps = rpc::FullDeps {
@@ -357,6 +388,8 @@ where
                 xcm_senders: None,
                 randomness_sender: None,
                 container_chain_exclusion_sender: None,
+                container_chain_spawner: container_chain_spawner.clone(),
+                session_keys: session_keys.clone(),
             };
 
             rpc::create_full(deps).map_err(Into::into)
@@ -441,7 +474,9 @@ where
         sync_oracle: node_builder.network.sync_service.clone(),
         overseer_handle: overseer_handle.clone(),
     };
-    let orchestrator_chain_interface = orchestrator_chain_interface_builder.build();
+    let orchestrator_in_process_interface = orchestrator_chain_interface_builder.build();
+    let orchestrator_chain_interface: Arc<dyn OrchestratorChainInterface> =
+        orchestrator_in_process_interface.clone();
 
     if validator {
         let collator_key = collator_key
@@ -451,13 +486,23 @@ where
         // Start task which detects para id assignment, and starts/stops container chains.
         // Note that if this node was started without a `container_chain_config`, we don't
         // support collation on container chains, so there is no need to detect changes to assignment
-        if container_chain_config.is_some() {
+        if let Some(session_keys) = session_keys {
             crate::build_check_assigned_para_id(
-                orchestrator_chain_interface.clone(),
+                orchestrator_in_process_interface,
                 sync_keystore.clone(),
+                session_keys.clone(),
                 cc_spawn_tx.clone(),
                 node_builder.task_manager.spawn_essential_handle(),
             );
+
+            session_keys.spawn(
+                orchestrator_chain_interface.clone(),
+                Some(SessionKeysSubmitter {
+                    client: node_builder.client.clone(),
+                    tx_pool: node_builder.transaction_pool.clone(),
+                }),
+                node_builder.task_manager.spawn_essential_handle(),
+            );
         }
 
         let start_collation = {
@@ -515,8 +560,8 @@ where
                 // Spawn new container chain node
                 cc_spawn_tx
                     .send(CcSpawnMsg::UpdateAssignment {
-                        current: Some(container_chain_para_id.into()),
-                        next: Some(container_chain_para_id.into()),
+                        current: BTreeSet::from([container_chain_para_id.into()]),
+                        next: BTreeSet::from([container_chain_para_id.into()]),
                     })
                     .map_err(|e| sc_service::Error::Application(Box::new(e) as Box<_>))?;
             }
@@ -528,6 +573,12 @@ where
         let spawn_handle = node_builder.task_manager.spawn_handle();
         let relay_chain_interface = relay_chain_interface.clone();
         let orchestrator_chain_interface = orchestrator_chain_interface.clone();
+        let metrics = node_builder
+            .prometheus_registry
+            .as_ref()
+            .map(SpawnerMetrics::register)
+            .transpose()
+            .map_err(|e| sc_service::Error::Application(Box::new(e)))?;
 
         // This considers that the container chains have the same APIs as dancebox, which
         // is not the case. However the spawner don't call APIs that are not part of the expected
@@ -562,14 +613,19 @@ where
                     >::new(),
                 override_sync_mode: Some(sc_cli::SyncMode::Warp),
                 start_bootnode_params: start_bootnode_params.clone(),
+                metrics: metrics.clone(),
                 phantom: PhantomData,
             },
-            state: Default::default(),
+            state: container_chain_spawner_state,
             db_folder_cleanup_done: false,
             collate_on_tanssi,
             collation_cancellation_constructs: None,
         };
         let state = container_chain_spawner.state.clone();
+        let orchestrator_chain_interface = container_chain_spawner
+            .params
+            .orchestrator_chain_interface
+            .clone();
 
         node_builder.task_manager.spawn_essential_handle().spawn(
             "container-chain-spawner-rx-loop",
@@ -580,8 +636,16 @@ where
         node_builder.task_manager.spawn_essential_handle().spawn(
             "container-chain-spawner-debug-state",
             None,
-            monitor::monitor_task(state),
-        )
+            monitor::monitor_task(state.clone()),
+        );
+
+        if let Some(metrics) = metrics {
+            node_builder.task_manager.spawn_handle().spawn(
+                "container-chain-spawner-lag-metrics",
+                None,
+                lag_metrics_task(state, orchestrator_chain_interface, metrics),
+            );
+        }
     }
 
     Ok(ParachainNodeStarted {
@@ -636,7 +700,7 @@ struct OrchestratorChainInProcessInterfaceBuilder {
 }
 
 impl OrchestratorChainInProcessInterfaceBuilder {
-    pub fn build(self) -> Arc<dyn OrchestratorChainInterface> {
+    pub fn build(self) -> Arc<OrchestratorChainInProcessInterface<ParachainClient>> {
         Arc::new(OrchestratorChainInProcessInterface::new(
             self.client,
             self.backend,
@@ -841,3 +905,35 @@ where
         Ok(runtime_api.check_para_id_assignment_next_session(orchestrator_parent, authority)?)
     }
 }
+
+#[async_trait::async_trait]
+impl<Client> OrchestratorAssignmentInterface for OrchestratorChainInProcessInterface<Client>
+where
+    Client: ProvideRuntimeApi<Block>
+        + BlockchainEvents<Block>
+        + AuxStore
+        + UsageProvider<Block>
+        + Sync
+        + Send,
+    Client::Api: TanssiAuthorityAssignmentApi<Block, NimbusId>
+        + AuthorityAssignmentApi<Block, NimbusId, ParaId>
+        + OnDemandBlockProductionApi<Block, ParaId, Slot>
+        + RegistrarApi<Block, ParaId>
+        + AuthorNotingApi<Block, AccountId, BlockNumber, ParaId>
+        + DataPreserversApi<Block, DataPreserverProfileId, ParaId>,
+{
+    async fn para_ids_of_authority(
+        &self,
+        orchestrator_parent: PHash,
+        authority: NimbusId,
+        next_session: bool,
+    ) -> OrchestratorChainResult<Vec<ParaId>> {
+        let runtime_api = self.full_client.runtime_api();
+
+        if next_session {
+            Ok(runtime_api.para_ids_of_authority_next_session(orchestrator_parent, authority)?)
+        } else {
+            Ok(runtime_api.para_ids_of_authority(orchestrator_parent, authority)?)
+        }
+    }
+}
@@ -22,13 +22,15 @@
 #![warn(missing_docs)]
 
 use {
+    crate::session_keys::{SessionKeysManager, SessionKeysManagerApiServer, SessionKeysManagerRpc},
     cumulus_primitives_core::ParaId,
-    dancebox_runtime::{opaque::Block, AccountId, Index as Nonce},
+    dancebox_runtime::{opaque::Block, AccountId, BlockNumber, Index as Nonce},
     manual_container_chains_exclusion_rpc::{
         ManualContainerChainsExclusion, ManualContainerChainsExclusionApiServer,
     },
     manual_randomness_rpc::{ManualRandomness, ManualRandomnessApiServer},
     manual_xcm_rpc::{ManualXcm, ManualXcmApiServer},
+    pallet_author_noting_runtime_api::AuthorNotingApi,
     polkadot_primitives::Hash,
     sc_client_api::{AuxStore, UsageProvider},
     sc_consensus_manual_seal::{
@@ -42,8 +44,13 @@ use {
     sp_api::ProvideRuntimeApi,
     sp_block_builder::BlockBuilder,
     sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata},
-    std::sync::Arc,
+    std::sync::{Arc, Mutex},
     stream_payment_rpc::{StreamPayment, StreamPaymentApiServer as _, StreamPaymentRuntimeApi},
+    tc_service_container_chain_spawner::{
+        admin_rpc::{ContainerChainSpawnerAdmin, ContainerChainSpawnerAdminApiServer},
+        spawner::{CcSpawnMsg, ContainerChainSpawnerState},
+    },
+    tokio::sync::mpsc,
 };
 
 /// A type representing all RPC extensions.
@@ -63,6 +70,13 @@ pub struct FullDeps<C, P> {
     pub randomness_sender: Option<flume::Sender<(bool, Option<[u8; 32]>)>>,
     /// Channels for manually excluding container chains from producing blocks
     pub container_chain_exclusion_sender: Option<flume::Sender<Vec<ParaId>>>,
+    /// Container chain spawner state and control channel, used by the spawner admin RPC
+    pub container_chain_spawner: Option<(
+        Arc<Mutex<ContainerChainSpawnerState>>,
+        mpsc::UnboundedSender<CcSpawnMsg>,
+    )>,
+    /// Session keys manager, only running for collators
+    pub session_keys: Option<SessionKeysManager>,
 }
 
 /// Instantiate all RPC extensions.
@@ -82,6 +96,7 @@ where
     C::Api: BlockBuilder<Block>,
     C::Api: StreamPaymentRuntimeApi<Block, u64, u128, u128>,
     C::Api: ServicesPaymentRuntimeApi<Block, AccountId, u128, ParaId>,
+    C::Api: AuthorNotingApi<Block, AccountId, BlockNumber, ParaId>,
     P: TransactionPool + Sync + Send + 'static,
 {
     use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -94,11 +109,13 @@ where
         xcm_senders,
         randomness_sender,
         container_chain_exclusion_sender,
+        container_chain_spawner,
+        session_keys,
     } = deps;
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
     module.merge(StreamPayment::<_, Block>::new(client.clone()).into_rpc())?;
-    module.merge(ServicesPayment::<_, Block, AccountId>::new(client).into_rpc())?;
+    module.merge(ServicesPayment::<_, Block, AccountId>::new(client.clone()).into_rpc())?;
 
     if let Some(command_sink) = command_sink {
         module.merge(
@@ -136,5 +153,13 @@ where
         )?;
     }
 
+    if let Some((state, cc_spawn_tx)) = container_chain_spawner {
+        module.merge(ContainerChainSpawnerAdmin::new(client, state, cc_spawn_tx).into_rpc())?;
+    }
+
+    if let Some(session_keys) = session_keys {
+        module.merge(SessionKeysManagerRpc::new(session_keys).into_rpc())?;
+    }
+
     Ok(module)
 }
@@ -0,0 +1,518 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Session keys management for collators.
+//!
+//! A collator is only assigned to a chain if one of the Nimbus keys of its keystore has been
+//! registered on the orchestrator chain with `session.set_keys`. [SessionKeysManager] checks on
+//! every finalized orchestrator block that the local keys are registered, by the configured
+//! controller account if there is one, and warns loudly if they are not. It can also:
+//!
+//! * Generate a Nimbus key and submit `session.set_keys` signed by the controller account. This
+//!   needs the embedded orchestrator node of parachain collators, and only works for orchestrator
+//!   runtimes whose session keys are a single Nimbus key.
+//! * Stop collating until the local keys match the on-chain session keys, see
+//!   [SessionKeysManager::collation_allowed].
+//!
+//! The result of the last check is exposed by the `tanssi_sessionKeysStatus` RPC method.
+
+use {
+    dancebox_runtime::{
+        opaque::Block, AccountId, BlockNumber, Index as Nonce, Runtime, RuntimeCall, SessionKeys,
+        TxExtension, UncheckedExtrinsic,
+    },
+    dc_orchestrator_chain_interface::{OrchestratorChainInterface, OrchestratorChainResult},
+    futures::StreamExt,
+    jsonrpsee::{
+        core::{async_trait, RpcResult},
+        proc_macros::rpc,
+    },
+    nimbus_primitives::{NimbusId, NIMBUS_KEY_ID},
+    parity_scale_codec::{Decode, Encode},
+    sc_transaction_pool::TransactionPoolHandle,
+    sc_transaction_pool_api::TransactionPool,
+    serde::{Deserialize, Serialize},
+    sp_api::{Core, ProvideRuntimeApi},
+    sp_blockchain::HeaderBackend,
+    sp_core::{
+        hashing::{twox_128, twox_64},
+        sr25519,
+        traits::SpawnEssentialNamed,
+        ByteArray, Bytes, Pair, H256,
+    },
+    sp_keystore::{Keystore, KeystorePtr},
+    sp_runtime::{
+        generic::{Era, SignedPayload},
+        traits::IdentifyAccount,
+        transaction_validity::TransactionSource,
+        MultiSigner,
+    },
+    std::sync::{Arc, Mutex},
+    substrate_frame_rpc_system::AccountNonceApi,
+    tc_service_container_chain_spawner::service::ParachainClient,
+};
+
+const LOG_TARGET: &str = "session-keys";
+
+/// Submit `session.set_keys` again if the keys are still not registered after this number of
+/// orchestrator blocks, in case the previous transaction was dropped.
+const RESUBMIT_AFTER_BLOCKS: BlockNumber = 100;
+
+/// Result of comparing the local Nimbus keys with the session keys registered on chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionKeysState {
+    /// Not checked yet, the node waits for the first finalized orchestrator block
+    Unknown,
+    /// The local keys are registered on chain, by the controller account if one is configured
+    Registered,
+    /// The keystore has no Nimbus keys
+    MissingLocalKeys,
+    /// None of the local Nimbus keys are registered on chain
+    NotRegistered,
+    /// Some local keys are registered by an account other than the configured controller
+    WrongOwner,
+}
+
+/// Nimbus key of the local keystore.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalSessionKey {
+    pub public_key: Bytes,
+    /// Account that registered this key with `session.set_keys`, if any
+    pub owner: Option<AccountId>,
+}
+
+/// Response of `tanssi_sessionKeysStatus`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionKeysStatus {
+    pub state: SessionKeysState,
+    /// Orchestrator block used for the last check
+    pub checked_at: Option<H256>,
+    pub local_keys: Vec<LocalSessionKey>,
+    /// Account used to submit `session.set_keys`, if configured
+    pub controller: Option<AccountId>,
+    /// Whether this node collates on the chains it is assigned to
+    pub collation_allowed: bool,
+    /// Orchestrator block number of the last `session.set_keys` submitted by this node
+    pub keys_submitted_at: Option<BlockNumber>,
+}
+
+/// Storage key of `Session::KeyOwner` for a Nimbus key. The session pallet has the same name in
+/// all the orchestrator runtimes, so this works for parachain and solochain collators.
+fn key_owner_storage_key(key: &NimbusId) -> Vec<u8> {
+    let encoded_key = (NIMBUS_KEY_ID, key.to_raw_vec()).encode();
+
+    [
+        twox_128(b"Session").as_slice(),
+        &twox_128(b"KeyOwner"),
+        &twox_64(&encoded_key),
+        &encoded_key,
+    ]
+    .concat()
+}
+
+fn session_keys_state(
+    local_keys: &[LocalSessionKey],
+    controller: Option<&AccountId>,
+) -> SessionKeysState {
+    let mut owners = local_keys
+        .iter()
+        .filter_map(|key| key.owner.as_ref())
+        .peekable();
+
+    if local_keys.is_empty() {
+        SessionKeysState::MissingLocalKeys
+    } else if owners.peek().is_none() {
+        SessionKeysState::NotRegistered
+    } else if controller.is_some_and(|controller| owners.any(|owner| owner != controller)) {
+        SessionKeysState::WrongOwner
+    } else {
+        SessionKeysState::Registered
+    }
+}
+
+/// Checks that the session keys registered on chain match the local keystore. Cloning it
+/// returns a handle to the same status.
+#[derive(Clone)]
+pub struct SessionKeysManager {
+    keystore: KeystorePtr,
+    controller: Option<sr25519::Pair>,
+    refuse_collating_on_mismatch: bool,
+    status: Arc<Mutex<SessionKeysStatus>>,
+}
+
+impl SessionKeysManager {
+    /// `controller_suri` is the secret URI of the account used to submit `session.set_keys`.
+    pub fn new(
+        keystore: KeystorePtr,
+        controller_suri: Option<&str>,
+        refuse_collating_on_mismatch: bool,
+    ) -> Result<Self, String> {
+        let controller = controller_suri
+            .map(|suri| {
+                sr25519::Pair::from_string(suri, None)
+                    .map_err(|e| format!("Invalid session keys controller: {:?}", e))
+            })
+            .transpose()?;
+        let controller_account = controller
+            .as_ref()
+            .map(|pair| MultiSigner::from(pair.public()).into_account());
+
+        if keystore.sr25519_public_keys(NIMBUS_KEY_ID).is_empty() && controller.is_none() {
+            log::warn!(
+                target: LOG_TARGET,
+                "🔏 No Nimbus keys in the keystore. This collator will not be assigned to any chain until a key is inserted with `author_rotateKeys` and registered with `session.set_keys`"
+            );
+        }
+
+        Ok(Self {
+            keystore,
+            controller,
+            refuse_collating_on_mismatch,
+            status: Arc::new(Mutex::new(SessionKeysStatus {
+                state: SessionKeysState::Unknown,
+                checked_at: None,
+                local_keys: vec![],
+                controller: controller_account,
+                collation_allowed: !refuse_collating_on_mismatch,
+                keys_submitted_at: None,
+            })),
+        })
+    }
+
+    pub fn status(&self) -> SessionKeysStatus {
+        self.status.lock().expect("poison error").clone()
+    }
+
+    /// Whether this node should collate on the chains it is assigned to. Always true unless the
+    /// node was started with `--refuse-collating-on-session-keys-mismatch`.
+    pub fn collation_allowed(&self) -> bool {
+        self.status.lock().expect("poison error").collation_allowed
+    }
+
+    /// Compare the local Nimbus keys with `Session::KeyOwner` at `block_hash`, and update the
+    /// status. Logs a warning when the state changes to a mismatch.
+    pub async fn check(
+        &self,
+        orchestrator: &dyn OrchestratorChainInterface,
+        block_hash: H256,
+    ) -> OrchestratorChainResult<SessionKeysState> {
+        let mut local_keys = vec![];
+        for public in self.keystore.sr25519_public_keys(NIMBUS_KEY_ID) {
+            let key = NimbusId::from(public);
+            let owner = orchestrator
+                .get_storage_by_key(block_hash, &key_owner_storage_key(&key))
+                .await?
+                .and_then(|owner| AccountId::decode(&mut &owner[..]).ok());
+
+            local_keys.push(LocalSessionKey {
+                public_key: key.to_raw_vec().into(),
+                owner,
+            });
+        }
+
+        let mut status = self.status.lock().expect("poison error");
+        let state = session_keys_state(&local_keys, status.controller.as_ref());
+        let previous_state = status.state;
+
+        status.state = state;
+        status.checked_at = Some(block_hash);
+        status.local_keys = local_keys;
+        status.collation_allowed =
+            !self.refuse_collating_on_mismatch || state == SessionKeysState::Registered;
+
+        if state != previous_state {
+            let consequence = if status.collation_allowed {
+                ""
+            } else {
+                " Collation is disabled until this is fixed."
+            };
+
+            match state {
+                SessionKeysState::Unknown => {}
+                SessionKeysState::Registered => log::info!(
+                    target: LOG_TARGET,
+                    "🔑 Local Nimbus keys are registered on the orchestrator chain"
+                ),
+                SessionKeysState::MissingLocalKeys => log::warn!(
+                    target: LOG_TARGET,
+                    "🔏 No Nimbus keys in the keystore, this collator will not be assigned to any chain.{}",
+                    consequence
+                ),
+                SessionKeysState::NotRegistered => log::warn!(
+                    target: LOG_TARGET,
+                    "🔏 None of the local Nimbus keys are registered with `session.set_keys`, this collator will not be assigned to any chain.{}",
+                    consequence
+                ),
+                SessionKeysState::WrongOwner => log::warn!(
+                    target: LOG_TARGET,
+                    "🔏 Local Nimbus keys are registered by an account other than the session keys controller {:?}.{}",
+                    status.controller,
+                    consequence
+                ),
+            }
+        }
+
+        Ok(state)
+    }
+
+    /// Spawn a task that checks the session keys on every finalized orchestrator block. If a
+    /// controller is configured and `submitter` is set, missing keys are generated and registered.
+    pub fn spawn(
+        self,
+        orchestrator: Arc<dyn OrchestratorChainInterface>,
+        submitter: Option<SessionKeysSubmitter>,
+        spawner: impl SpawnEssentialNamed,
+    ) {
+        if self.controller.is_some() && submitter.is_none() {
+            log::warn!(
+                target: LOG_TARGET,
+                "Submitting session keys is only supported by parachain collators, the session keys controller is only used to check the key owner"
+            );
+        }
+
+        let session_keys_task = async move {
+            let mut finality_notifications =
+                orchestrator.finality_notification_stream().await.unwrap();
+
+            while let Some(header) = finality_notifications.next().await {
+                let state = match self.check(orchestrator.as_ref(), header.hash()).await {
+                    Ok(state) => state,
+                    Err(e) => {
+                        log::warn!(target: LOG_TARGET, "Failed to check session keys: {}", e);
+                        continue;
+                    }
+                };
+
+                if matches!(
+                    state,
+                    SessionKeysState::MissingLocalKeys | SessionKeysState::NotRegistered
+                ) {
+                    if let (Some(controller), Some(submitter)) = (&self.controller, &submitter) {
+                        self.submit_keys(controller, submitter, header.number).await;
+                    }
+                }
+            }
+        };
+
+        spawner.spawn_essential("session-keys-manager", None, Box::pin(session_keys_task));
+    }
+
+    /// Register the first local Nimbus key on chain, generating one if the keystore has none.
+    async fn submit_keys(
+        &self,
+        controller: &sr25519::Pair,
+        submitter: &SessionKeysSubmitter,
+        block_number: BlockNumber,
+    ) {
+        let keys_submitted_at = self.status.lock().expect("poison error").keys_submitted_at;
+        if keys_submitted_at
+            .is_some_and(|submitted_at| block_number < submitted_at + RESUBMIT_AFTER_BLOCKS)
+        {
+            return;
+        }
+
+        let public = match self.keystore.sr25519_public_keys(NIMBUS_KEY_ID).first() {
+            Some(public) => *public,
+            None => match self.keystore.sr25519_generate_new(NIMBUS_KEY_ID, None) {
+                Ok(public) => {
+                    log::info!(target: LOG_TARGET, "🔑 Generated Nimbus key {}", public);
+                    public
+                }
+                Err(e) => {
+                    log::error!(target: LOG_TARGET, "Failed to generate Nimbus key: {}", e);
+                    return;
+                }
+            },
+        };
+
+        match submitter.submit_set_keys(controller, public.into()).await {
+            Ok(tx_hash) => {
+                log::info!(
+                    target: LOG_TARGET,
+                    "🔑 Submitted `session.set_keys` for Nimbus key {}: {:?}",
+                    public,
+                    tx_hash
+                );
+                self.status.lock().expect("poison error").keys_submitted_at = Some(block_number);
+            }
+            Err(e) => {
+                log::error!(target: LOG_TARGET, "Failed to submit `session.set_keys`: {}", e);
+            }
+        }
+    }
+}
+
+/// Embedded orchestrator node used to submit `session.set_keys`.
+pub struct SessionKeysSubmitter {
+    pub client: Arc<ParachainClient>,
+    pub tx_pool: Arc<TransactionPoolHandle<Block, ParachainClient>>,
+}
+
+impl SessionKeysSubmitter {
+    /// Submit `session.set_keys` signed by `controller` to the orchestrator transaction pool.
+    async fn submit_set_keys(
+        &self,
+        controller: &sr25519::Pair,
+        nimbus: NimbusId,
+    ) -> Result<H256, String> {
+        let best_hash = self.client.info().best_hash;
+        let extrinsic = self.set_keys_extrinsic(best_hash, controller, nimbus)?;
+
+        self.tx_pool
+            .submit_one(best_hash, TransactionSource::Local, extrinsic.into())
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Build a signed `session.set_keys` extrinsic, valid on top of `best_hash`.
+    fn set_keys_extrinsic(
+        &self,
+        best_hash: H256,
+        controller: &sr25519::Pair,
+        nimbus: NimbusId,
+    ) -> Result<UncheckedExtrinsic, String> {
+        let genesis_hash = self.client.info().genesis_hash;
+        let account = MultiSigner::from(controller.public()).into_account();
+        let runtime_api = self.client.runtime_api();
+        let version = runtime_api.version(best_hash).map_err(|e| e.to_string())?;
+        let nonce: Nonce = runtime_api
+            .account_nonce(best_hash, account.clone())
+            .map_err(|e| e.to_string())?;
+
+        let call = RuntimeCall::Session(pallet_session::Call::set_keys {
+            keys: SessionKeys { nimbus },
+            proof: vec![],
+        });
+        let tx_ext: TxExtension = (
+            frame_system::CheckNonZeroSender::<Runtime>::new(),
+            frame_system::CheckSpecVersion::<Runtime>::new(),
+            frame_system::CheckTxVersion::<Runtime>::new(),
+            frame_system::CheckGenesis::<Runtime>::new(),
+            frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+            frame_system::CheckNonce::<Runtime>::from(nonce),
+            frame_system::CheckWeight::<Runtime>::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+            frame_metadata_hash_extension::CheckMetadataHash::<Runtime>::new(false),
+        )
+            .into();
+        // The implicit data is read from the runtime storage when signing inside the runtime, so
+        // it has to be provided here
+        let implicit = (
+            (),
+            version.spec_version,
+            version.transaction_version,
+            genesis_hash,
+            genesis_hash,
+            (),
+            (),
+            (),
+            None,
+        );
+
+        let payload = SignedPayload::from_raw(call, tx_ext, implicit);
+        let signature = payload.using_encoded(|payload| controller.sign(payload));
+        let (call, tx_ext, _) = payload.deconstruct();
+
+        Ok(UncheckedExtrinsic::new_signed(
+            call,
+            account.into(),
+            signature.into(),
+            tx_ext,
+        ))
+    }
+}
+
+/// Session keys manager RPC.
+#[rpc(server)]
+pub trait SessionKeysManagerApi {
+    /// Result of the last comparison between the local Nimbus keys and the session keys
+    /// registered on the orchestrator chain.
+    #[method(name = "tanssi_sessionKeysStatus")]
+    async fn status(&self) -> RpcResult<SessionKeysStatus>;
+}
+
+/// Implementation of [SessionKeysManagerApiServer].
+pub struct SessionKeysManagerRpc {
+    manager: SessionKeysManager,
+}
+
+impl SessionKeysManagerRpc {
+    pub fn new(manager: SessionKeysManager) -> Self {
+        Self { manager }
+    }
+}
+
+#[async_trait]
+impl SessionKeysManagerApiServer for SessionKeysManagerRpc {
+    async fn status(&self) -> RpcResult<SessionKeysStatus> {
+        Ok(self.manager.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, sp_keyring::Sr25519Keyring};
+
+    fn local_key(owner: Option<AccountId>) -> LocalSessionKey {
+        LocalSessionKey {
+            public_key: vec![1; 32].into(),
+            owner,
+        }
+    }
+
+    #[test]
+    fn key_owner_storage_key_matches_session_pallet() {
+        let key = NimbusId::from(Sr25519Keyring::Alice.public());
+
+        assert_eq!(
+            key_owner_storage_key(&key),
+            pallet_session::KeyOwner::<Runtime>::hashed_key_for((NIMBUS_KEY_ID, key.to_raw_vec()))
+        );
+    }
+
+    #[test]
+    fn session_keys_state_compares_owner_with_controller() {
+        let alice = Sr25519Keyring::Alice.to_account_id();
+        let bob = Sr25519Keyring::Bob.to_account_id();
+
+        assert_eq!(
+            session_keys_state(&[], Some(&alice)),
+            SessionKeysState::MissingLocalKeys
+        );
+        assert_eq!(
+            session_keys_state(&[local_key(None)], None),
+            SessionKeysState::NotRegistered
+        );
+        assert_eq!(
+            session_keys_state(&[local_key(None), local_key(Some(bob.clone()))], None),
+            SessionKeysState::Registered
+        );
+        assert_eq!(
+            session_keys_state(&[local_key(Some(alice.clone()))], Some(&alice)),
+            SessionKeysState::Registered
+        );
+        assert_eq!(
+            session_keys_state(
+                &[local_key(Some(alice.clone())), local_key(Some(bob))],
+                Some(&alice)
+            ),
+            SessionKeysState::WrongOwner
+        );
+    }
+}
@@ -18,13 +18,17 @@ use node_common::service::node_builder::StartBootnodeParams;
 use polkadot_primitives::CollatorPair;
 use sc_sysinfo::HwBench;
 use {
+    crate::session_keys::SessionKeysManager,
     cumulus_client_cli::CollatorOptions,
     futures::FutureExt,
     log::{info, warn},
     node_common::{
         cli::RelayChainCli, service::solochain::RelayAsOrchestratorChainInterfaceBuilder,
     },
-    sc_cli::{CliConfiguration, DefaultConfigurationValues, LoggerBuilder, Signals, SubstrateCli},
+    sc_cli::{
+        CliConfiguration, Database, DefaultConfigurationValues, LoggerBuilder, Signals,
+        SubstrateCli,
+    },
     sc_service::{
         config::{ExecutorConfiguration, KeystoreConfig, NetworkConfiguration, TransportConfig},
         BasePath, BlocksPruning, ChainType, Configuration, DatabaseSource, GenericChainSpec,
@@ -32,6 +36,7 @@ use {
     },
     sp_keystore::KeystorePtr,
     std::{
+        collections::BTreeSet,
         future::Future,
         marker::PhantomData,
         num::NonZeroUsize,
@@ -218,7 +223,9 @@ pub async fn start_solochain_node(
         overseer_handle: overseer_handle.clone(),
         relay_chain_interface: relay_chain_interface.clone(),
     };
-    let orchestrator_chain_interface = orchestrator_chain_interface_builder.build();
+    let relay_as_orchestrator_interface = orchestrator_chain_interface_builder.build();
+    let orchestrator_chain_interface: Arc<dyn OrchestratorChainInterface> =
+        relay_as_orchestrator_interface.clone();
     // Channel to send messages to start/stop container chains
     let (cc_spawn_tx, cc_spawn_rx) = unbounded_channel();
 
@@ -227,13 +234,31 @@ pub async fn start_solochain_node(
             panic!("cannot be a validator if container chain spawner is disabled");
         }
 
+        let session_keys = SessionKeysManager::new(
+            sync_keystore.clone(),
+            container_chain_cli.base.session_keys_controller.as_deref(),
+            container_chain_cli
+                .base
+                .refuse_collating_on_session_keys_mismatch,
+        )?;
+
         // Start task which detects para id assignment, and starts/stops container chains.
         crate::build_check_assigned_para_id(
-            orchestrator_chain_interface.clone(),
+            relay_as_orchestrator_interface,
             sync_keystore.clone(),
+            session_keys.clone(),
             cc_spawn_tx.clone(),
             task_manager.spawn_essential_handle(),
         );
+
+        // There is no embedded orchestrator node to submit the session keys, so they are only
+        // checked. The status is not exposed by RPC either, this node only serves container
+        // chain RPCs.
+        session_keys.spawn(
+            orchestrator_chain_interface.clone(),
+            None,
+            task_manager.spawn_essential_handle(),
+        );
     }
 
     // If the orchestrator chain is running as a full-node, we start a full node for the
@@ -244,8 +269,8 @@ pub async fn start_solochain_node(
             // Spawn new container chain node
             cc_spawn_tx
                 .send(CcSpawnMsg::UpdateAssignment {
-                    current: Some(container_chain_para_id.into()),
-                    next: Some(container_chain_para_id.into()),
+                    current: BTreeSet::from([container_chain_para_id.into()]),
+                    next: BTreeSet::from([container_chain_para_id.into()]),
                 })
                 .map_err(|e| sc_service::Error::Application(Box::new(e) as Box<_>))?;
         }
@@ -288,6 +313,8 @@ pub async fn start_solochain_node(
                 override_sync_mode: Some(sc_cli::SyncMode::Warp),
                 phantom: PhantomData,
                 start_bootnode_params: start_bootnode_params.clone(),
+                // No metrics so no prometheus registry
+                metrics: None,
             },
             state: Default::default(),
             db_folder_cleanup_done: false,
@@ -325,6 +352,8 @@ pub struct SolochainConfig {
     pub network_node_name: String,
     pub role: Role,
     pub relay_chain: String,
+    /// Database backend of the container chains
+    pub database: Database,
 }
 
 /// Alternative to [Runner](sc_cli::Runner) struct used in solochain context.
@@ -365,9 +394,8 @@ impl SolochainRunner {
         info!("🏷  Node name: {}", self.config.network_node_name);
         info!("👤 Role: {}", self.config.role);
         info!(
-            "💾 Database: {} at {}",
-            // Container chains only support paritydb
-            "ParityDb",
+            "💾 Database: {:?} at {}",
+            self.config.database,
             // Print base path instead of db path because each container will have its own db in a
             // different subdirectory.
             self.config.base_path.path().display(),
@@ -451,6 +479,11 @@ pub fn create_runner<T: SubstrateCli + CliConfiguration<DVC>, DVC: DefaultConfig
     let network_node_name = command.node_name()?;
     let is_dev = command.is_dev()?;
     let role = command.role(is_dev)?;
+    // Container chains only support paritydb and rocksdb, the spawner uses paritydb by default
+    let database = match command.database()? {
+        Some(Database::RocksDb) => Database::RocksDb,
+        _ => Database::ParityDb,
+    };
     // This relay chain id is only used when the relay chain args have no `--chain` value
     // TODO: check if this works with an external relay rpc / light client
     let relay_chain_id = "dancelight_local_testnet".to_string();
@@ -461,6 +494,7 @@ pub fn create_runner<T: SubstrateCli + CliConfiguration<DVC>, DVC: DefaultConfig
         network_node_name,
         role,
         relay_chain: relay_chain_id,
+        database,
     };
 
     Ok(SolochainRunner {
@@ -17,6 +17,12 @@
 //! Runtime API for Author Noting pallet
 
 #![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+use {
+    alloc::boxed::Box, sp_runtime::traits::Block as BlockT,
+    tp_traits::ContainerChainEquivocationProof,
+};
 
 sp_api::decl_runtime_apis! {
     pub trait AuthorNotingApi<AccountId, BlockNumber, ParaId>
@@ -28,4 +34,10 @@ sp_api::decl_runtime_apis! {
         fn latest_block_number(para_id: ParaId) -> Option<BlockNumber>;
         fn latest_author(para_id: ParaId) -> Option<AccountId>;
     }
+
+    pub trait ContainerChainEquivocationApi {
+        /// Unsigned extrinsic reporting a container chain collator that sealed two different
+        /// blocks for the same slot.
+        fn create_equivocation_report_unsigned_extrinsic(equivocation_proof: ContainerChainEquivocationProof) -> Box<<Block as BlockT>::Extrinsic>;
+    }
 }
@@ -44,15 +44,21 @@
 //! This is verified in test `inherent_weight_is_base_plus_hooks`.
 
 use {
-    crate::{AuthorNotingInfo, Call, Config, HeadData, Pallet, ParaId, RelayOrPara},
+    crate::{
+        equivocation::seal_header, AuthorNotingInfo, Call, Config, HeadData, Pallet, ParaId,
+        RelayOrPara,
+    },
     alloc::{boxed::Box, vec},
     core::any::{Any, TypeId},
     frame_benchmarking::{account, v2::*},
     frame_support::{assert_ok, Hashable},
     frame_system::RawOrigin,
+    nimbus_primitives::NimbusId,
     parity_scale_codec::Encode,
+    sp_runtime::RuntimeAppPublic,
     tp_traits::{
-        AuthorNotingHook, ForSession, GetContainerChainAuthor, GetContainerChainsWithCollators,
+        AuthorNotingHook, ContainerChainEquivocationProof, ContainerChainHeader, ForSession,
+        GetCollatorAccount, GetContainerChainAuthor, GetContainerChainsWithCollators,
     },
 };
 
@@ -267,5 +273,44 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn report_container_chain_equivocation() -> Result<(), BenchmarkError> {
+        // Start at index 2000 because para_id < 2000 are system parachains
+        let para_id: ParaId = 2000.into();
+        let slot = 13u64.into();
+        let offender: T::AccountId = account("account id", 0u32, 0u32);
+        let author = NimbusId::generate_pair(None);
+
+        T::CollatorAccount::set_collator_account(author.clone(), offender.clone());
+        // Use the max allowed value for num_each_container_chain
+        let collators = vec![offender; 2];
+        T::ContainerChainAuthor::set_authors_for_para_id(para_id, collators.clone());
+        T::ContainerChains::set_container_chains_with_collators(
+            ForSession::Current,
+            &[(para_id, collators)],
+        );
+
+        let header = |number| ContainerChainHeader {
+            parent_hash: Default::default(),
+            number,
+            state_root: Default::default(),
+            extrinsics_root: Default::default(),
+            digest: Default::default(),
+        };
+        // Both headers extend a noted head of the para
+        Pallet::<T>::note_recent_head(para_id, Default::default());
+        let proof = ContainerChainEquivocationProof {
+            para_id,
+            slot,
+            first_header: seal_header(header(1), slot, &author),
+            second_header: seal_header(header(2), slot, &author),
+        };
+
+        #[extrinsic_call]
+        _(RawOrigin::None, Box::new(proof));
+
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,101 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Verification of container chain equivocation proofs.
+//!
+//! Container chain blocks are sealed by their author: the last digest item is a Nimbus signature
+//! of the hash of the header without that seal, and the header includes an Aura pre-runtime
+//! digest with the slot and a Nimbus pre-runtime digest with the author key.
+
+use {
+    nimbus_primitives::{CompatibleDigestItem, NimbusId},
+    parity_scale_codec::Decode,
+    sp_consensus_aura::{Slot, AURA_ENGINE_ID},
+    sp_core::H256,
+    sp_runtime::{traits::Header as HeaderT, RuntimeAppPublic},
+    tp_traits::{ContainerChainEquivocationProof, ContainerChainHeader},
+};
+
+/// Pre-seal hash, slot and author of a sealed container chain header. Returns `None` if any of
+/// them is missing or the seal is not a valid signature of the author.
+pub fn sealed_header_info(header: &ContainerChainHeader) -> Option<(H256, Slot, NimbusId)> {
+    let mut pre_header = header.clone();
+    let signature = pre_header.digest_mut().pop()?.as_nimbus_seal()?;
+
+    let mut slot = None;
+    let mut author = None;
+    for log in pre_header.digest().logs() {
+        match log.as_pre_runtime() {
+            Some((AURA_ENGINE_ID, mut data)) if slot.is_none() => {
+                slot = Slot::decode(&mut data).ok();
+            }
+            _ => {
+                if author.is_none() {
+                    author = log.as_nimbus_pre_digest();
+                }
+            }
+        }
+    }
+    let (slot, author) = (slot?, author?);
+
+    let pre_hash = pre_header.hash();
+    author
+        .verify(&pre_hash, &signature)
+        .then_some((pre_hash, slot, author))
+}
+
+/// Check that both headers of `proof` are different blocks sealed by the same author for the
+/// slot of the proof, and return that author.
+pub fn check_equivocation_proof(proof: &ContainerChainEquivocationProof) -> Option<NimbusId> {
+    let (first_hash, first_slot, first_author) = sealed_header_info(&proof.first_header)?;
+    let (second_hash, second_slot, second_author) = sealed_header_info(&proof.second_header)?;
+
+    // Headers with the same pre-seal hash are the same block, even if the signatures differ
+    let is_equivocation = first_hash != second_hash
+        && first_slot == proof.slot
+        && second_slot == proof.slot
+        && first_author == second_author;
+
+    is_equivocation.then_some(first_author)
+}
+
+/// Add the slot and author digests to `header` and seal it. The author key must be in the
+/// keystore.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub fn seal_header(
+    mut header: ContainerChainHeader,
+    slot: Slot,
+    author: &NimbusId,
+) -> ContainerChainHeader {
+    use {parity_scale_codec::Encode, sp_runtime::DigestItem};
+
+    header
+        .digest_mut()
+        .push(DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode()));
+    header
+        .digest_mut()
+        .push(<DigestItem as CompatibleDigestItem>::nimbus_pre_digest(
+            author.clone(),
+        ));
+    let signature = author
+        .sign(&header.hash())
+        .expect("author key must be in the keystore");
+    header
+        .digest_mut()
+        .push(<DigestItem as CompatibleDigestItem>::nimbus_seal(signature));
+
+    header
+}
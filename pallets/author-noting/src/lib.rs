@@ -32,8 +32,7 @@
 extern crate alloc;
 
 use {
-    alloc::borrow::Cow,
-    alloc::vec::Vec,
+    alloc::{borrow::Cow, boxed::Box, vec::Vec},
     cumulus_pallet_parachain_system::RelaychainStateProvider,
     cumulus_primitives_core::{
         relay_chain::{BlakeTwo256, BlockNumber, HeadData},
@@ -42,15 +41,19 @@ use {
     dp_core::well_known_keys::PARAS_HEADS_INDEX,
     frame_support::{dispatch::PostDispatchInfo, pallet_prelude::*, Hashable},
     frame_system::pallet_prelude::*,
-    nimbus_primitives::SlotBeacon,
+    nimbus_primitives::{NimbusId, SlotBeacon},
     parity_scale_codec::{Decode, Encode},
     sp_consensus_aura::{inherents::InherentType, Slot, AURA_ENGINE_ID},
+    sp_core::H256,
     sp_inherents::{InherentIdentifier, IsFatalError},
     sp_runtime::{traits::Header, DigestItem, DispatchResult},
+    sp_staking::{offence::ReportOffence, SessionIndex},
     tp_author_noting_inherent::INHERENT_IDENTIFIER,
     tp_traits::{
-        AuthorNotingHook, AuthorNotingInfo, ContainerChainBlockInfo, ForSession, GenericStateProof,
-        GenericStorageReader, GetContainerChainAuthor, GetContainerChainsWithCollators,
+        AuthorNotingHook, AuthorNotingInfo, ContainerChainBlockInfo,
+        ContainerChainEquivocationOffence, ContainerChainEquivocationProof, ContainerChainHeader,
+        ForSession, GenericStateProof, GenericStorageReader, GetCollatorAccount,
+        GetContainerChainAuthor, GetContainerChainsWithCollators, GetSessionIndex,
         LatestAuthorInfoFetcher, NativeStorageReader, ReadEntryErr,
     },
 };
@@ -58,6 +61,7 @@ use {
 #[cfg(test)]
 mod mock;
 
+pub mod equivocation;
 #[cfg(test)]
 mod tests;
 pub mod weights;
@@ -93,6 +97,20 @@ pub mod pallet {
         #[pallet::constant]
         type MaxContainerChains: Get<u32>;
 
+        /// Account of the collator that owns the Nimbus key sealing a container chain block.
+        type CollatorAccount: GetCollatorAccount<NimbusId, Self::AccountId>;
+
+        type CurrentSessionIndex: GetSessionIndex<u32>;
+
+        /// Receives the equivocations of container chain collators, so that they can be slashed.
+        /// Duplicate reports are already rejected by this pallet, so `is_known_offence` is never
+        /// called.
+        type EquivocationReporter: ReportOffence<
+            Self::AccountId,
+            Self::AccountId,
+            ContainerChainEquivocationOffence<Self::AccountId>,
+        >;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -107,6 +125,10 @@ pub mod pallet {
         NonDecodableSlot,
         AuthorNotFound,
         NonAuraDigest,
+        /// The equivocation proof is invalid, or its author is not assigned to the slot.
+        InvalidEquivocationProof,
+        /// The equivocation was already reported.
+        DuplicateOffenceReport,
     }
 
     #[pallet::pallet]
@@ -186,7 +208,7 @@ pub mod pallet {
                         para_id,
                         parent_tanssi_slot,
                     ) {
-                        Ok(block_info) => {
+                        Ok((block_info, head_hash)) => {
                             let _ = LatestAuthor::<T>::try_mutate(
                                 para_id,
                                 |maybe_old_block_info: &mut Option<
@@ -209,6 +231,7 @@ pub mod pallet {
                                         };
                                         infos.push(info);
                                         *maybe_old_block_info = Some(bi);
+                                        Self::note_recent_head(para_id, head_hash);
                                         Ok(())
                                     } else {
                                         // No need to mutate value if block number didn't change
@@ -280,9 +303,47 @@ pub mod pallet {
         pub fn kill_author_data(origin: OriginFor<T>, para_id: ParaId) -> DispatchResult {
             ensure_root(origin)?;
             LatestAuthor::<T>::remove(para_id);
+            RecentHeads::<T>::remove(para_id);
             Self::deposit_event(Event::RemovedAuthorData { para_id });
             Ok(())
         }
+
+        /// Report a collator that sealed two different blocks of a container chain for the same
+        /// slot. The author of the slot is computed using the current collator assignment, so
+        /// reports are rejected once the chain is assigned to other collators. Each header must
+        /// be, or be a child of, one of the `RecentHeads` of the para.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::report_container_chain_equivocation())]
+        pub fn report_container_chain_equivocation(
+            origin: OriginFor<T>,
+            equivocation_proof: Box<ContainerChainEquivocationProof>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+
+            let offence = Self::equivocation_offence(&equivocation_proof)?;
+            let (offence_session_index, para_id, slot, offender) = (
+                offence.session_index,
+                offence.para_id,
+                offence.slot,
+                offence.offender.clone(),
+            );
+
+            T::EquivocationReporter::report_offence(Vec::new(), offence)
+                .map_err(|_| Error::<T>::DuplicateOffenceReport)?;
+            ReportedEquivocations::<T>::insert(
+                offence_session_index,
+                (para_id, slot),
+                offender.clone(),
+            );
+
+            Self::deposit_event(Event::ContainerChainEquivocationReported {
+                para_id,
+                slot,
+                offender,
+            });
+
+            Ok(Pays::No.into())
+        }
     }
 
     #[pallet::event]
@@ -297,12 +358,43 @@ pub mod pallet {
         },
         /// Removed author data
         RemovedAuthorData { para_id: ParaId },
+        /// A collator sealed two different blocks of a container chain for the same slot
+        ContainerChainEquivocationReported {
+            para_id: ParaId,
+            slot: Slot,
+            offender: T::AccountId,
+        },
     }
 
     #[pallet::storage]
     pub(super) type LatestAuthor<T: Config> =
         StorageMap<_, Blake2_128Concat, ParaId, ContainerChainBlockInfo<T::AccountId>, OptionQuery>;
 
+    /// Collators already reported for sealing two different blocks of a container chain in the
+    /// same slot, by session of the report. Used to reject duplicate reports, entries are removed
+    /// once no proof for their slot can be bound to a recent head anymore.
+    #[pallet::storage]
+    pub type ReportedEquivocations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SessionIndex,
+        Twox64Concat,
+        (ParaId, Slot),
+        T::AccountId,
+        OptionQuery,
+    >;
+
+    /// Hashes of the last heads noted for each container chain, with the session in which they
+    /// were noted. Equivocation proofs are bound to their para through these heads.
+    #[pallet::storage]
+    pub type RecentHeads<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        ParaId,
+        BoundedVec<(H256, SessionIndex), ConstU32<MAX_RECENT_HEADS>>,
+        ValueQuery,
+    >;
+
     /// Was the containerAuthorData set?
     #[pallet::storage]
     pub type DidSetContainerAuthorData<T: Config> = StorageValue<_, bool, ValueQuery>;
@@ -332,15 +424,49 @@ pub mod pallet {
             matches!(call, Call::set_latest_author_data { .. })
         }
     }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            let Call::report_container_chain_equivocation { equivocation_proof } = call else {
+                return InvalidTransaction::Call.into();
+            };
+
+            let offence = Self::equivocation_offence(equivocation_proof).map_err(|e| match e {
+                Error::<T>::DuplicateOffenceReport => InvalidTransaction::Stale,
+                _ => InvalidTransaction::BadProof,
+            })?;
+
+            ValidTransaction::with_tag_prefix("ContainerChainEquivocation")
+                .priority(TransactionPriority::MAX)
+                .and_provides((offence.offender, offence.para_id, offence.slot))
+                .longevity(EQUIVOCATION_REPORT_LONGEVITY)
+                .propagate(true)
+                .build()
+        }
+    }
 }
 
+/// Number of orchestrator blocks an equivocation report stays in the transaction pool.
+const EQUIVOCATION_REPORT_LONGEVITY: u64 = 64;
+
+/// Number of noted heads kept in `RecentHeads` for each container chain.
+pub const MAX_RECENT_HEADS: u32 = 16;
+
+/// Heads noted in a session bind equivocation proofs during that session and the next one.
+/// The slot of a bound header can be one more session older than its head, so reports are
+/// kept for that many sessions after the one in which they were made.
+const REPORTED_EQUIVOCATIONS_SESSIONS: SessionIndex = 3;
+
 impl<T: Config> Pallet<T> {
     /// Fetch author and block number from a proof of header
     fn fetch_block_info_from_proof<S: GenericStorageReader>(
         relay_state_proof: &S,
         para_id: ParaId,
         tanssi_slot: Slot,
-    ) -> Result<ContainerChainBlockInfo<T::AccountId>, Error<T>> {
+    ) -> Result<(ContainerChainBlockInfo<T::AccountId>, H256), Error<T>> {
         let bytes = para_id.twox_64_concat();
         // CONCAT
         let key = [PARAS_HEADS_INDEX, bytes.as_slice()].concat();
@@ -369,7 +495,7 @@ impl<T: Config> Pallet<T> {
         let mut first_error = None;
         for aura_digest in author_header.digest().logs() {
             match Self::author_from_log(aura_digest, para_id, &author_header, tanssi_slot) {
-                Ok(x) => return Ok(x),
+                Ok(x) => return Ok((x, author_header.hash())),
                 Err(e) => {
                     if first_error.is_none() {
                         first_error = Some(e);
@@ -416,6 +542,91 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Check an equivocation proof and build the offence of its author, who must be the collator
+    /// assigned to the slot and must not have been reported already.
+    fn equivocation_offence(
+        proof: &ContainerChainEquivocationProof,
+    ) -> Result<ContainerChainEquivocationOffence<T::AccountId>, Error<T>> {
+        let author = equivocation::check_equivocation_proof(proof)
+            .ok_or(Error::<T>::InvalidEquivocationProof)?;
+        if !Self::is_recent_head_or_child(proof.para_id, &proof.first_header)
+            || !Self::is_recent_head_or_child(proof.para_id, &proof.second_header)
+        {
+            return Err(Error::<T>::InvalidEquivocationProof);
+        }
+        let offender = T::CollatorAccount::collator_account(&author)
+            .ok_or(Error::<T>::InvalidEquivocationProof)?;
+        if T::ContainerChainAuthor::author_for_slot(proof.slot, proof.para_id).as_ref()
+            != Some(&offender)
+        {
+            return Err(Error::<T>::InvalidEquivocationProof);
+        }
+
+        let session_index = T::CurrentSessionIndex::session_index();
+        let first_session =
+            session_index.saturating_sub(REPORTED_EQUIVOCATIONS_SESSIONS.saturating_sub(1));
+        if (first_session..=session_index).any(|session| {
+            ReportedEquivocations::<T>::contains_key(session, (proof.para_id, proof.slot))
+        }) {
+            return Err(Error::<T>::DuplicateOffenceReport);
+        }
+
+        let collator_count =
+            T::ContainerChains::container_chains_with_collators(ForSession::Current)
+                .into_iter()
+                .find_map(|(para_id, collators)| {
+                    (para_id == proof.para_id).then_some(collators.len() as u32)
+                })
+                .unwrap_or_default();
+
+        Ok(ContainerChainEquivocationOffence {
+            session_index,
+            para_id: proof.para_id,
+            slot: proof.slot,
+            collator_count,
+            offender,
+        })
+    }
+
+    /// Add the hash of a newly noted head of `para_id` to `RecentHeads`, dropping the oldest
+    /// one if there are already `MAX_RECENT_HEADS`.
+    fn note_recent_head(para_id: ParaId, head_hash: H256) {
+        let session_index = T::CurrentSessionIndex::session_index();
+        RecentHeads::<T>::mutate(para_id, |heads| {
+            if heads.is_full() {
+                heads.remove(0);
+            }
+            let _ = heads.try_push((head_hash, session_index));
+        });
+    }
+
+    /// Whether `header` or its parent is a head of `para_id` noted in the current or the
+    /// previous session. Heads are read from the relay chain storage of that para, so this ties
+    /// the header to the para.
+    fn is_recent_head_or_child(para_id: ParaId, header: &ContainerChainHeader) -> bool {
+        let first_session = T::CurrentSessionIndex::session_index().saturating_sub(1);
+        let header_hash = header.hash();
+
+        RecentHeads::<T>::get(para_id)
+            .iter()
+            .any(|(head_hash, session_index)| {
+                *session_index >= first_session
+                    && (*head_hash == header_hash || *head_hash == header.parent_hash)
+            })
+    }
+
+    /// Remove the equivocation reports that can no longer be duplicated, because no proof for
+    /// their slot can be bound to a recent head in `session_index`.
+    pub fn initializer_on_new_session(session_index: &SessionIndex) {
+        // Remove only if the checked sub does not saturate
+        if let Some(session_index_to_remove) =
+            session_index.checked_sub(REPORTED_EQUIVOCATIONS_SESSIONS)
+        {
+            let _ =
+                ReportedEquivocations::<T>::clear_prefix(session_index_to_remove, u32::MAX, None);
+        }
+    }
+
     pub fn latest_author(para_id: ParaId) -> Option<ContainerChainBlockInfo<T::AccountId>> {
         LatestAuthor::<T>::get(para_id)
     }
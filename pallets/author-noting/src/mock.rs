@@ -26,16 +26,20 @@ use {
         },
     },
     frame_system::{pallet_prelude::BlockNumberFor, RawOrigin},
+    nimbus_primitives::NimbusId,
     parity_scale_codec::{Decode, Encode},
     polkadot_parachain_primitives::primitives::RelayChainBlockNumber,
     polkadot_primitives::Slot,
     sp_core::H256,
+    sp_keystore::{testing::MemoryKeystore, KeystoreExt},
     sp_runtime::{
         traits::{BlakeTwo256, IdentityLookup},
         BuildStorage,
     },
+    sp_staking::offence::{Offence, OffenceError, ReportOffence},
     sp_state_machine::StorageProof,
     test_relay_sproof_builder::ParaHeaderSproofBuilder,
+    tp_traits::ContainerChainEquivocationOffence,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -105,6 +109,17 @@ pub mod mock_data {
     #[pallet::storage]
     pub(super) type Mock<T: Config> = StorageValue<_, Mocks, ValueQuery>;
 
+    #[pallet::storage]
+    pub(super) type CollatorAccounts<T: Config> =
+        StorageValue<_, Vec<(NimbusId, AccountId)>, ValueQuery>;
+
+    #[pallet::storage]
+    pub(super) type CurrentSession<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::storage]
+    pub(super) type ReportedOffences<T: Config> =
+        StorageValue<_, Vec<ContainerChainEquivocationOffence<AccountId>>, ValueQuery>;
+
     impl<T: Config> Pallet<T> {
         pub fn get() -> Mocks {
             Mock::<T>::get()
@@ -132,12 +147,15 @@ impl mock_data::Config for Test {}
 )]
 pub struct Mocks {
     pub container_chains: Vec<(ParaId, Vec<AccountId>)>,
+    /// Authors set by benchmarks, by default the author of a slot is the slot number.
+    pub container_chain_authors: Vec<(ParaId, Vec<AccountId>)>,
 }
 
 impl Default for Mocks {
     fn default() -> Self {
         Self {
             container_chains: vec![(1001.into(), vec![1])],
+            container_chain_authors: vec![],
         }
     }
 }
@@ -145,12 +163,99 @@ impl Default for Mocks {
 pub struct MockAuthorFetcher;
 
 impl tp_traits::GetContainerChainAuthor<AccountId> for MockAuthorFetcher {
-    fn author_for_slot(slot: Slot, _para_id: ParaId) -> Option<AccountId> {
-        Some(slot.into())
+    fn author_for_slot(slot: Slot, para_id: ParaId) -> Option<AccountId> {
+        let authors = MockData::get()
+            .container_chain_authors
+            .into_iter()
+            .find_map(|(id, authors)| (id == para_id).then_some(authors));
+
+        match authors {
+            Some(authors) if !authors.is_empty() => {
+                let index = u64::from(slot) % authors.len() as u64;
+                authors.get(index as usize).cloned()
+            }
+            _ => Some(slot.into()),
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_authors_for_para_id(para_id: ParaId, authors: Vec<AccountId>) {
+        MockData::mutate(|m| {
+            m.container_chain_authors.retain(|(id, _)| *id != para_id);
+            m.container_chain_authors.push((para_id, authors));
+        });
+    }
+}
+
+pub struct MockCollatorAccount;
+
+impl MockCollatorAccount {
+    pub fn set(author: NimbusId, account: AccountId) {
+        mock_data::CollatorAccounts::<Test>::append((author, account));
+    }
+}
+
+impl tp_traits::GetCollatorAccount<NimbusId, AccountId> for MockCollatorAccount {
+    fn collator_account(authority: &NimbusId) -> Option<AccountId> {
+        mock_data::CollatorAccounts::<Test>::get()
+            .into_iter()
+            .find_map(|(id, account)| (id == *authority).then_some(account))
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_collator_account(authority: NimbusId, account: AccountId) {
+        Self::set(authority, account);
+    }
+}
+
+pub struct MockSessionIndex;
+
+impl MockSessionIndex {
+    pub fn set(session_index: u32) {
+        mock_data::CurrentSession::<Test>::put(session_index);
+    }
+}
+
+impl tp_traits::GetSessionIndex<u32> for MockSessionIndex {
+    fn session_index() -> u32 {
+        mock_data::CurrentSession::<Test>::get()
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    fn set_authors_for_para_id(_para_id: ParaId, _authors: Vec<AccountId>) {}
+    fn skip_to_session(session_index: u32) {
+        Self::set(session_index);
+    }
+}
+
+/// Stores the reported offences in `mock_data::ReportedOffences`.
+pub struct MockEquivocationReporter;
+
+impl MockEquivocationReporter {
+    pub fn reported_offences() -> Vec<ContainerChainEquivocationOffence<AccountId>> {
+        mock_data::ReportedOffences::<Test>::get()
+    }
+}
+
+impl ReportOffence<AccountId, AccountId, ContainerChainEquivocationOffence<AccountId>>
+    for MockEquivocationReporter
+{
+    fn report_offence(
+        _reporters: Vec<AccountId>,
+        offence: ContainerChainEquivocationOffence<AccountId>,
+    ) -> Result<(), OffenceError> {
+        if Self::is_known_offence(&offence.offenders(), &offence.time_slot()) {
+            return Err(OffenceError::DuplicateReport);
+        }
+        mock_data::ReportedOffences::<Test>::append(offence);
+
+        Ok(())
+    }
+
+    fn is_known_offence(offenders: &[AccountId], time_slot: &(ParaId, Slot)) -> bool {
+        Self::reported_offences().iter().any(|offence| {
+            offence.time_slot() == *time_slot && offenders.contains(&offence.offender)
+        })
+    }
 }
 
 pub struct DummyBeacon {}
@@ -216,6 +321,9 @@ impl Config for Test {
     type ContainerChains = MockContainerChainGetter;
     type AuthorNotingHook = ();
     type MaxContainerChains = ConstU32<100>;
+    type CollatorAccount = MockCollatorAccount;
+    type CurrentSessionIndex = MockSessionIndex;
+    type EquivocationReporter = MockEquivocationReporter;
     // Unit tests only check ParaMode, for RelayMode see integration tests
     type RelayOrPara = ParaMode<MockRelayStateProvider>;
 }
@@ -229,10 +337,14 @@ struct BlockTest {
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    frame_system::GenesisConfig::<Test>::default()
+    let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::<Test>::default()
         .build_storage()
         .unwrap()
-        .into()
+        .into();
+    // Needed to seal container chain headers in equivocation tests
+    ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+
+    ext
 }
 
 fn wasm_ext() -> sp_io::TestExternalities {
@@ -19,25 +19,34 @@ use frame_support::traits::Get;
 use parity_scale_codec::Decode;
 use tp_author_noting_inherent::OwnParachainInherentData;
 use {
-    crate::{mock::*, ContainerChainBlockInfo, Event},
+    crate::{
+        equivocation::seal_header, mock::*, ContainerChainBlockInfo, Error, Event, RecentHeads,
+        ReportedEquivocations, MAX_RECENT_HEADS,
+    },
     cumulus_primitives_core::ParaId,
     frame_support::{
-        assert_ok,
-        dispatch::GetDispatchInfo,
+        assert_noop, assert_ok,
+        dispatch::{DispatchResultWithPostInfo, GetDispatchInfo, Pays},
         inherent::{InherentData, ProvideInherent},
         traits::UnfilteredDispatchable,
     },
     frame_system::RawOrigin,
     hex_literal::hex,
+    nimbus_primitives::NimbusId,
     parity_scale_codec::Encode,
-    sp_consensus_aura::{inherents::InherentType, AURA_ENGINE_ID},
+    sp_consensus_aura::{inherents::InherentType, Slot, AURA_ENGINE_ID},
     sp_core::H256,
     sp_runtime::{
         generic::DigestItem,
-        traits::{BlakeTwo256, HashingFor},
+        traits::{BlakeTwo256, HashingFor, Header as HeaderT, ValidateUnsigned},
+        transaction_validity::{InvalidTransaction, TransactionSource},
+        RuntimeAppPublic,
     },
     test_relay_sproof_builder::{HeaderAs, ParaHeaderSproofBuilder, ParaHeaderSproofBuilderItem},
-    tp_traits::{ForSession, GetContainerChainsWithCollators},
+    tp_traits::{
+        ContainerChainEquivocationOffence, ContainerChainEquivocationProof, ContainerChainHeader,
+        ForSession, GetContainerChainsWithCollators,
+    },
 };
 
 #[test]
@@ -811,3 +820,322 @@ fn test_author_id_insertion_not_first_log() {
             );
         });
 }
+
+/// Sealed child of the head with hash `H256::zero()`, see `note_parent_head`.
+fn sealed_header(number: u32, slot: u64, author: &NimbusId) -> ContainerChainHeader {
+    sealed_child_header(Default::default(), number, slot, author)
+}
+
+fn sealed_child_header(
+    parent_hash: H256,
+    number: u32,
+    slot: u64,
+    author: &NimbusId,
+) -> ContainerChainHeader {
+    let header = ContainerChainHeader {
+        parent_hash,
+        number,
+        state_root: Default::default(),
+        extrinsics_root: Default::default(),
+        digest: Default::default(),
+    };
+
+    seal_header(header, slot.into(), author)
+}
+
+/// Note `H256::zero()` as a head of para 1001, so that headers from `sealed_header` are bound
+/// to that para.
+fn note_parent_head() {
+    AuthorNoting::note_recent_head(1001.into(), H256::zero());
+}
+
+/// Equivocation proof of `author` for slot 13 of para 1001, whose author is account 13.
+fn equivocation_proof(author: &NimbusId) -> ContainerChainEquivocationProof {
+    ContainerChainEquivocationProof {
+        para_id: 1001.into(),
+        slot: 13u64.into(),
+        first_header: sealed_header(1, 13, author),
+        second_header: sealed_header(2, 13, author),
+    }
+}
+
+fn report_equivocation(proof: ContainerChainEquivocationProof) -> DispatchResultWithPostInfo {
+    AuthorNoting::report_container_chain_equivocation(RawOrigin::None.into(), Box::new(proof))
+}
+
+#[test]
+fn test_report_container_chain_equivocation() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        note_parent_head();
+
+        let result = report_equivocation(equivocation_proof(&author));
+        assert_eq!(result.map(|info| info.pays_fee), Ok(Pays::No));
+
+        System::assert_last_event(
+            Event::ContainerChainEquivocationReported {
+                para_id: 1001.into(),
+                slot: 13u64.into(),
+                offender: 13,
+            }
+            .into(),
+        );
+        assert_eq!(
+            MockEquivocationReporter::reported_offences(),
+            vec![ContainerChainEquivocationOffence {
+                session_index: 0,
+                para_id: 1001.into(),
+                slot: 13u64.into(),
+                collator_count: 1,
+                offender: 13,
+            }]
+        );
+        assert_eq!(
+            ReportedEquivocations::<Test>::get(0, (ParaId::from(1001), Slot::from(13u64))),
+            Some(13)
+        );
+    });
+}
+
+#[test]
+fn test_report_container_chain_equivocation_twice() {
+    new_test_ext().execute_with(|| {
+        let author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        note_parent_head();
+        assert_ok!(report_equivocation(equivocation_proof(&author)));
+
+        // Another pair of headers for the same slot is the same offence
+        let mut proof = equivocation_proof(&author);
+        proof.second_header = sealed_header(3, 13, &author);
+        let call = Call::report_container_chain_equivocation {
+            equivocation_proof: Box::new(proof.clone()),
+        };
+        assert_eq!(
+            AuthorNoting::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::Stale.into()
+        );
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::DuplicateOffenceReport
+        );
+    });
+}
+
+#[test]
+fn test_report_container_chain_equivocation_invalid_proofs() {
+    new_test_ext().execute_with(|| {
+        let author = NimbusId::generate_pair(None);
+        let other_author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        MockCollatorAccount::set(other_author.clone(), 14);
+        note_parent_head();
+
+        // Same block twice
+        let mut proof = equivocation_proof(&author);
+        proof.second_header = proof.first_header.clone();
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        // Headers sealed by different authors
+        let mut proof = equivocation_proof(&author);
+        proof.second_header = sealed_header(2, 13, &other_author);
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        // Headers for different slots
+        let mut proof = equivocation_proof(&author);
+        proof.second_header = sealed_header(2, 14, &author);
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        // Seal not signed by the author of the pre-digest
+        let mut proof = equivocation_proof(&author);
+        let forged_seal = sealed_header(2, 13, &other_author).digest.pop().unwrap();
+        proof.second_header.digest.pop();
+        proof.second_header.digest.push(forged_seal);
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        // Slot 13 is assigned to account 13, not to the owner of `other_author`
+        assert_noop!(
+            report_equivocation(equivocation_proof(&other_author)),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        // Unknown author key
+        let unknown_author = NimbusId::generate_pair(None);
+        assert_noop!(
+            report_equivocation(equivocation_proof(&unknown_author)),
+            Error::<Test>::InvalidEquivocationProof
+        );
+    });
+}
+
+#[test]
+fn test_validate_container_chain_equivocation_report() {
+    new_test_ext().execute_with(|| {
+        let author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        note_parent_head();
+
+        let call = Call::report_container_chain_equivocation {
+            equivocation_proof: Box::new(equivocation_proof(&author)),
+        };
+        let valid = AuthorNoting::validate_unsigned(TransactionSource::External, &call)
+            .expect("valid report");
+        assert_eq!(
+            valid.provides,
+            vec![(
+                "ContainerChainEquivocation",
+                (13u64, ParaId::from(1001), Slot::from(13u64))
+            )
+                .encode()]
+        );
+
+        let mut proof = equivocation_proof(&author);
+        proof.second_header = proof.first_header.clone();
+        let call = Call::report_container_chain_equivocation {
+            equivocation_proof: Box::new(proof),
+        };
+        assert_eq!(
+            AuthorNoting::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::BadProof.into()
+        );
+    });
+}
+
+#[test]
+fn test_report_container_chain_equivocation_of_other_para() {
+    new_test_ext().execute_with(|| {
+        let author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        note_parent_head();
+
+        // The headers extend a head of para 1001, the same slot of para 1002 is also assigned
+        // to account 13
+        let mut proof = equivocation_proof(&author);
+        proof.para_id = 1002.into();
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        // Only one of the headers extends a head of para 1001
+        let mut proof = equivocation_proof(&author);
+        proof.second_header = sealed_child_header(H256::repeat_byte(1), 2, 13, &author);
+        assert_noop!(
+            report_equivocation(proof),
+            Error::<Test>::InvalidEquivocationProof
+        );
+
+        assert_ok!(report_equivocation(equivocation_proof(&author)));
+    });
+}
+
+#[test]
+fn test_noted_heads_bind_equivocation_proofs() {
+    let noted_header = || ContainerChainHeader {
+        parent_hash: Default::default(),
+        number: 1,
+        state_root: Default::default(),
+        extrinsics_root: Default::default(),
+        digest: sp_runtime::generic::Digest {
+            logs: vec![DigestItem::PreRuntime(
+                AURA_ENGINE_ID,
+                InherentType::from(13u64).encode(),
+            )],
+        },
+    };
+
+    BlockTests::new()
+        .with_relay_sproof_builder(move |_, relay_block_num, sproof| match relay_block_num {
+            1 => {
+                sproof.items.push(ParaHeaderSproofBuilderItem {
+                    para_id: 1001.into(),
+                    author_id: HeaderAs::NonEncoded(noted_header()),
+                });
+            }
+            _ => unreachable!(),
+        })
+        .add(1, move || {
+            let noted_hash = noted_header().hash();
+            assert_eq!(
+                RecentHeads::<Test>::get(ParaId::from(1001)).into_inner(),
+                vec![(noted_hash, 0)]
+            );
+
+            // Two children of the noted head for slot 14
+            let author = NimbusId::generate_pair(None);
+            MockCollatorAccount::set(author.clone(), 14);
+            let proof = ContainerChainEquivocationProof {
+                para_id: 1001.into(),
+                slot: 14u64.into(),
+                first_header: sealed_child_header(noted_hash, 2, 14, &author),
+                second_header: sealed_child_header(noted_hash, 3, 14, &author),
+            };
+            assert_ok!(report_equivocation(proof));
+        });
+}
+
+#[test]
+fn test_recent_heads_are_bounded() {
+    new_test_ext().execute_with(|| {
+        for i in 0..=MAX_RECENT_HEADS {
+            AuthorNoting::note_recent_head(1001.into(), H256::from_low_u64_be(i.into()));
+        }
+
+        let heads = RecentHeads::<Test>::get(ParaId::from(1001));
+        assert_eq!(heads.len() as u32, MAX_RECENT_HEADS);
+        // The oldest head was dropped
+        assert_eq!(heads.first(), Some(&(H256::from_low_u64_be(1), 0)));
+    });
+}
+
+#[test]
+fn test_heads_of_old_sessions_do_not_bind_equivocation_proofs() {
+    new_test_ext().execute_with(|| {
+        let author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        note_parent_head();
+
+        MockSessionIndex::set(2);
+        assert_noop!(
+            report_equivocation(equivocation_proof(&author)),
+            Error::<Test>::InvalidEquivocationProof
+        );
+    });
+}
+
+#[test]
+fn test_reported_equivocations_are_pruned() {
+    new_test_ext().execute_with(|| {
+        let author = NimbusId::generate_pair(None);
+        MockCollatorAccount::set(author.clone(), 13);
+        note_parent_head();
+        assert_ok!(report_equivocation(equivocation_proof(&author)));
+
+        // The report is still used to reject duplicates while the slot can be bound to a head
+        MockSessionIndex::set(2);
+        AuthorNoting::initializer_on_new_session(&2);
+        note_parent_head();
+        assert_noop!(
+            report_equivocation(equivocation_proof(&author)),
+            Error::<Test>::DuplicateOffenceReport
+        );
+
+        MockSessionIndex::set(3);
+        AuthorNoting::initializer_on_new_session(&3);
+        assert_eq!(ReportedEquivocations::<Test>::iter_prefix(0).count(), 0);
+    });
+}
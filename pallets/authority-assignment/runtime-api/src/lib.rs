@@ -0,0 +1,37 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for AuthorityAssignment pallet. Can be used by collators to check every
+//! chain they are assigned to, as a collator can collate for several parathreads at the
+//! same time.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scale_info::prelude::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait AuthorityAssignmentApi<AuthorityId, ParaId> where
+        AuthorityId: parity_scale_codec::Codec,
+        ParaId: parity_scale_codec::Codec,
+    {
+        /// Return every paraId assigned to a given authority.
+        fn para_ids_of_authority(authority: AuthorityId) -> Vec<ParaId>;
+
+        /// Return every paraId assigned to a given authority on the next session.
+        /// On session boundary this returns the same as `para_ids_of_authority`.
+        fn para_ids_of_authority_next_session(authority: AuthorityId) -> Vec<ParaId>;
+    }
+}
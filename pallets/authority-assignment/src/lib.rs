@@ -21,13 +21,14 @@ extern crate alloc;
 
 pub use pallet::*;
 use {
-    alloc::{collections::btree_map::BTreeMap, vec},
+    alloc::{collections::btree_map::BTreeMap, vec, vec::Vec},
     dp_collator_assignment::AssignedCollators,
     frame_support::pallet_prelude::*,
     sp_runtime::{
         traits::{AtLeast32BitUnsigned, One, Zero},
         Saturating,
     },
+    tp_traits::ParaId,
 };
 
 #[cfg(test)]
@@ -119,5 +120,36 @@ pub mod pallet {
         ) -> Option<AssignedCollators<T::AuthorityId>> {
             CollatorContainerChain::<T>::get(session_index)
         }
+
+        /// Return every paraId assigned to `authority` in `session_index`, including
+        /// `orchestrator_para_id` if it is assigned to the orchestrator chain.
+        /// Unlike `AssignedCollators::para_id_of` this does not stop at the first match, as an
+        /// authority can be assigned to several parathreads at the same time.
+        pub fn para_ids_of(
+            session_index: T::SessionIndex,
+            authority: &T::AuthorityId,
+            orchestrator_para_id: ParaId,
+        ) -> Vec<ParaId>
+        where
+            T::AuthorityId: PartialEq,
+        {
+            let Some(assigned_authorities) = CollatorContainerChain::<T>::get(session_index) else {
+                return vec![];
+            };
+
+            let mut para_ids = vec![];
+            if assigned_authorities.orchestrator_chain.contains(authority) {
+                para_ids.push(orchestrator_para_id);
+            }
+            para_ids.extend(
+                assigned_authorities
+                    .container_chains
+                    .iter()
+                    .filter(|(_, authorities)| authorities.contains(authority))
+                    .map(|(para_id, _)| *para_id),
+            );
+
+            para_ids
+        }
     }
 }
@@ -15,7 +15,7 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{mock::*, CollatorContainerChain},
+    crate::{mock::*, CollatorContainerChain, Pallet},
     dp_collator_assignment::AssignedCollators,
     std::collections::BTreeMap,
 };
@@ -403,3 +403,31 @@ fn assign_collators_insert_collator() {
         assert_eq!(assigned_collators_at_session(3), None);
     });
 }
+
+#[test]
+fn para_ids_of_returns_every_assignment() {
+    new_test_ext().execute_with(|| {
+        CollatorContainerChain::<Test>::insert(
+            0,
+            AssignedCollators {
+                orchestrator_chain: vec!["nmbs1".to_string()],
+                container_chains: BTreeMap::from_iter(vec![
+                    (1001.into(), vec!["nmbs1".to_string(), "nmbs2".to_string()]),
+                    (1002.into(), vec!["nmbs2".to_string()]),
+                    (1003.into(), vec!["nmbs1".to_string()]),
+                ]),
+            },
+        );
+
+        assert_eq!(
+            Pallet::<Test>::para_ids_of(0, &"nmbs1".to_string(), 999.into()),
+            vec![999.into(), 1001.into(), 1003.into()]
+        );
+        assert_eq!(
+            Pallet::<Test>::para_ids_of(0, &"nmbs2".to_string(), 999.into()),
+            vec![1001.into(), 1002.into()]
+        );
+        assert!(Pallet::<Test>::para_ids_of(0, &"nmbs3".to_string(), 999.into()).is_empty());
+        assert!(Pallet::<Test>::para_ids_of(1, &"nmbs1".to_string(), 999.into()).is_empty());
+    });
+}
@@ -27,11 +27,13 @@ extern crate alloc;
 pub use pallet::*;
 use {
     alloc::{collections::btree_map::BTreeMap, vec},
+    core::marker::PhantomData,
     frame_support::pallet_prelude::*,
     sp_runtime::{
         traits::{AtLeast32BitUnsigned, CheckedSub},
         RuntimeAppPublic,
     },
+    tp_traits::{GetCollatorAccount, GetSessionIndex},
 };
 
 #[cfg(test)]
@@ -102,3 +104,29 @@ pub mod pallet {
         }
     }
 }
+
+/// [GetCollatorAccount] implementation using the mapping of the session returned by
+/// `CurrentSession`.
+pub struct CollatorAccountLookup<T, CurrentSession>(PhantomData<(T, CurrentSession)>);
+
+impl<T, CurrentSession> GetCollatorAccount<T::AuthorityId, T::AccountId>
+    for CollatorAccountLookup<T, CurrentSession>
+where
+    T: Config,
+    CurrentSession: GetSessionIndex<T::SessionIndex>,
+{
+    fn collator_account(authority: &T::AuthorityId) -> Option<T::AccountId> {
+        AuthorityIdMapping::<T>::get(CurrentSession::session_index())?
+            .get(authority)
+            .cloned()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_collator_account(authority: T::AuthorityId, account: T::AccountId) {
+        AuthorityIdMapping::<T>::mutate(CurrentSession::session_index(), |mapping| {
+            mapping
+                .get_or_insert_with(BTreeMap::new)
+                .insert(authority, account);
+        });
+    }
+}
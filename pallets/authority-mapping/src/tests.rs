@@ -19,6 +19,17 @@ use {
     crate::mock::{new_test_ext, AuthorityMapping, Test},
 };
 
+struct MockSession;
+
+impl GetSessionIndex<u32> for MockSession {
+    fn session_index() -> u32 {
+        1
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn skip_to_session(_session_index: u32) {}
+}
+
 #[test]
 fn session_0_fills_in_first_mapping() {
     new_test_ext().execute_with(|| {
@@ -67,3 +78,16 @@ fn session_2_fills_in_third_mapping_removes_first_not_second() {
         assert_eq!(v2.get(&3u64.into()), Some(&1u64));
     });
 }
+
+#[test]
+fn collator_account_uses_current_session_mapping() {
+    new_test_ext().execute_with(|| {
+        AuthorityMapping::initializer_on_new_session(&0, &[(1, 1u64.into())]);
+        AuthorityMapping::initializer_on_new_session(&1, &[(2, 2u64.into())]);
+
+        type Lookup = CollatorAccountLookup<Test, MockSession>;
+        assert_eq!(Lookup::collator_account(&2u64.into()), Some(2));
+        // Keys of previous sessions are ignored
+        assert_eq!(Lookup::collator_account(&1u64.into()), None);
+    });
+}
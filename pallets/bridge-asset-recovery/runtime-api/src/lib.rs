@@ -0,0 +1,41 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for Bridge Asset Recovery pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    pub trait BridgeAssetRecoveryApi<AccountId, TrapId, TrappedAssets, FailedTransfer>
+    where
+        AccountId: parity_scale_codec::Codec,
+        TrapId: parity_scale_codec::Codec,
+        TrappedAssets: parity_scale_codec::Codec,
+        FailedTransfer: parity_scale_codec::Codec,
+    {
+        /// Assets trapped for `claimer` that can be claimed with `claim_trapped_assets`, with
+        /// the id of the message that trapped them. Ordered by trap id.
+        fn trapped_assets(claimer: AccountId) -> Vec<(TrapId, TrappedAssets)>;
+
+        /// Transfers of `claimer` that could not be sent and can be sent again with
+        /// `retry_failed_transfer`. Ordered by id.
+        fn failed_transfers(claimer: AccountId) -> Vec<(TrapId, FailedTransfer)>;
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as BridgeAssetRecovery;
+use {
+    frame_benchmarking::{v2::*, BenchmarkError},
+    frame_system::RawOrigin,
+    xcm_builder::EnsureDelivery,
+    xcm_executor::traits::FeeReason,
+};
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    // Claiming to another chain is the worst case, it also builds and sends the transfer.
+    #[benchmark]
+    fn claim_trapped_assets() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let claimer_location = BridgeAssetRecovery::<T>::account_location(&caller);
+        let destination = T::ReachableDestination::get();
+        let assets: Assets = (Location::here(), 1_000_000_000_000u128).into();
+
+        T::DeliveryHelper::ensure_successful_delivery(
+            &claimer_location,
+            &destination,
+            FeeReason::InitiateTransfer,
+        );
+
+        IndexedAssetTrap::<T, T::AssetTrap>::drop_assets(
+            &claimer_location,
+            assets.into(),
+            &XcmContext::with_message_id([1; 32]),
+        );
+        let trap_id = NextTrapId::<T>::get()
+            .checked_sub(1)
+            .ok_or(BenchmarkError::Stop("Assets were not indexed"))?;
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            trap_id,
+            Box::new(destination.into()),
+            Box::new(claimer_location.into()),
+            0,
+        );
+
+        assert!(!TrappedAssets::<T>::contains_key(&caller, trap_id));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn retry_failed_transfer() -> Result<(), BenchmarkError> {
+        let caller: T::AccountId = whitelisted_caller();
+        let fee_payer = BridgeAssetRecovery::<T>::account_location(&caller);
+        let destination = T::ReachableDestination::get();
+        let fee: Asset = (Location::here(), 1_000_000_000_000u128).into();
+
+        T::AssetTransactor::deposit_asset(
+            &fee,
+            &fee_payer,
+            Some(&XcmContext::with_message_id([1; 32])),
+        )
+        .map_err(|_| BenchmarkError::Stop("Could not fund the fee payer"))?;
+        T::DeliveryHelper::ensure_successful_delivery(
+            &fee_payer,
+            &destination,
+            FeeReason::ChargeFees,
+        );
+
+        let message = Xcm::<()>(vec![
+            ReserveAssetDeposited(fee.clone().into()),
+            DepositAsset {
+                assets: Wild(AllCounted(1)),
+                beneficiary: fee_payer.clone(),
+            },
+        ]);
+        FailedTransfers::<T>::insert(
+            &caller,
+            0,
+            FailedTransferRecord {
+                message_id: H256::repeat_byte(1),
+                destination: destination.into(),
+                message: VersionedXcm::from(message),
+                fee: fee.into(),
+                fee_payer: fee_payer.into(),
+                failed_at: frame_system::Pallet::<T>::block_number(),
+            },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), 0);
+
+        assert!(!FailedTransfers::<T>::contains_key(&caller, 0));
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(
+        BridgeAssetRecovery,
+        crate::mock::new_test_ext(),
+        crate::mock::Test,
+    );
+}
@@ -0,0 +1,620 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! BridgeAssetRecovery pallet.
+//!
+//! This pallet keeps an index of the assets trapped for local accounts, so that users can find
+//! what they can claim and which message trapped it, and lets them claim the assets to a new
+//! destination in a single call.
+//!
+//! ## Indexing
+//!
+//! The index is fed by [`IndexedAssetTrap`] and [`IndexedAssetClaims`], which wrap the asset
+//! trap of the runtime (usually `pallet_xcm`) and add or remove entries when assets are trapped
+//! for, or claimed by, an `AccountId32` location. The origin message id is the topic of the XCM
+//! that trapped the assets, or its message id if it had no topic.
+//!
+//! Inbound bridge processors that fail after taking custody of the tokens use
+//! [`Pallet::trap_assets`] to trap them for the beneficiary, instead of leaving them in a
+//! sovereign account.
+//!
+//! ## Failed transfers
+//!
+//! Tokens reserved in another chain, such as the native tokens of container chains, can't be
+//! trapped here. Processors forwarding them use [`Pallet::send_transfer`], which keeps the
+//! transfer if it can't be sent, so that the claimer can send it again with
+//! `retry_failed_transfer` once the destination accepts it.
+//!
+//! ## Claiming
+//!
+//! `claim_trapped_assets` claims an indexed trap and deposits the assets into a beneficiary of
+//! this chain, or sends them to another chain as reserve transfers, paying the remote fees with
+//! one of the trapped assets. The claim is executed with the location the assets were trapped
+//! for, so it does not depend on the network the location was built with.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use {
+    alloc::{boxed::Box, vec, vec::Vec},
+    core::marker::PhantomData,
+    frame_support::{pallet_prelude::*, storage::with_storage_layer},
+    frame_system::pallet_prelude::*,
+    sp_core::{hashing::blake2_256, H256},
+    sp_runtime::DispatchResult,
+    xcm::{
+        latest::{prelude::*, AssetTransferFilter},
+        IdentifyVersion, Version, VersionedAsset, VersionedAssets, VersionedLocation, VersionedXcm,
+    },
+    xcm_executor::{
+        traits::{ClaimAssets, DropAssets, TransactAsset, WeightBounds},
+        AssetsInHolding,
+    },
+};
+
+pub use pallet::*;
+
+/// Identifier of an indexed trap.
+pub type TrapId = u64;
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct TrappedAssetsRecord<BlockNumber> {
+    /// Topic of the XCM that trapped the assets, which is the id of the bridge message for
+    /// inbound transfers.
+    pub message_id: H256,
+    /// Location the assets were trapped for, the claim must be executed with it.
+    pub origin: VersionedLocation,
+    pub assets: VersionedAssets,
+    /// Block in which the assets were trapped.
+    pub trapped_at: BlockNumber,
+}
+
+/// A transfer to another chain that could not be sent.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct FailedTransferRecord<BlockNumber> {
+    /// Id of the bridge message that started the transfer.
+    pub message_id: H256,
+    pub destination: VersionedLocation,
+    /// Message to send to `destination`.
+    pub message: VersionedXcm<()>,
+    /// Fee moved from `fee_payer` to the sovereign account of `destination` when the message is
+    /// sent, as it is reserve deposited there.
+    pub fee: VersionedAsset,
+    pub fee_payer: VersionedLocation,
+    /// Block in which the transfer failed.
+    pub failed_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    pub use crate::weights::WeightInfo;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config<AccountId: From<[u8; 32]> + Into<[u8; 32]>> {
+        /// Asset trap used by the XCM executor, wrapped by [`IndexedAssetTrap`] when trapping the
+        /// assets of failed transfers.
+        type AssetTrap: DropAssets;
+
+        /// Withdraws the assets of failed transfers before trapping them.
+        type AssetTransactor: TransactAsset;
+
+        /// Executes the XCM that claims and re-routes trapped assets.
+        type XcmExecutor: ExecuteXcm<<Self as frame_system::Config>::RuntimeCall>;
+
+        /// Weighs the XCM that claims and re-routes trapped assets.
+        type Weigher: WeightBounds<<Self as frame_system::Config>::RuntimeCall>;
+
+        /// Sends the messages of failed transfers.
+        type XcmRouter: SendXcm;
+
+        /// Account that can claim the assets of beneficiaries that have no account on this
+        /// chain, such as `AccountKey20` beneficiaries of container chains.
+        type FallbackClaimer: Get<Self::AccountId>;
+
+        /// The weight information of this pallet.
+        type WeightInfo: WeightInfo;
+
+        /// Makes the destination of the benchmarks reachable.
+        #[cfg(feature = "runtime-benchmarks")]
+        type DeliveryHelper: xcm_builder::EnsureDelivery;
+
+        /// Destination the benchmarks send assets to.
+        #[cfg(feature = "runtime-benchmarks")]
+        type ReachableDestination: Get<Location>;
+    }
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    // Versioned XCM types have no bounded encoding, same as the asset traps of pallet-xcm.
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// Assets trapped for each claimer.
+    #[pallet::storage]
+    pub type TrappedAssets<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        TrapId,
+        TrappedAssetsRecord<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Transfers of each claimer that could not be sent to their destination.
+    #[pallet::storage]
+    pub type FailedTransfers<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        TrapId,
+        FailedTransferRecord<BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Id of the next indexed trap or failed transfer.
+    #[pallet::storage]
+    pub type NextTrapId<T: Config> = StorageValue<_, TrapId, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Assets were trapped for a local account.
+        AssetsTrapped {
+            claimer: T::AccountId,
+            trap_id: TrapId,
+            message_id: H256,
+        },
+        /// Indexed assets were claimed, through this pallet or directly through the asset trap.
+        AssetsClaimed {
+            claimer: T::AccountId,
+            trap_id: TrapId,
+        },
+        /// Trapped assets were claimed and sent to `beneficiary` in `destination`.
+        AssetsRecovered {
+            claimer: T::AccountId,
+            trap_id: TrapId,
+            destination: VersionedLocation,
+            beneficiary: VersionedLocation,
+        },
+        /// A transfer could not be sent and was kept to be sent again by `claimer`.
+        TransferFailed {
+            claimer: T::AccountId,
+            transfer_id: TrapId,
+            message_id: H256,
+        },
+        /// A failed transfer was sent again.
+        FailedTransferSent {
+            claimer: T::AccountId,
+            transfer_id: TrapId,
+            message_hash: XcmHash,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// There are no trapped assets with this id for the caller.
+        UnknownTrap,
+        /// A location or the trapped assets could not be converted to the current XCM version.
+        BadVersion,
+        /// The fee asset item is not one of the trapped assets.
+        InvalidFeeAsset,
+        /// The claim message could not be weighed.
+        UnweighableMessage,
+        /// The claim message failed to execute.
+        ClaimFailed,
+        /// The assets of a failed transfer could not be withdrawn to be trapped.
+        WithdrawFailed,
+        /// There is no failed transfer with this id for the caller.
+        UnknownTransfer,
+        /// The fee of a transfer could not be paid to its destination.
+        FeePaymentFailed,
+        /// The message of a transfer could not be sent.
+        SendFailed,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Claim the assets of trap `trap_id` and deposit them into `beneficiary`.
+        ///
+        /// If `destination` is not `Here`, the assets are sent there as reserve transfers, and
+        /// the asset at `fee_asset_item` pays for the remote execution.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::claim_trapped_assets())]
+        pub fn claim_trapped_assets(
+            origin: OriginFor<T>,
+            trap_id: TrapId,
+            destination: Box<VersionedLocation>,
+            beneficiary: Box<VersionedLocation>,
+            fee_asset_item: u32,
+        ) -> DispatchResult {
+            let claimer = ensure_signed(origin)?;
+
+            let record =
+                TrappedAssets::<T>::get(&claimer, trap_id).ok_or(Error::<T>::UnknownTrap)?;
+            let trap_origin =
+                Location::try_from(record.origin.clone()).map_err(|_| Error::<T>::BadVersion)?;
+            let assets =
+                Assets::try_from(record.assets.clone()).map_err(|_| Error::<T>::BadVersion)?;
+            let destination_location =
+                Location::try_from((*destination).clone()).map_err(|_| Error::<T>::BadVersion)?;
+            let beneficiary_location =
+                Location::try_from((*beneficiary).clone()).map_err(|_| Error::<T>::BadVersion)?;
+
+            let mut message = Self::claim_message(
+                record.assets.identify_version(),
+                assets,
+                destination_location,
+                beneficiary_location,
+                fee_asset_item,
+            )?;
+            let weight = T::Weigher::weight(&mut message, Weight::MAX)
+                .map_err(|_| Error::<T>::UnweighableMessage)?;
+            let mut message_id = message.using_encoded(blake2_256);
+
+            T::XcmExecutor::prepare_and_execute(
+                trap_origin,
+                message,
+                &mut message_id,
+                weight,
+                weight,
+            )
+            .ensure_complete()
+            .map_err(|error| {
+                log::error!("BridgeAssetRecovery: claim of trap {trap_id} failed: {error:?}");
+                Error::<T>::ClaimFailed
+            })?;
+
+            Self::deposit_event(Event::<T>::AssetsRecovered {
+                claimer,
+                trap_id,
+                destination: *destination,
+                beneficiary: *beneficiary,
+            });
+            Ok(())
+        }
+
+        /// Send the failed transfer `transfer_id` again, paying its fee again.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::retry_failed_transfer())]
+        pub fn retry_failed_transfer(origin: OriginFor<T>, transfer_id: TrapId) -> DispatchResult {
+            let claimer = ensure_signed(origin)?;
+
+            let record = FailedTransfers::<T>::get(&claimer, transfer_id)
+                .ok_or(Error::<T>::UnknownTransfer)?;
+            let destination =
+                Location::try_from(record.destination).map_err(|_| Error::<T>::BadVersion)?;
+            let message =
+                Xcm::<()>::try_from(record.message).map_err(|_| Error::<T>::BadVersion)?;
+            let fee = Asset::try_from(record.fee).map_err(|_| Error::<T>::BadVersion)?;
+            let fee_payer =
+                Location::try_from(record.fee_payer).map_err(|_| Error::<T>::BadVersion)?;
+
+            let message_hash =
+                Self::pay_and_send(&destination, message, &fee, &fee_payer, record.message_id)?;
+
+            FailedTransfers::<T>::remove(&claimer, transfer_id);
+            Self::deposit_event(Event::<T>::FailedTransferSent {
+                claimer,
+                transfer_id,
+                message_hash,
+            });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Withdraw `assets` from `holder` and trap them for `claimer`, indexed under
+        /// `message_id`. Nothing is withdrawn if any of the assets fails.
+        pub fn trap_assets(
+            holder: &Location,
+            assets: Assets,
+            claimer: &T::AccountId,
+            message_id: H256,
+        ) -> DispatchResult {
+            with_storage_layer(|| -> DispatchResult {
+                let context = XcmContext {
+                    origin: Some(holder.clone()),
+                    message_id: message_id.0,
+                    topic: Some(message_id.0),
+                };
+
+                let mut holding = AssetsInHolding::new();
+                for asset in assets.inner() {
+                    let withdrawn =
+                        T::AssetTransactor::withdraw_asset(asset, holder, Some(&context)).map_err(
+                            |error| {
+                                log::error!(
+                                    "BridgeAssetRecovery: failed to withdraw {:?} from {:?}: {:?}",
+                                    asset,
+                                    holder,
+                                    error
+                                );
+                                Error::<T>::WithdrawFailed
+                            },
+                        )?;
+                    holding.subsume_assets(withdrawn);
+                }
+
+                IndexedAssetTrap::<T, T::AssetTrap>::drop_assets(
+                    &Self::account_location(claimer),
+                    holding,
+                    &context,
+                );
+                Ok(())
+            })
+        }
+
+        /// Move `fee` from `fee_payer` to the sovereign account of `destination` and send it
+        /// `message`. If either fails nothing is paid, and the transfer is kept so that `claimer`
+        /// can send it again with `retry_failed_transfer`.
+        pub fn send_transfer(
+            destination: Location,
+            message: Xcm<()>,
+            fee: Asset,
+            fee_payer: Location,
+            claimer: &T::AccountId,
+            message_id: H256,
+        ) -> Option<XcmHash> {
+            match Self::pay_and_send(&destination, message.clone(), &fee, &fee_payer, message_id) {
+                Ok(message_hash) => Some(message_hash),
+                Err(error) => {
+                    log::error!(
+                        "BridgeAssetRecovery: transfer {:?} to {:?} failed: {:?}",
+                        message_id,
+                        destination,
+                        error
+                    );
+
+                    let transfer_id = Self::next_trap_id();
+                    FailedTransfers::<T>::insert(
+                        claimer,
+                        transfer_id,
+                        FailedTransferRecord {
+                            message_id,
+                            destination: destination.into(),
+                            message: VersionedXcm::from(message),
+                            fee: fee.into(),
+                            fee_payer: fee_payer.into(),
+                            failed_at: frame_system::Pallet::<T>::block_number(),
+                        },
+                    );
+                    Self::deposit_event(Event::<T>::TransferFailed {
+                        claimer: claimer.clone(),
+                        transfer_id,
+                        message_id,
+                    });
+                    None
+                }
+            }
+        }
+
+        /// Failed transfers of `claimer`, ordered by id.
+        pub fn failed_transfers(
+            claimer: &T::AccountId,
+        ) -> Vec<(TrapId, FailedTransferRecord<BlockNumberFor<T>>)> {
+            let mut transfers: Vec<_> = FailedTransfers::<T>::iter_prefix(claimer).collect();
+            transfers.sort_by_key(|(transfer_id, _)| *transfer_id);
+            transfers
+        }
+
+        /// Assets trapped for `claimer`, ordered by trap id.
+        pub fn trapped_assets(
+            claimer: &T::AccountId,
+        ) -> Vec<(TrapId, TrappedAssetsRecord<BlockNumberFor<T>>)> {
+            let mut traps: Vec<_> = TrappedAssets::<T>::iter_prefix(claimer).collect();
+            traps.sort_by_key(|(trap_id, _)| *trap_id);
+            traps
+        }
+
+        /// Location of a local account, the one its assets are trapped for.
+        pub fn account_location(account: &T::AccountId) -> Location {
+            Location::new(
+                0,
+                [AccountId32 {
+                    network: None,
+                    id: account.clone().into(),
+                }],
+            )
+        }
+
+        /// Account whose traps are indexed when assets are trapped for `location`.
+        pub fn claimer_account(location: &Location) -> Option<T::AccountId> {
+            match location.unpack() {
+                (0, [AccountId32 { id, .. }]) => Some((*id).into()),
+                _ => None,
+            }
+        }
+
+        fn pay_and_send(
+            destination: &Location,
+            message: Xcm<()>,
+            fee: &Asset,
+            fee_payer: &Location,
+            message_id: H256,
+        ) -> Result<XcmHash, DispatchError> {
+            with_storage_layer(|| {
+                let context = XcmContext {
+                    origin: None,
+                    message_id: message_id.0,
+                    topic: None,
+                };
+                T::AssetTransactor::transfer_asset(fee, fee_payer, destination, &context)
+                    .map_err(|_| Error::<T>::FeePaymentFailed)?;
+
+                let (message_hash, _) = send_xcm::<T::XcmRouter>(destination.clone(), message)
+                    .map_err(|_| Error::<T>::SendFailed)?;
+                Ok(message_hash)
+            })
+        }
+
+        fn next_trap_id() -> TrapId {
+            NextTrapId::<T>::mutate(|next| {
+                let trap_id = *next;
+                *next = next.saturating_add(1);
+                trap_id
+            })
+        }
+
+        fn claim_message(
+            version: Version,
+            assets: Assets,
+            destination: Location,
+            beneficiary: Location,
+            fee_asset_item: u32,
+        ) -> Result<Xcm<<T as frame_system::Config>::RuntimeCall>, Error<T>> {
+            let count = assets.len() as u32;
+            // The ticket tells the asset trap the version the assets were trapped with.
+            let ticket = Location::new(0, [GeneralIndex(version.into())]);
+            let mut instructions = vec![ClaimAsset {
+                assets: assets.clone(),
+                ticket,
+            }];
+
+            if destination == Location::here() {
+                instructions.push(DepositAsset {
+                    assets: Wild(AllCounted(count)),
+                    beneficiary,
+                });
+            } else {
+                let fee_asset = assets
+                    .inner()
+                    .get(fee_asset_item as usize)
+                    .ok_or(Error::<T>::InvalidFeeAsset)?
+                    .clone();
+                instructions.push(InitiateTransfer {
+                    destination,
+                    remote_fees: Some(AssetTransferFilter::ReserveDeposit(Definite(
+                        fee_asset.into(),
+                    ))),
+                    preserve_origin: false,
+                    assets: vec![AssetTransferFilter::ReserveDeposit(Wild(AllCounted(count)))]
+                        .try_into()
+                        .expect("checked statically, qed"),
+                    remote_xcm: Xcm(vec![DepositAsset {
+                        assets: Wild(AllCounted(count)),
+                        beneficiary,
+                    }]),
+                });
+            }
+
+            Ok(Xcm(instructions))
+        }
+
+        pub(crate) fn index_trap(
+            origin: &Location,
+            assets: VersionedAssets,
+            context: &XcmContext,
+        ) -> bool {
+            let Some(claimer) = Self::claimer_account(origin) else {
+                return false;
+            };
+
+            let message_id = H256(context.topic.unwrap_or(context.message_id));
+            let trap_id = Self::next_trap_id();
+            TrappedAssets::<T>::insert(
+                &claimer,
+                trap_id,
+                TrappedAssetsRecord {
+                    message_id,
+                    origin: origin.clone().into(),
+                    assets,
+                    trapped_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::<T>::AssetsTrapped {
+                claimer,
+                trap_id,
+                message_id,
+            });
+            true
+        }
+
+        pub(crate) fn remove_claimed_trap(origin: &Location, assets: &Assets) {
+            let Some(claimer) = Self::claimer_account(origin) else {
+                return;
+            };
+
+            // Traps with the same origin and assets are interchangeable, any of them can go.
+            let claimed = TrappedAssets::<T>::iter_prefix(&claimer).find(|(_, record)| {
+                Location::try_from(record.origin.clone()).as_ref() == Ok(origin)
+                    && Assets::try_from(record.assets.clone()).as_ref() == Ok(assets)
+            });
+
+            if let Some((trap_id, _)) = claimed {
+                TrappedAssets::<T>::remove(&claimer, trap_id);
+                Self::deposit_event(Event::<T>::AssetsClaimed { claimer, trap_id });
+            }
+        }
+    }
+}
+
+/// Asset trap that indexes the assets trapped for local accounts and hands them to `Inner`.
+pub struct IndexedAssetTrap<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T: Config, Inner: DropAssets> DropAssets for IndexedAssetTrap<T, Inner> {
+    fn drop_assets(origin: &Location, assets: AssetsInHolding, context: &XcmContext) -> Weight {
+        if assets.is_empty() {
+            return Inner::drop_assets(origin, assets, context);
+        }
+
+        let versioned = VersionedAssets::from(Assets::from(assets.clone()));
+        let weight = Inner::drop_assets(origin, assets, context);
+
+        if Pallet::<T>::index_trap(origin, versioned, context) {
+            weight.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+        } else {
+            weight
+        }
+    }
+}
+
+/// Asset claims that remove the claimed assets from the index once `Inner` accepts the claim.
+pub struct IndexedAssetClaims<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T: Config, Inner: ClaimAssets> ClaimAssets for IndexedAssetClaims<T, Inner> {
+    fn claim_assets(
+        origin: &Location,
+        ticket: &Location,
+        what: &Assets,
+        context: &XcmContext,
+    ) -> bool {
+        if !Inner::claim_assets(origin, ticket, what, context) {
+            return false;
+        }
+
+        Pallet::<T>::remove_claimed_trap(origin, what);
+        true
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    crate::{self as pallet_bridge_asset_recovery, IndexedAssetClaims},
+    core::cell::RefCell,
+    frame_support::{
+        parameter_types,
+        traits::{ConstU128, ConstU32},
+        weights::Weight,
+    },
+    parity_scale_codec::Encode,
+    sp_core::H256,
+    sp_runtime::{
+        traits::{BlakeTwo256, IdentityLookup},
+        AccountId32, BuildStorage,
+    },
+    xcm::latest::{prelude::*, InstructionError, PreparedMessage},
+    xcm_builder::{
+        AccountId32Aliases, DescribeAllTerminal, DescribeFamily, FixedWeightBounds,
+        FungibleAdapter, HashedDescription, IsConcrete,
+    },
+    xcm_executor::{
+        traits::{ClaimAssets, ConvertLocation, DropAssets},
+        AssetsInHolding,
+    },
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        BridgeAssetRecovery: pallet_bridge_asset_recovery,
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Block = Block;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+    type ExtensionsWeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 4];
+    type MaxLocks = ();
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ();
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type RuntimeFreezeReason = ();
+    type DoneSlashHandler = ();
+    type WeightInfo = ();
+}
+
+thread_local! {
+    pub static TRAPPED: RefCell<Vec<(Location, Assets)>> = const { RefCell::new(Vec::new()) };
+    pub static EXECUTED: RefCell<Vec<(Location, Xcm<RuntimeCall>)>> = const { RefCell::new(Vec::new()) };
+    pub static SENT: RefCell<Vec<(Location, Xcm<()>)>> = const { RefCell::new(Vec::new()) };
+    pub static ROUTER_FAILS: RefCell<bool> = const { RefCell::new(false) };
+}
+
+/// Keeps the trapped assets in memory, like the asset traps of pallet-xcm.
+pub struct MockAssetTrap;
+
+impl DropAssets for MockAssetTrap {
+    fn drop_assets(origin: &Location, assets: AssetsInHolding, _context: &XcmContext) -> Weight {
+        TRAPPED.with(|r| r.borrow_mut().push((origin.clone(), assets.into())));
+        Weight::zero()
+    }
+}
+
+impl ClaimAssets for MockAssetTrap {
+    fn claim_assets(
+        origin: &Location,
+        _ticket: &Location,
+        what: &Assets,
+        _context: &XcmContext,
+    ) -> bool {
+        TRAPPED.with(|r| {
+            let mut trapped = r.borrow_mut();
+            match trapped
+                .iter()
+                .position(|(trap_origin, assets)| trap_origin == origin && assets == what)
+            {
+                Some(index) => {
+                    trapped.remove(index);
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+pub struct MockPrepared(Xcm<RuntimeCall>);
+
+impl PreparedMessage for MockPrepared {
+    fn weight_of(&self) -> Weight {
+        Weight::zero()
+    }
+}
+
+/// Records the executed messages and claims the assets of `ClaimAsset` instructions.
+pub struct MockXcmExecutor;
+
+impl ExecuteXcm<RuntimeCall> for MockXcmExecutor {
+    type Prepared = MockPrepared;
+
+    fn prepare(
+        message: Xcm<RuntimeCall>,
+        _weight_limit: Weight,
+    ) -> Result<Self::Prepared, InstructionError> {
+        Ok(MockPrepared(message))
+    }
+
+    fn execute(
+        origin: impl Into<Location>,
+        pre: Self::Prepared,
+        id: &mut XcmHash,
+        _weight_credit: Weight,
+    ) -> Outcome {
+        let origin = origin.into();
+        let context = XcmContext::with_message_id(*id);
+
+        for (index, instruction) in pre.0 .0.iter().enumerate() {
+            if let ClaimAsset { assets, ticket } = instruction {
+                if !IndexedAssetClaims::<Test, MockAssetTrap>::claim_assets(
+                    &origin, ticket, assets, &context,
+                ) {
+                    return Outcome::Incomplete {
+                        used: Weight::zero(),
+                        error: InstructionError {
+                            index: index as u8,
+                            error: XcmError::UnknownClaim,
+                        },
+                    };
+                }
+            }
+        }
+
+        EXECUTED.with(|r| r.borrow_mut().push((origin, pre.0)));
+        Outcome::Complete {
+            used: Weight::zero(),
+        }
+    }
+
+    fn charge_fees(_location: impl Into<Location>, _fees: Assets) -> XcmResult {
+        Ok(())
+    }
+}
+
+/// Records the sent messages, or rejects them after `set_router_fails(true)`.
+pub struct MockXcmRouter;
+
+impl SendXcm for MockXcmRouter {
+    type Ticket = (Location, Xcm<()>);
+
+    fn validate(
+        destination: &mut Option<Location>,
+        message: &mut Option<Xcm<()>>,
+    ) -> SendResult<Self::Ticket> {
+        if ROUTER_FAILS.with(|r| *r.borrow()) {
+            return Err(SendError::Transport("router fails"));
+        }
+        let destination = destination.take().ok_or(SendError::MissingArgument)?;
+        let message = message.take().ok_or(SendError::MissingArgument)?;
+        Ok(((destination, message), Assets::new()))
+    }
+
+    fn deliver(ticket: Self::Ticket) -> Result<XcmHash, SendError> {
+        let hash = ticket.1.using_encoded(sp_io::hashing::blake2_256);
+        SENT.with(|r| r.borrow_mut().push(ticket));
+        Ok(hash)
+    }
+}
+
+pub fn set_router_fails(fails: bool) {
+    ROUTER_FAILS.with(|r| *r.borrow_mut() = fails);
+}
+
+pub fn sent_messages() -> Vec<(Location, Xcm<()>)> {
+    SENT.with(|r| r.borrow().clone())
+}
+
+pub fn sovereign_account(location: &Location) -> AccountId {
+    LocationToAccountId::convert_location(location).expect("location has an account")
+}
+
+pub fn trapped_in_asset_trap() -> Vec<(Location, Assets)> {
+    TRAPPED.with(|r| r.borrow().clone())
+}
+
+pub fn executed_messages() -> Vec<(Location, Xcm<RuntimeCall>)> {
+    EXECUTED.with(|r| r.borrow().clone())
+}
+
+parameter_types! {
+    pub HereLocation: Location = Location::here();
+    pub const AnyNetwork: Option<NetworkId> = None;
+    pub UnitWeightCost: Weight = Weight::from_parts(1_000, 0);
+    pub FallbackClaimer: AccountId = AccountId::new([0xff; 32]);
+    pub ContainerLocation: Location = Location::new(0, [Parachain(2000)]);
+}
+
+pub type LocalAssetTransactor =
+    FungibleAdapter<Balances, IsConcrete<HereLocation>, LocationToAccountId, AccountId, ()>;
+
+pub type LocationToAccountId = (
+    AccountId32Aliases<AnyNetwork, AccountId>,
+    HashedDescription<AccountId, DescribeFamily<DescribeAllTerminal>>,
+);
+
+impl pallet_bridge_asset_recovery::Config for Test {
+    type AssetTrap = MockAssetTrap;
+    type AssetTransactor = LocalAssetTransactor;
+    type XcmExecutor = MockXcmExecutor;
+    type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, ConstU32<100>>;
+    type XcmRouter = MockXcmRouter;
+    type FallbackClaimer = FallbackClaimer;
+    type WeightInfo = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type DeliveryHelper = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type ReachableDestination = ContainerLocation;
+}
+
+pub const ALICE: AccountId = AccountId::new([1; 32]);
+pub const BOB: AccountId = AccountId::new([2; 32]);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(ALICE, 1_000), (BOB, 1_000)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
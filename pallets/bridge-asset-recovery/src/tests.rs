@@ -0,0 +1,501 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::mock::*,
+    frame_support::{assert_noop, assert_ok},
+};
+
+const MESSAGE_ID: H256 = H256::repeat_byte(7);
+
+fn native(amount: u128) -> Assets {
+    (Location::here(), amount).into()
+}
+
+fn account_location(account: &AccountId) -> Location {
+    BridgeAssetRecovery::account_location(account)
+}
+
+fn trap_for(claimer: &AccountId, amount: u128) -> TrapId {
+    assert_ok!(BridgeAssetRecovery::trap_assets(
+        &account_location(&ALICE),
+        native(amount),
+        claimer,
+        MESSAGE_ID,
+    ));
+    NextTrapId::<Test>::get() - 1
+}
+
+#[test]
+fn trap_assets_withdraws_and_indexes_them() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+
+        assert_eq!(Balances::free_balance(ALICE), 900);
+        assert_eq!(
+            trapped_in_asset_trap(),
+            vec![(account_location(&BOB), native(100))]
+        );
+        assert_eq!(
+            TrappedAssets::<Test>::get(BOB, trap_id),
+            Some(TrappedAssetsRecord {
+                message_id: MESSAGE_ID,
+                origin: account_location(&BOB).into(),
+                assets: native(100).into(),
+                trapped_at: 1,
+            })
+        );
+        System::assert_last_event(
+            Event::AssetsTrapped {
+                claimer: BOB,
+                trap_id,
+                message_id: MESSAGE_ID,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn trap_assets_fails_if_holder_cannot_pay() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            BridgeAssetRecovery::trap_assets(
+                &account_location(&ALICE),
+                native(10_000),
+                &BOB,
+                MESSAGE_ID,
+            ),
+            Error::<Test>::WithdrawFailed
+        );
+        assert!(trapped_in_asset_trap().is_empty());
+    });
+}
+
+#[test]
+fn only_traps_for_local_accounts_are_indexed() {
+    new_test_ext().execute_with(|| {
+        let context = XcmContext::with_message_id([1; 32]);
+
+        IndexedAssetTrap::<Test, MockAssetTrap>::drop_assets(
+            &Location::parent(),
+            native(10).into(),
+            &context,
+        );
+        assert_eq!(trapped_in_asset_trap().len(), 1);
+        assert_eq!(TrappedAssets::<Test>::iter().count(), 0);
+
+        // Locations with a network are indexed as well
+        let origin = Location::new(
+            0,
+            [AccountId32 {
+                network: Some(NetworkId::Polkadot),
+                id: BOB.into(),
+            }],
+        );
+        IndexedAssetTrap::<Test, MockAssetTrap>::drop_assets(&origin, native(10).into(), &context);
+        let traps = BridgeAssetRecovery::trapped_assets(&BOB);
+        assert_eq!(traps.len(), 1);
+        assert_eq!(traps[0].1.origin, origin.into());
+        assert_eq!(traps[0].1.message_id, H256([1; 32]));
+    });
+}
+
+#[test]
+fn topic_is_used_as_message_id() {
+    new_test_ext().execute_with(|| {
+        let context = XcmContext {
+            origin: None,
+            message_id: [1; 32],
+            topic: Some([2; 32]),
+        };
+
+        IndexedAssetTrap::<Test, MockAssetTrap>::drop_assets(
+            &account_location(&BOB),
+            native(10).into(),
+            &context,
+        );
+        assert_eq!(
+            BridgeAssetRecovery::trapped_assets(&BOB)[0].1.message_id,
+            H256([2; 32])
+        );
+    });
+}
+
+#[test]
+fn trapped_assets_are_listed_by_claimer() {
+    new_test_ext().execute_with(|| {
+        let first = trap_for(&BOB, 10);
+        let other = trap_for(&ALICE, 20);
+        let second = trap_for(&BOB, 30);
+
+        let traps = BridgeAssetRecovery::trapped_assets(&BOB);
+        assert_eq!(
+            traps
+                .iter()
+                .map(|(trap_id, record)| (*trap_id, record.assets.clone()))
+                .collect::<Vec<_>>(),
+            vec![(first, native(10).into()), (second, native(30).into())]
+        );
+        assert_eq!(
+            BridgeAssetRecovery::trapped_assets(&ALICE)
+                .iter()
+                .map(|(trap_id, _)| *trap_id)
+                .collect::<Vec<_>>(),
+            vec![other]
+        );
+    });
+}
+
+#[test]
+fn claims_through_the_asset_trap_remove_the_index() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+        let context = XcmContext::with_message_id([0; 32]);
+
+        // Claims rejected by the asset trap keep the index
+        assert!(!IndexedAssetClaims::<Test, MockAssetTrap>::claim_assets(
+            &account_location(&BOB),
+            &Location::here(),
+            &native(50),
+            &context,
+        ));
+        assert!(TrappedAssets::<Test>::contains_key(BOB, trap_id));
+
+        assert!(IndexedAssetClaims::<Test, MockAssetTrap>::claim_assets(
+            &account_location(&BOB),
+            &Location::here(),
+            &native(100),
+            &context,
+        ));
+        assert!(!TrappedAssets::<Test>::contains_key(BOB, trap_id));
+        System::assert_last_event(
+            Event::AssetsClaimed {
+                claimer: BOB,
+                trap_id,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn claim_trapped_assets_deposits_them_locally() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+        let beneficiary = account_location(&ALICE);
+
+        assert_ok!(BridgeAssetRecovery::claim_trapped_assets(
+            RuntimeOrigin::signed(BOB),
+            trap_id,
+            Box::new(Location::here().into()),
+            Box::new(beneficiary.clone().into()),
+            0,
+        ));
+
+        assert_eq!(
+            executed_messages(),
+            vec![(
+                account_location(&BOB),
+                Xcm(vec![
+                    ClaimAsset {
+                        assets: native(100),
+                        ticket: Location::new(0, [GeneralIndex(5)]),
+                    },
+                    DepositAsset {
+                        assets: Wild(AllCounted(1)),
+                        beneficiary: beneficiary.clone(),
+                    },
+                ])
+            )]
+        );
+        assert!(!TrappedAssets::<Test>::contains_key(BOB, trap_id));
+        assert!(trapped_in_asset_trap().is_empty());
+        System::assert_last_event(
+            Event::AssetsRecovered {
+                claimer: BOB,
+                trap_id,
+                destination: Location::here().into(),
+                beneficiary: beneficiary.into(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn claim_trapped_assets_sends_them_to_another_chain() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+        let destination = Location::new(0, [Parachain(2000)]);
+        let beneficiary = Location::new(
+            0,
+            [AccountKey20 {
+                network: None,
+                key: [5; 20],
+            }],
+        );
+
+        assert_ok!(BridgeAssetRecovery::claim_trapped_assets(
+            RuntimeOrigin::signed(BOB),
+            trap_id,
+            Box::new(destination.clone().into()),
+            Box::new(beneficiary.clone().into()),
+            0,
+        ));
+
+        let (origin, message) = executed_messages().pop().unwrap();
+        assert_eq!(origin, account_location(&BOB));
+        assert_eq!(
+            message.0.last(),
+            Some(&InitiateTransfer {
+                destination,
+                remote_fees: Some(AssetTransferFilter::ReserveDeposit(Definite(native(100)))),
+                preserve_origin: false,
+                assets: vec![AssetTransferFilter::ReserveDeposit(Wild(AllCounted(1)))]
+                    .try_into()
+                    .unwrap(),
+                remote_xcm: Xcm(vec![DepositAsset {
+                    assets: Wild(AllCounted(1)),
+                    beneficiary,
+                }]),
+            })
+        );
+        assert!(!TrappedAssets::<Test>::contains_key(BOB, trap_id));
+    });
+}
+
+#[test]
+fn claim_trapped_assets_fails_for_invalid_fee_asset() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+
+        assert_noop!(
+            BridgeAssetRecovery::claim_trapped_assets(
+                RuntimeOrigin::signed(BOB),
+                trap_id,
+                Box::new(Location::new(0, [Parachain(2000)]).into()),
+                Box::new(account_location(&BOB).into()),
+                1,
+            ),
+            Error::<Test>::InvalidFeeAsset
+        );
+    });
+}
+
+#[test]
+fn only_the_claimer_can_claim_trapped_assets() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+
+        assert_noop!(
+            BridgeAssetRecovery::claim_trapped_assets(
+                RuntimeOrigin::signed(ALICE),
+                trap_id,
+                Box::new(Location::here().into()),
+                Box::new(account_location(&ALICE).into()),
+                0,
+            ),
+            Error::<Test>::UnknownTrap
+        );
+        assert_noop!(
+            BridgeAssetRecovery::claim_trapped_assets(
+                RuntimeOrigin::signed(BOB),
+                trap_id + 1,
+                Box::new(Location::here().into()),
+                Box::new(account_location(&BOB).into()),
+                0,
+            ),
+            Error::<Test>::UnknownTrap
+        );
+    });
+}
+
+#[test]
+fn claim_fails_if_asset_trap_rejects_it() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+        // Claimed directly from the asset trap without going through the index
+        TRAPPED.with(|r| r.borrow_mut().clear());
+
+        assert_noop!(
+            BridgeAssetRecovery::claim_trapped_assets(
+                RuntimeOrigin::signed(BOB),
+                trap_id,
+                Box::new(Location::here().into()),
+                Box::new(account_location(&BOB).into()),
+                0,
+            ),
+            Error::<Test>::ClaimFailed
+        );
+    });
+}
+
+fn container() -> Location {
+    Location::new(0, [Parachain(2000)])
+}
+
+fn transfer_message() -> Xcm<()> {
+    Xcm(vec![
+        ReserveAssetDeposited(native(10)),
+        DepositAsset {
+            assets: Wild(AllCounted(1)),
+            beneficiary: account_location(&BOB),
+        },
+    ])
+}
+
+fn send_transfer() -> Option<XcmHash> {
+    BridgeAssetRecovery::send_transfer(
+        container(),
+        transfer_message(),
+        (Location::here(), 10).into(),
+        account_location(&ALICE),
+        &BOB,
+        MESSAGE_ID,
+    )
+}
+
+#[test]
+fn send_transfer_pays_the_fee_and_sends_the_message() {
+    new_test_ext().execute_with(|| {
+        assert!(send_transfer().is_some());
+
+        assert_eq!(Balances::free_balance(ALICE), 990);
+        assert_eq!(Balances::free_balance(sovereign_account(&container())), 10);
+        assert_eq!(sent_messages(), vec![(container(), transfer_message())]);
+        assert!(BridgeAssetRecovery::failed_transfers(&BOB).is_empty());
+    });
+}
+
+#[test]
+fn send_transfer_keeps_the_transfer_if_it_cannot_be_sent() {
+    new_test_ext().execute_with(|| {
+        set_router_fails(true);
+
+        assert!(send_transfer().is_none());
+
+        // The fee is not paid
+        assert_eq!(Balances::free_balance(ALICE), 1_000);
+        assert_eq!(Balances::free_balance(sovereign_account(&container())), 0);
+        assert!(sent_messages().is_empty());
+        assert_eq!(
+            BridgeAssetRecovery::failed_transfers(&BOB),
+            vec![(
+                0,
+                FailedTransferRecord {
+                    message_id: MESSAGE_ID,
+                    destination: container().into(),
+                    message: VersionedXcm::from(transfer_message()),
+                    fee: Asset::from((Location::here(), 10)).into(),
+                    fee_payer: account_location(&ALICE).into(),
+                    failed_at: 1,
+                }
+            )]
+        );
+        System::assert_last_event(
+            Event::TransferFailed {
+                claimer: BOB,
+                transfer_id: 0,
+                message_id: MESSAGE_ID,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn send_transfer_keeps_the_transfer_if_the_fee_cannot_be_paid() {
+    new_test_ext().execute_with(|| {
+        assert!(BridgeAssetRecovery::send_transfer(
+            container(),
+            transfer_message(),
+            (Location::here(), 10_000).into(),
+            account_location(&ALICE),
+            &BOB,
+            MESSAGE_ID,
+        )
+        .is_none());
+
+        assert_eq!(Balances::free_balance(ALICE), 1_000);
+        assert!(sent_messages().is_empty());
+        assert_eq!(BridgeAssetRecovery::failed_transfers(&BOB).len(), 1);
+    });
+}
+
+#[test]
+fn failed_transfers_can_be_sent_again() {
+    new_test_ext().execute_with(|| {
+        set_router_fails(true);
+        assert!(send_transfer().is_none());
+        assert_noop!(
+            BridgeAssetRecovery::retry_failed_transfer(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::SendFailed
+        );
+
+        set_router_fails(false);
+        assert_ok!(BridgeAssetRecovery::retry_failed_transfer(
+            RuntimeOrigin::signed(BOB),
+            0
+        ));
+
+        assert_eq!(Balances::free_balance(ALICE), 990);
+        assert_eq!(Balances::free_balance(sovereign_account(&container())), 10);
+        assert_eq!(sent_messages(), vec![(container(), transfer_message())]);
+        assert!(!FailedTransfers::<Test>::contains_key(BOB, 0));
+        System::assert_last_event(
+            Event::FailedTransferSent {
+                claimer: BOB,
+                transfer_id: 0,
+                message_hash: transfer_message().using_encoded(sp_io::hashing::blake2_256),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn only_the_claimer_can_send_failed_transfers_again() {
+    new_test_ext().execute_with(|| {
+        set_router_fails(true);
+        assert!(send_transfer().is_none());
+        set_router_fails(false);
+
+        assert_noop!(
+            BridgeAssetRecovery::retry_failed_transfer(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::UnknownTransfer
+        );
+        assert_noop!(
+            BridgeAssetRecovery::retry_failed_transfer(RuntimeOrigin::signed(BOB), 1),
+            Error::<Test>::UnknownTransfer
+        );
+    });
+}
+
+#[test]
+fn traps_and_failed_transfers_use_different_ids() {
+    new_test_ext().execute_with(|| {
+        let trap_id = trap_for(&BOB, 100);
+        set_router_fails(true);
+        assert!(send_transfer().is_none());
+
+        assert_eq!(
+            BridgeAssetRecovery::failed_transfers(&BOB)[0].0,
+            trap_id + 1
+        );
+    });
+}
@@ -0,0 +1,155 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as BridgeGuard;
+use {
+    frame_benchmarking::{account, v2::*, BenchmarkError},
+    sp_core::H160,
+};
+
+const SEED: u32 = 0;
+const MAX_RELEASES: u32 = 100;
+
+fn delayed_transfer<T: Config>(n: u32) -> DelayedTransfer<T::AccountId> {
+    DelayedTransfer {
+        source: account("source", n, SEED),
+        recipient: H160::repeat_byte(0x02),
+        token_id: H256::repeat_byte(0x01),
+        amount: 1_000_000_000_000,
+        foreign_token: None,
+        v2_reward: None,
+    }
+}
+
+fn set_up_limits<T: Config>() {
+    TokenLimits::<T>::insert(
+        H256::repeat_byte(0x01),
+        TransferLimits {
+            inbound_limit: Some(u128::MAX),
+            outbound_limit: Some(u128::MAX),
+            period: 100u32.into(),
+            large_transfer_threshold: Some(1),
+            max_net_inbound: Some(u128::MAX),
+        },
+    );
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_token_limits() -> Result<(), BenchmarkError> {
+        let origin =
+            T::GuardOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let limits = TransferLimits {
+            inbound_limit: Some(1),
+            outbound_limit: Some(1),
+            period: 100u32.into(),
+            large_transfer_threshold: Some(1),
+            max_net_inbound: Some(1),
+        };
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            H256::repeat_byte(0x01),
+            Some(limits),
+        );
+
+        assert!(TokenLimits::<T>::get(H256::repeat_byte(0x01)).is_some());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn pause_bridge() -> Result<(), BenchmarkError> {
+        let origin =
+            T::GuardOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(BridgePaused::<T>::get());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn resume_bridge() -> Result<(), BenchmarkError> {
+        let origin =
+            T::GuardOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        BridgePaused::<T>::put(true);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(!BridgePaused::<T>::get());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_delayed_transfer() -> Result<(), BenchmarkError> {
+        let origin =
+            T::GuardOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        set_up_limits::<T>();
+
+        let transfer = delayed_transfer::<T>(0);
+        T::DelayedTransferExecutor::set_up_benchmark(&transfer);
+        BridgeGuard::<T>::note_transfer(
+            transfer.token_id,
+            TransferDirection::Outbound,
+            transfer.amount,
+        );
+        let transfer_id = BridgeGuard::<T>::delay_transfer(transfer)
+            .map_err(|_| BenchmarkError::Stop("Failed to delay transfer"))?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, transfer_id);
+
+        assert!(PendingTransfers::<T>::get(transfer_id).is_none());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn on_initialize(n: Linear<0, MAX_RELEASES>) -> Result<(), BenchmarkError> {
+        set_up_limits::<T>();
+
+        for i in 0..n {
+            let transfer = delayed_transfer::<T>(i);
+            T::DelayedTransferExecutor::set_up_benchmark(&transfer);
+            BridgeGuard::<T>::delay_transfer(transfer)
+                .map_err(|_| BenchmarkError::Stop("Failed to delay transfer"))?;
+        }
+
+        let release_at =
+            frame_system::Pallet::<T>::block_number().saturating_add(T::LargeTransferDelay::get());
+
+        #[block]
+        {
+            BridgeGuard::<T>::release_delayed_transfers(release_at);
+        }
+
+        assert_eq!(
+            NextTransferToRelease::<T>::get(),
+            n.min(T::MaxReleasesPerBlock::get()) as u64
+        );
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(BridgeGuard, crate::mock::new_test_ext(), crate::mock::Test,);
+}
@@ -0,0 +1,577 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! BridgeGuard pallet.
+//!
+//! This pallet protects the token flows of the Ethereum bridge. It only affects the components
+//! that consult it through the `BridgeTransferGuard` trait (native token transfers, inbound
+//! token processors and the XCM exporters to Ethereum), the rest of the chain keeps working when
+//! the bridge is paused.
+//!
+//! ## Rate limits
+//!
+//! Each token can have an inbound and an outbound limit, applied over a rolling window of
+//! `period` blocks. The window is approximated with two buckets: the volume of the current
+//! bucket plus the volume of the previous one, weighted by how much of it still overlaps the
+//! window.
+//!
+//! ## Large transfer delay
+//!
+//! Outbound transfers equal to or above `large_transfer_threshold` are not sent right away but
+//! queued for `LargeTransferDelay` blocks, giving governance time to cancel them. Queued
+//! transfers are released in order in `on_initialize`, at most `MaxReleasesPerBlock` per
+//! block, and never while the bridge is paused. The volume of a transfer that is cancelled or
+//! fails to be sent is removed from the bucket it was recorded in.
+//!
+//! ## Circuit breaker
+//!
+//! If the inbound volume of a token exceeds its outbound volume by more than
+//! `max_net_inbound` within the window, the bridge is paused automatically. The transfer that
+//! trips the breaker still goes through, the following ones are rejected until governance
+//! resumes the bridge.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use {
+    frame_support::{pallet_prelude::*, storage::with_storage_layer, traits::Get},
+    frame_system::pallet_prelude::*,
+    sp_core::H256,
+    sp_runtime::{
+        traits::{AtLeast32BitUnsigned, Saturating, Zero},
+        DispatchError, SaturatedConversion,
+    },
+    tp_bridge::{
+        BridgeTransferGuard, DelayedTransfer, DelayedTransferExecutor, DelayedTransferQueue,
+        TransferDirection,
+    },
+};
+
+pub use pallet::*;
+
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+    RuntimeDebug,
+)]
+pub struct TransferLimits<BlockNumber> {
+    /// Maximum volume that can come from Ethereum within the window.
+    pub inbound_limit: Option<u128>,
+    /// Maximum volume that can be sent to Ethereum within the window.
+    pub outbound_limit: Option<u128>,
+    /// Length of the rolling window, in blocks.
+    pub period: BlockNumber,
+    /// Outbound transfers of at least this amount are delayed.
+    pub large_transfer_threshold: Option<u128>,
+    /// Maximum difference between inbound and outbound volume before the bridge is paused.
+    pub max_net_inbound: Option<u128>,
+}
+
+#[derive(
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+    RuntimeDebug,
+)]
+pub struct WindowUsage<BlockNumber> {
+    /// First block of the current bucket.
+    pub window_start: BlockNumber,
+    /// Volume of the current bucket.
+    pub current: u128,
+    /// Volume of the previous bucket.
+    pub previous: u128,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> WindowUsage<BlockNumber> {
+    /// First block of the bucket containing `now`.
+    pub fn bucket_start(now: BlockNumber, period: BlockNumber) -> BlockNumber {
+        if period.is_zero() {
+            return now;
+        }
+        now.saturating_sub(now % period)
+    }
+
+    /// Move the buckets forward so that the current one contains `now`.
+    pub fn roll(&mut self, now: BlockNumber, period: BlockNumber) {
+        if period.is_zero() {
+            return;
+        }
+        let window_start = Self::bucket_start(now, period);
+        if window_start == self.window_start {
+            return;
+        }
+        if window_start == self.window_start.saturating_add(period) {
+            self.previous = self.current;
+        } else {
+            self.previous = 0;
+        }
+        self.current = 0;
+        self.window_start = window_start;
+    }
+
+    /// Volume within the rolling window ending at `now`. Expects `roll` to have been called.
+    pub fn usage(&self, now: BlockNumber, period: BlockNumber) -> u128 {
+        if period.is_zero() {
+            return self.current;
+        }
+        let period: u128 = period.saturated_into();
+        let elapsed: u128 = now.saturating_sub(self.window_start).saturated_into();
+        let remaining = period.saturating_sub(elapsed);
+        let weighted_previous = self.previous.saturating_mul(remaining) / period;
+        self.current.saturating_add(weighted_previous)
+    }
+
+    /// Remove some volume from the bucket starting at `bucket`, if it is still part of the
+    /// window. Expects `roll` to have been called.
+    pub fn remove(&mut self, bucket: BlockNumber, amount: u128, period: BlockNumber) {
+        if period.is_zero() || bucket == self.window_start {
+            self.current = self.current.saturating_sub(amount);
+        } else if bucket.saturating_add(period) == self.window_start {
+            self.previous = self.previous.saturating_sub(amount);
+        }
+    }
+}
+
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+    RuntimeDebug,
+)]
+pub struct PendingTransfer<AccountId, BlockNumber> {
+    pub transfer: DelayedTransfer<AccountId>,
+    /// Block from which the transfer can be released.
+    pub release_at: BlockNumber,
+    /// Bucket the outbound volume of the transfer was recorded in, so that it can be removed if
+    /// the transfer is cancelled or fails. `None` if the token had no limits.
+    pub volume_bucket: Option<BlockNumber>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    pub use crate::weights::WeightInfo;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Origin allowed to set limits, pause the bridge and cancel delayed transfers.
+        type GuardOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of blocks large outbound transfers are held before being sent.
+        #[pallet::constant]
+        type LargeTransferDelay: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of delayed transfers released in a single block.
+        #[pallet::constant]
+        type MaxReleasesPerBlock: Get<u32>;
+
+        /// Sends or refunds delayed transfers.
+        type DelayedTransferExecutor: DelayedTransferExecutor<Self::AccountId>;
+
+        /// The weight information of this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    /// Limits configured for each token. Tokens without limits are only affected by the pause.
+    #[pallet::storage]
+    pub type TokenLimits<T: Config> =
+        StorageMap<_, Blake2_128Concat, H256, TransferLimits<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Volume transferred for each token and direction.
+    #[pallet::storage]
+    pub type TransferVolumes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        H256,
+        Twox64Concat,
+        TransferDirection,
+        WindowUsage<BlockNumberFor<T>>,
+        ValueQuery,
+    >;
+
+    /// Whether all bridge token flows are paused.
+    #[pallet::storage]
+    pub type BridgePaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Outbound transfers waiting for their delay to be over.
+    #[pallet::storage]
+    pub type PendingTransfers<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        PendingTransfer<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// Id of the next delayed transfer.
+    #[pallet::storage]
+    pub type NextPendingTransferId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Id of the next delayed transfer to release. Transfers are released in the order they
+    /// were delayed, since all of them are held for the same number of blocks.
+    #[pallet::storage]
+    pub type NextTransferToRelease<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// The limits of a token were set or removed.
+        TokenLimitsSet {
+            token_id: H256,
+            limits: Option<TransferLimits<BlockNumberFor<T>>>,
+        },
+        /// Bridge token flows were paused by governance.
+        BridgePaused,
+        /// Bridge token flows were resumed.
+        BridgeResumed,
+        /// The inbound volume of a token exceeded its outbound volume by too much, the bridge
+        /// was paused.
+        CircuitBreakerTripped { token_id: H256, net_inbound: u128 },
+        /// An outbound transfer was delayed.
+        TransferDelayed {
+            transfer_id: u64,
+            token_id: H256,
+            amount: u128,
+            release_at: BlockNumberFor<T>,
+        },
+        /// A delayed transfer was sent.
+        DelayedTransferReleased { transfer_id: u64 },
+        /// A delayed transfer could not be sent and was refunded.
+        DelayedTransferFailed {
+            transfer_id: u64,
+            error: DispatchError,
+        },
+        /// A delayed transfer was cancelled and refunded.
+        DelayedTransferCancelled { transfer_id: u64 },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Bridge token flows are paused.
+        BridgePaused,
+        /// The transfer exceeds the limit of the token for the current window.
+        RateLimitExceeded,
+        /// The rolling window of the limits must be at least one block.
+        InvalidPeriod,
+        /// There is no delayed transfer with this id.
+        UnknownDelayedTransfer,
+        /// The delayed transfer could not be refunded.
+        RefundFailed,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            if BridgePaused::<T>::get() {
+                return T::DbWeight::get().reads(1);
+            }
+
+            let released = Self::release_delayed_transfers(now);
+            T::WeightInfo::on_initialize(released)
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set the limits of a token, or remove them if `limits` is `None`.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_token_limits())]
+        pub fn set_token_limits(
+            origin: OriginFor<T>,
+            token_id: H256,
+            limits: Option<TransferLimits<BlockNumberFor<T>>>,
+        ) -> DispatchResult {
+            T::GuardOrigin::ensure_origin(origin)?;
+
+            match &limits {
+                Some(limits) => {
+                    ensure!(!limits.period.is_zero(), Error::<T>::InvalidPeriod);
+                    TokenLimits::<T>::insert(token_id, limits);
+                }
+                None => TokenLimits::<T>::remove(token_id),
+            }
+
+            Self::deposit_event(Event::<T>::TokenLimitsSet { token_id, limits });
+            Ok(())
+        }
+
+        /// Pause all bridge token flows.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::pause_bridge())]
+        pub fn pause_bridge(origin: OriginFor<T>) -> DispatchResult {
+            T::GuardOrigin::ensure_origin(origin)?;
+
+            BridgePaused::<T>::put(true);
+            Self::deposit_event(Event::<T>::BridgePaused);
+            Ok(())
+        }
+
+        /// Resume bridge token flows, after a manual pause or a tripped circuit breaker.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::resume_bridge())]
+        pub fn resume_bridge(origin: OriginFor<T>) -> DispatchResult {
+            T::GuardOrigin::ensure_origin(origin)?;
+
+            BridgePaused::<T>::put(false);
+            Self::deposit_event(Event::<T>::BridgeResumed);
+            Ok(())
+        }
+
+        /// Cancel a delayed transfer and refund its amount to its source.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::cancel_delayed_transfer())]
+        pub fn cancel_delayed_transfer(origin: OriginFor<T>, transfer_id: u64) -> DispatchResult {
+            T::GuardOrigin::ensure_origin(origin)?;
+
+            let pending = PendingTransfers::<T>::take(transfer_id)
+                .ok_or(Error::<T>::UnknownDelayedTransfer)?;
+
+            T::DelayedTransferExecutor::refund(&pending.transfer)
+                .map_err(|_| Error::<T>::RefundFailed)?;
+
+            Self::remove_delayed_volume(&pending);
+
+            Self::deposit_event(Event::<T>::DelayedTransferCancelled { transfer_id });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Current volume of a token in the given direction.
+        pub fn current_usage(token_id: H256, direction: TransferDirection) -> u128 {
+            let Some(limits) = TokenLimits::<T>::get(token_id) else {
+                return 0;
+            };
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut usage = TransferVolumes::<T>::get(token_id, direction);
+            usage.roll(now, limits.period);
+            usage.usage(now, limits.period)
+        }
+
+        /// Add some volume to the window of a token.
+        fn record(token_id: H256, direction: TransferDirection, amount: u128) {
+            let Some(limits) = TokenLimits::<T>::get(token_id) else {
+                return;
+            };
+            let now = frame_system::Pallet::<T>::block_number();
+
+            TransferVolumes::<T>::mutate(token_id, direction, |usage| {
+                usage.roll(now, limits.period);
+                usage.current = usage.current.saturating_add(amount);
+            });
+
+            if direction != TransferDirection::Inbound {
+                return;
+            }
+
+            if let Some(max_net_inbound) = limits.max_net_inbound {
+                let net_inbound = Self::current_usage(token_id, TransferDirection::Inbound)
+                    .saturating_sub(Self::current_usage(token_id, TransferDirection::Outbound));
+
+                if net_inbound > max_net_inbound && !BridgePaused::<T>::get() {
+                    log::warn!(
+                        target: "bridge_guard",
+                        "Circuit breaker tripped for token {:?}, net inbound volume {}",
+                        token_id,
+                        net_inbound
+                    );
+                    BridgePaused::<T>::put(true);
+                    Self::deposit_event(Event::<T>::CircuitBreakerTripped {
+                        token_id,
+                        net_inbound,
+                    });
+                }
+            }
+        }
+
+        /// Remove the outbound volume of a delayed transfer that was not sent from the bucket it
+        /// was recorded in.
+        fn remove_delayed_volume(pending: &PendingTransfer<T::AccountId, BlockNumberFor<T>>) {
+            let token_id = pending.transfer.token_id;
+            let (Some(bucket), Some(limits)) =
+                (pending.volume_bucket, TokenLimits::<T>::get(token_id))
+            else {
+                return;
+            };
+            let now = frame_system::Pallet::<T>::block_number();
+
+            TransferVolumes::<T>::mutate(token_id, TransferDirection::Outbound, |usage| {
+                usage.roll(now, limits.period);
+                usage.remove(bucket, pending.transfer.amount, limits.period);
+            });
+        }
+
+        /// Release the delayed transfers that are due, returning how many were processed.
+        pub(crate) fn release_delayed_transfers(now: BlockNumberFor<T>) -> u32 {
+            let next_id = NextPendingTransferId::<T>::get();
+            let mut cursor = NextTransferToRelease::<T>::get();
+            let mut processed = 0u32;
+
+            while cursor < next_id && processed < T::MaxReleasesPerBlock::get() {
+                if let Some(pending) = PendingTransfers::<T>::get(cursor) {
+                    if pending.release_at > now {
+                        break;
+                    }
+                    PendingTransfers::<T>::remove(cursor);
+
+                    let result = with_storage_layer(|| {
+                        T::DelayedTransferExecutor::execute(&pending.transfer)
+                    });
+
+                    match result {
+                        Ok(()) => Self::deposit_event(Event::<T>::DelayedTransferReleased {
+                            transfer_id: cursor,
+                        }),
+                        Err(error) => {
+                            log::error!(
+                                target: "bridge_guard",
+                                "Failed to release delayed transfer {}: {:?}",
+                                cursor,
+                                error
+                            );
+                            if let Err(refund_error) = with_storage_layer(|| {
+                                T::DelayedTransferExecutor::refund(&pending.transfer)
+                            }) {
+                                log::error!(
+                                    target: "bridge_guard",
+                                    "Failed to refund delayed transfer {}: {:?}",
+                                    cursor,
+                                    refund_error
+                                );
+                            }
+                            Self::remove_delayed_volume(&pending);
+                            Self::deposit_event(Event::<T>::DelayedTransferFailed {
+                                transfer_id: cursor,
+                                error,
+                            });
+                        }
+                    }
+                }
+                // Cancelled transfers leave gaps, they still count towards the limit since
+                // reading them is not free.
+                processed.saturating_inc();
+                cursor.saturating_inc();
+            }
+
+            NextTransferToRelease::<T>::put(cursor);
+            processed
+        }
+    }
+}
+
+impl<T: Config> BridgeTransferGuard for Pallet<T> {
+    fn ensure_transfer_allowed(
+        token_id: H256,
+        direction: TransferDirection,
+        amount: u128,
+    ) -> DispatchResult {
+        ensure!(!BridgePaused::<T>::get(), Error::<T>::BridgePaused);
+
+        let Some(limits) = TokenLimits::<T>::get(token_id) else {
+            return Ok(());
+        };
+        let limit = match direction {
+            TransferDirection::Inbound => limits.inbound_limit,
+            TransferDirection::Outbound => limits.outbound_limit,
+        };
+        if let Some(limit) = limit {
+            let usage = Self::current_usage(token_id, direction);
+            ensure!(
+                usage.saturating_add(amount) <= limit,
+                Error::<T>::RateLimitExceeded
+            );
+        }
+
+        Ok(())
+    }
+
+    fn note_transfer(token_id: H256, direction: TransferDirection, amount: u128) {
+        Self::record(token_id, direction, amount);
+    }
+}
+
+impl<T: Config> DelayedTransferQueue<T::AccountId> for Pallet<T> {
+    fn should_delay(token_id: H256, amount: u128) -> bool {
+        if T::LargeTransferDelay::get().is_zero() {
+            return false;
+        }
+        TokenLimits::<T>::get(token_id)
+            .and_then(|limits| limits.large_transfer_threshold)
+            .is_some_and(|threshold| amount >= threshold)
+    }
+
+    fn delay_transfer(transfer: DelayedTransfer<T::AccountId>) -> Result<u64, DispatchError> {
+        let transfer_id = NextPendingTransferId::<T>::get();
+        let now = frame_system::Pallet::<T>::block_number();
+        let release_at = now.saturating_add(T::LargeTransferDelay::get());
+        // The volume of the transfer is noted right before it is delayed
+        let volume_bucket = TokenLimits::<T>::get(transfer.token_id)
+            .map(|limits| WindowUsage::bucket_start(now, limits.period));
+
+        Self::deposit_event(Event::<T>::TransferDelayed {
+            transfer_id,
+            token_id: transfer.token_id,
+            amount: transfer.amount,
+            release_at,
+        });
+
+        PendingTransfers::<T>::insert(
+            transfer_id,
+            PendingTransfer {
+                transfer,
+                release_at,
+                volume_bucket,
+            },
+        );
+        NextPendingTransferId::<T>::put(transfer_id.saturating_add(1));
+
+        Ok(transfer_id)
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    crate as pallet_bridge_guard,
+    core::cell::RefCell,
+    frame_support::{parameter_types, traits::ConstU32},
+    frame_system::EnsureRoot,
+    sp_core::H256,
+    sp_runtime::{
+        traits::{BlakeTwo256, IdentityLookup},
+        BuildStorage, DispatchError, DispatchResult,
+    },
+    tp_bridge::{DelayedTransfer, DelayedTransferExecutor},
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        BridgeGuard: pallet_bridge_guard,
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Nonce = u64;
+    type Block = Block;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+    type RuntimeTask = ();
+    type SingleBlockMigrations = ();
+    type MultiBlockMigrator = ();
+    type PreInherents = ();
+    type PostInherents = ();
+    type PostTransactions = ();
+    type ExtensionsWeightInfo = ();
+}
+
+thread_local! {
+    pub static EXECUTED: RefCell<Vec<DelayedTransfer<u64>>> = const { RefCell::new(Vec::new()) };
+    pub static REFUNDED: RefCell<Vec<DelayedTransfer<u64>>> = const { RefCell::new(Vec::new()) };
+    pub static EXECUTION_FAILS: RefCell<bool> = const { RefCell::new(false) };
+}
+
+pub struct MockExecutor;
+
+impl DelayedTransferExecutor<u64> for MockExecutor {
+    fn execute(transfer: &DelayedTransfer<u64>) -> DispatchResult {
+        if EXECUTION_FAILS.with(|r| *r.borrow()) {
+            return Err(DispatchError::Other("execution failed"));
+        }
+        EXECUTED.with(|r| r.borrow_mut().push(transfer.clone()));
+        Ok(())
+    }
+
+    fn refund(transfer: &DelayedTransfer<u64>) -> DispatchResult {
+        REFUNDED.with(|r| r.borrow_mut().push(transfer.clone()));
+        Ok(())
+    }
+}
+
+pub fn executed_transfers() -> Vec<DelayedTransfer<u64>> {
+    EXECUTED.with(|r| r.borrow().clone())
+}
+
+pub fn refunded_transfers() -> Vec<DelayedTransfer<u64>> {
+    REFUNDED.with(|r| r.borrow().clone())
+}
+
+pub fn set_execution_fails(fails: bool) {
+    EXECUTION_FAILS.with(|r| *r.borrow_mut() = fails);
+}
+
+parameter_types! {
+    pub const LargeTransferDelay: u64 = 10;
+}
+
+impl pallet_bridge_guard::Config for Test {
+    type GuardOrigin = EnsureRoot<u64>;
+    type LargeTransferDelay = LargeTransferDelay;
+    type MaxReleasesPerBlock = ConstU32<2>;
+    type DelayedTransferExecutor = MockExecutor;
+    type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    let ext: sp_io::TestExternalities = t.into();
+
+    ext
+}
+
+pub const ALICE: u64 = 1;
+
+pub fn run_to_block(n: u64) {
+    System::run_to_block_with::<AllPalletsWithSystem>(n, frame_system::RunToBlockHooks::default());
+}
@@ -0,0 +1,442 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    super::*,
+    crate::mock::*,
+    frame_support::{assert_noop, assert_ok},
+    sp_core::H160,
+    sp_runtime::DispatchError::BadOrigin,
+};
+
+const TOKEN: H256 = H256::repeat_byte(1);
+
+fn limits(
+    inbound_limit: Option<u128>,
+    outbound_limit: Option<u128>,
+    large_transfer_threshold: Option<u128>,
+    max_net_inbound: Option<u128>,
+) -> TransferLimits<u64> {
+    TransferLimits {
+        inbound_limit,
+        outbound_limit,
+        period: 100,
+        large_transfer_threshold,
+        max_net_inbound,
+    }
+}
+
+fn delayed_transfer(amount: u128) -> DelayedTransfer<u64> {
+    DelayedTransfer {
+        source: ALICE,
+        recipient: H160::repeat_byte(2),
+        token_id: TOKEN,
+        foreign_token: None,
+        amount,
+        v2_reward: None,
+    }
+}
+
+#[test]
+fn only_guard_origin_can_manage_the_bridge() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_noop!(
+            BridgeGuard::set_token_limits(
+                RuntimeOrigin::signed(ALICE),
+                TOKEN,
+                Some(limits(None, None, None, None))
+            ),
+            BadOrigin
+        );
+        assert_noop!(
+            BridgeGuard::pause_bridge(RuntimeOrigin::signed(ALICE)),
+            BadOrigin
+        );
+        assert_noop!(
+            BridgeGuard::resume_bridge(RuntimeOrigin::signed(ALICE)),
+            BadOrigin
+        );
+        assert_noop!(
+            BridgeGuard::cancel_delayed_transfer(RuntimeOrigin::signed(ALICE), 0),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_token_limits_rejects_empty_period() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        let mut token_limits = limits(Some(10), None, None, None);
+        token_limits.period = 0;
+
+        assert_noop!(
+            BridgeGuard::set_token_limits(RuntimeOrigin::root(), TOKEN, Some(token_limits)),
+            Error::<Test>::InvalidPeriod
+        );
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(Some(10), None, None, None))
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::TokenLimitsSet {
+            token_id: TOKEN,
+            limits: Some(limits(Some(10), None, None, None)),
+        }));
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            None
+        ));
+        assert!(TokenLimits::<Test>::get(TOKEN).is_none());
+    });
+}
+
+#[test]
+fn transfers_without_limits_are_allowed() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::ensure_transfer_allowed(
+            TOKEN,
+            TransferDirection::Inbound,
+            u128::MAX
+        ));
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Inbound, u128::MAX);
+        assert!(!BridgeGuard::should_delay(TOKEN, u128::MAX));
+    });
+}
+
+#[test]
+fn rate_limit_is_applied_per_direction() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(Some(100), Some(50), None, None))
+        ));
+
+        assert_ok!(BridgeGuard::ensure_transfer_allowed(
+            TOKEN,
+            TransferDirection::Outbound,
+            40
+        ));
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 40);
+
+        assert_noop!(
+            BridgeGuard::ensure_transfer_allowed(TOKEN, TransferDirection::Outbound, 11),
+            Error::<Test>::RateLimitExceeded
+        );
+        assert_ok!(BridgeGuard::ensure_transfer_allowed(
+            TOKEN,
+            TransferDirection::Outbound,
+            10
+        ));
+
+        // Inbound volume is tracked separately
+        assert_ok!(BridgeGuard::ensure_transfer_allowed(
+            TOKEN,
+            TransferDirection::Inbound,
+            100
+        ));
+        assert_noop!(
+            BridgeGuard::ensure_transfer_allowed(TOKEN, TransferDirection::Inbound, 101),
+            Error::<Test>::RateLimitExceeded
+        );
+    });
+}
+
+#[test]
+fn rate_limit_window_rolls_over() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(None, Some(100), None, None))
+        ));
+
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 100);
+        assert_eq!(
+            BridgeGuard::current_usage(TOKEN, TransferDirection::Outbound),
+            100
+        );
+
+        // A quarter into the next bucket, three quarters of the previous one still count
+        run_to_block(125);
+        assert_eq!(
+            BridgeGuard::current_usage(TOKEN, TransferDirection::Outbound),
+            75
+        );
+        assert_ok!(BridgeGuard::ensure_transfer_allowed(
+            TOKEN,
+            TransferDirection::Outbound,
+            25
+        ));
+        assert_noop!(
+            BridgeGuard::ensure_transfer_allowed(TOKEN, TransferDirection::Outbound, 26),
+            Error::<Test>::RateLimitExceeded
+        );
+
+        // After two full periods nothing is left
+        run_to_block(200);
+        assert_eq!(
+            BridgeGuard::current_usage(TOKEN, TransferDirection::Outbound),
+            0
+        );
+    });
+}
+
+#[test]
+fn paused_bridge_rejects_transfers() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::pause_bridge(RuntimeOrigin::root()));
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::BridgePaused));
+
+        assert_noop!(
+            BridgeGuard::ensure_transfer_allowed(TOKEN, TransferDirection::Inbound, 1),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            BridgeGuard::ensure_transfer_allowed(TOKEN, TransferDirection::Outbound, 1),
+            Error::<Test>::BridgePaused
+        );
+
+        assert_ok!(BridgeGuard::resume_bridge(RuntimeOrigin::root()));
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::BridgeResumed));
+
+        assert_ok!(BridgeGuard::ensure_transfer_allowed(
+            TOKEN,
+            TransferDirection::Inbound,
+            1
+        ));
+    });
+}
+
+#[test]
+fn circuit_breaker_trips_on_net_inbound_volume() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(None, None, None, Some(50)))
+        ));
+
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 30);
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Inbound, 80);
+        assert!(!BridgePaused::<Test>::get());
+
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Inbound, 1);
+        assert!(BridgePaused::<Test>::get());
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::CircuitBreakerTripped {
+            token_id: TOKEN,
+            net_inbound: 51,
+        }));
+
+        assert_noop!(
+            BridgeGuard::ensure_transfer_allowed(TOKEN, TransferDirection::Inbound, 1),
+            Error::<Test>::BridgePaused
+        );
+    });
+}
+
+#[test]
+fn large_transfers_are_delayed_and_released() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(None, None, Some(1_000), None))
+        ));
+
+        assert!(!BridgeGuard::should_delay(TOKEN, 999));
+        assert!(BridgeGuard::should_delay(TOKEN, 1_000));
+
+        assert_eq!(BridgeGuard::delay_transfer(delayed_transfer(1_000)), Ok(0));
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::TransferDelayed {
+            transfer_id: 0,
+            token_id: TOKEN,
+            amount: 1_000,
+            release_at: 11,
+        }));
+
+        run_to_block(10);
+        assert!(executed_transfers().is_empty());
+
+        run_to_block(11);
+        assert_eq!(executed_transfers(), vec![delayed_transfer(1_000)]);
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::DelayedTransferReleased {
+            transfer_id: 0,
+        }));
+        assert!(PendingTransfers::<Test>::get(0).is_none());
+        assert_eq!(NextTransferToRelease::<Test>::get(), 1);
+    });
+}
+
+#[test]
+fn delayed_transfers_are_released_in_bounded_batches() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        for amount in 1..=3 {
+            assert_ok!(BridgeGuard::delay_transfer(delayed_transfer(amount)));
+        }
+
+        run_to_block(11);
+        assert_eq!(
+            executed_transfers(),
+            vec![delayed_transfer(1), delayed_transfer(2)]
+        );
+
+        run_to_block(12);
+        assert_eq!(executed_transfers().len(), 3);
+    });
+}
+
+#[test]
+fn delayed_transfers_are_not_released_while_paused() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::delay_transfer(delayed_transfer(1_000)));
+        assert_ok!(BridgeGuard::pause_bridge(RuntimeOrigin::root()));
+
+        run_to_block(20);
+        assert!(executed_transfers().is_empty());
+
+        assert_ok!(BridgeGuard::resume_bridge(RuntimeOrigin::root()));
+        run_to_block(21);
+        assert_eq!(executed_transfers(), vec![delayed_transfer(1_000)]);
+    });
+}
+
+#[test]
+fn cancel_delayed_transfer_refunds_it() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(None, Some(2_000), Some(1_000), None))
+        ));
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 1_500);
+        assert_ok!(BridgeGuard::delay_transfer(delayed_transfer(1_500)));
+
+        assert_ok!(BridgeGuard::cancel_delayed_transfer(
+            RuntimeOrigin::root(),
+            0
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::DelayedTransferCancelled {
+            transfer_id: 0,
+        }));
+        assert_eq!(refunded_transfers(), vec![delayed_transfer(1_500)]);
+        // The cancelled volume no longer counts towards the limit
+        assert_eq!(
+            BridgeGuard::current_usage(TOKEN, TransferDirection::Outbound),
+            0
+        );
+
+        assert_noop!(
+            BridgeGuard::cancel_delayed_transfer(RuntimeOrigin::root(), 0),
+            Error::<Test>::UnknownDelayedTransfer
+        );
+
+        // The cancelled transfer is skipped when releasing
+        assert_ok!(BridgeGuard::delay_transfer(delayed_transfer(1_000)));
+        run_to_block(11);
+        assert_eq!(executed_transfers(), vec![delayed_transfer(1_000)]);
+    });
+}
+
+#[test]
+fn cancel_delayed_transfer_removes_volume_from_its_bucket() {
+    new_test_ext().execute_with(|| {
+        run_to_block(95);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(None, Some(2_000), Some(1_000), None))
+        ));
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 1_500);
+        assert_ok!(BridgeGuard::delay_transfer(delayed_transfer(1_500)));
+        assert_eq!(
+            PendingTransfers::<Test>::get(0).unwrap().volume_bucket,
+            Some(0)
+        );
+
+        // Cancelled once the window moved to the next bucket
+        run_to_block(101);
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 100);
+        assert_ok!(BridgeGuard::cancel_delayed_transfer(
+            RuntimeOrigin::root(),
+            0
+        ));
+
+        assert_eq!(
+            TransferVolumes::<Test>::get(TOKEN, TransferDirection::Outbound),
+            WindowUsage {
+                window_start: 100,
+                current: 100,
+                previous: 0,
+            }
+        );
+    });
+}
+
+#[test]
+fn failed_release_refunds_the_transfer() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(BridgeGuard::set_token_limits(
+            RuntimeOrigin::root(),
+            TOKEN,
+            Some(limits(None, Some(2_000), Some(1_000), None))
+        ));
+        set_execution_fails(true);
+        BridgeGuard::note_transfer(TOKEN, TransferDirection::Outbound, 1_000);
+        assert_ok!(BridgeGuard::delay_transfer(delayed_transfer(1_000)));
+
+        run_to_block(11);
+        assert!(executed_transfers().is_empty());
+        assert_eq!(refunded_transfers(), vec![delayed_transfer(1_000)]);
+        System::assert_last_event(RuntimeEvent::BridgeGuard(Event::DelayedTransferFailed {
+            transfer_id: 0,
+            error: DispatchError::Other("execution failed"),
+        }));
+        // The volume of the transfer that was not sent no longer counts towards the limit
+        assert_eq!(
+            BridgeGuard::current_usage(TOKEN, TransferDirection::Outbound),
+            0
+        );
+    });
+}
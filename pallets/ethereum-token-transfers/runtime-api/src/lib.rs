@@ -0,0 +1,46 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for Ethereum Token Transfers pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet_ethereum_token_transfers::{TransferFeeQuote, TransferredToken};
+use {sp_core::H160, sp_runtime::DispatchError};
+
+sp_api::decl_runtime_apis! {
+    pub trait EthereumTokenTransfersApi<Balance>
+    where
+        Balance: parity_scale_codec::Codec,
+    {
+        /// Fees charged to send `amount` of `token` to `recipient` through Snowbridge V1.
+        fn quote_transfer(
+            token: TransferredToken,
+            amount: u128,
+            recipient: H160,
+        ) -> Result<TransferFeeQuote<Balance>, DispatchError>;
+
+        /// Fees charged to send `amount` of `token` to `recipient` through Snowbridge V2, paying
+        /// `reward` (the minimum reward if `None`) and adding `tip` later on.
+        fn quote_transfer_v2(
+            token: TransferredToken,
+            amount: u128,
+            recipient: H160,
+            reward: Option<u128>,
+            tip: u128,
+        ) -> Result<TransferFeeQuote<Balance>, DispatchError>;
+    }
+}
@@ -192,6 +192,56 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn transfer_foreign_token() -> Result<(), BenchmarkError> {
+        let channel_id = ChannelId::new([4u8; 32]);
+        let agent_id = AgentId::from([5u8; 32]);
+        let para_id: ParaId = 2000u32.into();
+
+        CurrentChannelInfo::<T>::put(ChannelInfo {
+            channel_id,
+            para_id,
+            agent_id,
+        });
+        T::BenchmarkHelper::set_up_channel(channel_id, para_id, agent_id);
+
+        let token = H160::repeat_byte(0x11);
+        let location = EthereumTokenTransfers::<T>::foreign_token_location(token)
+            .map_err(|_| BenchmarkError::Stop("Invalid foreign token location"))?;
+        T::BenchmarkHelper::set_up_foreign_token(location);
+        let (asset_id, _) = EthereumTokenTransfers::<T>::foreign_token_ids(token)
+            .map_err(|_| BenchmarkError::Stop("Foreign token not registered"))?;
+
+        let (caller, _) = create_funded_user::<T>("account", 1, 1000000000);
+        let amount_transferred = 10_000_000_000_000u128;
+        T::ForeignAssets::mint_into(
+            asset_id.clone(),
+            &caller,
+            amount_transferred.saturating_mul(2).into(),
+        )
+        .map_err(|_| BenchmarkError::Stop("Failed to mint foreign token"))?;
+        let recipient = H160::from([1u8; 20]);
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller.clone()),
+            token,
+            amount_transferred,
+            recipient,
+        );
+
+        assert_eq!(
+            <T::ForeignAssets as fungibles::Inspect<_>>::balance(asset_id, &caller),
+            amount_transferred.into()
+        );
+        assert!(matches!(
+            ethereum_token_transfers_events::<T>().last(),
+            Some(Event::ForeignTokenTransferred { .. })
+        ));
+
+        Ok(())
+    }
+
     #[benchmark]
     fn add_tip() -> Result<(), BenchmarkError> {
         let caller: T::AccountId = whitelisted_caller();
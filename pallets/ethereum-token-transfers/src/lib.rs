@@ -39,6 +39,18 @@
 //! double-spending and to track how much of the native token is sent to Ethereum.
 //!
 //! After that, the message is delivered to Ethereum through the T::OutboundQueue implementation.
+//!
+//! ### transfer_foreign_token:
+//!
+//! Sends back to Ethereum a token that was bridged from there and is held in T::ForeignAssets. The
+//! amount is burnt from the caller and released on Ethereum with a TransferToken command executed
+//! by the channel's agent (UnlockNativeToken in V2). Fees are charged in the native token, the same
+//! way as for transfer_native_token.
+//!
+//! Transfers are checked against T::BridgeGuard before any funds are moved. Transfers that the
+//! guard considers too large are charged as usual but delayed, the pallet sends them later
+//! through its DelayedTransferExecutor implementation (or refunds the amount, without the fees,
+//! if they are cancelled).
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -62,14 +74,16 @@ use {
         pallet_prelude::*,
         traits::{
             fungible::{self, Inspect, Mutate},
-            tokens::Preservation,
+            fungibles::{self, Mutate as FungiblesMutate},
+            tokens::{Fortitude, Precision, Preservation},
             Get,
         },
     },
     frame_system::{pallet_prelude::*, unique},
-    snowbridge_core::{reward::MessageId, AgentId, ChannelId, ParaId, TokenId},
+    snowbridge_core::{reward::MessageId, AgentId, ChannelId, ParaId, TokenId, TokenIdOf},
     snowbridge_outbound_queue_primitives::v1::{
-        Command as SnowbridgeCommand, Message as SnowbridgeMessage, SendMessage,
+        AgentExecuteCommand, Command as SnowbridgeCommand, Message as SnowbridgeMessage,
+        SendMessage,
     },
     snowbridge_outbound_queue_primitives::v2::{
         Command as SnowbridgeCommandV2, Message as SnowbridgeMessageV2,
@@ -78,10 +92,14 @@ use {
     snowbridge_outbound_queue_primitives::SendError,
     sp_core::{H160, H256},
     sp_runtime::{
-        traits::{MaybeEquivalence, TryConvert},
+        traits::{MaybeEquivalence, Saturating, TryConvert, Zero},
         DispatchResult,
     },
-    tp_bridge::{ChannelInfo, ConvertLocation, EthereumSystemChannelManager, TicketInfo},
+    tp_bridge::{
+        BridgeTransferGuard, ChannelInfo, ConvertLocation, DelayedTransfer,
+        DelayedTransferExecutor, DelayedTransferQueue, EthereumSystemChannelManager, TicketInfo,
+        TransferDirection,
+    },
     xcm::prelude::*,
 };
 
@@ -93,6 +111,38 @@ pub use pallet::*;
 pub type BalanceOf<T> =
     <<T as pallet::Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
+pub type ForeignAssetIdOf<T> = <<T as pallet::Config>::ForeignAssets as fungibles::Inspect<
+    <T as frame_system::Config>::AccountId,
+>>::AssetId;
+
+/// Fees charged to send a token transfer to Ethereum.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct TransferFeeQuote<Balance> {
+    /// Fee for processing the message on this chain, only charged by V1 transfers.
+    pub local_fee: Balance,
+    /// Fee paid to the relayers delivering the message to Ethereum. For V2 transfers, this is
+    /// the reward.
+    pub remote_fee: Balance,
+    /// Tip added on top of the V2 reward through `add_tip`.
+    pub tip: Balance,
+    /// Sum of all the fees.
+    pub total_fee: Balance,
+    /// Total amount of the native currency taken from the sender, including the transferred
+    /// amount for native token transfers.
+    pub total_cost: Balance,
+    /// Whether the bridge guard will hold the transfer for a while before sending it.
+    pub delayed: bool,
+}
+
+/// Token sent to Ethereum by a transfer.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub enum TransferredToken {
+    /// Native token of this chain.
+    Native,
+    /// Token bridged from Ethereum, by its address there, `H160::zero()` being Ether.
+    Foreign(H160),
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -150,6 +200,16 @@ pub mod pallet {
         /// The minimum reward for v2 transfers
         type MinV2Reward: Get<u128>;
 
+        /// Tokens bridged from Ethereum, burnt when they are sent back.
+        type ForeignAssets: fungibles::Inspect<Self::AccountId, Balance: From<u128>>
+            + fungibles::Mutate<Self::AccountId>;
+
+        /// How to convert the location of a token bridged from Ethereum into its asset id.
+        type ForeignAssetIdFromLocation: MaybeEquivalence<Location, ForeignAssetIdOf<Self>>;
+
+        /// Rate limits, delays and pauses applied to transfers.
+        type BridgeGuard: DelayedTransferQueue<Self::AccountId>;
+
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
 
@@ -182,6 +242,52 @@ pub mod pallet {
             amount: u128,
             fee: BalanceOf<T>,
         },
+        /// A native token transfer was charged but delayed by the bridge guard.
+        NativeTokenTransferDelayed {
+            transfer_id: u64,
+            source: T::AccountId,
+            recipient: H160,
+            token_id: H256,
+            amount: u128,
+            fee: BalanceOf<T>,
+        },
+        /// A delayed native token transfer was sent to Ethereum.
+        DelayedNativeTokenTransferSent {
+            message_id: H256,
+            channel_id: ChannelId,
+            source: T::AccountId,
+            recipient: H160,
+            token_id: H256,
+            amount: u128,
+        },
+        /// Some token bridged from Ethereum was successfully sent back.
+        ForeignTokenTransferred {
+            message_id: H256,
+            channel_id: ChannelId,
+            source: T::AccountId,
+            recipient: H160,
+            token: H160,
+            amount: u128,
+            fee: BalanceOf<T>,
+        },
+        /// A foreign token transfer was charged but delayed by the bridge guard.
+        ForeignTokenTransferDelayed {
+            transfer_id: u64,
+            source: T::AccountId,
+            recipient: H160,
+            token: H160,
+            amount: u128,
+            fee: BalanceOf<T>,
+        },
+        /// A delayed foreign token transfer was sent to Ethereum.
+        DelayedForeignTokenTransferSent {
+            message_id: H256,
+            channel_id: ChannelId,
+            source: T::AccountId,
+            recipient: H160,
+            token: H160,
+            amount: u128,
+        },
     }
 
     // Errors
@@ -203,6 +309,8 @@ pub mod pallet {
         LocationToOriginConversionFailed,
         LocationReanchorFailed,
         MinV2RewardNotAchieved,
+        /// The foreign token is not registered in this chain.
+        UnknownForeignToken,
     }
 
     #[pallet::pallet]
@@ -266,21 +374,16 @@ pub mod pallet {
             let channel_info =
                 CurrentChannelInfo::<T>::get().ok_or(Error::<T>::ChannelInfoNotSet)?;
 
-            let token_location = T::TokenLocationReanchored::get();
-            let token_id = T::TokenIdFromLocation::convert_back(&token_location)
-                .ok_or(Error::<T>::UnknownLocationForToken)?;
+            let token_id = Self::native_token_id()?;
+
+            T::BridgeGuard::ensure_transfer_allowed(token_id, TransferDirection::Outbound, amount)?;
 
-            let command = SnowbridgeCommand::MintForeignToken {
+            let message = Self::mint_foreign_token_message(
+                channel_info.channel_id,
                 token_id,
                 recipient,
                 amount,
-            };
-
-            let message = SnowbridgeMessage {
-                id: None,
-                channel_id: channel_info.channel_id,
-                command,
-            };
+            );
 
             let (ticket, fee) = T::OutboundQueue::validate(&message)
                 .map_err(|err| Error::<T>::InvalidMessage(err))?;
@@ -301,6 +404,20 @@ pub mod pallet {
                 Preservation::Preserve,
             )?;
 
+            T::BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+
+            if T::BridgeGuard::should_delay(token_id, amount) {
+                return Self::delay_transfer(
+                    source,
+                    recipient,
+                    token_id,
+                    None,
+                    amount,
+                    None,
+                    fee.total(),
+                );
+            }
+
             let message_id = ticket.message_id();
 
             T::OutboundQueue::deliver(ticket)
@@ -343,9 +460,9 @@ pub mod pallet {
             let channel_info =
                 CurrentChannelInfo::<T>::get().ok_or(Error::<T>::ChannelInfoNotSet)?;
 
-            let token_location = T::TokenLocationReanchored::get();
-            let token_id = T::TokenIdFromLocation::convert_back(&token_location)
-                .ok_or(Error::<T>::UnknownLocationForToken)?;
+            let token_id = Self::native_token_id()?;
+
+            T::BridgeGuard::ensure_transfer_allowed(token_id, TransferDirection::Outbound, amount)?;
 
             // Transfer amount to Ethereum's sovereign account.
             T::Currency::transfer(
@@ -363,30 +480,26 @@ pub mod pallet {
                 Preservation::Preserve,
             )?;
 
+            T::BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+
+            if T::BridgeGuard::should_delay(token_id, amount) {
+                return Self::delay_transfer(
+                    source,
+                    recipient,
+                    token_id,
+                    None,
+                    amount,
+                    Some(reward),
+                    reward.into(),
+                );
+            }
+
             let command = SnowbridgeCommandV2::MintForeignToken {
                 token_id,
                 recipient,
                 amount,
             };
-
-            let id = unique((origin, &command)).into();
-            let mut commands: Vec<SnowbridgeCommandV2> = Vec::new();
-            commands.push(command);
-
-            let message = SnowbridgeMessageV2 {
-                id,
-                commands: BoundedVec::try_from(commands)
-                    .map_err(|_| Error::<T>::TooManyCommands)?,
-                fee: reward,
-                origin,
-            };
-
-            let ticket = T::OutboundQueueV2::validate(&message)
-                .map_err(|err| Error::<T>::InvalidMessage(err))?;
-            let message_id = ticket.message_id();
-
-            T::OutboundQueueV2::deliver(ticket)
-                .map_err(|err| Error::<T>::TransferMessageNotSent(err))?;
+            let message_id = Self::send_v2(origin, command, reward)?;
 
             Self::deposit_event(Event::<T>::NativeTokenTransferred {
                 message_id,
@@ -417,9 +530,439 @@ pub mod pallet {
 
             Ok(())
         }
+
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::transfer_foreign_token())]
+        pub fn transfer_foreign_token(
+            origin: OriginFor<T>,
+            token: H160,
+            amount: u128,
+            recipient: H160,
+        ) -> DispatchResult {
+            let source = ensure_signed(origin)?;
+
+            let channel_info =
+                CurrentChannelInfo::<T>::get().ok_or(Error::<T>::ChannelInfoNotSet)?;
+
+            let (asset_id, token_id) = Self::foreign_token_ids(token)?;
+
+            T::BridgeGuard::ensure_transfer_allowed(token_id, TransferDirection::Outbound, amount)?;
+
+            let message = Self::transfer_token_message(&channel_info, token, recipient, amount);
+
+            let (ticket, fee) = T::OutboundQueue::validate(&message)
+                .map_err(|err| Error::<T>::InvalidMessage(err))?;
+
+            // Transfer fees to FeesAccount.
+            T::Currency::transfer(
+                &source,
+                &T::FeesAccount::get(),
+                fee.total(),
+                Preservation::Preserve,
+            )?;
+
+            // The token is released from its reserve on Ethereum, so burn it here.
+            T::ForeignAssets::burn_from(
+                asset_id,
+                &source,
+                amount.into(),
+                Preservation::Expendable,
+                Precision::Exact,
+                Fortitude::Polite,
+            )?;
+
+            T::BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+
+            if T::BridgeGuard::should_delay(token_id, amount) {
+                return Self::delay_transfer(
+                    source,
+                    recipient,
+                    token_id,
+                    Some(token),
+                    amount,
+                    None,
+                    fee.total(),
+                );
+            }
+
+            let message_id = ticket.message_id();
+
+            T::OutboundQueue::deliver(ticket)
+                .map_err(|err| Error::<T>::TransferMessageNotSent(err))?;
+
+            Self::deposit_event(Event::<T>::ForeignTokenTransferred {
+                message_id,
+                channel_id: channel_info.channel_id,
+                source,
+                recipient,
+                token,
+                amount,
+                fee: fee.total(),
+            });
+
+            Ok(())
+        }
+
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::transfer_foreign_token())]
+        pub fn transfer_foreign_token_v2(
+            origin: OriginFor<T>,
+            token: H160,
+            amount: u128,
+            recipient: H160,
+            reward: u128,
+        ) -> DispatchResult {
+            let source = ensure_signed(origin.clone())?;
+            let origin_location = T::OriginToLocation::try_convert(origin)
+                .map_err(|_| Error::<T>::OriginConversionFailed)?;
+            let origin = Self::location_to_message_origin(origin_location)?;
+
+            ensure!(T::ShouldUseV2::get(), Error::<T>::V2SendingIsNotAllowed);
+
+            // Check for minimum fee
+            ensure!(
+                reward >= T::MinV2Reward::get(),
+                Error::<T>::MinV2RewardNotAchieved
+            );
+
+            let channel_info =
+                CurrentChannelInfo::<T>::get().ok_or(Error::<T>::ChannelInfoNotSet)?;
+
+            let (asset_id, token_id) = Self::foreign_token_ids(token)?;
+
+            T::BridgeGuard::ensure_transfer_allowed(token_id, TransferDirection::Outbound, amount)?;
+
+            // The token is released from its reserve on Ethereum, so burn it here.
+            T::ForeignAssets::burn_from(
+                asset_id,
+                &source,
+                amount.into(),
+                Preservation::Expendable,
+                Precision::Exact,
+                Fortitude::Polite,
+            )?;
+
+            // Transfer fee to fee's account.
+            T::Currency::transfer(
+                &source,
+                &T::FeesAccount::get(),
+                reward.into(),
+                Preservation::Preserve,
+            )?;
+
+            T::BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+
+            if T::BridgeGuard::should_delay(token_id, amount) {
+                return Self::delay_transfer(
+                    source,
+                    recipient,
+                    token_id,
+                    Some(token),
+                    amount,
+                    Some(reward),
+                    reward.into(),
+                );
+            }
+
+            let command = SnowbridgeCommandV2::UnlockNativeToken {
+                token,
+                recipient,
+                amount,
+            };
+            let message_id = Self::send_v2(origin, command, reward)?;
+
+            Self::deposit_event(Event::<T>::ForeignTokenTransferred {
+                message_id,
+                channel_id: channel_info.channel_id,
+                source,
+                recipient,
+                token,
+                amount,
+                fee: reward.into(),
+            });
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// TokenId of the native token on Ethereum.
+        pub fn native_token_id() -> Result<TokenId, Error<T>> {
+            let token_location = T::TokenLocationReanchored::get();
+            T::TokenIdFromLocation::convert_back(&token_location)
+                .ok_or(Error::<T>::UnknownLocationForToken)
+        }
+
+        /// Location of a token bridged from Ethereum, `H160::zero()` being Ether.
+        pub fn foreign_token_location(token: H160) -> Result<Location, Error<T>> {
+            let ethereum_location = T::EthereumLocation::get();
+            if token.is_zero() {
+                return Ok(ethereum_location);
+            }
+
+            let network = ethereum_location
+                .interior()
+                .global_consensus()
+                .map_err(|_| Error::<T>::UnknownForeignToken)?;
+            ethereum_location
+                .pushed_with_interior(AccountKey20 {
+                    network: Some(network),
+                    key: token.into(),
+                })
+                .map_err(|_| Error::<T>::UnknownForeignToken)
+        }
+
+        /// Asset id of a token bridged from Ethereum, along with the TokenId used by the bridge
+        /// guard for it.
+        pub fn foreign_token_ids(token: H160) -> Result<(ForeignAssetIdOf<T>, TokenId), Error<T>> {
+            let location = Self::foreign_token_location(token)?;
+            let asset_id = T::ForeignAssetIdFromLocation::convert(&location)
+                .ok_or(Error::<T>::UnknownForeignToken)?;
+            let token_id =
+                TokenIdOf::convert_location(&location).ok_or(Error::<T>::UnknownForeignToken)?;
+
+            Ok((asset_id, token_id))
+        }
+
+        /// TokenId of a transferred token, failing if it is a foreign token not registered in
+        /// this chain.
+        pub fn transferred_token_id(token: TransferredToken) -> Result<TokenId, Error<T>> {
+            match token {
+                TransferredToken::Native => Self::native_token_id(),
+                TransferredToken::Foreign(token) => {
+                    Self::foreign_token_ids(token).map(|(_, token_id)| token_id)
+                }
+            }
+        }
+
+        /// Fees that `transfer_native_token` or `transfer_foreign_token` would charge to send
+        /// `amount` of `token` to `recipient`.
+        pub fn quote_transfer(
+            token: TransferredToken,
+            amount: u128,
+            recipient: H160,
+        ) -> Result<TransferFeeQuote<BalanceOf<T>>, DispatchError> {
+            let channel_info =
+                CurrentChannelInfo::<T>::get().ok_or(Error::<T>::ChannelInfoNotSet)?;
+            let token_id = Self::transferred_token_id(token)?;
+
+            T::BridgeGuard::ensure_transfer_allowed(token_id, TransferDirection::Outbound, amount)?;
+
+            let message = match token {
+                TransferredToken::Native => Self::mint_foreign_token_message(
+                    channel_info.channel_id,
+                    token_id,
+                    recipient,
+                    amount,
+                ),
+                TransferredToken::Foreign(token) => {
+                    Self::transfer_token_message(&channel_info, token, recipient, amount)
+                }
+            };
+            let (_, fee) = T::OutboundQueue::validate(&message)
+                .map_err(|err| Error::<T>::InvalidMessage(err))?;
+
+            Ok(Self::fee_quote(
+                token,
+                token_id,
+                amount,
+                fee.local,
+                fee.remote,
+                Zero::zero(),
+            ))
+        }
+
+        /// Fees that `transfer_native_token_v2` or `transfer_foreign_token_v2` would charge to
+        /// send `amount` of `token` to `recipient`, using `reward` (or the minimum reward if
+        /// `None`) and adding `tip` afterwards.
+        pub fn quote_transfer_v2(
+            token: TransferredToken,
+            amount: u128,
+            recipient: H160,
+            reward: Option<u128>,
+            tip: u128,
+        ) -> Result<TransferFeeQuote<BalanceOf<T>>, DispatchError> {
+            ensure!(T::ShouldUseV2::get(), Error::<T>::V2SendingIsNotAllowed);
+            let reward = reward.unwrap_or_else(T::MinV2Reward::get);
+            ensure!(
+                reward >= T::MinV2Reward::get(),
+                Error::<T>::MinV2RewardNotAchieved
+            );
+            ensure!(
+                CurrentChannelInfo::<T>::get().is_some(),
+                Error::<T>::ChannelInfoNotSet
+            );
+            let token_id = Self::transferred_token_id(token)?;
+
+            T::BridgeGuard::ensure_transfer_allowed(token_id, TransferDirection::Outbound, amount)?;
+
+            let command = match token {
+                TransferredToken::Native => SnowbridgeCommandV2::MintForeignToken {
+                    token_id,
+                    recipient,
+                    amount,
+                },
+                TransferredToken::Foreign(token) => SnowbridgeCommandV2::UnlockNativeToken {
+                    token,
+                    recipient,
+                    amount,
+                },
+            };
+            // The origin of the sender only changes the message id, not its validity
+            let message = Self::message_v2(H256::zero(), command, reward)?;
+            T::OutboundQueueV2::validate(&message)
+                .map_err(|err| Error::<T>::InvalidMessage(err))?;
+
+            Ok(Self::fee_quote(
+                token,
+                token_id,
+                amount,
+                Zero::zero(),
+                reward.into(),
+                tip.into(),
+            ))
+        }
+
+        fn fee_quote(
+            token: TransferredToken,
+            token_id: TokenId,
+            amount: u128,
+            local_fee: BalanceOf<T>,
+            remote_fee: BalanceOf<T>,
+            tip: BalanceOf<T>,
+        ) -> TransferFeeQuote<BalanceOf<T>> {
+            let total_fee = local_fee.saturating_add(remote_fee).saturating_add(tip);
+            // Foreign tokens are burnt from their asset, only the fees are paid in native currency
+            let total_cost = match token {
+                TransferredToken::Native => total_fee.saturating_add(amount.into()),
+                TransferredToken::Foreign(_) => total_fee,
+            };
+
+            TransferFeeQuote {
+                local_fee,
+                remote_fee,
+                tip,
+                total_fee,
+                total_cost,
+                delayed: T::BridgeGuard::should_delay(token_id, amount),
+            }
+        }
+
+        fn mint_foreign_token_message(
+            channel_id: ChannelId,
+            token_id: TokenId,
+            recipient: H160,
+            amount: u128,
+        ) -> SnowbridgeMessage {
+            SnowbridgeMessage {
+                id: None,
+                channel_id,
+                command: SnowbridgeCommand::MintForeignToken {
+                    token_id,
+                    recipient,
+                    amount,
+                },
+            }
+        }
+
+        fn transfer_token_message(
+            channel_info: &ChannelInfo,
+            token: H160,
+            recipient: H160,
+            amount: u128,
+        ) -> SnowbridgeMessage {
+            SnowbridgeMessage {
+                id: None,
+                channel_id: channel_info.channel_id,
+                command: SnowbridgeCommand::AgentExecute {
+                    agent_id: channel_info.agent_id,
+                    command: AgentExecuteCommand::TransferToken {
+                        token,
+                        recipient,
+                        amount,
+                    },
+                },
+            }
+        }
+
+        /// Build a V2 message with a single command.
+        fn message_v2(
+            origin: H256,
+            command: SnowbridgeCommandV2,
+            reward: u128,
+        ) -> Result<SnowbridgeMessageV2, Error<T>> {
+            let id = unique((origin, &command)).into();
+            let mut commands: Vec<SnowbridgeCommandV2> = Vec::new();
+            commands.push(command);
+
+            Ok(SnowbridgeMessageV2 {
+                id,
+                commands: BoundedVec::try_from(commands)
+                    .map_err(|_| Error::<T>::TooManyCommands)?,
+                fee: reward,
+                origin,
+            })
+        }
+
+        /// Build, validate and deliver a V2 message with a single command, returning its id.
+        fn send_v2(
+            origin: H256,
+            command: SnowbridgeCommandV2,
+            reward: u128,
+        ) -> Result<H256, Error<T>> {
+            let message = Self::message_v2(origin, command, reward)?;
+
+            let ticket = T::OutboundQueueV2::validate(&message)
+                .map_err(|err| Error::<T>::InvalidMessage(err))?;
+            let message_id = ticket.message_id();
+
+            T::OutboundQueueV2::deliver(ticket)
+                .map_err(|err| Error::<T>::TransferMessageNotSent(err))?;
+
+            Ok(message_id)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        fn delay_transfer(
+            source: T::AccountId,
+            recipient: H160,
+            token_id: TokenId,
+            foreign_token: Option<H160>,
+            amount: u128,
+            v2_reward: Option<u128>,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            let transfer_id = T::BridgeGuard::delay_transfer(DelayedTransfer {
+                source: source.clone(),
+                recipient,
+                token_id,
+                foreign_token,
+                amount,
+                v2_reward,
+            })?;
+
+            let event = match foreign_token {
+                None => Event::<T>::NativeTokenTransferDelayed {
+                    transfer_id,
+                    source,
+                    recipient,
+                    token_id,
+                    amount,
+                    fee,
+                },
+                Some(token) => Event::<T>::ForeignTokenTransferDelayed {
+                    transfer_id,
+                    source,
+                    recipient,
+                    token,
+                    amount,
+                    fee,
+                },
+            };
+            Self::deposit_event(event);
+
+            Ok(())
+        }
+
         pub fn location_to_message_origin(location: Location) -> Result<H256, Error<T>> {
             let reanchored_location = Self::reanchor(location)?;
             T::LocationHashOf::convert_location(&reanchored_location)
@@ -434,6 +977,132 @@ pub mod pallet {
     }
 }
 
+impl<T: Config> DelayedTransferExecutor<T::AccountId> for Pallet<T> {
+    fn execute(transfer: &DelayedTransfer<T::AccountId>) -> DispatchResult {
+        let channel_info = CurrentChannelInfo::<T>::get().ok_or(Error::<T>::ChannelInfoNotSet)?;
+
+        let message_id = match transfer.v2_reward {
+            None => {
+                let message = match transfer.foreign_token {
+                    None => Self::mint_foreign_token_message(
+                        channel_info.channel_id,
+                        transfer.token_id,
+                        transfer.recipient,
+                        transfer.amount,
+                    ),
+                    Some(token) => Self::transfer_token_message(
+                        &channel_info,
+                        token,
+                        transfer.recipient,
+                        transfer.amount,
+                    ),
+                };
+                // Fees were already charged when the transfer was requested.
+                let (ticket, _fee) = T::OutboundQueue::validate(&message)
+                    .map_err(|err| Error::<T>::InvalidMessage(err))?;
+                let message_id = ticket.message_id();
+
+                T::OutboundQueue::deliver(ticket)
+                    .map_err(|err| Error::<T>::TransferMessageNotSent(err))?;
+                message_id
+            }
+            Some(reward) => {
+                ensure!(T::ShouldUseV2::get(), Error::<T>::V2SendingIsNotAllowed);
+
+                let origin_location = T::OriginToLocation::try_convert(
+                    frame_system::RawOrigin::Signed(transfer.source.clone()).into(),
+                )
+                .map_err(|_| Error::<T>::OriginConversionFailed)?;
+                let origin = Self::location_to_message_origin(origin_location)?;
+
+                let command = match transfer.foreign_token {
+                    None => SnowbridgeCommandV2::MintForeignToken {
+                        token_id: transfer.token_id,
+                        recipient: transfer.recipient,
+                        amount: transfer.amount,
+                    },
+                    Some(token) => SnowbridgeCommandV2::UnlockNativeToken {
+                        token,
+                        recipient: transfer.recipient,
+                        amount: transfer.amount,
+                    },
+                };
+
+                Self::send_v2(origin, command, reward)?
+            }
+        };
+
+        let event = match transfer.foreign_token {
+            None => Event::<T>::DelayedNativeTokenTransferSent {
+                message_id,
+                channel_id: channel_info.channel_id,
+                source: transfer.source.clone(),
+                recipient: transfer.recipient,
+                token_id: transfer.token_id,
+                amount: transfer.amount,
+            },
+            Some(token) => Event::<T>::DelayedForeignTokenTransferSent {
+                message_id,
+                channel_id: channel_info.channel_id,
+                source: transfer.source.clone(),
+                recipient: transfer.recipient,
+                token,
+                amount: transfer.amount,
+            },
+        };
+        Self::deposit_event(event);
+
+        Ok(())
+    }
+
+    fn refund(transfer: &DelayedTransfer<T::AccountId>) -> DispatchResult {
+        match transfer.foreign_token {
+            None => {
+                T::Currency::transfer(
+                    &T::EthereumSovereignAccount::get(),
+                    &transfer.source,
+                    transfer.amount.into(),
+                    Preservation::Preserve,
+                )?;
+            }
+            Some(token) => {
+                // Foreign tokens were burnt when the transfer was requested.
+                let (asset_id, _) = Self::foreign_token_ids(token)?;
+                T::ForeignAssets::mint_into(asset_id, &transfer.source, transfer.amount.into())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_up_benchmark(transfer: &DelayedTransfer<T::AccountId>) {
+        let channel_info = ChannelInfo {
+            channel_id: ChannelId::new([4u8; 32]),
+            para_id: 2000u32.into(),
+            agent_id: AgentId::from([5u8; 32]),
+        };
+        T::BenchmarkHelper::set_up_channel(
+            channel_info.channel_id,
+            channel_info.para_id,
+            channel_info.agent_id,
+        );
+        T::BenchmarkHelper::set_up_token(T::TokenLocationReanchored::get(), transfer.token_id);
+        if let Some(location) = transfer
+            .foreign_token
+            .and_then(|token| Self::foreign_token_location(token).ok())
+        {
+            T::BenchmarkHelper::set_up_foreign_token(location);
+        }
+        CurrentChannelInfo::<T>::put(channel_info);
+
+        let _ = T::Currency::mint_into(
+            &T::EthereumSovereignAccount::get(),
+            transfer.amount.saturating_mul(2).into(),
+        );
+    }
+}
+
 pub struct DenyTipHandler<T>(core::marker::PhantomData<T>);
 
 impl<T, Origin> TipHandler<Origin> for DenyTipHandler<T> {
@@ -20,7 +20,10 @@ use {
     frame_support::{
         pallet_prelude::OriginTrait,
         parameter_types,
-        traits::{fungible::Mutate, tokens::Preservation, ConstU32, ConstU64, EnsureOrigin},
+        traits::{
+            fungible::Mutate, tokens::Preservation, AsEnsureOriginWithArg, ConstU128, ConstU32,
+            ConstU64, EnsureOrigin,
+        },
     },
     pallet_balances::AccountData,
     parity_scale_codec::{Decode, Encode},
@@ -29,13 +32,16 @@ use {
     },
     snowbridge_outbound_queue_primitives::v1::{Fee, Message},
     snowbridge_outbound_queue_primitives::{SendError, SendMessageFeeProvider},
-    sp_core::H256,
+    sp_core::{H160, H256},
     sp_runtime::{
         traits::{BlakeTwo256, IdentityLookup, MaybeEquivalence, TryConvert},
-        BuildStorage, DispatchResult,
+        BuildStorage, DispatchError, DispatchResult,
     },
     std::marker::PhantomData,
-    tp_bridge::{ChannelInfo, EthereumSystemChannelManager, TicketInfo},
+    tp_bridge::{
+        BridgeTransferGuard, ChannelInfo, DelayedTransfer, DelayedTransferQueue,
+        EthereumSystemChannelManager, TicketInfo, TransferDirection,
+    },
     xcm::prelude::*,
     xcm_builder::{DescribeLocation, HashedDescription},
 };
@@ -49,6 +55,7 @@ frame_support::construct_runtime!(
         System: frame_system,
         Balances: pallet_balances,
         Timestamp: pallet_timestamp,
+        ForeignAssets: pallet_assets,
         EthereumTokenTransfers: pallet_ethereum_token_transfers,
     }
 );
@@ -120,6 +127,30 @@ impl pallet_timestamp::Config for Test {
     type WeightInfo = ();
 }
 
+impl pallet_assets::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = u128;
+    type AssetId = u16;
+    type AssetIdParameter = u16;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<u64>>;
+    type ForceOrigin = frame_system::EnsureRoot<u64>;
+    type AssetDeposit = ConstU128<0>;
+    type MetadataDepositBase = ConstU128<0>;
+    type MetadataDepositPerByte = ConstU128<0>;
+    type ApprovalDeposit = ConstU128<0>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+    type CallbackHandle = ();
+    type AssetAccountDeposit = ConstU128<0>;
+    type RemoveItemsLimit = ConstU32<1000>;
+    type Holder = ();
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+}
+
 thread_local! {
     /// Detect we sent a message to Ethereum.
     pub static SENT_ETHEREUM_MESSAGE_NONCE: RefCell<u64> = const { RefCell::new(0) };
@@ -127,6 +158,12 @@ thread_local! {
     pub static SENT_ETHEREUM_MESSAGE_NONCE_V2: RefCell<u64> = const { RefCell::new(0) };
     /// Detect we called EthereumSystemHandler hook.
     pub static ETHEREUM_SYSTEM_HANDLER_NONCE: RefCell<u64> = const { RefCell::new(0) };
+    /// Whether MockBridgeGuard rejects all transfers.
+    pub static BRIDGE_GUARD_PAUSED: RefCell<bool> = const { RefCell::new(false) };
+    /// Amount from which MockBridgeGuard delays transfers.
+    pub static BRIDGE_GUARD_DELAY_THRESHOLD: RefCell<Option<u128>> = const { RefCell::new(None) };
+    /// Transfers delayed by MockBridgeGuard.
+    pub static DELAYED_TRANSFERS: RefCell<Vec<DelayedTransfer<u64>>> = const { RefCell::new(Vec::new()) };
 }
 
 pub fn sent_ethereum_message_nonce() -> u64 {
@@ -137,6 +174,51 @@ pub fn sent_ethereum_message_nonce_v2() -> u64 {
     SENT_ETHEREUM_MESSAGE_NONCE_V2.with(|q| (*q.borrow()))
 }
 
+pub fn set_bridge_guard_paused(paused: bool) {
+    BRIDGE_GUARD_PAUSED.with(|q| *q.borrow_mut() = paused);
+}
+
+pub fn set_bridge_guard_delay_threshold(threshold: Option<u128>) {
+    BRIDGE_GUARD_DELAY_THRESHOLD.with(|q| *q.borrow_mut() = threshold);
+}
+
+pub fn delayed_transfers() -> Vec<DelayedTransfer<u64>> {
+    DELAYED_TRANSFERS.with(|q| q.borrow().clone())
+}
+
+pub struct MockBridgeGuard;
+
+impl BridgeTransferGuard for MockBridgeGuard {
+    fn ensure_transfer_allowed(
+        _token_id: H256,
+        _direction: TransferDirection,
+        _amount: u128,
+    ) -> DispatchResult {
+        if BRIDGE_GUARD_PAUSED.with(|q| *q.borrow()) {
+            return Err(DispatchError::Other("bridge paused"));
+        }
+        Ok(())
+    }
+
+    fn note_transfer(_token_id: H256, _direction: TransferDirection, _amount: u128) {}
+}
+
+impl DelayedTransferQueue<u64> for MockBridgeGuard {
+    fn should_delay(_token_id: H256, amount: u128) -> bool {
+        BRIDGE_GUARD_DELAY_THRESHOLD
+            .with(|q| *q.borrow())
+            .is_some_and(|threshold| amount >= threshold)
+    }
+
+    fn delay_transfer(transfer: DelayedTransfer<u64>) -> Result<u64, DispatchError> {
+        DELAYED_TRANSFERS.with(|q| {
+            let mut delayed = q.borrow_mut();
+            delayed.push(transfer);
+            Ok(delayed.len() as u64 - 1)
+        })
+    }
+}
+
 pub fn ethereum_system_handler_nonce() -> u64 {
     ETHEREUM_SYSTEM_HANDLER_NONCE.with(|q| (*q.borrow()))
 }
@@ -223,6 +305,30 @@ parameter_types! {
     pub UniversalLocation: InteriorLocation = ThisNetwork::get().into();
 }
 
+/// ERC20 token bridged from Ethereum and registered as a foreign asset.
+pub const ERC20_TOKEN: H160 = H160::repeat_byte(0x11);
+pub const ERC20_ASSET_ID: u16 = 1;
+/// Ether, registered as a foreign asset.
+pub const ETHER_ASSET_ID: u16 = 2;
+
+pub struct MockForeignAssetIdConvert;
+impl MaybeEquivalence<Location, u16> for MockForeignAssetIdConvert {
+    fn convert(loc: &Location) -> Option<u16> {
+        if *loc == EthereumLocation::get() {
+            return Some(ETHER_ASSET_ID);
+        }
+        match loc.unpack() {
+            (1, [GlobalConsensus(_), AccountKey20 { key, .. }]) if *key == ERC20_TOKEN.0 => {
+                Some(ERC20_ASSET_ID)
+            }
+            _ => None,
+        }
+    }
+    fn convert_back(_id: &u16) -> Option<Location> {
+        None
+    }
+}
+
 pub struct MockTokenIdConvert;
 impl MaybeEquivalence<TokenId, Location> for MockTokenIdConvert {
     fn convert(_id: &TokenId) -> Option<Location> {
@@ -297,6 +403,9 @@ impl pallet_ethereum_token_transfers::Config for Test {
     type LocationHashOf = MockAgentIdOf;
     type EthereumLocation = EthereumLocation;
     type MinV2Reward = MinV2Reward;
+    type ForeignAssets = ForeignAssets;
+    type ForeignAssetIdFromLocation = MockForeignAssetIdConvert;
+    type BridgeGuard = MockBridgeGuard;
     type OriginToLocation = LocalOriginToLocation;
     type UniversalLocation = UniversalLocation;
     type WeightInfo = ();
@@ -319,6 +428,17 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     .assimilate_storage(&mut t)
     .unwrap();
 
+    pallet_assets::GenesisConfig::<Test> {
+        assets: vec![
+            (ERC20_ASSET_ID, EthereumSovereignAccount::get(), true, 1),
+            (ETHER_ASSET_ID, EthereumSovereignAccount::get(), true, 1),
+        ],
+        accounts: vec![(ERC20_ASSET_ID, ALICE, 100), (ETHER_ASSET_ID, ALICE, 100)],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
     let ext: sp_io::TestExternalities = t.into();
 
     ext
@@ -19,7 +19,7 @@ use {
     crate::mock::*,
     frame_support::{assert_noop, assert_ok},
     snowbridge_core::{reward::MessageId, AgentId, ChannelId, ParaId},
-    sp_runtime::DispatchError::BadOrigin,
+    sp_runtime::{DispatchError, DispatchError::BadOrigin},
 };
 
 #[test]
@@ -493,3 +493,568 @@ fn test_add_tip_transfers_balances_succeeds() {
         assert_eq!(recipient_balance_after - recipient_balance_before, amount);
     });
 }
+
+#[test]
+fn test_transfer_native_token_rejected_by_bridge_guard() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        ShouldUseV2::set(&true);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        set_bridge_guard_paused(true);
+
+        assert_noop!(
+            EthereumTokenTransfers::transfer_native_token(
+                RuntimeOrigin::signed(ALICE),
+                10u128,
+                H160::default(),
+            ),
+            DispatchError::Other("bridge paused")
+        );
+        assert_noop!(
+            EthereumTokenTransfers::transfer_native_token_v2(
+                RuntimeOrigin::signed(ALICE),
+                10u128,
+                H160::default(),
+                10u128
+            ),
+            DispatchError::Other("bridge paused")
+        );
+        assert_eq!(sent_ethereum_message_nonce(), 0);
+    });
+}
+
+#[test]
+fn test_transfer_native_token_delayed_by_bridge_guard() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let channel_id = ChannelId::new([5u8; 32]);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            channel_id,
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        set_bridge_guard_delay_threshold(Some(10));
+
+        // Below the threshold, sent right away
+        assert_ok!(EthereumTokenTransfers::transfer_native_token(
+            RuntimeOrigin::signed(BOB),
+            9u128,
+            H160::default(),
+        ));
+        assert_eq!(sent_ethereum_message_nonce(), 1);
+
+        assert_ok!(EthereumTokenTransfers::transfer_native_token(
+            RuntimeOrigin::signed(ALICE),
+            10u128,
+            H160::default(),
+        ));
+
+        let token_id = MockTokenIdConvert::convert_back(&TokenLocation::get()).unwrap();
+        System::assert_last_event(RuntimeEvent::EthereumTokenTransfers(
+            crate::Event::NativeTokenTransferDelayed {
+                transfer_id: 0,
+                source: ALICE,
+                recipient: H160::default(),
+                token_id,
+                amount: 10u128,
+                fee: 50u128,
+            },
+        ));
+
+        // Funds are taken but the message is not sent yet
+        assert_eq!(sent_ethereum_message_nonce(), 1);
+        assert_eq!(Balances::free_balance(ALICE), 40u128);
+        assert_eq!(
+            Balances::free_balance(EthereumSovereignAccount::get()),
+            19u128
+        );
+
+        let transfer = DelayedTransfer {
+            source: ALICE,
+            recipient: H160::default(),
+            token_id,
+            foreign_token: None,
+            amount: 10u128,
+            v2_reward: None,
+        };
+        assert_eq!(delayed_transfers(), vec![transfer.clone()]);
+
+        assert_ok!(EthereumTokenTransfers::execute(&transfer));
+        assert_eq!(sent_ethereum_message_nonce(), 2);
+        System::assert_last_event(RuntimeEvent::EthereumTokenTransfers(
+            crate::Event::DelayedNativeTokenTransferSent {
+                message_id: Default::default(),
+                channel_id,
+                source: ALICE,
+                recipient: H160::default(),
+                token_id,
+                amount: 10u128,
+            },
+        ));
+    });
+}
+
+#[test]
+fn test_delayed_transfer_refund_returns_amount() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        assert_ok!(EthereumTokenTransfers::transfer_native_token(
+            RuntimeOrigin::signed(BOB),
+            20u128,
+            H160::default(),
+        ));
+
+        set_bridge_guard_delay_threshold(Some(10));
+        assert_ok!(EthereumTokenTransfers::transfer_native_token(
+            RuntimeOrigin::signed(ALICE),
+            10u128,
+            H160::default(),
+        ));
+        assert_eq!(Balances::free_balance(ALICE), 40u128);
+
+        let transfer = delayed_transfers().pop().unwrap();
+        assert_ok!(EthereumTokenTransfers::refund(&transfer));
+
+        // The amount is refunded, the fee is not
+        assert_eq!(Balances::free_balance(ALICE), 50u128);
+        assert_eq!(
+            Balances::free_balance(EthereumSovereignAccount::get()),
+            20u128
+        );
+    });
+}
+
+#[test]
+fn test_quote_transfer_matches_charged_fee() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_noop!(
+            EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Native,
+                10u128,
+                H160::default()
+            ),
+            Error::<Test>::ChannelInfoNotSet
+        );
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        assert_eq!(
+            EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Native,
+                10u128,
+                H160::default()
+            ),
+            Ok(TransferFeeQuote {
+                local_fee: 20u128,
+                remote_fee: 30u128,
+                tip: 0u128,
+                total_fee: 50u128,
+                total_cost: 60u128,
+                delayed: false,
+            })
+        );
+        assert_noop!(
+            EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Foreign(H160::repeat_byte(0x22)),
+                10u128,
+                H160::default()
+            ),
+            Error::<Test>::UnknownForeignToken
+        );
+
+        let alice_balance_before = Balances::free_balance(ALICE);
+        assert_ok!(EthereumTokenTransfers::transfer_native_token(
+            RuntimeOrigin::signed(ALICE),
+            10u128,
+            H160::default(),
+        ));
+        assert_eq!(Balances::free_balance(ALICE), alice_balance_before - 60u128);
+    });
+}
+
+#[test]
+fn test_quote_transfer_v2_uses_min_reward_and_tip() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        assert_noop!(
+            EthereumTokenTransfers::quote_transfer_v2(
+                TransferredToken::Native,
+                10u128,
+                H160::default(),
+                None,
+                0u128
+            ),
+            Error::<Test>::V2SendingIsNotAllowed
+        );
+
+        ShouldUseV2::set(&true);
+
+        assert_eq!(
+            EthereumTokenTransfers::quote_transfer_v2(
+                TransferredToken::Native,
+                10u128,
+                H160::default(),
+                None,
+                3u128
+            ),
+            Ok(TransferFeeQuote {
+                local_fee: 0u128,
+                remote_fee: MinV2Reward::get(),
+                tip: 3u128,
+                total_fee: MinV2Reward::get() + 3u128,
+                total_cost: MinV2Reward::get() + 13u128,
+                delayed: false,
+            })
+        );
+        assert_noop!(
+            EthereumTokenTransfers::quote_transfer_v2(
+                TransferredToken::Native,
+                10u128,
+                H160::default(),
+                Some(0u128),
+                0u128
+            ),
+            Error::<Test>::MinV2RewardNotAchieved
+        );
+    });
+}
+
+#[test]
+fn test_quote_transfer_reports_bridge_guard_checks() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        set_bridge_guard_delay_threshold(Some(10));
+        assert!(
+            !EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Native,
+                9u128,
+                H160::default()
+            )
+            .unwrap()
+            .delayed
+        );
+        assert!(
+            EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Native,
+                10u128,
+                H160::default()
+            )
+            .unwrap()
+            .delayed
+        );
+
+        set_bridge_guard_paused(true);
+        assert_noop!(
+            EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Native,
+                9u128,
+                H160::default()
+            ),
+            DispatchError::Other("bridge paused")
+        );
+    });
+}
+
+#[test]
+fn test_quote_transfer_for_foreign_tokens_matches_charged_fee() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        // The token is burnt, only the fees are paid in the native token
+        assert_eq!(
+            EthereumTokenTransfers::quote_transfer(
+                TransferredToken::Foreign(ERC20_TOKEN),
+                10u128,
+                H160::default()
+            ),
+            Ok(TransferFeeQuote {
+                local_fee: 20u128,
+                remote_fee: 30u128,
+                tip: 0u128,
+                total_fee: 50u128,
+                total_cost: 50u128,
+                delayed: false,
+            })
+        );
+
+        let alice_balance_before = Balances::free_balance(ALICE);
+        assert_ok!(EthereumTokenTransfers::transfer_foreign_token(
+            RuntimeOrigin::signed(ALICE),
+            ERC20_TOKEN,
+            10u128,
+            H160::default(),
+        ));
+        assert_eq!(Balances::free_balance(ALICE), alice_balance_before - 50u128);
+
+        ShouldUseV2::set(&true);
+        assert_eq!(
+            EthereumTokenTransfers::quote_transfer_v2(
+                TransferredToken::Foreign(H160::zero()),
+                10u128,
+                H160::default(),
+                Some(10u128),
+                0u128
+            ),
+            Ok(TransferFeeQuote {
+                local_fee: 0u128,
+                remote_fee: 10u128,
+                tip: 0u128,
+                total_fee: 10u128,
+                total_cost: 10u128,
+                delayed: false,
+            })
+        );
+        assert_noop!(
+            EthereumTokenTransfers::quote_transfer_v2(
+                TransferredToken::Foreign(H160::repeat_byte(0x22)),
+                10u128,
+                H160::default(),
+                None,
+                0u128
+            ),
+            Error::<Test>::UnknownForeignToken
+        );
+    });
+}
+
+#[test]
+fn test_transfer_foreign_token_succeeds() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let channel_id = ChannelId::new([5u8; 32]);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            channel_id,
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        assert_ok!(EthereumTokenTransfers::transfer_foreign_token(
+            RuntimeOrigin::signed(ALICE),
+            ERC20_TOKEN,
+            10u128,
+            H160::default(),
+        ));
+
+        System::assert_last_event(RuntimeEvent::EthereumTokenTransfers(
+            crate::Event::ForeignTokenTransferred {
+                message_id: Default::default(),
+                channel_id,
+                source: ALICE,
+                recipient: H160::default(),
+                token: ERC20_TOKEN,
+                amount: 10u128,
+                fee: 50u128,
+            },
+        ));
+
+        // The token is burnt, fees are paid in the native token
+        assert_eq!(ForeignAssets::balance(ERC20_ASSET_ID, ALICE), 90u128);
+        assert_eq!(ForeignAssets::total_supply(ERC20_ASSET_ID), 90u128);
+        assert_eq!(Balances::free_balance(ALICE), 50u128);
+        assert_eq!(Balances::free_balance(FeesAccount::get()), 50u128);
+        assert_eq!(
+            Balances::free_balance(EthereumSovereignAccount::get()),
+            0u128
+        );
+
+        assert_eq!(sent_ethereum_message_nonce(), 1);
+    });
+}
+
+#[test]
+fn test_transfer_foreign_token_succeeds_v2() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        ShouldUseV2::set(&true);
+        let channel_id = ChannelId::new([5u8; 32]);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            channel_id,
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        // Ether is identified by the zero address
+        assert_ok!(EthereumTokenTransfers::transfer_foreign_token_v2(
+            RuntimeOrigin::signed(ALICE),
+            H160::zero(),
+            10u128,
+            H160::default(),
+            10u128
+        ));
+
+        System::assert_last_event(RuntimeEvent::EthereumTokenTransfers(
+            crate::Event::ForeignTokenTransferred {
+                message_id: Default::default(),
+                channel_id,
+                source: ALICE,
+                recipient: H160::default(),
+                token: H160::zero(),
+                amount: 10u128,
+                fee: 10u128,
+            },
+        ));
+
+        assert_eq!(ForeignAssets::balance(ETHER_ASSET_ID, ALICE), 90u128);
+        assert_eq!(Balances::free_balance(ALICE), 90u128);
+        assert_eq!(Balances::free_balance(FeesAccount::get()), 10u128);
+
+        assert_eq!(sent_ethereum_message_nonce_v2(), 1);
+    });
+}
+
+#[test]
+fn test_transfer_foreign_token_fails_for_unknown_token() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        assert_noop!(
+            EthereumTokenTransfers::transfer_foreign_token(
+                RuntimeOrigin::signed(ALICE),
+                H160::repeat_byte(0x22),
+                10u128,
+                H160::default(),
+            ),
+            Error::<Test>::UnknownForeignToken
+        );
+    });
+}
+
+#[test]
+fn test_transfer_foreign_token_fails_without_enough_balance() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            ChannelId::new([5u8; 32]),
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        assert!(EthereumTokenTransfers::transfer_foreign_token(
+            RuntimeOrigin::signed(BOB),
+            ERC20_TOKEN,
+            10u128,
+            H160::default(),
+        )
+        .is_err());
+        assert_eq!(sent_ethereum_message_nonce(), 0);
+    });
+}
+
+#[test]
+fn test_delayed_foreign_token_transfer_is_sent_or_refunded() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let channel_id = ChannelId::new([5u8; 32]);
+
+        assert_ok!(EthereumTokenTransfers::set_token_transfer_channel(
+            RuntimeOrigin::root(),
+            channel_id,
+            AgentId::random(),
+            2000u32.into()
+        ));
+
+        set_bridge_guard_delay_threshold(Some(10));
+
+        assert_ok!(EthereumTokenTransfers::transfer_foreign_token(
+            RuntimeOrigin::signed(ALICE),
+            ERC20_TOKEN,
+            10u128,
+            H160::default(),
+        ));
+
+        System::assert_last_event(RuntimeEvent::EthereumTokenTransfers(
+            crate::Event::ForeignTokenTransferDelayed {
+                transfer_id: 0,
+                source: ALICE,
+                recipient: H160::default(),
+                token: ERC20_TOKEN,
+                amount: 10u128,
+                fee: 50u128,
+            },
+        ));
+        assert_eq!(sent_ethereum_message_nonce(), 0);
+        assert_eq!(ForeignAssets::balance(ERC20_ASSET_ID, ALICE), 90u128);
+
+        let transfer = delayed_transfers().pop().unwrap();
+        assert_eq!(transfer.foreign_token, Some(ERC20_TOKEN));
+
+        assert_ok!(EthereumTokenTransfers::execute(&transfer));
+        assert_eq!(sent_ethereum_message_nonce(), 1);
+        System::assert_last_event(RuntimeEvent::EthereumTokenTransfers(
+            crate::Event::DelayedForeignTokenTransferSent {
+                message_id: Default::default(),
+                channel_id,
+                source: ALICE,
+                recipient: H160::default(),
+                token: ERC20_TOKEN,
+                amount: 10u128,
+            },
+        ));
+
+        // A cancelled transfer mints the burnt amount back, without the fees
+        assert_ok!(EthereumTokenTransfers::refund(&transfer));
+        assert_eq!(ForeignAssets::balance(ERC20_ASSET_ID, ALICE), 100u128);
+        assert_eq!(Balances::free_balance(ALICE), 50u128);
+    });
+}
@@ -21,7 +21,7 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as ExternalValidatorSlashes;
 use {
-    crate::SlashingModeOption,
+    crate::{SlashReportFormatOption, SlashingModeOption},
     frame_benchmarking::{v2::*, BenchmarkError},
     frame_system::RawOrigin,
     pallet_session::{self as session},
@@ -111,6 +111,8 @@ mod benchmarks {
         }
 
         UnreportedSlashesQueue::<T>::set(queue);
+        // Reporting the merkle root is the worst case, as the chunk is also stored
+        SlashReportFormat::<T>::put(SlashReportFormatOption::MerkleRoot);
 
         let processed;
 
@@ -133,6 +135,18 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn set_slash_report_format() -> Result<(), BenchmarkError> {
+        #[extrinsic_call]
+        _(RawOrigin::Root, SlashReportFormatOption::MerkleRoot);
+
+        assert_eq!(
+            SlashReportFormat::<T>::get(),
+            SlashReportFormatOption::MerkleRoot
+        );
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         ExternalValidatorSlashes,
         crate::mock::new_test_ext(),
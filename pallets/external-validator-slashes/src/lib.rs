@@ -25,6 +25,14 @@
 //!
 //! The pallet also implements the OnOffence trait that reacts to offences being injected by other pallets
 //! Invulnerables are not slashed and no slashing information is stored for them
+//!
+//! ## Reporting slashes
+//!
+//! Slashes are queued in `UnreportedSlashesQueue` and sent to Ethereum in chunks of
+//! `QueuedSlashesProcessedPerBlock`, one message per block. Each message only contains slashes of
+//! one external source and is sent on the channel of that source. Depending on `SlashReportFormat`,
+//! each message contains the full list of slashes or only the merkle root of the chunk. In the latter case,
+//! the chunks are kept for `BondingDuration` eras so proofs can be generated for each slash.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -36,9 +44,10 @@ use {
     log::log,
     pallet_staking::SessionInterface,
     parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, FullCodec},
+    snowbridge_merkle_tree::{merkle_proof, merkle_root, verify_proof, MerkleProof},
     sp_core::H256,
     sp_runtime::{
-        traits::{Convert, Debug, One, Saturating, Zero},
+        traits::{Convert, Debug, Hash, One, Saturating, Zero},
         DispatchResult, Perbill,
     },
     sp_staking::{
@@ -46,8 +55,8 @@ use {
         EraIndex, SessionIndex,
     },
     tp_traits::{
-        apply, derive_storage_traits, EraIndexProvider, ExternalIndexProvider,
-        InvulnerablesProvider, OnEraStart,
+        apply, derive_storage_traits, EraIndexProvider, ExternalIndexProvider, ExternalSourceId,
+        ExternalSourcesProvider, InvulnerablesProvider, OnEraStart, DEFAULT_EXTERNAL_SOURCE,
     },
 };
 
@@ -56,7 +65,7 @@ use {
     tp_bridge::{Command, DeliverMessage, SlashData, TanssiMessage, TicketInfo, ValidateMessage},
 };
 
-pub use pallet::*;
+pub use {pallet::*, tp_traits::SlashingModeOption};
 
 #[cfg(test)]
 mod mock;
@@ -87,6 +96,8 @@ pub mod pallet {
             message_id: H256,
             slashes_command: Command,
         },
+        /// The format used to report slashes to Ethereum was changed.
+        SlashReportFormatSet { format: SlashReportFormatOption },
     }
 
     #[pallet::config]
@@ -145,10 +156,17 @@ pub mod pallet {
         /// Provider to retrieve the current external index of validators
         type ExternalIndexProvider: ExternalIndexProvider;
 
+        /// Provider to know which external source supplied each validator, so that the slash
+        /// carries the external index of that source
+        type ExternalSourcesProvider: ExternalSourcesProvider<Self::AccountId>;
+
         /// How many queued slashes are being processed per block.
         #[pallet::constant]
         type QueuedSlashesProcessedPerBlock: Get<u32>;
 
+        /// Hashing tool used to generate/verify slashes merkle roots and proofs.
+        type Hashing: Hash<Output = H256>;
+
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -179,11 +197,12 @@ pub mod pallet {
 
     #[apply(derive_storage_traits)]
     #[derive(MaxEncodedLen, DecodeWithMemTracking, Default)]
-    pub enum SlashingModeOption {
+    pub enum SlashReportFormatOption {
+        /// Send the full list of slashes in `Command::ReportSlashes`.
         #[default]
-        Enabled,
-        LogOnly,
-        Disabled,
+        Full,
+        /// Send only the merkle root of the slashes in `Command::ReportSlashesMerkleRoot`.
+        MerkleRoot,
     }
 
     #[pallet::pallet]
@@ -227,6 +246,28 @@ pub mod pallet {
     #[pallet::storage]
     pub type SlashingMode<T: Config> = StorageValue<_, SlashingModeOption, ValueQuery>;
 
+    /// How slashes are reported to Ethereum.
+    #[pallet::storage]
+    pub type SlashReportFormat<T: Config> = StorageValue<_, SlashReportFormatOption, ValueQuery>;
+
+    /// Slashes sent as a merkle root, by era in which they were reported and chunk index.
+    /// Kept for `BondingDuration` eras to be able to generate proofs.
+    #[pallet::storage]
+    pub type ReportedSlashesChunks<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        u32,
+        BoundedVec<ReportedSlash<T::AccountId>, T::QueuedSlashesProcessedPerBlock>,
+        OptionQuery,
+    >;
+
+    /// Number of chunks of slashes sent as a merkle root in each era.
+    #[pallet::storage]
+    pub type ReportedSlashesChunkCount<T: Config> =
+        StorageMap<_, Twox64Concat, EraIndex, u32, ValueQuery>;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Cancel a slash that was deferred for a later era
@@ -292,6 +333,8 @@ pub mod pallet {
                 .ok_or(Error::<T>::ProvidedNonSlashableEra)?;
 
             let next_slash_id = NextSlashId::<T>::get();
+            let source = T::ExternalSourcesProvider::source_of(era, &validator)
+                .unwrap_or(DEFAULT_EXTERNAL_SOURCE);
 
             let slash = compute_slash::<T>(
                 percentage,
@@ -300,6 +343,7 @@ pub mod pallet {
                 validator,
                 slash_defer_duration,
                 external_idx,
+                source,
             )
             .ok_or(Error::<T>::ErrorComputingSlash)?;
 
@@ -381,6 +425,24 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Set how slashes are reported to Ethereum: the full list of slashes, or only the merkle
+        /// root of each chunk of slashes. Applies to the slashes sent from the next block on.
+        ///
+        /// The origin for this call must be root.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_slash_report_format())]
+        pub fn set_slash_report_format(
+            origin: OriginFor<T>,
+            format: SlashReportFormatOption,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            SlashReportFormat::<T>::put(format.clone());
+
+            Self::deposit_event(Event::SlashReportFormatSet { format });
+            Ok(())
+        }
     }
 
     #[pallet::hooks]
@@ -486,6 +548,16 @@ where
                 continue;
             }
 
+            // Validators supplied by a non-default source are reported with the external index
+            // of that source, on its channel.
+            let source = T::ExternalSourcesProvider::source_of(slash_era, stash)
+                .unwrap_or(DEFAULT_EXTERNAL_SOURCE);
+            let validator_external_idx = Some(source)
+                .filter(|source| *source != DEFAULT_EXTERNAL_SOURCE)
+                .and_then(|source| T::ExternalSourcesProvider::external_index_of(slash_era, source))
+                .unwrap_or(external_idx);
+            add_db_reads_writes(2, 0);
+
             // Account for one read and one possible write inside compute_slash.
             add_db_reads_writes(1, 1);
 
@@ -495,7 +567,8 @@ where
                 slash_era,
                 stash.clone(),
                 slash_defer_duration,
-                external_idx,
+                validator_external_idx,
+                source,
             );
 
             if let Some(mut slash) = slash {
@@ -565,6 +638,11 @@ impl<T: Config> OnEraStart for Pallet<T> {
                         );
                     }
                     Slashes::<T>::remove(pruned_era);
+                    let _ = ReportedSlashesChunks::<T>::clear_prefix(
+                        pruned_era,
+                        ReportedSlashesChunkCount::<T>::take(pruned_era),
+                        None,
+                    );
                 }
 
                 if let Some(&(_, first_session, _)) = bonded.first() {
@@ -578,45 +656,119 @@ impl<T: Config> OnEraStart for Pallet<T> {
 }
 
 impl<T: Config> Pallet<T> {
+    /// Leaves of the merkle tree built from `slashes`, in the same order.
+    pub fn slashes_merkle_leaves(slashes: &[SlashData]) -> Vec<H256> {
+        generate_slashes_merkle_leaves::<<T as Config>::Hashing>(slashes)
+    }
+
+    /// Generate the merkle proof of the slash of `validator` reported as a merkle root during
+    /// `era_index`. Returns `None` if no such slash was reported.
+    pub fn generate_slashes_merkle_proof(
+        validator: T::AccountId,
+        era_index: EraIndex,
+    ) -> Option<MerkleProof> {
+        ReportedSlashesChunks::<T>::iter_prefix(era_index).find_map(|(_, slashes)| {
+            let leaf_index = slashes
+                .iter()
+                .position(|slash| slash.validator == validator)?;
+            let slashes: Vec<SlashData> = slashes.iter().map(ReportedSlash::slash_data).collect();
+            Some(merkle_proof::<<T as Config>::Hashing, _>(
+                Self::slashes_merkle_leaves(&slashes).into_iter(),
+                leaf_index as u64,
+            ))
+        })
+    }
+
+    pub fn verify_slashes_merkle_proof(merkle_proof: MerkleProof) -> bool {
+        verify_proof::<<T as Config>::Hashing, _, _>(
+            &merkle_proof.root,
+            merkle_proof.proof,
+            merkle_proof.number_of_leaves,
+            merkle_proof.leaf_index,
+            merkle_proof.leaf,
+        )
+    }
+
     fn add_era_slashes_to_queue(active_era: EraIndex) {
         let mut slashes: VecDeque<_> = Slashes::<T>::get(active_era).into();
 
         UnreportedSlashesQueue::<T>::mutate(|queue| queue.append(&mut slashes));
     }
 
-    /// Returns number of slashes that were sent to ethereum.
+    /// Returns number of slashes that were sent to ethereum. Each message only contains slashes
+    /// of the source of the oldest slash in the queue, and is sent on the channel of that source.
     fn process_slashes_queue(amount: u32) -> u32 {
-        let mut slashes_to_send: Vec<_> = vec![];
+        let mut reported_slashes: Vec<_> = vec![];
         let era_index = T::EraIndexProvider::active_era().index;
+        let mut source = DEFAULT_EXTERNAL_SOURCE;
 
         UnreportedSlashesQueue::<T>::mutate(|queue| {
-            for _ in 0..amount {
-                let Some(slash) = queue.pop_front() else {
-                    // no more slashes to process in the queue
-                    break;
-                };
-
-                slashes_to_send.push(SlashData {
-                    encoded_validator_id: slash.validator.clone().encode(),
-                    slash_fraction: slash.percentage.deconstruct(),
-                    external_idx: slash.external_idx,
-                });
+            let Some(oldest) = queue.front() else {
+                // no more slashes to process in the queue
+                return;
+            };
+            source = oldest.source;
+
+            // Slashes of other sources keep their order in the queue
+            let mut remaining = VecDeque::with_capacity(queue.len());
+            for slash in queue.drain(..) {
+                if slash.source == source && reported_slashes.len() < amount as usize {
+                    reported_slashes.push(ReportedSlash {
+                        validator: slash.validator,
+                        slash_fraction: slash.percentage.deconstruct(),
+                        external_idx: slash.external_idx,
+                    });
+                } else {
+                    remaining.push_back(slash);
+                }
             }
+            *queue = remaining;
         });
 
-        if slashes_to_send.is_empty() {
+        if reported_slashes.is_empty() {
             return 0;
         }
 
-        let slashes_count = slashes_to_send.len() as u32;
+        let slashes_count = reported_slashes.len() as u32;
+        let slashes_to_send: Vec<SlashData> = reported_slashes
+            .iter()
+            .map(ReportedSlash::slash_data)
+            .collect();
 
         // Build command with slashes.
-        let command = Command::ReportSlashes {
-            era_index,
-            slashes: slashes_to_send,
+        let command = match SlashReportFormat::<T>::get() {
+            SlashReportFormatOption::Full => Command::ReportSlashes {
+                era_index,
+                slashes: slashes_to_send,
+            },
+            SlashReportFormatOption::MerkleRoot => {
+                let slashes_merkle_root = merkle_root::<<T as Config>::Hashing, _>(
+                    Self::slashes_merkle_leaves(&slashes_to_send).into_iter(),
+                );
+                let chunk_index = ReportedSlashesChunkCount::<T>::mutate(era_index, |count| {
+                    let chunk_index = *count;
+                    count.saturating_inc();
+                    chunk_index
+                });
+                // At most `QueuedSlashesProcessedPerBlock` slashes are sent per block
+                ReportedSlashesChunks::<T>::insert(
+                    era_index,
+                    chunk_index,
+                    BoundedVec::truncate_from(reported_slashes),
+                );
+
+                Command::ReportSlashesMerkleRoot {
+                    era_index,
+                    chunk_index,
+                    slashes_count,
+                    slashes_merkle_root,
+                }
+            }
         };
 
-        let channel_id: ChannelId = snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL;
+        let channel_id = T::ExternalSourcesProvider::channel_of(era_index, source)
+            .map(ChannelId::from)
+            .unwrap_or(snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL);
 
         let outbound_message = TanssiMessage {
             id: None,
@@ -646,12 +798,47 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+/// Hash each slash to get the leaves of the slashes merkle tree. Used both by the pallet and to
+/// generate proofs offline.
+pub fn generate_slashes_merkle_leaves<Hasher: Hash<Output = H256>>(
+    slashes: &[SlashData],
+) -> Vec<H256> {
+    slashes
+        .iter()
+        .map(|slash| Hasher::hash(&slash.merkle_leaf_data()))
+        .collect()
+}
+
+/// A slash reported to Ethereum as part of a merkle root, kept to generate its proof.
+#[derive(Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo, Clone, PartialEq)]
+pub struct ReportedSlash<AccountId> {
+    /// The stash ID of the offending validator.
+    pub validator: AccountId,
+    /// The slashed fraction, as the parts of a `Perbill`.
+    pub slash_fraction: u32,
+    /// External index identifying the set of validators of the offender.
+    pub external_idx: u64,
+}
+
+impl<AccountId: Encode> ReportedSlash<AccountId> {
+    /// The slash as sent to Ethereum.
+    pub fn slash_data(&self) -> SlashData {
+        SlashData {
+            encoded_validator_id: self.validator.encode(),
+            slash_fraction: self.slash_fraction,
+            external_idx: self.external_idx,
+        }
+    }
+}
+
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo, Clone, PartialEq)]
 pub struct Slash<AccountId, SlashId> {
     /// external index identifying a given set of validators
     pub external_idx: u64,
+    /// External source that supplied the offender. The slash is reported on its channel.
+    pub source: ExternalSourceId,
     /// The stash ID of the offending validator.
     pub validator: AccountId,
     /// Reporters of the offence; bounty payout recipients.
@@ -668,6 +855,7 @@ impl<AccountId, SlashId: One> Slash<AccountId, SlashId> {
     pub fn default_from(validator: AccountId) -> Self {
         Self {
             external_idx: 0,
+            source: DEFAULT_EXTERNAL_SOURCE,
             validator,
             reporters: vec![],
             slash_id: One::one(),
@@ -690,6 +878,7 @@ pub(crate) fn compute_slash<T: Config>(
     stash: T::AccountId,
     slash_defer_duration: EraIndex,
     external_idx: u64,
+    source: ExternalSourceId,
 ) -> Option<Slash<T::AccountId, T::SlashId>> {
     let prior_slash_p = ValidatorSlashInEra::<T>::get(slash_era, &stash).unwrap_or(Zero::zero());
 
@@ -711,6 +900,7 @@ pub(crate) fn compute_slash<T: Config>(
     let confirmed = slash_defer_duration.is_zero();
     Some(Slash {
         external_idx,
+        source,
         validator: stash.clone(),
         percentage: slash_fraction,
         slash_id,
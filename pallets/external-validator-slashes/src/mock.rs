@@ -23,11 +23,12 @@ use {
         weights::constants::RocksDbWeight,
     },
     frame_system as system,
-    snowbridge_outbound_queue_primitives::{SendError, SendMessageFeeProvider},
+    snowbridge_core::ChannelId,
+    snowbridge_outbound_queue_primitives::{v1::Fee, SendError, SendMessageFeeProvider},
     sp_core::H256,
     sp_runtime::{
         testing::UintAuthorityId,
-        traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+        traits::{BlakeTwo256, ConvertInto, IdentityLookup, Keccak256},
         BuildStorage,
     },
     sp_staking::SessionIndex,
@@ -243,6 +244,46 @@ impl tp_traits::ExternalIndexProvider for ExternalIndexProvider {
 
 parameter_types! {
     pub const BondingDuration: u32 = 5u32;
+    // (validator, source, external index of the source)
+    pub static ValidatorSources: Vec<(u64, tp_traits::ExternalSourceId, u64)> = vec![];
+    // (source, channel of the source)
+    pub static SourceChannels: Vec<(tp_traits::ExternalSourceId, [u8; 32])> = vec![];
+    // Channel of every message validated, in order
+    pub static SentChannels: Vec<ChannelId> = vec![];
+}
+
+pub struct MockValidateMessage;
+impl tp_bridge::ValidateMessage for MockValidateMessage {
+    type Ticket = ();
+
+    fn validate(message: &tp_bridge::TanssiMessage) -> Result<(Self::Ticket, Fee<u64>), SendError> {
+        SentChannels::mutate(|channels| channels.push(message.channel_id));
+        <() as tp_bridge::ValidateMessage>::validate(message)
+    }
+}
+
+pub struct MockExternalSourcesProvider;
+impl tp_traits::ExternalSourcesProvider<u64> for MockExternalSourcesProvider {
+    fn source_of(_era_index: EraIndex, validator: &u64) -> Option<tp_traits::ExternalSourceId> {
+        ValidatorSources::get()
+            .into_iter()
+            .find(|(v, _, _)| v == validator)
+            .map(|(_, source, _)| source)
+    }
+
+    fn external_index_of(_era_index: EraIndex, source: tp_traits::ExternalSourceId) -> Option<u64> {
+        ValidatorSources::get()
+            .into_iter()
+            .find(|(_, s, _)| *s == source)
+            .map(|(_, _, external_idx)| external_idx)
+    }
+
+    fn channel_of(_era_index: EraIndex, source: tp_traits::ExternalSourceId) -> Option<[u8; 32]> {
+        SourceChannels::get()
+            .into_iter()
+            .find(|(s, _)| *s == source)
+            .map(|(_, channel)| channel)
+    }
 }
 
 impl external_validator_slashes::Config for Test {
@@ -254,10 +295,12 @@ impl external_validator_slashes::Config for Test {
     type SessionInterface = ();
     type EraIndexProvider = MockEraIndexProvider;
     type InvulnerablesProvider = MockInvulnerableProvider;
-    type ValidateMessage = ();
+    type ValidateMessage = MockValidateMessage;
     type OutboundQueue = MockOkOutboundQueue;
     type ExternalIndexProvider = ExternalIndexProvider;
+    type ExternalSourcesProvider = MockExternalSourcesProvider;
     type QueuedSlashesProcessedPerBlock = ConstU32<20>;
+    type Hashing = Keccak256;
     type WeightInfo = ();
 }
 
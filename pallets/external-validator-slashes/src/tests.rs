@@ -19,12 +19,13 @@ use {
     crate::{
         mock::{
             new_test_ext, run_block, sent_ethereum_message_nonce, DeferPeriodGetter,
-            ExternalValidatorSlashes, MockEraIndexProvider, RuntimeEvent, RuntimeOrigin, System,
-            Test,
+            ExternalValidatorSlashes, MockEraIndexProvider, RuntimeEvent, RuntimeOrigin,
+            SentChannels, SourceChannels, System, Test, ValidatorSources,
         },
-        Slash,
+        ReportedSlash, Slash,
     },
     frame_support::{assert_noop, assert_ok},
+    sp_runtime::traits::{BadOrigin, Keccak256},
 };
 
 #[test]
@@ -42,6 +43,7 @@ fn root_can_inject_manual_offence() {
             Slashes::<Test>::get(get_slashing_era(0)),
             vec![Slash {
                 external_idx: 1,
+                source: 0,
                 validator: 1,
                 percentage: Perbill::from_percent(75),
                 confirmed: false,
@@ -215,6 +217,7 @@ fn test_after_bonding_period_we_can_remove_slashes() {
             Slashes::<Test>::get(get_slashing_era(0)),
             vec![Slash {
                 external_idx: 1,
+                source: 0,
                 validator: 1,
                 percentage: Perbill::from_percent(75),
                 confirmed: false,
@@ -252,6 +255,36 @@ fn test_on_offence_injects_offences() {
             Slashes::<Test>::get(get_slashing_era(0)),
             vec![Slash {
                 external_idx: 0,
+                source: 0,
+                validator: 3,
+                percentage: Perbill::from_percent(75),
+                confirmed: false,
+                reporters: vec![],
+                slash_id: 0
+            }]
+        );
+    });
+}
+
+#[test]
+fn test_on_offence_uses_external_index_of_validator_source() {
+    new_test_ext().execute_with(|| {
+        start_era(0, 0, 0);
+        start_era(1, 1, 1);
+        ValidatorSources::set(vec![(3, 1, 1234)]);
+        Pallet::<Test>::on_offence(
+            &[OffenceDetails {
+                offender: (3, ()),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(75)],
+            0,
+        );
+        assert_eq!(
+            Slashes::<Test>::get(get_slashing_era(0)),
+            vec![Slash {
+                external_idx: 1234,
+                source: 1,
                 validator: 3,
                 percentage: Perbill::from_percent(75),
                 confirmed: false,
@@ -324,6 +357,7 @@ fn defer_period_of_zero_confirms_immediately_slashes() {
             Slashes::<Test>::get(get_slashing_era(0)),
             vec![Slash {
                 external_idx: 1,
+                source: 0,
                 validator: 1,
                 percentage: Perbill::from_percent(75),
                 confirmed: true,
@@ -373,6 +407,7 @@ fn test_on_offence_defer_period_0() {
             Slashes::<Test>::get(get_slashing_era(1)),
             vec![Slash {
                 external_idx: 0,
+                source: 0,
                 validator: 3,
                 percentage: Perbill::from_percent(75),
                 confirmed: true,
@@ -408,6 +443,7 @@ fn test_slashes_command_matches_event() {
             Slashes::<Test>::get(get_slashing_era(1)),
             vec![Slash {
                 external_idx: 0,
+                source: 0,
                 validator: 3,
                 percentage: Perbill::from_percent(75),
                 confirmed: true,
@@ -440,6 +476,69 @@ fn test_slashes_command_matches_event() {
     });
 }
 
+#[test]
+fn test_slashes_are_sent_on_the_channel_of_their_source() {
+    new_test_ext().execute_with(|| {
+        crate::mock::DeferPeriodGetter::with_defer_period(0);
+        start_era(0, 0, 0);
+        start_era(1, 1, 1);
+        ValidatorSources::set(vec![(4, 1, 1234)]);
+        SourceChannels::set(vec![(1, [9; 32])]);
+        for offender in [3, 4, 5] {
+            Pallet::<Test>::on_offence(
+                &[OffenceDetails {
+                    offender: (offender, ()),
+                    reporters: vec![],
+                }],
+                &[Perbill::from_percent(75)],
+                0,
+            );
+        }
+        start_era(2, 2, 2);
+        assert_eq!(UnreportedSlashesQueue::<Test>::get().len(), 3);
+
+        // The slashes of the default source go first, skipping the one of source 1
+        run_block();
+        let slash_data = |validator: u64, external_idx: u64| SlashData {
+            encoded_validator_id: validator.encode(),
+            slash_fraction: Perbill::from_percent(75).deconstruct(),
+            external_idx,
+        };
+        System::assert_last_event(RuntimeEvent::ExternalValidatorSlashes(
+            crate::Event::SlashesMessageSent {
+                message_id: Default::default(),
+                slashes_command: Command::ReportSlashes {
+                    era_index: 2u32,
+                    slashes: vec![slash_data(3, 0), slash_data(5, 0)],
+                },
+            },
+        ));
+        assert_eq!(
+            SentChannels::get(),
+            vec![snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL]
+        );
+
+        run_block();
+        System::assert_last_event(RuntimeEvent::ExternalValidatorSlashes(
+            crate::Event::SlashesMessageSent {
+                message_id: Default::default(),
+                slashes_command: Command::ReportSlashes {
+                    era_index: 2u32,
+                    slashes: vec![slash_data(4, 1234)],
+                },
+            },
+        ));
+        assert_eq!(
+            SentChannels::get(),
+            vec![
+                snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL,
+                ChannelId::from([9; 32])
+            ]
+        );
+        assert_eq!(UnreportedSlashesQueue::<Test>::get().len(), 0);
+    });
+}
+
 #[test]
 fn test_on_offence_defer_period_0_messages_get_queued() {
     new_test_ext().execute_with(|| {
@@ -482,6 +581,7 @@ fn test_account_id_encoding() {
 
         let slash = Slash::<OpaqueAccountId, u32> {
             external_idx: 0,
+            source: 0,
             validator: OpaqueAccountId::from(alice_account),
             reporters: vec![],
             slash_id: 1,
@@ -551,6 +651,164 @@ fn test_on_offence_defer_period_0_messages_get_queued_across_eras() {
     });
 }
 
+#[test]
+fn only_root_can_set_slash_report_format() {
+    new_test_ext().execute_with(|| {
+        run_block();
+        assert_noop!(
+            ExternalValidatorSlashes::set_slash_report_format(
+                RuntimeOrigin::signed(1),
+                SlashReportFormatOption::MerkleRoot
+            ),
+            BadOrigin
+        );
+        assert_ok!(ExternalValidatorSlashes::set_slash_report_format(
+            RuntimeOrigin::root(),
+            SlashReportFormatOption::MerkleRoot
+        ));
+        assert_eq!(
+            SlashReportFormat::<Test>::get(),
+            SlashReportFormatOption::MerkleRoot
+        );
+        System::assert_last_event(RuntimeEvent::ExternalValidatorSlashes(
+            crate::Event::SlashReportFormatSet {
+                format: SlashReportFormatOption::MerkleRoot,
+            },
+        ));
+    });
+}
+
+#[test]
+fn test_slashes_merkle_root_command_matches_event() {
+    new_test_ext().execute_with(|| {
+        crate::mock::DeferPeriodGetter::with_defer_period(0);
+        assert_ok!(ExternalValidatorSlashes::set_slash_report_format(
+            RuntimeOrigin::root(),
+            SlashReportFormatOption::MerkleRoot
+        ));
+        start_era(0, 0, 0);
+        start_era(1, 1, 1);
+        Pallet::<Test>::on_offence(
+            &[OffenceDetails {
+                // 1 and 2 are invulnerables
+                offender: (3, ()),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(75)],
+            0,
+        );
+        start_era(2, 2, 2);
+        run_block();
+
+        assert_eq!(sent_ethereum_message_nonce(), 1);
+
+        let expected_slashes = vec![SlashData {
+            encoded_validator_id: 3u64.encode(),
+            slash_fraction: Perbill::from_percent(75).deconstruct(),
+            external_idx: 0u64,
+        }];
+        let expected_command = Command::ReportSlashesMerkleRoot {
+            era_index: 2u32,
+            chunk_index: 0,
+            slashes_count: 1,
+            slashes_merkle_root: merkle_root::<Keccak256, _>(
+                ExternalValidatorSlashes::slashes_merkle_leaves(&expected_slashes).into_iter(),
+            ),
+        };
+
+        System::assert_last_event(RuntimeEvent::ExternalValidatorSlashes(
+            crate::Event::SlashesMessageSent {
+                message_id: Default::default(),
+                slashes_command: expected_command,
+            },
+        ));
+        let reported_chunk: Vec<_> = ReportedSlashesChunks::<Test>::get(2, 0)
+            .unwrap()
+            .iter()
+            .map(ReportedSlash::slash_data)
+            .collect();
+        assert_eq!(reported_chunk, expected_slashes);
+        assert_eq!(ReportedSlashesChunkCount::<Test>::get(2), 1);
+    });
+}
+
+#[test]
+fn test_slashes_merkle_root_chunks_and_proofs() {
+    new_test_ext().execute_with(|| {
+        crate::mock::DeferPeriodGetter::with_defer_period(0);
+        assert_ok!(ExternalValidatorSlashes::set_slash_report_format(
+            RuntimeOrigin::root(),
+            SlashReportFormatOption::MerkleRoot
+        ));
+        start_era(0, 0, 0);
+        start_era(1, 1, 1);
+        // The limit is 20, so two chunks are sent
+        for i in 0..25 {
+            Pallet::<Test>::on_offence(
+                &[OffenceDetails {
+                    // 1 and 2 are invulnerables
+                    offender: (3 + i, ()),
+                    reporters: vec![],
+                }],
+                &[Perbill::from_percent(75)],
+                0,
+            );
+        }
+        start_era(2, 2, 2);
+        run_block();
+        run_block();
+
+        assert_eq!(sent_ethereum_message_nonce(), 2);
+        assert_eq!(ReportedSlashesChunkCount::<Test>::get(2), 2);
+        assert_eq!(ReportedSlashesChunks::<Test>::get(2, 0).unwrap().len(), 20);
+        assert_eq!(ReportedSlashesChunks::<Test>::get(2, 1).unwrap().len(), 5);
+
+        // Validator 25 was sent in the second chunk
+        let proof = ExternalValidatorSlashes::generate_slashes_merkle_proof(25, 2).unwrap();
+        let second_chunk: Vec<_> = ReportedSlashesChunks::<Test>::get(2, 1)
+            .unwrap()
+            .iter()
+            .map(ReportedSlash::slash_data)
+            .collect();
+        assert_eq!(
+            proof.root,
+            merkle_root::<Keccak256, _>(
+                ExternalValidatorSlashes::slashes_merkle_leaves(&second_chunk).into_iter()
+            )
+        );
+        assert_eq!(proof.number_of_leaves, 5);
+        assert!(ExternalValidatorSlashes::verify_slashes_merkle_proof(proof));
+
+        // No proof for validators that were not slashed or eras without slashes
+        assert!(ExternalValidatorSlashes::generate_slashes_merkle_proof(1, 2).is_none());
+        assert!(ExternalValidatorSlashes::generate_slashes_merkle_proof(25, 1).is_none());
+    });
+}
+
+#[test]
+fn test_full_report_format_does_not_store_chunks() {
+    new_test_ext().execute_with(|| {
+        crate::mock::DeferPeriodGetter::with_defer_period(0);
+        start_era(0, 0, 0);
+        start_era(1, 1, 1);
+        Pallet::<Test>::on_offence(
+            &[OffenceDetails {
+                // 1 and 2 are invulnerables
+                offender: (3, ()),
+                reporters: vec![],
+            }],
+            &[Perbill::from_percent(75)],
+            0,
+        );
+        start_era(2, 2, 2);
+        run_block();
+
+        assert_eq!(sent_ethereum_message_nonce(), 1);
+        assert_eq!(ReportedSlashesChunkCount::<Test>::get(2), 0);
+        assert!(ExternalValidatorSlashes::generate_slashes_merkle_proof(3, 2).is_none());
+    });
+}
+
 fn start_era(era_index: EraIndex, session_index: SessionIndex, external_idx: u64) {
     Pallet::<Test>::on_era_start(era_index, session_index, external_idx);
     crate::mock::MockEraIndexProvider::with_era(era_index);
@@ -79,6 +79,15 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn set_rewards_reporting_paused() -> Result<(), BenchmarkError> {
+        #[extrinsic_call]
+        _(frame_system::RawOrigin::Root, true);
+
+        assert!(RewardsReportingPaused::<T>::get());
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         ExternalValidatorsRewards,
         crate::mock::new_test_ext(),
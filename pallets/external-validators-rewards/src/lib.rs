@@ -45,10 +45,17 @@ use {
     snowbridge_core::{ChannelId, TokenId},
     snowbridge_merkle_tree::{merkle_proof, merkle_root, verify_proof, MerkleProof},
     sp_core::H256,
-    sp_runtime::traits::{Hash, MaybeEquivalence, Zero},
+    sp_runtime::{
+        helpers_128bit::multiply_by_rational_with_rounding,
+        traits::{Hash, MaybeEquivalence, Zero},
+        Rounding,
+    },
     sp_staking::SessionIndex,
     tp_bridge::{Command, DeliverMessage, TanssiMessage, TicketInfo, ValidateMessage},
-    tp_traits::ExternalIndexProvider,
+    tp_traits::{
+        ExternalIndexProvider, ExternalSourceId, ExternalSourcesProvider, ExternalStakeProvider,
+        DEFAULT_EXTERNAL_SOURCE,
+    },
     xcm::prelude::*,
 };
 
@@ -66,7 +73,7 @@ pub mod pallet {
     pub use crate::weights::WeightInfo;
     use {
         super::*, alloc::collections::btree_map::BTreeMap, frame_support::pallet_prelude::*,
-        sp_runtime::Saturating, tp_traits::EraIndexProvider,
+        frame_system::pallet_prelude::*, sp_runtime::Saturating, tp_traits::EraIndexProvider,
     };
 
     /// The current storage version.
@@ -98,6 +105,17 @@ pub mod pallet {
         /// Provider to retrieve the current external index indetifying the validators
         type ExternalIndexProvider: ExternalIndexProvider;
 
+        /// Provider to know which external source supplied each validator, so that rewards
+        /// are reported back to the source they came from.
+        type ExternalSourcesProvider: ExternalSourcesProvider<Self::AccountId>;
+
+        /// Provider to retrieve the stake of each external validator in a given era.
+        type ExternalStakeProvider: ExternalStakeProvider<Self::AccountId>;
+
+        /// Whether era points are weighted by the stake of each validator before being sent to
+        /// Ethereum. Sources that did not send the stake of their validators are not weighted.
+        type StakeWeightedPoints: Get<bool>;
+
         type GetWhitelistedValidators: Get<Vec<Self::AccountId>>;
 
         /// Hashing tool used to generate/verify merkle roots and proofs.
@@ -143,10 +161,12 @@ pub mod pallet {
             message_id: H256,
             rewards_command: Command,
         },
+        /// Sending rewards to Ethereum was paused or resumed.
+        RewardsReportingPausedSet { paused: bool },
     }
 
     /// Keep tracks of distributed points per validator and total.
-    #[derive(RuntimeDebug, Encode, Decode, PartialEq, Eq, TypeInfo)]
+    #[derive(RuntimeDebug, Encode, Decode, PartialEq, Eq, Clone, TypeInfo)]
     pub struct EraRewardPoints<AccountId> {
         pub total: RewardPoints,
         pub individual: BTreeMap<AccountId, RewardPoints>,
@@ -202,6 +222,62 @@ pub mod pallet {
                 total_points,
             })
         }
+
+        /// Splits the points by the external source returned by `source_of` for each validator.
+        /// Validators without a source are accounted to the default one.
+        pub fn split_by_source(
+            self,
+            source_of: impl Fn(&AccountId) -> Option<ExternalSourceId>,
+        ) -> BTreeMap<ExternalSourceId, Self> {
+            let mut by_source: BTreeMap<ExternalSourceId, Self> = BTreeMap::new();
+
+            for (account_id, points) in self.individual {
+                let source = source_of(&account_id).unwrap_or(DEFAULT_EXTERNAL_SOURCE);
+                let source_rewards = by_source.entry(source).or_default();
+                source_rewards.total.saturating_accrue(points);
+                source_rewards.individual.insert(account_id, points);
+            }
+
+            by_source
+        }
+
+        /// Redistributes the points proportionally to `points * stake` of each validator, as
+        /// returned by `stake_of`, keeping the same total except for rounding. Nothing is
+        /// changed if the stake of any of the validators is unknown.
+        pub fn weight_by_stake(&mut self, stake_of: impl Fn(&AccountId) -> Option<u128>) {
+            let Some(weights) = self
+                .individual
+                .iter()
+                .map(|(account_id, points)| {
+                    stake_of(account_id).map(|stake| stake.saturating_mul(u128::from(*points)))
+                })
+                .collect::<Option<Vec<u128>>>()
+            else {
+                return;
+            };
+
+            let total_weight = weights
+                .iter()
+                .fold(0u128, |acc, weight| acc.saturating_add(*weight));
+            if total_weight.is_zero() {
+                return;
+            }
+
+            let total_points = u128::from(self.total);
+            let mut new_total: RewardPoints = 0;
+            for (points, weight) in self.individual.values_mut().zip(weights) {
+                let weighted = multiply_by_rational_with_rounding(
+                    total_points,
+                    weight,
+                    total_weight,
+                    Rounding::Down,
+                )
+                .unwrap_or_default();
+                *points = RewardPoints::try_from(weighted).unwrap_or(RewardPoints::MAX);
+                new_total.saturating_accrue(*points);
+            }
+            self.total = new_total;
+        }
     }
 
     impl<AccountId> Default for EraRewardPoints<AccountId> {
@@ -220,7 +296,66 @@ pub mod pallet {
     pub type RewardPointsForEra<T: Config> =
         StorageMap<_, Twox64Concat, EraIndex, EraRewardPoints<T::AccountId>, ValueQuery>;
 
+    /// If set, rewards are not minted nor sent to Ethereum at the end of each era. Points are
+    /// still tracked and kept for `HistoryDepth` eras.
+    #[pallet::storage]
+    pub type RewardsReportingPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Eras that ended while rewards reporting was paused. Once reporting is resumed, their
+    /// rewards are sent to Ethereum one era per block, unless their points were already removed
+    /// by then.
+    #[pallet::storage]
+    pub type PendingRewardsEras<T: Config> =
+        StorageValue<_, BoundedVec<EraIndex, T::HistoryDepth>, ValueQuery>;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            if RewardsReportingPaused::<T>::get() {
+                return weight;
+            }
+
+            weight.saturating_accrue(T::DbWeight::get().reads(1));
+            let mut pending_eras = PendingRewardsEras::<T>::get();
+            if pending_eras.is_empty() {
+                return weight;
+            }
+
+            // Only one era per block, each one sends a message per external source. The weight
+            // of those messages is registered by `send_era_rewards`.
+            let era_index = pending_eras.remove(0);
+            PendingRewardsEras::<T>::put(pending_eras);
+            weight.saturating_accrue(T::DbWeight::get().writes(1));
+            Self::send_era_rewards(era_index);
+
+            weight
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Pause or resume sending rewards to Ethereum.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_rewards_reporting_paused())]
+        pub fn set_rewards_reporting_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+            ensure_root(origin)?;
+
+            Self::set_rewards_reporting_paused_inner(paused);
+
+            Ok(())
+        }
+    }
+
     impl<T: Config> Pallet<T> {
+        /// Pause or resume sending rewards to Ethereum, without checking the origin. When
+        /// resuming, the rewards of the eras that ended while paused are sent from
+        /// `on_initialize` in the following blocks.
+        pub fn set_rewards_reporting_paused_inner(paused: bool) {
+            RewardsReportingPaused::<T>::put(paused);
+            Self::deposit_event(Event::RewardsReportingPausedSet { paused });
+        }
+
         /// Reward validators. Does not check if the validators are valid, caller needs to make sure of that.
         pub fn reward_by_ids(points: impl IntoIterator<Item = (T::AccountId, RewardPoints)>) {
             let active_era = T::EraIndexProvider::active_era();
@@ -234,11 +369,48 @@ pub mod pallet {
             })
         }
 
+        /// Splits the reward points of `era_index` by the external source that supplied each
+        /// validator. Validators without a source are accounted to the default one. If
+        /// `StakeWeightedPoints` is enabled, the points of each source are weighted by stake.
+        pub fn era_rewards_by_source(
+            era_index: EraIndex,
+        ) -> BTreeMap<ExternalSourceId, EraRewardPoints<T::AccountId>> {
+            let mut by_source =
+                RewardPointsForEra::<T>::get(era_index).split_by_source(|account_id| {
+                    T::ExternalSourcesProvider::source_of(era_index, account_id)
+                });
+
+            if T::StakeWeightedPoints::get() {
+                for source_rewards in by_source.values_mut() {
+                    Self::weight_points_by_stake(era_index, source_rewards);
+                }
+            }
+
+            by_source
+        }
+
+        /// Redistributes the points of `era_rewards` proportionally to `points * stake` of each
+        /// validator, keeping the same total except for rounding. Nothing is changed if the
+        /// stake of any of the validators is unknown.
+        pub fn weight_points_by_stake(
+            era_index: EraIndex,
+            era_rewards: &mut EraRewardPoints<T::AccountId>,
+        ) {
+            era_rewards.weight_by_stake(|account_id| {
+                T::ExternalStakeProvider::stake_of(era_index, account_id)
+            });
+        }
+
         pub fn generate_rewards_merkle_proof(
             account_id: T::AccountId,
             era_index: EraIndex,
         ) -> Option<MerkleProof> {
-            let era_rewards = RewardPointsForEra::<T>::get(era_index);
+            // Each source receives a merkle root of its own validators only
+            let source = T::ExternalSourcesProvider::source_of(era_index, &account_id)
+                .unwrap_or(DEFAULT_EXTERNAL_SOURCE);
+            let era_rewards = Self::era_rewards_by_source(era_index)
+                .remove(&source)
+                .unwrap_or_default();
             let utils = era_rewards.generate_era_rewards_utils::<<T as Config>::Hashing>(
                 era_index,
                 Some(account_id),
@@ -266,87 +438,95 @@ pub mod pallet {
             };
 
             RewardPointsForEra::<T>::remove(era_index_to_delete);
+            PendingRewardsEras::<T>::mutate(|eras| eras.retain(|era| *era > era_index_to_delete));
         }
     }
 
     impl<T: Config> tp_traits::OnEraEnd for Pallet<T> {
         fn on_era_end(era_index: EraIndex) {
+            if RewardsReportingPaused::<T>::get() {
+                log::warn!(target: "ext_validators_rewards", "Not sending message for era {:?} because rewards reporting is paused", era_index);
+                // Sent once reporting is resumed
+                PendingRewardsEras::<T>::mutate(|eras| {
+                    if eras.try_push(era_index).is_err() {
+                        log::error!(target: "ext_validators_rewards", "Too many eras pending, rewards for era {:?} will not be sent", era_index);
+                    }
+                });
+                return;
+            }
+
+            Self::send_era_rewards(era_index);
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Mint the inflation of `era_index` and report its rewards to Ethereum.
+        fn send_era_rewards(era_index: EraIndex) {
             // Will send a ReportRewards message to Ethereum unless:
             // - the reward token is misconfigured
             // - the tokens inflation is 0 (misconfigured inflation)
             // - the total points is 0 (no rewards to distribute)
             // - it fails to mint the tokens in the Ethereum Sovereign Account
             // - the generated message doesn't pass validation
+            //
+            // One message is sent per external source, each one with the points of the
+            // validators supplied by that source and its share of the inflation.
 
             let token_location = T::TokenLocationReanchored::get();
             let token_id = T::TokenIdFromLocation::convert_back(&token_location);
 
-            if let Some(token_id) = token_id {
-                let era_rewards = RewardPointsForEra::<T>::get(era_index);
-                if let Some(utils) = era_rewards
-                    .generate_era_rewards_utils::<<T as Config>::Hashing>(era_index, None)
-                {
-                    let tokens_inflated = T::EraInflationProvider::get();
+            let Some(token_id) = token_id else {
+                log::error!(target: "ext_validators_rewards", "no token id found for location {:?}", token_location);
+                return;
+            };
 
-                    if tokens_inflated.is_zero() {
-                        log::error!(target: "ext_validators_rewards", "Not sending message because tokens_inflated is 0");
-                        return;
-                    }
+            let tokens_inflated = T::EraInflationProvider::get();
 
-                    if utils.total_points.is_zero() {
-                        log::error!(target: "ext_validators_rewards", "Not sending message because total_points is 0");
-                        return;
-                    }
+            if tokens_inflated.is_zero() {
+                log::error!(target: "ext_validators_rewards", "Not sending message because tokens_inflated is 0");
+                return;
+            }
 
-                    let ethereum_sovereign_account = T::RewardsEthereumSovereignAccount::get();
-                    if let Err(err) =
-                        T::Currency::mint_into(&ethereum_sovereign_account, tokens_inflated.into())
-                    {
-                        log::error!(target: "ext_validators_rewards", "Failed to mint inflation into Ethereum Soverein Account: {err:?}");
-                        log::error!(target: "ext_validators_rewards", "Not sending message since there are no rewards to distribute");
-                        return;
-                    }
+            let rewards_by_source = Self::era_rewards_by_source(era_index);
+            let total_points = rewards_by_source.values().fold(0u128, |acc, rewards| {
+                acc.saturating_add(u128::from(rewards.total))
+            });
+            if total_points.is_zero() {
+                log::error!(target: "ext_validators_rewards", "Not sending message because total_points is 0");
+                return;
+            }
 
-                    let command = Command::ReportRewards {
-                        external_idx: T::ExternalIndexProvider::get_external_index(),
-                        era_index,
-                        total_points: utils.total_points,
-                        tokens_inflated,
-                        rewards_merkle_root: utils.rewards_merkle_root,
-                        token_id,
-                    };
-
-                    let channel_id: ChannelId = snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL;
-
-                    let outbound_message = TanssiMessage {
-                        id: None,
-                        channel_id,
-                        command: command.clone(),
-                    };
-
-                    // Validate and deliver the message
-                    match T::ValidateMessage::validate(&outbound_message) {
-                        Ok((ticket, _fee)) => {
-                            let message_id = ticket.message_id();
-                            if let Err(err) = T::OutboundQueue::deliver(ticket) {
-                                log::error!(target: "ext_validators_rewards", "OutboundQueue delivery of message failed. {err:?}");
-                            } else {
-                                Self::deposit_event(Event::RewardsMessageSent {
-                                    message_id,
-                                    rewards_command: command,
-                                });
-                            }
-                        }
-                        Err(err) => {
-                            log::error!(target: "ext_validators_rewards", "OutboundQueue validation of message failed. {err:?}");
-                        }
-                    }
+            let ethereum_sovereign_account = T::RewardsEthereumSovereignAccount::get();
+            if let Err(err) =
+                T::Currency::mint_into(&ethereum_sovereign_account, tokens_inflated.into())
+            {
+                log::error!(target: "ext_validators_rewards", "Failed to mint inflation into Ethereum Soverein Account: {err:?}");
+                log::error!(target: "ext_validators_rewards", "Not sending message since there are no rewards to distribute");
+                return;
+            }
 
-                    frame_system::Pallet::<T>::register_extra_weight_unchecked(
-                        T::WeightInfo::on_era_end(),
-                        DispatchClass::Mandatory,
-                    );
+            let last_source = rewards_by_source.len().saturating_sub(1);
+            let mut remaining_tokens = tokens_inflated;
+
+            for (i, (source, era_rewards)) in rewards_by_source.into_iter().enumerate() {
+                // The last source gets whatever is left so that no tokens are lost to rounding
+                let source_tokens = if i == last_source {
+                    remaining_tokens
                 } else {
+                    multiply_by_rational_with_rounding(
+                        tokens_inflated,
+                        u128::from(era_rewards.total),
+                        total_points,
+                        Rounding::Down,
+                    )
+                    .unwrap_or_default()
+                    .min(remaining_tokens)
+                };
+                remaining_tokens.saturating_reduce(source_tokens);
+
+                let Some(utils) = era_rewards
+                    .generate_era_rewards_utils::<<T as Config>::Hashing>(era_index, None)
+                else {
                     // Unreachable, this should never happen as we are sending
                     // None as the second param in Self::generate_era_rewards_utils.
                     log::error!(
@@ -354,9 +534,61 @@ pub mod pallet {
                         "Outbound message not sent for era {:?}!",
                         era_index
                     );
+                    continue;
+                };
+
+                // Use the external index and channel each source had in this era, they may have
+                // changed since
+                let external_idx = T::ExternalSourcesProvider::external_index_of(era_index, source)
+                    .unwrap_or_else(|| {
+                        // Not recorded when external validators are skipped
+                        if source == DEFAULT_EXTERNAL_SOURCE {
+                            T::ExternalIndexProvider::get_external_index()
+                        } else {
+                            Default::default()
+                        }
+                    });
+                let channel_id = T::ExternalSourcesProvider::channel_of(era_index, source)
+                    .map(ChannelId::from)
+                    .unwrap_or(snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL);
+
+                let command = Command::ReportRewards {
+                    external_idx,
+                    era_index,
+                    total_points: utils.total_points,
+                    tokens_inflated: source_tokens,
+                    rewards_merkle_root: utils.rewards_merkle_root,
+                    token_id,
+                };
+
+                let outbound_message = TanssiMessage {
+                    id: None,
+                    channel_id,
+                    command: command.clone(),
+                };
+
+                // Validate and deliver the message
+                match T::ValidateMessage::validate(&outbound_message) {
+                    Ok((ticket, _fee)) => {
+                        let message_id = ticket.message_id();
+                        if let Err(err) = T::OutboundQueue::deliver(ticket) {
+                            log::error!(target: "ext_validators_rewards", "OutboundQueue delivery of message failed. {err:?}");
+                        } else {
+                            Self::deposit_event(Event::RewardsMessageSent {
+                                message_id,
+                                rewards_command: command,
+                            });
+                        }
+                    }
+                    Err(err) => {
+                        log::error!(target: "ext_validators_rewards", "OutboundQueue validation of message failed. {err:?}");
+                    }
                 }
-            } else {
-                log::error!(target: "ext_validators_rewards", "no token id found for location {:?}", token_location);
+
+                frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                    T::WeightInfo::on_era_end(),
+                    DispatchClass::Mandatory,
+                );
             }
         }
     }
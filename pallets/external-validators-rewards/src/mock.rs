@@ -157,6 +157,7 @@ parameter_types! {
         = 0xffffffffffffffff;
     pub RewardTokenLocation: Location = Location::here();
     pub EraInflationProvider: u128 = Mock::mock().era_inflation.unwrap_or(42);
+    pub StakeWeightedPoints: bool = Mock::mock().stake_weighted_points;
 }
 
 impl pallet_external_validators_rewards::Config for Test {
@@ -166,6 +167,9 @@ impl pallet_external_validators_rewards::Config for Test {
     type DisputeStatementPoints = ConstU32<20>;
     type EraInflationProvider = EraInflationProvider;
     type ExternalIndexProvider = TimestampProvider;
+    type ExternalSourcesProvider = Mock;
+    type ExternalStakeProvider = Mock;
+    type StakeWeightedPoints = StakeWeightedPoints;
     type GetWhitelistedValidators = ();
     type Hashing = Keccak256;
     type ValidateMessage = ();
@@ -184,7 +188,10 @@ impl pallet_external_validators_rewards::Config for Test {
 pub mod mock_data {
     use {
         frame_support::pallet_prelude::*,
-        tp_traits::{ActiveEraInfo, EraIndex, EraIndexProvider},
+        tp_traits::{
+            ActiveEraInfo, EraIndex, EraIndexProvider, ExternalSourceId, ExternalSourcesProvider,
+            ExternalStakeProvider, DEFAULT_EXTERNAL_SOURCE,
+        },
         xcm::latest::prelude::*,
     };
 
@@ -192,6 +199,10 @@ pub mod mock_data {
     pub struct Mocks {
         pub active_era: Option<ActiveEraInfo>,
         pub era_inflation: Option<u128>,
+        pub validator_sources: Vec<(u64, ExternalSourceId)>,
+        pub validator_stakes: Vec<(u64, u128)>,
+        pub stake_weighted_points: bool,
+        pub default_external_index: Option<u64>,
     }
 
     #[pallet::config]
@@ -242,6 +253,38 @@ pub mod mock_data {
         }
     }
 
+    impl<T: Config> ExternalSourcesProvider<u64> for Pallet<T> {
+        fn source_of(_era_index: EraIndex, validator: &u64) -> Option<ExternalSourceId> {
+            Self::mock()
+                .validator_sources
+                .iter()
+                .find(|(v, _)| v == validator)
+                .map(|(_, source)| *source)
+        }
+
+        fn external_index_of(_era_index: EraIndex, source: ExternalSourceId) -> Option<u64> {
+            if source == DEFAULT_EXTERNAL_SOURCE {
+                Self::mock().default_external_index
+            } else {
+                Some(u64::from(source) * 1000)
+            }
+        }
+
+        fn channel_of(_era_index: EraIndex, source: ExternalSourceId) -> Option<[u8; 32]> {
+            (source != DEFAULT_EXTERNAL_SOURCE).then_some([source as u8; 32])
+        }
+    }
+
+    impl<T: Config> ExternalStakeProvider<u64> for Pallet<T> {
+        fn stake_of(_era_index: EraIndex, validator: &u64) -> Option<u128> {
+            Self::mock()
+                .validator_stakes
+                .iter()
+                .find(|(v, _)| v == validator)
+                .map(|(_, stake)| *stake)
+        }
+    }
+
     impl<T: Config> Get<Location> for Pallet<T> {
         fn get() -> Location {
             Self::token_loc()
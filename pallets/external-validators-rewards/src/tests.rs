@@ -15,9 +15,13 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
-    crate::{self as pallet_external_validators_rewards, mock::*},
+    crate::{
+        self as pallet_external_validators_rewards, mock::*, PendingRewardsEras, RewardPointsForEra,
+    },
     alloc::collections::btree_map::BTreeMap,
+    frame_support::{assert_noop, assert_ok},
     sp_core::H256,
+    sp_runtime::traits::BadOrigin,
     tp_bridge::Command,
     tp_traits::{ActiveEraInfo, OnEraEnd, OnEraStart},
     xcm::latest::prelude::*,
@@ -129,6 +133,166 @@ fn test_on_era_end() {
     })
 }
 
+#[test]
+fn test_on_era_end_with_multiple_sources() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Mock::mutate(|mock| {
+            mock.active_era = Some(ActiveEraInfo {
+                index: 1,
+                start: None,
+            });
+            mock.era_inflation = Some(100);
+            mock.validator_sources = vec![(3, 1), (5, 2)];
+        });
+        ExternalValidatorsRewards::reward_by_ids([(1, 10), (2, 10), (3, 20), (5, 30)]);
+        ExternalValidatorsRewards::on_era_end(1);
+
+        let by_source = ExternalValidatorsRewards::era_rewards_by_source(1);
+        assert_eq!(by_source.len(), 3);
+        let root_of = |source| {
+            by_source[&source]
+                .generate_era_rewards_utils::<<Test as pallet_external_validators_rewards::Config>::Hashing>(1, None)
+                .unwrap()
+                .rewards_merkle_root
+        };
+
+        let sent_commands: Vec<Command> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                RuntimeEvent::ExternalValidatorsRewards(
+                    crate::Event::RewardsMessageSent {
+                        rewards_command, ..
+                    },
+                ) => Some(rewards_command),
+                _ => None,
+            })
+            .collect();
+
+        // Validators without a source go through the default one, the last source gets the
+        // rounding remainder
+        assert_eq!(
+            sent_commands,
+            vec![
+                Command::ReportRewards {
+                    external_idx: 31000u64,
+                    era_index: 1u32,
+                    total_points: 20,
+                    tokens_inflated: 28,
+                    rewards_merkle_root: root_of(0),
+                    token_id: H256::repeat_byte(0x01),
+                },
+                Command::ReportRewards {
+                    external_idx: 1000u64,
+                    era_index: 1u32,
+                    total_points: 20,
+                    tokens_inflated: 28,
+                    rewards_merkle_root: root_of(1),
+                    token_id: H256::repeat_byte(0x01),
+                },
+                Command::ReportRewards {
+                    external_idx: 2000u64,
+                    era_index: 1u32,
+                    total_points: 30,
+                    tokens_inflated: 44,
+                    rewards_merkle_root: root_of(2),
+                    token_id: H256::repeat_byte(0x01),
+                },
+            ]
+        );
+
+        // Proofs are generated against the root of the validator's source
+        let proof = ExternalValidatorsRewards::generate_rewards_merkle_proof(5, 1).unwrap();
+        assert_eq!(proof.root, root_of(2));
+        assert!(ExternalValidatorsRewards::verify_rewards_merkle_proof(proof));
+    })
+}
+
+#[test]
+fn test_on_era_end_uses_external_index_of_the_era() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Mock::mutate(|mock| {
+            mock.active_era = Some(ActiveEraInfo {
+                index: 1,
+                start: None,
+            });
+            // The current external index is 31000, the era had an older one
+            mock.default_external_index = Some(500);
+        });
+        ExternalValidatorsRewards::reward_by_ids([(1, 10)]);
+        ExternalValidatorsRewards::on_era_end(1);
+
+        let era_rewards = pallet_external_validators_rewards::RewardPointsForEra::<Test>::get(1);
+        let rewards_utils = era_rewards.generate_era_rewards_utils::<<Test as pallet_external_validators_rewards::Config>::Hashing>(1, None);
+
+        System::assert_last_event(RuntimeEvent::ExternalValidatorsRewards(
+            crate::Event::RewardsMessageSent {
+                message_id: Default::default(),
+                rewards_command: Command::ReportRewards {
+                    external_idx: 500u64,
+                    era_index: 1u32,
+                    total_points: 10,
+                    tokens_inflated:
+                        <Test as pallet_external_validators_rewards::Config>::EraInflationProvider::get(),
+                    rewards_merkle_root: rewards_utils.unwrap().rewards_merkle_root,
+                    token_id: H256::repeat_byte(0x01),
+                },
+            },
+        ));
+    })
+}
+
+#[test]
+fn era_points_can_be_weighted_by_stake() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Mock::mutate(|mock| {
+            mock.active_era = Some(ActiveEraInfo {
+                index: 1,
+                start: None,
+            });
+            mock.stake_weighted_points = true;
+            mock.validator_stakes = vec![(1, 100), (3, 300)];
+        });
+        ExternalValidatorsRewards::reward_by_ids([(1, 10), (3, 30)]);
+
+        let mut expected = BTreeMap::new();
+        expected.insert(1, 4);
+        expected.insert(3, 36);
+        let by_source = ExternalValidatorsRewards::era_rewards_by_source(1);
+        assert_eq!(by_source[&0].individual, expected);
+        assert_eq!(by_source[&0].total, 40);
+
+        // Points stored for the era are not modified
+        let era_rewards = pallet_external_validators_rewards::RewardPointsForEra::<Test>::get(1);
+        assert_eq!(era_rewards.individual[&1], 10);
+        assert_eq!(era_rewards.individual[&3], 30);
+    })
+}
+
+#[test]
+fn era_points_are_not_weighted_if_some_stake_is_unknown() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Mock::mutate(|mock| {
+            mock.active_era = Some(ActiveEraInfo {
+                index: 1,
+                start: None,
+            });
+            mock.stake_weighted_points = true;
+            mock.validator_stakes = vec![(1, 100)];
+        });
+        ExternalValidatorsRewards::reward_by_ids([(1, 10), (3, 30)]);
+
+        let by_source = ExternalValidatorsRewards::era_rewards_by_source(1);
+        assert_eq!(
+            by_source[&0].individual,
+            RewardPointsForEra::<Test>::get(1).individual
+        );
+    })
+}
+
 #[test]
 fn test_on_era_end_without_proper_token() {
     new_test_ext().execute_with(|| {
@@ -277,3 +441,108 @@ fn test_on_era_end_with_zero_points() {
         );
     })
 }
+
+#[test]
+fn test_on_era_end_with_rewards_reporting_paused() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Mock::mutate(|mock| {
+            mock.active_era = Some(ActiveEraInfo {
+                index: 1,
+                start: None,
+            })
+        });
+        assert_noop!(
+            ExternalValidatorsRewards::set_rewards_reporting_paused(RuntimeOrigin::signed(1), true),
+            BadOrigin
+        );
+        assert_ok!(ExternalValidatorsRewards::set_rewards_reporting_paused(
+            RuntimeOrigin::root(),
+            true
+        ));
+
+        ExternalValidatorsRewards::reward_by_ids([(1, 10), (3, 30)]);
+        ExternalValidatorsRewards::on_era_end(1);
+
+        // Points are kept but nothing is sent
+        assert_eq!(RewardPointsForEra::<Test>::get(1).total, 40);
+        assert_eq!(PendingRewardsEras::<Test>::get().into_inner(), vec![1]);
+        assert!(!System::events().iter().any(|record| matches!(
+            record.event,
+            RuntimeEvent::ExternalValidatorsRewards(crate::Event::RewardsMessageSent { .. })
+        )));
+
+        // The skipped era is sent in the next block after resuming
+        assert_ok!(ExternalValidatorsRewards::set_rewards_reporting_paused(
+            RuntimeOrigin::root(),
+            false
+        ));
+        assert_eq!(PendingRewardsEras::<Test>::get().into_inner(), vec![1]);
+        run_to_block(2);
+        assert!(PendingRewardsEras::<Test>::get().is_empty());
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            RuntimeEvent::ExternalValidatorsRewards(crate::Event::RewardsMessageSent {
+                rewards_command: Command::ReportRewards { era_index: 1, .. },
+                ..
+            })
+        )));
+    })
+}
+
+#[test]
+fn test_pending_rewards_eras_are_removed_with_their_points() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ExternalValidatorsRewards::set_rewards_reporting_paused(
+            RuntimeOrigin::root(),
+            true
+        ));
+
+        for era_index in 1..=3 {
+            Mock::mutate(|mock| {
+                mock.active_era = Some(ActiveEraInfo {
+                    index: era_index,
+                    start: None,
+                })
+            });
+            ExternalValidatorsRewards::reward_by_ids([(1, 10)]);
+            ExternalValidatorsRewards::on_era_end(era_index);
+        }
+        assert_eq!(
+            PendingRewardsEras::<Test>::get().into_inner(),
+            vec![1, 2, 3]
+        );
+
+        // HistoryDepth is 10, era 1 goes out of history and its points cannot be reported
+        ExternalValidatorsRewards::on_era_start(11, 0, 11);
+        assert_eq!(PendingRewardsEras::<Test>::get().into_inner(), vec![2, 3]);
+
+        assert_ok!(ExternalValidatorsRewards::set_rewards_reporting_paused(
+            RuntimeOrigin::root(),
+            false
+        ));
+        let sent_eras = || -> Vec<_> {
+            System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    RuntimeEvent::ExternalValidatorsRewards(crate::Event::RewardsMessageSent {
+                        rewards_command: Command::ReportRewards { era_index, .. },
+                        ..
+                    }) => Some(era_index),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Pending eras are sent one per block
+        System::reset_events();
+        run_to_block(2);
+        assert_eq!(sent_eras(), vec![2]);
+        assert_eq!(PendingRewardsEras::<Test>::get().into_inner(), vec![3]);
+        System::reset_events();
+        run_to_block(3);
+        assert_eq!(sent_eras(), vec![3]);
+        assert!(PendingRewardsEras::<Test>::get().is_empty());
+    })
+}
@@ -22,6 +22,7 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `COV0768`, CPU: `AMD Ryzen 9 7950X 16-Core Processor`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dancelight-dev"), DB CACHE: 1024
+//! PLACEHOLDERS, written by hand and not benchmarked yet: `set_rewards_reporting_paused`
 
 // Executed Command:
 // target/release/tanssi-relay
@@ -54,6 +55,7 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_external_validators_rewards.
 pub trait WeightInfo {
 	fn on_era_end() -> Weight;
+	fn set_rewards_reporting_paused() -> Weight;
 }
 
 /// Weights for pallet_external_validators_rewards using the Substrate node and recommended hardware.
@@ -75,15 +77,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `MessageQueue::Pages` (`max_values`: None, `max_size`: Some(32845), added: 35320, mode: `MaxEncodedLen`)
 	/// Storage: UNKNOWN KEY `0xf5207f03cfdce586301014700e2c2593fad157e461d71fd4c1f936839a5f1f3e` (r:0 w:1)
 	/// Proof: UNKNOWN KEY `0xf5207f03cfdce586301014700e2c2593fad157e461d71fd4c1f936839a5f1f3e` (r:0 w:1)
+	/// Storage: `ExternalValidatorsRewards::RewardsReportingPaused` (r:1 w:0)
+	/// Proof: `ExternalValidatorsRewards::RewardsReportingPaused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
 	fn on_era_end() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `36522`
 		//  Estimated: `39987`
 		// Minimum execution time: 1_042_933_000 picoseconds.
 		Weight::from_parts(1_136_401_000, 39987)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_rewards_reporting_paused() -> Weight {
+		Weight::from_parts(4_238_000, 1743)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -104,13 +114,21 @@ impl WeightInfo for () {
 	/// Proof: `MessageQueue::Pages` (`max_values`: None, `max_size`: Some(32845), added: 35320, mode: `MaxEncodedLen`)
 	/// Storage: UNKNOWN KEY `0xf5207f03cfdce586301014700e2c2593fad157e461d71fd4c1f936839a5f1f3e` (r:0 w:1)
 	/// Proof: UNKNOWN KEY `0xf5207f03cfdce586301014700e2c2593fad157e461d71fd4c1f936839a5f1f3e` (r:0 w:1)
+	/// Storage: `ExternalValidatorsRewards::RewardsReportingPaused` (r:1 w:0)
+	/// Proof: `ExternalValidatorsRewards::RewardsReportingPaused` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
 	fn on_era_end() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `36522`
 		//  Estimated: `39987`
 		// Minimum execution time: 1_042_933_000 picoseconds.
 		Weight::from_parts(1_136_401_000, 39987)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_rewards_reporting_paused() -> Weight {
+		Weight::from_parts(4_238_000, 1743)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -210,6 +210,83 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn set_external_source() -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        // Worst case: all the other sources are registered, so the channel check has to go
+        // through all of them
+        let max_sources = T::MaxExternalSources::get();
+        for source in 1..max_sources {
+            ExternalSources::<T>::insert(
+                source,
+                ExternalSourceInfo {
+                    channel_id: [source as u8; 32],
+                    max_validators: T::MaxExternalValidators::get(),
+                },
+            );
+        }
+        let info = ExternalSourceInfo {
+            channel_id: [u8::MAX; 32],
+            max_validators: T::MaxExternalValidators::get(),
+        };
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, max_sources, info.clone());
+
+        assert_last_event::<T>(
+            Event::ExternalSourceSet {
+                source: max_sources,
+                info,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_external_source() -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        let source = 1;
+        ExternalSources::<T>::insert(
+            source,
+            ExternalSourceInfo {
+                channel_id: [1; 32],
+                max_validators: T::MaxExternalValidators::get(),
+            },
+        );
+        let (_account_ids, validator_ids): (Vec<T::AccountId>, Vec<<T as Config>::ValidatorId>) =
+            invulnerables::<T>(4).into_iter().unzip();
+        ExternalValidators::<T>::set_external_validators_for_source(source, validator_ids, 1)
+            .expect("set validators for source failed");
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, source);
+
+        assert_last_event::<T>(Event::ExternalSourceRemoved { source }.into());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_external_merge_policy() -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, MergePolicy::RoundRobin);
+
+        assert_last_event::<T>(
+            Event::ExternalMergePolicySet {
+                policy: MergePolicy::RoundRobin,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
     // worst case for new session.
     #[benchmark]
     fn new_session(
@@ -24,6 +24,14 @@
 //!      Are not rewarded.
 //! - ExternalValidators: Validators set using storage proofs from another blockchain. Can be disabled by setting
 //!     `SkipExternalValidators` to true.
+//! - ExternalSources: Additional sources of external validators, for example other Symbiotic networks. Each one
+//!     has its own channel, maximum number of seats and external index. The default source (`DEFAULT_EXTERNAL_SOURCE`)
+//!     is the one stored in `ExternalValidators`. Validators of all the sources are merged following the
+//!     `ExternalMergePolicy`, and the source of each validator is kept for `HistoryDepth` eras so rewards and
+//!     slashes can be routed back to it.
+//! - ExternalValidatorStakes: Sources can send their validators together with their stake. In that case only the
+//!     validators with the highest stake are selected, and the stake is kept for `HistoryDepth` eras so rewards can be
+//!     weighted by it.
 //!
 //! Validators only change once per era. By default the era changes after a fixed number of sessions, but new eras
 //! can be forced or disabled using a root extrinsic.
@@ -45,11 +53,14 @@ use {
     sp_runtime::{traits::Get, RuntimeDebug},
     sp_staking::SessionIndex,
     tp_traits::{
-        ActiveEraInfo, EraIndex, EraIndexProvider, ExternalIndexProvider, InvulnerablesProvider,
-        OnEraEnd, OnEraStart, ValidatorProvider,
+        ActiveEraInfo, EraIndex, EraIndexProvider, ExternalIndexProvider, ExternalSourcesProvider,
+        ExternalStakeProvider, InvulnerablesProvider, OnEraEnd, OnEraStart, ValidatorProvider,
     },
 };
 
+/// The default source is the one stored in `ExternalValidators`.
+pub use tp_traits::{ExternalSourceId, DEFAULT_EXTERNAL_SOURCE};
+
 #[cfg(test)]
 mod mock;
 
@@ -75,6 +86,7 @@ pub mod pallet {
             BoundedVec, DefaultNoBound,
         },
         frame_system::pallet_prelude::*,
+        snowbridge_core::{ChannelId, PRIMARY_GOVERNANCE_CHANNEL},
         sp_runtime::{traits::Convert, SaturatedConversion},
     };
 
@@ -102,10 +114,14 @@ pub mod pallet {
         #[pallet::constant]
         type MaxWhitelistedValidators: Get<u32>;
 
-        /// Maximum number of external validators.
+        /// Maximum number of external validators, including all the external sources.
         #[pallet::constant]
         type MaxExternalValidators: Get<u32>;
 
+        /// Maximum number of external sources, not counting the default one.
+        #[pallet::constant]
+        type MaxExternalSources: Get<u32>;
+
         /// A stable ID for a validator.
         type ValidatorId: Member
             + Parameter
@@ -164,10 +180,97 @@ pub mod pallet {
         StorageValue<_, BoundedVec<T::ValidatorId, T::MaxWhitelistedValidators>, ValueQuery>;
 
     /// Validators set using storage proofs from another blockchain. Ignored if `SkipExternalValidators` is true.
+    /// These are the validators of the default external source.
     #[pallet::storage]
     pub type ExternalValidators<T: Config> =
         StorageValue<_, BoundedVec<T::ValidatorId, T::MaxExternalValidators>, ValueQuery>;
 
+    /// Additional external sources, not including the default one.
+    #[pallet::storage]
+    pub type ExternalSources<T: Config> =
+        CountedStorageMap<_, Twox64Concat, ExternalSourceId, ExternalSourceInfo, OptionQuery>;
+
+    /// Validators set by each of the additional external sources.
+    #[pallet::storage]
+    pub type ExternalValidatorsBySource<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        ExternalSourceId,
+        BoundedVec<T::ValidatorId, T::MaxExternalValidators>,
+        ValueQuery,
+    >;
+
+    /// Latest external index received from each of the additional external sources.
+    #[pallet::storage]
+    pub type ExternalIndexBySource<T: Config> =
+        StorageMap<_, Twox64Concat, ExternalSourceId, u64, ValueQuery>;
+
+    /// Stake of the validators of each source, including the default one. Only present if the
+    /// source sent its validators together with their stake.
+    #[pallet::storage]
+    pub type ExternalValidatorStakes<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        ExternalSourceId,
+        BoundedVec<(T::ValidatorId, u128), T::MaxExternalValidators>,
+        ValueQuery,
+    >;
+
+    /// How validators from the different external sources are merged.
+    #[pallet::storage]
+    pub type ExternalMergePolicy<T: Config> = StorageValue<_, MergePolicy, ValueQuery>;
+
+    /// Source that supplied each external validator of an era, for the last
+    /// [`Config::HistoryDepth`] eras.
+    #[pallet::storage]
+    pub type ErasValidatorSource<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::ValidatorId,
+        ExternalSourceId,
+        OptionQuery,
+    >;
+
+    /// External index of each source for an era, for the last [`Config::HistoryDepth`] eras.
+    #[pallet::storage]
+    pub type ErasExternalIndices<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        ExternalSourceId,
+        u64,
+        OptionQuery,
+    >;
+
+    /// Channel of each additional source for an era, for the last [`Config::HistoryDepth`] eras.
+    /// Kept so that messages about an era use the channel of the source at that time, even if
+    /// the source was removed or changed since.
+    #[pallet::storage]
+    pub type ErasSourceChannel<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        ExternalSourceId,
+        [u8; 32],
+        OptionQuery,
+    >;
+
+    /// Stake of each external validator of an era, for the last [`Config::HistoryDepth`] eras.
+    #[pallet::storage]
+    pub type ErasValidatorStake<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        EraIndex,
+        Twox64Concat,
+        T::ValidatorId,
+        u128,
+        OptionQuery,
+    >;
+
     /// Allow to disable external validators.
     #[pallet::storage]
     pub type SkipExternalValidators<T: Config> = StorageValue<_, bool, ValueQuery>;
@@ -262,6 +365,21 @@ pub mod pallet {
             validators: Vec<T::ValidatorId>,
             external_index: u64,
         },
+        /// External validators of an additional source were set.
+        ExternalSourceValidatorsSet {
+            source: ExternalSourceId,
+            validators: Vec<T::ValidatorId>,
+            external_index: u64,
+        },
+        /// An external source was registered or updated.
+        ExternalSourceSet {
+            source: ExternalSourceId,
+            info: ExternalSourceInfo,
+        },
+        /// An external source was removed.
+        ExternalSourceRemoved { source: ExternalSourceId },
+        /// A new merge policy for external sources was set.
+        ExternalMergePolicySet { policy: MergePolicy },
     }
 
     #[pallet::error]
@@ -276,6 +394,15 @@ pub mod pallet {
         NoKeysRegistered,
         /// Unable to derive validator id from account id
         UnableToDeriveValidatorId,
+        /// The default external source cannot be registered or removed
+        DefaultExternalSource,
+        /// There are too many external sources
+        TooManyExternalSources,
+        /// External source is not registered
+        UnknownExternalSource,
+        /// Another external source already uses this channel, or it is the primary governance
+        /// channel used by the default source
+        ChannelAlreadyInUse,
     }
 
     #[pallet::call]
@@ -303,6 +430,127 @@ pub mod pallet {
         #[allow(clippy::useless_conversion)]
         pub fn add_whitelisted(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
             T::UpdateOrigin::ensure_origin(origin)?;
+            Self::add_whitelisted_inner(who)
+        }
+
+        /// Remove an account `who` from the list of `WhitelistedValidators` collators.
+        ///
+        /// The origin for this call must be the `UpdateOrigin`.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::remove_whitelisted(T::MaxWhitelistedValidators::get()))]
+        pub fn remove_whitelisted(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            Self::remove_whitelisted_inner(who)
+        }
+
+        /// Force when the next era will start. Possible values: next session, never, same as always.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::force_era())]
+        pub fn force_era(origin: OriginFor<T>, mode: Forcing) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            Self::set_force_era(mode);
+            Ok(())
+        }
+
+        /// Manually set external validators. Should only be needed for tests, validators are set
+        /// automatically by the bridge.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::set_external_validators())]
+        pub fn set_external_validators(
+            origin: OriginFor<T>,
+            validators: Vec<T::ValidatorId>,
+            external_index: u64,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            Self::set_external_validators_inner(validators, external_index)
+        }
+
+        /// Register a new external source or update an existing one. Each source needs a channel
+        /// of its own, other than the primary governance channel used by the default source.
+        ///
+        /// The origin for this call must be the `UpdateOrigin`.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_external_source())]
+        pub fn set_external_source(
+            origin: OriginFor<T>,
+            source: ExternalSourceId,
+            info: ExternalSourceInfo,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                source != DEFAULT_EXTERNAL_SOURCE,
+                Error::<T>::DefaultExternalSource
+            );
+            ensure!(
+                ChannelId::from(info.channel_id) != PRIMARY_GOVERNANCE_CHANNEL,
+                Error::<T>::ChannelAlreadyInUse
+            );
+            ensure!(
+                Self::source_for_channel(&info.channel_id)
+                    .is_none_or(|existing| existing == source),
+                Error::<T>::ChannelAlreadyInUse
+            );
+            if !ExternalSources::<T>::contains_key(source) {
+                ensure!(
+                    ExternalSources::<T>::count() < T::MaxExternalSources::get(),
+                    Error::<T>::TooManyExternalSources
+                );
+            }
+
+            ExternalSources::<T>::insert(source, info.clone());
+
+            Self::deposit_event(Event::ExternalSourceSet { source, info });
+            Ok(())
+        }
+
+        /// Remove an external source together with its validators.
+        ///
+        /// The origin for this call must be the `UpdateOrigin`.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::remove_external_source())]
+        pub fn remove_external_source(
+            origin: OriginFor<T>,
+            source: ExternalSourceId,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                ExternalSources::<T>::contains_key(source),
+                Error::<T>::UnknownExternalSource
+            );
+
+            ExternalSources::<T>::remove(source);
+            ExternalValidatorsBySource::<T>::remove(source);
+            ExternalIndexBySource::<T>::remove(source);
+            ExternalValidatorStakes::<T>::remove(source);
+
+            Self::deposit_event(Event::ExternalSourceRemoved { source });
+            Ok(())
+        }
+
+        /// Set how validators from the different external sources are merged.
+        ///
+        /// The origin for this call must be the `UpdateOrigin`.
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::set_external_merge_policy())]
+        pub fn set_external_merge_policy(
+            origin: OriginFor<T>,
+            policy: MergePolicy,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ExternalMergePolicy::<T>::put(policy);
+
+            Self::deposit_event(Event::ExternalMergePolicySet { policy });
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Add `who` to the list of `WhitelistedValidators`, without checking the origin.
+        pub fn add_whitelisted_inner(who: T::AccountId) -> DispatchResult {
             // don't let one unprepared validator ruin things for everyone.
             let maybe_validator_id = T::ValidatorIdOf::convert(who.clone())
                 .filter(T::ValidatorRegistration::is_registered);
@@ -324,14 +572,8 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Remove an account `who` from the list of `WhitelistedValidators` collators.
-        ///
-        /// The origin for this call must be the `UpdateOrigin`.
-        #[pallet::call_index(2)]
-        #[pallet::weight(T::WeightInfo::remove_whitelisted(T::MaxWhitelistedValidators::get()))]
-        pub fn remove_whitelisted(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
-            T::UpdateOrigin::ensure_origin(origin)?;
-
+        /// Remove `who` from the list of `WhitelistedValidators`, without checking the origin.
+        pub fn remove_whitelisted_inner(who: T::AccountId) -> DispatchResult {
             let validator_id = T::ValidatorIdOf::convert(who.clone())
                 .ok_or(Error::<T>::UnableToDeriveValidatorId)?;
 
@@ -348,31 +590,6 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Force when the next era will start. Possible values: next session, never, same as always.
-        #[pallet::call_index(3)]
-        #[pallet::weight(T::WeightInfo::force_era())]
-        pub fn force_era(origin: OriginFor<T>, mode: Forcing) -> DispatchResult {
-            T::UpdateOrigin::ensure_origin(origin)?;
-            Self::set_force_era(mode);
-            Ok(())
-        }
-
-        /// Manually set external validators. Should only be needed for tests, validators are set
-        /// automatically by the bridge.
-        #[pallet::call_index(4)]
-        #[pallet::weight(T::WeightInfo::set_external_validators())]
-        pub fn set_external_validators(
-            origin: OriginFor<T>,
-            validators: Vec<T::ValidatorId>,
-            external_index: u64,
-        ) -> DispatchResult {
-            T::UpdateOrigin::ensure_origin(origin)?;
-
-            Self::set_external_validators_inner(validators, external_index)
-        }
-    }
-
-    impl<T: Config> Pallet<T> {
         pub fn set_external_validators_inner(
             validators: Vec<T::ValidatorId>,
             external_index: u64,
@@ -381,6 +598,7 @@ pub mod pallet {
             let validators = BoundedVec::truncate_from(validators);
             <ExternalValidators<T>>::put(&validators);
             <ExternalIndex<T>>::put(external_index);
+            <ExternalValidatorStakes<T>>::remove(DEFAULT_EXTERNAL_SOURCE);
 
             Self::deposit_event(Event::<T>::ExternalValidatorsSet {
                 validators: validators.into_inner(),
@@ -389,8 +607,120 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Set the validators of `source`. The default source is stored in `ExternalValidators`,
+        /// other sources must have been registered first.
+        pub fn set_external_validators_for_source(
+            source: ExternalSourceId,
+            validators: Vec<T::ValidatorId>,
+            external_index: u64,
+        ) -> DispatchResult {
+            if source == DEFAULT_EXTERNAL_SOURCE {
+                return Self::set_external_validators_inner(validators, external_index);
+            }
+
+            let info =
+                ExternalSources::<T>::get(source).ok_or(Error::<T>::UnknownExternalSource)?;
+
+            // If more validators than the seats of this source, take the first n
+            let validators: BoundedVec<_, T::MaxExternalValidators> = BoundedVec::truncate_from(
+                validators
+                    .into_iter()
+                    .take(info.max_validators as usize)
+                    .collect(),
+            );
+            ExternalValidatorsBySource::<T>::insert(source, &validators);
+            ExternalIndexBySource::<T>::insert(source, external_index);
+            ExternalValidatorStakes::<T>::remove(source);
+
+            Self::deposit_event(Event::<T>::ExternalSourceValidatorsSet {
+                source,
+                validators: validators.into_inner(),
+                external_index,
+            });
+            Ok(())
+        }
+
+        /// Set the validators of `source` together with their stake. Validators are sorted by
+        /// stake, highest first, so only the top ones are kept if there are more validators
+        /// than seats. Ties keep the order in which they were received.
+        pub fn set_external_validators_with_stake(
+            source: ExternalSourceId,
+            mut validators: Vec<(T::ValidatorId, u128)>,
+            external_index: u64,
+        ) -> DispatchResult {
+            let seats = if source == DEFAULT_EXTERNAL_SOURCE {
+                T::MaxExternalValidators::get()
+            } else {
+                ExternalSources::<T>::get(source)
+                    .ok_or(Error::<T>::UnknownExternalSource)?
+                    .max_validators
+            };
+
+            validators.sort_by(|(_, a), (_, b)| b.cmp(a));
+            let mut seen = BTreeSet::new();
+            let stakes: BoundedVec<_, T::MaxExternalValidators> = BoundedVec::truncate_from(
+                validators
+                    .into_iter()
+                    .filter(|(v, _)| seen.insert(v.clone()))
+                    .take(seats as usize)
+                    .collect(),
+            );
+
+            Self::set_external_validators_for_source(
+                source,
+                stakes.iter().map(|(v, _)| v.clone()).collect(),
+                external_index,
+            )?;
+            ExternalValidatorStakes::<T>::insert(source, stakes);
+
+            Ok(())
+        }
+
+        /// Returns the additional external source that uses `channel_id`, if any.
+        pub fn source_for_channel(channel_id: &[u8; 32]) -> Option<ExternalSourceId> {
+            ExternalSources::<T>::iter()
+                .find(|(_, info)| &info.channel_id == channel_id)
+                .map(|(source, _)| source)
+        }
+
+        /// Returns the external validators of all the sources, each paired with the source that
+        /// supplied it. Sources are merged following `ExternalMergePolicy`, the default source
+        /// being the first one and the rest sorted by id. Validators supplied by more than one
+        /// source are assigned to the first one, and the total is limited to
+        /// `MaxExternalValidators`.
+        pub fn external_validators_with_source() -> Vec<(T::ValidatorId, ExternalSourceId)> {
+            let mut sources: Vec<ExternalSourceId> = ExternalSources::<T>::iter_keys().collect();
+            sources.sort();
+
+            let mut lists: Vec<Vec<(T::ValidatorId, ExternalSourceId)>> =
+                Vec::with_capacity(sources.len().saturating_add(1));
+            lists.push(
+                ExternalValidators::<T>::get()
+                    .into_iter()
+                    .map(|v| (v, DEFAULT_EXTERNAL_SOURCE))
+                    .collect(),
+            );
+            for source in sources {
+                lists.push(
+                    ExternalValidatorsBySource::<T>::get(source)
+                        .into_iter()
+                        .map(|v| (v, source))
+                        .collect(),
+                );
+            }
+
+            let merged = ExternalMergePolicy::<T>::get().merge(lists);
+
+            let mut seen = BTreeSet::new();
+            merged
+                .into_iter()
+                .filter(|(v, _)| seen.insert(v.clone()))
+                .take(T::MaxExternalValidators::get() as usize)
+                .collect()
+        }
+
         /// Helper to set a new `ForceEra` mode.
-        pub(crate) fn set_force_era(mode: Forcing) {
+        pub fn set_force_era(mode: Forcing) {
             log::info!("Setting force era mode {:?}.", mode);
             ForceEra::<T>::put(mode);
             Self::deposit_event(Event::<T>::ForceEra { mode });
@@ -419,7 +749,11 @@ pub mod pallet {
             let mut validators: Vec<_> = WhitelistedValidators::<T>::get().into();
 
             if !SkipExternalValidators::<T>::get() {
-                validators.extend(ExternalValidators::<T>::get())
+                validators.extend(
+                    Self::external_validators_with_source()
+                        .into_iter()
+                        .map(|(v, _)| v),
+                )
             }
 
             remove_duplicates(validators)
@@ -564,6 +898,9 @@ pub mod pallet {
             WhitelistedValidatorsActiveEraPending::<T>::put(WhitelistedValidators::<T>::get());
             // Save the external index for when the era truly changes (start_era)
             PendingExternalIndex::<T>::put(ExternalIndex::<T>::get());
+            // Save the source of each external validator so it can be rewarded and slashed
+            // through it
+            Self::note_external_sources(new_planned_era);
 
             // Returns new validators
             Self::validators()
@@ -578,9 +915,57 @@ pub mod pallet {
             Some(Self::trigger_new_era(start_session_index))
         }
 
+        /// Store the source of each external validator and the external index and channel of each
+        /// source for `era_index`.
+        pub(crate) fn note_external_sources(era_index: EraIndex) {
+            if SkipExternalValidators::<T>::get() {
+                return;
+            }
+
+            ErasExternalIndices::<T>::insert(
+                era_index,
+                DEFAULT_EXTERNAL_SOURCE,
+                ExternalIndex::<T>::get(),
+            );
+            for (source, external_index) in ExternalIndexBySource::<T>::iter() {
+                ErasExternalIndices::<T>::insert(era_index, source, external_index);
+            }
+            for (source, info) in ExternalSources::<T>::iter() {
+                ErasSourceChannel::<T>::insert(era_index, source, info.channel_id);
+            }
+            for (validator, source) in Self::external_validators_with_source() {
+                ErasValidatorSource::<T>::insert(era_index, validator, source);
+            }
+            for (source, stakes) in ExternalValidatorStakes::<T>::iter() {
+                for (validator, stake) in stakes {
+                    // Only the source that supplied the validator counts, in case of duplicates
+                    if ErasValidatorSource::<T>::get(era_index, &validator) == Some(source) {
+                        ErasValidatorStake::<T>::insert(era_index, validator, stake);
+                    }
+                }
+            }
+        }
+
         /// Clear all era information for given era.
         pub(crate) fn clear_era_information(era_index: EraIndex) {
             ErasStartSessionIndex::<T>::remove(era_index);
+            let _ = ErasValidatorSource::<T>::clear_prefix(
+                era_index,
+                T::MaxExternalValidators::get(),
+                None,
+            );
+            let _ = ErasValidatorStake::<T>::clear_prefix(
+                era_index,
+                T::MaxExternalValidators::get(),
+                None,
+            );
+            let _ = ErasExternalIndices::<T>::clear_prefix(
+                era_index,
+                T::MaxExternalSources::get().saturating_add(1),
+                None,
+            );
+            let _ =
+                ErasSourceChannel::<T>::clear_prefix(era_index, T::MaxExternalSources::get(), None);
         }
     }
 
@@ -684,6 +1069,26 @@ impl<T: Config> ValidatorProvider<T::ValidatorId> for Pallet<T> {
     }
 }
 
+impl<T: Config> ExternalSourcesProvider<T::ValidatorId> for Pallet<T> {
+    fn source_of(era_index: EraIndex, validator: &T::ValidatorId) -> Option<ExternalSourceId> {
+        ErasValidatorSource::<T>::get(era_index, validator)
+    }
+
+    fn external_index_of(era_index: EraIndex, source: ExternalSourceId) -> Option<u64> {
+        ErasExternalIndices::<T>::get(era_index, source)
+    }
+
+    fn channel_of(era_index: EraIndex, source: ExternalSourceId) -> Option<[u8; 32]> {
+        ErasSourceChannel::<T>::get(era_index, source)
+    }
+}
+
+impl<T: Config> ExternalStakeProvider<T::ValidatorId> for Pallet<T> {
+    fn stake_of(era_index: EraIndex, validator: &T::ValidatorId) -> Option<u128> {
+        ErasValidatorStake::<T>::get(era_index, validator)
+    }
+}
+
 impl<T: Config> InvulnerablesProvider<T::ValidatorId> for Pallet<T> {
     fn invulnerables() -> Vec<T::ValidatorId> {
         Self::whitelisted_validators()
@@ -715,3 +1120,66 @@ pub enum Forcing {
     /// Force a new era at the end of all sessions indefinitely.
     ForceAlways,
 }
+
+/// Configuration of an additional external source of validators.
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct ExternalSourceInfo {
+    /// Channel through which the source sends its validators and receives its rewards.
+    pub channel_id: [u8; 32],
+    /// Maximum number of validators taken from this source.
+    pub max_validators: u32,
+}
+
+/// How validators from the different external sources are merged into a single list.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum MergePolicy {
+    /// All the validators of a source go before the ones of the next source.
+    #[default]
+    Priority,
+    /// Take one validator of each source in turns.
+    RoundRobin,
+}
+
+impl MergePolicy {
+    /// Merge `lists`, which are already sorted by source priority.
+    pub fn merge<V>(&self, lists: Vec<Vec<V>>) -> Vec<V> {
+        match self {
+            MergePolicy::Priority => lists.into_iter().flatten().collect(),
+            MergePolicy::RoundRobin => {
+                let total = lists.iter().map(Vec::len).sum();
+                let mut result = Vec::with_capacity(total);
+                let mut iters: Vec<_> = lists.into_iter().map(Vec::into_iter).collect();
+                while result.len() < total {
+                    for iter in iters.iter_mut() {
+                        if let Some(item) = iter.next() {
+                            result.push(item);
+                        }
+                    }
+                }
+                result
+            }
+        }
+    }
+}
@@ -132,6 +132,7 @@ impl Config for Test {
     type HistoryDepth = ConstU32<84>;
     type MaxWhitelistedValidators = ConstU32<20>;
     type MaxExternalValidators = ConstU32<20>;
+    type MaxExternalSources = ConstU32<3>;
     type ValidatorId = <Self as frame_system::Config>::AccountId;
     type ValidatorIdOf = ConvertInto;
     type ValidatorRegistration = IsRegistered;
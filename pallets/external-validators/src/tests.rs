@@ -19,11 +19,13 @@ use {
             last_event, new_test_ext, run_to_block, run_to_session, ExternalValidators, HookCall,
             Mock, RootAccount, RuntimeEvent, RuntimeOrigin, Session, System, Test,
         },
-        Error,
+        Error, ExternalSourceInfo, MergePolicy, DEFAULT_EXTERNAL_SOURCE,
     },
     frame_support::{assert_noop, assert_ok},
     sp_runtime::traits::BadOrigin,
-    tp_traits::{ExternalIndexProvider, ValidatorProvider},
+    tp_traits::{
+        ExternalIndexProvider, ExternalSourcesProvider, ExternalStakeProvider, ValidatorProvider,
+    },
 };
 
 #[test]
@@ -387,3 +389,331 @@ fn era_hooks_with_external_index() {
         assert_eq!(Mock::mock().called_hooks, expected_calls);
     });
 }
+
+fn source_info(channel: u8, max_validators: u32) -> ExternalSourceInfo {
+    ExternalSourceInfo {
+        channel_id: [channel; 32],
+        max_validators,
+    }
+}
+
+#[test]
+fn set_external_source_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 5)
+        ));
+        System::assert_last_event(RuntimeEvent::ExternalValidators(
+            crate::Event::ExternalSourceSet {
+                source: 1,
+                info: source_info(1, 5),
+            },
+        ));
+        assert_eq!(ExternalValidators::source_for_channel(&[1; 32]), Some(1));
+
+        // updating an existing source can keep its channel
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 10)
+        ));
+
+        // cannot add with non-root
+        assert_noop!(
+            ExternalValidators::set_external_source(RuntimeOrigin::signed(1), 2, source_info(2, 5)),
+            BadOrigin
+        );
+        // the default source is implicit
+        assert_noop!(
+            ExternalValidators::set_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                DEFAULT_EXTERNAL_SOURCE,
+                source_info(2, 5)
+            ),
+            Error::<Test>::DefaultExternalSource
+        );
+        // channels cannot be shared
+        assert_noop!(
+            ExternalValidators::set_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                2,
+                source_info(1, 5)
+            ),
+            Error::<Test>::ChannelAlreadyInUse
+        );
+        // the primary channel belongs to the default source
+        assert_noop!(
+            ExternalValidators::set_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                2,
+                ExternalSourceInfo {
+                    channel_id: snowbridge_core::PRIMARY_GOVERNANCE_CHANNEL.into(),
+                    max_validators: 5,
+                }
+            ),
+            Error::<Test>::ChannelAlreadyInUse
+        );
+    });
+}
+
+#[test]
+fn external_sources_limit_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        // MaxExternalSources is 3
+        for source in 1..=3 {
+            assert_ok!(ExternalValidators::set_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                source,
+                source_info(source as u8, 5)
+            ));
+        }
+        assert_noop!(
+            ExternalValidators::set_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                4,
+                source_info(4, 5)
+            ),
+            Error::<Test>::TooManyExternalSources
+        );
+    });
+}
+
+#[test]
+fn remove_external_source_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 5)
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            1,
+            vec![60, 61],
+            7
+        ));
+        assert_eq!(ExternalValidators::validators(), vec![1, 2, 60, 61]);
+
+        assert_ok!(ExternalValidators::remove_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1
+        ));
+        System::assert_last_event(RuntimeEvent::ExternalValidators(
+            crate::Event::ExternalSourceRemoved { source: 1 },
+        ));
+        assert_eq!(ExternalValidators::validators(), vec![1, 2]);
+
+        assert_noop!(
+            ExternalValidators::remove_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                1
+            ),
+            Error::<Test>::UnknownExternalSource
+        );
+    });
+}
+
+#[test]
+fn set_external_validators_for_unknown_source_fails() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_noop!(
+            ExternalValidators::set_external_validators_for_source(1, vec![60, 61], 7),
+            Error::<Test>::UnknownExternalSource
+        );
+    });
+}
+
+#[test]
+fn external_source_seats_are_limited() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 2)
+        ));
+
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            1,
+            vec![60, 61, 62],
+            7
+        ));
+        System::assert_last_event(RuntimeEvent::ExternalValidators(
+            crate::Event::ExternalSourceValidatorsSet {
+                source: 1,
+                validators: vec![60, 61],
+                external_index: 7,
+            },
+        ));
+    });
+}
+
+#[test]
+fn external_sources_are_merged_by_priority() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        for source in [2, 1] {
+            assert_ok!(ExternalValidators::set_external_source(
+                RuntimeOrigin::signed(RootAccount::get()),
+                source,
+                source_info(source as u8, 5)
+            ));
+        }
+        assert_ok!(ExternalValidators::set_external_validators_inner(
+            vec![50, 51],
+            1
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            2,
+            vec![70, 51],
+            3
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            1,
+            vec![60, 61],
+            2
+        ));
+
+        // default source first, then sorted by id. Duplicates keep the first source.
+        assert_eq!(
+            ExternalValidators::external_validators_with_source(),
+            vec![(50, 0), (51, 0), (60, 1), (61, 1), (70, 2)]
+        );
+    });
+}
+
+#[test]
+fn external_sources_are_merged_round_robin() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ExternalValidators::set_external_merge_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            MergePolicy::RoundRobin
+        ));
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 5)
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_inner(
+            vec![50, 51, 52],
+            1
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            1,
+            vec![60],
+            2
+        ));
+
+        assert_eq!(
+            ExternalValidators::external_validators_with_source(),
+            vec![(50, 0), (60, 1), (51, 0), (52, 0)]
+        );
+    });
+}
+
+#[test]
+fn external_sources_are_tracked_per_era() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 5)
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_inner(
+            vec![50],
+            1000
+        ));
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            1,
+            vec![51],
+            2000
+        ));
+
+        run_to_session(6);
+
+        assert_eq!(ExternalValidators::source_of(1, &50), Some(0));
+        assert_eq!(ExternalValidators::source_of(1, &51), Some(1));
+        // whitelisted validators do not come from any source
+        assert_eq!(ExternalValidators::source_of(1, &1), None);
+        assert_eq!(ExternalValidators::external_index_of(1, 0), Some(1000));
+        assert_eq!(ExternalValidators::external_index_of(1, 1), Some(2000));
+        assert_eq!(ExternalValidators::channel_of(1, 1), Some([1; 32]));
+        assert_eq!(ExternalValidators::channel_of(1, 0), None);
+
+        // The channel of the era is kept after the source is removed
+        assert_ok!(ExternalValidators::remove_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1
+        ));
+        assert_eq!(ExternalValidators::channel_of(1, 1), Some([1; 32]));
+    });
+}
+
+#[test]
+fn validators_with_stake_are_selected_by_stake() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(ExternalValidators::set_external_source(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            source_info(1, 2)
+        ));
+
+        assert_ok!(ExternalValidators::set_external_validators_with_stake(
+            1,
+            vec![(60, 10), (61, 30), (62, 20), (61, 5)],
+            7
+        ));
+        assert_eq!(
+            crate::ExternalValidatorsBySource::<Test>::get(1).into_inner(),
+            vec![61, 62]
+        );
+        assert_eq!(
+            crate::ExternalValidatorStakes::<Test>::get(1).into_inner(),
+            vec![(61, 30), (62, 20)]
+        );
+
+        // Validators without stake clear the previous stakes
+        assert_ok!(ExternalValidators::set_external_validators_for_source(
+            1,
+            vec![60],
+            8
+        ));
+        assert!(crate::ExternalValidatorStakes::<Test>::get(1).is_empty());
+
+        assert_noop!(
+            ExternalValidators::set_external_validators_with_stake(2, vec![(60, 10)], 7),
+            Error::<Test>::UnknownExternalSource
+        );
+    });
+}
+
+#[test]
+fn validator_stakes_are_tracked_per_era() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ExternalValidators::set_external_validators_with_stake(
+            DEFAULT_EXTERNAL_SOURCE,
+            vec![(50, 100), (51, 200)],
+            1000
+        ));
+
+        run_to_session(6);
+
+        assert_eq!(
+            crate::ExternalValidators::<Test>::get().into_inner(),
+            vec![51, 50]
+        );
+        assert_eq!(ExternalValidators::stake_of(1, &50), Some(100));
+        assert_eq!(ExternalValidators::stake_of(1, &51), Some(200));
+        // whitelisted validators have no stake
+        assert_eq!(ExternalValidators::stake_of(1, &1), None);
+    });
+}
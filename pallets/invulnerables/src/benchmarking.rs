@@ -31,7 +31,7 @@ use {
     pallet_session::{self as session, SessionManager},
     rand::{RngCore, SeedableRng},
     sp_runtime::{codec, traits::AtLeast32BitUnsigned},
-    tp_traits::DistributeRewards,
+    tp_traits::{DistributeRewards, GetSessionIndex},
 };
 const SEED: u32 = 0;
 
@@ -101,11 +101,6 @@ fn invulnerables<
         .collect()
 }
 
-pub type BalanceOf<T> =
-    <<T as crate::Config>::Currency as frame_support::traits::fungible::Inspect<
-        <T as frame_system::Config>::AccountId,
-    >>::Balance;
-
 pub(crate) fn currency_issue<T: Config + frame_system::Config>(
     amount: BalanceOf<T>,
 ) -> crate::CreditOf<T, T::Currency> {
@@ -240,6 +235,161 @@ mod benchmarks {
 
         Ok(())
     }
+
+    #[benchmark]
+    fn set_reward_policy() -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let (who, _collator_id, _keys) = invulnerable::<T>(1);
+        let policy = RewardPolicy {
+            treasury: sp_runtime::Perbill::from_percent(30),
+            burn: sp_runtime::Perbill::from_percent(30),
+            staking: sp_runtime::Perbill::from_percent(30),
+        };
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who.clone(), Some(policy));
+
+        assert_last_event::<T>(
+            Event::RewardPolicySet {
+                account_id: who,
+                policy: Some(policy),
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn claim_pending_rewards() -> Result<(), BenchmarkError> {
+        // Worst case: account does not exist yet
+        let who: T::AccountId = account("pending", 0, SEED);
+        let amount =
+            <T::Currency as frame_support::traits::fungible::Inspect<_>>::minimum_balance()
+                * 10u32.into();
+        PendingRewards::<T>::insert(&who, amount);
+        // The pending rewards are held by the pending rewards account, kept above its minimum
+        let _ = <T::Currency as Balanced<T::AccountId>>::resolve(
+            &T::PendingRewardsAccount::get(),
+            currency_issue::<T>(amount * 2u32.into()),
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(who.clone()));
+
+        assert_last_event::<T>(
+            Event::PendingRewardsClaimed {
+                account_id: who,
+                amount,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_invulnerable_expiry(
+        b: Linear<{ 1 }, { T::MaxInvulnerables::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let origin =
+            T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let mut invulnerables = invulnerables::<T>(b);
+        invulnerables.sort();
+
+        let (account_ids, collator_ids): (Vec<T::AccountId>, Vec<T::CollatorId>) =
+            invulnerables.into_iter().unzip();
+
+        let invulnerables: frame_support::BoundedVec<_, T::MaxInvulnerables> =
+            frame_support::BoundedVec::try_from(collator_ids).unwrap();
+        <Invulnerables<T>>::put(invulnerables);
+
+        // worst case: last element of the list
+        let who = account_ids.last().unwrap().clone();
+        let expires_at = T::CurrentSessionIndex::session_index().saturating_add(10);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who.clone(), Some(expires_at));
+
+        assert_last_event::<T>(
+            Event::InvulnerableExpirySet {
+                account_id: who,
+                expires_at: Some(expires_at),
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn swap_invulnerable(
+        b: Linear<{ 1 }, { T::MaxInvulnerables::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let mut invulnerables = invulnerables::<T>(b);
+        invulnerables.sort();
+
+        let (account_ids, collator_ids): (Vec<T::AccountId>, Vec<T::CollatorId>) =
+            invulnerables.into_iter().unzip();
+
+        let invulnerables: frame_support::BoundedVec<_, T::MaxInvulnerables> =
+            frame_support::BoundedVec::try_from(collator_ids.clone()).unwrap();
+        <Invulnerables<T>>::put(invulnerables);
+
+        // worst case: last element of the list, with expiry, reward policy and pending rewards to
+        // move
+        let old = account_ids.last().unwrap().clone();
+        let old_collator_id = collator_ids.last().unwrap().clone();
+        InvulnerableExpiry::<T>::insert(&old_collator_id, 10);
+        RewardPolicies::<T>::insert(&old_collator_id, RewardPolicy::default());
+        PendingRewards::<T>::insert(&old, BalanceOf::<T>::from(1u32));
+
+        let (new, _collator_id, keys) = invulnerable::<T>(b + 1);
+        <session::Pallet<T>>::set_keys(RawOrigin::Signed(new.clone()).into(), keys, Vec::new())
+            .unwrap();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(old.clone()), new.clone());
+
+        assert_last_event::<T>(
+            Event::InvulnerableSwapped {
+                old_account_id: old,
+                new_account_id: new,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn expire_invulnerables(
+        r: Linear<0, { T::MaxInvulnerables::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let mut invulnerables = invulnerables::<T>(T::MaxInvulnerables::get());
+        invulnerables.sort();
+
+        let (_account_ids, collator_ids): (Vec<T::AccountId>, Vec<T::CollatorId>) =
+            invulnerables.into_iter().unzip();
+
+        let invulnerables: frame_support::BoundedVec<_, T::MaxInvulnerables> =
+            frame_support::BoundedVec::try_from(collator_ids.clone()).unwrap();
+        <Invulnerables<T>>::put(invulnerables);
+
+        // worst case: all of them expire
+        for collator_id in collator_ids.iter().take(r as usize) {
+            InvulnerableExpiry::<T>::insert(collator_id, 1);
+        }
+
+        #[block]
+        {
+            <InvulnerablesPallet<T>>::initializer_on_new_session(&1);
+        }
+
+        assert_eq!(
+            Invulnerables::<T>::get().len(),
+            (T::MaxInvulnerables::get() - r) as usize
+        );
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         InvulnerablesPallet,
         crate::mock::new_test_ext(),
@@ -22,6 +22,25 @@
 //!
 //! - Collator: A parachain block producer.
 //! - Invulnerable: An account appointed by governance and guaranteed to be in the collator set.
+//!
+//! ## Rewards
+//!
+//! By default invulnerables receive their whole reward directly. Governance can set a
+//! [`RewardPolicy`] per invulnerable to send part of it to the treasury, burn it, or route it
+//! through the staking pools. Direct rewards that cannot be deposited (for example because they
+//! are below the existential deposit) are kept in the `PendingRewardsAccount` and tracked in
+//! [`PendingRewards`] until they can be paid. The staking share of an invulnerable that cannot be
+//! rewarded through the staking pools is paid directly.
+//!
+//! ## Expiry
+//!
+//! Invulnerables can optionally be given an expiry session with `set_invulnerable_expiry`. They
+//! are removed automatically at the first session change that plans a session whose index is
+//! equal or greater than their expiry, before the collators of that session are selected. Runtimes
+//! that do not use this pallet as their `SessionManager` must call
+//! [`Pallet::initializer_on_new_session`] with the index of the planned session (the current
+//! session index plus one, the same index `SessionManager::new_session` receives) for that to
+//! happen.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -29,7 +48,17 @@ extern crate alloc;
 pub use pallet::*;
 use {
     core::marker::PhantomData,
-    sp_runtime::{traits::Convert, TokenError},
+    frame_support::traits::{
+        fungible::{Balanced, Credit, Inspect},
+        tokens::{Fortitude, Precision, Preservation},
+        Imbalance,
+    },
+    parity_scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen},
+    scale_info::TypeInfo,
+    sp_runtime::{
+        traits::{Convert, Saturating, Zero},
+        Perbill, RuntimeDebug,
+    },
 };
 
 #[cfg(test)]
@@ -46,20 +75,19 @@ pub mod weights;
 pub mod pallet {
     pub use crate::weights::WeightInfo;
 
-    #[cfg(feature = "runtime-benchmarks")]
-    use frame_support::traits::Currency;
-
     use {
+        super::{BalanceOf, RewardPolicy},
         alloc::vec::Vec,
         frame_support::{
             pallet_prelude::*,
-            traits::{EnsureOrigin, ValidatorRegistration},
+            traits::{fungible, tokens::Preservation, EnsureOrigin, ValidatorRegistration},
             BoundedVec, DefaultNoBound,
         },
         frame_system::pallet_prelude::*,
         pallet_session::SessionManager,
-        sp_runtime::traits::Convert,
+        sp_runtime::traits::{Convert, Zero},
         sp_staking::SessionIndex,
+        tp_traits::GetSessionIndex,
     };
 
     /// The current storage version.
@@ -86,12 +114,21 @@ pub mod pallet {
         /// Validate a user is registered
         type CollatorRegistration: ValidatorRegistration<Self::CollatorId>;
 
+        /// Currency in which invulnerable rewards are paid.
+        type Currency: fungible::Mutate<Self::AccountId> + fungible::Balanced<Self::AccountId>;
+
+        /// Account receiving the treasury share of invulnerable rewards.
+        type RewardsTreasury: Get<Self::AccountId>;
+
+        /// Account holding the direct rewards that could not be deposited yet. It must be kept
+        /// above the existential deposit.
+        type PendingRewardsAccount: Get<Self::AccountId>;
+
+        /// Get the current session index, used to validate expiry sessions.
+        type CurrentSessionIndex: GetSessionIndex<SessionIndex>;
+
         /// The weight information of this pallet.
         type WeightInfo: WeightInfo;
-
-        #[cfg(feature = "runtime-benchmarks")]
-        type Currency: Currency<Self::AccountId>
-            + frame_support::traits::fungible::Balanced<Self::AccountId>;
     }
 
     #[pallet::pallet]
@@ -103,6 +140,25 @@ pub mod pallet {
     pub type Invulnerables<T: Config> =
         StorageValue<_, BoundedVec<T::CollatorId, T::MaxInvulnerables>, ValueQuery>;
 
+    /// Reward policy of each invulnerable. Invulnerables without an entry receive the whole
+    /// reward directly.
+    #[pallet::storage]
+    pub type RewardPolicies<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::CollatorId, RewardPolicy, OptionQuery>;
+
+    /// Session at which each invulnerable stops being an invulnerable. Invulnerables without an
+    /// entry never expire.
+    #[pallet::storage]
+    pub type InvulnerableExpiry<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::CollatorId, SessionIndex, OptionQuery>;
+
+    /// Direct rewards that could not be deposited to an invulnerable account, usually because
+    /// they were below the existential deposit. They are paid together with the next reward, or
+    /// can be claimed with `claim_pending_rewards`.
+    #[pallet::storage]
+    pub type PendingRewards<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
     #[pallet::genesis_config]
     #[derive(DefaultNoBound)]
     pub struct GenesisConfig<T: Config> {
@@ -136,6 +192,41 @@ pub mod pallet {
         InvulnerableAdded { account_id: T::AccountId },
         /// An Invulnerable was removed.
         InvulnerableRemoved { account_id: T::AccountId },
+        /// The expiry session of an Invulnerable was set or cleared.
+        InvulnerableExpirySet {
+            account_id: T::AccountId,
+            expires_at: Option<SessionIndex>,
+        },
+        /// An Invulnerable was removed because its term expired.
+        InvulnerableExpired { collator_id: T::CollatorId },
+        /// An Invulnerable replaced its account with a new one.
+        InvulnerableSwapped {
+            old_account_id: T::AccountId,
+            new_account_id: T::AccountId,
+        },
+        /// The reward policy of an account was set or cleared.
+        RewardPolicySet {
+            account_id: T::AccountId,
+            policy: Option<RewardPolicy>,
+        },
+        /// A direct reward could not be deposited and was added to the pending rewards.
+        RewardsAccumulated {
+            account_id: T::AccountId,
+            total_pending: BalanceOf<T>,
+        },
+        /// Pending rewards were paid to their account.
+        PendingRewardsClaimed {
+            account_id: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// A direct reward could neither be deposited nor kept as pending, because the pending
+        /// rewards account does not exist. It was sent to the rewards treasury, or burned if the
+        /// treasury could not take it either.
+        RewardsNotPaid {
+            account_id: T::AccountId,
+            amount: BalanceOf<T>,
+            sent_to_treasury: bool,
+        },
     }
 
     #[pallet::error]
@@ -150,6 +241,15 @@ pub mod pallet {
         NoKeysRegistered,
         /// Unable to derive collator id from account id
         UnableToDeriveCollatorId,
+        /// The shares of the reward policy add up to more than 100%
+        InvalidRewardPolicy,
+        /// Account has no pending rewards
+        NoPendingRewards,
+        /// Pending rewards could not be deposited, most likely because they are still below the
+        /// existential deposit
+        CannotDepositPendingRewards,
+        /// The expiry session is not in the future
+        ExpiryInThePast,
     }
 
     #[pallet::call]
@@ -204,16 +304,212 @@ pub mod pallet {
                 invulnerables.remove(pos);
                 Ok(())
             })?;
+            InvulnerableExpiry::<T>::remove(&collator_id);
 
             Self::deposit_event(Event::InvulnerableRemoved { account_id: who });
             Ok(())
         }
+
+        /// Set the reward policy of account `who`, or reset it to the default policy of paying
+        /// the whole reward directly if `policy` is `None`.
+        ///
+        /// The policy is kept if the account stops being an invulnerable, and applies again if it
+        /// is added back.
+        ///
+        /// The origin for this call must be the `UpdateOrigin`.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_reward_policy())]
+        pub fn set_reward_policy(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            policy: Option<RewardPolicy>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            let collator_id = T::CollatorIdOf::convert(who.clone())
+                .ok_or(Error::<T>::UnableToDeriveCollatorId)?;
+
+            if let Some(policy) = &policy {
+                ensure!(policy.is_valid(), Error::<T>::InvalidRewardPolicy);
+            }
+
+            RewardPolicies::<T>::set(&collator_id, policy);
+
+            Self::deposit_event(Event::RewardPolicySet {
+                account_id: who,
+                policy,
+            });
+            Ok(())
+        }
+
+        /// Deposit the pending rewards of the caller into its account.
+        ///
+        /// Fails if the pending amount is still below the existential deposit of a non-existing
+        /// account.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::claim_pending_rewards())]
+        pub fn claim_pending_rewards(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let amount = PendingRewards::<T>::get(&who);
+            ensure!(!amount.is_zero(), Error::<T>::NoPendingRewards);
+
+            <T::Currency as fungible::Mutate<_>>::transfer(
+                &T::PendingRewardsAccount::get(),
+                &who,
+                amount,
+                Preservation::Preserve,
+            )
+            .map_err(|_| Error::<T>::CannotDepositPendingRewards)?;
+            PendingRewards::<T>::remove(&who);
+
+            Self::deposit_event(Event::PendingRewardsClaimed {
+                account_id: who,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Set the session at which Invulnerable `who` is automatically removed, or remove its
+        /// expiry if `expires_at` is `None`.
+        ///
+        /// The origin for this call must be the `UpdateOrigin`.
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::set_invulnerable_expiry(T::MaxInvulnerables::get()))]
+        pub fn set_invulnerable_expiry(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            expires_at: Option<SessionIndex>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            let collator_id = T::CollatorIdOf::convert(who.clone())
+                .ok_or(Error::<T>::UnableToDeriveCollatorId)?;
+            ensure!(
+                Invulnerables::<T>::get().contains(&collator_id),
+                Error::<T>::NotInvulnerable
+            );
+            if let Some(expires_at) = expires_at {
+                ensure!(
+                    expires_at > T::CurrentSessionIndex::session_index(),
+                    Error::<T>::ExpiryInThePast
+                );
+            }
+
+            InvulnerableExpiry::<T>::set(&collator_id, expires_at);
+
+            Self::deposit_event(Event::InvulnerableExpirySet {
+                account_id: who,
+                expires_at,
+            });
+            Ok(())
+        }
+
+        /// Replace the account of the calling Invulnerable with `new`, keeping its position,
+        /// expiry, reward policy and pending rewards. `new` must have session keys registered.
+        ///
+        /// The new account is used from the next collator selection on.
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::swap_invulnerable(T::MaxInvulnerables::get()))]
+        pub fn swap_invulnerable(origin: OriginFor<T>, new: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let old_collator_id = T::CollatorIdOf::convert(who.clone())
+                .ok_or(Error::<T>::UnableToDeriveCollatorId)?;
+            let new_collator_id = T::CollatorIdOf::convert(new.clone())
+                .filter(T::CollatorRegistration::is_registered)
+                .ok_or(Error::<T>::NoKeysRegistered)?;
+
+            <Invulnerables<T>>::try_mutate(|invulnerables| -> DispatchResult {
+                if invulnerables.contains(&new_collator_id) {
+                    Err(Error::<T>::AlreadyInvulnerable)?;
+                }
+                let pos = invulnerables
+                    .iter()
+                    .position(|x| x == &old_collator_id)
+                    .ok_or(Error::<T>::NotInvulnerable)?;
+                invulnerables[pos] = new_collator_id.clone();
+                Ok(())
+            })?;
+
+            if let Some(expires_at) = InvulnerableExpiry::<T>::take(&old_collator_id) {
+                InvulnerableExpiry::<T>::insert(&new_collator_id, expires_at);
+            }
+            if let Some(policy) = RewardPolicies::<T>::take(&old_collator_id) {
+                RewardPolicies::<T>::insert(&new_collator_id, policy);
+            }
+            let pending = PendingRewards::<T>::take(&who);
+            if !pending.is_zero() {
+                PendingRewards::<T>::mutate(&new, |new_pending| {
+                    *new_pending = new_pending.saturating_add(pending)
+                });
+            }
+
+            Self::deposit_event(Event::InvulnerableSwapped {
+                old_account_id: who,
+                new_account_id: new,
+            });
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
         pub fn invulnerables() -> BoundedVec<T::CollatorId, T::MaxInvulnerables> {
             Invulnerables::<T>::get()
         }
+
+        pub fn reward_policy(collator_id: &T::CollatorId) -> RewardPolicy {
+            RewardPolicies::<T>::get(collator_id).unwrap_or_default()
+        }
+
+        /// Remove the Invulnerables whose expiry is at or before `session_index`. Must be called at
+        /// every session change, before the collators of the next session are selected.
+        ///
+        /// `session_index` is the index of the session being planned, the one whose collators are
+        /// about to be selected. This is the index `SessionManager::new_session` receives, one
+        /// more than the index of the session that just started.
+        pub fn initializer_on_new_session(session_index: &SessionIndex) {
+            let expiries: Vec<(T::CollatorId, SessionIndex)> =
+                InvulnerableExpiry::<T>::iter().collect();
+            let checked = expiries.len() as u32;
+            let expired: Vec<T::CollatorId> = expiries
+                .into_iter()
+                .filter(|(_, expires_at)| expires_at <= session_index)
+                .map(|(collator_id, _)| collator_id)
+                .collect();
+
+            if !expired.is_empty() {
+                Invulnerables::<T>::mutate(|invulnerables| {
+                    invulnerables.retain(|x| !expired.contains(x))
+                });
+                for collator_id in expired {
+                    InvulnerableExpiry::<T>::remove(&collator_id);
+                    log::info!(
+                        "invulnerable {:?} expired at session {}",
+                        collator_id,
+                        session_index
+                    );
+                    Self::deposit_event(Event::InvulnerableExpired { collator_id });
+                }
+            }
+
+            frame_system::Pallet::<T>::register_extra_weight_unchecked(
+                T::WeightInfo::expire_invulnerables(checked),
+                DispatchClass::Mandatory,
+            );
+        }
+
+        /// Add `amount` to the pending rewards of `who`.
+        pub(crate) fn accumulate_pending_rewards(who: &T::AccountId, amount: BalanceOf<T>) {
+            let total_pending = PendingRewards::<T>::mutate(who, |pending| {
+                *pending = pending.saturating_add(amount);
+                *pending
+            });
+            Self::deposit_event(Event::RewardsAccumulated {
+                account_id: who.clone(),
+                total_pending,
+            });
+        }
     }
 
     /// Play the role of the session manager.
@@ -225,6 +521,8 @@ pub mod pallet {
                 <frame_system::Pallet<T>>::block_number(),
             );
 
+            Self::initializer_on_new_session(&index);
+
             let invulnerables = Self::invulnerables().to_vec();
             frame_system::Pallet::<T>::register_extra_weight_unchecked(
                 T::WeightInfo::new_session(invulnerables.len() as u32),
@@ -241,17 +539,53 @@ pub mod pallet {
     }
 }
 
-/// If the rewarded account is an Invulnerable, distribute the entire reward
-/// amount to them. Otherwise use the `Fallback` distribution.
+/// Portions of an invulnerable reward sent to destinations other than the invulnerable account.
+/// Whatever is not assigned to `treasury`, `burn` or `staking` is paid directly to the
+/// invulnerable, so the default policy pays the whole reward directly.
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct RewardPolicy {
+    /// Portion sent to the treasury account.
+    pub treasury: Perbill,
+    /// Portion burned.
+    pub burn: Perbill,
+    /// Portion routed through the staking pools of the invulnerable, shared with its delegators.
+    pub staking: Perbill,
+}
+
+impl RewardPolicy {
+    /// A policy is valid if its portions add up to at most 100%.
+    pub fn is_valid(&self) -> bool {
+        [self.treasury, self.burn, self.staking]
+            .iter()
+            .try_fold(0u32, |acc, share| acc.checked_add(share.deconstruct()))
+            .is_some_and(|total| total <= Perbill::one().deconstruct())
+    }
+}
+
+/// If the rewarded account is an Invulnerable, split the reward according to its
+/// [`RewardPolicy`]. Otherwise use the `Fallback` distribution.
 pub struct InvulnerableRewardDistribution<Runtime, Currency, Fallback>(
     PhantomData<(Runtime, Currency, Fallback)>,
 );
 
 use {frame_support::pallet_prelude::Weight, sp_runtime::traits::Get};
 
-type CreditOf<Runtime, Currency> =
-    frame_support::traits::fungible::Credit<<Runtime as frame_system::Config>::AccountId, Currency>;
+type CreditOf<Runtime, Currency> = Credit<<Runtime as frame_system::Config>::AccountId, Currency>;
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
 
 impl<Runtime, Currency, Fallback>
     tp_traits::DistributeRewards<AccountIdOf<Runtime>, CreditOf<Runtime, Currency>>
@@ -259,7 +593,7 @@ impl<Runtime, Currency, Fallback>
 where
     Runtime: frame_system::Config + Config,
     Fallback: tp_traits::DistributeRewards<AccountIdOf<Runtime>, CreditOf<Runtime, Currency>>,
-    Currency: frame_support::traits::fungible::Balanced<AccountIdOf<Runtime>>,
+    Currency: Balanced<AccountIdOf<Runtime>, Balance = BalanceOf<Runtime>>,
 {
     fn distribute_rewards(
         rewarded: AccountIdOf<Runtime>,
@@ -276,15 +610,109 @@ where
             if let Some(weight) = post_info.actual_weight {
                 total_weight.saturating_accrue(weight);
             }
+            return Ok(Some(total_weight).into());
+        }
+
+        // Reward invulnerable
+        let policy = Pallet::<Runtime>::reward_policy(&collator_id);
+        let total = amount.peek();
+        let (treasury_credit, rest) = amount.split(policy.treasury.mul_floor(total));
+        let (burn_credit, rest) = rest.split(policy.burn.mul_floor(total));
+        let (staking_credit, mut direct_credit) = if Fallback::can_distribute_rewards(&rewarded) {
+            rest.split(policy.staking.mul_floor(total))
         } else {
-            // Reward invulnerable
-            // TODO: if invulnerable deletes its account, and reward is smaller than existential deposit,
-            // this could fail.
-            Currency::resolve(&rewarded, amount).map_err(|_| TokenError::NotExpendable)?;
-            total_weight.saturating_accrue(Runtime::WeightInfo::reward_invulnerable(
-                Runtime::MaxInvulnerables::get(),
-            ))
+            // Not rewardable through the staking pools, pay the staking share directly.
+            (CreditOf::<Runtime, Currency>::zero(), rest)
+        };
+
+        // Dropping a credit decreases the total issuance.
+        drop(burn_credit);
+
+        if !treasury_credit.peek().is_zero() {
+            if let Err(credit) =
+                Currency::resolve(&Runtime::RewardsTreasury::get(), treasury_credit)
+            {
+                // The treasury could not take it, pay it to the invulnerable instead.
+                direct_credit.subsume(credit);
+            }
         }
+
+        if !staking_credit.peek().is_zero() {
+            match Fallback::distribute_rewards(rewarded.clone(), staking_credit) {
+                Ok(post_info) => {
+                    if let Some(weight) = post_info.actual_weight {
+                        total_weight.saturating_accrue(weight);
+                    }
+                }
+                Err(e) => {
+                    // The credit has been consumed, keep paying the other shares.
+                    log::error!(
+                        "Failed to distribute the staking share of invulnerable rewards: {:?}",
+                        e
+                    );
+                }
+            }
+        }
+
+        // Pay previously accumulated rewards together with this one. They are only removed from
+        // `PendingRewards` once paid.
+        let holder = Runtime::PendingRewardsAccount::get();
+        let pending = PendingRewards::<Runtime>::get(&rewarded);
+        let mut withdrawn_pending = BalanceOf::<Runtime>::zero();
+        if !pending.is_zero() {
+            if let Ok(credit) = Currency::withdraw(
+                &holder,
+                pending,
+                Precision::Exact,
+                Preservation::Preserve,
+                Fortitude::Polite,
+            ) {
+                withdrawn_pending = pending;
+                direct_credit.subsume(credit);
+            }
+        }
+        if !direct_credit.peek().is_zero() {
+            match Currency::resolve(&rewarded, direct_credit) {
+                Ok(()) => {
+                    if !withdrawn_pending.is_zero() {
+                        PendingRewards::<Runtime>::remove(&rewarded);
+                    }
+                }
+                Err(credit) => {
+                    // Most likely below the existential deposit of a non-existing account. Keep
+                    // the credit in the holding account until it can be paid, the withdrawn
+                    // pending rewards are still accounted in `PendingRewards`.
+                    let owed = credit.peek().saturating_sub(withdrawn_pending);
+                    match Currency::resolve(&holder, credit) {
+                        Ok(()) => {
+                            if !owed.is_zero() {
+                                Pallet::<Runtime>::accumulate_pending_rewards(&rewarded, owed);
+                            }
+                        }
+                        Err(credit) => {
+                            // Only possible if the holding account does not exist, so no pending
+                            // rewards were withdrawn from it.
+                            let amount = credit.peek();
+                            log::error!(
+                                "Pending rewards account cannot hold {:?} of invulnerable rewards",
+                                amount
+                            );
+                            let sent_to_treasury =
+                                Currency::resolve(&Runtime::RewardsTreasury::get(), credit).is_ok();
+                            Pallet::<Runtime>::deposit_event(Event::RewardsNotPaid {
+                                account_id: rewarded.clone(),
+                                amount,
+                                sent_to_treasury,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        total_weight.saturating_accrue(Runtime::WeightInfo::reward_invulnerable(
+            Runtime::MaxInvulnerables::get(),
+        ));
+
         Ok(Some(total_weight).into())
     }
 
@@ -18,7 +18,10 @@ use {
     crate as invulnerables,
     frame_support::{
         ord_parameter_types, parameter_types,
-        traits::{ConstU32, ValidatorRegistration},
+        traits::{
+            fungible::{Balanced, Credit},
+            ConstU32, ValidatorRegistration,
+        },
     },
     frame_system::{self as system, EnsureSignedBy},
     pallet_balances::AccountData,
@@ -107,6 +110,48 @@ ord_parameter_types! {
     pub const RootAccount: u64 = 777;
 }
 
+parameter_types! {
+    pub const TreasuryAccount: u64 = 999;
+    pub const StakingPoolsAccount: u64 = 888;
+    pub const PendingRewardsAccount: u64 = 666;
+    pub static NonStakingCandidates: Vec<u64> = vec![];
+}
+
+/// Mock staking reward distribution, sends all the rewards to `StakingPoolsAccount`. Accounts in
+/// `NonStakingCandidates` cannot be rewarded.
+pub struct MockStakingRewards;
+impl tp_traits::DistributeRewards<u64, Credit<u64, Balances>> for MockStakingRewards {
+    fn distribute_rewards(
+        _rewarded: u64,
+        amount: Credit<u64, Balances>,
+    ) -> frame_support::pallet_prelude::DispatchResultWithPostInfo {
+        Balances::resolve(&StakingPoolsAccount::get(), amount)
+            .map_err(|_| sp_runtime::TokenError::NotExpendable)?;
+        Ok(().into())
+    }
+
+    fn can_distribute_rewards(rewarded: &u64) -> bool {
+        !NonStakingCandidates::get().contains(rewarded)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn prepare_worst_case_for_bench(_a: &u64) {}
+}
+
+pub struct CurrentSessionIndexGetter;
+
+impl tp_traits::GetSessionIndex<u32> for CurrentSessionIndexGetter {
+    /// Returns current session index.
+    fn session_index() -> u32 {
+        Session::current_index()
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn skip_to_session(_session_index: u32) {}
+}
+
+pub type RewardDistribution = InvulnerableRewardDistribution<Test, Balances, MockStakingRewards>;
+
 pub struct IsRegistered;
 impl ValidatorRegistration<u64> for IsRegistered {
     fn is_registered(id: &u64) -> bool {
@@ -120,9 +165,11 @@ impl Config for Test {
     type CollatorId = <Self as frame_system::Config>::AccountId;
     type CollatorIdOf = ConvertInto;
     type CollatorRegistration = IsRegistered;
-    type WeightInfo = ();
-    #[cfg(feature = "runtime-benchmarks")]
     type Currency = Balances;
+    type RewardsTreasury = TreasuryAccount;
+    type PendingRewardsAccount = PendingRewardsAccount;
+    type CurrentSessionIndex = CurrentSessionIndexGetter;
+    type WeightInfo = ();
 }
 
 sp_runtime::impl_opaque_keys! {
@@ -182,7 +229,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         .unwrap();
     let invulnerables = vec![1, 2];
 
-    let balances = vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)];
+    let mut balances = vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)];
     let keys = balances
         .iter()
         .map(|&(i, _)| {
@@ -195,6 +242,8 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
             )
         })
         .collect::<Vec<_>>();
+    // The pending rewards account is kept alive by its existential deposit
+    balances.push((PendingRewardsAccount::get(), 5));
     let session = pallet_session::GenesisConfig::<Test> {
         keys,
         ..Default::default()
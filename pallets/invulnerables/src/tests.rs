@@ -16,13 +16,18 @@
 use {
     crate::{
         mock::{
-            new_test_ext, run_to_block, Invulnerables, RootAccount, RuntimeEvent, RuntimeOrigin,
-            System, Test,
+            new_test_ext, run_to_block, Balances, Invulnerables, NonStakingCandidates,
+            PendingRewardsAccount, RewardDistribution, RootAccount, RuntimeEvent, RuntimeOrigin,
+            Session, StakingPoolsAccount, System, Test, TreasuryAccount,
         },
-        Error,
+        Error, InvulnerableExpiry, PendingRewards, RewardPolicies, RewardPolicy,
     },
-    frame_support::{assert_noop, assert_ok},
-    sp_runtime::traits::BadOrigin,
+    frame_support::{
+        assert_noop, assert_ok,
+        traits::fungible::{Balanced, Inspect, Mutate},
+    },
+    sp_runtime::{traits::BadOrigin, Perbill},
+    tp_traits::DistributeRewards,
 };
 
 #[test]
@@ -144,3 +149,481 @@ fn remove_invulnerable_works() {
         );
     });
 }
+
+fn reward(who: u64, amount: u64) {
+    assert_ok!(RewardDistribution::distribute_rewards(
+        who,
+        Balances::issue(amount)
+    ));
+}
+
+#[test]
+fn invulnerable_receives_whole_reward_by_default() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let issuance = Balances::total_issuance();
+
+        reward(1, 100);
+
+        assert_eq!(Balances::balance(&1), 200);
+        assert_eq!(Balances::total_issuance(), issuance + 100);
+    });
+}
+
+#[test]
+fn non_invulnerable_reward_goes_to_fallback() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        reward(3, 100);
+
+        assert_eq!(Balances::balance(&3), 100);
+        assert_eq!(Balances::balance(&StakingPoolsAccount::get()), 100);
+    });
+}
+
+#[test]
+fn set_reward_policy_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let policy = RewardPolicy {
+            treasury: Perbill::from_percent(50),
+            burn: Perbill::from_percent(25),
+            staking: Perbill::from_percent(25),
+        };
+
+        assert_ok!(Invulnerables::set_reward_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(policy)
+        ));
+        System::assert_last_event(RuntimeEvent::Invulnerables(crate::Event::RewardPolicySet {
+            account_id: 1,
+            policy: Some(policy),
+        }));
+        assert_eq!(Invulnerables::reward_policy(&1), policy);
+
+        assert_ok!(Invulnerables::set_reward_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            None
+        ));
+        assert_eq!(Invulnerables::reward_policy(&1), RewardPolicy::default());
+
+        // cannot set without privilege
+        assert_noop!(
+            Invulnerables::set_reward_policy(RuntimeOrigin::signed(1), 1, Some(policy)),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_reward_policy_rejects_shares_above_100_percent() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let policy = RewardPolicy {
+            treasury: Perbill::from_percent(50),
+            burn: Perbill::from_percent(30),
+            staking: Perbill::from_percent(30),
+        };
+
+        assert_noop!(
+            Invulnerables::set_reward_policy(
+                RuntimeOrigin::signed(RootAccount::get()),
+                1,
+                Some(policy)
+            ),
+            Error::<Test>::InvalidRewardPolicy
+        );
+    });
+}
+
+#[test]
+fn reward_is_split_according_to_policy() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::set_reward_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(RewardPolicy {
+                treasury: Perbill::from_percent(20),
+                burn: Perbill::from_percent(30),
+                staking: Perbill::from_percent(10),
+            })
+        ));
+        let issuance = Balances::total_issuance();
+
+        reward(1, 1000);
+
+        assert_eq!(Balances::balance(&TreasuryAccount::get()), 200);
+        assert_eq!(Balances::balance(&StakingPoolsAccount::get()), 100);
+        assert_eq!(Balances::balance(&1), 100 + 400);
+        // burned part never reaches the issuance
+        assert_eq!(Balances::total_issuance(), issuance + 700);
+    });
+}
+
+#[test]
+fn policy_only_applies_to_invulnerables() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::set_reward_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            3,
+            Some(RewardPolicy {
+                burn: Perbill::from_percent(100),
+                ..Default::default()
+            })
+        ));
+
+        reward(3, 100);
+
+        assert_eq!(Balances::balance(&StakingPoolsAccount::get()), 100);
+    });
+}
+
+#[test]
+fn staking_share_is_paid_directly_to_non_staking_candidates() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::set_reward_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(RewardPolicy {
+                treasury: Perbill::from_percent(20),
+                burn: Perbill::from_percent(30),
+                staking: Perbill::from_percent(10),
+            })
+        ));
+        NonStakingCandidates::set(vec![1]);
+        let issuance = Balances::total_issuance();
+
+        reward(1, 1000);
+
+        assert_eq!(Balances::balance(&TreasuryAccount::get()), 200);
+        assert_eq!(Balances::balance(&StakingPoolsAccount::get()), 0);
+        assert_eq!(Balances::balance(&1), 100 + 500);
+        assert_eq!(Balances::total_issuance(), issuance + 700);
+    });
+}
+
+#[test]
+fn reward_below_existential_deposit_is_accumulated() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        // account 6 has no balance, ExistentialDeposit is 5
+        assert_ok!(Invulnerables::add_invulnerable(
+            RuntimeOrigin::signed(RootAccount::get()),
+            6
+        ));
+        let issuance = Balances::total_issuance();
+
+        reward(6, 3);
+
+        assert_eq!(Balances::balance(&6), 0);
+        assert_eq!(PendingRewards::<Test>::get(6), 3);
+        // the reward is held, not burned
+        assert_eq!(Balances::balance(&PendingRewardsAccount::get()), 5 + 3);
+        assert_eq!(Balances::total_issuance(), issuance + 3);
+        System::assert_last_event(RuntimeEvent::Invulnerables(
+            crate::Event::RewardsAccumulated {
+                account_id: 6,
+                total_pending: 3,
+            },
+        ));
+
+        // pending rewards are paid together with the next reward
+        reward(6, 3);
+
+        assert_eq!(Balances::balance(&6), 6);
+        assert_eq!(PendingRewards::<Test>::get(6), 0);
+        assert_eq!(Balances::balance(&PendingRewardsAccount::get()), 5);
+        assert_eq!(Balances::total_issuance(), issuance + 6);
+    });
+}
+
+#[test]
+fn pending_rewards_are_kept_while_below_existential_deposit() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::add_invulnerable(
+            RuntimeOrigin::signed(RootAccount::get()),
+            6
+        ));
+        let issuance = Balances::total_issuance();
+
+        reward(6, 1);
+        reward(6, 1);
+
+        // the pending reward is withdrawn, fails to be paid again and is held back
+        assert_eq!(Balances::balance(&6), 0);
+        assert_eq!(PendingRewards::<Test>::get(6), 2);
+        assert_eq!(Balances::balance(&PendingRewardsAccount::get()), 5 + 2);
+        assert_eq!(Balances::total_issuance(), issuance + 2);
+        System::assert_last_event(RuntimeEvent::Invulnerables(
+            crate::Event::RewardsAccumulated {
+                account_id: 6,
+                total_pending: 2,
+            },
+        ));
+    });
+}
+
+#[test]
+fn rewards_that_cannot_be_held_are_sent_to_treasury() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::add_invulnerable(
+            RuntimeOrigin::signed(RootAccount::get()),
+            6
+        ));
+        // the holding account is only funded at genesis
+        Balances::set_balance(&PendingRewardsAccount::get(), 0);
+        let issuance = Balances::total_issuance();
+
+        // the treasury is empty too, the reward is burned
+        reward(6, 3);
+
+        assert_eq!(PendingRewards::<Test>::get(6), 0);
+        assert_eq!(Balances::total_issuance(), issuance);
+        System::assert_last_event(RuntimeEvent::Invulnerables(crate::Event::RewardsNotPaid {
+            account_id: 6,
+            amount: 3,
+            sent_to_treasury: false,
+        }));
+
+        Balances::set_balance(&TreasuryAccount::get(), 5);
+        reward(6, 3);
+
+        assert_eq!(PendingRewards::<Test>::get(6), 0);
+        assert_eq!(Balances::balance(&TreasuryAccount::get()), 5 + 3);
+        System::assert_last_event(RuntimeEvent::Invulnerables(crate::Event::RewardsNotPaid {
+            account_id: 6,
+            amount: 3,
+            sent_to_treasury: true,
+        }));
+    });
+}
+
+#[test]
+fn claim_pending_rewards_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::add_invulnerable(
+            RuntimeOrigin::signed(RootAccount::get()),
+            6
+        ));
+        reward(6, 3);
+
+        // still below the existential deposit
+        assert_noop!(
+            Invulnerables::claim_pending_rewards(RuntimeOrigin::signed(6)),
+            Error::<Test>::CannotDepositPendingRewards
+        );
+
+        reward(6, 1);
+        assert_eq!(PendingRewards::<Test>::get(6), 4);
+        // pending rewards are held in the pending rewards account until paid
+        Balances::resolve(&PendingRewardsAccount::get(), Balances::issue(6)).unwrap();
+        PendingRewards::<Test>::insert(6, 10);
+        let issuance = Balances::total_issuance();
+        assert_ok!(Invulnerables::claim_pending_rewards(RuntimeOrigin::signed(
+            6
+        )));
+        System::assert_last_event(RuntimeEvent::Invulnerables(
+            crate::Event::PendingRewardsClaimed {
+                account_id: 6,
+                amount: 10,
+            },
+        ));
+        assert_eq!(Balances::balance(&6), 10);
+        assert_eq!(Balances::balance(&PendingRewardsAccount::get()), 5);
+        assert_eq!(Balances::total_issuance(), issuance);
+
+        assert_noop!(
+            Invulnerables::claim_pending_rewards(RuntimeOrigin::signed(6)),
+            Error::<Test>::NoPendingRewards
+        );
+    });
+}
+
+#[test]
+fn set_invulnerable_expiry_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        assert_ok!(Invulnerables::set_invulnerable_expiry(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(2)
+        ));
+        System::assert_last_event(RuntimeEvent::Invulnerables(
+            crate::Event::InvulnerableExpirySet {
+                account_id: 1,
+                expires_at: Some(2),
+            },
+        ));
+        assert_eq!(InvulnerableExpiry::<Test>::get(1), Some(2));
+
+        assert_ok!(Invulnerables::set_invulnerable_expiry(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            None
+        ));
+        assert_eq!(InvulnerableExpiry::<Test>::get(1), None);
+
+        // cannot set without privilege
+        assert_noop!(
+            Invulnerables::set_invulnerable_expiry(RuntimeOrigin::signed(1), 1, Some(2)),
+            BadOrigin
+        );
+        // only invulnerables can expire
+        assert_noop!(
+            Invulnerables::set_invulnerable_expiry(
+                RuntimeOrigin::signed(RootAccount::get()),
+                3,
+                Some(2)
+            ),
+            Error::<Test>::NotInvulnerable
+        );
+    });
+}
+
+#[test]
+fn set_invulnerable_expiry_must_be_in_the_future() {
+    new_test_ext().execute_with(|| {
+        // Period is 10 blocks
+        run_to_block(21);
+        assert_eq!(Session::current_index(), 2);
+
+        assert_noop!(
+            Invulnerables::set_invulnerable_expiry(
+                RuntimeOrigin::signed(RootAccount::get()),
+                1,
+                Some(2)
+            ),
+            Error::<Test>::ExpiryInThePast
+        );
+    });
+}
+
+#[test]
+fn expired_invulnerables_are_removed_at_session_change() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::set_invulnerable_expiry(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(3)
+        ));
+
+        // Session manager is called with the index of the session being planned
+        run_to_block(11);
+        assert_eq!(Invulnerables::invulnerables(), vec![1, 2]);
+
+        run_to_block(21);
+        assert_eq!(Invulnerables::invulnerables(), vec![2]);
+        assert_eq!(InvulnerableExpiry::<Test>::get(1), None);
+        System::assert_has_event(RuntimeEvent::Invulnerables(
+            crate::Event::InvulnerableExpired { collator_id: 1 },
+        ));
+    });
+}
+
+#[test]
+fn remove_invulnerable_clears_expiry() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(Invulnerables::set_invulnerable_expiry(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(3)
+        ));
+        assert_ok!(Invulnerables::remove_invulnerable(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1
+        ));
+
+        assert_eq!(InvulnerableExpiry::<Test>::get(1), None);
+    });
+}
+
+#[test]
+fn swap_invulnerable_works() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let policy = RewardPolicy {
+            burn: Perbill::from_percent(10),
+            ..Default::default()
+        };
+        assert_ok!(Invulnerables::set_invulnerable_expiry(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(5)
+        ));
+        assert_ok!(Invulnerables::set_reward_policy(
+            RuntimeOrigin::signed(RootAccount::get()),
+            1,
+            Some(policy)
+        ));
+
+        assert_ok!(Invulnerables::swap_invulnerable(
+            RuntimeOrigin::signed(1),
+            3
+        ));
+
+        System::assert_last_event(RuntimeEvent::Invulnerables(
+            crate::Event::InvulnerableSwapped {
+                old_account_id: 1,
+                new_account_id: 3,
+            },
+        ));
+        // position in the list is kept
+        assert_eq!(Invulnerables::invulnerables(), vec![3, 2]);
+        assert_eq!(InvulnerableExpiry::<Test>::get(3), Some(5));
+        assert_eq!(InvulnerableExpiry::<Test>::get(1), None);
+        assert_eq!(RewardPolicies::<Test>::get(3), Some(policy));
+        assert_eq!(RewardPolicies::<Test>::get(1), None);
+    });
+}
+
+#[test]
+fn swap_invulnerable_moves_pending_rewards() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        PendingRewards::<Test>::insert(1, 4);
+        PendingRewards::<Test>::insert(3, 2);
+
+        assert_ok!(Invulnerables::swap_invulnerable(
+            RuntimeOrigin::signed(1),
+            3
+        ));
+
+        assert_eq!(PendingRewards::<Test>::get(1), 0);
+        assert_eq!(PendingRewards::<Test>::get(3), 6);
+    });
+}
+
+#[test]
+fn swap_invulnerable_checks() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+
+        // caller is not an invulnerable
+        assert_noop!(
+            Invulnerables::swap_invulnerable(RuntimeOrigin::signed(3), 4),
+            Error::<Test>::NotInvulnerable
+        );
+        // new account is already an invulnerable
+        assert_noop!(
+            Invulnerables::swap_invulnerable(RuntimeOrigin::signed(1), 2),
+            Error::<Test>::AlreadyInvulnerable
+        );
+        // new account has no keys
+        assert_noop!(
+            Invulnerables::swap_invulnerable(RuntimeOrigin::signed(1), 42),
+            Error::<Test>::NoKeysRegistered
+        );
+    });
+}
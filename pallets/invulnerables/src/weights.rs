@@ -22,6 +22,7 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `girazoki-XPS-15-9530`, CPU: `13th Gen Intel(R) Core(TM) i9-13900H`
 //! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! PLACEHOLDERS, written by hand and not benchmarked yet: `claim_pending_rewards`, `expire_invulnerables`, `reward_invulnerable`, `set_invulnerable_expiry`, `set_reward_policy`, `swap_invulnerable`
 
 // Executed Command:
 // ./target/release/tanssi-node
@@ -57,6 +58,11 @@ pub trait WeightInfo {
 	fn remove_invulnerable(_b: u32) -> Weight;
 	fn new_session(_b: u32) -> Weight;
 	fn reward_invulnerable(_b: u32) -> Weight;
+	fn set_reward_policy() -> Weight;
+	fn claim_pending_rewards() -> Weight;
+	fn set_invulnerable_expiry(b: u32) -> Weight;
+	fn swap_invulnerable(b: u32) -> Weight;
+	fn expire_invulnerables(r: u32) -> Weight;
 }
 
 /// Weights for pallet_invulnerables using the Substrate node and recommended hardware.
@@ -75,8 +81,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(17_124_910, 4687)
 			// Standard Error: 1_519
 			.saturating_add(Weight::from_parts(76_594, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(2_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 			.saturating_add(Weight::from_parts(0, 37).saturating_mul(b.into()))
 	}
 	/// Storage: `Invulnerables::Invulnerables` (r:1 w:1)
@@ -109,21 +115,47 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
-	/// Storage: `Invulnerables::Invulnerables` (r:1 w:0)
-	/// Proof: `Invulnerables::Invulnerables` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 100]`.
+	/// Placeholder weight, not benchmarked yet.
 	fn reward_invulnerable(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `218 + b * (33 ±0)`
-		//  Estimated: `4687`
-		// Minimum execution time: 17_514_000 picoseconds.
 		Weight::from_parts(19_797_082, 4687)
-			// Standard Error: 1_701
 			.saturating_add(Weight::from_parts(69_693, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_reward_policy() -> Weight {
+		Weight::from_parts(7_412_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn claim_pending_rewards() -> Weight {
+		Weight::from_parts(32_480_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_invulnerable_expiry(b: u32, ) -> Weight {
+		Weight::from_parts(15_203_118, 4687)
+			.saturating_add(Weight::from_parts(61_092, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn swap_invulnerable(b: u32, ) -> Weight {
+		Weight::from_parts(26_342_571, 4687)
+			.saturating_add(Weight::from_parts(88_430, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn expire_invulnerables(r: u32, ) -> Weight {
+		Weight::from_parts(7_913_441, 4687)
+			.saturating_add(Weight::from_parts(7_390_871, 0).saturating_mul(r.into()))
 			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(r.into()))
 	}
 }
 
@@ -176,20 +208,46 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-	/// Storage: `Invulnerables::Invulnerables` (r:1 w:0)
-	/// Proof: `Invulnerables::Invulnerables` (`max_values`: Some(1), `max_size`: Some(3202), added: 3697, mode: `MaxEncodedLen`)
-	/// Storage: `System::Account` (r:1 w:1)
-	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	/// The range of component `b` is `[1, 100]`.
+	/// Placeholder weight, not benchmarked yet.
 	fn reward_invulnerable(b: u32, ) -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `218 + b * (33 ±0)`
-		//  Estimated: `4687`
-		// Minimum execution time: 17_514_000 picoseconds.
 		Weight::from_parts(19_797_082, 4687)
-			// Standard Error: 1_701
 			.saturating_add(Weight::from_parts(69_693, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_reward_policy() -> Weight {
+		Weight::from_parts(7_412_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn claim_pending_rewards() -> Weight {
+		Weight::from_parts(32_480_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn set_invulnerable_expiry(b: u32, ) -> Weight {
+		Weight::from_parts(15_203_118, 4687)
+			.saturating_add(Weight::from_parts(61_092, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn swap_invulnerable(b: u32, ) -> Weight {
+		Weight::from_parts(26_342_571, 4687)
+			.saturating_add(Weight::from_parts(88_430, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Placeholder weight, not benchmarked yet.
+	fn expire_invulnerables(r: u32, ) -> Weight {
+		Weight::from_parts(7_913_441, 4687)
+			.saturating_add(Weight::from_parts(7_390_871, 0).saturating_mul(r.into()))
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2543).saturating_mul(r.into()))
 	}
 }
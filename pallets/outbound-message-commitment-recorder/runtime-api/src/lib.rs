@@ -0,0 +1,59 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for Outbound Message Commitment Recorder pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use {
+    alloc::vec::Vec,
+    parity_scale_codec::{Decode, Encode},
+    sp_core::H256,
+};
+
+/// Status of an outbound message, with the merkle proof of its inclusion in the commitment of
+/// the block that accepted it.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OutboundMessageStatus<BlockNumber> {
+    /// Nonce of the message in its channel, or in the V2 outbound queue.
+    pub nonce: u64,
+    /// Channel of the message, `None` for messages sent through the V2 outbound queue.
+    pub channel_id: Option<H256>,
+    /// Block in which the message was accepted by the outbound queue.
+    pub block_number: BlockNumber,
+    /// Commitment root recorded for the block, `None` if it does not match the proof.
+    pub commitment_root: Option<H256>,
+    pub leaf_index: u64,
+    pub leaf: H256,
+    pub number_of_leaves: u64,
+    /// Merkle proof of `leaf` against `commitment_root`.
+    pub proof: Vec<H256>,
+}
+
+sp_api::decl_runtime_apis! {
+    pub trait OutboundMessageTrackingApi<BlockNumber>
+    where
+        BlockNumber: parity_scale_codec::Codec,
+    {
+        /// Status of a message sent to Ethereum, by message id (V1) or topic (V2). Returns
+        /// `None` if the message is unknown or was accepted before the tracking period.
+        fn outbound_message_status(message_id: H256) -> Option<OutboundMessageStatus<BlockNumber>>;
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Benchmarking setup for pallet_outbound_message_commitment_recorder
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as OutboundMessageCommitmentRecorder;
+use {alloc::vec::Vec, frame_benchmarking::v2::*, sp_core::H256, sp_runtime::traits::Hash};
+
+fn committed_message<T: Config>(n: u32) -> CommittedMessage {
+    let id = H256::from_low_u64_be(n.into());
+    CommittedMessage {
+        message_id: id,
+        channel_id: Some(H256::zero()),
+        nonce: n.into(),
+        leaf: <T as Config>::Hashing::hash(id.as_bytes()),
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn record_commitment(n: Linear<1, { T::MaxMessagesPerBlock::get() }>) {
+        let messages: Vec<_> = (0..n).map(committed_message::<T>).collect();
+        let commitment = H256::repeat_byte(0x01);
+
+        #[block]
+        {
+            OutboundMessageCommitmentRecorder::<T>::record_commitment(
+                MessageQueueVersion::V1,
+                commitment,
+                messages,
+            );
+        }
+
+        assert_eq!(
+            BlockMessages::<T>::get(
+                frame_system::Pallet::<T>::block_number(),
+                MessageQueueVersion::V1
+            )
+            .len(),
+            n as usize
+        );
+        assert_eq!(RecordedCommitment::<T>::get(), Some(commitment));
+    }
+
+    impl_benchmark_test_suite!(
+        OutboundMessageCommitmentRecorder,
+        crate::mock::new_test_ext(),
+        crate::mock::Test,
+    );
+}
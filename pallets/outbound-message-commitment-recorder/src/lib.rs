@@ -18,21 +18,62 @@
 //!
 //! A pallet to record outbound message commitment.
 //!
+//! It also keeps, for a limited number of blocks, an index of the messages accepted by the
+//! outbound queues, so that their nonce, leaf index and merkle proof can be queried by id.
+//!
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
-    use {frame_support::pallet_prelude::*, sp_core::H256};
+    use {
+        frame_support::pallet_prelude::*,
+        frame_system::pallet_prelude::*,
+        snowbridge_merkle_tree::{merkle_proof, MerkleProof},
+        sp_core::H256,
+        sp_runtime::traits::{Hash, Saturating},
+    };
+
+    pub use crate::weights::WeightInfo;
 
     /// The current storage version.
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
 
+    /// Maximum number of commitments recorded in a single block, one per outbound queue version.
+    pub const MAX_COMMITMENTS_PER_BLOCK: u32 = 2;
+
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
-    pub trait Config: frame_system::Config {}
+    pub trait Config: frame_system::Config {
+        /// Hashing used by the outbound queues to build the message merkle tree.
+        type Hashing: Hash<Output = H256>;
+
+        /// Maximum number of messages accepted by an outbound queue in a single block.
+        #[pallet::constant]
+        type MaxMessagesPerBlock: Get<u32>;
+
+        /// Number of blocks during which the messages of a block can be tracked.
+        #[pallet::constant]
+        type MessageTrackingPeriod: Get<BlockNumberFor<Self>>;
+
+        /// Weight information for recording commitments.
+        type WeightInfo: WeightInfo;
+    }
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -45,11 +86,103 @@ pub mod pallet {
         CommitmentRootRead { commitment: H256 },
     }
 
+    /// Version of the outbound queue that accepted a message.
+    #[derive(
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        MaxEncodedLen,
+        TypeInfo,
+        RuntimeDebug,
+    )]
+    pub enum MessageQueueVersion {
+        V1,
+        V2,
+    }
+
+    /// Where an outbound message was included.
+    #[derive(
+        Clone,
+        PartialEq,
+        Eq,
+        Encode,
+        Decode,
+        DecodeWithMemTracking,
+        MaxEncodedLen,
+        TypeInfo,
+        RuntimeDebug,
+    )]
+    pub struct OutboundMessageRecord<BlockNumber> {
+        pub version: MessageQueueVersion,
+        /// Channel of the message, `None` for V2 messages which don't use channels.
+        pub channel_id: Option<H256>,
+        pub nonce: u64,
+        pub block_number: BlockNumber,
+        pub leaf_index: u64,
+    }
+
+    /// A message committed by an outbound queue.
+    #[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct CommittedMessage {
+        pub message_id: H256,
+        /// Channel of the message, `None` for V2 messages which don't use channels.
+        pub channel_id: Option<H256>,
+        pub nonce: u64,
+        /// Hash of the message as committed in the merkle tree of the block.
+        pub leaf: H256,
+    }
+
     /// Message commitment from last block.
     /// This will be set only when there are messages to relay.
     #[pallet::storage]
     pub type RecordedCommitment<T: Config> = StorageValue<_, H256, OptionQuery>;
 
+    /// Outbound messages accepted during the tracking period, by message id.
+    #[pallet::storage]
+    pub type MessageRecords<T: Config> =
+        StorageMap<_, Twox64Concat, H256, OutboundMessageRecord<BlockNumberFor<T>>, OptionQuery>;
+
+    /// Ids and merkle leaves of the messages accepted by each outbound queue in a block, in
+    /// leaf order.
+    #[pallet::storage]
+    pub type BlockMessages<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Twox64Concat,
+        MessageQueueVersion,
+        BoundedVec<(H256, H256), T::MaxMessagesPerBlock>,
+        ValueQuery,
+    >;
+
+    /// Commitments recorded in a block during the tracking period.
+    #[pallet::storage]
+    pub type BlockCommitments<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<H256, ConstU32<MAX_COMMITMENTS_PER_BLOCK>>,
+        ValueQuery,
+    >;
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            // Commitments are recorded on finalize, reserve their worst case upfront
+            let weight = Self::commitments_weight();
+            let period = T::MessageTrackingPeriod::get();
+            if now <= period {
+                return weight;
+            }
+
+            weight.saturating_add(Self::prune_block(now.saturating_sub(period)))
+        }
+    }
+
     impl<T: Config> Pallet<T> {
         pub fn take_commitment_root() -> Option<H256> {
             let maybe_commitment = RecordedCommitment::<T>::take();
@@ -63,7 +196,103 @@ pub mod pallet {
 
         pub fn record_commitment_root(commitment: H256) {
             RecordedCommitment::<T>::put(commitment);
+            BlockCommitments::<T>::mutate(frame_system::Pallet::<T>::block_number(), |roots| {
+                let _ = roots.try_push(commitment);
+            });
             Pallet::<T>::deposit_event(Event::<T>::NewCommitmentRootRecorded { commitment });
         }
+
+        /// Record the commitment of an outbound queue along with the messages it commits to, in
+        /// leaf order. Meant to be called from the commit of the queue, while it still holds the
+        /// messages of the block, so that they are read once instead of on every new message.
+        ///
+        /// Its weight is reserved in `on_initialize`, see [`Self::commitments_weight`].
+        pub fn record_commitment(
+            version: MessageQueueVersion,
+            commitment: H256,
+            messages: impl IntoIterator<Item = CommittedMessage>,
+        ) {
+            let block_number = frame_system::Pallet::<T>::block_number();
+            let mut leaves = BoundedVec::<(H256, H256), T::MaxMessagesPerBlock>::new();
+            for message in messages {
+                let leaf_index = leaves.len() as u64;
+                if leaves.try_push((message.message_id, message.leaf)).is_err() {
+                    log::warn!(
+                        target: "outbound-message-commitment-recorder",
+                        "Too many messages in block, message {:?} will not be tracked",
+                        message.message_id
+                    );
+                    continue;
+                }
+
+                MessageRecords::<T>::insert(
+                    message.message_id,
+                    OutboundMessageRecord {
+                        version,
+                        channel_id: message.channel_id,
+                        nonce: message.nonce,
+                        block_number,
+                        leaf_index,
+                    },
+                );
+            }
+
+            BlockMessages::<T>::insert(block_number, version, leaves);
+            Self::record_commitment_root(commitment);
+        }
+
+        /// Worst case weight of the commitments recorded in a block: one per outbound queue, each
+        /// with `MaxMessagesPerBlock` messages, plus reading the messages and leaves of the queue.
+        pub fn commitments_weight() -> Weight {
+            T::WeightInfo::record_commitment(T::MaxMessagesPerBlock::get())
+                .saturating_add(T::DbWeight::get().reads(2))
+                .saturating_mul(MAX_COMMITMENTS_PER_BLOCK.into())
+        }
+
+        /// Record of a tracked message and its merkle proof against the root committed in the
+        /// block that included it.
+        pub fn outbound_message_proof(
+            message_id: H256,
+        ) -> Option<(OutboundMessageRecord<BlockNumberFor<T>>, MerkleProof)> {
+            let record = MessageRecords::<T>::get(message_id)?;
+            let leaves = BlockMessages::<T>::get(record.block_number, record.version);
+            if record.leaf_index >= leaves.len() as u64 {
+                return None;
+            }
+
+            let proof = merkle_proof::<T::Hashing, _>(
+                leaves.into_iter().map(|(_, leaf)| leaf),
+                record.leaf_index,
+            );
+
+            Some((record, proof))
+        }
+
+        /// Whether `root` was recorded as a commitment in `block_number`.
+        pub fn is_recorded_commitment(block_number: BlockNumberFor<T>, root: H256) -> bool {
+            BlockCommitments::<T>::get(block_number).contains(&root)
+        }
+
+        /// Forget the messages and commitments of a block that left the tracking period.
+        pub(crate) fn prune_block(block_number: BlockNumberFor<T>) -> Weight {
+            let mut removed = 0u64;
+            for version in [MessageQueueVersion::V1, MessageQueueVersion::V2] {
+                for (message_id, _) in BlockMessages::<T>::take(block_number, version) {
+                    // Ids can be reused, keep the record if it points to a later block
+                    MessageRecords::<T>::mutate_exists(message_id, |record| {
+                        if record
+                            .as_ref()
+                            .is_some_and(|r| r.block_number == block_number)
+                        {
+                            *record = None;
+                        }
+                    });
+                    removed.saturating_accrue(1);
+                }
+            }
+            BlockCommitments::<T>::remove(block_number);
+
+            T::DbWeight::get().reads_writes(removed.saturating_add(3), removed.saturating_add(3))
+        }
     }
 }
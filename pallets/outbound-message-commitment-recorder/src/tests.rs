@@ -0,0 +1,218 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+use {
+    crate::{mock::*, *},
+    frame_support::traits::Hooks,
+    snowbridge_merkle_tree::{merkle_root, verify_proof},
+    sp_core::H256,
+    sp_runtime::traits::Keccak256,
+};
+
+fn message_id(n: u8) -> H256 {
+    H256::repeat_byte(n)
+}
+
+fn leaf(n: u8) -> H256 {
+    H256::repeat_byte(n.wrapping_add(0x80))
+}
+
+fn committed_message(n: u8) -> CommittedMessage {
+    CommittedMessage {
+        message_id: message_id(n),
+        channel_id: None,
+        nonce: n as u64,
+        leaf: leaf(n),
+    }
+}
+
+fn commit(version: MessageQueueVersion, messages: impl IntoIterator<Item = u8>) {
+    OutboundMessageCommitmentRecorder::record_commitment(
+        version,
+        H256::zero(),
+        messages.into_iter().map(committed_message),
+    )
+}
+
+#[test]
+fn messages_are_tracked_in_leaf_order() {
+    new_test_ext().execute_with(|| {
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V1,
+            H256::zero(),
+            (1..=3).map(|n| CommittedMessage {
+                channel_id: Some(H256::zero()),
+                ..committed_message(n)
+            }),
+        );
+
+        let record = MessageRecords::<Test>::get(message_id(2)).unwrap();
+        assert_eq!(
+            record,
+            OutboundMessageRecord {
+                version: MessageQueueVersion::V1,
+                channel_id: Some(H256::zero()),
+                nonce: 2,
+                block_number: 1,
+                leaf_index: 1,
+            }
+        );
+    });
+}
+
+#[test]
+fn versions_use_separate_trees() {
+    new_test_ext().execute_with(|| {
+        commit(MessageQueueVersion::V1, [1]);
+        commit(MessageQueueVersion::V2, [2]);
+
+        let record = MessageRecords::<Test>::get(message_id(2)).unwrap();
+        assert_eq!(record.version, MessageQueueVersion::V2);
+        assert_eq!(record.leaf_index, 0);
+        assert_eq!(
+            BlockMessages::<Test>::get(1, MessageQueueVersion::V1).into_inner(),
+            vec![(message_id(1), leaf(1))]
+        );
+    });
+}
+
+#[test]
+fn messages_above_limit_are_not_tracked() {
+    new_test_ext().execute_with(|| {
+        commit(MessageQueueVersion::V1, 1..=4);
+
+        assert!(MessageRecords::<Test>::get(message_id(3)).is_some());
+        assert!(MessageRecords::<Test>::get(message_id(4)).is_none());
+    });
+}
+
+#[test]
+fn worst_case_commitments_are_reserved_on_initialize() {
+    new_test_ext().execute_with(|| {
+        let reserved = <() as WeightInfo>::record_commitment(3).saturating_mul(2);
+        assert_eq!(
+            OutboundMessageCommitmentRecorder::commitments_weight(),
+            reserved
+        );
+        assert_eq!(
+            OutboundMessageCommitmentRecorder::on_initialize(1),
+            reserved
+        );
+        // Pruning is charged on top of the reservation
+        assert!(OutboundMessageCommitmentRecorder::on_initialize(11).all_gte(reserved));
+    });
+}
+
+#[test]
+fn commitment_is_recorded_with_its_messages() {
+    new_test_ext().execute_with(|| {
+        let root = merkle_root::<Keccak256, _>((1..=2).map(leaf));
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V1,
+            root,
+            (1..=2).map(committed_message),
+        );
+
+        assert_eq!(RecordedCommitment::<Test>::get(), Some(root));
+        assert!(OutboundMessageCommitmentRecorder::is_recorded_commitment(
+            1, root
+        ));
+        assert_eq!(
+            BlockMessages::<Test>::get(1, MessageQueueVersion::V1).len(),
+            2
+        );
+    });
+}
+
+#[test]
+fn proof_matches_recorded_commitment() {
+    new_test_ext().execute_with(|| {
+        let root = merkle_root::<Keccak256, _>((1..=3).map(leaf));
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V1,
+            root,
+            (1..=3).map(committed_message),
+        );
+
+        let (record, proof) =
+            OutboundMessageCommitmentRecorder::outbound_message_proof(message_id(3)).unwrap();
+        assert_eq!(record.leaf_index, 2);
+        assert_eq!(proof.root, root);
+        assert_eq!(proof.leaf, leaf(3));
+        assert!(verify_proof::<Keccak256, _, _>(
+            &proof.root,
+            proof.proof,
+            proof.number_of_leaves,
+            proof.leaf_index,
+            proof.leaf,
+        ));
+        assert!(OutboundMessageCommitmentRecorder::is_recorded_commitment(
+            1, root
+        ));
+    });
+}
+
+#[test]
+fn unknown_message_has_no_proof() {
+    new_test_ext().execute_with(|| {
+        assert!(OutboundMessageCommitmentRecorder::outbound_message_proof(message_id(1)).is_none());
+    });
+}
+
+#[test]
+fn messages_are_pruned_after_tracking_period() {
+    new_test_ext().execute_with(|| {
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V1,
+            leaf(1),
+            [committed_message(1)],
+        );
+
+        run_to_block(10);
+        assert!(MessageRecords::<Test>::get(message_id(1)).is_some());
+
+        run_to_block(11);
+        assert!(MessageRecords::<Test>::get(message_id(1)).is_none());
+        assert!(BlockMessages::<Test>::get(1, MessageQueueVersion::V1).is_empty());
+        assert!(BlockCommitments::<Test>::get(1).is_empty());
+    });
+}
+
+#[test]
+fn pruning_keeps_reused_id_from_later_block() {
+    new_test_ext().execute_with(|| {
+        commit(MessageQueueVersion::V1, [1]);
+
+        run_to_block(5);
+        OutboundMessageCommitmentRecorder::record_commitment(
+            MessageQueueVersion::V1,
+            H256::zero(),
+            [CommittedMessage {
+                nonce: 2,
+                leaf: leaf(2),
+                ..committed_message(1)
+            }],
+        );
+
+        run_to_block(12);
+        assert_eq!(
+            MessageRecords::<Test>::get(message_id(1))
+                .unwrap()
+                .block_number,
+            5
+        );
+    });
+}
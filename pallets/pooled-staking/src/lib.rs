@@ -68,6 +68,7 @@ pub mod pallet {
     use {
         super::*,
         crate::{
+            candidate::Candidates,
             traits::{IsCandidateEligible, Timer},
             weights::WeightInfo,
         },
@@ -85,7 +86,7 @@ pub mod pallet {
         scale_info::TypeInfo,
         serde::{Deserialize, Serialize},
         sp_core::Get,
-        sp_runtime::{BoundedVec, Perbill},
+        sp_runtime::{traits::Zero, BoundedVec, Perbill},
         tp_maths::MulDiv,
     };
 
@@ -794,6 +795,10 @@ pub mod pallet {
             pools::distribute_rewards::<T>(&candidate, rewards)
         }
 
+        fn can_distribute_rewards(candidate: &Candidate<T>) -> bool {
+            !Candidates::<T>::total_stake(candidate).0.is_zero()
+        }
+
         #[cfg(feature = "runtime-benchmarks")]
         fn prepare_worst_case_for_bench(caller: &Candidate<T>) {
             // Worst case: account exists in both pools
@@ -19,9 +19,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
-pub use pallet_xcm_core_buyer::BuyingError;
+pub use pallet_xcm_core_buyer::{
+    BuyingError, CorePurchaseKind, CorePurchaseOutcome, CorePurchaseRecord, CorePurchaseStats,
+};
 use {
-    alloc::boxed::Box,
+    alloc::{boxed::Box, vec::Vec},
     frame_support::sp_runtime,
     sp_consensus_slots::Slot,
     sp_runtime::{traits::Block as BlockT, RuntimeAppPublic},
@@ -29,10 +31,17 @@ use {
 };
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait XCMCoreBuyerApi<BlockNumber, ParaId, CollatorPublicKey> where ParaId: parity_scale_codec::Codec, BlockNumber: parity_scale_codec::Codec, BuyingError<BlockNumber>: parity_scale_codec::Codec, CollatorPublicKey: RuntimeAppPublic + Clone + core::fmt::Debug + parity_scale_codec::Codec,  {
         fn is_core_buying_allowed(para_id: ParaId, collator_public_key: CollatorPublicKey) -> Result<(), BuyingError<BlockNumber>>;
         fn create_buy_core_unsigned_extrinsic(para_id: ParaId, proof: BuyCoreCollatorProof<CollatorPublicKey>) -> Box<<Block as BlockT>::Extrinsic>;
         fn get_buy_core_signature_nonce(para_id: ParaId) -> u64;
         fn get_buy_core_slot_drift() -> Slot;
+        /// Latest core purchases of this parathread, oldest first.
+        #[api_version(2)]
+        fn get_core_purchase_history(para_id: ParaId) -> Vec<CorePurchaseRecord<BlockNumber, CollatorPublicKey>>;
+        /// Aggregated stats of all the core purchases of this parathread.
+        #[api_version(2)]
+        fn get_core_purchase_stats(para_id: ParaId) -> CorePurchaseStats;
     }
 }
@@ -19,19 +19,48 @@
 //! Benchmarking
 use {
     crate::{
-        Call, Config, GetParathreadParams, InFlightOrders, Pallet, RelayXcmWeightConfig,
-        RelayXcmWeightConfigInner,
+        BulkRegionId, BulkRegions, Call, Config, CorePurchaseHistory, CorePurchaseKind,
+        CorePurchaseOutcome, CorePurchaseRecord, CoretimeXcmWeightConfig, GetParathreadParams,
+        InFlightOrders, Pallet, RelayXcmWeightConfig, RelayXcmWeightConfigInner,
     },
+    alloc::vec::Vec,
     core::marker::PhantomData,
     frame_benchmarking::v2::*,
-    frame_support::{assert_ok, pallet_prelude::Weight, BoundedVec},
+    frame_support::{
+        assert_ok,
+        pallet_prelude::Weight,
+        traits::{EnsureOriginWithArg, Get},
+        BoundedVec,
+    },
     frame_system::RawOrigin,
+    sp_runtime::RuntimeAppPublic,
     tp_traits::{ParaId, ParathreadParams, SlotFrequency},
+    xcm::latest::QueryId,
 };
 
 pub const BUY_EXECUTION_COST: u128 = 50_000_000;
 pub const PLACE_ORDER_WEIGHT_AT_MOST: Weight = Weight::from_parts(1_000_000_000, 100_000);
 
+/// Fill the purchase history of `para_id` with pending purchases, the last one with
+/// `last_query_id`, so that it needs to be searched and the oldest record removed.
+fn fill_core_purchase_history<T: Config>(para_id: ParaId, last_query_id: QueryId) {
+    let max_history_length: u32 = T::MaxPurchaseHistoryLength::get();
+    let history = (0..max_history_length)
+        .map(|i| CorePurchaseRecord {
+            query_id: last_query_id.saturating_sub(QueryId::from(max_history_length - 1 - i)),
+            kind: CorePurchaseKind::OnDemand,
+            sent_at: <frame_system::Pallet<T>>::block_number(),
+            max_price: Some(u128::MAX),
+            execution_fee: BUY_EXECUTION_COST,
+            collator: Some(T::CollatorPublicKey::generate_pair(None)),
+            outcome: CorePurchaseOutcome::Pending,
+            finished_at: None,
+        })
+        .collect::<Vec<_>>();
+
+    CorePurchaseHistory::<T>::insert(para_id, BoundedVec::truncate_from(history));
+}
+
 #[benchmarks(where <T as frame_system::Config>::RuntimeOrigin: From<pallet_xcm::Origin>)]
 mod benchmarks {
     use {
@@ -79,6 +108,7 @@ mod benchmarks {
         }
 
         assert!(InFlightOrders::<T>::get(para_id).is_none());
+        fill_core_purchase_history::<T>(para_id, QueryId::from(x) + 1000);
 
         // For the extrinsic to succeed, we need to ensure that:
         // * the para_id is a parathread
@@ -134,6 +164,7 @@ mod benchmarks {
         }
 
         assert!(InFlightOrders::<T>::get(para_id).is_none());
+        fill_core_purchase_history::<T>(para_id, QueryId::from(x) + 1000);
 
         // For the extrinsic to succeed, we need to ensure that:
         // * the para_id is a parathread
@@ -171,6 +202,7 @@ mod benchmarks {
         }
 
         assert!(InFlightOrders::<T>::get(para_id).is_some());
+        fill_core_purchase_history::<T>(para_id, QueryId::from(x));
 
         let response = if x % 2 == 0 {
             Response::DispatchResult(MaybeErrorCode::Success)
@@ -262,5 +294,36 @@ mod benchmarks {
         Pallet::<T>::set_relay_chain(RawOrigin::Root, Some(T::RelayChain::default()));
     }
 
+    #[benchmark]
+    fn set_coretime_xcm_weight_config() {
+        let xcm_weights = RelayXcmWeightConfigInner {
+            buy_execution_cost: BUY_EXECUTION_COST,
+            weight_at_most: PLACE_ORDER_WEIGHT_AT_MOST,
+            _phantom: PhantomData,
+        };
+
+        #[extrinsic_call]
+        Pallet::<T>::set_coretime_xcm_weight_config(RawOrigin::Root, Some(xcm_weights.clone()));
+
+        assert_eq!(CoretimeXcmWeightConfig::<T>::get(), Some(xcm_weights));
+    }
+
+    #[benchmark]
+    fn set_bulk_region() {
+        let para_id = ParaId::from(1000);
+        let origin = T::ManagerOrigin::try_successful_origin(&para_id)
+            .expect("failed to create ManagerOrigin");
+        let region_id = BulkRegionId {
+            begin: 1,
+            core: 1,
+            mask: [0xff; 10],
+        };
+
+        #[extrinsic_call]
+        Pallet::<T>::set_bulk_region(origin, para_id, Some(region_id));
+
+        assert!(BulkRegions::<T>::get(para_id).is_some());
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
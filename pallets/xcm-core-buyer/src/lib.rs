@@ -17,6 +17,10 @@
 //! # XCM Core Buyer Pallet
 //!
 //! This pallet allows collators to buy parathread cores on demand.
+//!
+//! Parathreads can also hold a bulk coretime region, bought in the coretime chain by their tank
+//! account. The first time a collator requests a core, the region is assigned to the parathread
+//! using XCM, and any later request falls back to buying an on-demand core.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
@@ -44,7 +48,10 @@ use {
     frame_support::{
         dispatch::GetDispatchInfo,
         pallet_prelude::*,
-        traits::fungible::{Balanced, Inspect},
+        traits::{
+            fungible::{Balanced, Inspect},
+            EnsureOriginWithArg,
+        },
     },
     frame_system::pallet_prelude::*,
     parity_scale_codec::EncodeLike,
@@ -100,6 +107,123 @@ pub struct InFlightCoreBuyingOrder<BN> {
     ttl: BN,
 }
 
+/// Identifier of a bulk coretime region, with the same encoding as `pallet_broker::RegionId`.
+#[derive(
+    RuntimeDebug,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    TypeInfo,
+    Serialize,
+    Deserialize,
+    MaxEncodedLen,
+)]
+pub struct BulkRegionId {
+    /// Timeslice at which the region starts.
+    pub begin: u32,
+    /// Index of the core of the region.
+    pub core: u16,
+    /// Parts of the core covered by the region.
+    pub mask: [u8; 10],
+}
+
+#[derive(
+    RuntimeDebug,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    Clone,
+    TypeInfo,
+    Serialize,
+    Deserialize,
+    MaxEncodedLen,
+)]
+pub enum BulkRegionStatus {
+    /// The region has not been assigned to the parathread yet.
+    Unassigned,
+    /// An XCM message to assign the region has been sent to the coretime chain.
+    AssignmentInFlight { query_id: QueryId },
+    /// The region has been assigned to the parathread.
+    Assigned,
+    /// The coretime chain failed to assign the region.
+    AssignmentFailed,
+}
+
+#[derive(
+    RuntimeDebug,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    Clone,
+    TypeInfo,
+    Serialize,
+    Deserialize,
+    MaxEncodedLen,
+)]
+pub struct BulkRegion {
+    pub region_id: BulkRegionId,
+    pub status: BulkRegionStatus,
+}
+
+#[derive(RuntimeDebug, PartialEq, Eq, Encode, Decode, Clone, TypeInfo, MaxEncodedLen)]
+pub enum CorePurchaseKind {
+    /// On-demand core bought in the relay chain.
+    OnDemand,
+    /// Bulk region assigned to the parathread in the coretime chain.
+    BulkRegionAssignment,
+}
+
+#[derive(RuntimeDebug, PartialEq, Eq, Encode, Decode, Clone, TypeInfo, MaxEncodedLen)]
+pub enum CorePurchaseOutcome {
+    /// No response has been received yet.
+    Pending,
+    /// The relay or coretime chain dispatched the call successfully.
+    Success,
+    /// The relay or coretime chain failed to dispatch the call.
+    Failed,
+    /// No response was received before the order expired.
+    Expired,
+}
+
+/// Core purchase sent for a parathread, kept in `CorePurchaseHistory`.
+#[derive(RuntimeDebug, PartialEq, Eq, Encode, Decode, Clone, TypeInfo, MaxEncodedLen)]
+pub struct CorePurchaseRecord<BN, PublicKey> {
+    pub query_id: QueryId,
+    pub kind: CorePurchaseKind,
+    /// Block in which the XCM message was sent.
+    pub sent_at: BN,
+    /// Max price of the core, in relay chain currency. The relay chain does not report the
+    /// price paid, which is at most this amount. `None` for bulk region assignments.
+    pub max_price: Option<u128>,
+    /// Amount withdrawn from the tank account to pay for XCM execution. The surplus is refunded.
+    pub execution_fee: u128,
+    /// Collator that requested the core, `None` if it was bought by root.
+    pub collator: Option<PublicKey>,
+    pub outcome: CorePurchaseOutcome,
+    /// Block in which the outcome was known.
+    pub finished_at: Option<BN>,
+}
+
+/// Aggregated stats of all the core purchases of a parathread.
+#[derive(RuntimeDebug, Default, PartialEq, Eq, Encode, Decode, Clone, TypeInfo, MaxEncodedLen)]
+pub struct CorePurchaseStats {
+    pub orders_sent: u32,
+    pub orders_succeeded: u32,
+    pub orders_failed: u32,
+    pub orders_expired: u32,
+    /// Sum of the max price of all successful on-demand orders, an upper bound of the amount
+    /// spent in cores.
+    pub max_core_spending: u128,
+    /// Sum of the execution fees withdrawn by all the orders, an upper bound of the amount spent
+    /// in fees.
+    pub max_execution_fees: u128,
+}
+
 #[derive(
     Debug, Clone, PartialEq, Eq, Encode, Decode, scale_info::TypeInfo, Serialize, Deserialize,
 )]
@@ -168,6 +292,9 @@ pub mod pallet {
         /// Get encoded call to buy a core in the relay chain. This will be passed to the XCM
         /// `Transact` instruction.
         type GetPurchaseCoreCall: GetPurchaseCoreCall<Self::RelayChain>;
+        /// Get encoded call to assign a bulk region in the coretime chain. This will be passed to
+        /// the XCM `Transact` instruction.
+        type GetAssignRegionCall: GetAssignRegionCall<Self::RelayChain>;
         /// How to convert a `ParaId` into an `AccountId32`. Used to derive the parathread tank
         /// account in `interior_multilocation`.
         type GetParathreadAccountId: Convert<ParaId, [u8; 32]>;
@@ -243,6 +370,17 @@ pub mod pallet {
             + MaybeSerializeDeserialize
             + MaxEncodedLen;
 
+        /// Max number of core purchases kept in the history of each parathread. Older purchases are
+        /// only kept in the aggregated stats.
+        #[pallet::constant]
+        type MaxPurchaseHistoryLength: Get<u32>;
+
+        /// Origin allowed to set the bulk region of a parathread.
+        type ManagerOrigin: EnsureOriginWithArg<
+            <Self as frame_system::Config>::RuntimeOrigin,
+            ParaId,
+        >;
+
         type WeightInfo: WeightInfo;
     }
 
@@ -262,6 +400,20 @@ pub mod pallet {
 
         /// We cleaned up expired in flight orders entries.
         CleanedUpExpiredInFlightOrderEntries { para_ids: Vec<ParaId> },
+
+        /// An XCM message to assign the bulk region of this parathread has been sent to the
+        /// coretime chain.
+        AssignBulkRegionXcmSent {
+            para_id: ParaId,
+            region_id: BulkRegionId,
+            transaction_status_query_id: QueryId,
+        },
+
+        /// The bulk region of this parathread has been set or removed.
+        BulkRegionSet {
+            para_id: ParaId,
+            region_id: Option<BulkRegionId>,
+        },
     }
 
     #[pallet::error]
@@ -299,6 +451,9 @@ pub mod pallet {
         IncorrectCollatorSignatureNonce,
         /// Collator signature is invalid
         InvalidCollatorSignature,
+        /// The `CoretimeXcmWeightConfig` storage has not been set. This must have been set by root
+        /// with the value of the coretime chain xcm call weight and extrinsic weight
+        CoretimeXcmWeightStorageNotSet,
     }
 
     impl<T: Config> From<BuyingError<BlockNumberFor<T>>> for Error<T> {
@@ -337,6 +492,35 @@ pub mod pallet {
     pub type RelayXcmWeightConfig<T: Config> =
         StorageValue<_, RelayXcmWeightConfigInner<T>, OptionQuery>;
 
+    /// Same as `RelayXcmWeightConfig`, for the XCM messages sent to the coretime chain to assign
+    /// bulk regions.
+    #[pallet::storage]
+    pub type CoretimeXcmWeightConfig<T: Config> =
+        StorageValue<_, RelayXcmWeightConfigInner<T>, OptionQuery>;
+
+    /// Bulk region held by the parathread tank account in the coretime chain, and whether it has
+    /// been assigned to the parathread.
+    #[pallet::storage]
+    pub type BulkRegions<T: Config> = StorageMap<_, Twox128, ParaId, BulkRegion, OptionQuery>;
+
+    /// Latest core purchases of each parathread, oldest first.
+    #[pallet::storage]
+    pub type CorePurchaseHistory<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        ParaId,
+        BoundedVec<
+            CorePurchaseRecord<BlockNumberFor<T>, T::CollatorPublicKey>,
+            T::MaxPurchaseHistoryLength,
+        >,
+        ValueQuery,
+    >;
+
+    /// Aggregated stats of all the core purchases of each parathread.
+    #[pallet::storage]
+    pub type CorePurchaseStatsOf<T: Config> =
+        StorageMap<_, Twox64Concat, ParaId, CorePurchaseStats, ValueQuery>;
+
     /// Collator signature nonce for reply protection
     #[pallet::storage]
     pub type CollatorSignatureNonce<T: Config> = StorageMap<_, Twox128, ParaId, u64, ValueQuery>;
@@ -450,22 +634,45 @@ pub mod pallet {
             QueryIdToParaId::<T>::remove(query_id);
             InFlightOrders::<T>::remove(para_id);
 
-            match response {
-                Response::DispatchResult(MaybeErrorCode::Success) => {
+            // The order was a bulk region assignment if the region is waiting for this query
+            let maybe_bulk_region = BulkRegions::<T>::get(para_id).filter(|region| {
+                region.status == BulkRegionStatus::AssignmentInFlight { query_id }
+            });
+
+            match (&response, maybe_bulk_region) {
+                (Response::DispatchResult(MaybeErrorCode::Success), None) => {
                     // Success. Add para id to pending block
                     let now = <frame_system::Pallet<T>>::block_number();
                     let ttl = T::PendingBlocksTtl::get();
                     PendingBlocks::<T>::insert(para_id, now.saturating_add(ttl));
                 }
-                Response::DispatchResult(_) => {
+                (Response::DispatchResult(MaybeErrorCode::Success), Some(mut region)) => {
+                    // The region only becomes usable at its start, so we do not add para id to
+                    // pending block
+                    region.status = BulkRegionStatus::Assigned;
+                    BulkRegions::<T>::insert(para_id, region);
+                }
+                (Response::DispatchResult(_), None) => {
                     // We do not add paraid to pending block on failure
                 }
+                (Response::DispatchResult(_), Some(mut region)) => {
+                    // Next core request will fall back to on-demand
+                    region.status = BulkRegionStatus::AssignmentFailed;
+                    BulkRegions::<T>::insert(para_id, region);
+                }
                 _ => {
                     // Unexpected.
                     return Err(Error::<T>::UnexpectedXCMResponse.into());
                 }
             }
 
+            let outcome = if response == Response::DispatchResult(MaybeErrorCode::Success) {
+                CorePurchaseOutcome::Success
+            } else {
+                CorePurchaseOutcome::Failed
+            };
+            Self::finish_core_purchase(para_id, query_id, outcome);
+
             Self::deposit_event(Event::ReceivedBuyCoreXCMResult { para_id, response });
 
             Ok(())
@@ -516,6 +723,11 @@ pub mod pallet {
                     if in_flight_order.ttl < now {
                         InFlightOrders::<T>::remove(para_id);
                         QueryIdToParaId::<T>::remove(in_flight_order.query_id);
+                        Self::finish_core_purchase(
+                            para_id,
+                            in_flight_order.query_id,
+                            CorePurchaseOutcome::Expired,
+                        );
                         cleaned_up_para_ids.push(para_id);
                     } else {
                         // Ignore if not expired
@@ -529,6 +741,49 @@ pub mod pallet {
 
             Ok(())
         }
+
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::set_coretime_xcm_weight_config())]
+        pub fn set_coretime_xcm_weight_config(
+            origin: OriginFor<T>,
+            xcm_weights: Option<RelayXcmWeightConfigInner<T>>,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            CoretimeXcmWeightConfig::<T>::set(xcm_weights);
+
+            Ok(())
+        }
+
+        /// Set the bulk region held by the parathread tank account in the coretime chain. The
+        /// region will be assigned to the parathread the next time a collator requests a core.
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::set_bulk_region())]
+        pub fn set_bulk_region(
+            origin: OriginFor<T>,
+            para_id: ParaId,
+            region_id: Option<BulkRegionId>,
+        ) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin, &para_id)?;
+
+            // Replacing the region would make us ignore the result of the order
+            ensure!(
+                !InFlightOrders::<T>::contains_key(para_id),
+                Error::<T>::OrderAlreadyExists
+            );
+
+            BulkRegions::<T>::set(
+                para_id,
+                region_id.clone().map(|region_id| BulkRegion {
+                    region_id,
+                    status: BulkRegionStatus::Unassigned,
+                }),
+            );
+
+            Self::deposit_event(Event::BulkRegionSet { para_id, region_id });
+
+            Ok(())
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -549,16 +804,31 @@ pub mod pallet {
         pub fn relay_relative_multilocation(
             interior_multilocation: InteriorLocation,
         ) -> Result<Location, Error<T>> {
-            let relay_chain = Location::parent();
+            Self::reanchored_multilocation(interior_multilocation, &Location::parent())
+        }
+
+        /// Same as `relay_relative_multilocation`, but from the point of view of `destination`.
+        pub fn reanchored_multilocation(
+            interior_multilocation: InteriorLocation,
+            destination: &Location,
+        ) -> Result<Location, Error<T>> {
             let context: InteriorLocation = [Parachain(T::SelfParaId::get().into())].into();
             let mut reanchored: Location = interior_multilocation.into();
             reanchored
-                .reanchor(&relay_chain, &context)
+                .reanchor(destination, &context)
                 .map_err(|_| Error::<T>::ReanchorFailed)?;
 
             Ok(reanchored)
         }
 
+        /// Location of the coretime chain of the current relay chain, where bulk regions live.
+        pub fn coretime_chain_location() -> Location {
+            let coretime_chain =
+                T::GetAssignRegionCall::coretime_chain_para_id(RelayChain::<T>::get());
+
+            Location::new(1, [Parachain(coretime_chain.into())])
+        }
+
         pub fn is_core_buying_allowed(
             para_id: ParaId,
             _maybe_collator_public_key: Option<<T as Config>::CollatorPublicKey>,
@@ -569,6 +839,11 @@ pub mod pallet {
             if let Some(in_flight_order) = maybe_in_flight_order {
                 if in_flight_order.ttl < <frame_system::Pallet<T>>::block_number() {
                     InFlightOrders::<T>::remove(para_id);
+                    Self::finish_core_purchase(
+                        para_id,
+                        in_flight_order.query_id,
+                        CorePurchaseOutcome::Expired,
+                    );
                 } else {
                     return Err(BuyingError::OrderAlreadyExists {
                         ttl: in_flight_order.ttl,
@@ -616,29 +891,32 @@ pub mod pallet {
             Ok(())
         }
 
-        /// Send an XCM message to the relay chain to try to buy a core for this para_id.
+        /// Send an XCM message to the relay chain to try to buy a core for this para_id, or to the
+        /// coretime chain to assign its bulk region if it has one that was not assigned yet.
         fn on_collator_instantaneous_core_requested(
             para_id: ParaId,
             maybe_collator_public_key: Option<<T as Config>::CollatorPublicKey>,
         ) -> DispatchResult {
-            Self::is_core_buying_allowed(para_id, maybe_collator_public_key)
+            Self::is_core_buying_allowed(para_id, maybe_collator_public_key.clone())
                 .map_err(Into::<Error<T>>::into)?;
 
+            // Regions that are already assigned, failed to be assigned, or whose assignment order
+            // expired, fall back to on-demand cores.
+            match BulkRegions::<T>::get(para_id) {
+                Some(region) if region.status == BulkRegionStatus::Unassigned => {
+                    Self::assign_bulk_region(para_id, region, maybe_collator_public_key)
+                }
+                _ => Self::buy_on_demand_core(para_id, maybe_collator_public_key),
+            }
+        }
+
+        fn buy_on_demand_core(
+            para_id: ParaId,
+            maybe_collator_public_key: Option<<T as Config>::CollatorPublicKey>,
+        ) -> DispatchResult {
             let xcm_weights_storage =
                 RelayXcmWeightConfig::<T>::get().ok_or(Error::<T>::XcmWeightStorageNotSet)?;
 
-            let withdraw_amount = xcm_weights_storage.buy_execution_cost;
-
-            // Use the account derived from the multilocation composed with DescendOrigin
-            // Buy on-demand cores
-            // Any failure should return everything to the derivative account
-
-            // Don't use utility::as_derivative because that will make the tanssi sovereign account
-            // pay for fees, instead use `DescendOrigin` to make the parathread tank account
-            // pay for fees.
-            // TODO: when coretime is implemented, use coretime instantaneous credits instead of
-            // buying on-demand cores at the price defined by the relay
-            let origin = OriginKind::SovereignAccount;
             // TODO: max_amount is the max price of a core that this parathread is willing to pay
             // It should be defined in a storage item somewhere, controllable by the container chain
             // manager.
@@ -646,19 +924,185 @@ pub mod pallet {
                 T::GetParathreadMaxCorePrice::get_max_core_price(para_id).unwrap_or(u128::MAX);
             let call =
                 T::GetPurchaseCoreCall::get_encoded(RelayChain::<T>::get(), max_amount, para_id);
-            let weight_at_most = xcm_weights_storage.weight_at_most;
 
             // Assumption: derived account already has DOT
             // The balance should be enough to cover the `Withdraw` needed to `BuyExecution`, plus
             // the price of the core, which can change based on demand.
-            let relay_asset_total: Asset = (Here, withdraw_amount).into();
+            let query_id = Self::send_transact_order(
+                para_id,
+                Location::parent(),
+                (Here, xcm_weights_storage.buy_execution_cost).into(),
+                xcm_weights_storage.weight_at_most,
+                call,
+            )?;
+
+            Self::deposit_event(Event::BuyCoreXcmSent {
+                para_id,
+                transaction_status_query_id: query_id,
+            });
+
+            Self::record_core_purchase(
+                para_id,
+                query_id,
+                CorePurchaseKind::OnDemand,
+                Some(max_amount),
+                xcm_weights_storage.buy_execution_cost,
+                maybe_collator_public_key,
+            );
+
+            Ok(())
+        }
+
+        fn assign_bulk_region(
+            para_id: ParaId,
+            mut region: BulkRegion,
+            maybe_collator_public_key: Option<<T as Config>::CollatorPublicKey>,
+        ) -> DispatchResult {
+            let xcm_weights_storage = CoretimeXcmWeightConfig::<T>::get()
+                .ok_or(Error::<T>::CoretimeXcmWeightStorageNotSet)?;
+
+            let call = T::GetAssignRegionCall::get_encoded(
+                RelayChain::<T>::get(),
+                region.region_id.clone(),
+                para_id,
+            );
+
+            // The region must be owned by the parathread tank account in the coretime chain, which
+            // also pays for fees in relay chain tokens.
+            let query_id = Self::send_transact_order(
+                para_id,
+                Self::coretime_chain_location(),
+                (Parent, xcm_weights_storage.buy_execution_cost).into(),
+                xcm_weights_storage.weight_at_most,
+                call,
+            )?;
+
+            Self::deposit_event(Event::AssignBulkRegionXcmSent {
+                para_id,
+                region_id: region.region_id.clone(),
+                transaction_status_query_id: query_id,
+            });
+
+            region.status = BulkRegionStatus::AssignmentInFlight { query_id };
+            BulkRegions::<T>::insert(para_id, region);
+
+            Self::record_core_purchase(
+                para_id,
+                query_id,
+                CorePurchaseKind::BulkRegionAssignment,
+                None,
+                xcm_weights_storage.buy_execution_cost,
+                maybe_collator_public_key,
+            );
+
+            Ok(())
+        }
+
+        /// Add a pending core purchase to the history of this para_id, removing the oldest one if
+        /// the history is full.
+        fn record_core_purchase(
+            para_id: ParaId,
+            query_id: QueryId,
+            kind: CorePurchaseKind,
+            max_price: Option<u128>,
+            execution_fee: u128,
+            collator: Option<<T as Config>::CollatorPublicKey>,
+        ) {
+            let record = CorePurchaseRecord {
+                query_id,
+                kind,
+                sent_at: <frame_system::Pallet<T>>::block_number(),
+                max_price,
+                execution_fee,
+                collator,
+                outcome: CorePurchaseOutcome::Pending,
+                finished_at: None,
+            };
+
+            CorePurchaseHistory::<T>::mutate(para_id, |history| {
+                if history.is_full() && !history.is_empty() {
+                    history.remove(0);
+                }
+                // Only fails if the max history length is 0
+                let _ = history.try_push(record);
+            });
+
+            CorePurchaseStatsOf::<T>::mutate(para_id, |stats| {
+                stats.orders_sent = stats.orders_sent.saturating_add(1);
+                stats.max_execution_fees = stats.max_execution_fees.saturating_add(execution_fee);
+            });
+        }
+
+        /// Set the outcome of a pending core purchase of this para_id and update its stats.
+        /// Does nothing if the purchase already has an outcome.
+        fn finish_core_purchase(para_id: ParaId, query_id: QueryId, outcome: CorePurchaseOutcome) {
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            // Purchases that already have an outcome, like expired ones that got a late response,
+            // are not counted twice. Neither are the ones no longer in the history.
+            let Ok(maybe_max_core_price) = CorePurchaseHistory::<T>::try_mutate(
+                para_id,
+                |history| -> Result<Option<u128>, ()> {
+                    let record = history
+                        .iter_mut()
+                        .find(|record| {
+                            record.query_id == query_id
+                                && record.outcome == CorePurchaseOutcome::Pending
+                        })
+                        .ok_or(())?;
+
+                    record.outcome = outcome.clone();
+                    record.finished_at = Some(now);
+
+                    Ok(match record.kind {
+                        CorePurchaseKind::OnDemand => record.max_price,
+                        CorePurchaseKind::BulkRegionAssignment => None,
+                    })
+                },
+            ) else {
+                return;
+            };
+
+            CorePurchaseStatsOf::<T>::mutate(para_id, |stats| match outcome {
+                CorePurchaseOutcome::Success => {
+                    stats.orders_succeeded = stats.orders_succeeded.saturating_add(1);
+                    stats.max_core_spending = stats
+                        .max_core_spending
+                        .saturating_add(maybe_max_core_price.unwrap_or_default());
+                }
+                CorePurchaseOutcome::Failed => {
+                    stats.orders_failed = stats.orders_failed.saturating_add(1);
+                }
+                CorePurchaseOutcome::Expired => {
+                    stats.orders_expired = stats.orders_expired.saturating_add(1);
+                }
+                CorePurchaseOutcome::Pending => {}
+            });
+        }
+
+        /// Send an XCM message to `destination` to dispatch `call` from the parathread tank
+        /// account, and track its outcome as an in flight order of this para_id.
+        fn send_transact_order(
+            para_id: ParaId,
+            destination: Location,
+            fee_asset: Asset,
+            weight_at_most: Weight,
+            call: Vec<u8>,
+        ) -> Result<QueryId, DispatchError> {
+            // Use the account derived from the multilocation composed with DescendOrigin
+            // Any failure should return everything to the derivative account
+
+            // Don't use utility::as_derivative because that will make the tanssi sovereign account
+            // pay for fees, instead use `DescendOrigin` to make the parathread tank account
+            // pay for fees.
+            let origin = OriginKind::SovereignAccount;
             let refund_asset_filter: AssetFilter = AssetFilter::Wild(WildAsset::AllCounted(1));
 
             let interior_multilocation = Self::interior_multilocation(para_id);
             // The parathread tank account is derived from the tanssi sovereign account and the
             // parathread para id.
             let derived_account =
-                Self::relay_relative_multilocation(interior_multilocation.clone())?;
+                Self::reanchored_multilocation(interior_multilocation.clone(), &destination)?;
 
             // Need to use `builder_unsafe` because safe `builder` does not allow `descend_origin` as first instruction.
             // We use `descend_origin` instead of wrapping the transact call in `utility.as_derivative`
@@ -674,10 +1118,8 @@ pub mod pallet {
             let notify_query_ttl = <frame_system::Pallet<T>>::block_number()
                 .saturating_add(T::CoreBuyingXCMQueryTtl::get());
 
-            // Send XCM to relay chain
-            let relay_chain = Location::parent();
             let query_id = T::XCMNotifier::new_notify_query(
-                relay_chain.clone(),
+                destination.clone(),
                 notify_call,
                 notify_query_ttl,
                 interior_multilocation.clone(),
@@ -685,15 +1127,15 @@ pub mod pallet {
 
             let message: Xcm<()> = Xcm::builder_unsafe()
                 .descend_origin(interior_multilocation.clone())
-                .withdraw_asset(Assets::from(vec![relay_asset_total.clone()]))
-                .buy_execution(relay_asset_total, Unlimited)
+                .withdraw_asset(Assets::from(vec![fee_asset.clone()]))
+                .buy_execution(fee_asset, Unlimited)
                 // Both in case of error and in case of success, we want to refund the unused weight
                 .set_appendix(
                     Xcm::builder_unsafe()
                         .report_transact_status(QueryResponseInfo {
                             // This location from the point of view of destination
                             destination: T::UniversalLocation::get()
-                                .invert_target(&relay_chain)
+                                .invert_target(&destination)
                                 .map_err(|e| {
                                     log::error!("invert_target: {:?}", e);
 
@@ -711,7 +1153,7 @@ pub mod pallet {
 
             // We intentionally do not charge any fees
             let (ticket, _price) =
-                T::XcmSender::validate(&mut Some(relay_chain), &mut Some(message)).map_err(
+                T::XcmSender::validate(&mut Some(destination), &mut Some(message)).map_err(
                     |e| {
                         log::error!("XcmSender::validate: {:?}", e);
                         Error::<T>::ErrorValidatingXCM
@@ -721,10 +1163,6 @@ pub mod pallet {
                 log::error!("XcmSender::deliver: {:?}", e);
                 Error::<T>::ErrorDeliveringXCM
             })?;
-            Self::deposit_event(Event::BuyCoreXcmSent {
-                para_id,
-                transaction_status_query_id: query_id,
-            });
 
             let in_flight_order_ttl =
                 notify_query_ttl.saturating_add(T::AdditionalTtlForInflightOrders::get());
@@ -739,7 +1177,7 @@ pub mod pallet {
 
             QueryIdToParaId::<T>::insert(query_id, para_id);
 
-            Ok(())
+            Ok(query_id)
         }
 
         pub fn para_deregistered(para_id: ParaId) {
@@ -751,6 +1189,10 @@ pub mod pallet {
 
             // We need to clean the pending block entry if any
             PendingBlocks::<T>::remove(para_id);
+
+            BulkRegions::<T>::remove(para_id);
+            CorePurchaseHistory::<T>::remove(para_id);
+            CorePurchaseStatsOf::<T>::remove(para_id);
         }
     }
 
@@ -804,6 +1246,14 @@ pub trait GetPurchaseCoreCall<RelayChain> {
     fn get_encoded(relay_chain: RelayChain, max_amount: u128, para_id: ParaId) -> Vec<u8>;
 }
 
+pub trait GetAssignRegionCall<RelayChain> {
+    /// Para id of the coretime chain of this relay chain.
+    fn coretime_chain_para_id(relay_chain: RelayChain) -> ParaId;
+    /// Get the encoded call to assign the bulk region `region_id` to this `para_id` in the
+    /// coretime chain.
+    fn get_encoded(relay_chain: RelayChain, region_id: BulkRegionId, para_id: ParaId) -> Vec<u8>;
+}
+
 pub trait CheckCollatorValidity<AccountId, PublicKey> {
     fn is_valid_collator(para_id: ParaId, public_key: PublicKey) -> bool;
 
@@ -16,8 +16,8 @@
 
 use {
     crate::{
-        self as pallet_xcm_core_buyer, CheckCollatorValidity, GetPurchaseCoreCall,
-        ParaIdIntoAccountTruncating, RelayXcmWeightConfigInner,
+        self as pallet_xcm_core_buyer, BulkRegionId, CheckCollatorValidity, GetAssignRegionCall,
+        GetPurchaseCoreCall, ParaIdIntoAccountTruncating, RelayXcmWeightConfigInner,
     },
     alloc::collections::btree_map::BTreeMap,
     dp_core::ParaId,
@@ -27,6 +27,7 @@ use {
         parameter_types,
         traits::{ConstU64, Everything},
     },
+    frame_system::EnsureRoot,
     nimbus_primitives::NimbusId,
     pallet_xcm::Origin,
     serde::{Deserialize, Serialize},
@@ -221,6 +222,7 @@ impl pallet_xcm_core_buyer::Config for Test {
     type Currency = Balances;
     type XcmSender = DevNull;
     type GetPurchaseCoreCall = EncodedCallToBuyCore;
+    type GetAssignRegionCall = EncodedCallToAssignRegion;
     type GetParathreadAccountId = ParaIdIntoAccountTruncating;
     type GetParathreadMaxCorePrice = ();
     type SelfParaId = ParachainId;
@@ -239,6 +241,8 @@ impl pallet_xcm_core_buyer::Config for Test {
     type LatestAuthorInfoFetcher = LatestAuthorInfoFetcherImpl;
     type SlotBeacon = DummyBeacon;
     type CollatorPublicKey = NimbusId;
+    type MaxPurchaseHistoryLength = ConstU32<3>;
+    type ManagerOrigin = EnsureRoot<AccountId>;
 
     type WeightInfo = ();
 }
@@ -330,8 +334,22 @@ impl GetPurchaseCoreCall<()> for EncodedCallToBuyCore {
     }
 }
 
+pub struct EncodedCallToAssignRegion;
+
+impl GetAssignRegionCall<()> for EncodedCallToAssignRegion {
+    fn coretime_chain_para_id(_relay_chain: ()) -> ParaId {
+        CORETIME_PARA_ID.into()
+    }
+
+    fn get_encoded(_relay_chain: (), _region_id: BulkRegionId, _para_id: ParaId) -> Vec<u8> {
+        vec![]
+    }
+}
+
+pub const CORETIME_PARA_ID: u32 = 1005;
 pub const BUY_EXECUTION_COST: u128 = 50_000_000;
 pub const PLACE_ORDER_WEIGHT_AT_MOST: Weight = Weight::from_parts(1_000_000_000, 100_000);
+pub const ASSIGN_REGION_WEIGHT_AT_MOST: Weight = Weight::from_parts(500_000_000, 50_000);
 
 #[derive(Default)]
 pub struct ExtBuilder {
@@ -367,6 +385,14 @@ impl ExtBuilder {
                     _phantom: PhantomData,
                 }),
             ));
+            assert_ok!(XcmCoreBuyer::set_coretime_xcm_weight_config(
+                RuntimeOrigin::root(),
+                Some(RelayXcmWeightConfigInner {
+                    buy_execution_cost: BUY_EXECUTION_COST,
+                    weight_at_most: ASSIGN_REGION_WEIGHT_AT_MOST,
+                    _phantom: PhantomData,
+                }),
+            ));
         });
 
         let memory_key_store = MemoryKeystore::new();
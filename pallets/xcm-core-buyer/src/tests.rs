@@ -804,6 +804,17 @@ fn paraid_data_is_cleaned_up_at_deregistration() {
 
             // After de-registration pending block entry should be cleared
             assert!(PendingBlocks::<Test>::get(para_id).is_none());
+
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+
+            XcmCoreBuyer::para_deregistered(para_id);
+
+            // After de-registration bulk region should be cleared
+            assert!(BulkRegions::<Test>::get(para_id).is_none());
         });
 }
 
@@ -891,3 +902,427 @@ fn xcm_locations() {
             assert_eq!(format!("{:?}", absolute_mloc), "Location { parents: 0, interior: X2([Parachain(1000), AccountId32 { network: None, id: [112, 97, 114, 97, 5, 13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0] }]) }");
         });
 }
+
+fn region_id() -> BulkRegionId {
+    BulkRegionId {
+        begin: 10,
+        core: 2,
+        mask: [0xff; 10],
+    }
+}
+
+fn assign_bulk_region_query_id() -> QueryId {
+    match events().last() {
+        Some(Event::AssignBulkRegionXcmSent {
+            transaction_status_query_id,
+            ..
+        }) => *transaction_status_query_id,
+        other => panic!("Expected AssignBulkRegionXcmSent event, got {:?}", other),
+    }
+}
+
+#[test]
+fn manager_can_set_bulk_region() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            let para_id = 3333.into();
+
+            assert_noop!(
+                XcmCoreBuyer::set_bulk_region(
+                    RuntimeOrigin::signed(ALICE),
+                    para_id,
+                    Some(region_id())
+                ),
+                BadOrigin
+            );
+
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+            assert_eq!(
+                BulkRegions::<Test>::get(para_id),
+                Some(BulkRegion {
+                    region_id: region_id(),
+                    status: BulkRegionStatus::Unassigned,
+                })
+            );
+            assert_eq!(
+                events(),
+                vec![Event::BulkRegionSet {
+                    para_id,
+                    region_id: Some(region_id()),
+                }]
+            );
+
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                None
+            ));
+            assert!(BulkRegions::<Test>::get(para_id).is_none());
+        });
+}
+
+#[test]
+fn bulk_region_is_assigned_before_buying_on_demand_cores() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            let para_id = 3333.into();
+
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+
+            let query_id = assign_bulk_region_query_id();
+            assert_eq!(
+                BulkRegions::<Test>::get(para_id).map(|region| region.status),
+                Some(BulkRegionStatus::AssignmentInFlight { query_id })
+            );
+            assert_eq!(
+                InFlightOrders::<Test>::get(para_id).map(|order| order.query_id),
+                Some(query_id)
+            );
+            assert_eq!(QueryIdToParaId::<Test>::get(query_id), Some(para_id));
+
+            run_to_block(2);
+
+            assert_noop!(
+                XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id),
+                Error::<Test>::OrderAlreadyExists
+            );
+            assert_noop!(
+                XcmCoreBuyer::set_bulk_region(RuntimeOrigin::root(), para_id, None),
+                Error::<Test>::OrderAlreadyExists
+            );
+
+            assert_ok!(XcmCoreBuyer::query_response(
+                RuntimeOrigin::root(),
+                query_id,
+                Response::DispatchResult(MaybeErrorCode::Success)
+            ));
+
+            assert_eq!(
+                BulkRegions::<Test>::get(para_id).map(|region| region.status),
+                Some(BulkRegionStatus::Assigned)
+            );
+            assert!(InFlightOrders::<Test>::get(para_id).is_none());
+            assert!(QueryIdToParaId::<Test>::get(query_id).is_none());
+            // The region is not usable yet, so there is no pending block
+            assert!(PendingBlocks::<Test>::get(para_id).is_none());
+
+            run_to_block(3);
+
+            // Once the region is assigned, collators requesting a core get an on-demand one
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            let system_events = events();
+            assert_eq!(system_events.len(), 1);
+            assert!(
+                matches!(system_events[0], Event::BuyCoreXcmSent { para_id: event_para_id, .. } if event_para_id == para_id)
+            );
+        });
+}
+
+#[test]
+fn failed_bulk_region_assignment_falls_back_to_on_demand_cores() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            let para_id = 3333.into();
+
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            let query_id = assign_bulk_region_query_id();
+
+            assert_ok!(XcmCoreBuyer::query_response(
+                RuntimeOrigin::root(),
+                query_id,
+                Response::DispatchResult(MaybeErrorCode::Error(BoundedVec::new()))
+            ));
+
+            assert_eq!(
+                BulkRegions::<Test>::get(para_id).map(|region| region.status),
+                Some(BulkRegionStatus::AssignmentFailed)
+            );
+            assert!(InFlightOrders::<Test>::get(para_id).is_none());
+
+            run_to_block(2);
+
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            let system_events = events();
+            assert_eq!(system_events.len(), 1);
+            assert!(
+                matches!(system_events[0], Event::BuyCoreXcmSent { para_id: event_para_id, .. } if event_para_id == para_id)
+            );
+
+            // A new region can be set once the order finishes, and it will be assigned next
+            let query_id = match system_events[0] {
+                Event::BuyCoreXcmSent { transaction_status_query_id, .. } => transaction_status_query_id,
+                _ => panic!("We checked for the event variant above; qed")
+            };
+            assert_ok!(XcmCoreBuyer::query_response(
+                RuntimeOrigin::root(),
+                query_id,
+                Response::DispatchResult(MaybeErrorCode::Error(BoundedVec::new()))
+            ));
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            assign_bulk_region_query_id();
+        });
+}
+
+#[test]
+fn expired_bulk_region_assignment_falls_back_to_on_demand_cores() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            let para_id = 3333.into();
+
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            assign_bulk_region_query_id();
+
+            let ttl = InFlightOrders::<Test>::get(para_id)
+                .expect("In flight order for para id must be there")
+                .ttl;
+            run_to_block(ttl + 1);
+
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            let system_events = events();
+            assert_eq!(system_events.len(), 1);
+            assert!(
+                matches!(system_events[0], Event::BuyCoreXcmSent { para_id: event_para_id, .. } if event_para_id == para_id)
+            );
+        });
+}
+
+#[test]
+fn cannot_assign_bulk_region_if_no_coretime_weights_storage_set() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            assert_ok!(XcmCoreBuyer::set_coretime_xcm_weight_config(
+                RuntimeOrigin::root(),
+                None
+            ));
+
+            let para_id = 3333.into();
+            assert_ok!(XcmCoreBuyer::set_bulk_region(
+                RuntimeOrigin::root(),
+                para_id,
+                Some(region_id())
+            ));
+
+            assert_noop!(
+                XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id),
+                Error::<Test>::CoretimeXcmWeightStorageNotSet
+            );
+        });
+}
+
+#[test]
+fn coretime_chain_xcm_locations() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+
+            let para_id = 3333.into();
+            let coretime_chain = XcmCoreBuyer::coretime_chain_location();
+            assert_eq!(
+                coretime_chain,
+                Location::new(1, [Parachain(CORETIME_PARA_ID)])
+            );
+
+            let interior_mloc = XcmCoreBuyer::interior_multilocation(para_id);
+            let coretime_relative_mloc =
+                XcmCoreBuyer::reanchored_multilocation(interior_mloc.clone(), &coretime_chain)
+                    .expect("reanchor failed");
+
+            assert_eq!(coretime_relative_mloc.parents, 1);
+            let (rest, first) = coretime_relative_mloc.interior.clone().split_first();
+            assert_eq!(first, Some(Parachain(1000)));
+            assert_eq!(rest, interior_mloc);
+        });
+}
+
+#[test]
+fn core_purchases_are_recorded_in_history() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            let para_id = 3333.into();
+
+            let collator = MockData::mock().container_chain_collators[&para_id][0].clone();
+            let proof = BuyCoreCollatorProof::new(0, para_id, collator.clone())
+                .expect("creating collator proof must succeed");
+            assert_ok!(XcmCoreBuyer::buy_core(
+                RuntimeOrigin::none(),
+                para_id,
+                proof
+            ));
+
+            let query_id = InFlightOrders::<Test>::get(para_id)
+                .expect("In flight order for para id must be there")
+                .query_id;
+            let mut expected_record = CorePurchaseRecord {
+                query_id,
+                kind: CorePurchaseKind::OnDemand,
+                sent_at: 1,
+                max_price: Some(u128::MAX),
+                execution_fee: BUY_EXECUTION_COST,
+                collator: Some(collator),
+                outcome: CorePurchaseOutcome::Pending,
+                finished_at: None,
+            };
+            assert_eq!(
+                CorePurchaseHistory::<Test>::get(para_id).into_inner(),
+                vec![expected_record.clone()]
+            );
+
+            run_to_block(2);
+
+            assert_ok!(XcmCoreBuyer::query_response(
+                RuntimeOrigin::root(),
+                query_id,
+                Response::DispatchResult(MaybeErrorCode::Success)
+            ));
+
+            expected_record.outcome = CorePurchaseOutcome::Success;
+            expected_record.finished_at = Some(2);
+            assert_eq!(
+                CorePurchaseHistory::<Test>::get(para_id).into_inner(),
+                vec![expected_record]
+            );
+            assert_eq!(
+                CorePurchaseStatsOf::<Test>::get(para_id),
+                CorePurchaseStats {
+                    orders_sent: 1,
+                    orders_succeeded: 1,
+                    orders_failed: 0,
+                    orders_expired: 0,
+                    max_core_spending: u128::MAX,
+                    max_execution_fees: BUY_EXECUTION_COST,
+                }
+            );
+        });
+}
+
+#[test]
+fn core_purchase_history_is_bounded() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            let para_id = 3333.into();
+            let max_history_length: u32 = <Test as Config>::MaxPurchaseHistoryLength::get();
+
+            for block in 1..=u64::from(max_history_length) + 1 {
+                run_to_block(block);
+                assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+                let query_id = InFlightOrders::<Test>::get(para_id)
+                    .expect("In flight order for para id must be there")
+                    .query_id;
+                assert_ok!(XcmCoreBuyer::query_response(
+                    RuntimeOrigin::root(),
+                    query_id,
+                    Response::DispatchResult(MaybeErrorCode::Error(BoundedVec::new()))
+                ));
+            }
+
+            let history = CorePurchaseHistory::<Test>::get(para_id);
+            assert_eq!(history.len(), max_history_length as usize);
+            // The oldest purchase was removed
+            assert_eq!(history[0].sent_at, 2);
+            assert!(history
+                .iter()
+                .all(|record| record.outcome == CorePurchaseOutcome::Failed
+                    && record.collator.is_none()));
+
+            let stats = CorePurchaseStatsOf::<Test>::get(para_id);
+            assert_eq!(stats.orders_sent, max_history_length + 1);
+            assert_eq!(stats.orders_failed, max_history_length + 1);
+            assert_eq!(stats.max_core_spending, 0);
+
+            XcmCoreBuyer::para_deregistered(para_id);
+
+            assert!(CorePurchaseHistory::<Test>::get(para_id).is_empty());
+            assert_eq!(
+                CorePurchaseStatsOf::<Test>::get(para_id),
+                CorePurchaseStats::default()
+            );
+        });
+}
+
+#[test]
+fn expired_core_purchases_are_not_counted_twice() {
+    ExtBuilder::default()
+        .with_balances([(ALICE, 1_000)].into())
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            let para_id = 3333.into();
+
+            assert_ok!(XcmCoreBuyer::force_buy_core(RuntimeOrigin::root(), para_id));
+            let in_flight_order = InFlightOrders::<Test>::get(para_id)
+                .expect("In flight order for para id must be there");
+
+            run_to_block(in_flight_order.ttl + 1);
+            assert_ok!(XcmCoreBuyer::clean_up_expired_in_flight_orders(
+                RuntimeOrigin::signed(BOB),
+                vec![para_id]
+            ));
+
+            let history = CorePurchaseHistory::<Test>::get(para_id);
+            assert_eq!(history[0].outcome, CorePurchaseOutcome::Expired);
+            assert_eq!(history[0].finished_at, Some(in_flight_order.ttl + 1));
+
+            // A late response does not change the outcome
+            QueryIdToParaId::<Test>::insert(in_flight_order.query_id, para_id);
+            assert_ok!(XcmCoreBuyer::query_response(
+                RuntimeOrigin::root(),
+                in_flight_order.query_id,
+                Response::DispatchResult(MaybeErrorCode::Success)
+            ));
+
+            assert_eq!(
+                CorePurchaseHistory::<Test>::get(para_id)[0].outcome,
+                CorePurchaseOutcome::Expired
+            );
+            let stats = CorePurchaseStatsOf::<Test>::get(para_id);
+            assert_eq!(stats.orders_sent, 1);
+            assert_eq!(stats.orders_expired, 1);
+            assert_eq!(stats.orders_succeeded, 0);
+        });
+}
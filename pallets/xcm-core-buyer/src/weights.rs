@@ -60,6 +60,8 @@ pub trait WeightInfo {
 	fn clean_up_expired_pending_blocks(x: u32, ) -> Weight;
 	fn set_relay_xcm_weight_config() -> Weight;
 	fn set_relay_chain() -> Weight;
+	fn set_coretime_xcm_weight_config() -> Weight;
+	fn set_bulk_region() -> Weight;
 }
 
 /// Weights for pallet_xcm_core_buyer using the Substrate node and recommended hardware.
@@ -75,6 +77,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Registrar::ParathreadParams` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::RelayXcmWeightConfig` (r:1 w:0)
 	/// Proof: `XcmCoreBuyer::RelayXcmWeightConfig` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ServicesPayment::MaxCorePrice` (r:1 w:0)
@@ -99,14 +103,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn buy_core() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1534`
 		//  Estimated: `4999`
 		// Minimum execution time: 46_000_000 picoseconds.
 		Weight::from_parts(47_000_000, 4999)
-			.saturating_add(T::DbWeight::get().reads(15_u64))
-			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
 	}
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1 w:1)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -116,6 +124,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Registrar::ParathreadParams` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::RelayXcmWeightConfig` (r:1 w:0)
 	/// Proof: `XcmCoreBuyer::RelayXcmWeightConfig` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ServicesPayment::MaxCorePrice` (r:1 w:0)
@@ -140,34 +150,48 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn force_buy_core() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1534`
 		//  Estimated: `4999`
 		// Minimum execution time: 44_000_000 picoseconds.
 		Weight::from_parts(45_000_000, 4999)
-			.saturating_add(T::DbWeight::get().reads(14_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().reads(17_u64))
+			.saturating_add(T::DbWeight::get().writes(9_u64))
 	}
 	/// Storage: `XcmCoreBuyer::QueryIdToParaId` (r:1 w:1)
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::PendingBlocks` (r:0 w:1)
 	/// Proof: `XcmCoreBuyer::PendingBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:0 w:1)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn query_response() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1221`
 		//  Estimated: `4686`
 		// Minimum execution time: 15_000_000 picoseconds.
-		Weight::from_parts(15_000_000, 4686)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		Weight::from_parts(16_000_000, 4686)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1000 w:1000)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::QueryIdToParaId` (r:0 w:1000)
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1000 w:1000)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1000 w:1000)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// The range of component `x` is `[1, 1000]`.
 	fn clean_up_expired_in_flight_orders(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -178,9 +202,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 11_138
 			.saturating_add(Weight::from_parts(4_263_561, 0).saturating_mul(x.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(x.into())))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
-			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(x.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(x.into())))
 			.saturating_add(Weight::from_parts(0, 2512).saturating_mul(x.into()))
 	}
 	/// Storage: `XcmCoreBuyer::PendingBlocks` (r:1000 w:1000)
@@ -220,6 +244,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(2_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `XcmCoreBuyer::CoretimeXcmWeightConfig` (r:0 w:1)
+	/// Proof: `XcmCoreBuyer::CoretimeXcmWeightConfig` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_coretime_xcm_weight_config() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_000_000 picoseconds.
+		Weight::from_parts(2_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1 w:0)
+	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:0 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_bulk_region() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(7_000_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -234,6 +281,8 @@ impl WeightInfo for () {
 	/// Proof: `Registrar::ParathreadParams` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::RelayXcmWeightConfig` (r:1 w:0)
 	/// Proof: `XcmCoreBuyer::RelayXcmWeightConfig` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ServicesPayment::MaxCorePrice` (r:1 w:0)
@@ -258,14 +307,18 @@ impl WeightInfo for () {
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn buy_core() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1534`
 		//  Estimated: `4999`
 		// Minimum execution time: 46_000_000 picoseconds.
 		Weight::from_parts(47_000_000, 4999)
-			.saturating_add(RocksDbWeight::get().reads(15_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
 	}
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1 w:1)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -275,6 +328,8 @@ impl WeightInfo for () {
 	/// Proof: `Registrar::ParathreadParams` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `AuthorNoting::LatestAuthor` (r:1 w:0)
 	/// Proof: `AuthorNoting::LatestAuthor` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::RelayXcmWeightConfig` (r:1 w:0)
 	/// Proof: `XcmCoreBuyer::RelayXcmWeightConfig` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `ServicesPayment::MaxCorePrice` (r:1 w:0)
@@ -299,34 +354,48 @@ impl WeightInfo for () {
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `PolkadotXcm::Queries` (r:0 w:1)
 	/// Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn force_buy_core() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1534`
 		//  Estimated: `4999`
 		// Minimum execution time: 44_000_000 picoseconds.
 		Weight::from_parts(45_000_000, 4999)
-			.saturating_add(RocksDbWeight::get().reads(14_u64))
-			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().reads(17_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: `XcmCoreBuyer::QueryIdToParaId` (r:1 w:1)
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::PendingBlocks` (r:0 w:1)
 	/// Proof: `XcmCoreBuyer::PendingBlocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:0 w:1)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1 w:1)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn query_response() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1221`
 		//  Estimated: `4686`
 		// Minimum execution time: 15_000_000 picoseconds.
-		Weight::from_parts(15_000_000, 4686)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		Weight::from_parts(16_000_000, 4686)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
 	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1000 w:1000)
 	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `XcmCoreBuyer::QueryIdToParaId` (r:0 w:1000)
 	/// Proof: `XcmCoreBuyer::QueryIdToParaId` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseHistory` (r:1000 w:1000)
+	/// Proof: `XcmCoreBuyer::CorePurchaseHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::CorePurchaseStatsOf` (r:1000 w:1000)
+	/// Proof: `XcmCoreBuyer::CorePurchaseStatsOf` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// The range of component `x` is `[1, 1000]`.
 	fn clean_up_expired_in_flight_orders(x: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -337,9 +406,9 @@ impl WeightInfo for () {
 			// Standard Error: 11_138
 			.saturating_add(Weight::from_parts(4_263_561, 0).saturating_mul(x.into()))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(x.into())))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(x.into())))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
-			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(x.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(x.into())))
 			.saturating_add(Weight::from_parts(0, 2512).saturating_mul(x.into()))
 	}
 	/// Storage: `XcmCoreBuyer::PendingBlocks` (r:1000 w:1000)
@@ -379,4 +448,27 @@ impl WeightInfo for () {
 		Weight::from_parts(2_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `XcmCoreBuyer::CoretimeXcmWeightConfig` (r:0 w:1)
+	/// Proof: `XcmCoreBuyer::CoretimeXcmWeightConfig` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_coretime_xcm_weight_config() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_000_000 picoseconds.
+		Weight::from_parts(2_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `XcmCoreBuyer::InFlightOrders` (r:1 w:0)
+	/// Proof: `XcmCoreBuyer::InFlightOrders` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `XcmCoreBuyer::BulkRegions` (r:0 w:1)
+	/// Proof: `XcmCoreBuyer::BulkRegions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_bulk_region() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(7_000_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -24,5 +24,7 @@ pub trait TokenChannelSetterBenchmarkHelperTrait {
     fn set_up_token(_location: Location, _token_id: TokenId) {}
     /// Set up channel info
     fn set_up_channel(_channel_id: ChannelId, _para_id: ParaId, _agent_id: AgentId) {}
+    /// Register a token bridged from Ethereum as a foreign asset
+    fn set_up_foreign_token(_location: Location) {}
 }
 impl TokenChannelSetterBenchmarkHelperTrait for () {}
@@ -0,0 +1,167 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Traits used to guard the token flows of the bridge (rate limits, delays and pauses).
+
+use {
+    alloc::vec::Vec,
+    frame_support::pallet_prelude::{Decode, Encode},
+    parity_scale_codec::{DecodeWithMemTracking, MaxEncodedLen},
+    scale_info::TypeInfo,
+    snowbridge_core::{TokenId, TokenIdOf},
+    sp_core::{H160, H256},
+    sp_runtime::{DispatchError, DispatchResult, RuntimeDebug},
+    xcm::prelude::*,
+    xcm_executor::traits::ConvertLocation,
+};
+
+/// Direction of a token transfer, from the point of view of this chain.
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+    RuntimeDebug,
+)]
+pub enum TransferDirection {
+    /// Tokens coming from Ethereum.
+    Inbound,
+    /// Tokens sent to Ethereum.
+    Outbound,
+}
+
+/// Outbound transfer that was delayed because of its size. The amount and fees were already taken
+/// from `source` when the transfer was requested.
+#[derive(
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    MaxEncodedLen,
+    TypeInfo,
+    RuntimeDebug,
+)]
+pub struct DelayedTransfer<AccountId> {
+    pub source: AccountId,
+    pub recipient: H160,
+    pub token_id: H256,
+    /// Address of the token on Ethereum if it was bridged from there, `None` for the native token.
+    pub foreign_token: Option<H160>,
+    pub amount: u128,
+    /// Reward paid for a Snowbridge V2 transfer, `None` if the transfer uses V1.
+    pub v2_reward: Option<u128>,
+}
+
+/// Tokens moved by an outbound message, checked when the message is validated and recorded when
+/// it is delivered.
+pub type GuardedTransfers = Vec<(H256, u128)>;
+
+/// Token id of an Ethereum token (`H160::zero()` for ETH), the same one used by the inbound
+/// token processors.
+pub fn ethereum_token_id(network: NetworkId, token: H160) -> Option<TokenId> {
+    let token_location = if token == H160::zero() {
+        Location::new(1, [GlobalConsensus(network)])
+    } else {
+        Location::new(
+            1,
+            [
+                GlobalConsensus(network),
+                AccountKey20 {
+                    network: Some(network),
+                    key: token.into(),
+                },
+            ],
+        )
+    };
+
+    TokenIdOf::convert_location(&token_location)
+}
+
+/// Checks applied to the token flows of the bridge.
+pub trait BridgeTransferGuard {
+    /// Ensure that a transfer can go through, without recording it.
+    fn ensure_transfer_allowed(
+        token_id: H256,
+        direction: TransferDirection,
+        amount: u128,
+    ) -> DispatchResult;
+
+    /// Record a transfer that went through.
+    fn note_transfer(token_id: H256, direction: TransferDirection, amount: u128);
+}
+
+/// No checks.
+impl BridgeTransferGuard for () {
+    fn ensure_transfer_allowed(
+        _token_id: H256,
+        _direction: TransferDirection,
+        _amount: u128,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn note_transfer(_token_id: H256, _direction: TransferDirection, _amount: u128) {}
+}
+
+/// Bridge guard that can also hold large outbound transfers for a while.
+pub trait DelayedTransferQueue<AccountId>: BridgeTransferGuard {
+    /// Whether an outbound transfer is large enough to be delayed.
+    fn should_delay(token_id: H256, amount: u128) -> bool;
+
+    /// Queue a delayed transfer, returning its id. The transfer must have been recorded already.
+    fn delay_transfer(transfer: DelayedTransfer<AccountId>) -> Result<u64, DispatchError>;
+}
+
+/// Transfers are never delayed.
+impl<AccountId> DelayedTransferQueue<AccountId> for () {
+    fn should_delay(_token_id: H256, _amount: u128) -> bool {
+        false
+    }
+
+    fn delay_transfer(_transfer: DelayedTransfer<AccountId>) -> Result<u64, DispatchError> {
+        Err(DispatchError::Other("Transfers cannot be delayed"))
+    }
+}
+
+/// Sends or refunds the transfers delayed by the bridge guard.
+pub trait DelayedTransferExecutor<AccountId> {
+    /// Send the transfer to Ethereum once its delay is over.
+    fn execute(transfer: &DelayedTransfer<AccountId>) -> DispatchResult;
+
+    /// Give the amount back to the source of a transfer that was cancelled.
+    fn refund(transfer: &DelayedTransfer<AccountId>) -> DispatchResult;
+
+    /// Prepare the state needed to execute or refund `transfer` in benchmarks.
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_up_benchmark(_transfer: &DelayedTransfer<AccountId>) {}
+}
+
+impl<AccountId> DelayedTransferExecutor<AccountId> for () {
+    fn execute(_transfer: &DelayedTransfer<AccountId>) -> DispatchResult {
+        Err(DispatchError::Other("Delayed transfers are not supported"))
+    }
+
+    fn refund(_transfer: &DelayedTransfer<AccountId>) -> DispatchResult {
+        Err(DispatchError::Other("Delayed transfers are not supported"))
+    }
+}
@@ -17,7 +17,8 @@
 #[cfg(not(feature = "runtime-benchmarks"))]
 use crate::{match_expression, XcmConverterError};
 use {
-    alloc::vec::Vec,
+    crate::{BridgeTransferGuard, GuardedTransfers, TransferDirection},
+    alloc::{vec, vec::Vec},
     core::{iter::Peekable, marker::PhantomData, slice::Iter},
     frame_support::{ensure, traits::Get},
     parity_scale_codec::{Decode, Encode},
@@ -30,6 +31,8 @@ use {
     xcm_executor::traits::ExportXcm,
 };
 
+/// Exports container token transfers to Ethereum. `BridgeGuard` is checked when validating and
+/// notified on delivery; transfers exported through XCM are never delayed.
 pub struct ContainerEthereumBlobExporter<
     UniversalLocation,
     EthereumNetwork,
@@ -37,6 +40,7 @@ pub struct ContainerEthereumBlobExporter<
     OutboundQueue,
     ConvertAssetId,
     BridgeChannelInfo,
+    BridgeGuard = (),
 >(
     PhantomData<(
         UniversalLocation,
@@ -45,6 +49,7 @@ pub struct ContainerEthereumBlobExporter<
         OutboundQueue,
         ConvertAssetId,
         BridgeChannelInfo,
+        BridgeGuard,
     )>,
 );
 
@@ -55,6 +60,7 @@ impl<
         OutboundQueue,
         ConvertAssetId,
         BridgeChannelInfo,
+        BridgeGuard,
     > ExportXcm
     for ContainerEthereumBlobExporter<
         UniversalLocation,
@@ -63,6 +69,7 @@ impl<
         OutboundQueue,
         ConvertAssetId,
         BridgeChannelInfo,
+        BridgeGuard,
     >
 where
     UniversalLocation: Get<InteriorLocation>,
@@ -71,8 +78,9 @@ where
     OutboundQueue: SendMessage<Balance = u128>,
     ConvertAssetId: MaybeEquivalence<TokenId, Location>,
     BridgeChannelInfo: Get<Option<(ChannelId, AgentId)>>,
+    BridgeGuard: BridgeTransferGuard,
 {
-    type Ticket = (Vec<u8>, XcmHash);
+    type Ticket = (Vec<u8>, XcmHash, GuardedTransfers);
 
     fn validate(
         network: NetworkId,
@@ -139,6 +147,20 @@ where
             Unroutable
         })?;
 
+        let guarded_transfers: GuardedTransfers = match &command {
+            Command::MintForeignToken {
+                token_id, amount, ..
+            } => vec![(*token_id, *amount)],
+            _ => vec![],
+        };
+        for (token_id, amount) in &guarded_transfers {
+            BridgeGuard::ensure_transfer_allowed(*token_id, TransferDirection::Outbound, *amount)
+                .map_err(|err| {
+                    log::error!(target: "xcm::container_ethereum_blob_exporter", "transfer rejected by the bridge guard: {err:?}");
+                    Unroutable
+                })?;
+        }
+
         let (channel_id, _) = BridgeChannelInfo::get().ok_or_else(|| {
             log::error!(target: "xcm::container_ethereum_blob_exporter", "channel id and agent id cannot be fetched");
             Unroutable
@@ -159,10 +181,10 @@ where
         // convert fee to Asset (specify native tokens)
         let fee = Asset::from((Location::new(0, []), fee.total())).into();
 
-        Ok(((ticket.encode(), message_id), fee))
+        Ok(((ticket.encode(), message_id, guarded_transfers), fee))
     }
 
-    fn deliver(blob: (Vec<u8>, XcmHash)) -> Result<XcmHash, SendError> {
+    fn deliver(blob: (Vec<u8>, XcmHash, GuardedTransfers)) -> Result<XcmHash, SendError> {
         let ticket: OutboundQueue::Ticket = OutboundQueue::Ticket::decode(&mut blob.0.as_ref())
             .map_err(|_| {
                 log::trace!(target: "xcm::container_ethereum_blob_exporter", "undeliverable due to decoding error");
@@ -174,6 +196,10 @@ where
             SendError::Transport("other transport error")
         })?;
 
+        for (token_id, amount) in blob.2 {
+            BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+        }
+
         log::info!(target: "xcm::container_ethereum_blob_exporter", "message delivered {message_id:#?}.");
         Ok(message_id.into())
     }
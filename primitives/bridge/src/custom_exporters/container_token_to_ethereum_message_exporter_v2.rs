@@ -15,6 +15,7 @@
 // along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
 
 use {
+    crate::{BridgeTransferGuard, GuardedTransfers, TransferDirection},
     alloc::vec::Vec,
     core::{iter::Peekable, marker::PhantomData, ops::ControlFlow, slice::Iter},
     frame_support::{
@@ -24,7 +25,9 @@ use {
     },
     parity_scale_codec::{Decode, Encode},
     snowbridge_core::TokenId,
-    snowbridge_outbound_queue_primitives::v2::message::{Message, SendMessage},
+    snowbridge_outbound_queue_primitives::v2::message::{
+        Command as OutboundCommand, Message, SendMessage,
+    },
     sp_runtime::traits::MaybeEquivalence,
     xcm::latest::SendError::NotApplicable,
     xcm::prelude::*,
@@ -43,6 +46,9 @@ use {
 // In case of error, we will always return NotApplicable.
 // If we return other kind of errors, the tuple implementation for ExportXcm will not
 // allow us to go to the next exporter available.
+//
+// `BridgeGuard` is checked when validating and notified on delivery; transfers exported through
+// XCM are never delayed.
 pub struct ContainerEthereumBlobExporterV2<
     UniversalLocation,
     EthereumNetwork,
@@ -50,6 +56,7 @@ pub struct ContainerEthereumBlobExporterV2<
     OutboundQueue,
     ConvertAssetId,
     MinReward,
+    BridgeGuard = (),
 >(
     PhantomData<(
         UniversalLocation,
@@ -58,6 +65,7 @@ pub struct ContainerEthereumBlobExporterV2<
         OutboundQueue,
         ConvertAssetId,
         MinReward,
+        BridgeGuard,
     )>,
 );
 
@@ -68,6 +76,7 @@ impl<
         OutboundQueue,
         ConvertAssetId,
         MinReward,
+        BridgeGuard,
     > ExportXcm
     for ContainerEthereumBlobExporterV2<
         UniversalLocation,
@@ -76,6 +85,7 @@ impl<
         OutboundQueue,
         ConvertAssetId,
         MinReward,
+        BridgeGuard,
     >
 where
     UniversalLocation: Get<InteriorLocation>,
@@ -84,8 +94,9 @@ where
     OutboundQueue: SendMessage,
     ConvertAssetId: MaybeEquivalence<TokenId, Location>,
     MinReward: Get<Asset>,
+    BridgeGuard: BridgeTransferGuard,
 {
-    type Ticket = (Vec<u8>, XcmHash);
+    type Ticket = (Vec<u8>, XcmHash, GuardedTransfers);
 
     fn validate(
         network: NetworkId,
@@ -178,6 +189,24 @@ where
             NotApplicable
         })?;
 
+        let guarded_transfers: GuardedTransfers = outbound_message
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                OutboundCommand::MintForeignToken {
+                    token_id, amount, ..
+                } => Some((*token_id, *amount)),
+                _ => None,
+            })
+            .collect();
+        for (token_id, amount) in &guarded_transfers {
+            BridgeGuard::ensure_transfer_allowed(*token_id, TransferDirection::Outbound, *amount)
+                .map_err(|err| {
+                    log::error!(target: "xcm::ethereum_blob_exporter", "transfer rejected by the bridge guard: {err:?}");
+                    NotApplicable
+                })?;
+        }
+
         // validate the message
         let ticket = OutboundQueue::validate(&outbound_message).map_err(|err| {
             log::error!(target: "xcm::ethereum_blob_exporter", "OutboundQueue validation of message failed. {err:?}");
@@ -187,10 +216,17 @@ where
         // convert fee to Asset
         let fee = Asset::from((MinReward::get().id, outbound_message.fee)).into();
 
-        Ok(((ticket.encode(), XcmHash::from(outbound_message.id)), fee))
+        Ok((
+            (
+                ticket.encode(),
+                XcmHash::from(outbound_message.id),
+                guarded_transfers,
+            ),
+            fee,
+        ))
     }
 
-    fn deliver(blob: (Vec<u8>, XcmHash)) -> Result<XcmHash, SendError> {
+    fn deliver(blob: (Vec<u8>, XcmHash, GuardedTransfers)) -> Result<XcmHash, SendError> {
         let ticket: OutboundQueue::Ticket = OutboundQueue::Ticket::decode(&mut blob.0.as_ref())
             .map_err(|_| {
                 log::trace!(target: "xcm::ethereum_blob_exporter", "undeliverable due to decoding error");
@@ -202,6 +238,10 @@ where
             SendError::Transport("other transport error")
         })?;
 
+        for (token_id, amount) in blob.2 {
+            BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+        }
+
         log::info!(target: "xcm::ethereum_blob_exporter", "message delivered {message_id:#?}.");
         Ok(message_id.into())
     }
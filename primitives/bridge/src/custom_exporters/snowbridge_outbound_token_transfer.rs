@@ -18,7 +18,10 @@
 // Rewrite of the following code which cause issues as Tanssi is not a parachain
 // https://github.com/moondance-labs/polkadot-sdk/blob/tanssi-polkadot-stable2412/bridges/snowbridge/primitives/router/src/outbound/mod.rs#L98
 
-use crate::{match_expression, XcmConverterError};
+use crate::{
+    ethereum_token_id, match_expression, BridgeTransferGuard, GuardedTransfers, TransferDirection,
+    XcmConverterError,
+};
 use alloc::vec::Vec;
 use core::iter::Peekable;
 use core::marker::PhantomData;
@@ -40,12 +43,15 @@ use xcm::{
 use xcm_builder::{ensure_is_remote, InspectMessageQueues};
 use xcm_executor::traits::{validate_export, ExportXcm};
 
+/// Exports transfers of Ethereum tokens back to Ethereum. `BridgeGuard` is checked when
+/// validating and notified on delivery.
 pub struct EthereumBlobExporter<
     UniversalLocation,
     EthereumNetwork,
     OutboundQueue,
     ConvertAssetId,
     BridgeChannelInfo,
+    BridgeGuard = (),
 >(
     PhantomData<(
         UniversalLocation,
@@ -53,16 +59,25 @@ pub struct EthereumBlobExporter<
         OutboundQueue,
         ConvertAssetId,
         BridgeChannelInfo,
+        BridgeGuard,
     )>,
 );
 
-impl<UniversalLocation, EthereumNetwork, OutboundQueue, ConvertAssetId, BridgeChannelInfo> ExportXcm
+impl<
+        UniversalLocation,
+        EthereumNetwork,
+        OutboundQueue,
+        ConvertAssetId,
+        BridgeChannelInfo,
+        BridgeGuard,
+    > ExportXcm
     for EthereumBlobExporter<
         UniversalLocation,
         EthereumNetwork,
         OutboundQueue,
         ConvertAssetId,
         BridgeChannelInfo,
+        BridgeGuard,
     >
 where
     UniversalLocation: Get<InteriorLocation>,
@@ -70,8 +85,9 @@ where
     OutboundQueue: SendMessage<Balance = u128>,
     ConvertAssetId: MaybeEquivalence<TokenId, Location>,
     BridgeChannelInfo: Get<Option<(ChannelId, AgentId)>>,
+    BridgeGuard: BridgeTransferGuard,
 {
-    type Ticket = (Vec<u8>, XcmHash);
+    type Ticket = (Vec<u8>, XcmHash, GuardedTransfers);
 
     fn validate(
         network: NetworkId,
@@ -137,6 +153,24 @@ where
             SendError::Unroutable
         })?;
 
+        let guarded_transfers: GuardedTransfers = match &command {
+            Command::AgentExecute {
+                command: AgentExecuteCommand::TransferToken { token, amount, .. },
+                ..
+            } => ethereum_token_id(expected_network, *token)
+                .map(|token_id| (token_id, *amount))
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        };
+        for (token_id, amount) in &guarded_transfers {
+            BridgeGuard::ensure_transfer_allowed(*token_id, TransferDirection::Outbound, *amount)
+                .map_err(|err| {
+                    log::error!(target: "xcm::ethereum_blob_exporter", "transfer rejected by the bridge guard: {err:?}");
+                    SendError::Unroutable
+                })?;
+        }
+
         let outbound_message = Message {
             id: Some(message_id.into()),
             channel_id,
@@ -152,10 +186,10 @@ where
         // convert fee to Asset
         let fee = Asset::from((Location::here(), fee.total())).into();
 
-        Ok(((ticket.encode(), message_id), fee))
+        Ok(((ticket.encode(), message_id, guarded_transfers), fee))
     }
 
-    fn deliver(blob: (Vec<u8>, XcmHash)) -> Result<XcmHash, SendError> {
+    fn deliver(blob: (Vec<u8>, XcmHash, GuardedTransfers)) -> Result<XcmHash, SendError> {
         let ticket: OutboundQueue::Ticket = OutboundQueue::Ticket::decode(&mut blob.0.as_ref())
             .map_err(|_| {
                 log::trace!(target: "xcm::ethereum_blob_exporter", "undeliverable due to decoding error");
@@ -167,6 +201,10 @@ where
             SendError::Transport("other transport error")
         })?;
 
+        for (token_id, amount) in blob.2 {
+            BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+        }
+
         log::info!(target: "xcm::ethereum_blob_exporter", "message delivered {message_id:#?}.");
         Ok(message_id.into())
     }
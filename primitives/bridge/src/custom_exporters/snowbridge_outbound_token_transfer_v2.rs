@@ -18,7 +18,10 @@
 // Rewrite of the following code which cause issues as Tanssi is not a parachain
 // https://github.com/moondance-labs/polkadot-sdk/blob/tanssi-polkadot-stable2412/bridges/snowbridge/primitives/router/src/outbound/mod.rs#L98
 
-use crate::{match_expression, XcmConverterError};
+use crate::{
+    ethereum_token_id, match_expression, BridgeTransferGuard, GuardedTransfers, TransferDirection,
+    XcmConverterError,
+};
 use alloc::vec::Vec;
 use core::iter::Peekable;
 use core::marker::PhantomData;
@@ -38,6 +41,8 @@ use xcm::prelude::*;
 use xcm_builder::{CreateMatcher, HandleFee, MatchXcm};
 use xcm_executor::traits::{ConvertLocation, ExportXcm, FeeReason};
 
+/// Exports transfers of Ethereum tokens back to Ethereum using Snowbridge V2. `BridgeGuard` is
+/// checked when validating and notified on delivery.
 pub struct EthereumBlobExporterV2<
     UniversalLocation,
     EthereumNetwork,
@@ -45,6 +50,7 @@ pub struct EthereumBlobExporterV2<
     ConvertAssetId,
     MinReward,
     FeeHandler,
+    BridgeGuard = (),
 >(
     PhantomData<(
         UniversalLocation,
@@ -53,11 +59,19 @@ pub struct EthereumBlobExporterV2<
         ConvertAssetId,
         MinReward,
         FeeHandler,
+        BridgeGuard,
     )>,
 );
 
-impl<UniversalLocation, EthereumNetwork, OutboundQueue, ConvertAssetId, MinReward, FeeHandler>
-    ExportXcm
+impl<
+        UniversalLocation,
+        EthereumNetwork,
+        OutboundQueue,
+        ConvertAssetId,
+        MinReward,
+        FeeHandler,
+        BridgeGuard,
+    > ExportXcm
     for EthereumBlobExporterV2<
         UniversalLocation,
         EthereumNetwork,
@@ -65,6 +79,7 @@ impl<UniversalLocation, EthereumNetwork, OutboundQueue, ConvertAssetId, MinRewar
         ConvertAssetId,
         MinReward,
         FeeHandler,
+        BridgeGuard,
     >
 where
     UniversalLocation: Get<InteriorLocation>,
@@ -74,8 +89,9 @@ where
     // Evaluated in XCM context!
     MinReward: Get<Asset>,
     FeeHandler: HandleFee,
+    BridgeGuard: BridgeTransferGuard,
 {
-    type Ticket = (Vec<u8>, XcmHash);
+    type Ticket = (Vec<u8>, XcmHash, GuardedTransfers);
 
     fn validate(
         network: NetworkId,
@@ -155,6 +171,24 @@ where
             SendError::Unroutable
         })?;
 
+        let guarded_transfers: GuardedTransfers = outbound_message
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::UnlockNativeToken { token, amount, .. } => {
+                    ethereum_token_id(expected_network, *token).map(|token_id| (token_id, *amount))
+                }
+                _ => None,
+            })
+            .collect();
+        for (token_id, amount) in &guarded_transfers {
+            BridgeGuard::ensure_transfer_allowed(*token_id, TransferDirection::Outbound, *amount)
+                .map_err(|err| {
+                    log::error!(target: "xcm::ethereum_blob_exporterv2", "transfer rejected by the bridge guard: {err:?}");
+                    SendError::Unroutable
+                })?;
+        }
+
         // validate the message
         let ticket = OutboundQueue::validate(&outbound_message).map_err(|err| {
             log::error!(target: "xcm::ethereum_blob_exporter", "OutboundQueue validation of message failed. {err:?}");
@@ -178,12 +212,16 @@ where
         );
 
         Ok((
-            (ticket.encode(), XcmHash::from(outbound_message.id)),
+            (
+                ticket.encode(),
+                XcmHash::from(outbound_message.id),
+                guarded_transfers,
+            ),
             Assets::default(),
         ))
     }
 
-    fn deliver(blob: (Vec<u8>, XcmHash)) -> Result<XcmHash, SendError> {
+    fn deliver(blob: (Vec<u8>, XcmHash, GuardedTransfers)) -> Result<XcmHash, SendError> {
         let ticket: OutboundQueue::Ticket = OutboundQueue::Ticket::decode(&mut blob.0.as_ref())
             .map_err(|_| {
                 log::trace!(target: "xcm::ethereum_blob_exporterv2", "undeliverable due to decoding error");
@@ -195,6 +233,10 @@ where
             SendError::Transport("other transport error")
         })?;
 
+        for (token_id, amount) in blob.2 {
+            BridgeGuard::note_transfer(token_id, TransferDirection::Outbound, amount);
+        }
+
         log::info!(target: "xcm::ethereum_blob_exporterv2", "message delivered {message_id:#?}.");
         Ok(message_id.into())
     }
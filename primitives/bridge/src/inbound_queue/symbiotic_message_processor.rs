@@ -20,13 +20,15 @@ use snowbridge_inbound_queue_primitives::v2;
 use {
     alloc::vec::Vec,
     frame_support::pallet_prelude::*,
+    pallet_external_validators::{ExternalSourceId, Forcing},
     parity_scale_codec::DecodeAll,
     snowbridge_core::{Channel, PRIMARY_GOVERNANCE_CHANNEL},
     snowbridge_inbound_queue_primitives::{
         v1::{Envelope, MessageProcessor},
         v2::MessageProcessorError,
     },
-    sp_runtime::DispatchError,
+    sp_runtime::{DispatchError, DispatchResult},
+    tp_traits::SlashingModeOption,
 };
 
 /// Magic bytes are added in every payload intended for this processor to make sure
@@ -53,6 +55,8 @@ where
     T: pallet_external_validators::Config,
 {
     V1(InboundCommand<T>),
+    V2(InboundCommandV2<T>),
+    V3(InboundCommandV3<T>),
 }
 
 /// Command to be executed by this message processor
@@ -67,11 +71,142 @@ where
     },
 }
 
-pub struct SymbioticMessageProcessor<T>(PhantomData<T>);
+/// Commands of the second version of the message format, which carry the stake of each
+/// validator
+#[derive(Encode, Decode, DebugNoBound)]
+pub enum InboundCommandV2<T>
+where
+    T: pallet_external_validators::Config,
+{
+    ReceiveValidatorsWithStake {
+        validators: Vec<(<T as pallet_external_validators::Config>::ValidatorId, u128)>,
+        external_index: u64,
+    },
+}
+
+/// Governance commands sent by the Ethereum middleware. They are only accepted through the
+/// primary governance channel, and each kind of command must be enabled explicitly through
+/// `SymbioticGovernanceHandler::is_enabled`.
+#[derive(Encode, Decode, DebugNoBound)]
+pub enum InboundCommandV3<T>
+where
+    T: pallet_external_validators::Config,
+{
+    AddWhitelisted {
+        validator: <T as frame_system::Config>::AccountId,
+    },
+    RemoveWhitelisted {
+        validator: <T as frame_system::Config>::AccountId,
+    },
+    ForceEra {
+        mode: Forcing,
+    },
+    SetSlashingMode {
+        mode: SlashingModeOption,
+    },
+    SetRewardsReportingPaused {
+        paused: bool,
+    },
+    CancelDeferredSlash {
+        era: u32,
+        slash_indices: Vec<u32>,
+    },
+}
+
+impl<T> InboundCommandV3<T>
+where
+    T: pallet_external_validators::Config,
+{
+    pub fn kind(&self) -> GovernanceCommandKind {
+        match self {
+            Self::AddWhitelisted { .. } | Self::RemoveWhitelisted { .. } => {
+                GovernanceCommandKind::Whitelist
+            }
+            Self::ForceEra { .. } => GovernanceCommandKind::ForceEra,
+            Self::SetSlashingMode { .. } => GovernanceCommandKind::SlashingMode,
+            Self::SetRewardsReportingPaused { .. } => GovernanceCommandKind::RewardsReporting,
+            Self::CancelDeferredSlash { .. } => GovernanceCommandKind::CancelDeferredSlash,
+        }
+    }
+}
+
+/// Kinds of governance commands, each one can be enabled separately.
+#[derive(Clone, Copy, Encode, Decode, Debug, PartialEq, Eq)]
+pub enum GovernanceCommandKind {
+    Whitelist,
+    ForceEra,
+    SlashingMode,
+    RewardsReporting,
+    CancelDeferredSlash,
+}
+
+/// Runtime hooks for the governance commands. Commands that target
+/// `pallet_external_validators` are executed by the processor itself.
+pub trait SymbioticGovernanceHandler {
+    /// Whether commands of this kind are accepted from Ethereum.
+    fn is_enabled(kind: GovernanceCommandKind) -> bool;
+    fn set_slashing_mode(mode: SlashingModeOption) -> DispatchResult;
+    fn set_rewards_reporting_paused(paused: bool) -> DispatchResult;
+    fn cancel_deferred_slash(era: u32, slash_indices: Vec<u32>) -> DispatchResult;
+}
+
+/// Governance commands are not accepted.
+impl SymbioticGovernanceHandler for () {
+    fn is_enabled(_kind: GovernanceCommandKind) -> bool {
+        false
+    }
+
+    fn set_slashing_mode(_mode: SlashingModeOption) -> DispatchResult {
+        Err(DispatchError::Other("Governance commands not supported"))
+    }
 
-impl<T> SymbioticMessageProcessor<T>
+    fn set_rewards_reporting_paused(_paused: bool) -> DispatchResult {
+        Err(DispatchError::Other("Governance commands not supported"))
+    }
+
+    fn cancel_deferred_slash(_era: u32, _slash_indices: Vec<u32>) -> DispatchResult {
+        Err(DispatchError::Other("Governance commands not supported"))
+    }
+}
+
+/// Execute a governance command if its kind is enabled. Callers must have checked that the
+/// message comes from the primary governance channel or the gateway.
+pub fn process_governance_command<T, Governance>(command: InboundCommandV3<T>) -> DispatchResult
 where
     T: pallet_external_validators::Config,
+    Governance: SymbioticGovernanceHandler,
+{
+    if !Governance::is_enabled(command.kind()) {
+        return Err(DispatchError::Other("Governance command is not enabled"));
+    }
+
+    match command {
+        InboundCommandV3::AddWhitelisted { validator } => {
+            pallet_external_validators::Pallet::<T>::add_whitelisted_inner(validator)
+        }
+        InboundCommandV3::RemoveWhitelisted { validator } => {
+            pallet_external_validators::Pallet::<T>::remove_whitelisted_inner(validator)
+        }
+        InboundCommandV3::ForceEra { mode } => {
+            pallet_external_validators::Pallet::<T>::set_force_era(mode);
+            Ok(())
+        }
+        InboundCommandV3::SetSlashingMode { mode } => Governance::set_slashing_mode(mode),
+        InboundCommandV3::SetRewardsReportingPaused { paused } => {
+            Governance::set_rewards_reporting_paused(paused)
+        }
+        InboundCommandV3::CancelDeferredSlash { era, slash_indices } => {
+            Governance::cancel_deferred_slash(era, slash_indices)
+        }
+    }
+}
+
+pub struct SymbioticMessageProcessor<T, Governance = ()>(PhantomData<(T, Governance)>);
+
+impl<T, Governance> SymbioticMessageProcessor<T, Governance>
+where
+    T: pallet_external_validators::Config,
+    Governance: SymbioticGovernanceHandler,
 {
     fn can_process_message(mut payload: &[u8]) -> bool {
         let decode_result = Payload::<T>::decode_all(&mut payload);
@@ -111,26 +246,58 @@ where
                 validators,
                 external_index,
             }) => {
-                if let Some(channel_id) = channel_id {
-                    if channel_id != PRIMARY_GOVERNANCE_CHANNEL {
-                        return Err(DispatchError::Other(
-                            "Received governance message from invalid channel id",
-                        ));
-                    }
-                }
-                pallet_external_validators::Pallet::<T>::set_external_validators_inner(
+                let source = Self::source_for_channel(channel_id)?;
+                pallet_external_validators::Pallet::<T>::set_external_validators_for_source(
+                    source,
                     validators,
                     external_index,
                 )?;
                 Ok(())
             }
+            Message::V2(InboundCommandV2::ReceiveValidatorsWithStake {
+                validators,
+                external_index,
+            }) => {
+                let source = Self::source_for_channel(channel_id)?;
+                pallet_external_validators::Pallet::<T>::set_external_validators_with_stake(
+                    source,
+                    validators,
+                    external_index,
+                )?;
+                Ok(())
+            }
+            Message::V3(command) => {
+                if channel_id != Some(PRIMARY_GOVERNANCE_CHANNEL) {
+                    return Err(DispatchError::Other(
+                        "Governance commands are only accepted from the primary governance channel",
+                    ));
+                }
+                process_governance_command::<T, Governance>(command)
+            }
+        }
+    }
+
+    /// Validators received through the primary governance channel belong to the default source,
+    /// any other channel must be registered as an external source.
+    fn source_for_channel(
+        channel_id: Option<ChannelId>,
+    ) -> Result<ExternalSourceId, DispatchError> {
+        match channel_id {
+            Some(channel_id) if channel_id != PRIMARY_GOVERNANCE_CHANNEL => {
+                pallet_external_validators::Pallet::<T>::source_for_channel(&channel_id.into())
+                    .ok_or(DispatchError::Other(
+                        "Received governance message from invalid channel id",
+                    ))
+            }
+            _ => Ok(pallet_external_validators::DEFAULT_EXTERNAL_SOURCE),
         }
     }
 }
 
-impl<T> v2::MessageProcessor<AccountId> for SymbioticMessageProcessor<T>
+impl<T, Governance> v2::MessageProcessor<AccountId> for SymbioticMessageProcessor<T, Governance>
 where
     T: pallet_external_validators::Config,
+    Governance: SymbioticGovernanceHandler,
 {
     fn can_process_message(_who: &AccountId, message: &v2::Message) -> bool {
         match &message.payload {
@@ -154,9 +321,10 @@ where
     }
 }
 
-impl<T> MessageProcessor for SymbioticMessageProcessor<T>
+impl<T, Governance> MessageProcessor for SymbioticMessageProcessor<T, Governance>
 where
     T: pallet_external_validators::Config,
+    Governance: SymbioticGovernanceHandler,
 {
     fn can_process_message(_channel: &Channel, envelope: &Envelope) -> bool {
         Self::can_process_message(&envelope.payload)
@@ -75,10 +75,12 @@ pub use xcm_executor::traits::ConvertLocation;
 #[cfg(feature = "runtime-benchmarks")]
 pub use benchmarks::*;
 
+pub mod bridge_guard;
 pub mod custom_exporters;
 pub mod inbound_queue;
 pub mod outbound_queue;
 
+pub use bridge_guard::*;
 pub use custom_exporters::*;
 pub use inbound_queue::*;
 pub use outbound_queue::*;
@@ -168,6 +170,19 @@ pub struct SlashData {
     pub external_idx: u64,
 }
 
+impl SlashData {
+    /// Bytes hashed to build the leaf of this slash in a slashes merkle tree:
+    /// `encoded_validator_id ++ slash_fraction ++ external_idx`, integers being SCALE encoded.
+    /// For 32-byte validator ids, this is the same as SCALE encoding the tuple
+    /// `(validatorId, slashFraction, externalIdx)`.
+    pub fn merkle_leaf_data(&self) -> Vec<u8> {
+        let mut data = self.encoded_validator_id.clone();
+        data.extend(self.slash_fraction.encode());
+        data.extend(self.external_idx.encode());
+        data
+    }
+}
+
 /// A command which is executable by the Gateway contract on Ethereum
 #[derive(Clone, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo, PartialEq)]
 pub enum Command {
@@ -193,6 +208,16 @@ pub enum Command {
         // vec of `SlashData`
         slashes: Vec<SlashData>,
     },
+    ReportSlashesMerkleRoot {
+        // index of the era we are sending info of
+        era_index: u32,
+        // index of this chunk among the chunks sent for the same era
+        chunk_index: u32,
+        // number of slashes included in this chunk
+        slashes_count: u32,
+        // merkle root of vec![(validatorId, slashFraction, externalIdx)]
+        slashes_merkle_root: H256,
+    },
 }
 
 impl Command {
@@ -203,6 +228,7 @@ impl Command {
             Command::Test { .. } => 32,
             Command::ReportRewards { .. } => 33,
             Command::ReportSlashes { .. } => 34,
+            Command::ReportSlashesMerkleRoot { .. } => 35,
         }
     }
 
@@ -253,6 +279,24 @@ impl Command {
                 let slashes_tokens_array = Token::Array(slashes_tokens_vec);
                 ethabi::encode(&[Token::Tuple(vec![era_index_token, slashes_tokens_array])])
             }
+            Command::ReportSlashesMerkleRoot {
+                era_index,
+                chunk_index,
+                slashes_count,
+                slashes_merkle_root,
+            } => {
+                let era_index_token = Token::Uint(U256::from(*era_index));
+                let chunk_index_token = Token::Uint(U256::from(*chunk_index));
+                let slashes_count_token = Token::Uint(U256::from(*slashes_count));
+                let slashes_mr_token = Token::FixedBytes(slashes_merkle_root.0.to_vec());
+
+                ethabi::encode(&[Token::Tuple(vec![
+                    era_index_token,
+                    chunk_index_token,
+                    slashes_count_token,
+                    slashes_mr_token,
+                ])])
+            }
         }
     }
 }
@@ -302,6 +302,8 @@ impl ConstantGasMeter {
             // TODO: revisit gas cost
             Command::ReportRewards { .. } => 1_000_000,
             Command::ReportSlashes { .. } => 1_000_000,
+            // Only stores the root, slashes are applied later by proving them against it
+            Command::ReportSlashesMerkleRoot { .. } => 200_000,
         }
     }
 }
@@ -59,6 +59,43 @@ fn test_report_rewards_encoding() {
     assert_eq!(command.abi_encode(), expected);
 }
 
+#[test]
+fn test_report_slashes_merkle_root_encoding() {
+    let command = Command::ReportSlashesMerkleRoot {
+        era_index: 42,
+        chunk_index: 1,
+        slashes_count: 300,
+        slashes_merkle_root: H256::from(hex!(
+            "b6e16d27ac5ab427a7f68900ac5559ce272dc6c37c82b3e052246c82244c50e4"
+        )),
+    };
+
+    let expected = hex!(
+        // no tuple offset since all fields have static size
+        "000000000000000000000000000000000000000000000000000000000000002A" // era index
+        "0000000000000000000000000000000000000000000000000000000000000001" // chunk index
+        "000000000000000000000000000000000000000000000000000000000000012C" // slashes count
+        "b6e16d27ac5ab427a7f68900ac5559ce272dc6c37c82b3e052246c82244c50e4" // root
+    );
+
+    assert_eq!(command.abi_encode(), expected);
+}
+
+#[test]
+fn test_slash_data_merkle_leaf_data() {
+    let account = sp_runtime::AccountId32::from([4u8; 32]);
+    let slash = SlashData {
+        encoded_validator_id: account.encode(),
+        slash_fraction: 5_000u32,
+        external_idx: 500u64,
+    };
+
+    assert_eq!(
+        slash.merkle_leaf_data(),
+        (account, 5_000u32, 500u64).encode()
+    );
+}
+
 #[test]
 fn test_report_slashes_encoding() {
     pub const ALICE: [u8; 32] = [4u8; 32];
@@ -0,0 +1,31 @@
+// Copyright (C) Moondance Labs Ltd.
+// This file is part of Tanssi.
+
+// Tanssi is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tanssi is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tanssi.  If not, see <http://www.gnu.org/licenses/>
+
+//! Runtime API for container chain token transfers
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use tp_container_chain::sovereign_paid_remote_exporter::ExportFeeQuote;
+use {xcm::VersionedXcm, xcm_runtime_apis::fees::Error as XcmPaymentApiError};
+
+sp_api::decl_runtime_apis! {
+    pub trait ContainerEthereumTransfersApi {
+        /// Fees of exporting `message` to Ethereum through the relay chain: the execution fee
+        /// paid by this chain's sovereign account on the relay chain and the delivery fees paid
+        /// here.
+        fn quote_ethereum_export(message: VersionedXcm<()>) -> Result<ExportFeeQuote, XcmPaymentApiError>;
+    }
+}
@@ -18,7 +18,10 @@ use {
     alloc::vec,
     core::marker::PhantomData,
     cumulus_primitives_core::ParaId,
-    frame_support::traits::Get,
+    frame_support::{
+        pallet_prelude::{Decode, Encode, TypeInfo},
+        traits::Get,
+    },
     xcm::{
         latest::{Location, NetworkId},
         prelude::*,
@@ -38,6 +41,16 @@ fn is_ethereum_location(loc: &Location, ethereum_network: NetworkId) -> bool {
     )
 }
 
+/// Fees paid to export a message to Ethereum from a container chain.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct ExportFeeQuote {
+    /// Amount of the relay chain native token withdrawn from the container chain sovereign
+    /// account to pay for the export on the relay chain.
+    pub relay_execution_fee: u128,
+    /// Fees paid on this chain to deliver the export message to the relay chain.
+    pub delivery_fees: VersionedAssets,
+}
+
 pub struct SovereignPaidRemoteExporter<
     Router,
     UniversalLocation,
@@ -160,6 +173,37 @@ where
     }
 }
 
+impl<
+        Router: SendXcm,
+        UniversalLocation: Get<InteriorLocation>,
+        EthereumNetwork,
+        ExecutionFee,
+        SelfParaId,
+    >
+    SovereignPaidRemoteExporter<
+        Router,
+        UniversalLocation,
+        EthereumNetwork,
+        ExecutionFee,
+        SelfParaId,
+    >
+where
+    EthereumNetwork: Get<NetworkId>,
+    ExecutionFee: Get<u128>,
+    SelfParaId: Get<ParaId>,
+{
+    /// Estimate the fees of exporting `xcm` to Ethereum, without sending anything.
+    pub fn quote_export(xcm: Xcm<()>) -> Result<ExportFeeQuote, SendError> {
+        let mut dest = Some(Location::new(2, GlobalConsensus(EthereumNetwork::get())));
+        let (_, delivery_fees) = Self::validate(&mut dest, &mut Some(xcm))?;
+
+        Ok(ExportFeeQuote {
+            relay_execution_fee: ExecutionFee::get(),
+            delivery_fees: delivery_fees.into(),
+        })
+    }
+}
+
 impl<
         Router: SendXcm,
         UniversalLocation: Get<InteriorLocation>,
@@ -53,9 +53,10 @@ use {
     sp_core::H256,
     sp_runtime::{
         app_crypto::sp_core,
-        traits::{CheckedAdd, CheckedMul},
+        traits::{BlakeTwo256, CheckedAdd, CheckedMul},
         ArithmeticError, DispatchResult, Perbill, RuntimeDebug,
     },
+    sp_staking::offence::{Kind, Offence},
 };
 
 /// The collator-assignment hook to react to collators being assigned to container chains.
@@ -154,6 +155,11 @@ impl<AccountId> AuthorNotingHook<AccountId> for Tuple {
 
 pub trait DistributeRewards<AccountId, Imbalance> {
     fn distribute_rewards(rewarded: AccountId, amount: Imbalance) -> DispatchResultWithPostInfo;
+    /// Whether `distribute_rewards` can reward `rewarded`. Callers that must not fail can use it
+    /// to pay the reward in some other way beforehand.
+    fn can_distribute_rewards(_rewarded: &AccountId) -> bool {
+        true
+    }
     #[cfg(feature = "runtime-benchmarks")]
     /// Some benchmarks need a special setup: register accounts, transfer tokens, etc.
     /// Use this method to perform that setup. In some cases, there is a mandatory delay needed to
@@ -416,6 +422,76 @@ pub trait LatestAuthorInfoFetcher<AccountId> {
     fn get_latest_author_info(para_id: ParaId) -> Option<ContainerChainBlockInfo<AccountId>>;
 }
 
+/// Header of container chain blocks, as seen by the orchestrator.
+pub type ContainerChainHeader = sp_runtime::generic::Header<BlockNumber, BlakeTwo256>;
+
+/// Proof that a collator sealed two different container chain blocks for the same slot.
+#[derive(
+    Clone,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    PartialEq,
+    Eq,
+    sp_core::RuntimeDebug,
+    scale_info::TypeInfo,
+)]
+pub struct ContainerChainEquivocationProof {
+    pub para_id: ParaId,
+    pub slot: Slot,
+    /// Sealed header, the seal being the last digest item.
+    pub first_header: ContainerChainHeader,
+    /// Sealed header, the seal being the last digest item.
+    pub second_header: ContainerChainHeader,
+}
+
+/// Offence of a collator that sealed two different container chain blocks for the same slot.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, sp_core::RuntimeDebug, scale_info::TypeInfo)]
+pub struct ContainerChainEquivocationOffence<Offender> {
+    pub session_index: sp_staking::SessionIndex,
+    pub para_id: ParaId,
+    pub slot: Slot,
+    /// Number of collators assigned to the container chain.
+    pub collator_count: u32,
+    pub offender: Offender,
+}
+
+impl<Offender: Clone> Offence<Offender> for ContainerChainEquivocationOffence<Offender> {
+    const ID: Kind = *b"tanssi:cc-equivo";
+    type TimeSlot = (ParaId, Slot);
+
+    fn offenders(&self) -> Vec<Offender> {
+        vec![self.offender.clone()]
+    }
+
+    fn session_index(&self) -> sp_staking::SessionIndex {
+        self.session_index
+    }
+
+    fn validator_set_count(&self) -> u32 {
+        self.collator_count
+    }
+
+    fn time_slot(&self) -> Self::TimeSlot {
+        (self.para_id, self.slot)
+    }
+
+    /// Same as BABE equivocations: `min(3 * k / n, 1)^2`, where `k` is the number of offenders
+    /// in the same time slot.
+    fn slash_fraction(&self, offenders_count: u32) -> Perbill {
+        let x = Perbill::from_rational(offenders_count.saturating_mul(3), self.collator_count);
+        x.square()
+    }
+}
+
+/// Returns the collator account that owns an authority key, used to identify the author of
+/// container chain blocks.
+pub trait GetCollatorAccount<AuthorityId, AccountId> {
+    fn collator_account(authority: &AuthorityId) -> Option<AccountId>;
+    #[cfg(feature = "runtime-benchmarks")]
+    fn set_collator_account(authority: AuthorityId, account: AccountId);
+}
+
 pub trait StorageDeposit<Data, Balance> {
     fn compute_deposit(data: &Data) -> Result<Balance, DispatchErrorWithPostInfo>;
 }
@@ -653,6 +729,60 @@ pub trait ExternalIndexProvider {
     fn get_external_index() -> u64;
 }
 
+/// How offences are handled by the external validator slashes pallet.
+#[apply(derive_storage_traits)]
+#[derive(Copy, MaxEncodedLen, DecodeWithMemTracking, Default)]
+pub enum SlashingModeOption {
+    #[default]
+    Enabled,
+    LogOnly,
+    Disabled,
+}
+
+/// Identifier of a source of external validators, for example a Symbiotic network.
+pub type ExternalSourceId = u32;
+
+/// Source of the validators fed through the primary governance channel.
+pub const DEFAULT_EXTERNAL_SOURCE: ExternalSourceId = 0;
+
+// A trait to retrieve which external source supplied each validator in a given era, so rewards
+// and slashes can be reported back to that source with its own external index
+pub trait ExternalSourcesProvider<ValidatorId> {
+    /// Source that supplied `validator` for era `era_index`, if it was an external validator.
+    fn source_of(era_index: EraIndex, validator: &ValidatorId) -> Option<ExternalSourceId>;
+    /// External index used by `source` in era `era_index`.
+    fn external_index_of(era_index: EraIndex, source: ExternalSourceId) -> Option<u64>;
+    /// Channel through which `source` communicated in era `era_index`, if it is not the default
+    /// one.
+    fn channel_of(era_index: EraIndex, source: ExternalSourceId) -> Option<[u8; 32]>;
+}
+
+impl<ValidatorId> ExternalSourcesProvider<ValidatorId> for () {
+    fn source_of(_era_index: EraIndex, _validator: &ValidatorId) -> Option<ExternalSourceId> {
+        None
+    }
+
+    fn external_index_of(_era_index: EraIndex, _source: ExternalSourceId) -> Option<u64> {
+        None
+    }
+
+    fn channel_of(_era_index: EraIndex, _source: ExternalSourceId) -> Option<[u8; 32]> {
+        None
+    }
+}
+
+// A trait to retrieve the stake backing each external validator in a given era
+pub trait ExternalStakeProvider<ValidatorId> {
+    /// Stake of `validator` in era `era_index`, if it was received together with the validator.
+    fn stake_of(era_index: EraIndex, validator: &ValidatorId) -> Option<u128>;
+}
+
+impl<ValidatorId> ExternalStakeProvider<ValidatorId> for () {
+    fn stake_of(_era_index: EraIndex, _validator: &ValidatorId) -> Option<u128> {
+        None
+    }
+}
+
 // A trait to check invulnerables
 pub trait InvulnerablesHelper<AccountId> {
     /// Checks if the given `AccountId` is invulnerable.
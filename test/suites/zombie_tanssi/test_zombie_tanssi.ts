@@ -471,7 +471,7 @@ describeSuite({
                     await checkLogsNotExist(logFilePath, [
                         "Entering off-chain worker.",
                         "Shutdown error",
-                        "Timeout when waiting for paritydb lock",
+                        "Timeout when waiting for db lock",
                         "Error waiting for chain",
                         "Failed to start container chain",
                         "Shutting down container chain service",
@@ -143,6 +143,24 @@ describeSuite({
             },
         });
 
+        it({
+            id: "T11",
+            title: "Test metrics: container chain spawner metrics are exposed by the orchestrator node",
+            test: async () => {
+                const response = await fetch("http://127.0.0.1:27125/metrics");
+                const metrics = await response.text();
+
+                expect(metrics).to.match(
+                    /substrate_container_chain_spawner_running_chains\{[^}]*para_id="2000"[^}]*\} 1/
+                );
+                expect(metrics).to.contain("substrate_container_chain_spawner_spawn_duration_seconds_count");
+                expect(metrics).to.contain("substrate_container_chain_spawner_assignment_changes_total");
+                expect(metrics).to.match(
+                    /substrate_container_chain_spawner_best_block_lag\{[^}]*para_id="2000"[^}]*\}/
+                );
+            },
+        });
+
         it({
             id: "T12",
             title: "Test metrics: deregister container chain and metrics should stop",
@@ -391,7 +391,7 @@ describeSuite({
                     const logFilePath = getTmpZombiePath() + log;
                     await checkLogsNotExist(logFilePath, [
                         "Shutdown error",
-                        "Timeout when waiting for paritydb lock",
+                        "Timeout when waiting for db lock",
                         "Error waiting for chain",
                         "Failed to start container chain",
                         "Shutting down container chain service",